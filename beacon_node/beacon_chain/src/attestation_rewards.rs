@@ -1,6 +1,6 @@
-use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
 use eth2::lighthouse::attestation_rewards::{IdealAttestationRewards, TotalAttestationRewards};
-use eth2::lighthouse::StandardAttestationRewards;
+use eth2::lighthouse::{EpochRewards, StandardAttestationRewards, StandardBlockReward};
 use participation_cache::ParticipationCache;
 use safe_arith::SafeArith;
 use serde_utils::quoted_u64::Quoted;
@@ -8,8 +8,10 @@ use slog::debug;
 use state_processing::{
     common::altair::BaseRewardPerIncrement,
     per_epoch_processing::altair::{participation_cache, rewards_and_penalties::get_flag_weight},
+    BlockReplayer,
 };
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use store::consts::altair::{
     PARTICIPATION_FLAG_WEIGHTS, TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX,
     TIMELY_TARGET_FLAG_INDEX,
@@ -44,10 +46,144 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .state_root_at_slot(state_slot)?
             .ok_or(BeaconChainError::NoStateForSlot(state_slot))?;
 
-        let state = self
+        let mut state = self
             .get_state(&state_root, Some(state_slot))?
             .ok_or(BeaconChainError::MissingBeaconState(state_root))?;
 
+        self.compute_attestation_rewards_from_state(&mut state, validators)
+    }
+
+    /// Compute the attestation rewards for a range of epochs, along with the rewards for the blocks
+    /// proposed by `validators` in those epochs. All validators are included if `validators` is
+    /// empty.
+    ///
+    /// Rather than loading a state for every epoch, a single state from before `start_epoch` is
+    /// loaded and the blocks of the range are replayed on top of it. `on_epoch` is called with the
+    /// rewards of each epoch as soon as they are known, and the computation stops early if it
+    /// returns `false`.
+    pub fn compute_epoch_rewards_batch<F>(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        validators: Vec<ValidatorId>,
+        on_epoch: F,
+    ) -> Result<(), BeaconChainError>
+    where
+        F: FnMut(EpochRewards) -> bool,
+    {
+        debug!(
+            self.log,
+            "computing epoch rewards";
+            "start_epoch" => start_epoch,
+            "end_epoch" => end_epoch,
+            "validator_count" => validators.len(),
+        );
+
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let start_slot = start_epoch.start_slot(slots_per_epoch);
+        let prior_slot = start_slot.saturating_sub(1_u64);
+        // The attestation rewards for an epoch are known at the end of the following epoch.
+        let end_slot = (end_epoch + 1).end_slot(slots_per_epoch);
+
+        let end_block_root = self
+            .block_root_at_slot(end_slot, WhenSlotSkipped::Prev)?
+            .ok_or(BeaconChainError::NoStateForSlot(end_slot))?;
+        let blocks = self
+            .store
+            .load_blocks_to_replay(start_slot, end_slot, end_block_root)?;
+
+        let state_root = self
+            .state_root_at_slot(prior_slot)?
+            .ok_or(BeaconChainError::NoStateForSlot(prior_slot))?;
+        let mut state = self
+            .get_state(&state_root, Some(prior_slot))?
+            .ok_or(BeaconChainError::MissingBeaconState(state_root))?;
+        state.build_caches(&self.spec)?;
+
+        let proposers = if validators.is_empty() {
+            None
+        } else {
+            Some(
+                Self::validators_ids_to_indices(&mut state, validators.clone())?
+                    .into_iter()
+                    .map(|index| index as u64)
+                    .collect::<HashSet<_>>(),
+            )
+        };
+
+        // Both hooks need access to the pending block rewards and the callback.
+        let block_rewards = RefCell::new(BTreeMap::<Epoch, Vec<StandardBlockReward>>::new());
+        let on_epoch = RefCell::new(on_epoch);
+        let emit = |epoch: Epoch, state: &mut BeaconState<T::EthSpec>| {
+            let attestation_rewards =
+                self.compute_attestation_rewards_from_state(state, validators.clone())?;
+            let epoch_rewards = EpochRewards {
+                epoch,
+                attestation_rewards,
+                block_rewards: block_rewards
+                    .borrow_mut()
+                    .remove(&epoch)
+                    .unwrap_or_default(),
+            };
+            if (*on_epoch.borrow_mut())(epoch_rewards) {
+                Ok(())
+            } else {
+                Err(BeaconChainError::EpochRewardsCancelled)
+            }
+        };
+
+        let result = BlockReplayer::<_, BeaconChainError, _>::new(state, &self.spec)
+            .pre_block_hook(Box::new(|state, block| {
+                let epoch = block.slot().epoch(slots_per_epoch);
+                let proposer_index = block.message().proposer_index();
+                if epoch <= end_epoch
+                    && proposers
+                        .as_ref()
+                        .map_or(true, |proposers| proposers.contains(&proposer_index))
+                {
+                    let reward = self.compute_beacon_block_reward(
+                        block.message(),
+                        block.canonical_root(),
+                        state,
+                    )?;
+                    block_rewards
+                        .borrow_mut()
+                        .entry(epoch)
+                        .or_default()
+                        .push(reward);
+                }
+                Ok(())
+            }))
+            .pre_slot_hook(Box::new(|state| {
+                // At the last slot of each epoch, the rewards of the previous epoch are known.
+                let slot = state.slot();
+                let epoch = slot.epoch(slots_per_epoch);
+                if slot == epoch.end_slot(slots_per_epoch) && epoch > start_epoch {
+                    emit(epoch - 1, state)?;
+                }
+                Ok(())
+            }))
+            .state_root_iter(self.forwards_iter_state_roots_until(prior_slot, end_slot)?)
+            .no_signature_verification()
+            .minimal_block_root_verification()
+            .apply_blocks(blocks, Some(end_slot))
+            .and_then(|replayer| {
+                // The hooks don't run at `end_slot` itself, so emit the final epoch here.
+                let mut state = replayer.into_state();
+                emit(end_epoch, &mut state)
+            });
+
+        match result {
+            Err(BeaconChainError::EpochRewardsCancelled) => Ok(()),
+            result => result,
+        }
+    }
+
+    fn compute_attestation_rewards_from_state(
+        &self,
+        state: &mut BeaconState<T::EthSpec>,
+        validators: Vec<ValidatorId>,
+    ) -> Result<StandardAttestationRewards, BeaconChainError> {
         match state {
             BeaconState::Base(_) => self.compute_attestation_rewards_base(state, validators),
             BeaconState::Altair(_) | BeaconState::Merge(_) | BeaconState::Capella(_) => {
@@ -58,24 +194,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     fn compute_attestation_rewards_base(
         &self,
-        mut state: BeaconState<T::EthSpec>,
+        state: &mut BeaconState<T::EthSpec>,
         validators: Vec<ValidatorId>,
     ) -> Result<StandardAttestationRewards, BeaconChainError> {
         let spec = &self.spec;
-        let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
-        validator_statuses.process_attestations(&state)?;
+        let mut validator_statuses = ValidatorStatuses::new(state, spec)?;
+        validator_statuses.process_attestations(state)?;
 
         let ideal_rewards =
-            self.compute_ideal_rewards_base(&state, &validator_statuses.total_balances)?;
+            self.compute_ideal_rewards_base(state, &validator_statuses.total_balances)?;
 
         let indices_to_attestation_delta = if validators.is_empty() {
-            get_attestation_deltas_all(&state, &validator_statuses, spec)?
+            get_attestation_deltas_all(state, &validator_statuses, spec)?
                 .into_iter()
                 .enumerate()
                 .collect()
         } else {
-            let validator_indices = Self::validators_ids_to_indices(&mut state, validators)?;
-            get_attestation_deltas_subset(&state, &validator_statuses, &validator_indices, spec)?
+            let validator_indices = Self::validators_ids_to_indices(state, validators)?;
+            get_attestation_deltas_subset(state, &validator_statuses, &validator_indices, spec)?
         };
 
         let mut total_rewards = vec![];
@@ -116,13 +252,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     fn compute_attestation_rewards_altair(
         &self,
-        mut state: BeaconState<T::EthSpec>,
+        state: &mut BeaconState<T::EthSpec>,
         validators: Vec<ValidatorId>,
     ) -> Result<StandardAttestationRewards, BeaconChainError> {
         let spec = &self.spec;
 
         // Calculate ideal_rewards
-        let participation_cache = ParticipationCache::new(&state, spec)?;
+        let participation_cache = ParticipationCache::new(state, spec)?;
 
         let previous_epoch = state.previous_epoch();
 
@@ -181,7 +317,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let validators = if validators.is_empty() {
             participation_cache.eligible_validator_indices().to_vec()
         } else {
-            Self::validators_ids_to_indices(&mut state, validators)?
+            Self::validators_ids_to_indices(state, validators)?
         };
 
         for validator_index in &validators {
@@ -52,6 +52,7 @@ use state_processing::{
     },
 };
 use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
 use strum::AsRefStr;
 use tree_hash::TreeHash;
 use types::{
@@ -801,7 +802,27 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
         subnet_id: Option<SubnetId>,
         chain: &BeaconChain<T>,
     ) -> Result<Self, Error> {
-        Self::verify_slashable(attestation, subnet_id, chain)
+        Self::verify_with_committees(attestation, subnet_id, chain, None)
+    }
+
+    /// As `Self::verify`, but reading the committee from `batch_committees` if a prior attestation
+    /// in the same batch was from the same committee.
+    pub(crate) fn verify_in_batch(
+        attestation: &'a Attestation<T::EthSpec>,
+        subnet_id: Option<SubnetId>,
+        chain: &BeaconChain<T>,
+        batch_committees: &mut BatchCommittees,
+    ) -> Result<Self, Error> {
+        Self::verify_with_committees(attestation, subnet_id, chain, Some(batch_committees))
+    }
+
+    fn verify_with_committees(
+        attestation: &'a Attestation<T::EthSpec>,
+        subnet_id: Option<SubnetId>,
+        chain: &BeaconChain<T>,
+        batch_committees: Option<&mut BatchCommittees>,
+    ) -> Result<Self, Error> {
+        Self::verify_slashable_with_committees(attestation, subnet_id, chain, batch_committees)
             .map(|verified_unaggregated| {
                 if let Some(slasher) = chain.slasher.as_ref() {
                     slasher.accept_attestation(verified_unaggregated.indexed_attestation.clone());
@@ -816,6 +837,15 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
         attestation: &'a Attestation<T::EthSpec>,
         subnet_id: Option<SubnetId>,
         chain: &BeaconChain<T>,
+    ) -> Result<Self, AttestationSlashInfo<'a, T, Error>> {
+        Self::verify_slashable_with_committees(attestation, subnet_id, chain, None)
+    }
+
+    fn verify_slashable_with_committees(
+        attestation: &'a Attestation<T::EthSpec>,
+        subnet_id: Option<SubnetId>,
+        chain: &BeaconChain<T>,
+        batch_committees: Option<&mut BatchCommittees>,
     ) -> Result<Self, AttestationSlashInfo<'a, T, Error>> {
         use AttestationSlashInfo::*;
 
@@ -823,13 +853,18 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
             return Err(SignatureNotChecked(attestation, e));
         }
 
-        let (indexed_attestation, committees_per_slot) =
-            match obtain_indexed_attestation_and_committees_per_slot(chain, attestation) {
-                Ok(x) => x,
-                Err(e) => {
-                    return Err(SignatureNotChecked(attestation, e));
-                }
-            };
+        let indexing_result = match batch_committees {
+            Some(batch_committees) => {
+                batch_committees.obtain_indexed_attestation(chain, attestation)
+            }
+            None => obtain_indexed_attestation_and_committees_per_slot(chain, attestation),
+        };
+        let (indexed_attestation, committees_per_slot) = match indexing_result {
+            Ok(x) => x,
+            Err(e) => {
+                return Err(SignatureNotChecked(attestation, e));
+            }
+        };
 
         let (validator_index, expected_subnet_id) = match Self::verify_middle_checks(
             attestation,
@@ -1231,6 +1266,50 @@ pub fn obtain_indexed_attestation_and_committees_per_slot<T: BeaconChainTypes>(
     })
 }
 
+/// The committees read whilst verifying a batch of attestations.
+///
+/// Attestations from the same committee are usually batched together, so keeping the committees
+/// for the duration of the batch avoids reading the shared shuffling cache for each attestation.
+#[derive(Default)]
+pub(crate) struct BatchCommittees {
+    /// Map from the target root, slot and index of a committee to its validator indices and the
+    /// committee count per slot.
+    committees: HashMap<(Hash256, Slot, CommitteeIndex), (Vec<usize>, CommitteesPerSlot)>,
+}
+
+impl BatchCommittees {
+    /// As `obtain_indexed_attestation_and_committees_per_slot`, but only reads the committee from
+    /// the `chain` if it has not been read for a prior attestation.
+    fn obtain_indexed_attestation<T: BeaconChainTypes>(
+        &mut self,
+        chain: &BeaconChain<T>,
+        attestation: &Attestation<T::EthSpec>,
+    ) -> Result<(IndexedAttestation<T::EthSpec>, CommitteesPerSlot), Error> {
+        let key = (
+            attestation.data.target.root,
+            attestation.data.slot,
+            attestation.data.index,
+        );
+        let (committee, committees_per_slot) = match self.committees.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let committee = map_attestation_committee(
+                    chain,
+                    attestation,
+                    |(committee, committees_per_slot)| {
+                        Ok((committee.committee.to_vec(), committees_per_slot))
+                    },
+                )?;
+                entry.insert(committee)
+            }
+        };
+
+        get_indexed_attestation(committee, attestation)
+            .map(|attestation| (attestation, *committees_per_slot))
+            .map_err(Error::Invalid)
+    }
+}
+
 /// Runs the `map_fn` with the committee and committee count per slot for the given `attestation`.
 ///
 /// This function exists in this odd "map" pattern because efficiently obtaining the committee for
@@ -1284,3 +1363,55 @@ where
         })
         .map_err(BeaconChainError::from)?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+    use std::collections::HashSet;
+    use types::MinimalEthSpec;
+
+    /// Enough validators for two committees per slot.
+    const VALIDATOR_COUNT: usize = 64;
+
+    #[test]
+    fn batch_committees_reads_each_committee_once() {
+        let harness: BeaconChainHarness<EphemeralHarnessType<MinimalEthSpec>> =
+            BeaconChainHarness::builder(MinimalEthSpec)
+                .default_spec()
+                .deterministic_keypairs(VALIDATOR_COUNT)
+                .fresh_ephemeral_store()
+                .build();
+        let (state, state_root) = harness.get_current_state_and_root();
+        let attestations = harness
+            .make_unaggregated_attestations(
+                &harness.get_all_validators(),
+                &state,
+                state_root,
+                harness.head_block_root().into(),
+                harness.get_current_slot(),
+            )
+            .into_iter()
+            .flatten()
+            .map(|(attestation, _)| attestation)
+            .collect::<Vec<_>>();
+        let committee_count = attestations
+            .iter()
+            .map(|attestation| attestation.data.index)
+            .collect::<HashSet<_>>()
+            .len();
+        assert!(committee_count > 1, "attestations should span committees");
+
+        let mut batch_committees = BatchCommittees::default();
+        for attestation in &attestations {
+            let batched = batch_committees
+                .obtain_indexed_attestation(&harness.chain, attestation)
+                .unwrap();
+            let individual =
+                obtain_indexed_attestation_and_committees_per_slot(&harness.chain, attestation)
+                    .unwrap();
+            assert_eq!(batched, individual);
+        }
+        assert_eq!(batch_committees.committees.len(), committee_count);
+    }
+}
@@ -10,8 +10,8 @@
 //! supplied as input. Each result provides the exact success or failure result of the corresponding
 //! attestation, with no loss of fidelity when compared to individual verification.
 use super::{
-    CheckAttestationSignature, Error, IndexedAggregatedAttestation, IndexedUnaggregatedAttestation,
-    VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
+    BatchCommittees, CheckAttestationSignature, Error, IndexedAggregatedAttestation,
+    IndexedUnaggregatedAttestation, VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
 };
 use crate::{
     beacon_chain::VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT, metrics, BeaconChain, BeaconChainError,
@@ -149,9 +149,15 @@ where
     let mut num_failed = 0;
 
     // Perform partial verification of all attestations, collecting the results.
+    let mut batch_committees = BatchCommittees::default();
     let partial_results = attestations
         .map(|(attn, subnet_opt)| {
-            let result = IndexedUnaggregatedAttestation::verify(attn, subnet_opt, chain);
+            let result = IndexedUnaggregatedAttestation::verify_in_batch(
+                attn,
+                subnet_opt,
+                chain,
+                &mut batch_committees,
+            );
             if result.is_ok() {
                 num_partially_verified += 1;
             } else {
@@ -7,6 +7,7 @@ use crate::attester_cache::{AttesterCache, AttesterCacheKey};
 use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckEarlyAttesterCache};
 use crate::beacon_proposer_cache::compute_proposer_duties_from_head;
 use crate::beacon_proposer_cache::BeaconProposerCache;
+use crate::block_provenance::{BlockProvenanceLog, SegmentSource};
 use crate::block_times_cache::BlockTimesCache;
 use crate::block_verification::{
     check_block_is_finalized_checkpoint_or_descendant, check_block_relevancy, get_block_root,
@@ -15,6 +16,7 @@ use crate::block_verification::{
 };
 pub use crate::canonical_head::{CanonicalHead, CanonicalHeadRwLock};
 use crate::chain_config::ChainConfig;
+use crate::consecutive_re_orgs::ConsecutiveReOrgs;
 use crate::early_attester_cache::EarlyAttesterCache;
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
 use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
@@ -30,6 +32,7 @@ use crate::light_client_finality_update_verification::{
 use crate::light_client_optimistic_update_verification::{
     Error as LightClientOptimisticUpdateError, VerifiedLightClientOptimisticUpdate,
 };
+use crate::light_client_server_cache::LightClientServerCache;
 use crate::migrate::BackgroundMigrator;
 use crate::naive_aggregation_pool::{
     AggregatedAttestationMap, Error as NaiveAggregationError, NaiveAggregationPool,
@@ -58,6 +61,7 @@ use crate::validator_monitor::{
 };
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
 use crate::{metrics, BeaconChainError, BeaconForkChoiceStore, BeaconSnapshot, CachedHead};
+use eth2::lighthouse::CheckpointSyncReport;
 use eth2::types::{EventKind, SseBlock, SseExtendedPayloadAttributes, SyncDuty};
 use execution_layer::{
     BlockProposalContents, BuilderParams, ChainHealth, ExecutionLayer, FailedCondition,
@@ -70,7 +74,9 @@ use fork_choice::{
 use futures::channel::mpsc::Sender;
 use itertools::process_results;
 use itertools::Itertools;
-use operation_pool::{AttestationRef, OperationPool, PersistedOperationPool, ReceivedPreCapella};
+use operation_pool::{
+    AttestationPacking, AttestationRef, OperationPool, PersistedOperationPool, ReceivedPreCapella,
+};
 use parking_lot::{Mutex, RwLock};
 use proto_array::{DoNotReOrg, ProposerHeadError};
 use safe_arith::SafeArith;
@@ -415,6 +421,10 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub block_times_cache: Arc<RwLock<BlockTimesCache>>,
     /// A cache used to track pre-finalization block roots for quick rejection.
     pub pre_finalization_block_cache: PreFinalizationBlockCache,
+    /// A log of how recently imported blocks arrived at this node.
+    pub block_provenance: BlockProvenanceLog,
+    /// Caches the data needed to serve light clients, when the light client server is enabled.
+    pub light_client_server_cache: LightClientServerCache<T>,
     /// Sender given to tasks, so that if they encounter a state in which execution cannot
     /// continue they can request that everything shuts down.
     pub shutdown_sender: Sender<ShutdownReason>,
@@ -428,6 +438,12 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub validator_monitor: RwLock<ValidatorMonitor<T::EthSpec>>,
     /// The slot at which blocks are downloaded back to.
     pub genesis_backfill_slot: Slot,
+    /// The outcome of verifying the checkpoint sync state, if the node was checkpoint synced from
+    /// a URL since it started.
+    pub checkpoint_sync_report: Option<CheckpointSyncReport>,
+    /// The number of re-orging blocks proposed in a row, used to enforce
+    /// `ChainConfig::re_org_max_consecutive`.
+    pub(crate) consecutive_re_orgs: Mutex<ConsecutiveReOrgs>,
 }
 
 type BeaconBlockAndState<T, Payload> = (BeaconBlock<T, Payload>, BeaconState<T>);
@@ -1111,6 +1127,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(self.store.get_blinded_block(block_root)?)
     }
 
+    /// Returns the SSZ bytes of the blinded block at the given root, if any.
+    ///
+    /// This avoids decoding the block for callers which only need to forward it, e.g. the HTTP
+    /// API.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error.
+    pub fn get_blinded_block_ssz(&self, block_root: &Hash256) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.store.get_blinded_block_bytes(block_root)?)
+    }
+
     /// Returns the state at the given root, if any.
     ///
     /// ## Errors
@@ -1124,6 +1152,33 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(self.store.get_state(state_root, slot)?)
     }
 
+    /// Returns the `LightClientBootstrap` for the block at `block_root`, along with the fork at
+    /// the block's slot.
+    ///
+    /// Returns `None` if the block or its state is unknown.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error, or an error if the block is from before Altair.
+    pub fn get_light_client_bootstrap(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<(LightClientBootstrap<T::EthSpec>, ForkName)>, Error> {
+        let block = match self.get_blinded_block(block_root)? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+
+        match self.get_state(&block.state_root(), Some(block.slot()))? {
+            Some(mut state) => {
+                let fork_name = self.spec.fork_name_at_slot::<T::EthSpec>(block.slot());
+                let bootstrap = LightClientBootstrap::from_beacon_state(&mut state)?;
+                Ok(Some((bootstrap, fork_name)))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Return the sync committee at `slot + 1` from the canonical chain.
     ///
     /// This is useful when dealing with sync committee messages, because messages are signed
@@ -2536,6 +2591,32 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self: &Arc<Self>,
         chain_segment: Vec<Arc<SignedBeaconBlock<T::EthSpec>>>,
         notify_execution_layer: NotifyExecutionLayer,
+    ) -> ChainSegmentResult<T::EthSpec> {
+        self.process_chain_segment_inner(chain_segment, None, notify_execution_layer)
+            .await
+    }
+
+    /// As `Self::process_chain_segment`, but also records `segment_source` in the block
+    /// provenance log for each block which is not already known.
+    pub async fn process_chain_segment_from(
+        self: &Arc<Self>,
+        chain_segment: Vec<Arc<SignedBeaconBlock<T::EthSpec>>>,
+        segment_source: SegmentSource,
+        notify_execution_layer: NotifyExecutionLayer,
+    ) -> ChainSegmentResult<T::EthSpec> {
+        self.process_chain_segment_inner(
+            chain_segment,
+            Some(segment_source),
+            notify_execution_layer,
+        )
+        .await
+    }
+
+    async fn process_chain_segment_inner(
+        self: &Arc<Self>,
+        chain_segment: Vec<Arc<SignedBeaconBlock<T::EthSpec>>>,
+        segment_source: Option<SegmentSource>,
+        notify_execution_layer: NotifyExecutionLayer,
     ) -> ChainSegmentResult<T::EthSpec> {
         let mut imported_blocks = 0;
 
@@ -2556,6 +2637,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         };
 
+        // Reuse the block roots computed while filtering the segment.
+        if let Some(segment_source) = segment_source {
+            for (block_root, _) in &filtered_chain_segment {
+                self.observe_block_source(
+                    *block_root,
+                    segment_source.source,
+                    segment_source.peer_id.clone(),
+                    segment_source.seen_timestamp,
+                );
+            }
+        }
+
         while let Some((_root, block)) = filtered_chain_segment.first() {
             // Determine the epoch of the first block in the remaining segment.
             let start_epoch = block.slot().epoch(T::EthSpec::slots_per_epoch());
@@ -3008,6 +3101,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             parent_block.slot(),
         );
         self.import_block_update_slasher(block, &state, &mut consensus_context);
+        self.consecutive_re_orgs
+            .lock()
+            .block_imported(block.slot(), block.parent_root());
 
         let db_write_timer = metrics::start_timer(&metrics::BLOCK_PROCESSING_DB_WRITE);
 
@@ -3079,6 +3175,22 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         };
         let current_finalized_checkpoint = state.finalized_checkpoint();
 
+        // Cache the light client data of the block before its state is moved into the snapshot
+        // cache.
+        if self.config.enable_light_client_server {
+            if let Err(e) = self
+                .light_client_server_cache
+                .cache_state_data(block, block_root, &mut state)
+            {
+                warn!(
+                    self.log,
+                    "Failed to cache light client data";
+                    "error" => ?e,
+                    "block_root" => ?block_root,
+                );
+            }
+        }
+
         self.snapshot_cache
             .try_write_for(BLOCK_PROCESSING_CACHE_LOCK_TIMEOUT)
             .ok_or(Error::SnapshotCacheLockTimeout)
@@ -3124,6 +3236,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.pre_finalization_block_cache
             .block_processed(block_root);
 
+        self.import_block_update_light_client_server_cache(block);
+
         self.import_block_update_metrics_and_events(
             block,
             block_root,
@@ -3135,6 +3249,34 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(block_root)
     }
 
+    /// Update the light client updates served by this node with the sync aggregate of `block`.
+    fn import_block_update_light_client_server_cache(&self, block: BeaconBlockRef<T::EthSpec>) {
+        if !self.config.enable_light_client_server {
+            return;
+        }
+
+        // Blocks from before Altair have no sync aggregate.
+        let sync_aggregate = match block.body().sync_aggregate() {
+            Ok(sync_aggregate) => sync_aggregate,
+            Err(_) => return,
+        };
+
+        if let Err(e) = self.light_client_server_cache.recompute_and_cache_updates(
+            &self.store,
+            block.parent_root(),
+            block.slot(),
+            sync_aggregate,
+            &self.spec,
+        ) {
+            warn!(
+                self.log,
+                "Failed to recompute light client updates";
+                "error" => ?e,
+                "slot" => block.slot(),
+            );
+        }
+    }
+
     /// Check block's consistentency with any configured weak subjectivity checkpoint.
     fn check_block_against_weak_subjectivity_checkpoint(
         &self,
@@ -3409,6 +3551,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        self.block_provenance.on_import(
+            block_root,
+            block.slot(),
+            self.slot_clock.start_of(block.slot()),
+            block_time_imported,
+        );
+
         let block_delay_total =
             get_slot_delay_ms(block_time_imported, block.slot(), &self.slot_clock);
 
@@ -3694,8 +3843,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         };
         let (state, state_root_opt) = if head_slot < slot {
             // Attempt an aggressive re-org if configured and the conditions are right.
-            if let Some(re_org_state) = self.get_state_for_re_org(slot, head_slot, head_block_root)
-            {
+            let re_org_state = self.get_state_for_re_org(slot, head_slot, head_block_root);
+            self.consecutive_re_orgs
+                .lock()
+                .block_produced(slot, head_block_root);
+
+            if let Some(re_org_state) = re_org_state {
                 info!(
                     self.log,
                     "Proposing block to re-org current head";
@@ -3747,14 +3900,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     /// Fetch the beacon state to use for producing a block if a 1-slot proposer re-org is viable.
     ///
-    /// This function will return `None` if proposer re-orgs are disabled.
+    /// This function will return `None` if proposer re-orgs are disabled or in dry-run mode.
     fn get_state_for_re_org(
         &self,
         slot: Slot,
         head_slot: Slot,
         canonical_head: Hash256,
     ) -> Option<BlockProductionPreState<T::EthSpec>> {
-        let re_org_threshold = self.config.re_org_threshold?;
+        let re_org_threshold = self
+            .config
+            .re_org_threshold_at(slot.epoch(T::EthSpec::slots_per_epoch()))?;
 
         if self.spec.proposer_score_boost.is_none() {
             warn!(
@@ -3802,6 +3957,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return None;
         }
 
+        if let Err(reason) = self.check_consecutive_re_orgs() {
+            debug!(
+                self.log,
+                "Not attempting re-org";
+                "reason" => %reason,
+            );
+            return None;
+        }
+
         // Is the current head weak and appropriate for re-orging?
         let proposer_head_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_GET_PROPOSER_HEAD_TIMES);
@@ -3852,6 +4016,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 None
             })?;
 
+        if self.config.re_org_dry_run {
+            info!(
+                self.log,
+                "Would have attempted re-org due to weak head";
+                "weak_head" => ?canonical_head,
+                "parent" => ?re_org_parent_block,
+                "head_weight" => proposer_head.head_node.weight,
+                "threshold_weight" => proposer_head.re_org_weight_threshold,
+                "re_org_threshold" => re_org_threshold.0,
+            );
+            return None;
+        }
+
         info!(
             self.log,
             "Attempting re-org due to weak head";
@@ -3864,6 +4041,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Some(pre_state)
     }
 
+    /// Returns an error if `ChainConfig::re_org_max_consecutive` re-orging blocks have already
+    /// been proposed in a row.
+    fn check_consecutive_re_orgs(&self) -> Result<(), DoNotReOrg> {
+        let count = self.consecutive_re_orgs.lock().count();
+        match self.config.re_org_max_consecutive {
+            Some(max_consecutive) if count >= max_consecutive => {
+                Err(DoNotReOrg::TooManyConsecutiveReOrgs { count })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Get the proposer index and `prev_randao` value for a proposal at slot `proposal_slot`.
     ///
     /// The `proposer_head` may be the head block of `cached_head` or its parent. An error will
@@ -4079,8 +4268,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Never override if proposer re-orgs are disabled.
         let re_org_threshold = self
             .config
-            .re_org_threshold
+            .re_org_threshold_at(self.epoch().map_err(ProposerHeadError::Error)?)
             .ok_or(DoNotReOrg::ReOrgsDisabled)?;
+        self.check_consecutive_re_orgs()?;
 
         let head_block_root = canonical_forkchoice_params.head_root;
 
@@ -4183,6 +4373,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return Err(DoNotReOrg::HeadNotLate.into());
         }
 
+        // In dry-run mode the re-org will not be attempted, so the execution layer must follow the
+        // canonical head.
+        if self.config.re_org_dry_run {
+            return Err(DoNotReOrg::DryRun.into());
+        }
+
         let parent_head_hash = info.parent_node.execution_status.block_hash();
         let forkchoice_update_params = ForkchoiceUpdateParameters {
             head_root: info.parent_node.root,
@@ -4409,12 +4605,25 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.filter_op_pool_attestation(&mut curr_filter_cache, att, &state)
         };
 
+        let packing = if self
+            .config
+            .max_cover_attestation_packing_fork
+            .and_then(|fork_name| self.spec.fork_epoch(fork_name))
+            .map_or(false, |fork_epoch| state.current_epoch() >= fork_epoch)
+        {
+            AttestationPacking::MaxCover {
+                time_budget: self.config.attestation_packing_time_budget,
+            }
+        } else {
+            AttestationPacking::Greedy
+        };
         let mut attestations = self
             .op_pool
-            .get_attestations(
+            .get_attestations_with_packing(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                packing,
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
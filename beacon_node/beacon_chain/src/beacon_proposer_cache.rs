@@ -53,12 +53,15 @@ pub struct EpochBlockProposers {
 /// See the module-level documentation for more information.
 pub struct BeaconProposerCache {
     cache: LruCache<(Epoch, Hash256), EpochBlockProposers>,
+    /// The epoch and shuffling decision block of the most recent next-epoch proposer lookahead.
+    lookahead: Option<(Epoch, Hash256)>,
 }
 
 impl Default for BeaconProposerCache {
     fn default() -> Self {
         Self {
             cache: LruCache::new(CACHE_SIZE),
+            lookahead: None,
         }
     }
 }
@@ -105,6 +108,21 @@ impl BeaconProposerCache {
         self.cache.get(&key).map(|cache| &cache.proposers)
     }
 
+    /// Records `shuffling_decision_block` as the decision block of the proposer lookahead for
+    /// `epoch`.
+    ///
+    /// Returns `true` if the lookahead for `epoch` was previously recorded with a different
+    /// decision block, i.e. the dependent root of the lookahead has changed.
+    pub fn update_lookahead(&mut self, epoch: Epoch, shuffling_decision_block: Hash256) -> bool {
+        let changed = matches!(
+            self.lookahead,
+            Some((lookahead_epoch, lookahead_block))
+                if lookahead_epoch == epoch && lookahead_block != shuffling_decision_block
+        );
+        self.lookahead = Some((epoch, shuffling_decision_block));
+        changed
+    }
+
     /// Insert the proposers into the cache.
     ///
     /// See `Self::get` for a description of `shuffling_decision_block`.
@@ -200,3 +218,25 @@ pub fn ensure_state_is_in_epoch<E: EthSpec>(
         Ordering::Equal => Ok(()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookahead_changes_with_the_dependent_root() {
+        let mut cache = BeaconProposerCache::default();
+        let epoch = Epoch::new(1);
+        let root_a = Hash256::repeat_byte(1);
+        let root_b = Hash256::repeat_byte(2);
+
+        // The first lookahead for an epoch is not a change.
+        assert!(!cache.update_lookahead(epoch, root_a));
+        assert!(!cache.update_lookahead(epoch, root_a));
+        // A new dependent root for the same epoch is.
+        assert!(cache.update_lookahead(epoch, root_b));
+        assert!(cache.update_lookahead(epoch, root_a));
+        // A new epoch is not.
+        assert!(!cache.update_lookahead(epoch + 1, root_b));
+    }
+}
@@ -0,0 +1,212 @@
+//! Records how each imported block arrived at this node, to help debug block propagation.
+//!
+//! Block sources are observed before processing and matched to the block when it is imported.
+use crate::{BeaconChain, BeaconChainTypes};
+pub use eth2::lighthouse::{BlockProvenance, BlockSource};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::time::Duration;
+use types::{Hash256, Slot};
+
+/// The maximum number of blocks whose source is known, but which haven't been imported yet.
+const PENDING_BLOCK_LIMIT: usize = 1024;
+/// The maximum number of imported blocks to remember.
+const IMPORTED_BLOCK_LIMIT: usize = 1024;
+
+/// The source of a chain segment, which is recorded for each of its blocks once their roots have
+/// been computed.
+#[derive(Debug, Clone)]
+pub struct SegmentSource {
+    pub source: BlockSource,
+    pub peer_id: Option<String>,
+    pub seen_timestamp: Duration,
+}
+
+struct Observation {
+    source: BlockSource,
+    peer_id: Option<String>,
+    seen_timestamp: Duration,
+}
+
+/// A bounded log of the provenance of recently imported blocks.
+#[derive(Default)]
+pub struct BlockProvenanceLog {
+    log: Mutex<Log>,
+}
+
+struct Log {
+    pending: LruCache<Hash256, Observation>,
+    /// Imported blocks, oldest first.
+    imported: VecDeque<BlockProvenance>,
+}
+
+impl Default for Log {
+    fn default() -> Self {
+        Log {
+            pending: LruCache::new(PENDING_BLOCK_LIMIT),
+            imported: VecDeque::with_capacity(IMPORTED_BLOCK_LIMIT),
+        }
+    }
+}
+
+impl BlockProvenanceLog {
+    /// Record that the block with `block_root` was received from `source` at `seen_timestamp`.
+    ///
+    /// If the block is received more than once before being imported, the latest observation is
+    /// kept since it is the one which led to the block being processed.
+    pub fn observe(
+        &self,
+        block_root: Hash256,
+        source: BlockSource,
+        peer_id: Option<String>,
+        seen_timestamp: Duration,
+    ) {
+        self.log.lock().pending.put(
+            block_root,
+            Observation {
+                source,
+                peer_id,
+                seen_timestamp,
+            },
+        );
+    }
+
+    /// Record the import of the block with `block_root`.
+    pub fn on_import(
+        &self,
+        block_root: Hash256,
+        slot: Slot,
+        slot_start: Option<Duration>,
+        imported_timestamp: Duration,
+    ) {
+        let mut log = self.log.lock();
+        let provenance = match log.pending.pop(&block_root) {
+            Some(observation) => BlockProvenance {
+                block_root,
+                slot,
+                source: observation.source,
+                peer_id: observation.peer_id,
+                observed_delay_ms: slot_start
+                    .and_then(|start| observation.seen_timestamp.checked_sub(start))
+                    .map(|delay| delay.as_millis() as u64),
+                import_delay_ms: imported_timestamp
+                    .checked_sub(observation.seen_timestamp)
+                    .map(|delay| delay.as_millis() as u64),
+            },
+            None => BlockProvenance {
+                block_root,
+                slot,
+                source: BlockSource::Unknown,
+                peer_id: None,
+                observed_delay_ms: None,
+                import_delay_ms: None,
+            },
+        };
+
+        if log.imported.len() >= IMPORTED_BLOCK_LIMIT {
+            log.imported.pop_front();
+        }
+        log.imported.push_back(provenance);
+    }
+
+    /// Returns the provenance of recently imported blocks, oldest first.
+    pub fn imported(&self) -> Vec<BlockProvenance> {
+        self.log.lock().imported.iter().cloned().collect()
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Record that the block with `block_root` was received from `source` at `seen_timestamp`,
+    /// ahead of it being processed.
+    pub fn observe_block_source(
+        &self,
+        block_root: Hash256,
+        source: BlockSource,
+        peer_id: Option<String>,
+        seen_timestamp: Duration,
+    ) {
+        self.block_provenance
+            .observe(block_root, source, peer_id, seen_timestamp);
+    }
+
+    /// Returns the provenance of recently imported blocks, oldest first.
+    pub fn block_provenance(&self) -> Vec<BlockProvenance> {
+        self.block_provenance.imported()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_provenance_log() {
+        let log = BlockProvenanceLog::default();
+        let slot_start = Duration::from_secs(120);
+
+        let gossip_root = Hash256::repeat_byte(1);
+        log.observe(
+            gossip_root,
+            BlockSource::RpcRoot,
+            Some("peer_a".into()),
+            Duration::from_secs(130),
+        );
+        // The latest observation replaces the earlier one.
+        log.observe(
+            gossip_root,
+            BlockSource::Gossip,
+            Some("peer_b".into()),
+            Duration::from_millis(122_500),
+        );
+        log.on_import(
+            gossip_root,
+            Slot::new(10),
+            Some(slot_start),
+            Duration::from_millis(122_750),
+        );
+
+        let unknown_root = Hash256::repeat_byte(2);
+        log.on_import(unknown_root, Slot::new(11), None, Duration::from_secs(133));
+
+        assert_eq!(
+            log.imported(),
+            vec![
+                BlockProvenance {
+                    block_root: gossip_root,
+                    slot: Slot::new(10),
+                    source: BlockSource::Gossip,
+                    peer_id: Some("peer_b".into()),
+                    observed_delay_ms: Some(2_500),
+                    import_delay_ms: Some(250),
+                },
+                BlockProvenance {
+                    block_root: unknown_root,
+                    slot: Slot::new(11),
+                    source: BlockSource::Unknown,
+                    peer_id: None,
+                    observed_delay_ms: None,
+                    import_delay_ms: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn block_provenance_log_is_bounded() {
+        let log = BlockProvenanceLog::default();
+
+        for i in 0..IMPORTED_BLOCK_LIMIT as u64 + 10 {
+            log.on_import(
+                Hash256::from_low_u64_be(i),
+                Slot::new(i),
+                None,
+                Duration::from_secs(i),
+            );
+        }
+
+        let imported = log.imported();
+        assert_eq!(imported.len(), IMPORTED_BLOCK_LIMIT);
+        assert_eq!(imported[0].slot, Slot::new(10));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::beacon_chain::{CanonicalHead, BEACON_CHAIN_DB_KEY, ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY};
+use crate::consecutive_re_orgs::ConsecutiveReOrgs;
 use crate::eth1_chain::{CachingEth1Backend, SszEth1};
 use crate::eth1_finalization_cache::Eth1FinalizationCache;
 use crate::fork_choice_signal::ForkChoiceSignalTx;
@@ -17,11 +18,12 @@ use crate::{
     Eth1ChainBackend, ServerSentEventHandler,
 };
 use eth1::Config as Eth1Config;
+use eth2::lighthouse::CheckpointSyncReport;
 use execution_layer::ExecutionLayer;
 use fork_choice::{ForkChoice, ResetPayloadStatuses};
 use futures::channel::mpsc::Sender;
 use operation_pool::{OperationPool, PersistedOperationPool};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use slasher::Slasher;
 use slog::{crit, debug, error, info, Logger};
@@ -91,6 +93,7 @@ pub struct BeaconChainBuilder<T: BeaconChainTypes> {
     log: Option<Logger>,
     graffiti: Graffiti,
     slasher: Option<Arc<Slasher<T::EthSpec>>>,
+    checkpoint_sync_report: Option<CheckpointSyncReport>,
     validator_monitor: Option<ValidatorMonitor<T::EthSpec>>,
     // Pending I/O batch that is constructed during building and should be executed atomically
     // alongside `PersistedBeaconChain` storage when `BeaconChainBuilder::build` is called.
@@ -132,6 +135,7 @@ where
             log: None,
             graffiti: Graffiti::default(),
             slasher: None,
+            checkpoint_sync_report: None,
             validator_monitor: None,
             pending_io_batch: vec![],
             task_executor: None,
@@ -594,6 +598,12 @@ where
         self
     }
 
+    /// Records the outcome of verifying the checkpoint sync state against remote providers.
+    pub fn checkpoint_sync_report(mut self, report: CheckpointSyncReport) -> Self {
+        self.checkpoint_sync_report = Some(report);
+        self
+    }
+
     /// Sets the `graffiti` field.
     pub fn graffiti(mut self, graffiti: Graffiti) -> Self {
         self.graffiti = graffiti;
@@ -759,6 +769,8 @@ where
             log.clone(),
         );
 
+        validator_monitor
+            .set_metric_aggregation(self.chain_config.validator_monitor_metric_aggregation);
         if let Some(slot) = slot_clock.now() {
             validator_monitor.process_valid_state(
                 slot.epoch(TEthSpec::slots_per_epoch()),
@@ -801,6 +813,7 @@ where
         let head_for_snapshot_cache = head_snapshot.clone();
         let canonical_head = CanonicalHead::new(fork_choice, Arc::new(head_snapshot));
         let shuffling_cache_size = self.chain_config.shuffling_cache_size;
+        let shuffling_cache_memory_budget = self.chain_config.shuffling_cache_memory_budget;
 
         // Calculate the weak subjectivity point in which to backfill blocks to.
         let genesis_backfill_slot = if self.chain_config.genesis_backfill {
@@ -878,6 +891,7 @@ where
             )),
             shuffling_cache: TimeoutRwLock::new(ShufflingCache::new(
                 shuffling_cache_size,
+                shuffling_cache_memory_budget,
                 head_shuffling_ids,
                 log.clone(),
             )),
@@ -885,6 +899,8 @@ where
             beacon_proposer_cache: <_>::default(),
             block_times_cache: <_>::default(),
             pre_finalization_block_cache: <_>::default(),
+            block_provenance: <_>::default(),
+            light_client_server_cache: <_>::default(),
             validator_pubkey_cache: TimeoutRwLock::new(validator_pubkey_cache),
             attester_cache: <_>::default(),
             early_attester_cache: <_>::default(),
@@ -896,6 +912,8 @@ where
             slasher: self.slasher.clone(),
             validator_monitor: RwLock::new(validator_monitor),
             genesis_backfill_slot,
+            consecutive_re_orgs: Mutex::new(ConsecutiveReOrgs::default()),
+            checkpoint_sync_report: self.checkpoint_sync_report,
         };
 
         let head = beacon_chain.head_snapshot();
@@ -45,7 +45,10 @@ use crate::{
     validator_monitor::{get_slot_delay_ms, timestamp_now},
     BeaconChain, BeaconChainError as Error, BeaconChainTypes, BeaconSnapshot,
 };
-use eth2::types::{EventKind, SseChainReorg, SseFinalizedCheckpoint, SseHead, SseLateHead};
+use eth2::types::{
+    EventKind, SseChainReorg, SseChainReorgDetail, SseDependentRootChange, SseFinalizedCheckpoint,
+    SseHead, SseLateHead, SseReorgBlock, SseReorgBranch,
+};
 use fork_choice::{
     ExecutionStatus, ForkChoiceStore, ForkChoiceView, ForkchoiceUpdateParameters, ProtoBlock,
     ResetPayloadStatuses,
@@ -902,20 +905,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.op_pool.prune_attestations(self.epoch()?);
         }
 
+        // Return operations from the orphaned blocks to the op pool so they may be included in
+        // a future block.
+        if reorg_distance.is_some() {
+            self.spawn_reinclude_orphaned_operations(
+                old_snapshot.beacon_block_root,
+                new_snapshot.clone(),
+            );
+        }
+
         // Register server-sent-events for a new head.
         if let Some(event_handler) = self
             .event_handler
             .as_ref()
             .filter(|handler| handler.has_head_subscribers())
         {
-            match (dependent_root, prev_dependent_root) {
+            match (&dependent_root, &prev_dependent_root) {
                 (Ok(current_duty_dependent_root), Ok(previous_duty_dependent_root)) => {
                     event_handler.register(EventKind::Head(SseHead {
                         slot: head_slot,
                         block: new_snapshot.beacon_block_root,
                         state: state_root,
-                        current_duty_dependent_root,
-                        previous_duty_dependent_root,
+                        current_duty_dependent_root: *current_duty_dependent_root,
+                        previous_duty_dependent_root: *previous_duty_dependent_root,
                         epoch_transition: is_epoch_transition,
                         execution_optimistic: new_head_is_optimistic,
                     }));
@@ -948,11 +960,146 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     execution_optimistic: new_head_is_optimistic,
                 }));
             }
+
+            if let Some(event_handler) = self
+                .event_handler
+                .as_ref()
+                .filter(|handler| handler.has_reorg_detail_subscribers())
+            {
+                if let Some(detail) = self.reorg_detail(
+                    old_snapshot.beacon_block_root,
+                    new_snapshot.beacon_block_root,
+                    head_slot,
+                    depth,
+                    new_head_is_optimistic,
+                ) {
+                    event_handler.register(EventKind::ChainReorgDetail(Box::new(detail)));
+                }
+            }
+
+            // Register a server-sent-event if the re-org changed the dependent root of any duties
+            // which were already known under the old head.
+            if let Some(event_handler) = self
+                .event_handler
+                .as_ref()
+                .filter(|handler| handler.has_dependent_root_change_subscribers())
+            {
+                if let (Ok(current_duty_dependent_root), Ok(previous_duty_dependent_root)) =
+                    (dependent_root, prev_dependent_root)
+                {
+                    let old_state = &old_snapshot.beacon_state;
+                    let old_epoch = old_state.current_epoch();
+                    let new_epoch = new_snapshot.beacon_state.current_epoch();
+
+                    let (old_previous_duty_dependent_root, old_current_duty_dependent_root) =
+                        if old_epoch == new_epoch {
+                            (
+                                old_state
+                                    .attester_shuffling_decision_root(
+                                        self.genesis_block_root,
+                                        RelativeEpoch::Current,
+                                    )
+                                    .ok(),
+                                old_state
+                                    .proposer_shuffling_decision_root(self.genesis_block_root)
+                                    .ok(),
+                            )
+                        } else if old_epoch + 1 == new_epoch {
+                            // The attester duties for the new epoch were determined by the old
+                            // head's current duty dependent root.
+                            (
+                                old_state
+                                    .proposer_shuffling_decision_root(self.genesis_block_root)
+                                    .ok(),
+                                None,
+                            )
+                        } else {
+                            (None, None)
+                        };
+
+                    let changed = old_previous_duty_dependent_root
+                        .map_or(false, |root| root != previous_duty_dependent_root)
+                        || old_current_duty_dependent_root
+                            .map_or(false, |root| root != current_duty_dependent_root);
+
+                    if changed {
+                        event_handler.register(EventKind::DependentRootChange(
+                            SseDependentRootChange {
+                                slot: head_slot,
+                                epoch: new_epoch,
+                                block: new_snapshot.beacon_block_root,
+                                old_previous_duty_dependent_root,
+                                previous_duty_dependent_root,
+                                old_current_duty_dependent_root,
+                                current_duty_dependent_root,
+                                execution_optimistic: new_head_is_optimistic,
+                            },
+                        ));
+                    }
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Describes the weight of, votes for and proposers of each side of the re-org from
+    /// `old_head_root` to `new_head_root`.
+    ///
+    /// This takes a read-lock on fork choice and reads the orphaned blocks from the database, so
+    /// it should only be called when the result will be used.
+    fn reorg_detail(
+        &self,
+        old_head_root: Hash256,
+        new_head_root: Hash256,
+        head_slot: Slot,
+        depth: Slot,
+        execution_optimistic: bool,
+    ) -> Option<SseChainReorgDetail> {
+        let info = self
+            .canonical_head
+            .fork_choice_read_lock()
+            .proto_array()
+            .reorg_info(old_head_root, new_head_root)?;
+
+        let branch = |branch: proto_array::ReorgBranch| SseReorgBranch {
+            blocks: branch
+                .blocks
+                .into_iter()
+                .map(|(block, slot)| SseReorgBlock {
+                    block,
+                    slot,
+                    proposer_index: self
+                        .store
+                        .get_blinded_block(&block)
+                        .ok()
+                        .flatten()
+                        .map(|block| block.message().proposer_index()),
+                })
+                .collect(),
+            weight: branch.weight,
+            validators: branch.validators,
+            balance: branch.balance,
+        };
+
+        Some(SseChainReorgDetail {
+            slot: head_slot,
+            epoch: head_slot.epoch(T::EthSpec::slots_per_epoch()),
+            depth: depth.as_u64(),
+            old_head_block: old_head_root,
+            new_head_block: new_head_root,
+            common_ancestor_block: info.common_ancestor,
+            common_ancestor_slot: info.common_ancestor_slot,
+            weight_difference: (info.new_branch.weight as i64)
+                .saturating_sub(info.old_branch.weight as i64),
+            old_branch: branch(info.old_branch),
+            new_branch: branch(info.new_branch),
+            proposer_boost_block: info.proposer_boost_root,
+            proposer_boost_score: info.proposer_boost_score,
+            execution_optimistic,
+        })
+    }
+
     /// Perform updates to caches and other components after the finalized checkpoint has been
     /// changed.
     ///
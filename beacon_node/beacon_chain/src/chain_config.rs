@@ -1,13 +1,15 @@
+use crate::validator_monitor::MetricAggregation;
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use types::{Checkpoint, Epoch, ProgressiveBalancesMode};
+use types::{Checkpoint, Epoch, ForkName, ProgressiveBalancesMode};
 
 pub const DEFAULT_RE_ORG_THRESHOLD: ReOrgThreshold = ReOrgThreshold(20);
 pub const DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION: Epoch = Epoch::new(2);
 /// Default to 1/12th of the slot, which is 1 second on mainnet.
 pub const DEFAULT_RE_ORG_CUTOFF_DENOMINATOR: u32 = 12;
 pub const DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT: u64 = 250;
+pub const DEFAULT_ATTESTATION_PACKING_TIME_BUDGET: Duration = Duration::from_millis(50);
 
 /// Default fraction of a slot lookahead for payload preparation (12/3 = 4 seconds on mainnet).
 pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
@@ -42,6 +44,12 @@ pub struct ChainConfig {
     /// By default this list is empty, but it can be useful for reacting to network conditions, e.g.
     /// slow gossip of re-org blocks at slot 1 in the epoch.
     pub re_org_disallowed_offsets: DisallowedReOrgOffsets,
+    /// Thresholds which replace `re_org_threshold` from the given epoch onwards, sorted by epoch.
+    pub re_org_threshold_schedule: Vec<(Epoch, ReOrgThreshold)>,
+    /// Maximum number of re-orging blocks to propose in a row, or `None` for no limit.
+    pub re_org_max_consecutive: Option<u64>,
+    /// Log when a re-org would be attempted, without attempting it.
+    pub re_org_dry_run: bool,
     /// Number of milliseconds to wait for fork choice before proposing a block.
     ///
     /// If set to 0 then block proposal will not wait for fork choice at all.
@@ -72,6 +80,10 @@ pub struct ChainConfig {
     pub optimistic_finalized_sync: bool,
     /// The size of the shuffling cache,
     pub shuffling_cache_size: usize,
+    /// The maximum memory used by the shuffling cache in bytes, if any.
+    ///
+    /// The least-recently used shufflings are evicted to respect the budget.
+    pub shuffling_cache_memory_budget: Option<usize>,
     /// If using a weak-subjectivity sync, whether we should download blocks all the way back to
     /// genesis.
     pub genesis_backfill: bool,
@@ -83,6 +95,21 @@ pub struct ChainConfig {
     pub progressive_balances_mode: ProgressiveBalancesMode,
     /// Number of epochs between each migration of data from the hot database to the freezer.
     pub epochs_per_migration: u64,
+    /// Whether to compute and cache the updates needed to serve light clients.
+    pub enable_light_client_server: bool,
+    /// Labels used for validator monitor metrics once validators are no longer tracked
+    /// individually.
+    pub validator_monitor_metric_aggregation: MetricAggregation,
+    /// Number of epochs between audits of the head state root, or `None` to disable the audit.
+    pub state_root_audit_interval: Option<u64>,
+    /// Maximum number of threads used to process validators in parallel during epoch processing,
+    /// or `None` to use the global thread pool.
+    pub max_epoch_processing_threads: Option<usize>,
+    /// The fork from which attestations are packed into blocks using a local search rather than
+    /// greedily, or `None` to always pack greedily.
+    pub max_cover_attestation_packing_fork: Option<ForkName>,
+    /// The maximum time spent improving the packing of attestations for each epoch.
+    pub attestation_packing_time_budget: Duration,
 }
 
 impl Default for ChainConfig {
@@ -97,6 +124,9 @@ impl Default for ChainConfig {
             re_org_max_epochs_since_finalization: DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
             re_org_cutoff_millis: None,
             re_org_disallowed_offsets: DisallowedReOrgOffsets::default(),
+            re_org_threshold_schedule: vec![],
+            re_org_max_consecutive: None,
+            re_org_dry_run: false,
             fork_choice_before_proposal_timeout_ms: DEFAULT_FORK_CHOICE_BEFORE_PROPOSAL_TIMEOUT,
             // Builder fallback configs that are set in `clap` will override these.
             builder_fallback_skips: 3,
@@ -110,10 +140,17 @@ impl Default for ChainConfig {
             // This value isn't actually read except in tests.
             optimistic_finalized_sync: true,
             shuffling_cache_size: crate::shuffling_cache::DEFAULT_CACHE_SIZE,
+            shuffling_cache_memory_budget: None,
             genesis_backfill: false,
             always_prepare_payload: false,
             progressive_balances_mode: ProgressiveBalancesMode::Checked,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
+            enable_light_client_server: false,
+            validator_monitor_metric_aggregation: MetricAggregation::Total,
+            state_root_audit_interval: None,
+            max_epoch_processing_threads: None,
+            max_cover_attestation_packing_fork: None,
+            attestation_packing_time_budget: DEFAULT_ATTESTATION_PACKING_TIME_BUDGET,
         }
     }
 }
@@ -127,4 +164,16 @@ impl ChainConfig {
                 Duration::from_secs(seconds_per_slot) / DEFAULT_RE_ORG_CUTOFF_DENOMINATOR
             })
     }
+
+    /// The re-org threshold which applies at `epoch`, or `None` if re-orgs are disabled.
+    pub fn re_org_threshold_at(&self, epoch: Epoch) -> Option<ReOrgThreshold> {
+        let threshold = self.re_org_threshold?;
+        Some(
+            self.re_org_threshold_schedule
+                .iter()
+                .rev()
+                .find(|(start_epoch, _)| *start_epoch <= epoch)
+                .map_or(threshold, |(_, threshold)| *threshold),
+        )
+    }
 }
@@ -0,0 +1,80 @@
+//! Counts the re-orging blocks proposed in a row, to enforce
+//! `ChainConfig::re_org_max_consecutive`.
+//!
+//! A produced block may never be signed or published, so blocks are only counted once they have
+//! been imported.
+use types::{Hash256, Slot};
+
+#[derive(Debug, Default)]
+pub struct ConsecutiveReOrgs {
+    /// The number of re-orging blocks proposed in a row.
+    count: u64,
+    /// The slot of the last block produced by this node and the head it was produced against, if
+    /// the block has not been imported yet.
+    produced: Option<(Slot, Hash256)>,
+}
+
+impl ConsecutiveReOrgs {
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Records that a block was produced at `slot` while `head_block_root` was the head.
+    ///
+    /// This replaces any earlier block which was never imported.
+    pub fn block_produced(&mut self, slot: Slot, head_block_root: Hash256) {
+        self.produced = Some((slot, head_block_root));
+    }
+
+    /// Updates the count if the block at `slot` with `parent_root` is the last block produced.
+    ///
+    /// The block re-orged the head if it was not built atop the head it was produced against.
+    pub fn block_imported(&mut self, slot: Slot, parent_root: Hash256) {
+        match self.produced {
+            Some((produced_slot, head_block_root)) if produced_slot == slot => {
+                self.produced = None;
+                if parent_root == head_block_root {
+                    self.count = 0;
+                } else {
+                    self.count += 1;
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_imported_re_orgs() {
+        let mut re_orgs = ConsecutiveReOrgs::default();
+        let head = Hash256::repeat_byte(1);
+        let parent = Hash256::repeat_byte(2);
+
+        // A re-orging block which is never imported is not counted.
+        re_orgs.block_produced(Slot::new(1), head);
+        assert_eq!(re_orgs.count(), 0);
+
+        // A re-orging block is counted once it is imported, and only once.
+        re_orgs.block_produced(Slot::new(2), head);
+        re_orgs.block_imported(Slot::new(2), parent);
+        assert_eq!(re_orgs.count(), 1);
+        re_orgs.block_imported(Slot::new(2), parent);
+        assert_eq!(re_orgs.count(), 1);
+
+        // Blocks from other proposers are ignored.
+        re_orgs.block_produced(Slot::new(4), head);
+        re_orgs.block_imported(Slot::new(3), parent);
+        assert_eq!(re_orgs.count(), 1);
+        re_orgs.block_imported(Slot::new(4), parent);
+        assert_eq!(re_orgs.count(), 2);
+
+        // The count resets when a block built atop the head is imported.
+        re_orgs.block_produced(Slot::new(5), head);
+        re_orgs.block_imported(Slot::new(5), head);
+        assert_eq!(re_orgs.count(), 0);
+    }
+}
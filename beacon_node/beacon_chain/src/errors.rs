@@ -168,6 +168,7 @@ pub enum BeaconChainError {
     BlockRewardSyncError,
     SyncCommitteeRewardsSyncError,
     AttestationRewardsError,
+    EpochRewardsCancelled,
     HeadMissingFromForkChoice(Hash256),
     FinalizedBlockMissingFromForkChoice(Hash256),
     HeadBlockMissingFromForkChoice(Hash256),
@@ -217,6 +218,7 @@ pub enum BeaconChainError {
     InconsistentFork(InconsistentFork),
     ProposerHeadForkChoiceError(fork_choice::Error<proto_array::Error>),
     UnableToPublish,
+    LightClientUpdateError(LightClientUpdateError),
 }
 
 easy_from_to!(SlotProcessingError, BeaconChainError);
@@ -242,6 +244,7 @@ easy_from_to!(HistoricalBlockError, BeaconChainError);
 easy_from_to!(StateAdvanceError, BeaconChainError);
 easy_from_to!(BlockReplayError, BeaconChainError);
 easy_from_to!(InconsistentFork, BeaconChainError);
+easy_from_to!(LightClientUpdateError, BeaconChainError);
 
 #[derive(Debug)]
 pub enum BlockProductionError {
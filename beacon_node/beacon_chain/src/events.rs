@@ -13,10 +13,15 @@ pub struct ServerSentEventHandler<T: EthSpec> {
     head_tx: Sender<EventKind<T>>,
     exit_tx: Sender<EventKind<T>>,
     chain_reorg_tx: Sender<EventKind<T>>,
+    chain_reorg_detail_tx: Sender<EventKind<T>>,
+    dependent_root_change_tx: Sender<EventKind<T>>,
     contribution_tx: Sender<EventKind<T>>,
     payload_attributes_tx: Sender<EventKind<T>>,
     late_head: Sender<EventKind<T>>,
     block_reward_tx: Sender<EventKind<T>>,
+    builder_bid_tx: Sender<EventKind<T>>,
+    payload_revealed_tx: Sender<EventKind<T>>,
+    payload_delivered_tx: Sender<EventKind<T>>,
     log: Logger,
 }
 
@@ -35,10 +40,15 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         let (head_tx, _) = broadcast::channel(capacity);
         let (exit_tx, _) = broadcast::channel(capacity);
         let (chain_reorg_tx, _) = broadcast::channel(capacity);
+        let (chain_reorg_detail_tx, _) = broadcast::channel(capacity);
+        let (dependent_root_change_tx, _) = broadcast::channel(capacity);
         let (contribution_tx, _) = broadcast::channel(capacity);
         let (payload_attributes_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
         let (block_reward_tx, _) = broadcast::channel(capacity);
+        let (builder_bid_tx, _) = broadcast::channel(capacity);
+        let (payload_revealed_tx, _) = broadcast::channel(capacity);
+        let (payload_delivered_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -47,10 +57,15 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
             head_tx,
             exit_tx,
             chain_reorg_tx,
+            chain_reorg_detail_tx,
+            dependent_root_change_tx,
             contribution_tx,
             payload_attributes_tx,
             late_head,
             block_reward_tx,
+            builder_bid_tx,
+            payload_revealed_tx,
+            payload_delivered_tx,
             log,
         }
     }
@@ -89,6 +104,14 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
                 .chain_reorg_tx
                 .send(kind)
                 .map(|count| log_count("chain reorg", count)),
+            EventKind::ChainReorgDetail(_) => self
+                .chain_reorg_detail_tx
+                .send(kind)
+                .map(|count| log_count("chain reorg detail", count)),
+            EventKind::DependentRootChange(_) => self
+                .dependent_root_change_tx
+                .send(kind)
+                .map(|count| log_count("dependent root change", count)),
             EventKind::ContributionAndProof(_) => self
                 .contribution_tx
                 .send(kind)
@@ -105,6 +128,18 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
                 .block_reward_tx
                 .send(kind)
                 .map(|count| log_count("block reward", count)),
+            EventKind::BuilderBid(_) => self
+                .builder_bid_tx
+                .send(kind)
+                .map(|count| log_count("builder bid", count)),
+            EventKind::PayloadRevealed(_) => self
+                .payload_revealed_tx
+                .send(kind)
+                .map(|count| log_count("payload revealed", count)),
+            EventKind::PayloadDelivered(_) => self
+                .payload_delivered_tx
+                .send(kind)
+                .map(|count| log_count("payload delivered", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -135,6 +170,14 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.chain_reorg_tx.subscribe()
     }
 
+    pub fn subscribe_reorg_details(&self) -> Receiver<EventKind<T>> {
+        self.chain_reorg_detail_tx.subscribe()
+    }
+
+    pub fn subscribe_dependent_root_change(&self) -> Receiver<EventKind<T>> {
+        self.dependent_root_change_tx.subscribe()
+    }
+
     pub fn subscribe_contributions(&self) -> Receiver<EventKind<T>> {
         self.contribution_tx.subscribe()
     }
@@ -151,6 +194,18 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.block_reward_tx.subscribe()
     }
 
+    pub fn subscribe_builder_bid(&self) -> Receiver<EventKind<T>> {
+        self.builder_bid_tx.subscribe()
+    }
+
+    pub fn subscribe_payload_revealed(&self) -> Receiver<EventKind<T>> {
+        self.payload_revealed_tx.subscribe()
+    }
+
+    pub fn subscribe_payload_delivered(&self) -> Receiver<EventKind<T>> {
+        self.payload_delivered_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -175,6 +230,14 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         self.chain_reorg_tx.receiver_count() > 0
     }
 
+    pub fn has_reorg_detail_subscribers(&self) -> bool {
+        self.chain_reorg_detail_tx.receiver_count() > 0
+    }
+
+    pub fn has_dependent_root_change_subscribers(&self) -> bool {
+        self.dependent_root_change_tx.receiver_count() > 0
+    }
+
     pub fn has_contribution_subscribers(&self) -> bool {
         self.contribution_tx.receiver_count() > 0
     }
@@ -190,4 +253,16 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
     pub fn has_block_reward_subscribers(&self) -> bool {
         self.block_reward_tx.receiver_count() > 0
     }
+
+    pub fn has_builder_bid_subscribers(&self) -> bool {
+        self.builder_bid_tx.receiver_count() > 0
+    }
+
+    pub fn has_payload_revealed_subscribers(&self) -> bool {
+        self.payload_revealed_tx.receiver_count() > 0
+    }
+
+    pub fn has_payload_delivered_subscribers(&self) -> bool {
+        self.payload_delivered_tx.receiver_count() > 0
+    }
 }
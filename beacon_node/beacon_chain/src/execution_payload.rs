@@ -7,6 +7,7 @@
 //! So, this module contains functions that one might expect to find in other crates, but they live
 //! here for good reason.
 
+use crate::events::EventKind;
 use crate::otb_verification_service::OptimisticTransitionBlock;
 use crate::{
     BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockProductionError,
@@ -480,9 +481,10 @@ where
     T: BeaconChainTypes,
     Payload: AbstractExecPayload<T::EthSpec>,
 {
-    let current_epoch = builder_params.slot.epoch(T::EthSpec::slots_per_epoch());
+    let slot = builder_params.slot;
+    let current_epoch = slot.epoch(T::EthSpec::slots_per_epoch());
     let spec = &chain.spec;
-    let fork = spec.fork_name_at_slot::<T::EthSpec>(builder_params.slot);
+    let fork = spec.fork_name_at_slot::<T::EthSpec>(slot);
     let execution_layer = chain
         .execution_layer
         .as_ref()
@@ -554,5 +556,17 @@ where
         .await
         .map_err(BlockProductionError::GetPayloadFailed)?;
 
+    // Report any bid received from the builder during this request.
+    if let Some(event_handler) = chain.event_handler.as_ref() {
+        if event_handler.has_builder_bid_subscribers() {
+            if let Some(bid) = execution_layer
+                .get_builder_bid(slot)
+                .filter(|bid| bid.parent_hash == parent_hash)
+            {
+                event_handler.register(EventKind::BuilderBid(Box::new(bid)));
+            }
+        }
+    }
+
     Ok(block_contents)
 }
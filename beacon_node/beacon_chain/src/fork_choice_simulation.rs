@@ -0,0 +1,140 @@
+//! Runs a copy of fork choice with hypothetical blocks and attestations, to find out whether they
+//! would change the head without mutating the live fork choice.
+use crate::beacon_chain::{BeaconForkChoice, ForkChoiceError};
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, BeaconForkChoiceStore};
+pub use eth2::lighthouse::{ForkChoiceSimulation, ForkChoiceSimulationRequest};
+use fork_choice::{
+    AttestationFromBlock, ForkChoice, PayloadVerificationStatus, ResetPayloadStatuses,
+};
+use state_processing::per_block_processing::is_execution_enabled;
+use state_processing::{BlockReplayError, BlockReplayer};
+use std::time::Duration;
+use types::{Hash256, Slot};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The parent of the hypothetical block is unknown.
+    UnknownParent(Hash256),
+    /// The hypothetical block could not be applied to its parent state.
+    BlockProcessing(BlockReplayError),
+    /// Fork choice rejected the hypothetical block.
+    InvalidBlock(ForkChoiceError),
+    /// Fork choice rejected the attestation at `index`.
+    InvalidAttestation {
+        index: usize,
+        error: ForkChoiceError,
+    },
+    BeaconChainError(BeaconChainError),
+}
+
+impl From<BeaconChainError> for Error {
+    fn from(e: BeaconChainError) -> Self {
+        Error::BeaconChainError(e)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Apply the block and attestations of `request` to a copy of fork choice and report the head
+    /// before and after.
+    pub fn simulate_fork_choice(
+        &self,
+        request: &ForkChoiceSimulationRequest<T::EthSpec>,
+    ) -> Result<ForkChoiceSimulation, Error> {
+        let current_slot = self.slot()?;
+        let slot = request
+            .slot
+            .map_or(current_slot, |slot| slot.max(current_slot));
+
+        let mut fork_choice = self.fork_choice_copy()?;
+        let original_head_root = fork_choice
+            .get_head(slot, &self.spec)
+            .map_err(BeaconChainError::from)?;
+
+        if let Some(block) = &request.block {
+            let parent_block = self
+                .get_blinded_block(&block.parent_root())?
+                .ok_or_else(|| Error::UnknownParent(block.parent_root()))?;
+            let parent_state = self
+                .get_state(&parent_block.state_root(), Some(parent_block.slot()))?
+                .ok_or(BeaconChainError::MissingBeaconState(
+                    parent_block.state_root(),
+                ))?;
+
+            let mut state = BlockReplayer::<_, BlockReplayError, _>::new(parent_state, &self.spec)
+                .no_signature_verification()
+                .minimal_block_root_verification()
+                .apply_blocks(vec![block.clone_as_blinded()], None)
+                .map_err(Error::BlockProcessing)?
+                .into_state();
+            state
+                .build_all_committee_caches(&self.spec)
+                .map_err(BeaconChainError::from)?;
+
+            let payload_verification_status =
+                if is_execution_enabled(&state, block.message().body()) {
+                    PayloadVerificationStatus::Optimistic
+                } else {
+                    PayloadVerificationStatus::Irrelevant
+                };
+
+            fork_choice
+                .on_block(
+                    slot,
+                    block.message(),
+                    block.canonical_root(),
+                    Duration::from_millis(request.block_delay_ms),
+                    &state,
+                    payload_verification_status,
+                    self.config.progressive_balances_mode,
+                    &self.spec,
+                    &self.log,
+                )
+                .map_err(Error::InvalidBlock)?;
+        }
+
+        for (index, attestation) in request.attestations.iter().enumerate() {
+            fork_choice
+                .on_attestation(slot, attestation, AttestationFromBlock::False)
+                .map_err(|error| Error::InvalidAttestation { index, error })?;
+        }
+
+        let simulated_head_root = fork_choice
+            .get_head(slot, &self.spec)
+            .map_err(BeaconChainError::from)?;
+
+        let head_slot = |root: Hash256| -> Result<Slot, Error> {
+            Ok(fork_choice
+                .get_block(&root)
+                .ok_or(BeaconChainError::MissingBeaconBlock(root))?
+                .slot)
+        };
+
+        Ok(ForkChoiceSimulation {
+            slot,
+            original_head_root,
+            original_head_slot: head_slot(original_head_root)?,
+            simulated_head_root,
+            simulated_head_slot: head_slot(simulated_head_root)?,
+            head_changed: original_head_root != simulated_head_root,
+        })
+    }
+
+    /// Returns an independent copy of the live fork choice.
+    fn fork_choice_copy(&self) -> Result<BeaconForkChoice<T>, BeaconChainError> {
+        let fork_choice = self.canonical_head.fork_choice_read_lock();
+        let persisted_fork_choice = fork_choice.to_persisted();
+        let fc_store = BeaconForkChoiceStore::from_persisted(
+            fork_choice.fc_store().to_persisted(),
+            self.store.clone(),
+        )?;
+        drop(fork_choice);
+
+        Ok(ForkChoice::from_persisted(
+            persisted_fork_choice,
+            ResetPayloadStatuses::OnlyWithInvalidPayload,
+            fc_store,
+            &self.spec,
+            &self.log,
+        )?)
+    }
+}
@@ -7,6 +7,7 @@ mod beacon_chain;
 mod beacon_fork_choice_store;
 pub mod beacon_proposer_cache;
 mod beacon_snapshot;
+mod block_provenance;
 pub mod block_reward;
 mod block_times_cache;
 mod block_verification;
@@ -14,6 +15,7 @@ pub mod builder;
 pub mod canonical_head;
 pub mod capella_readiness;
 pub mod chain_config;
+mod consecutive_re_orgs;
 mod early_attester_cache;
 mod errors;
 pub mod eth1_chain;
@@ -21,11 +23,13 @@ mod eth1_finalization_cache;
 pub mod events;
 pub mod execution_payload;
 pub mod fork_choice_signal;
+pub mod fork_choice_simulation;
 pub mod fork_revert;
 mod head_tracker;
 pub mod historical_blocks;
 pub mod light_client_finality_update_verification;
 pub mod light_client_optimistic_update_verification;
+pub mod light_client_server_cache;
 pub mod merge_readiness;
 pub mod metrics;
 pub mod migrate;
@@ -34,6 +38,7 @@ mod observed_aggregates;
 mod observed_attesters;
 pub mod observed_block_producers;
 pub mod observed_operations;
+pub mod orphaned_operations;
 pub mod otb_verification_service;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
@@ -43,6 +48,7 @@ pub mod schema_change;
 pub mod shuffling_cache;
 mod snapshot_cache;
 pub mod state_advance_timer;
+pub mod state_root_audit;
 pub mod sync_committee_rewards;
 pub mod sync_committee_verification;
 pub mod test_utils;
@@ -62,6 +68,7 @@ pub use self::errors::{BeaconChainError, BlockProductionError};
 pub use self::historical_blocks::HistoricalBlockError;
 pub use attestation_verification::Error as AttestationError;
 pub use beacon_fork_choice_store::{BeaconForkChoiceStore, Error as ForkChoiceStoreError};
+pub use block_provenance::{BlockSource, SegmentSource};
 pub use block_verification::{
     get_block_root, BlockError, ExecutionPayloadError, GossipVerifiedBlock,
     IntoExecutionPendingBlock, IntoGossipVerifiedBlock,
@@ -0,0 +1,352 @@
+//! Caches the data required to serve light clients.
+//!
+//! The proofs a light client needs are taken from the post-state of each imported block. When a
+//! child block carrying a sync aggregate over that block is imported, the light client updates are
+//! built from the cached proofs, without loading any states from the database.
+use crate::errors::BeaconChainError;
+use crate::{metrics, BeaconChain, BeaconChainTypes, BeaconStore};
+use lru::LruCache;
+use parking_lot::{Mutex, RwLock};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, FINALIZED_ROOT_INDEX,
+    NEXT_SYNC_COMMITTEE_INDEX,
+};
+use types::{
+    BeaconBlockHeader, BeaconBlockRef, BeaconState, ChainSpec, EthSpec, FixedVector, Hash256,
+    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, Slot, SyncAggregate,
+    SyncCommittee,
+};
+
+/// The number of recent blocks for which light client data is kept, ready for the import of their
+/// children.
+const PREV_BLOCK_CACHE_SIZE: usize = 32;
+
+/// The maximum number of sync committee periods for which the best update is kept. This is also
+/// the maximum number of updates which may be requested at once.
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// The light client data of a block, taken from its post-state.
+#[derive(Clone)]
+struct LightClientCachedData<E: EthSpec> {
+    header: BeaconBlockHeader,
+    finalized_block_root: Hash256,
+    finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
+    next_sync_committee: Arc<SyncCommittee<E>>,
+    next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+}
+
+/// Keeps the latest light client updates, and the best update of each sync committee period.
+pub struct LightClientServerCache<T: BeaconChainTypes> {
+    latest_finality_update: RwLock<Option<LightClientFinalityUpdate<T::EthSpec>>>,
+    latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<T::EthSpec>>>,
+    /// The best `LightClientUpdate` of each sync committee period, keyed by period.
+    best_updates: RwLock<BTreeMap<u64, LightClientUpdate<T::EthSpec>>>,
+    prev_block_cache: Mutex<LruCache<Hash256, LightClientCachedData<T::EthSpec>>>,
+    /// The header of the most recently used finalized block, to avoid reading it from the
+    /// database for each update.
+    finalized_header: Mutex<Option<(Hash256, BeaconBlockHeader)>>,
+}
+
+impl<T: BeaconChainTypes> Default for LightClientServerCache<T> {
+    fn default() -> Self {
+        Self {
+            latest_finality_update: <_>::default(),
+            latest_optimistic_update: <_>::default(),
+            best_updates: <_>::default(),
+            prev_block_cache: Mutex::new(LruCache::new(PREV_BLOCK_CACHE_SIZE)),
+            finalized_header: <_>::default(),
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> LightClientServerCache<T> {
+    /// Cache the light client data of `block`, taken from its post-state.
+    ///
+    /// Blocks from before Altair have no sync committees and are ignored.
+    pub fn cache_state_data(
+        &self,
+        block: BeaconBlockRef<T::EthSpec>,
+        block_root: Hash256,
+        block_post_state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<(), BeaconChainError> {
+        let next_sync_committee = match block_post_state.next_sync_committee() {
+            Ok(committee) => committee.clone(),
+            Err(_) => return Ok(()),
+        };
+
+        let data = LightClientCachedData {
+            header: block.block_header(),
+            finalized_block_root: block_post_state.finalized_checkpoint().root,
+            finality_branch: FixedVector::new(
+                block_post_state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?,
+            )?,
+            next_sync_committee,
+            next_sync_committee_branch: FixedVector::new(
+                block_post_state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?,
+            )?,
+        };
+        self.prev_block_cache.lock().put(block_root, data);
+
+        Ok(())
+    }
+
+    /// Update the cached light client updates following the import of a block at `signature_slot`
+    /// with `sync_aggregate` over its parent, `attested_block_root`.
+    pub fn recompute_and_cache_updates(
+        &self,
+        store: &BeaconStore<T>,
+        attested_block_root: Hash256,
+        signature_slot: Slot,
+        sync_aggregate: &SyncAggregate<T::EthSpec>,
+        spec: &ChainSpec,
+    ) -> Result<(), BeaconChainError> {
+        let _timer = metrics::start_timer(&metrics::LIGHT_CLIENT_SERVER_CACHE_RECOMPUTE_TIMES);
+
+        if sync_aggregate.num_set_bits() < spec.min_sync_committee_participants as usize {
+            return Ok(());
+        }
+
+        // The attested block must have been imported recently for its data to be cached.
+        let attested = match self.prev_block_cache.lock().get(&attested_block_root) {
+            Some(data) => data.clone(),
+            None => return Ok(()),
+        };
+        let attested_slot = attested.header.slot;
+
+        let is_newer_optimistic_update =
+            self.latest_optimistic_update
+                .read()
+                .as_ref()
+                .map_or(true, |latest| {
+                    (attested_slot, signature_slot)
+                        > (latest.attested_header.slot, latest.signature_slot)
+                });
+        if is_newer_optimistic_update {
+            *self.latest_optimistic_update.write() = Some(LightClientOptimisticUpdate {
+                attested_header: attested.header.clone(),
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            });
+        }
+
+        let finalized_header = self.finalized_header(store, attested.finalized_block_root)?;
+
+        if let Some(finalized_header) = &finalized_header {
+            let is_newer_finality_update =
+                self.latest_finality_update
+                    .read()
+                    .as_ref()
+                    .map_or(true, |latest| {
+                        (attested_slot, signature_slot)
+                            > (latest.attested_header.slot, latest.signature_slot)
+                    });
+            if is_newer_finality_update {
+                *self.latest_finality_update.write() = Some(LightClientFinalityUpdate {
+                    attested_header: attested.header.clone(),
+                    finalized_header: finalized_header.clone(),
+                    finality_branch: attested.finality_branch.clone(),
+                    sync_aggregate: sync_aggregate.clone(),
+                    signature_slot,
+                });
+            }
+        }
+
+        // Only updates whose sync aggregate was signed by the attested block's sync committee can
+        // be used to sync between periods.
+        let attested_period = sync_committee_period::<T::EthSpec>(attested_slot, spec)?;
+        if attested_period != sync_committee_period::<T::EthSpec>(signature_slot, spec)? {
+            return Ok(());
+        }
+
+        // An update without a finalized header carries an empty finality branch, as the branch of
+        // the attested state proves the zero root rather than a header.
+        let (finalized_header, finality_branch) = match finalized_header {
+            Some(header) => (header, attested.finality_branch),
+            None => (empty_header(), FixedVector::from_elem(Hash256::zero())),
+        };
+        let update = LightClientUpdate {
+            attested_header: attested.header,
+            next_sync_committee: attested.next_sync_committee,
+            next_sync_committee_branch: attested.next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            sync_aggregate: sync_aggregate.clone(),
+            signature_slot,
+        };
+
+        let mut best_updates = self.best_updates.write();
+        let is_better_update = best_updates
+            .get(&attested_period)
+            .map_or(true, |best| is_better_update(&update, best, spec));
+        if is_better_update {
+            best_updates.insert(attested_period, update);
+            while best_updates.len() > MAX_REQUEST_LIGHT_CLIENT_UPDATES as usize {
+                best_updates.pop_first();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the header of the block at `finalized_block_root`, or `None` if nothing has been
+    /// finalized yet.
+    fn finalized_header(
+        &self,
+        store: &BeaconStore<T>,
+        finalized_block_root: Hash256,
+    ) -> Result<Option<BeaconBlockHeader>, BeaconChainError> {
+        if finalized_block_root == Hash256::zero() {
+            return Ok(None);
+        }
+
+        let mut cached = self.finalized_header.lock();
+        if let Some((root, header)) = cached.as_ref() {
+            if *root == finalized_block_root {
+                return Ok(Some(header.clone()));
+            }
+        }
+
+        let header = store
+            .get_blinded_block(&finalized_block_root)?
+            .map(|block| block.message().block_header());
+        if let Some(header) = &header {
+            *cached = Some((finalized_block_root, header.clone()));
+        }
+        Ok(header)
+    }
+
+    pub fn get_latest_finality_update(&self) -> Option<LightClientFinalityUpdate<T::EthSpec>> {
+        self.latest_finality_update.read().clone()
+    }
+
+    pub fn get_latest_optimistic_update(&self) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
+        self.latest_optimistic_update.read().clone()
+    }
+
+    /// Returns the best update of each of the `count` sync committee periods from `start_period`
+    /// for which an update is known.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Vec<LightClientUpdate<T::EthSpec>> {
+        let count = std::cmp::min(count, MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+        let end_period = start_period.saturating_add(count);
+        self.best_updates
+            .read()
+            .range(start_period..end_period)
+            .map(|(_, update)| update.clone())
+            .collect()
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns the latest `LightClientFinalityUpdate` if it should be published on gossip at
+    /// `current_slot`, and records it as seen.
+    ///
+    /// An update is only published if it was signed during `current_slot` and finalizes a later
+    /// block than any update already seen on gossip.
+    pub fn light_client_finality_update_to_publish(
+        &self,
+        current_slot: Slot,
+    ) -> Option<LightClientFinalityUpdate<T::EthSpec>> {
+        let update = self
+            .light_client_server_cache
+            .get_latest_finality_update()?;
+        if update.signature_slot != current_slot {
+            return None;
+        }
+
+        let mut latest_seen = self.latest_seen_finality_update.lock();
+        if latest_seen.as_ref().map_or(false, |seen| {
+            update.finalized_header.slot <= seen.finalized_header.slot
+        }) {
+            return None;
+        }
+        *latest_seen = Some(update.clone());
+
+        Some(update)
+    }
+
+    /// Returns the latest `LightClientOptimisticUpdate` if it should be published on gossip at
+    /// `current_slot`, and records it as seen.
+    ///
+    /// An update is only published if it was signed during `current_slot` and attests to a later
+    /// block than any update already seen on gossip.
+    pub fn light_client_optimistic_update_to_publish(
+        &self,
+        current_slot: Slot,
+    ) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
+        let update = self
+            .light_client_server_cache
+            .get_latest_optimistic_update()?;
+        if update.signature_slot != current_slot {
+            return None;
+        }
+
+        let mut latest_seen = self.latest_seen_optimistic_update.lock();
+        if latest_seen.as_ref().map_or(false, |seen| {
+            update.attested_header.slot <= seen.attested_header.slot
+        }) {
+            return None;
+        }
+        *latest_seen = Some(update.clone());
+
+        Some(update)
+    }
+}
+
+fn sync_committee_period<E: EthSpec>(
+    slot: Slot,
+    spec: &ChainSpec,
+) -> Result<u64, BeaconChainError> {
+    Ok(slot
+        .epoch(E::slots_per_epoch())
+        .sync_committee_period(spec)?)
+}
+
+/// The finalized header of an update which doesn't prove finality.
+fn empty_header() -> BeaconBlockHeader {
+    BeaconBlockHeader {
+        slot: Slot::new(0),
+        proposer_index: 0,
+        parent_root: Hash256::zero(),
+        state_root: Hash256::zero(),
+        body_root: Hash256::zero(),
+    }
+}
+
+/// Returns `true` if `new` is a better update than `old` for the same sync committee period.
+///
+/// This follows `is_better_update` from the light client specification: a supermajority of
+/// participants is preferred, then an update which proves finality, then more participants, and
+/// finally an older update.
+fn is_better_update<E: EthSpec>(
+    new: &LightClientUpdate<E>,
+    old: &LightClientUpdate<E>,
+    spec: &ChainSpec,
+) -> bool {
+    let committee_size = E::sync_committee_size();
+    let rank = |update: &LightClientUpdate<E>| {
+        let participants = update.sync_aggregate.num_set_bits();
+        let has_supermajority = participants * 3 >= committee_size * 2;
+        let has_finality = update.finalized_header != empty_header();
+        let has_sync_committee_finality = has_finality
+            && sync_committee_period::<E>(update.finalized_header.slot, spec).ok()
+                == sync_committee_period::<E>(update.attested_header.slot, spec).ok();
+        (
+            has_supermajority,
+            // Without a supermajority, participation matters more than finality.
+            if has_supermajority { 0 } else { participants },
+            has_finality,
+            has_sync_committee_finality,
+            participants,
+            // Older updates are better.
+            std::cmp::Reverse(update.attested_header.slot),
+            std::cmp::Reverse(update.signature_slot),
+        )
+    };
+    rank(new) > rank(old)
+}
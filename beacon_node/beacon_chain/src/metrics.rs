@@ -273,6 +273,12 @@ lazy_static! {
         try_create_int_counter("beacon_shuffling_cache_promise_hits_total", "Count of times shuffling cache returns a promise to future shuffling");
     pub static ref SHUFFLING_CACHE_PROMISE_FAILS: Result<IntCounter> =
         try_create_int_counter("beacon_shuffling_cache_promise_fails_total", "Count of times shuffling cache detects a failed promise");
+    pub static ref SHUFFLING_CACHE_SIZE: Result<IntGauge> =
+        try_create_int_gauge("beacon_shuffling_cache_size", "Number of shufflings and promises in the shuffling cache");
+    pub static ref SHUFFLING_CACHE_MEMORY_BYTES: Result<IntGauge> =
+        try_create_int_gauge("beacon_shuffling_cache_memory_bytes", "Estimated memory used by the shufflings in the shuffling cache");
+    pub static ref SHUFFLING_CACHE_EVICTIONS: Result<IntCounter> =
+        try_create_int_counter("beacon_shuffling_cache_evictions_total", "Count of shufflings evicted from the shuffling cache to respect its limits");
 
     /*
      * Early attester cache
@@ -435,6 +441,18 @@ lazy_static! {
     pub static ref HEAD_STATE_ETH1_DEPOSITS_INTEROP: Result<IntGauge> =
         try_create_int_gauge("beacon_processed_deposits_total", "Total Eth1 deposits at the head of the chain");
 
+    /*
+     * State Root Audit
+     */
+    pub static ref STATE_ROOT_AUDIT_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_state_root_audit_seconds",
+        "Time taken to hash the head state from scratch during a state root audit"
+    );
+    pub static ref STATE_ROOT_AUDIT_MISMATCHES: Result<IntCounter> = try_create_int_counter(
+        "beacon_state_root_audit_mismatches_total",
+        "Count of state root audits where the cached tree hash root differed from a fresh hash"
+    );
+
     /*
      * Operation Pool
      */
@@ -452,6 +470,12 @@ lazy_static! {
         try_create_int_gauge("beacon_op_pool_voluntary_exits_total", "Count of voluntary exits in the op pool");
     pub static ref OP_POOL_NUM_SYNC_CONTRIBUTIONS: Result<IntGauge> =
         try_create_int_gauge("beacon_op_pool_sync_contributions_total", "Count of sync contributions in the op pool");
+    pub static ref OP_POOL_REORG_REINCLUDED_OPERATIONS: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_op_pool_reorg_reincluded_operations_total",
+            "Count of operations from orphaned blocks returned to the op pool after a re-org",
+            &["type"]
+        );
 
 
     /*
@@ -999,6 +1023,13 @@ lazy_static! {
         "Number of light client optimistic updates verified for gossip"
     );
     /*
+    * Light server data cache
+    */
+    pub static ref LIGHT_CLIENT_SERVER_CACHE_RECOMPUTE_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_light_client_server_cache_recompute_updates_time_seconds",
+        "Time taken to recompute the light client updates after importing a block"
+    );
+    /*
     * Aggregate subset metrics
      */
     pub static ref SYNC_CONTRIBUTION_SUBSETS: Result<IntCounter> = try_create_int_counter(
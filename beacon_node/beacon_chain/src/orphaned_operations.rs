@@ -0,0 +1,206 @@
+//! Returns operations from blocks orphaned by a re-org to the operation pool.
+//!
+//! Operations included in an orphaned block are no longer on the canonical chain, however they may
+//! have only ever been seen in that block. Returning them to the op pool allows them to be packed
+//! into a future local block, provided they are still valid.
+//!
+//! Orphaned blocks remain in the database until they are pruned, so the blocks themselves provide
+//! the set of operations that each one included.
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes, BeaconSnapshot};
+use operation_pool::ReceivedPreCapella;
+use slog::{debug, warn};
+use state_processing::common::get_attesting_indices_from_state;
+use state_processing::VerifyOperation;
+use std::sync::Arc;
+use types::beacon_state::CloneConfig;
+use types::{Hash256, RelativeEpoch};
+
+/// The maximum number of orphaned blocks to recover operations from after a single re-org.
+///
+/// Deeper re-orgs are rare and are likely to have orphaned operations which are too old to be
+/// included in a block anyway.
+pub const MAX_ORPHANED_BLOCKS: usize = 32;
+
+/// The number of each type of operation returned to the op pool.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ReincludedOperations {
+    pub attestations: usize,
+    pub proposer_slashings: usize,
+    pub attester_slashings: usize,
+    pub voluntary_exits: usize,
+    pub bls_to_execution_changes: usize,
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Spawns a task which returns the operations from blocks between `old_head_root` and the
+    /// common ancestor with `new_head` to the op pool.
+    pub(crate) fn spawn_reinclude_orphaned_operations(
+        self: &Arc<Self>,
+        old_head_root: Hash256,
+        new_head: Arc<BeaconSnapshot<T::EthSpec>>,
+    ) {
+        // If there's no eth1 chain then it's impossible to produce blocks and therefore useless to
+        // put things in the op pool.
+        if self.eth1_chain.is_none() {
+            return;
+        }
+
+        let chain = self.clone();
+        self.task_executor.spawn_blocking(
+            move || match chain.reinclude_orphaned_operations(old_head_root, &new_head) {
+                Ok(reincluded) => debug!(
+                    chain.log,
+                    "Re-included orphaned operations";
+                    "old_head_root" => ?old_head_root,
+                    "new_head_root" => ?new_head.beacon_block_root,
+                    "attestations" => reincluded.attestations,
+                    "proposer_slashings" => reincluded.proposer_slashings,
+                    "attester_slashings" => reincluded.attester_slashings,
+                    "voluntary_exits" => reincluded.voluntary_exits,
+                    "bls_to_execution_changes" => reincluded.bls_to_execution_changes,
+                ),
+                Err(e) => warn!(
+                    chain.log,
+                    "Failed to re-include orphaned operations";
+                    "error" => ?e,
+                    "old_head_root" => ?old_head_root,
+                ),
+            },
+            "reinclude_orphaned_operations",
+        );
+    }
+
+    /// Returns the roots of the blocks which are ancestors of `old_head_root` (inclusive) but not
+    /// of `new_head_root`, most recent first.
+    ///
+    /// At most `MAX_ORPHANED_BLOCKS` roots are returned.
+    pub fn orphaned_block_roots(
+        &self,
+        old_head_root: Hash256,
+        new_head_root: Hash256,
+    ) -> Vec<Hash256> {
+        let fork_choice = self.canonical_head.fork_choice_read_lock();
+        let proto_array = fork_choice.proto_array();
+
+        let mut orphaned = vec![];
+        let mut block_root = old_head_root;
+        while orphaned.len() < MAX_ORPHANED_BLOCKS
+            && !proto_array.is_descendant(block_root, new_head_root)
+        {
+            match fork_choice
+                .get_block(&block_root)
+                .and_then(|block| block.parent_root)
+            {
+                Some(parent_root) => {
+                    orphaned.push(block_root);
+                    block_root = parent_root;
+                }
+                None => break,
+            }
+        }
+        orphaned
+    }
+
+    /// Returns the operations from blocks between `old_head_root` and the common ancestor with
+    /// `new_head` to the op pool, if they are still valid for inclusion on top of `new_head`.
+    pub fn reinclude_orphaned_operations(
+        &self,
+        old_head_root: Hash256,
+        new_head: &BeaconSnapshot<T::EthSpec>,
+    ) -> Result<ReincludedOperations, BeaconChainError> {
+        let orphaned_roots = self.orphaned_block_roots(old_head_root, new_head.beacon_block_root);
+        if orphaned_roots.is_empty() {
+            return Ok(ReincludedOperations::default());
+        }
+
+        let mut state = new_head
+            .beacon_state
+            .clone_with(CloneConfig::committee_caches_only());
+        state.build_committee_cache(RelativeEpoch::Previous, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+        let previous_epoch = state.previous_epoch();
+
+        let mut reincluded = ReincludedOperations::default();
+        for block_root in orphaned_roots {
+            let block = match self.get_blinded_block(&block_root)? {
+                Some(block) => block,
+                None => continue,
+            };
+            let body = block.message().body();
+
+            for attestation in body.attestations() {
+                // Attestations from before the previous epoch can no longer be included.
+                if attestation.data.target.epoch < previous_epoch {
+                    continue;
+                }
+                let attesting_indices = match get_attesting_indices_from_state(&state, attestation)
+                {
+                    Ok(attesting_indices) => attesting_indices,
+                    Err(_) => continue,
+                };
+                if self
+                    .op_pool
+                    .insert_attestation(attestation.clone(), attesting_indices)
+                    .is_ok()
+                {
+                    reincluded.attestations += 1;
+                }
+            }
+
+            for proposer_slashing in body.proposer_slashings() {
+                if let Ok(verified) = proposer_slashing.clone().validate(&state, &self.spec) {
+                    self.op_pool.insert_proposer_slashing(verified);
+                    reincluded.proposer_slashings += 1;
+                }
+            }
+
+            // Attester slashings were applied to fork choice when the block was imported, so they
+            // only need to be returned to the op pool.
+            for attester_slashing in body.attester_slashings() {
+                if let Ok(verified) = attester_slashing.clone().validate(&state, &self.spec) {
+                    self.op_pool.insert_attester_slashing(verified);
+                    reincluded.attester_slashings += 1;
+                }
+            }
+
+            for exit in body.voluntary_exits() {
+                if let Ok(verified) = exit.clone().validate(&state, &self.spec) {
+                    self.op_pool.insert_voluntary_exit(verified);
+                    reincluded.voluntary_exits += 1;
+                }
+            }
+
+            if let Ok(bls_to_execution_changes) = body.bls_to_execution_changes() {
+                for change in bls_to_execution_changes {
+                    if let Ok(verified) = change.clone().validate(&state, &self.spec) {
+                        if self
+                            .op_pool
+                            .insert_bls_to_execution_change(verified, ReceivedPreCapella::No)
+                        {
+                            reincluded.bls_to_execution_changes += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        for (label, count) in [
+            ("attestation", reincluded.attestations),
+            ("proposer_slashing", reincluded.proposer_slashings),
+            ("attester_slashing", reincluded.attester_slashings),
+            ("voluntary_exit", reincluded.voluntary_exits),
+            (
+                "bls_to_execution_change",
+                reincluded.bls_to_execution_changes,
+            ),
+        ] {
+            metrics::inc_counter_vec_by(
+                &metrics::OP_POOL_REORG_REINCLUDED_OPERATIONS,
+                &[label],
+                count as u64,
+            );
+        }
+
+        Ok(reincluded)
+    }
+}
@@ -11,9 +11,10 @@ use crate::eth1_chain::SszEth1;
 use crate::types::ChainSpec;
 use slog::{warn, Logger};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use store::hot_cold_store::{HotColdDB, HotColdDBError};
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION};
-use store::{Error as StoreError, StoreItem};
+use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, MIN_SUPPORTED_SCHEMA_VERSION};
+use store::{Error as StoreError, KeyValueStoreOp, StoreItem};
 
 /// Migrate the database from one schema version to another, applying all requisite mutations.
 #[allow(clippy::only_used_in_recursion)] // spec is not used but likely to be used in future
@@ -55,19 +56,124 @@ pub fn migrate_schema<T: BeaconChainTypes>(
             migrate_schema::<T>(db, deposit_contract_deploy_block, next, to, log, spec)
         }
 
+        // Apply a single step.
+        (_, _) => {
+            let ops = migration_ops::<T>(db.clone(), deposit_contract_deploy_block, from, to, log)?;
+            db.store_schema_version_atomically(to, ops)
+        }
+    }
+}
+
+/// A single step of a schema migration, as reported by `plan_migration`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationStep {
+    pub from: SchemaVersion,
+    pub to: SchemaVersion,
+    /// The changes the step would make, if they were computed.
+    pub changes: Option<MigrationChanges>,
+}
+
+/// The changes made by a single migration step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationChanges {
+    pub keys_written: usize,
+    pub bytes_written: u64,
+    pub keys_deleted: usize,
+    /// The time taken to compute the changes. Writing them is a single atomic batch, so this is
+    /// an estimate of the duration of the step.
+    pub duration: Duration,
+}
+
+/// Report the steps of a migration from one schema version to another, without changing the
+/// database.
+///
+/// Every step reads the database as left by the previous step, so only the changes of the first
+/// step can be computed without applying it. The remaining steps are checked to be supported.
+pub fn plan_migration<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    deposit_contract_deploy_block: u64,
+    from: SchemaVersion,
+    to: SchemaVersion,
+    log: Logger,
+) -> Result<Vec<MigrationStep>, StoreError> {
+    let supported = MIN_SUPPORTED_SCHEMA_VERSION.as_u64()..=CURRENT_SCHEMA_VERSION.as_u64();
+    if !supported.contains(&from.as_u64()) || !supported.contains(&to.as_u64()) {
+        return Err(HotColdDBError::UnsupportedSchemaVersion {
+            target_version: to,
+            current_version: from,
+        }
+        .into());
+    }
+
+    let versions: Vec<u64> = if from <= to {
+        (from.as_u64()..=to.as_u64()).collect()
+    } else {
+        (to.as_u64()..=from.as_u64()).rev().collect()
+    };
+
+    versions
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let (step_from, step_to) = (SchemaVersion(pair[0]), SchemaVersion(pair[1]));
+            let changes = if i == 0 {
+                let timer = Instant::now();
+                let ops = migration_ops::<T>(
+                    db.clone(),
+                    deposit_contract_deploy_block,
+                    step_from,
+                    step_to,
+                    log.clone(),
+                )?;
+                let (mut keys_written, mut bytes_written, mut keys_deleted) = (0, 0, 0);
+                for op in &ops {
+                    match op {
+                        KeyValueStoreOp::PutKeyValue(_, value) => {
+                            keys_written += 1;
+                            bytes_written += value.len() as u64;
+                        }
+                        KeyValueStoreOp::DeleteKey(_) => keys_deleted += 1,
+                    }
+                }
+                Some(MigrationChanges {
+                    keys_written,
+                    bytes_written,
+                    keys_deleted,
+                    duration: timer.elapsed(),
+                })
+            } else {
+                None
+            };
+            Ok(MigrationStep {
+                from: step_from,
+                to: step_to,
+                changes,
+            })
+        })
+        .collect()
+}
+
+/// Compute the changes made by migrating the database a single step from one schema version to
+/// the next (or previous), without applying them.
+fn migration_ops<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    deposit_contract_deploy_block: u64,
+    from: SchemaVersion,
+    to: SchemaVersion,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, StoreError> {
+    match (from, to) {
         //
         // Migrations from before SchemaVersion(11) are deprecated.
         //
 
         // Upgrade from v11 to v12 to store richer metadata in the attestation op pool.
         (SchemaVersion(11), SchemaVersion(12)) => {
-            let ops = migration_schema_v12::upgrade_to_v12::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v12::upgrade_to_v12::<T>(db, log)
         }
         // Downgrade from v12 to v11 to drop richer metadata from the attestation op pool.
         (SchemaVersion(12), SchemaVersion(11)) => {
-            let ops = migration_schema_v12::downgrade_from_v12::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v12::downgrade_from_v12::<T>(db, log)
         }
         (SchemaVersion(12), SchemaVersion(13)) => {
             let mut ops = vec![];
@@ -86,9 +192,7 @@ pub fn migrate_schema<T: BeaconChainTypes>(
                 ops.push(upgraded_eth1_cache.as_kv_store_op(ETH1_CACHE_DB_KEY));
             }
 
-            db.store_schema_version_atomically(to, ops)?;
-
-            Ok(())
+            Ok(ops)
         }
         (SchemaVersion(13), SchemaVersion(12)) => {
             let mut ops = vec![];
@@ -114,41 +218,31 @@ pub fn migrate_schema<T: BeaconChainTypes>(
                 ops.push(downgraded_eth1_cache.as_kv_store_op(ETH1_CACHE_DB_KEY));
             }
 
-            db.store_schema_version_atomically(to, ops)?;
-
-            Ok(())
+            Ok(ops)
         }
         (SchemaVersion(13), SchemaVersion(14)) => {
-            let ops = migration_schema_v14::upgrade_to_v14::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v14::upgrade_to_v14::<T>(db, log)
         }
         (SchemaVersion(14), SchemaVersion(13)) => {
-            let ops = migration_schema_v14::downgrade_from_v14::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v14::downgrade_from_v14::<T>(db, log)
         }
         (SchemaVersion(14), SchemaVersion(15)) => {
-            let ops = migration_schema_v15::upgrade_to_v15::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v15::upgrade_to_v15::<T>(db, log)
         }
         (SchemaVersion(15), SchemaVersion(14)) => {
-            let ops = migration_schema_v15::downgrade_from_v15::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v15::downgrade_from_v15::<T>(db, log)
         }
         (SchemaVersion(15), SchemaVersion(16)) => {
-            let ops = migration_schema_v16::upgrade_to_v16::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v16::upgrade_to_v16::<T>(db, log)
         }
         (SchemaVersion(16), SchemaVersion(15)) => {
-            let ops = migration_schema_v16::downgrade_from_v16::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v16::downgrade_from_v16::<T>(db, log)
         }
         (SchemaVersion(16), SchemaVersion(17)) => {
-            let ops = migration_schema_v17::upgrade_to_v17::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v17::upgrade_to_v17::<T>(db, log)
         }
         (SchemaVersion(17), SchemaVersion(16)) => {
-            let ops = migration_schema_v17::downgrade_from_v17::<T>(db.clone(), log)?;
-            db.store_schema_version_atomically(to, ops)
+            migration_schema_v17::downgrade_from_v17::<T>(db, log)
         }
         // Anything else is an error.
         (_, _) => Err(HotColdDBError::UnsupportedSchemaVersion {
@@ -43,6 +43,14 @@ impl CacheItem {
         matches!(self, CacheItem::Promise(_))
     }
 
+    /// Returns the memory used by the committee, or zero for a promise.
+    fn memory_size(&self) -> usize {
+        match self {
+            CacheItem::Committee(committee) => committee.memory_size(),
+            CacheItem::Promise(_) => 0,
+        }
+    }
+
     pub fn wait(self) -> Result<Arc<CommitteeCache>, BeaconChainError> {
         match self {
             CacheItem::Committee(cache) => Ok(cache),
@@ -53,29 +61,55 @@ impl CacheItem {
     }
 }
 
+struct CacheEntry {
+    item: CacheItem,
+    /// The value of `ShufflingCache::clock` when the entry was last inserted or retrieved.
+    last_used: u64,
+}
+
 /// Provides a cache for `CommitteeCache`.
 ///
+/// Shufflings are keyed by their epoch and decision block, so a single entry is shared by all the
+/// fork choice branches which descend from the decision block.
+///
 /// It has been named `ShufflingCache` because `CommitteeCacheCache` is a bit weird and looks like
 /// a find/replace error.
 pub struct ShufflingCache {
-    cache: HashMap<AttestationShufflingId, CacheItem>,
+    cache: HashMap<AttestationShufflingId, CacheEntry>,
     cache_size: usize,
+    /// The maximum number of bytes used by the committees in the cache, if any.
+    memory_budget: Option<usize>,
+    /// Incremented on each access, to find the least-recently used entries.
+    clock: u64,
     head_shuffling_ids: BlockShufflingIds,
     logger: Logger,
 }
 
 impl ShufflingCache {
-    pub fn new(cache_size: usize, head_shuffling_ids: BlockShufflingIds, logger: Logger) -> Self {
+    pub fn new(
+        cache_size: usize,
+        memory_budget: Option<usize>,
+        head_shuffling_ids: BlockShufflingIds,
+        logger: Logger,
+    ) -> Self {
         Self {
             cache: HashMap::new(),
             cache_size,
+            memory_budget,
+            clock: 0,
             head_shuffling_ids,
             logger,
         }
     }
 
     pub fn get(&mut self, key: &AttestationShufflingId) -> Option<CacheItem> {
-        match self.cache.get(key) {
+        self.clock += 1;
+        let clock = self.clock;
+        if let Some(entry) = self.cache.get_mut(key) {
+            entry.last_used = clock;
+        }
+
+        match self.cache.get(key).map(|entry| &entry.item) {
             // The cache contained the committee cache, return it.
             item @ Some(CacheItem::Committee(_)) => {
                 metrics::inc_counter(&metrics::SHUFFLING_CACHE_HITS);
@@ -113,6 +147,7 @@ impl ShufflingCache {
                     metrics::inc_counter(&metrics::SHUFFLING_CACHE_PROMISE_FAILS);
                     metrics::inc_counter(&metrics::SHUFFLING_CACHE_MISSES);
                     self.cache.remove(key);
+                    self.update_metrics();
                     None
                 }
             },
@@ -138,7 +173,7 @@ impl ShufflingCache {
             .get(&key)
             // Replace the committee if it's not present or if it's a promise. A bird in the hand is
             // worth two in the promise-bush!
-            .map_or(true, CacheItem::is_promise)
+            .map_or(true, |entry| entry.item.is_promise())
         {
             self.insert_cache_item(
                 key,
@@ -149,46 +184,101 @@ impl ShufflingCache {
 
     /// Prunes the cache first before inserting a new cache item.
     fn insert_cache_item(&mut self, key: AttestationShufflingId, cache_item: CacheItem) {
-        self.prune_cache();
-        self.cache.insert(key, cache_item);
+        // Don't count an entry which is about to be replaced against the limits.
+        self.cache.remove(&key);
+        self.prune_cache(cache_item.memory_size());
+        self.clock += 1;
+        self.cache.insert(
+            key,
+            CacheEntry {
+                item: cache_item,
+                last_used: self.clock,
+            },
+        );
+        self.update_metrics();
+    }
+
+    /// Returns the number of bytes used by the committees in the cache.
+    pub fn memory_usage(&self) -> usize {
+        self.cache
+            .values()
+            .map(|entry| entry.item.memory_size())
+            .sum()
     }
 
-    /// Prunes the `cache` to keep the size below the `cache_size` limit, based on the following
-    /// preferences:
-    /// - Entries from more recent epochs are preferred over older ones.
+    /// Prunes the `cache` to make room for a new entry of `incoming_size` bytes without exceeding
+    /// the `cache_size` or `memory_budget` limits, based on the following preferences:
+    /// - The least-recently used entries are pruned first.
     /// - Entries with shuffling ids matching the head's previous, current, and future epochs must
     ///   not be pruned.
-    fn prune_cache(&mut self) {
+    /// - Promises use no memory, so they are only pruned to respect the `cache_size` limit.
+    ///
+    /// The limits may be exceeded if the head's shufflings alone exceed them.
+    fn prune_cache(&mut self, incoming_size: usize) {
         let target_cache_size = self.cache_size.saturating_sub(1);
-        if let Some(prune_count) = self.cache.len().checked_sub(target_cache_size) {
-            let shuffling_ids_to_prune = self
-                .cache
-                .keys()
-                .sorted_by_key(|key| key.shuffling_epoch)
-                .filter(|shuffling_id| {
-                    Some(shuffling_id)
-                        != self
-                            .head_shuffling_ids
-                            .id_for_epoch(shuffling_id.shuffling_epoch)
-                            .as_ref()
-                            .as_ref()
-                })
-                .take(prune_count)
-                .cloned()
-                .collect::<Vec<_>>();
-
-            for shuffling_id in shuffling_ids_to_prune.iter() {
-                debug!(
-                    self.logger,
-                    "Removing old shuffling from cache";
-                    "shuffling_epoch" => shuffling_id.shuffling_epoch,
-                    "shuffling_decision_block" => ?shuffling_id.shuffling_decision_block
-                );
-                self.cache.remove(shuffling_id);
+        let target_memory_usage = self
+            .memory_budget
+            .map(|budget| budget.saturating_sub(incoming_size));
+
+        let mut cache_size = self.cache.len();
+        let mut memory_usage = self.memory_usage();
+        let over_limits = |cache_size: usize, memory_usage: usize| {
+            cache_size > target_cache_size
+                || target_memory_usage.map_or(false, |target| memory_usage > target)
+        };
+        if !over_limits(cache_size, memory_usage) {
+            return;
+        }
+
+        let candidates = self
+            .cache
+            .iter()
+            .filter(|(shuffling_id, _)| {
+                Some(*shuffling_id)
+                    != self
+                        .head_shuffling_ids
+                        .id_for_epoch(shuffling_id.shuffling_epoch)
+                        .as_ref()
+            })
+            .sorted_by_key(|(_, entry)| entry.last_used)
+            .map(|(shuffling_id, entry)| {
+                (
+                    shuffling_id.clone(),
+                    entry.item.memory_size(),
+                    entry.item.is_promise(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (shuffling_id, size, is_promise) in candidates {
+            if !over_limits(cache_size, memory_usage) {
+                break;
             }
+            if is_promise && cache_size <= target_cache_size {
+                continue;
+            }
+            debug!(
+                self.logger,
+                "Removing shuffling from cache";
+                "shuffling_epoch" => shuffling_id.shuffling_epoch,
+                "shuffling_decision_block" => ?shuffling_id.shuffling_decision_block,
+                "memory_usage" => memory_usage,
+            );
+            self.cache.remove(&shuffling_id);
+            metrics::inc_counter(&metrics::SHUFFLING_CACHE_EVICTIONS);
+            cache_size -= 1;
+            memory_usage = memory_usage.saturating_sub(size);
         }
     }
 
+    fn update_metrics(&self) {
+        metrics::set_gauge(&metrics::SHUFFLING_CACHE_SIZE, self.cache.len() as i64);
+        metrics::set_gauge(
+            &metrics::SHUFFLING_CACHE_MEMORY_BYTES,
+            self.memory_usage() as i64,
+        );
+    }
+
     pub fn create_promise(
         &mut self,
         key: AttestationShufflingId,
@@ -196,7 +286,7 @@ impl ShufflingCache {
         let num_active_promises = self
             .cache
             .iter()
-            .filter(|(_, item)| item.is_promise())
+            .filter(|(_, entry)| entry.item.is_promise())
             .count();
         if num_active_promises >= MAX_CONCURRENT_PROMISES {
             return Err(BeaconChainError::MaxCommitteePromises(num_active_promises));
@@ -316,7 +406,7 @@ mod test {
             block_root: Hash256::from_low_u64_le(0),
         };
         let logger = null_logger().unwrap();
-        ShufflingCache::new(TEST_CACHE_SIZE, head_shuffling_ids, logger)
+        ShufflingCache::new(TEST_CACHE_SIZE, None, head_shuffling_ids, logger)
     }
 
     /// Returns two different committee caches for testing.
@@ -581,4 +671,90 @@ mod test {
             "should limit cache size"
         );
     }
+
+    #[test]
+    fn should_prune_least_recently_used_committee_cache() {
+        let mut cache = new_shuffling_cache();
+        let committee_cache = Arc::new(CommitteeCache::default());
+
+        for i in 0..TEST_CACHE_SIZE {
+            cache.insert_committee_cache(shuffling_id(i as u64), &committee_cache);
+        }
+
+        // Reading the oldest epoch should protect it from the next eviction.
+        assert!(cache.get(&shuffling_id(0)).is_some());
+        cache.insert_committee_cache(shuffling_id(TEST_CACHE_SIZE as u64), &committee_cache);
+
+        assert!(
+            cache.contains(&shuffling_id(0)),
+            "should retain recently used shuffling id"
+        );
+        assert!(
+            !cache.contains(&shuffling_id(1)),
+            "should prune least recently used shuffling id"
+        );
+        assert_eq!(cache.cache.len(), cache.cache_size);
+    }
+
+    #[test]
+    fn should_respect_memory_budget() {
+        let committee_cache = Arc::new(CommitteeCache::default());
+        let budget = 3 * committee_cache.memory_size();
+        let mut cache = ShufflingCache::new(
+            TEST_CACHE_SIZE,
+            Some(budget),
+            BlockShufflingIds {
+                current: shuffling_id(0),
+                next: shuffling_id(1),
+                previous: None,
+                block_root: Hash256::from_low_u64_le(0),
+            },
+            null_logger().unwrap(),
+        );
+
+        for i in 0..TEST_CACHE_SIZE {
+            cache.insert_committee_cache(shuffling_id(i as u64), &committee_cache);
+        }
+
+        assert_eq!(cache.cache.len(), 3, "should limit cache memory");
+        assert!(cache.memory_usage() <= budget);
+        assert!(
+            cache.contains(&shuffling_id(0)) && cache.contains(&shuffling_id(1)),
+            "should retain head shuffling ids"
+        );
+        assert!(cache.contains(&shuffling_id(TEST_CACHE_SIZE as u64 - 1)));
+
+        // Promises don't count towards the budget.
+        cache.create_promise(shuffling_id(100)).unwrap();
+        assert_eq!(cache.cache.len(), 4);
+    }
+
+    #[test]
+    fn should_not_evict_promises_for_memory_budget() {
+        let committee_cache = Arc::new(CommitteeCache::default());
+        let budget = 3 * committee_cache.memory_size();
+        let mut cache = ShufflingCache::new(
+            2 * TEST_CACHE_SIZE,
+            Some(budget),
+            BlockShufflingIds {
+                current: shuffling_id(0),
+                next: shuffling_id(1),
+                previous: None,
+                block_root: Hash256::from_low_u64_le(0),
+            },
+            null_logger().unwrap(),
+        );
+
+        // The promise is the least-recently used entry.
+        let _sender = cache.create_promise(shuffling_id(100)).unwrap();
+        for i in 2..2 + TEST_CACHE_SIZE {
+            cache.insert_committee_cache(shuffling_id(i as u64), &committee_cache);
+        }
+
+        assert!(cache.memory_usage() <= budget);
+        assert!(
+            cache.contains(&shuffling_id(100)),
+            "should not evict a pending promise to free memory"
+        );
+    }
 }
@@ -0,0 +1,146 @@
+//! Provides an opt-in audit of the tree hash cache of the head state.
+//!
+//! The tree hash cache only re-hashes the parts of a `BeaconState` which have been marked as
+//! changed. A bug which fails to invalidate part of the cache would silently produce an incorrect
+//! state root, which would cause the node to reject valid blocks or propose invalid ones.
+//!
+//! When enabled, the audit periodically hashes the head state from scratch and compares the result
+//! with both the root computed via the cache and the state root in the head block, logging a
+//! critical error if they differ.
+use crate::{metrics, BeaconChain, BeaconChainError, BeaconChainTypes};
+use slog::{crit, debug, error, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+use types::beacon_state::CloneConfig;
+use types::{EthSpec, Hash256, Slot};
+
+/// The result of auditing the state root of the head state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateRootAudit {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    /// The state root in the head block.
+    pub expected_root: Hash256,
+    /// The root computed using the tree hash cache of the head state.
+    pub cached_root: Hash256,
+    /// The root computed from scratch, without any cache.
+    pub fresh_root: Hash256,
+}
+
+impl StateRootAudit {
+    /// Returns `true` if all of the roots are equal.
+    pub fn is_consistent(&self) -> bool {
+        self.cached_root == self.fresh_root && self.fresh_root == self.expected_root
+    }
+}
+
+/// Spawns a task which audits the state root of the head state every `interval_epochs` epochs.
+pub fn spawn_state_root_auditor<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+    interval_epochs: u64,
+    log: Logger,
+) {
+    executor.spawn(
+        state_root_auditor(executor.clone(), chain, interval_epochs.max(1), log),
+        "state_root_auditor",
+    );
+}
+
+async fn state_root_auditor<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+    interval_epochs: u64,
+    log: Logger,
+) {
+    let slot_duration = chain.slot_clock.slot_duration();
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+
+    loop {
+        // Run halfway through the first slot of the epoch, clear of block import at the start of
+        // the slot and the state advance at the end of it.
+        match chain.slot_clock.duration_to_next_epoch(slots_per_epoch) {
+            Some(duration) => sleep(duration + slot_duration / 2).await,
+            None => {
+                error!(log, "Failed to read slot clock");
+                // If we can't read the slot clock, just wait another slot.
+                sleep(slot_duration).await;
+                continue;
+            }
+        }
+
+        match chain.epoch() {
+            Ok(epoch) if epoch.as_u64() % interval_epochs == 0 => {}
+            _ => continue,
+        }
+
+        let inner_chain = chain.clone();
+        let result = executor.spawn_blocking_handle(
+            move || inner_chain.audit_head_state_root(),
+            "state_root_audit",
+        );
+        let result = match result {
+            Some(handle) => handle.await,
+            // The executor is shutting down.
+            None => return,
+        };
+
+        match result {
+            Ok(Ok(audit)) if audit.is_consistent() => debug!(
+                log,
+                "State root audit passed";
+                "slot" => audit.slot,
+                "state_root" => ?audit.fresh_root,
+            ),
+            Ok(Ok(audit)) => {
+                metrics::inc_counter(&metrics::STATE_ROOT_AUDIT_MISMATCHES);
+                crit!(
+                    log,
+                    "State root audit failed";
+                    "msg" => "the tree hash cache is inconsistent, please report this bug",
+                    "slot" => audit.slot,
+                    "block_root" => ?audit.block_root,
+                    "expected_root" => ?audit.expected_root,
+                    "cached_root" => ?audit.cached_root,
+                    "fresh_root" => ?audit.fresh_root,
+                );
+            }
+            Ok(Err(e)) => error!(
+                log,
+                "Unable to audit state root";
+                "error" => ?e,
+            ),
+            Err(e) => error!(
+                log,
+                "State root audit task failed";
+                "error" => ?e,
+            ),
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Computes the root of the head state both with its tree hash cache and from scratch.
+    ///
+    /// The head state is cloned so the cache of the head snapshot is not modified.
+    pub fn audit_head_state_root(&self) -> Result<StateRootAudit, BeaconChainError> {
+        let head = self.head_snapshot();
+        let mut state = head.beacon_state.clone_with(CloneConfig::all());
+
+        let cached_root = state.update_tree_hash_cache()?;
+        let fresh_root = {
+            let _timer = metrics::start_timer(&metrics::STATE_ROOT_AUDIT_TIMES);
+            state.canonical_root()
+        };
+
+        Ok(StateRootAudit {
+            slot: state.slot(),
+            block_root: head.beacon_block_root,
+            expected_root: head.beacon_state_root(),
+            cached_root,
+            fresh_root,
+        })
+    }
+}
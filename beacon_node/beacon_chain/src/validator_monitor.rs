@@ -4,6 +4,7 @@
 
 use crate::metrics;
 use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use slog::{crit, debug, info, Logger};
 use slot_clock::SlotClock;
 use state_processing::per_epoch_processing::{
@@ -11,9 +12,10 @@ use state_processing::per_epoch_processing::{
 };
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
+use std::fmt;
 use std::io;
 use std::marker::PhantomData;
-use std::str::Utf8Error;
+use std::str::{FromStr, Utf8Error};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use store::AbstractExecPayload;
 use types::{
@@ -37,6 +39,99 @@ pub const HISTORIC_EPOCHS: usize = 10;
 /// Prometheus cardinality and log volume.
 pub const DEFAULT_INDIVIDUAL_TRACKING_THRESHOLD: usize = 64;
 
+/// Used for Prometheus labels when aggregating validators by status.
+const PENDING_LABEL: &str = "pending";
+const ACTIVE_LABEL: &str = "active";
+const EXITED_LABEL: &str = "exited";
+const SLASHED_LABEL: &str = "slashed";
+
+/// The maximum length of a cohort label.
+pub const MAX_COHORT_LEN: usize = 32;
+
+/// Returns `true` if `cohort` is suitable for use as a Prometheus label value.
+pub fn is_valid_cohort(cohort: &str) -> bool {
+    !cohort.is_empty()
+        && cohort.len() <= MAX_COHORT_LEN
+        && cohort
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Determines which labels are used for aggregate metrics once the validator monitor has stopped
+/// tracking validators individually.
+///
+/// The `total` label is always used. The other modes add a label per group of validators, which
+/// keeps Prometheus cardinality bounded whilst still allowing under-performing groups to be found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricAggregation {
+    /// Only use the `total` label.
+    #[default]
+    Total,
+    /// Group validators by their status in the most recent state: `pending`, `active`, `exited`
+    /// or `slashed`.
+    Status,
+    /// Group validators into ranges of this many validator indices, e.g. `0-999`.
+    IndexRange(u64),
+    /// Group validators by the cohort they were registered with via the HTTP API.
+    Cohort,
+}
+
+impl MetricAggregation {
+    /// Returns the group label for a validator with `index`, `status` and `cohort`, if any.
+    fn label(
+        &self,
+        index: Option<u64>,
+        status: Option<&'static str>,
+        cohort: Option<&str>,
+    ) -> Option<String> {
+        match self {
+            MetricAggregation::Total => None,
+            MetricAggregation::Status => status.map(String::from),
+            MetricAggregation::Cohort => cohort.map(String::from),
+            MetricAggregation::IndexRange(size) => index.map(|index| {
+                let size = std::cmp::max(*size, 1);
+                let start = index / size * size;
+                format!("{}-{}", start, start.saturating_add(size - 1))
+            }),
+        }
+    }
+}
+
+impl FromStr for MetricAggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "total" => Ok(MetricAggregation::Total),
+            "status" => Ok(MetricAggregation::Status),
+            "cohort" => Ok(MetricAggregation::Cohort),
+            other => match other.strip_prefix("index-range:") {
+                Some(size) => match size.parse::<u64>() {
+                    Ok(size) if size > 0 => Ok(MetricAggregation::IndexRange(size)),
+                    _ => Err(format!("invalid index range size: {}", size)),
+                },
+                None => Err(format!(
+                    "{} is not a valid metric aggregation, expected total, status, cohort or \
+                     index-range:<size>",
+                    other
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for MetricAggregation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MetricAggregation::Total => write!(f, "total"),
+            MetricAggregation::Status => write!(f, "status"),
+            MetricAggregation::IndexRange(size) => write!(f, "index-range:{}", size),
+            MetricAggregation::Cohort => write!(f, "cohort"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     InvalidPubkey(String),
@@ -242,6 +337,10 @@ pub struct MonitoredValidator {
     pub summaries: RwLock<SummaryMap>,
     /// Validator metrics to be exposed over the HTTP API.
     pub metrics: RwLock<ValidatorMetrics>,
+    /// The status label of the validator in the most recently processed state.
+    status: RwLock<Option<&'static str>>,
+    /// The cohort the validator was registered with, used to aggregate metrics.
+    pub cohort: Option<String>,
 }
 
 impl MonitoredValidator {
@@ -253,6 +352,8 @@ impl MonitoredValidator {
             index,
             summaries: <_>::default(),
             metrics: <_>::default(),
+            status: <_>::default(),
+            cohort: None,
         }
     }
 
@@ -343,6 +444,9 @@ pub struct ValidatorMonitor<T> {
     /// large validator counts causing infeasibly high cardinailty for
     /// Prometheus and high log volumes.
     individual_tracking_threshold: usize,
+    /// Determines the labels used for aggregate metrics when validators are not being tracked
+    /// individually.
+    metric_aggregation: MetricAggregation,
     log: Logger,
     _phantom: PhantomData<T>,
 }
@@ -359,6 +463,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             indices: <_>::default(),
             auto_register,
             individual_tracking_threshold,
+            metric_aggregation: MetricAggregation::default(),
             log,
             _phantom: PhantomData,
         };
@@ -375,6 +480,18 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         self.validators.len() <= self.individual_tracking_threshold
     }
 
+    /// Returns the labels used for aggregate metrics.
+    pub fn metric_aggregation(&self) -> MetricAggregation {
+        self.metric_aggregation
+    }
+
+    /// Changes the labels used for aggregate metrics.
+    ///
+    /// Series for the previous labels are not removed, they will simply stop being updated.
+    pub fn set_metric_aggregation(&mut self, metric_aggregation: MetricAggregation) {
+        self.metric_aggregation = metric_aggregation;
+    }
+
     /// Add some validators to `self` for additional monitoring.
     fn add_validator_pubkey(&mut self, pubkey: PublicKeyBytes) {
         let index_opt = self
@@ -394,6 +511,19 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         });
     }
 
+    /// Add a validator for additional monitoring, regardless of `self.auto_register`, and set
+    /// the cohort its metrics are aggregated under.
+    ///
+    /// If `cohort` is `None` the validator keeps the cohort it was previously registered with.
+    pub fn register_validator(&mut self, pubkey: PublicKeyBytes, cohort: Option<String>) {
+        self.add_validator_pubkey(pubkey);
+        if let Some(cohort) = cohort {
+            if let Some(validator) = self.validators.get_mut(&pubkey) {
+                validator.cohort = Some(cohort);
+            }
+        }
+    }
+
     /// Reads information from the given `state`. The `state` *must* be valid (i.e, able to be
     /// imported).
     pub fn process_valid_state(&mut self, current_epoch: Epoch, state: &BeaconState<T>) {
@@ -424,6 +554,18 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         summary.register_validator_total_balance(*balance)
                     });
                 }
+                if let Some(validator) = state.validators().get(i) {
+                    let status = if validator.slashed {
+                        SLASHED_LABEL
+                    } else if validator.is_exited_at(current_epoch) {
+                        EXITED_LABEL
+                    } else if validator.is_active_at(current_epoch) {
+                        ACTIVE_LABEL
+                    } else {
+                        PENDING_LABEL
+                    };
+                    *monitored_validator.status.write() = Some(status);
+                }
 
                 // Only log the per-validator metrics if it's enabled.
                 if !self.individual_tracking() {
@@ -491,8 +633,8 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         }
     }
 
-    /// Run `func` with the `TOTAL_LABEL` and optionally the
-    /// `individual_id`.
+    /// Run `func` with the `TOTAL_LABEL` and either the `id` of the `validator` or the label of
+    /// its group.
     ///
     /// This function is used for registering metrics that can be applied to
     /// both all validators and an indivdual validator. For example, the count
@@ -501,11 +643,19 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     ///
     /// We allow disabling tracking metrics on an individual validator basis
     /// since it can result in untenable cardinality with high validator counts.
-    fn aggregatable_metric<F: Fn(&str)>(&self, individual_id: &str, func: F) {
+    /// In that case the `metric_aggregation` determines whether the validator
+    /// is also counted against a group label.
+    fn aggregatable_metric<F: Fn(&str)>(&self, validator: &MonitoredValidator, func: F) {
         func(TOTAL_LABEL);
 
         if self.individual_tracking() {
-            func(individual_id);
+            func(&validator.id);
+        } else if let Some(label) = self.metric_aggregation.label(
+            validator.index,
+            *validator.status.read(),
+            validator.cohort.as_deref(),
+        ) {
+            func(&label);
         }
     }
 
@@ -579,7 +729,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 // For Base states, this will be *any* attestation whatsoever. For Altair states,
                 // this will be any attestation that matched a "timely" flag.
                 if previous_epoch_matched_any {
-                    self.aggregatable_metric(id, |label| {
+                    self.aggregatable_metric(monitored_validator, |label| {
                         metrics::inc_counter_vec(
                             &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_ATTESTER_HIT,
                             &[label],
@@ -598,7 +748,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         )
                     }
                 } else {
-                    self.aggregatable_metric(id, |label| {
+                    self.aggregatable_metric(monitored_validator, |label| {
                         metrics::inc_counter_vec(
                             &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_ATTESTER_MISS,
                             &[label],
@@ -617,14 +767,14 @@ impl<T: EthSpec> ValidatorMonitor<T> {
 
                 // Indicates if any on-chain attestation hit the head.
                 if previous_epoch_matched_head {
-                    self.aggregatable_metric(id, |label| {
+                    self.aggregatable_metric(monitored_validator, |label| {
                         metrics::inc_counter_vec(
                             &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_HEAD_ATTESTER_HIT,
                             &[label],
                         );
                     });
                 } else {
-                    self.aggregatable_metric(id, |label| {
+                    self.aggregatable_metric(monitored_validator, |label| {
                         metrics::inc_counter_vec(
                             &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_HEAD_ATTESTER_MISS,
                             &[label],
@@ -643,14 +793,14 @@ impl<T: EthSpec> ValidatorMonitor<T> {
 
                 // Indicates if any on-chain attestation hit the target.
                 if previous_epoch_matched_target {
-                    self.aggregatable_metric(id, |label| {
+                    self.aggregatable_metric(monitored_validator, |label| {
                         metrics::inc_counter_vec(
                             &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_TARGET_ATTESTER_HIT,
                             &[label],
                         );
                     });
                 } else {
-                    self.aggregatable_metric(id, |label| {
+                    self.aggregatable_metric(monitored_validator, |label| {
                         metrics::inc_counter_vec(
                             &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ON_CHAIN_TARGET_ATTESTER_MISS,
                             &[label],
@@ -881,7 +1031,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             let id = &validator.id;
             let delay = get_block_delay_ms(seen_timestamp, block, slot_clock);
 
-            self.aggregatable_metric(id, |label| {
+            self.aggregatable_metric(validator, |label| {
                 metrics::inc_counter_vec(
                     &metrics::VALIDATOR_MONITOR_BEACON_BLOCK_TOTAL,
                     &[src, label],
@@ -957,7 +1107,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             if let Some(validator) = self.get_validator(*i) {
                 let id = &validator.id;
 
-                self.aggregatable_metric(id, |label| {
+                self.aggregatable_metric(validator, |label| {
                     metrics::inc_counter_vec(
                         &metrics::VALIDATOR_MONITOR_UNAGGREGATED_ATTESTATION_TOTAL,
                         &[src, label],
@@ -1045,7 +1195,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         if let Some(validator) = self.get_validator(aggregator_index) {
             let id = &validator.id;
 
-            self.aggregatable_metric(id, |label| {
+            self.aggregatable_metric(validator, |label| {
                 metrics::inc_counter_vec(
                     &metrics::VALIDATOR_MONITOR_AGGREGATED_ATTESTATION_TOTAL,
                     &[src, label],
@@ -1080,7 +1230,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             if let Some(validator) = self.get_validator(*i) {
                 let id = &validator.id;
 
-                self.aggregatable_metric(id, |label| {
+                self.aggregatable_metric(validator, |label| {
                     metrics::inc_counter_vec(
                         &metrics::VALIDATOR_MONITOR_ATTESTATION_IN_AGGREGATE_TOTAL,
                         &[src, label],
@@ -1138,7 +1288,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             if let Some(validator) = self.get_validator(*i) {
                 let id = &validator.id;
 
-                self.aggregatable_metric(id, |label| {
+                self.aggregatable_metric(validator, |label| {
                     metrics::inc_counter_vec(
                         &metrics::VALIDATOR_MONITOR_ATTESTATION_IN_BLOCK_TOTAL,
                         &["block", label],
@@ -1220,7 +1370,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 slot_clock,
             );
 
-            self.aggregatable_metric(id, |label| {
+            self.aggregatable_metric(validator, |label| {
                 metrics::inc_counter_vec(
                     &metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_MESSAGES_TOTAL,
                     &[src, label],
@@ -1308,7 +1458,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         if let Some(validator) = self.get_validator(aggregator_index) {
             let id = &validator.id;
 
-            self.aggregatable_metric(id, |label| {
+            self.aggregatable_metric(validator, |label| {
                 metrics::inc_counter_vec(
                     &metrics::VALIDATOR_MONITOR_SYNC_CONTRIBUTIONS_TOTAL,
                     &[src, label],
@@ -1342,7 +1492,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             if let Some(validator) = self.validators.get(validator_pubkey) {
                 let id = &validator.id;
 
-                self.aggregatable_metric(id, |label| {
+                self.aggregatable_metric(validator, |label| {
                     metrics::inc_counter_vec(
                         &metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_MESSAGE_IN_CONTRIBUTION_TOTAL,
                         &[src, label],
@@ -1382,7 +1532,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             if let Some(validator) = self.validators.get(validator_pubkey) {
                 let id = &validator.id;
 
-                self.aggregatable_metric(id, |label| {
+                self.aggregatable_metric(validator, |label| {
                     metrics::inc_counter_vec(
                         &metrics::VALIDATOR_MONITOR_SYNC_COMMITTEE_MESSAGE_IN_BLOCK_TOTAL,
                         &["block", label],
@@ -1427,7 +1577,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             let id = &validator.id;
             let epoch = exit.epoch;
 
-            self.aggregatable_metric(id, |label| {
+            self.aggregatable_metric(validator, |label| {
                 metrics::inc_counter_vec(&metrics::VALIDATOR_MONITOR_EXIT_TOTAL, &[src, label]);
             });
 
@@ -1470,7 +1620,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         if let Some(validator) = self.get_validator(proposer) {
             let id = &validator.id;
 
-            self.aggregatable_metric(id, |label| {
+            self.aggregatable_metric(validator, |label| {
                 metrics::inc_counter_vec(
                     &metrics::VALIDATOR_MONITOR_PROPOSER_SLASHING_TOTAL,
                     &[src, label],
@@ -1527,7 +1677,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 let id = &validator.id;
                 let epoch = data.slot.epoch(T::slots_per_epoch());
 
-                self.aggregatable_metric(id, |label| {
+                self.aggregatable_metric(validator, |label| {
                     metrics::inc_counter_vec(
                         &metrics::VALIDATOR_MONITOR_ATTESTER_SLASHING_TOTAL,
                         &[src, label],
@@ -1586,7 +1736,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                      * Attestations
                      */
                     if let Some(delay) = summary.attestation_min_delay {
-                        self.aggregatable_metric(id, |tag| {
+                        self.aggregatable_metric(validator, |tag| {
                             metrics::observe_timer_vec(
                                 &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ATTESTATIONS_MIN_DELAY_SECONDS,
                                 &[tag],
@@ -1623,7 +1773,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                      * Sync committee messages
                      */
                     if let Some(delay) = summary.sync_committee_message_min_delay {
-                        self.aggregatable_metric(id, |tag| {
+                        self.aggregatable_metric(validator, |tag| {
                             metrics::observe_timer_vec(
                                 &metrics::VALIDATOR_MONITOR_PREV_EPOCH_SYNC_COMMITTEE_MESSAGES_MIN_DELAY_SECONDS,
                                 &[tag],
@@ -1678,7 +1828,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         );
                     }
                     if let Some(delay) = summary.block_min_delay {
-                        self.aggregatable_metric(id, |tag| {
+                        self.aggregatable_metric(validator, |tag| {
                             metrics::observe_timer_vec(
                                 &metrics::VALIDATOR_MONITOR_PREV_EPOCH_BEACON_BLOCKS_MIN_DELAY_SECONDS,
                                 &[tag],
@@ -1697,7 +1847,7 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         );
                     }
                     if let Some(delay) = summary.aggregate_min_delay {
-                        self.aggregatable_metric(id, |tag| {
+                        self.aggregatable_metric(validator, |tag| {
                             metrics::observe_timer_vec(
                                 &metrics::VALIDATOR_MONITOR_PREV_EPOCH_AGGREGATES_MIN_DELAY_SECONDS,
                                 &[tag],
@@ -1793,3 +1943,69 @@ fn min_opt<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metric_aggregation_from_str() {
+        for aggregation in [
+            MetricAggregation::Total,
+            MetricAggregation::Status,
+            MetricAggregation::IndexRange(1000),
+            MetricAggregation::Cohort,
+        ] {
+            assert_eq!(
+                MetricAggregation::from_str(&aggregation.to_string()),
+                Ok(aggregation)
+            );
+        }
+        assert!(MetricAggregation::from_str("index-range:0").is_err());
+        assert!(MetricAggregation::from_str("index-range").is_err());
+        assert!(MetricAggregation::from_str("individual").is_err());
+    }
+
+    #[test]
+    fn cohort_validation() {
+        assert!(is_valid_cohort("node-a_1"));
+        assert!(is_valid_cohort(&"a".repeat(MAX_COHORT_LEN)));
+        assert!(!is_valid_cohort(""));
+        assert!(!is_valid_cohort(&"a".repeat(MAX_COHORT_LEN + 1)));
+        assert!(!is_valid_cohort("node a"));
+        assert!(!is_valid_cohort("node\"a"));
+    }
+
+    #[test]
+    fn metric_aggregation_labels() {
+        assert_eq!(
+            MetricAggregation::Total.label(Some(5), Some(ACTIVE_LABEL), None),
+            None
+        );
+        assert_eq!(
+            MetricAggregation::Status.label(Some(5), Some(ACTIVE_LABEL), None),
+            Some(ACTIVE_LABEL.to_string())
+        );
+        assert_eq!(MetricAggregation::Status.label(Some(5), None, None), None);
+        assert_eq!(
+            MetricAggregation::IndexRange(1000).label(Some(0), None, None),
+            Some("0-999".to_string())
+        );
+        assert_eq!(
+            MetricAggregation::IndexRange(1000).label(Some(12345), None, None),
+            Some("12000-12999".to_string())
+        );
+        assert_eq!(
+            MetricAggregation::IndexRange(1000).label(None, None, None),
+            None
+        );
+        assert_eq!(
+            MetricAggregation::Cohort.label(Some(5), Some(ACTIVE_LABEL), Some("node-a")),
+            Some("node-a".to_string())
+        );
+        assert_eq!(
+            MetricAggregation::Cohort.label(Some(5), Some(ACTIVE_LABEL), None),
+            None
+        );
+    }
+}
@@ -0,0 +1,129 @@
+#![cfg(not(debug_assertions))]
+
+use beacon_chain::test_utils::{
+    AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
+    SyncCommitteeStrategy,
+};
+use beacon_chain::ChainConfig;
+use lazy_static::lazy_static;
+use types::{BeaconBlockHeader, Epoch, EthSpec, Hash256, Keypair, MinimalEthSpec};
+
+type E = MinimalEthSpec;
+
+pub const VALIDATOR_COUNT: usize = 64;
+
+lazy_static! {
+    /// A cached set of keys.
+    static ref KEYPAIRS: Vec<Keypair> = types::test_utils::generate_deterministic_keypairs(VALIDATOR_COUNT);
+}
+
+fn get_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .chain_config(ChainConfig {
+            enable_light_client_server: true,
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS.to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    harness.advance_slot();
+
+    harness
+}
+
+#[tokio::test]
+async fn light_client_updates_are_published_once() {
+    let harness = get_harness();
+    harness
+        .extend_chain_with_sync(
+            E::slots_per_epoch() as usize * 5 - 1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+            SyncCommitteeStrategy::AllValidators,
+        )
+        .await;
+
+    let chain = &harness.chain;
+    let current_slot = chain.slot().unwrap();
+    let head = chain.head_snapshot();
+    assert_eq!(head.beacon_block.slot(), current_slot);
+
+    let optimistic_update = chain
+        .light_client_optimistic_update_to_publish(current_slot)
+        .expect("should publish an optimistic update");
+    assert_eq!(
+        optimistic_update.attested_header.canonical_root(),
+        head.beacon_block.parent_root()
+    );
+    assert_eq!(optimistic_update.signature_slot, current_slot);
+    assert!(chain
+        .light_client_optimistic_update_to_publish(current_slot)
+        .is_none());
+
+    let finality_update = chain
+        .light_client_finality_update_to_publish(current_slot)
+        .expect("should publish a finality update");
+    assert_eq!(
+        finality_update.finalized_header.canonical_root(),
+        head.beacon_state.finalized_checkpoint().root
+    );
+    assert!(chain
+        .light_client_finality_update_to_publish(current_slot)
+        .is_none());
+
+    let updates = chain
+        .light_client_server_cache
+        .get_light_client_updates(0, 1);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(
+        updates[0].sync_aggregate.num_set_bits(),
+        E::sync_committee_size()
+    );
+}
+
+#[tokio::test]
+async fn light_client_update_without_finality_has_empty_branch() {
+    let harness = get_harness();
+    harness
+        .extend_chain_with_sync(
+            2,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+            SyncCommitteeStrategy::AllValidators,
+        )
+        .await;
+    assert_eq!(
+        harness
+            .chain
+            .head_snapshot()
+            .beacon_state
+            .finalized_checkpoint()
+            .root,
+        Hash256::zero()
+    );
+
+    let updates = harness
+        .chain
+        .light_client_server_cache
+        .get_light_client_updates(0, 1);
+    assert_eq!(updates.len(), 1);
+    assert_eq!(
+        updates[0].finalized_header,
+        BeaconBlockHeader {
+            slot: 0_u64.into(),
+            proposer_index: 0,
+            parent_root: Hash256::zero(),
+            state_root: Hash256::zero(),
+            body_root: Hash256::zero(),
+        }
+    );
+    assert!(updates[0]
+        .finality_branch
+        .iter()
+        .all(|node| *node == Hash256::zero()));
+}
@@ -2,6 +2,7 @@ mod attestation_production;
 mod attestation_verification;
 mod block_verification;
 mod capella;
+mod light_client;
 mod merge;
 mod op_verification;
 mod payload_invalidation;
@@ -6,10 +6,11 @@ use beacon_chain::observed_operations::ObservationOutcome;
 use beacon_chain::test_utils::{
     test_spec, AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
 };
+use beacon_chain::BeaconSnapshot;
 use lazy_static::lazy_static;
 use sloggers::{null::NullLoggerBuilder, Build};
 use std::sync::Arc;
-use store::{LevelDB, StoreConfig};
+use store::{BeaconNodeBackend, StoreConfig};
 use tempfile::{tempdir, TempDir};
 use types::*;
 
@@ -23,7 +24,7 @@ lazy_static! {
 
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
-type HotColdDB = store::HotColdDB<E, LevelDB<E>, LevelDB<E>>;
+type HotColdDB = store::HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>;
 
 fn get_store(db_path: &TempDir) -> Arc<HotColdDB> {
     let spec = test_spec::<E>();
@@ -232,3 +233,68 @@ fn attester_slashing() {
         ObservationOutcome::AlreadyKnown
     ));
 }
+
+#[tokio::test]
+async fn reinclude_orphaned_voluntary_exit() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), VALIDATOR_COUNT);
+    let spec = &harness.chain.spec.clone();
+
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * (spec.shard_committee_period + 1)) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let validator_index = VALIDATOR_COUNT as u64 - 1;
+    let exit_epoch = Epoch::new(spec.shard_committee_period);
+    let parent_state = harness.get_current_state();
+    let slot_a = parent_state.slot() + 1;
+    let slot_b = parent_state.slot() + 2;
+
+    // Block A includes an exit that was never seen on gossip.
+    let (block_a, _) = harness
+        .make_block_with_modifier(parent_state.clone(), slot_a, |block| {
+            harness.add_voluntary_exit(block, validator_index, exit_epoch)
+        })
+        .await;
+    let root_a = block_a.canonical_root();
+    harness
+        .process_block(slot_a, root_a, block_a)
+        .await
+        .unwrap();
+
+    // Block B is a sibling of block A which does not include the exit.
+    let (block_b, _) = harness.make_block(parent_state, slot_b).await;
+    let root_b = block_b.canonical_root();
+    harness
+        .process_block(slot_b, root_b, block_b.clone())
+        .await
+        .unwrap();
+    let state_b = harness
+        .chain
+        .get_state(&block_b.state_root(), Some(slot_b))
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        harness.chain.orphaned_block_roots(root_a, root_b),
+        vec![root_a]
+    );
+
+    let new_head = BeaconSnapshot::new(Arc::new(block_b), root_b, state_b);
+    let reincluded = harness
+        .chain
+        .reinclude_orphaned_operations(root_a, &new_head)
+        .unwrap();
+    assert_eq!(reincluded.voluntary_exits, 1);
+    assert!(harness
+        .chain
+        .op_pool
+        .get_all_voluntary_exits()
+        .iter()
+        .any(|exit| exit.message.validator_index == validator_index));
+}
@@ -265,6 +265,68 @@ async fn test_verify_attestation_rewards_base_subset_only() {
     assert_eq!(expected_balances, balances);
 }
 
+#[tokio::test]
+async fn test_epoch_rewards_batch() {
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    let harness = get_harness(spec);
+
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * 5) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let validators = vec![ValidatorId::Index(0), ValidatorId::Index(7)];
+
+    let mut epoch_rewards = vec![];
+    harness
+        .chain
+        .compute_epoch_rewards_batch(
+            Epoch::new(1),
+            Epoch::new(3),
+            validators.clone(),
+            |rewards| {
+                epoch_rewards.push(rewards);
+                true
+            },
+        )
+        .unwrap();
+
+    // The batch matches the rewards computed one epoch at a time.
+    assert_eq!(epoch_rewards.len(), 3);
+    for (rewards, epoch) in epoch_rewards.iter().zip(1..) {
+        assert_eq!(rewards.epoch, epoch);
+        let expected = harness
+            .chain
+            .compute_attestation_rewards(Epoch::new(epoch), validators.clone())
+            .unwrap();
+        assert_eq!(rewards.attestation_rewards, expected);
+        assert!(rewards
+            .block_rewards
+            .iter()
+            .all(|reward| reward.proposer_index == 0 || reward.proposer_index == 7));
+    }
+
+    // Every block in the epoch has a reward when no validators are given.
+    let mut epoch_rewards = vec![];
+    harness
+        .chain
+        .compute_epoch_rewards_batch(Epoch::new(1), Epoch::new(3), vec![], |rewards| {
+            epoch_rewards.push(rewards);
+            // Stop after the first epoch.
+            false
+        })
+        .unwrap();
+    assert_eq!(epoch_rewards.len(), 1);
+    assert_eq!(
+        epoch_rewards[0].block_rewards.len(),
+        E::slots_per_epoch() as usize
+    );
+}
+
 /// Apply a vec of `TotalAttestationRewards` to initial balances, and return
 fn apply_attestation_rewards(
     initial_balances: &[u64],
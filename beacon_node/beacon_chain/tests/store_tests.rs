@@ -2,7 +2,7 @@
 
 use beacon_chain::attestation_verification::Error as AttnError;
 use beacon_chain::builder::BeaconChainBuilder;
-use beacon_chain::schema_change::migrate_schema;
+use beacon_chain::schema_change::{migrate_schema, plan_migration};
 use beacon_chain::test_utils::{
     test_spec, AttestationStrategy, BeaconChainHarness, BlockStrategy, DiskHarnessType,
 };
@@ -23,10 +23,13 @@ use std::collections::HashSet;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION};
+use store::hot_cold_store::HotColdDBError;
+use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, MIN_SUPPORTED_SCHEMA_VERSION};
 use store::{
+    hdiff::HotStateDiff,
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, LevelDB, StoreConfig,
+    BeaconNodeBackend, DBColumn, Error as StoreError, HotColdDB, ItemStore, KeyValueStore,
+    StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -46,17 +49,24 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     get_store_with_spec(db_path, test_spec::<E>())
 }
 
 fn get_store_with_spec(
     db_path: &TempDir,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
+    get_store_generic(db_path, StoreConfig::default(), spec)
+}
+
+fn get_store_generic(
+    db_path: &TempDir,
+    config: StoreConfig,
+    spec: ChainSpec,
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
     let log = test_logger();
 
     HotColdDB::open(&hot_path, &cold_path, |_, _, _| Ok(()), config, spec, log)
@@ -64,7 +74,7 @@ fn get_store_with_spec(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     // Most tests expect to retain historic states, so we use this as the default.
@@ -76,7 +86,7 @@ fn get_harness(
 }
 
 fn get_harness_generic(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
     validator_count: usize,
     chain_config: ChainConfig,
 ) -> TestHarness {
@@ -113,6 +123,66 @@ async fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[tokio::test]
+async fn hdiff_hot_states() {
+    let num_blocks_produced = E::slots_per_epoch() * 10;
+    let db_path = tempdir().unwrap();
+    let config = StoreConfig {
+        hdiff_exponents: vec![1, 2],
+        ..StoreConfig::default()
+    };
+    let store = get_store_generic(&db_path, config, test_spec::<E>());
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    check_finalization(&harness, num_blocks_produced);
+    check_split_slot(&harness, store.clone());
+    check_chain_dump(&harness, num_blocks_produced + 1);
+    check_iterators(&harness);
+
+    // Some of the hot epoch boundary states are stored as diffs, and none of them are diffed
+    // against a state which was deleted on finalization.
+    let split_slot = store.get_split_slot();
+    let diffs = store
+        .hot_db
+        .iter_column_keys(DBColumn::BeaconStateDiff)
+        .map(|state_root| {
+            store
+                .hot_db
+                .get::<HotStateDiff>(&state_root.unwrap())
+                .unwrap()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(!diffs.is_empty(), "some states should be stored as diffs");
+    for diff in &diffs {
+        assert!(diff.base_slot >= split_slot, "diff base should be retained");
+        assert!(diff.base_slot < diff.slot);
+    }
+
+    // Every hot epoch boundary state loads to the state with the requested root.
+    let head_slot = harness.head_slot();
+    for slot in (split_slot.as_u64()..=head_slot.as_u64())
+        .step_by(E::slots_per_epoch() as usize)
+        .map(Slot::new)
+    {
+        let state_root = harness.chain.state_root_at_slot(slot).unwrap().unwrap();
+        let state = store
+            .get_state(&state_root, Some(slot))
+            .unwrap()
+            .expect("hot epoch boundary state should load");
+        assert_eq!(state.slot(), slot);
+        assert_eq!(state.canonical_root(), state_root);
+    }
+}
+
 #[tokio::test]
 async fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
@@ -300,6 +370,59 @@ async fn split_slot_restore() {
     assert_eq!(store.get_split_slot(), split_slot);
 }
 
+// Check that a backup of the hot DB can be opened alongside the freezer DB it was taken from.
+#[tokio::test]
+async fn hot_db_backup_restore() {
+    let db_path = tempdir().unwrap();
+    let backup_path = tempdir().unwrap();
+    let backup_dir = backup_path.path().join("backup");
+
+    let (manifest, head_block_root) = {
+        let store = get_store(&db_path);
+        let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+        let num_blocks = 4 * E::slots_per_epoch();
+
+        harness
+            .extend_chain(
+                num_blocks as usize,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            )
+            .await;
+
+        let manifest = store.backup_hot_db(&backup_dir, None).unwrap();
+
+        let status = store.backup_status().unwrap();
+        assert!(status.complete);
+        assert_eq!(status.error, None);
+        assert_eq!(status.keys_copied, manifest.keys);
+
+        // A second backup into the same directory must not overwrite the first.
+        assert!(store.backup_hot_db(&backup_dir, None).is_err());
+
+        (manifest, harness.head_block_root())
+    };
+    assert_ne!(manifest.split.slot, Slot::new(0));
+    assert!(backup_dir
+        .join(store::backup::BACKUP_MANIFEST_FILENAME)
+        .exists());
+
+    // Open the backup with the original freezer DB.
+    let store = HotColdDB::open(
+        &backup_dir.join(store::backup::BACKUP_HOT_DB_DIRNAME),
+        &db_path.path().join("cold_db"),
+        |_, _, _| Ok(()),
+        StoreConfig::default(),
+        test_spec::<E>(),
+        test_logger(),
+    )
+    .expect("backup should open");
+
+    assert_eq!(store.get_split_info(), manifest.split);
+    assert!(store.get_blinded_block(&head_block_root).unwrap().is_some());
+}
+
 // Check attestation processing and `load_epoch_boundary_state` in the presence of a split DB.
 // This is a bit of a monster test in that it tests lots of different things, but until they're
 // tested elsewhere, this is as good a place as any.
@@ -2796,6 +2919,109 @@ async fn schema_downgrade_to_min_version() {
     .expect_err("should not downgrade below minimum version");
 }
 
+// Check that planning a migration reports every step between the two versions, computes the
+// changes of the first step only, and leaves the database untouched.
+#[tokio::test]
+async fn schema_migration_plan() {
+    let num_blocks_produced = E::slots_per_epoch() * 2;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let spec = &harness.chain.spec.clone();
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    // Close the database to ensure everything is written to disk.
+    drop(store);
+    drop(harness);
+    let store = get_store(&db_path);
+    let log = store.logger().clone();
+    let deposit_contract_deploy_block = 0;
+    let plan = |from, to| {
+        plan_migration::<DiskHarnessType<E>>(
+            store.clone(),
+            deposit_contract_deploy_block,
+            from,
+            to,
+            log.clone(),
+        )
+    };
+
+    // Planning a migration to the current version is a no-op.
+    assert_eq!(
+        plan(CURRENT_SCHEMA_VERSION, CURRENT_SCHEMA_VERSION).unwrap(),
+        vec![]
+    );
+
+    // Versions outside of the supported range are rejected, in either direction.
+    let below_min = SchemaVersion(MIN_SUPPORTED_SCHEMA_VERSION.as_u64() - 1);
+    let above_current = SchemaVersion(CURRENT_SCHEMA_VERSION.as_u64() + 1);
+    for (from, to) in [
+        (CURRENT_SCHEMA_VERSION, below_min),
+        (below_min, CURRENT_SCHEMA_VERSION),
+        (CURRENT_SCHEMA_VERSION, above_current),
+        (above_current, CURRENT_SCHEMA_VERSION),
+    ] {
+        assert!(
+            matches!(
+                plan(from, to),
+                Err(StoreError::HotColdDBError(
+                    HotColdDBError::UnsupportedSchemaVersion { .. }
+                ))
+            ),
+            "{:?} -> {:?} should be unsupported",
+            from,
+            to
+        );
+    }
+
+    // A downgrade to the minimum version is reported one step at a time, in descending order.
+    let steps = plan(CURRENT_SCHEMA_VERSION, MIN_SUPPORTED_SCHEMA_VERSION).unwrap();
+    assert_eq!(
+        steps.len() as u64,
+        CURRENT_SCHEMA_VERSION.as_u64() - MIN_SUPPORTED_SCHEMA_VERSION.as_u64()
+    );
+    for (i, step) in steps.iter().enumerate() {
+        assert_eq!(
+            step.from.as_u64(),
+            CURRENT_SCHEMA_VERSION.as_u64() - i as u64
+        );
+        assert_eq!(step.to.as_u64(), step.from.as_u64() - 1);
+        assert_eq!(step.changes.is_some(), i == 0);
+    }
+
+    // The first step rewrites the persisted fork choice.
+    let changes = steps[0].changes.as_ref().unwrap();
+    assert_eq!(changes.keys_written, 1);
+    assert!(changes.bytes_written > 0);
+    assert_eq!(changes.keys_deleted, 0);
+
+    // The database is untouched, so the downgrade still applies, after which an upgrade back to
+    // the current version is planned in ascending order.
+    let previous_version = SchemaVersion(CURRENT_SCHEMA_VERSION.as_u64() - 1);
+    migrate_schema::<DiskHarnessType<E>>(
+        store.clone(),
+        deposit_contract_deploy_block,
+        CURRENT_SCHEMA_VERSION,
+        previous_version,
+        log.clone(),
+        spec,
+    )
+    .expect("schema downgrade after planning should work");
+
+    let steps = plan(previous_version, CURRENT_SCHEMA_VERSION).unwrap();
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].from, previous_version);
+    assert_eq!(steps[0].to, CURRENT_SCHEMA_VERSION);
+    assert_eq!(steps[0].changes.as_ref().unwrap().keys_written, 1);
+}
+
 /// Checks that two chains are the same, for the purpose of these tests.
 ///
 /// Several fields that are hard/impossible to check are ignored (e.g., the store).
@@ -2870,7 +3096,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
@@ -319,6 +319,26 @@ async fn finalizes_with_full_participation() {
     );
 }
 
+#[tokio::test]
+async fn head_state_root_audit() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            MinimalEthSpec::slots_per_epoch() as usize * 2,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let audit = harness
+        .chain
+        .audit_head_state_root()
+        .expect("should audit head state root");
+    assert!(audit.is_consistent(), "{:?}", audit);
+    assert_eq!(audit.block_root, harness.head_block_root());
+}
+
 #[tokio::test]
 async fn finalizes_with_two_thirds_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
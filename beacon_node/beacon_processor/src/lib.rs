@@ -38,6 +38,8 @@
 //! checks the queues to see if there are more parcels of work that can be spawned in a new worker
 //! task.
 
+use crate::status::WorkClass;
+pub use crate::status::{BeaconProcessorStatus, PriorityWeights, QueueStatus, StatusSnapshot};
 use crate::work_reprocessing_queue::{
     spawn_reprocess_scheduler, QueuedAggregate, QueuedBackfillBatch, QueuedGossipBlock,
     QueuedLightClientUpdate, QueuedRpcBlock, QueuedUnaggregate, ReadyWork, ReprocessQueueMessage,
@@ -58,7 +60,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::Context;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TrySendError;
@@ -66,6 +68,7 @@ use types::{Attestation, EthSpec, Hash256, SignedAggregateAndProof, Slot, Subnet
 use work_reprocessing_queue::IgnoredRpcBlock;
 
 mod metrics;
+pub mod status;
 pub mod work_reprocessing_queue;
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
@@ -79,6 +82,10 @@ const DEFAULT_MAX_WORK_EVENT_QUEUE_LEN: usize = 16_384;
 /// set to the CPU count, but we set it high to be safe.
 const MAX_IDLE_QUEUE_LEN: usize = 16_384;
 
+/// The minimum time between updates of the published `StatusSnapshot`, except when the processor
+/// becomes idle.
+const STATUS_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
 /// The maximum size of the channel for re-processing work events.
 const DEFAULT_MAX_SCHEDULED_WORK_QUEUE_LEN: usize = 3 * DEFAULT_MAX_WORK_EVENT_QUEUE_LEN / 4;
 
@@ -154,6 +161,10 @@ const MAX_STATUS_QUEUE_LEN: usize = 1_024;
 /// will be stored before we start dropping them.
 const MAX_BLOCKS_BY_RANGE_QUEUE_LEN: usize = 1_024;
 
+/// The maximum number of queued `BlocksByRangeRequest` objects for finalized history that were
+/// deprioritized because the node is near its outbound bandwidth budget.
+const MAX_HISTORICAL_BLOCKS_BY_RANGE_QUEUE_LEN: usize = 1_024;
+
 /// The maximum number of queued `BlocksByRootRequest` objects received from the network RPC that
 /// will be stored before we start dropping them.
 const MAX_BLOCKS_BY_ROOTS_QUEUE_LEN: usize = 1_024;
@@ -194,11 +205,21 @@ const WORKER_TASK_NAME: &str = "beacon_processor_worker";
 /// invalid signature causes the entire batch to fail. When a batch fails, we fall-back to
 /// individually verifying each attestation signature.
 const DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE: usize = 64;
+
 const DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE: usize = 64;
 
+/// The number of queued unaggregated attestations which are searched for attestations from the
+/// same subnet when forming a batch.
+///
+/// Batches are only grouped by subnet. Workers are still shared between all subnets, and the
+/// committees read while verifying a batch are only cached for the duration of that batch, rather
+/// than by dedicated per-subnet workers.
+const ATTESTATION_BATCH_SUBNET_SEARCH_LEN: usize = 1_024;
+
 /// Unique IDs used for metrics and testing.
 pub const WORKER_FREED: &str = "worker_freed";
 pub const NOTHING_TO_DO: &str = "nothing_to_do";
+pub const ATTESTATION_BATCH_READY: &str = "attestation_batch_ready";
 pub const GOSSIP_ATTESTATION: &str = "gossip_attestation";
 pub const GOSSIP_ATTESTATION_BATCH: &str = "gossip_attestation_batch";
 pub const GOSSIP_AGGREGATE: &str = "gossip_aggregate";
@@ -218,6 +239,7 @@ pub const CHAIN_SEGMENT: &str = "chain_segment";
 pub const CHAIN_SEGMENT_BACKFILL: &str = "chain_segment_backfill";
 pub const STATUS_PROCESSING: &str = "status_processing";
 pub const BLOCKS_BY_RANGE_REQUEST: &str = "blocks_by_range_request";
+pub const HISTORICAL_BLOCKS_BY_RANGE_REQUEST: &str = "historical_blocks_by_range_request";
 pub const BLOCKS_BY_ROOTS_REQUEST: &str = "blocks_by_roots_request";
 pub const LIGHT_CLIENT_BOOTSTRAP_REQUEST: &str = "light_client_bootstrap";
 pub const UNKNOWN_BLOCK_ATTESTATION: &str = "unknown_block_attestation";
@@ -235,6 +257,10 @@ pub struct BeaconProcessorConfig {
     pub max_gossip_attestation_batch_size: usize,
     pub max_gossip_aggregate_batch_size: usize,
     pub enable_backfill_rate_limiting: bool,
+    /// If set, unaggregated attestations which could be processed immediately are instead held
+    /// for this long, so that those arriving in quick succession (on any subnet) are verified in
+    /// a single batch.
+    pub attestation_batch_window: Option<Duration>,
 }
 
 impl Default for BeaconProcessorConfig {
@@ -246,16 +272,18 @@ impl Default for BeaconProcessorConfig {
             max_gossip_attestation_batch_size: DEFAULT_MAX_GOSSIP_ATTESTATION_BATCH_SIZE,
             max_gossip_aggregate_batch_size: DEFAULT_MAX_GOSSIP_AGGREGATE_BATCH_SIZE,
             enable_backfill_rate_limiting: true,
+            attestation_batch_window: None,
         }
     }
 }
 
-// The channels necessary to instantiate a `BeaconProcessor`.
+// The channels (and shared status) necessary to instantiate a `BeaconProcessor`.
 pub struct BeaconProcessorChannels<E: EthSpec> {
     pub beacon_processor_tx: BeaconProcessorSend<E>,
     pub beacon_processor_rx: mpsc::Receiver<WorkEvent<E>>,
     pub work_reprocessing_tx: mpsc::Sender<ReprocessQueueMessage>,
     pub work_reprocessing_rx: mpsc::Receiver<ReprocessQueueMessage>,
+    pub status: Arc<BeaconProcessorStatus>,
 }
 
 impl<E: EthSpec> BeaconProcessorChannels<E> {
@@ -270,6 +298,7 @@ impl<E: EthSpec> BeaconProcessorChannels<E> {
             beacon_processor_rx,
             work_reprocessing_rx,
             work_reprocessing_tx,
+            status: Arc::new(BeaconProcessorStatus::default()),
         }
     }
 }
@@ -284,6 +313,7 @@ impl<E: EthSpec> Default for BeaconProcessorChannels<E> {
 struct FifoQueue<T> {
     queue: VecDeque<T>,
     max_length: usize,
+    dropped: u64,
 }
 
 impl<T> FifoQueue<T> {
@@ -292,6 +322,7 @@ impl<T> FifoQueue<T> {
         Self {
             queue: VecDeque::default(),
             max_length,
+            dropped: 0,
         }
     }
 
@@ -300,6 +331,7 @@ impl<T> FifoQueue<T> {
     /// Drops `item` if the queue is full.
     pub fn push(&mut self, item: T, item_desc: &str, log: &Logger) {
         if self.queue.len() == self.max_length {
+            self.dropped = self.dropped.saturating_add(1);
             error!(
                 log,
                 "Work queue is full";
@@ -321,12 +353,23 @@ impl<T> FifoQueue<T> {
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Returns the state of the queue, for introspection.
+    pub fn status(&self, name: &'static str) -> QueueStatus {
+        QueueStatus {
+            name,
+            length: self.queue.len(),
+            max_length: self.max_length,
+            dropped: self.dropped,
+        }
+    }
 }
 
 /// A simple last-in-first-out queue with a maximum length.
 struct LifoQueue<T> {
     queue: VecDeque<T>,
     max_length: usize,
+    dropped: u64,
 }
 
 impl<T> LifoQueue<T> {
@@ -335,6 +378,7 @@ impl<T> LifoQueue<T> {
         Self {
             queue: VecDeque::default(),
             max_length,
+            dropped: 0,
         }
     }
 
@@ -344,6 +388,7 @@ impl<T> LifoQueue<T> {
     pub fn push(&mut self, item: T) {
         if self.queue.len() == self.max_length {
             self.queue.pop_back();
+            self.dropped = self.dropped.saturating_add(1);
         }
         self.queue.push_front(item);
     }
@@ -353,6 +398,66 @@ impl<T> LifoQueue<T> {
         self.queue.pop_front()
     }
 
+    /// Remove up to `max` items for which `predicate` returns `true`, searching only the first
+    /// `search_len` items of the queue.
+    ///
+    /// The removed items are returned in the order in which they would have been popped.
+    pub fn pop_matching(
+        &mut self,
+        max: usize,
+        search_len: usize,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Vec<T> {
+        let mut matching = Vec::with_capacity(max);
+        let mut i = 0;
+        for _ in 0..search_len {
+            if matching.len() >= max || i >= self.queue.len() {
+                break;
+            }
+            if predicate(&self.queue[i]) {
+                matching.extend(self.queue.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        matching
+    }
+
+    /// Remove up to `max` items to be processed as a batch.
+    ///
+    /// The batch starts with the next item in the queue. It is filled with the items which have
+    /// the same `key` as the first item, searching only the first `search_len` items, and then
+    /// topped up with the next items in the queue. Items without a key are never matched.
+    pub fn pop_batch<K: PartialEq>(
+        &mut self,
+        max: usize,
+        search_len: usize,
+        key: impl Fn(&T) -> Option<K>,
+    ) -> Vec<T> {
+        let mut batch = Vec::with_capacity(max);
+        let first = match self.pop() {
+            Some(first) => first,
+            None => return batch,
+        };
+        let first_key = key(&first);
+        batch.push(first);
+
+        if first_key.is_some() {
+            batch.extend(
+                self.pop_matching(max.saturating_sub(1), search_len, |item| {
+                    key(item) == first_key
+                }),
+            );
+        }
+        while batch.len() < max {
+            match self.pop() {
+                Some(item) => batch.push(item),
+                None => break,
+            }
+        }
+        batch
+    }
+
     /// Returns `true` if the queue is full.
     pub fn is_full(&self) -> bool {
         self.queue.len() >= self.max_length
@@ -362,6 +467,24 @@ impl<T> LifoQueue<T> {
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Returns the state of the queue, for introspection.
+    pub fn status(&self, name: &'static str) -> QueueStatus {
+        QueueStatus {
+            name,
+            length: self.queue.len(),
+            max_length: self.max_length,
+            dropped: self.dropped,
+        }
+    }
+}
+
+/// Returns the subnet of a `Work::GossipAttestation` item.
+fn attestation_subnet_id<E: EthSpec>(work: &Work<E>) -> Option<SubnetId> {
+    match work {
+        Work::GossipAttestation { attestation, .. } => Some(attestation.subnet_id),
+        _ => None,
+    }
 }
 
 /// A handle that sends a message on the provided channel to a receiver when it gets dropped.
@@ -588,6 +711,7 @@ pub enum Work<E: EthSpec> {
     ChainSegmentBackfill(AsyncFn),
     Status(BlockingFn),
     BlocksByRangeRequest(BlockingFnWithManualSendOnIdle),
+    HistoricalBlocksByRangeRequest(BlockingFnWithManualSendOnIdle),
     BlocksByRootsRequest(BlockingFnWithManualSendOnIdle),
     GossipBlsToExecutionChange(BlockingFn),
     LightClientBootstrapRequest(BlockingFn),
@@ -624,6 +748,7 @@ impl<E: EthSpec> Work<E> {
             Work::ChainSegmentBackfill(_) => CHAIN_SEGMENT_BACKFILL,
             Work::Status(_) => STATUS_PROCESSING,
             Work::BlocksByRangeRequest(_) => BLOCKS_BY_RANGE_REQUEST,
+            Work::HistoricalBlocksByRangeRequest(_) => HISTORICAL_BLOCKS_BY_RANGE_REQUEST,
             Work::BlocksByRootsRequest(_) => BLOCKS_BY_ROOTS_REQUEST,
             Work::LightClientBootstrapRequest(_) => LIGHT_CLIENT_BOOTSTRAP_REQUEST,
             Work::UnknownBlockAttestation { .. } => UNKNOWN_BLOCK_ATTESTATION,
@@ -634,6 +759,42 @@ impl<E: EthSpec> Work<E> {
             Work::ApiRequestP1 { .. } => API_REQUEST_P1,
         }
     }
+
+    /// Returns the class of work whose share of the workers is limited by the
+    /// `PriorityWeights`, if any.
+    fn priority_class(&self) -> Option<WorkClass> {
+        match self {
+            Work::GossipAttestation { .. }
+            | Work::GossipAttestationBatch { .. }
+            | Work::GossipAggregate { .. }
+            | Work::GossipAggregateBatch { .. }
+            | Work::UnknownBlockAttestation { .. }
+            | Work::UnknownBlockAggregate { .. } => Some(WorkClass::Attestations),
+            Work::GossipSyncSignature(_) | Work::GossipSyncContribution(_) => {
+                Some(WorkClass::SyncCommittee)
+            }
+            Work::Status(_)
+            | Work::BlocksByRangeRequest(_)
+            | Work::HistoricalBlocksByRangeRequest(_)
+            | Work::BlocksByRootsRequest(_)
+            | Work::LightClientBootstrapRequest(_) => Some(WorkClass::Rpc),
+            Work::ApiRequestP1(_) => Some(WorkClass::Api),
+            Work::ChainSegmentBackfill(_) => Some(WorkClass::Backfill),
+            Work::GossipBlock(_)
+            | Work::DelayedImportBlock { .. }
+            | Work::RpcBlock { .. }
+            | Work::IgnoredRpcBlock { .. }
+            | Work::ChainSegment(_)
+            | Work::GossipVoluntaryExit(_)
+            | Work::GossipProposerSlashing(_)
+            | Work::GossipAttesterSlashing(_)
+            | Work::GossipLightClientFinalityUpdate(_)
+            | Work::GossipLightClientOptimisticUpdate(_)
+            | Work::UnknownLightClientOptimisticUpdate { .. }
+            | Work::GossipBlsToExecutionChange(_)
+            | Work::ApiRequestP0(_) => None,
+        }
+    }
 }
 
 /// Unifies all the messages processed by the `BeaconProcessor`.
@@ -644,6 +805,8 @@ enum InboundEvent<E: EthSpec> {
     WorkEvent(WorkEvent<E>),
     /// A work event that was queued for re-processing has become ready.
     ReprocessingWork(WorkEvent<E>),
+    /// The unaggregated attestations held for the batch window are ready to be processed.
+    AttestationBatchReady,
 }
 
 /// Combines the various incoming event streams for the `BeaconProcessor` into a single stream.
@@ -657,6 +820,8 @@ struct InboundEvents<E: EthSpec> {
     event_rx: mpsc::Receiver<WorkEvent<E>>,
     /// Used internally for queuing work ready to be re-processed.
     reprocess_work_rx: mpsc::Receiver<ReadyWork>,
+    /// Fires at the end of the window in which unaggregated attestations are held for batching.
+    attestation_batch_timer: Option<Pin<Box<tokio::time::Sleep>>>,
 }
 
 impl<E: EthSpec> Stream for InboundEvents<E> {
@@ -675,6 +840,13 @@ impl<E: EthSpec> Stream for InboundEvents<E> {
             Poll::Pending => {}
         }
 
+        if let Some(attestation_batch_timer) = self.attestation_batch_timer.as_mut() {
+            if attestation_batch_timer.as_mut().poll(cx).is_ready() {
+                self.attestation_batch_timer = None;
+                return Poll::Ready(Some(InboundEvent::AttestationBatchReady));
+            }
+        }
+
         // Poll for delayed blocks before polling for new work. It might be the case that a delayed
         // block is required to successfully process some new work.
         match self.reprocess_work_rx.poll_recv(cx) {
@@ -710,6 +882,7 @@ pub struct BeaconProcessor<E: EthSpec> {
     pub executor: TaskExecutor,
     pub current_workers: usize,
     pub config: BeaconProcessorConfig,
+    pub status: Arc<BeaconProcessorStatus>,
     pub log: Logger,
 }
 
@@ -744,6 +917,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
         let mut aggregate_debounce = TimeLatch::default();
         let mut attestation_queue = LifoQueue::new(MAX_UNAGGREGATED_ATTESTATION_QUEUE_LEN);
         let mut attestation_debounce = TimeLatch::default();
+        let mut last_status_update: Option<Instant> = None;
         let mut unknown_block_aggregate_queue =
             LifoQueue::new(MAX_AGGREGATED_ATTESTATION_REPROCESS_QUEUE_LEN);
         let mut unknown_block_attestation_queue =
@@ -778,6 +952,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
 
         let mut status_queue = FifoQueue::new(MAX_STATUS_QUEUE_LEN);
         let mut bbrange_queue = FifoQueue::new(MAX_BLOCKS_BY_RANGE_QUEUE_LEN);
+        let mut historical_bbrange_queue = FifoQueue::new(MAX_HISTORICAL_BLOCKS_BY_RANGE_QUEUE_LEN);
         let mut bbroots_queue = FifoQueue::new(MAX_BLOCKS_BY_ROOTS_QUEUE_LEN);
 
         let mut gossip_bls_to_execution_change_queue =
@@ -810,16 +985,29 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 idle_rx,
                 event_rx,
                 reprocess_work_rx: ready_work_rx,
+                attestation_batch_timer: None,
             };
 
             let enable_backfill_rate_limiting = self.config.enable_backfill_rate_limiting;
+            let attestation_batch_window = self.config.attestation_batch_window;
 
             loop {
+                let mut attestation_batch_ready = false;
                 let work_event = match inbound_events.next().await {
                     Some(InboundEvent::WorkerIdle) => {
                         self.current_workers = self.current_workers.saturating_sub(1);
                         None
                     }
+                    // Treat the end of the batch window like an idle worker, so that the held
+                    // attestations are served by the usual prioritization. If all workers are busy
+                    // the attestations remain queued until one becomes idle.
+                    Some(InboundEvent::AttestationBatchReady) => {
+                        if self.current_workers >= self.config.max_workers {
+                            continue;
+                        }
+                        attestation_batch_ready = true;
+                        None
+                    }
                     Some(InboundEvent::WorkEvent(event)) if enable_backfill_rate_limiting => {
                         match QueuedBackfillBatch::try_from(event) {
                             Ok(backfill_batch) => {
@@ -880,15 +1068,16 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         &metrics::BEACON_PROCESSOR_WORK_EVENTS_RX_COUNT,
                         &[event.work.str_id()],
                     );
-                } else {
+                } else if !attestation_batch_ready {
                     metrics::inc_counter(&metrics::BEACON_PROCESSOR_IDLE_EVENTS_TOTAL);
                 }
 
                 if let Some(work_journal_tx) = &work_journal_tx {
-                    let id = work_event
-                        .as_ref()
-                        .map(|event| event.work.str_id())
-                        .unwrap_or(WORKER_FREED);
+                    let id = match &work_event {
+                        Some(event) => event.work.str_id(),
+                        None if attestation_batch_ready => ATTESTATION_BATCH_READY,
+                        None => WORKER_FREED,
+                    };
 
                     // We don't care if this message was successfully sent, we only use the journal
                     // during testing.
@@ -896,6 +1085,12 @@ impl<E: EthSpec> BeaconProcessor<E> {
                 }
 
                 let can_spawn = self.current_workers < self.config.max_workers;
+                // Some classes of work may be restricted to a share of the workers, so that the
+                // remainder are kept free for block import.
+                let priority_weights = self.status.priority_weights();
+                let current_workers = self.current_workers;
+                let max_workers = self.config.max_workers;
+                let allows = |class| priority_weights.allows(class, current_workers, max_workers);
                 let drop_during_sync = work_event
                     .as_ref()
                     .map_or(false, |event| event.drop_during_sync);
@@ -929,7 +1124,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         // Check the aggregates, *then* the unaggregates since we assume that
                         // aggregates are more valuable to local validators and effectively give us
                         // more information with less signature verification time.
-                        } else if aggregate_queue.len() > 0 {
+                        } else if allows(WorkClass::Attestations) && aggregate_queue.len() > 0 {
                             let batch_size = cmp::min(
                                 aggregate_queue.len(),
                                 self.config.max_gossip_aggregate_batch_size,
@@ -989,7 +1184,7 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         // Check the unaggregated attestation queue.
                         //
                         // Potentially use batching.
-                        } else if attestation_queue.len() > 0 {
+                        } else if allows(WorkClass::Attestations) && attestation_queue.len() > 0 {
                             let batch_size = cmp::min(
                                 attestation_queue.len(),
                                 self.config.max_gossip_attestation_batch_size,
@@ -1004,28 +1199,34 @@ impl<E: EthSpec> BeaconProcessor<E> {
                                 // Collect two or more attestations into a batch, so they can take
                                 // advantage of batch signature verification.
                                 //
+                                // The batch is filled with attestations from the same subnet as
+                                // the most recent attestation first. They are likely to share a
+                                // committee, which the worker then only reads from the shuffling
+                                // cache once, rather than contending with other workers for it.
+                                //
                                 // Note: this will convert the `Work::GossipAttestation` item into a
                                 // `Work::GossipAttestationBatch` item.
+                                let items = attestation_queue.pop_batch(
+                                    batch_size,
+                                    ATTESTATION_BATCH_SUBNET_SEARCH_LEN,
+                                    attestation_subnet_id,
+                                );
+
                                 let mut attestations = Vec::with_capacity(batch_size);
                                 let mut process_batch_opt = None;
-                                for _ in 0..batch_size {
-                                    if let Some(item) = attestation_queue.pop() {
-                                        match item {
-                                            Work::GossipAttestation {
-                                                attestation,
-                                                process_individual: _,
-                                                process_batch,
-                                            } => {
-                                                attestations.push(attestation);
-                                                if process_batch_opt.is_none() {
-                                                    process_batch_opt = Some(process_batch);
-                                                }
+                                for item in items {
+                                    match item {
+                                        Work::GossipAttestation {
+                                            attestation,
+                                            process_individual: _,
+                                            process_batch,
+                                        } => {
+                                            attestations.push(attestation);
+                                            if process_batch_opt.is_none() {
+                                                process_batch_opt = Some(process_batch);
                                             }
-                                            _ => error!(
-                                                self.log,
-                                                "Invalid item in attestation queue"
-                                            ),
                                         }
+                                        _ => error!(self.log, "Invalid item in attestation queue"),
                                     }
                                 }
 
@@ -1049,24 +1250,44 @@ impl<E: EthSpec> BeaconProcessor<E> {
                             }
                         // Check sync committee messages after attestations as their rewards are lesser
                         // and they don't influence fork choice.
-                        } else if let Some(item) = sync_contribution_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::SyncCommittee)
+                            .then(|| sync_contribution_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = sync_message_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::SyncCommittee)
+                            .then(|| sync_message_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
                         // Aggregates and unaggregates queued for re-processing are older and we
                         // care about fresher ones, so check those first.
-                        } else if let Some(item) = unknown_block_aggregate_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::Attestations)
+                            .then(|| unknown_block_aggregate_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = unknown_block_attestation_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::Attestations)
+                            .then(|| unknown_block_attestation_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
                         // Check RPC methods next. Status messages are needed for sync so
                         // prioritize them over syncing requests from other peers (BlocksByRange
                         // and BlocksByRoot)
-                        } else if let Some(item) = status_queue.pop() {
+                        } else if let Some(item) =
+                            allows(WorkClass::Rpc).then(|| status_queue.pop()).flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = bbrange_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::Rpc)
+                            .then(|| bbrange_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
-                        } else if let Some(item) = bbroots_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::Rpc)
+                            .then(|| bbroots_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
                         // Check slashings after all other consensus messages so we prioritize
                         // following head.
@@ -1087,13 +1308,29 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         // processed all the interesting things from the network
                         // and things required for us to stay in good repute
                         // with our P2P peers.
-                        } else if let Some(item) = api_request_p1_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::Api)
+                            .then(|| api_request_p1_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
                         // Handle backfill sync chain segments.
-                        } else if let Some(item) = backfill_chain_segment.pop() {
+                        } else if let Some(item) = allows(WorkClass::Backfill)
+                            .then(|| backfill_chain_segment.pop())
+                            .flatten()
+                        {
+                            self.spawn_worker(item, idle_tx);
+                        // Serve requests for finalized history last when the node is near its
+                        // bandwidth budget, so that syncing peers never crowd out gossip.
+                        } else if let Some(item) = allows(WorkClass::Rpc)
+                            .then(|| historical_bbrange_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
                         // This statement should always be the final else statement.
-                        } else if let Some(item) = lcbootstrap_queue.pop() {
+                        } else if let Some(item) = allows(WorkClass::Rpc)
+                            .then(|| lcbootstrap_queue.pop())
+                            .flatten()
+                        {
                             self.spawn_worker(item, idle_tx);
                         } else {
                             // Let the journal know that a worker is freed and there's nothing else
@@ -1138,7 +1375,19 @@ impl<E: EthSpec> BeaconProcessor<E> {
                         let work_id = work.str_id();
 
                         match work {
-                            _ if can_spawn => self.spawn_worker(work, idle_tx),
+                            Work::GossipAttestation { .. }
+                                if can_spawn && attestation_batch_window.is_some() =>
+                            {
+                                attestation_queue.push(work);
+                                if inbound_events.attestation_batch_timer.is_none() {
+                                    inbound_events.attestation_batch_timer =
+                                        attestation_batch_window
+                                            .map(|window| Box::pin(tokio::time::sleep(window)));
+                                }
+                            }
+                            _ if can_spawn && work.priority_class().map_or(true, allows) => {
+                                self.spawn_worker(work, idle_tx)
+                            }
                             Work::GossipAttestation { .. } => attestation_queue.push(work),
                             // Attestation batches are formed internally within the
                             // `BeaconProcessor`, they are not sent from external services.
@@ -1193,6 +1442,9 @@ impl<E: EthSpec> BeaconProcessor<E> {
                             Work::BlocksByRangeRequest { .. } => {
                                 bbrange_queue.push(work, work_id, &self.log)
                             }
+                            Work::HistoricalBlocksByRangeRequest { .. } => {
+                                historical_bbrange_queue.push(work, work_id, &self.log)
+                            }
                             Work::BlocksByRootsRequest { .. } => {
                                 bbroots_queue.push(work, work_id, &self.log)
                             }
@@ -1282,6 +1534,61 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     api_request_p1_queue.len() as i64,
                 );
 
+                // Keep serving the attestations held for the batch window whilst there are workers
+                // free for them, rather than waiting for a worker to become idle.
+                if attestation_batch_ready
+                    && attestation_queue.len() > 0
+                    && self.status.priority_weights().allows(
+                        WorkClass::Attestations,
+                        self.current_workers,
+                        self.config.max_workers,
+                    )
+                {
+                    inbound_events.attestation_batch_timer =
+                        Some(Box::pin(tokio::time::sleep(Duration::ZERO)));
+                }
+
+                // Copying every queue's status after each event would be wasteful under load, so
+                // the snapshot is updated periodically. It is always updated once the processor
+                // is idle, so that the snapshot is never left showing work which has completed.
+                if self.current_workers == 0
+                    || last_status_update
+                        .map_or(true, |updated| updated.elapsed() >= STATUS_UPDATE_INTERVAL)
+                {
+                    last_status_update = Some(Instant::now());
+                    self.status.update(
+                        &[
+                            chain_segment_queue.status("chain_segment"),
+                            rpc_block_queue.status("rpc_block"),
+                            delayed_block_queue.status("delayed_block"),
+                            gossip_block_queue.status("gossip_block"),
+                            api_request_p0_queue.status("api_request_p0"),
+                            aggregate_queue.status("aggregate"),
+                            attestation_queue.status("attestation"),
+                            sync_contribution_queue.status("sync_contribution"),
+                            sync_message_queue.status("sync_message"),
+                            unknown_block_aggregate_queue.status("unknown_block_aggregate"),
+                            unknown_block_attestation_queue.status("unknown_block_attestation"),
+                            status_queue.status("status"),
+                            bbrange_queue.status("blocks_by_range"),
+                            bbroots_queue.status("blocks_by_roots"),
+                            gossip_attester_slashing_queue.status("attester_slashing"),
+                            gossip_proposer_slashing_queue.status("proposer_slashing"),
+                            gossip_voluntary_exit_queue.status("voluntary_exit"),
+                            gossip_bls_to_execution_change_queue.status("bls_to_execution_change"),
+                            api_request_p1_queue.status("api_request_p1"),
+                            backfill_chain_segment.status("backfill_chain_segment"),
+                            historical_bbrange_queue.status("historical_blocks_by_range"),
+                            lcbootstrap_queue.status("light_client_bootstrap"),
+                            finality_update_queue.status("light_client_finality_update"),
+                            optimistic_update_queue.status("light_client_optimistic_update"),
+                            unknown_light_client_update_queue.status("unknown_light_client_update"),
+                        ],
+                        self.current_workers,
+                        self.config.max_workers,
+                    );
+                }
+
                 if aggregate_queue.is_full() && aggregate_debounce.elapsed() {
                     error!(
                         self.log,
@@ -1393,7 +1700,9 @@ impl<E: EthSpec> BeaconProcessor<E> {
             Work::GossipBlock(work) => task_spawner.spawn_async(async move {
                 work.await;
             }),
-            Work::BlocksByRangeRequest(work) | Work::BlocksByRootsRequest(work) => {
+            Work::BlocksByRangeRequest(work)
+            | Work::HistoricalBlocksByRangeRequest(work)
+            | Work::BlocksByRootsRequest(work) => {
                 task_spawner.spawn_blocking_with_manual_send_idle(work)
             }
             Work::ChainSegmentBackfill(process_fn) => task_spawner.spawn_async(process_fn),
@@ -1502,3 +1811,77 @@ impl Drop for SendOnDrop {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A queue of `(subnet, id)` pairs, where the `id`s are pushed in ascending order.
+    fn new_queue(subnets: &[u64]) -> LifoQueue<(u64, usize)> {
+        let mut queue = LifoQueue::new(subnets.len());
+        for (id, subnet) in subnets.iter().enumerate() {
+            queue.push((*subnet, id));
+        }
+        queue
+    }
+
+    fn ids(items: &[(u64, usize)]) -> Vec<usize> {
+        items.iter().map(|(_, id)| *id).collect()
+    }
+
+    fn drain(mut queue: LifoQueue<(u64, usize)>) -> Vec<usize> {
+        std::iter::from_fn(|| queue.pop().map(|(_, id)| id)).collect()
+    }
+
+    #[test]
+    fn pop_matching_keeps_the_order_of_other_items() {
+        let mut queue = new_queue(&[0, 1, 0, 2, 1, 0]);
+
+        let matching = queue.pop_matching(10, 10, |(subnet, _)| *subnet == 0);
+        assert_eq!(ids(&matching), vec![5, 2, 0]);
+        assert_eq!(drain(queue), vec![4, 3, 1]);
+    }
+
+    #[test]
+    fn pop_matching_respects_max_and_search_len() {
+        let mut queue = new_queue(&[0, 0, 1, 0, 0]);
+        let matching = queue.pop_matching(2, 10, |(subnet, _)| *subnet == 0);
+        assert_eq!(ids(&matching), vec![4, 3]);
+        assert_eq!(drain(queue), vec![2, 1, 0]);
+
+        // Only the first two items are searched.
+        let mut queue = new_queue(&[0, 0, 1, 0, 0]);
+        let matching = queue.pop_matching(10, 2, |(subnet, _)| *subnet == 1);
+        assert!(matching.is_empty());
+        assert_eq!(queue.len(), 5);
+    }
+
+    #[test]
+    fn pop_batch_groups_items_by_key() {
+        let mut queue = new_queue(&[1, 2, 1, 2, 3, 1, 2]);
+
+        // The newest item is on subnet 2, so the batch takes every item on subnet 2.
+        let batch = queue.pop_batch(3, 10, |(subnet, _)| Some(*subnet));
+        assert_eq!(ids(&batch), vec![6, 3, 1]);
+
+        let batch = queue.pop_batch(3, 10, |(subnet, _)| Some(*subnet));
+        assert_eq!(ids(&batch), vec![5, 2, 0]);
+
+        assert_eq!(drain(queue), vec![4]);
+    }
+
+    #[test]
+    fn pop_batch_tops_up_with_other_items() {
+        let mut queue = new_queue(&[1, 2, 3, 1]);
+
+        // Only one other item is on subnet 1, so the batch is topped up in queue order.
+        let batch = queue.pop_batch(3, 10, |(subnet, _)| Some(*subnet));
+        assert_eq!(ids(&batch), vec![3, 0, 2]);
+        assert_eq!(drain(queue), vec![1]);
+
+        // Items without a key are never matched.
+        let mut queue = new_queue(&[1, 2, 1]);
+        let batch = queue.pop_batch(2, 10, |_| None::<u64>);
+        assert_eq!(ids(&batch), vec![2, 1]);
+    }
+}
@@ -0,0 +1,193 @@
+//! Live introspection of the `BeaconProcessor` queues and runtime-adjustable scheduling weights.
+//!
+//! The manager task periodically publishes a snapshot of its queues, which can be read from other
+//! tasks (e.g. the HTTP API) without interrupting the manager. In the other direction, operators
+//! may restrict the share of workers available to some classes of work so that the remaining
+//! workers are kept free for block import under load.
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+/// The maximum (and default) priority weight, which places no restriction on a class of work.
+pub const MAX_PRIORITY_WEIGHT: u8 = 100;
+
+/// The classes of work whose share of the workers can be restricted.
+///
+/// Blocks, chain segments and priority 0 API requests are never restricted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorkClass {
+    /// Gossip attestations and aggregates, including those awaiting re-processing.
+    Attestations,
+    /// Gossip sync committee messages and contributions.
+    SyncCommittee,
+    /// Requests from peers via RPC.
+    Rpc,
+    /// Priority 1 API requests.
+    Api,
+    /// Backfill sync chain segments.
+    Backfill,
+}
+
+/// The percentage of the workers which each class of work may occupy before it must wait.
+///
+/// A class with a weight of `w` may only start new work while fewer than `max_workers * w / 100`
+/// (but at least one) workers are busy. The default weights of `100` retain the usual strict
+/// prioritization of the `BeaconProcessor`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriorityWeights {
+    pub attestations: u8,
+    pub sync_committee: u8,
+    pub rpc: u8,
+    pub api: u8,
+    pub backfill: u8,
+}
+
+impl Default for PriorityWeights {
+    fn default() -> Self {
+        Self {
+            attestations: MAX_PRIORITY_WEIGHT,
+            sync_committee: MAX_PRIORITY_WEIGHT,
+            rpc: MAX_PRIORITY_WEIGHT,
+            api: MAX_PRIORITY_WEIGHT,
+            backfill: MAX_PRIORITY_WEIGHT,
+        }
+    }
+}
+
+impl PriorityWeights {
+    /// Returns an error if any weight is outside of `1..=MAX_PRIORITY_WEIGHT`.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, weight) in [
+            ("attestations", self.attestations),
+            ("sync_committee", self.sync_committee),
+            ("rpc", self.rpc),
+            ("api", self.api),
+            ("backfill", self.backfill),
+        ] {
+            if weight == 0 || weight > MAX_PRIORITY_WEIGHT {
+                return Err(format!(
+                    "{} weight must be between 1 and {}, not {}",
+                    name, MAX_PRIORITY_WEIGHT, weight
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn weight(&self, class: WorkClass) -> u8 {
+        match class {
+            WorkClass::Attestations => self.attestations,
+            WorkClass::SyncCommittee => self.sync_committee,
+            WorkClass::Rpc => self.rpc,
+            WorkClass::Api => self.api,
+            WorkClass::Backfill => self.backfill,
+        }
+    }
+
+    /// Returns `true` if work of `class` may be started whilst `active_workers` are busy.
+    ///
+    /// Work is always allowed to start on an idle processor, so no class can be starved.
+    pub fn allows(&self, class: WorkClass, active_workers: usize, max_workers: usize) -> bool {
+        let limit =
+            max_workers.saturating_mul(self.weight(class) as usize) / MAX_PRIORITY_WEIGHT as usize;
+        active_workers < std::cmp::max(limit, 1)
+    }
+}
+
+/// The state of a single queue of the `BeaconProcessor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueueStatus {
+    pub name: &'static str,
+    pub length: usize,
+    pub max_length: usize,
+    /// The number of items which have been dropped because the queue was full.
+    pub dropped: u64,
+}
+
+/// A point-in-time view of the `BeaconProcessor`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusSnapshot {
+    /// The queues, in the order in which they are served by idle workers.
+    pub queues: Vec<QueueStatus>,
+    pub active_workers: usize,
+    pub max_workers: usize,
+}
+
+/// Shared between the `BeaconProcessor` manager task and any outside observers.
+#[derive(Default)]
+pub struct BeaconProcessorStatus {
+    snapshot: RwLock<StatusSnapshot>,
+    priority_weights: RwLock<PriorityWeights>,
+}
+
+impl BeaconProcessorStatus {
+    /// Returns the most recent snapshot published by the manager task.
+    ///
+    /// The snapshot is empty if the manager has not yet handled any events.
+    pub fn snapshot(&self) -> StatusSnapshot {
+        self.snapshot.read().clone()
+    }
+
+    pub fn priority_weights(&self) -> PriorityWeights {
+        *self.priority_weights.read()
+    }
+
+    /// Replaces the priority weights, which take effect from the next event handled by the
+    /// manager task.
+    pub fn set_priority_weights(&self, priority_weights: PriorityWeights) -> Result<(), String> {
+        priority_weights.validate()?;
+        *self.priority_weights.write() = priority_weights;
+        Ok(())
+    }
+
+    /// Publishes a new snapshot, reusing the allocation of the previous one.
+    pub(crate) fn update(&self, queues: &[QueueStatus], active_workers: usize, max_workers: usize) {
+        let mut snapshot = self.snapshot.write();
+        snapshot.queues.clear();
+        snapshot.queues.extend_from_slice(queues);
+        snapshot.active_workers = active_workers;
+        snapshot.max_workers = max_workers;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_weights_are_unrestricted() {
+        let weights = PriorityWeights::default();
+        for active_workers in 0..8 {
+            assert!(weights.allows(WorkClass::Attestations, active_workers, 8));
+        }
+        assert!(!weights.allows(WorkClass::Attestations, 8, 8));
+    }
+
+    #[test]
+    fn restricted_weights_reserve_workers() {
+        let weights = PriorityWeights {
+            attestations: 50,
+            backfill: 1,
+            ..PriorityWeights::default()
+        };
+        assert!(weights.allows(WorkClass::Attestations, 3, 8));
+        assert!(!weights.allows(WorkClass::Attestations, 4, 8));
+        assert!(weights.allows(WorkClass::Rpc, 7, 8));
+
+        // A class may always use an idle processor.
+        assert!(weights.allows(WorkClass::Backfill, 0, 8));
+        assert!(!weights.allows(WorkClass::Backfill, 1, 8));
+    }
+
+    #[test]
+    fn invalid_weights_are_rejected() {
+        let status = BeaconProcessorStatus::default();
+        for rpc in [0, MAX_PRIORITY_WEIGHT + 1] {
+            let weights = PriorityWeights {
+                rpc,
+                ..PriorityWeights::default()
+            };
+            assert!(status.set_priority_weights(weights).is_err());
+        }
+        assert_eq!(status.priority_weights(), PriorityWeights::default());
+    }
+}
@@ -63,6 +63,11 @@ impl BuilderHttpClient {
         &self.user_agent
     }
 
+    /// Returns the URL of the builder this client is connected to.
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     async fn get_with_timeout<T: DeserializeOwned, U: IntoUrl>(
         &self,
         url: U,
@@ -1,4 +1,5 @@
 use crate::address_change_broadcast::broadcast_address_changes_at_capella;
+use crate::checkpoint_sync::verify_checkpoint_state;
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
@@ -10,7 +11,8 @@ use beacon_chain::{
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     slot_clock::{SlotClock, SystemTimeSlotClock},
     state_advance_timer::spawn_state_advance_timer,
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    state_root_audit::spawn_state_root_auditor,
+    store::{BeaconNodeBackend, HotColdDB, ItemStore, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, MigratorConfig, ServerSentEventHandler,
 };
 use beacon_processor::BeaconProcessorConfig;
@@ -26,6 +28,7 @@ use genesis::{interop_genesis_state, Eth1GenesisService, DEFAULT_ETH1_BLOCK_HASH
 use lighthouse_network::{prometheus_client::registry::Registry, NetworkGlobals};
 use monitoring_api::{MonitoringHttpClient, ProcessType};
 use network::{NetworkConfig, NetworkSenders, NetworkService};
+use otlp::OtlpExporter;
 use slasher::Slasher;
 use slasher_service::SlasherService;
 use slog::{debug, info, warn, Logger};
@@ -67,6 +70,7 @@ pub struct ClientBuilder<T: BeaconChainTypes> {
     eth1_service: Option<Eth1Service>,
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     network_senders: Option<NetworkSenders<T::EthSpec>>,
+    network_drained: Option<exit_future::Exit>,
     gossipsub_registry: Option<Registry>,
     db_path: Option<PathBuf>,
     freezer_db_path: Option<PathBuf>,
@@ -101,6 +105,7 @@ where
             eth1_service: None,
             network_globals: None,
             network_senders: None,
+            network_drained: None,
             gossipsub_registry: None,
             db_path: None,
             freezer_db_path: None,
@@ -282,11 +287,17 @@ where
                     .weak_subjectivity_state(anchor_state, anchor_block, genesis_state)
                     .map(|v| (v, None))?
             }
-            ClientGenesis::CheckpointSyncUrl { url } => {
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                cross_check_urls,
+                quorum,
+            } => {
                 info!(
                     context.log(),
                     "Starting checkpoint sync";
                     "remote_url" => %url,
+                    "cross_check_urls" => cross_check_urls.len(),
+                    "quorum" => quorum,
                 );
                 if config.chain.genesis_backfill {
                     info!(
@@ -295,12 +306,8 @@ where
                     );
                 }
 
-                let remote = BeaconNodeHttpClient::new(
-                    url,
-                    Timeouts::set_all(Duration::from_secs(
-                        config.chain.checkpoint_sync_url_timeout,
-                    )),
-                );
+                let timeout = Duration::from_secs(config.chain.checkpoint_sync_url_timeout);
+                let remote = BeaconNodeHttpClient::new(url.clone(), Timeouts::set_all(timeout));
 
                 let deposit_snapshot = if config.sync_eth1_chain {
                     // We want to fetch deposit snapshot before fetching the finalized beacon state to
@@ -359,6 +366,17 @@ where
 
                 debug!(context.log(), "Downloaded finalized state"; "slot" => ?state.slot());
 
+                let checkpoint_sync_report = verify_checkpoint_state(
+                    &url,
+                    state.slot(),
+                    state.canonical_root(),
+                    &cross_check_urls,
+                    quorum,
+                    timeout,
+                    context.log(),
+                )
+                .await?;
+
                 let finalized_block_slot = state.latest_block_header().slot;
 
                 debug!(context.log(), "Downloading finalized block"; "block_slot" => ?finalized_block_slot);
@@ -412,6 +430,7 @@ where
                     });
 
                 builder
+                    .checkpoint_sync_report(checkpoint_sync_report)
                     .weak_subjectivity_state(state, block, genesis_state)
                     .map(|v| (v, service))?
             }
@@ -449,9 +468,12 @@ where
                         network_senders: None,
                         network_globals: None,
                         beacon_processor_send: None,
+                        beacon_processor_status: None,
                         eth1_service: Some(genesis_service.eth1_service.clone()),
+                        otlp_exporter: None,
                         log: context.log().clone(),
                         sse_logging_components: runtime_context.sse_logging_components.clone(),
+                        runtime_log_filter: runtime_context.runtime_log_filter.clone(),
                     });
 
                     // Discard the error from the oneshot.
@@ -538,7 +560,7 @@ where
             None
         };
 
-        let (network_globals, network_senders) = NetworkService::start(
+        let (network_globals, network_senders, network_drained) = NetworkService::start(
             beacon_chain,
             config,
             context.executor,
@@ -553,6 +575,7 @@ where
 
         self.network_globals = Some(network_globals);
         self.network_senders = Some(network_senders);
+        self.network_drained = Some(network_drained);
         self.gossipsub_registry = gossipsub_registry;
 
         Ok(self)
@@ -680,6 +703,17 @@ where
         let log = runtime_context.log().clone();
 
         let http_api_listen_addr = if self.http_api_config.enabled {
+            let otlp_exporter = self.http_api_config.otlp_endpoint.clone().map(|endpoint| {
+                info!(log, "Tracing block proposals"; "otlp_endpoint" => %endpoint);
+                let (exporter, task) = OtlpExporter::new(endpoint, "lighthouse-beacon-node");
+                let otlp_log = log.clone();
+                runtime_context.executor.spawn(
+                    task.run(move |e| warn!(otlp_log, "Unable to export traces"; "error" => ?e)),
+                    "otlp_export",
+                );
+                exporter
+            });
+
             let ctx = Arc::new(http_api::Context {
                 config: self.http_api_config.clone(),
                 chain: self.beacon_chain.clone(),
@@ -687,7 +721,10 @@ where
                 network_globals: self.network_globals.clone(),
                 eth1_service: self.eth1_service.clone(),
                 beacon_processor_send: Some(beacon_processor_channels.beacon_processor_tx.clone()),
+                beacon_processor_status: Some(beacon_processor_channels.status.clone()),
                 sse_logging_components: runtime_context.sse_logging_components.clone(),
+                runtime_log_filter: runtime_context.runtime_log_filter.clone(),
+                otlp_exporter,
                 log: log.clone(),
             });
 
@@ -743,13 +780,20 @@ where
         }
 
         if let Some(beacon_chain) = self.beacon_chain.as_ref() {
-            if let Some(network_globals) = &self.network_globals {
+            if let (Some(network_globals), Some(network_drained)) =
+                (&self.network_globals, &self.network_drained)
+            {
                 let beacon_processor_context = runtime_context.service_context("bproc".into());
                 BeaconProcessor {
                     network_globals: network_globals.clone(),
-                    executor: beacon_processor_context.executor.clone(),
+                    // Keep processing after the exit signal until the network has drained, so
+                    // that in-flight requests can still be answered.
+                    executor: beacon_processor_context
+                        .executor
+                        .with_exit(network_drained.clone()),
                     current_workers: 0,
                     config: beacon_processor_config,
+                    status: beacon_processor_channels.status,
                     log: beacon_processor_context.log().clone(),
                 }
                 .spawn_manager(
@@ -770,6 +814,17 @@ where
                 state_advance_log,
             );
 
+            if let Some(interval) = beacon_chain.config.state_root_audit_interval {
+                let audit_context = runtime_context.service_context("state_root_audit".into());
+                let audit_log = audit_context.log().clone();
+                spawn_state_root_auditor(
+                    audit_context.executor,
+                    beacon_chain.clone(),
+                    interval,
+                    audit_log,
+                );
+            }
+
             if let Some(execution_layer) = beacon_chain.execution_layer.as_ref() {
                 // Only send a head update *after* genesis.
                 if let Ok(current_slot) = beacon_chain.slot() {
@@ -887,7 +942,15 @@ where
 }
 
 impl<TSlotClock, TEth1Backend, TEthSpec>
-    ClientBuilder<Witness<TSlotClock, TEth1Backend, TEthSpec, LevelDB<TEthSpec>, LevelDB<TEthSpec>>>
+    ClientBuilder<
+        Witness<
+            TSlotClock,
+            TEth1Backend,
+            TEthSpec,
+            BeaconNodeBackend<TEthSpec>,
+            BeaconNodeBackend<TEthSpec>,
+        >,
+    >
 where
     TSlotClock: SlotClock + 'static,
     TEth1Backend: Eth1ChainBackend<TEthSpec> + 'static,
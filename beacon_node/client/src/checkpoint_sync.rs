@@ -0,0 +1,166 @@
+//! Verification of a checkpoint sync state against several remote beacon nodes.
+//!
+//! The state is downloaded from a single provider. Each other provider is asked for the root of
+//! the finalized state at the same slot, and the state is only imported if enough providers agree
+//! with the root of the downloaded state.
+use eth2::lighthouse::{CheckpointSyncDisagreement, CheckpointSyncReport};
+use eth2::types::StateId;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use futures::future::join_all;
+use sensitive_url::SensitiveUrl;
+use slog::{info, warn, Logger};
+use std::time::Duration;
+use types::{Hash256, Slot};
+
+/// The response of a provider asked for the finalized state root at a slot.
+type ProviderResponse = Result<(Hash256, Option<bool>), String>;
+
+/// Asks each of `cross_check_urls` for the root of the state at `state_slot` and checks that at
+/// least `quorum` providers, including `provider`, agree with `state_root`.
+pub async fn verify_checkpoint_state(
+    provider: &SensitiveUrl,
+    state_slot: Slot,
+    state_root: Hash256,
+    cross_check_urls: &[SensitiveUrl],
+    quorum: usize,
+    timeout: Duration,
+    log: &Logger,
+) -> Result<CheckpointSyncReport, String> {
+    // Providers are asked concurrently, so that unresponsive providers delay startup by at most
+    // a single timeout.
+    let responses = join_all(cross_check_urls.iter().map(|url| async move {
+        let remote = BeaconNodeHttpClient::new(url.clone(), Timeouts::set_all(timeout));
+        let response = match remote
+            .get_beacon_states_root(StateId::Slot(state_slot))
+            .await
+        {
+            Ok(Some(response)) => Ok((response.data.root, response.finalized)),
+            Ok(None) => Err("state not found".to_string()),
+            Err(e) => Err(format!("{:?}", e)),
+        };
+        (url.to_string(), response)
+    }))
+    .await;
+
+    let report = tally_responses(
+        provider.to_string(),
+        state_slot,
+        state_root,
+        quorum,
+        responses,
+    );
+
+    for disagreement in &report.disagreed {
+        warn!(
+            log,
+            "Checkpoint sync provider disagrees";
+            "provider" => &disagreement.provider,
+            "state_root" => ?disagreement.state_root,
+            "reason" => &disagreement.reason,
+            "expected_state_root" => ?state_root,
+            "state_slot" => state_slot,
+        );
+    }
+
+    if report.agreed.len() < quorum {
+        return Err(format!(
+            "Checkpoint state root {:?} at slot {} was confirmed by {} of {} providers, but a \
+             quorum of {} is required. Disagreeing providers: {:?}",
+            state_root,
+            state_slot,
+            report.agreed.len(),
+            cross_check_urls.len() + 1,
+            quorum,
+            report
+                .disagreed
+                .iter()
+                .map(|disagreement| &disagreement.provider)
+                .collect::<Vec<_>>()
+        ));
+    }
+
+    if !cross_check_urls.is_empty() {
+        info!(
+            log,
+            "Verified checkpoint state";
+            "agreed" => report.agreed.len(),
+            "disagreed" => report.disagreed.len(),
+            "quorum" => quorum,
+            "state_root" => ?state_root,
+        );
+    }
+
+    Ok(report)
+}
+
+/// Sorts the providers into those which agree with `state_root` and those which do not.
+fn tally_responses(
+    provider: String,
+    state_slot: Slot,
+    state_root: Hash256,
+    quorum: usize,
+    responses: Vec<(String, ProviderResponse)>,
+) -> CheckpointSyncReport {
+    let mut agreed = vec![provider.clone()];
+    let mut disagreed = vec![];
+
+    for (url, response) in responses {
+        match response {
+            Ok((root, finalized)) if root == state_root && finalized != Some(false) => {
+                agreed.push(url)
+            }
+            Ok((root, finalized)) => disagreed.push(CheckpointSyncDisagreement {
+                provider: url,
+                state_root: Some(root),
+                reason: if root != state_root {
+                    "different state root".to_string()
+                } else {
+                    "state is not finalized".to_string()
+                },
+            }),
+            Err(reason) => disagreed.push(CheckpointSyncDisagreement {
+                provider: url,
+                state_root: None,
+                reason,
+            }),
+        }
+    }
+
+    CheckpointSyncReport {
+        provider,
+        state_slot,
+        state_root,
+        quorum,
+        agreed,
+        disagreed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tally() {
+        let root = Hash256::repeat_byte(1);
+        let other_root = Hash256::repeat_byte(2);
+        let responses = vec![
+            ("b".to_string(), Ok((root, Some(true)))),
+            ("c".to_string(), Ok((root, None))),
+            ("d".to_string(), Ok((other_root, Some(true)))),
+            ("e".to_string(), Ok((root, Some(false)))),
+            ("f".to_string(), Err("timeout".to_string())),
+        ];
+
+        let report = tally_responses("a".to_string(), Slot::new(64), root, 3, responses);
+        assert_eq!(report.agreed, vec!["a", "b", "c"]);
+        assert_eq!(
+            report
+                .disagreed
+                .iter()
+                .map(|disagreement| (disagreement.provider.as_str(), disagreement.state_root))
+                .collect::<Vec<_>>(),
+            vec![("d", Some(other_root)), ("e", Some(root)), ("f", None)]
+        );
+    }
+}
@@ -33,7 +33,14 @@ pub enum ClientGenesis {
         anchor_block_bytes: Vec<u8>,
     },
     CheckpointSyncUrl {
+        /// The provider from which the state and block are downloaded.
         url: SensitiveUrl,
+        /// Other providers against which the root of the downloaded state is verified.
+        #[serde(default)]
+        cross_check_urls: Vec<SensitiveUrl>,
+        /// The number of providers, including `url`, which must agree on the state root.
+        #[serde(default = "default_checkpoint_sync_quorum")]
+        quorum: usize,
     },
 }
 
@@ -204,6 +211,11 @@ impl Config {
     }
 }
 
+/// Without other providers, the state is imported as long as the provider serves it.
+fn default_checkpoint_sync_quorum() -> usize {
+    1
+}
+
 /// Ensure that the directory at `path` exists, by creating it and all parents if necessary.
 fn ensure_dir_exists(path: PathBuf) -> Result<PathBuf, String> {
     fs::create_dir_all(&path).map_err(|e| format!("Unable to create {}: {}", path.display(), e))?;
@@ -1,6 +1,7 @@
 extern crate slog;
 
 mod address_change_broadcast;
+mod checkpoint_sync;
 pub mod config;
 mod metrics;
 mod notifier;
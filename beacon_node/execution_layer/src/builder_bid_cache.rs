@@ -0,0 +1,32 @@
+use eth2::types::SseBuilderBid;
+use lru::LruCache;
+use parking_lot::Mutex;
+use types::Slot;
+
+pub const DEFAULT_BUILDER_BID_CACHE_SIZE: usize = 8;
+
+/// A cache of the most recent builder bid received for each slot.
+///
+/// This allows a payload which is later revealed by a builder to be attributed to the relay
+/// and bid value which produced it.
+pub struct BuilderBidCache {
+    bids: Mutex<LruCache<Slot, SseBuilderBid>>,
+}
+
+impl Default for BuilderBidCache {
+    fn default() -> Self {
+        BuilderBidCache {
+            bids: Mutex::new(LruCache::new(DEFAULT_BUILDER_BID_CACHE_SIZE)),
+        }
+    }
+}
+
+impl BuilderBidCache {
+    pub fn put(&self, bid: SseBuilderBid) -> Option<SseBuilderBid> {
+        self.bids.lock().put(bid.slot, bid)
+    }
+
+    pub fn get(&self, slot: Slot) -> Option<SseBuilderBid> {
+        self.bids.lock().get(&slot).cloned()
+    }
+}
@@ -153,7 +153,7 @@ pub struct ExecutionBlockWithTransactions<T: EthSpec> {
     pub fee_recipient: Address,
     pub state_root: Hash256,
     pub receipts_root: Hash256,
-    #[serde(with = "ssz_types::serde_utils::hex_fixed_vec")]
+    #[serde(with = "types::ssz_hex")]
     pub logs_bloom: FixedVector<u8, T::BytesPerLogsBloom>,
     #[serde(alias = "mixHash")]
     pub prev_randao: Hash256,
@@ -165,7 +165,7 @@ pub struct ExecutionBlockWithTransactions<T: EthSpec> {
     pub gas_used: u64,
     #[serde(with = "serde_utils::u64_hex_be")]
     pub timestamp: u64,
-    #[serde(with = "ssz_types::serde_utils::hex_var_list")]
+    #[serde(with = "types::ssz_hex")]
     pub extra_data: VariableList<u8, T::MaxExtraDataBytes>,
     pub base_fee_per_gas: Uint256,
     #[serde(rename = "hash")]
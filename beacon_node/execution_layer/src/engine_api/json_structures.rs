@@ -2,9 +2,7 @@ use super::*;
 use serde::{Deserialize, Serialize};
 use strum::EnumString;
 use superstruct::superstruct;
-use types::{
-    EthSpec, ExecutionBlockHash, FixedVector, Transactions, Unsigned, VariableList, Withdrawal,
-};
+use types::{EthSpec, ExecutionBlockHash, FixedVector, Transactions, VariableList, Withdrawal};
 use types::{ExecutionPayload, ExecutionPayloadCapella, ExecutionPayloadMerge};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -76,7 +74,7 @@ pub struct JsonExecutionPayload<T: EthSpec> {
     pub fee_recipient: Address,
     pub state_root: Hash256,
     pub receipts_root: Hash256,
-    #[serde(with = "serde_logs_bloom")]
+    #[serde(with = "types::ssz_hex")]
     pub logs_bloom: FixedVector<u8, T::BytesPerLogsBloom>,
     pub prev_randao: Hash256,
     #[serde(with = "serde_utils::u64_hex_be")]
@@ -87,12 +85,12 @@ pub struct JsonExecutionPayload<T: EthSpec> {
     pub gas_used: u64,
     #[serde(with = "serde_utils::u64_hex_be")]
     pub timestamp: u64,
-    #[serde(with = "ssz_types::serde_utils::hex_var_list")]
+    #[serde(with = "types::ssz_hex")]
     pub extra_data: VariableList<u8, T::MaxExtraDataBytes>,
     #[serde(with = "serde_utils::u256_hex_be")]
     pub base_fee_per_gas: Uint256,
     pub block_hash: ExecutionBlockHash,
-    #[serde(with = "ssz_types::serde_utils::list_of_hex_var_list")]
+    #[serde(with = "types::ssz_hex::list")]
     pub transactions: Transactions<T>,
     #[superstruct(only(V2))]
     pub withdrawals: VariableList<JsonWithdrawal, T::MaxWithdrawalsPerPayload>,
@@ -496,7 +494,7 @@ impl From<ForkchoiceUpdatedResponse> for JsonForkchoiceUpdatedV1Response {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(bound = "E: EthSpec")]
 pub struct JsonExecutionPayloadBodyV1<E: EthSpec> {
-    #[serde(with = "ssz_types::serde_utils::list_of_hex_var_list")]
+    #[serde(with = "types::ssz_hex::list")]
     pub transactions: Transactions<E>,
     pub withdrawals: Option<VariableList<JsonWithdrawal, E::MaxWithdrawalsPerPayload>>,
 }
@@ -526,32 +524,3 @@ pub struct TransitionConfigurationV1 {
     #[serde(with = "serde_utils::u64_hex_be")]
     pub terminal_block_number: u64,
 }
-
-/// Serializes the `logs_bloom` field of an `ExecutionPayload`.
-pub mod serde_logs_bloom {
-    use super::*;
-    use serde::{Deserializer, Serializer};
-    use serde_utils::hex::PrefixedHexVisitor;
-
-    pub fn serialize<S, U>(bytes: &FixedVector<u8, U>, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-        U: Unsigned,
-    {
-        let mut hex_string: String = "0x".to_string();
-        hex_string.push_str(&hex::encode(&bytes[..]));
-
-        serializer.serialize_str(&hex_string)
-    }
-
-    pub fn deserialize<'de, D, U>(deserializer: D) -> Result<FixedVector<u8, U>, D::Error>
-    where
-        D: Deserializer<'de>,
-        U: Unsigned,
-    {
-        let vec = deserializer.deserialize_string(PrefixedHexVisitor)?;
-
-        FixedVector::new(vec)
-            .map_err(|e| serde::de::Error::custom(format!("invalid logs bloom: {:?}", e)))
-    }
-}
@@ -4,7 +4,10 @@
 //! This crate only provides useful functionality for "The Merge", it does not provide any of the
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
+use crate::builder_bid_cache::BuilderBidCache;
 use crate::payload_cache::PayloadCache;
+use crate::proposal_decisions::ProposalDecisionLog;
+use crate::relay_stats::{HeaderOutcome, RelayStatsTracker};
 use arc_swap::ArcSwapOption;
 use auth::{strip_prefix, Auth, JwtKey};
 use builder_client::BuilderHttpClient;
@@ -15,7 +18,11 @@ pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
 use engines::{Engine, EngineError};
 pub use engines::{EngineState, ForkchoiceState};
 use eth2::types::builder_bid::SignedBuilderBid;
+use eth2::types::{
+    ProposalDecision, ProposalPayloadSource, RelayStats, SseBlockValue, SseBuilderBid,
+};
 use fork_choice::ForkchoiceUpdateParameters;
+use futures::future::{join_all, select_ok};
 use lru::LruCache;
 use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
@@ -28,13 +35,14 @@ use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
-use strum::AsRefStr;
+use strum::{AsRefStr, IntoStaticStr};
 use task_executor::TaskExecutor;
 use tokio::{
-    sync::{Mutex, MutexGuard, RwLock},
+    sync::{mpsc, Mutex, MutexGuard, RwLock},
     time::sleep,
 };
 use tokio_stream::wrappers::WatchStream;
@@ -47,12 +55,15 @@ use types::{
 };
 
 mod block_hash;
+pub mod builder_bid_cache;
 mod engine_api;
 pub mod engines;
 mod keccak;
 mod metrics;
 pub mod payload_cache;
 mod payload_status;
+pub mod proposal_decisions;
+pub mod relay_stats;
 pub mod test_utils;
 
 /// Indicates the default jwt authenticated execution endpoint.
@@ -65,6 +76,11 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: usize = 128;
 
+/// The number of payload bodies fetched from the execution node to reconstruct full blocks which
+/// are cached, so that blocks requested repeatedly, e.g. by several peers or API clients, are only
+/// fetched once.
+const PAYLOAD_BODIES_LRU_CACHE_SIZE: usize = 64;
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -210,15 +226,24 @@ pub enum FailedCondition {
 
 struct Inner<E: EthSpec> {
     engine: Arc<Engine>,
+    /// Engines which mirror the calls made to `engine` to cross-check its responses.
+    cross_check_engines: Vec<CrossCheckEngine>,
     builder: ArcSwapOption<BuilderHttpClient>,
+    /// Relays which are queried for bids alongside `builder`.
+    additional_builders: Vec<Arc<BuilderHttpClient>>,
+    relay_stats: RelayStatsTracker,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
     execution_blocks: Mutex<LruCache<ExecutionBlockHash, ExecutionBlock>>,
+    payload_bodies: Mutex<LruCache<ExecutionBlockHash, ExecutionPayloadBodyV1<E>>>,
     proposers: RwLock<HashMap<ProposerKey, Proposer>>,
     executor: TaskExecutor,
     payload_cache: PayloadCache<E>,
+    builder_bid_cache: BuilderBidCache,
     builder_profit_threshold: Uint256,
+    builder_bid_margin_percent: u64,
+    proposal_decisions: ProposalDecisionLog,
     log: Logger,
     always_prefer_builder_payload: bool,
     /// Track whether the last `newPayload` call errored.
@@ -231,12 +256,21 @@ struct Inner<E: EthSpec> {
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// Endpoint urls for EL nodes that are running the engine api.
+    ///
+    /// The first endpoint is canonical: its responses are used by the beacon node and it produces
+    /// payloads. Any other endpoints are sent the same `newPayload` and `forkchoiceUpdated` calls
+    /// so that their responses can be cross-checked against the canonical endpoint.
     pub execution_endpoints: Vec<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
     pub builder_url: Option<SensitiveUrl>,
+    /// Endpoint urls for further builder api services which are asked for bids alongside
+    /// `builder_url`. The highest valid bid is used.
+    pub additional_builder_urls: Vec<SensitiveUrl>,
     /// User agent to send with requests to the builder API.
     pub builder_user_agent: Option<String>,
     /// JWT secrets for the above endpoints running the engine api.
+    ///
+    /// Endpoints without a corresponding secret use the first secret.
     pub secret_files: Vec<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
@@ -249,6 +283,9 @@ pub struct Config {
     pub default_datadir: PathBuf,
     /// The minimum value of an external payload for it to be considered in a proposal.
     pub builder_profit_threshold: u128,
+    /// The percentage by which a builder bid must exceed the value of the local payload for the
+    /// builder payload to be used.
+    pub builder_bid_margin_percent: u64,
     pub execution_timeout_multiplier: Option<u32>,
     pub always_prefer_builder_payload: bool,
 }
@@ -260,12 +297,137 @@ pub struct ExecutionLayer<T: EthSpec> {
     inner: Arc<Inner<T>>,
 }
 
+/// The result of comparing the status returned by a cross-check engine to that returned by the
+/// canonical engine.
+#[derive(Clone, Copy, Debug, PartialEq, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+enum CrossCheckOutcome {
+    /// Both engines returned the same status.
+    Agree,
+    /// One engine considers the payload valid and the other invalid.
+    Disagree,
+    /// The statuses differ, but one engine is yet to determine the validity of the payload.
+    Inconclusive,
+    /// The cross-check engine returned an error.
+    Error,
+    /// The request was not made because too many requests were waiting for the cross-check
+    /// engine.
+    Dropped,
+}
+
+impl CrossCheckOutcome {
+    fn compare(canonical: PayloadStatusV1Status, other: PayloadStatusV1Status) -> Self {
+        let is_invalid = |status| {
+            matches!(
+                status,
+                PayloadStatusV1Status::Invalid | PayloadStatusV1Status::InvalidBlockHash
+            )
+        };
+        let is_valid = |status| status == PayloadStatusV1Status::Valid;
+
+        if canonical == other {
+            Self::Agree
+        } else if (is_valid(canonical) && is_invalid(other))
+            || (is_invalid(canonical) && is_valid(other))
+        {
+            Self::Disagree
+        } else {
+            Self::Inconclusive
+        }
+    }
+}
+
+/// The number of requests which may wait for each cross-check engine before further requests are
+/// dropped.
+const CROSS_CHECK_QUEUE_LEN: usize = 64;
+
+/// A request to a cross-check engine, including the comparison of its response.
+type CrossCheckRequest = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// An engine which mirrors the calls made to the canonical engine.
+///
+/// Its requests are made one at a time, in the order in which they were made to the canonical
+/// engine, so that e.g. a `forkchoiceUpdated` call never overtakes the `newPayload` call for its
+/// head block.
+struct CrossCheckEngine {
+    engine: Arc<Engine>,
+    queue: mpsc::Sender<CrossCheckRequest>,
+}
+
+impl CrossCheckEngine {
+    fn new(engine: Arc<Engine>, executor: &TaskExecutor) -> Self {
+        Self {
+            engine,
+            queue: spawn_request_queue(executor),
+        }
+    }
+}
+
+/// Spawns a task which runs the requests sent to the returned queue one at a time, in order.
+fn spawn_request_queue(executor: &TaskExecutor) -> mpsc::Sender<CrossCheckRequest> {
+    let (sender, mut receiver) = mpsc::channel::<CrossCheckRequest>(CROSS_CHECK_QUEUE_LEN);
+    executor.spawn(
+        async move {
+            while let Some(request) = receiver.recv().await {
+                request.await;
+            }
+        },
+        "exec_cross_check",
+    );
+    sender
+}
+
+/// Returns `true` if `builder_value` exceeds `local_value` by more than `margin_percent` percent.
+fn builder_bid_exceeds_local(
+    builder_value: Uint256,
+    local_value: Uint256,
+    margin_percent: u64,
+) -> bool {
+    let hundred = Uint256::from(100);
+    builder_value.saturating_mul(hundred)
+        > local_value.saturating_mul(hundred.saturating_add(Uint256::from(margin_percent)))
+}
+
+/// Reads the JWT secret from `secret_file`, or generates one and writes it to `secret_file` if the
+/// file does not exist.
+fn load_jwt_key(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    }
+}
+
 impl<T: EthSpec> ExecutionLayer<T> {
     /// Instantiate `Self` with an Execution engine specified in `Config`, using JSON-RPC via HTTP.
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoints: urls,
             builder_url,
+            additional_builder_urls,
             builder_user_agent,
             secret_files,
             suggested_fee_recipient,
@@ -273,69 +435,75 @@ impl<T: EthSpec> ExecutionLayer<T> {
             jwt_version,
             default_datadir,
             builder_profit_threshold,
+            builder_bid_margin_percent,
             execution_timeout_multiplier,
             always_prefer_builder_payload,
         } = config;
 
-        if urls.len() > 1 {
-            warn!(log, "Only the first execution engine url will be used");
-        }
-        let execution_url = urls.into_iter().next().ok_or(Error::NoEngine)?;
+        let mut urls = urls.into_iter();
+        let execution_url = urls.next().ok_or(Error::NoEngine)?;
 
         // Use the default jwt secret path if not provided via cli.
-        let secret_file = secret_files
-            .into_iter()
-            .next()
+        let default_secret_file = secret_files
+            .first()
+            .cloned()
             .unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
 
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
-
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
-            debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
-            let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
+        let new_engine = |url: SensitiveUrl, secret_file: &PathBuf| -> Result<Engine, Error> {
+            let jwt_key = load_jwt_key(secret_file, &log)?;
+            let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
+            debug!(log, "Loaded execution endpoint"; "endpoint" => %url, "jwt_path" => ?secret_file.as_path());
+            let api = HttpJsonRpc::new_with_auth(url, auth, execution_timeout_multiplier)
                 .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
+            Ok(Engine::new(api, executor.clone(), &log))
         };
 
+        let engine = new_engine(execution_url, &default_secret_file)?;
+        let cross_check_engines = urls
+            .enumerate()
+            .map(|(i, url)| {
+                let secret_file = secret_files.get(i + 1).unwrap_or(&default_secret_file);
+                new_engine(url, secret_file)
+                    .map(|engine| CrossCheckEngine::new(Arc::new(engine), &executor))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !cross_check_engines.is_empty() {
+            info!(
+                log,
+                "Cross-checking execution engine responses";
+                "canonical_endpoint" => %engine.api.url,
+                "cross_check_endpoints" => cross_check_engines.len(),
+            );
+        }
+
+        let additional_builders = additional_builder_urls
+            .into_iter()
+            .map(|url| {
+                info!(log, "Using additional block builder"; "builder_url" => ?url);
+                BuilderHttpClient::new(url, builder_user_agent.clone())
+                    .map(Arc::new)
+                    .map_err(Error::Builder)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
         let inner = Inner {
             engine: Arc::new(engine),
+            cross_check_engines,
             builder: ArcSwapOption::empty(),
+            additional_builders,
+            relay_stats: RelayStatsTracker::default(),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
             proposers: RwLock::new(HashMap::new()),
             execution_blocks: Mutex::new(LruCache::new(EXECUTION_BLOCKS_LRU_CACHE_SIZE)),
+            payload_bodies: Mutex::new(LruCache::new(PAYLOAD_BODIES_LRU_CACHE_SIZE)),
             executor,
             payload_cache: PayloadCache::default(),
+            builder_bid_cache: BuilderBidCache::default(),
             builder_profit_threshold: Uint256::from(builder_profit_threshold),
+            builder_bid_margin_percent,
+            proposal_decisions: ProposalDecisionLog::default(),
             log,
             always_prefer_builder_payload,
             last_new_payload_errored: RwLock::new(false),
@@ -360,6 +528,23 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.inner.builder.load_full()
     }
 
+    /// Returns every configured builder, starting with the one returned by `Self::builder`.
+    pub fn builders(&self) -> Vec<Arc<BuilderHttpClient>> {
+        self.builder()
+            .into_iter()
+            .chain(self.inner.additional_builders.iter().cloned())
+            .collect()
+    }
+
+    /// Returns the reliability statistics of each configured builder.
+    pub fn relay_stats(&self) -> Vec<RelayStats> {
+        let builders = self.builders();
+        self.inner.relay_stats.stats(
+            builders.iter().map(|builder| builder.server()),
+            Instant::now(),
+        )
+    }
+
     /// Set the builder URL after initialization.
     ///
     /// This is useful for breaking circular dependencies between mock ELs and mock builders in
@@ -392,6 +577,45 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.inner.payload_cache.get(root)
     }
 
+    /// Returns the most recent builder bid received for `slot`, if any.
+    pub fn get_builder_bid(&self, slot: Slot) -> Option<SseBuilderBid> {
+        self.inner.builder_bid_cache.get(slot)
+    }
+
+    /// Returns the most recent choices between the local payload and a builder bid, most recent
+    /// first.
+    pub fn recent_proposal_decisions(&self) -> Vec<ProposalDecision> {
+        self.inner.proposal_decisions.recent()
+    }
+
+    /// Record a bid received from `builder` so it can be reported on the events stream and
+    /// attributed to a revealed payload later.
+    fn cache_builder_bid<Payload: AbstractExecPayload<T>>(
+        &self,
+        builder: &BuilderHttpClient,
+        bid: &ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+        slot: Slot,
+        local_block_value: Option<Uint256>,
+        relay_duration: Duration,
+        selected: bool,
+    ) {
+        if selected {
+            self.inner.relay_stats.record_selected(builder.server());
+        }
+        let header = &bid.data.message.header;
+        self.inner.builder_bid_cache.put(SseBuilderBid {
+            slot,
+            parent_hash: header.parent_hash(),
+            block_hash: header.block_hash(),
+            builder_pubkey: bid.data.message.pubkey,
+            value: bid.data.message.value,
+            local_block_value: local_block_value.map(SseBlockValue),
+            relay: builder.server().to_string(),
+            relay_response_ms: relay_duration.as_millis() as u64,
+            selected,
+        });
+    }
+
     pub fn executor(&self) -> &TaskExecutor {
         &self.inner.executor
     }
@@ -413,6 +637,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
         self.inner.execution_blocks.lock().await
     }
 
+    /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
+    async fn payload_bodies(
+        &self,
+    ) -> MutexGuard<'_, LruCache<ExecutionBlockHash, ExecutionPayloadBodyV1<T>>> {
+        self.inner.payload_bodies.lock().await
+    }
+
     /// Gives access to a channel containing if the last engine state is online or not.
     ///
     /// This can be called several times.
@@ -470,6 +701,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
         self.engine().upcheck().await;
+        join_all(
+            self.inner
+                .cross_check_engines
+                .iter()
+                .map(|cross_check| cross_check.engine.upcheck()),
+        )
+        .await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -724,6 +962,122 @@ impl<T: EthSpec> ExecutionLayer<T> {
         }
     }
 
+    /// Requests a bid from each of `builders` concurrently and returns the most valuable bid which
+    /// is not objectively invalid, along with the builder which made it and its response time.
+    ///
+    /// Builders which have failed repeatedly are skipped, unless every builder has.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_best_builder_header<Payload: AbstractExecPayload<T>>(
+        &self,
+        builders: &[Arc<BuilderHttpClient>],
+        slot: Slot,
+        parent_hash: ExecutionBlockHash,
+        pubkey: &PublicKeyBytes,
+        payload_attributes: &PayloadAttributes,
+        current_fork: ForkName,
+        spec: &ChainSpec,
+    ) -> (BuilderHeaderResult<T, Payload>, Duration) {
+        let start = Instant::now();
+        let relay_stats = &self.inner.relay_stats;
+        let available = builders
+            .iter()
+            .filter(|builder| relay_stats.is_available(builder.server(), start))
+            .cloned()
+            .collect::<Vec<_>>();
+        let queried = if available.is_empty() {
+            builders.to_vec()
+        } else {
+            available
+        };
+
+        let responses = join_all(queried.iter().map(|builder| {
+            timed_future(
+                metrics::GET_BLINDED_PAYLOAD_BUILDER,
+                builder.get_builder_header::<T, Payload>(slot, parent_hash, pubkey),
+            )
+        }))
+        .await;
+
+        let mut best: Option<(Arc<BuilderHttpClient>, _, Duration)> = None;
+        let mut outcomes = Vec::with_capacity(queried.len());
+        let mut last_error = None;
+        for (builder, (result, duration)) in queried.into_iter().zip(responses) {
+            let outcome = match result {
+                Ok(Some(bid)) => match verify_builder_bid(
+                    &bid,
+                    parent_hash,
+                    payload_attributes,
+                    None,
+                    self.inner.builder_profit_threshold,
+                    current_fork,
+                    spec,
+                ) {
+                    Err(reason) if reason.payload_invalid() => {
+                        metrics::inc_counter_vec(
+                            &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                            &[reason.as_ref().as_ref()],
+                        );
+                        warn!(
+                            self.log(),
+                            "Builder returned invalid payload";
+                            "info" => "ignoring bid",
+                            "reason" => %reason,
+                            "relay" => %builder.server(),
+                            "parent_hash" => ?parent_hash,
+                        );
+                        HeaderOutcome::InvalidBid
+                    }
+                    _ => {
+                        let is_best = best.as_ref().map_or(true, |(best_builder, best_bid, _)| {
+                            let value = bid.data.message.value;
+                            let best_value = best_bid.data.message.value;
+                            value > best_value
+                                || (value == best_value
+                                    && relay_stats.failure_rate(builder.server())
+                                        < relay_stats.failure_rate(best_builder.server()))
+                        });
+                        if is_best {
+                            best = Some((builder.clone(), bid, duration));
+                        }
+                        HeaderOutcome::Bid
+                    }
+                },
+                Ok(None) => HeaderOutcome::NoBid,
+                Err(e) => {
+                    debug!(
+                        self.log(),
+                        "Builder error when requesting payload";
+                        "relay" => %builder.server(),
+                        "error" => ?e,
+                    );
+                    last_error = Some(e);
+                    HeaderOutcome::Error
+                }
+            };
+            outcomes.push((builder, outcome, duration));
+        }
+
+        // Record the outcomes only once all bids are compared so that the comparison of equal bids
+        // does not depend on the order of the builders.
+        let now = Instant::now();
+        for (builder, outcome, duration) in &outcomes {
+            relay_stats.record(builder.server(), *outcome, *duration, now);
+        }
+
+        match (best, last_error) {
+            (Some((builder, bid, duration)), _) => (Ok(Some((builder, bid))), duration),
+            // Only report an error if every builder failed.
+            (None, Some(e))
+                if outcomes
+                    .iter()
+                    .all(|(_, outcome, _)| *outcome == HeaderOutcome::Error) =>
+            {
+                (Err(e), start.elapsed())
+            }
+            (None, _) => (Ok(None), start.elapsed()),
+        }
+    }
+
     async fn get_blinded_payload<Payload: AbstractExecPayload<T>>(
         &self,
         parent_hash: ExecutionBlockHash,
@@ -733,7 +1087,8 @@ impl<T: EthSpec> ExecutionLayer<T> {
         current_fork: ForkName,
         spec: &ChainSpec,
     ) -> Result<ProvenancedPayload<BlockProposalContents<T, Payload>>, Error> {
-        if let Some(builder) = self.builder() {
+        let builders = self.builders();
+        if !builders.is_empty() {
             let slot = builder_params.slot;
             let pubkey = builder_params.pubkey;
 
@@ -742,18 +1097,23 @@ impl<T: EthSpec> ExecutionLayer<T> {
                     info!(
                         self.log(),
                         "Requesting blinded header from connected builder";
+                        "builders" => builders.len(),
                         "slot" => ?slot,
                         "pubkey" => ?pubkey,
                         "parent_hash" => ?parent_hash,
                     );
 
-                    // Wait for the builder *and* local EL to produce a payload (or return an error).
+                    // Wait for the builders *and* local EL to produce a payload (or return an error).
                     let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
-                        timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
-                            builder
-                                .get_builder_header::<T, Payload>(slot, parent_hash, &pubkey)
-                                .await
-                        }),
+                        self.get_best_builder_header::<Payload>(
+                            &builders,
+                            slot,
+                            parent_hash,
+                            &pubkey,
+                            payload_attributes,
+                            current_fork,
+                            spec,
+                        ),
                         timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
                             self.get_full_payload_caching::<Payload>(
                                 parent_hash,
@@ -769,7 +1129,7 @@ impl<T: EthSpec> ExecutionLayer<T> {
                         self.log(),
                         "Requested blinded execution payload";
                         "relay_fee_recipient" => match &relay_result {
-                            Ok(Some(r)) => format!("{:?}", r.data.message.header.fee_recipient()),
+                            Ok(Some((_, r))) => format!("{:?}", r.data.message.header.fee_recipient()),
                             Ok(None) => "empty response".to_string(),
                             Err(_) => "request failed".to_string(),
                         },
@@ -782,7 +1142,17 @@ impl<T: EthSpec> ExecutionLayer<T> {
                         "parent_hash" => ?parent_hash,
                     );
 
-                    return match (relay_result, local_result) {
+                    let local_block_value = local_result
+                        .as_ref()
+                        .ok()
+                        .map(|local| SseBlockValue(*local.block_value()));
+                    let builder_bid_value = relay_result
+                        .as_ref()
+                        .ok()
+                        .and_then(Option::as_ref)
+                        .map(|(_, relay)| SseBlockValue(relay.data.message.value));
+
+                    let result = match (relay_result, local_result) {
                         (Err(e), Ok(local)) => {
                             warn!(
                                 self.log(),
@@ -804,12 +1174,13 @@ impl<T: EthSpec> ExecutionLayer<T> {
                             );
                             Ok(ProvenancedPayload::Local(local))
                         }
-                        (Ok(Some(relay)), Ok(local)) => {
+                        (Ok(Some((builder, relay))), Ok(local)) => {
                             let header = &relay.data.message.header;
 
                             info!(
                                 self.log(),
                                 "Received local and builder payloads";
+                                "relay" => %builder.server(),
                                 "relay_block_hash" => ?header.block_hash(),
                                 "local_block_hash" => ?local.payload().block_hash(),
                                 "parent_hash" => ?parent_hash,
@@ -817,75 +1188,119 @@ impl<T: EthSpec> ExecutionLayer<T> {
 
                             let relay_value = relay.data.message.value;
                             let local_value = *local.block_value();
-                            if !self.inner.always_prefer_builder_payload {
-                                if local_value >= relay_value {
-                                    info!(
-                                        self.log(),
-                                        "Local block is more profitable than relay block";
-                                        "local_block_value" => %local_value,
-                                        "relay_value" => %relay_value
-                                    );
-                                    return Ok(ProvenancedPayload::Local(local));
-                                } else {
+                            let margin_percent = self.inner.builder_bid_margin_percent;
+                            let builder_more_profitable = self.inner.always_prefer_builder_payload
+                                || builder_bid_exceeds_local(
+                                    relay_value,
+                                    local_value,
+                                    margin_percent,
+                                );
+                            if !builder_more_profitable {
+                                info!(
+                                    self.log(),
+                                    "Local block is more profitable than relay block";
+                                    "local_block_value" => %local_value,
+                                    "relay_value" => %relay_value,
+                                    "margin_percent" => margin_percent,
+                                );
+                                self.cache_builder_bid(
+                                    &builder,
+                                    &relay,
+                                    slot,
+                                    Some(local_value),
+                                    relay_duration,
+                                    false,
+                                );
+                                Ok(ProvenancedPayload::Local(local))
+                            } else {
+                                if !self.inner.always_prefer_builder_payload {
                                     info!(
                                         self.log(),
                                         "Relay block is more profitable than local block";
                                         "local_block_value" => %local_value,
-                                        "relay_value" => %relay_value
+                                        "relay_value" => %relay_value,
+                                        "margin_percent" => margin_percent,
                                     );
                                 }
-                            }
 
-                            match verify_builder_bid(
-                                &relay,
-                                parent_hash,
-                                payload_attributes,
-                                Some(local.payload().block_number()),
-                                self.inner.builder_profit_threshold,
-                                current_fork,
-                                spec,
-                            ) {
-                                Ok(()) => Ok(ProvenancedPayload::Builder(
-                                    BlockProposalContents::Payload {
-                                        payload: relay.data.message.header,
-                                        block_value: relay.data.message.value,
-                                        _phantom: PhantomData,
-                                    },
-                                )),
-                                Err(reason) if !reason.payload_invalid() => {
-                                    info!(
-                                        self.log(),
-                                        "Builder payload ignored";
-                                        "info" => "using local payload",
-                                        "reason" => %reason,
-                                        "relay_block_hash" => ?header.block_hash(),
-                                        "parent_hash" => ?parent_hash,
-                                    );
-                                    Ok(ProvenancedPayload::Local(local))
-                                }
-                                Err(reason) => {
-                                    metrics::inc_counter_vec(
-                                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                                        &[reason.as_ref().as_ref()],
-                                    );
-                                    warn!(
-                                        self.log(),
-                                        "Builder returned invalid payload";
-                                        "info" => "using local payload",
-                                        "reason" => %reason,
-                                        "relay_block_hash" => ?header.block_hash(),
-                                        "parent_hash" => ?parent_hash,
-                                    );
-                                    Ok(ProvenancedPayload::Local(local))
+                                match verify_builder_bid(
+                                    &relay,
+                                    parent_hash,
+                                    payload_attributes,
+                                    Some(local.payload().block_number()),
+                                    self.inner.builder_profit_threshold,
+                                    current_fork,
+                                    spec,
+                                ) {
+                                    Ok(()) => {
+                                        self.cache_builder_bid(
+                                            &builder,
+                                            &relay,
+                                            slot,
+                                            Some(local_value),
+                                            relay_duration,
+                                            true,
+                                        );
+                                        Ok(ProvenancedPayload::Builder(
+                                            BlockProposalContents::Payload {
+                                                payload: relay.data.message.header,
+                                                block_value: relay.data.message.value,
+                                                _phantom: PhantomData,
+                                            },
+                                        ))
+                                    }
+                                    Err(reason) if !reason.payload_invalid() => {
+                                        self.cache_builder_bid(
+                                            &builder,
+                                            &relay,
+                                            slot,
+                                            Some(local_value),
+                                            relay_duration,
+                                            false,
+                                        );
+                                        info!(
+                                            self.log(),
+                                            "Builder payload ignored";
+                                            "info" => "using local payload",
+                                            "reason" => %reason,
+                                            "relay_block_hash" => ?header.block_hash(),
+                                            "parent_hash" => ?parent_hash,
+                                        );
+                                        Ok(ProvenancedPayload::Local(local))
+                                    }
+                                    Err(reason) => {
+                                        self.cache_builder_bid(
+                                            &builder,
+                                            &relay,
+                                            slot,
+                                            Some(local_value),
+                                            relay_duration,
+                                            false,
+                                        );
+                                        metrics::inc_counter_vec(
+                                            &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                                            &[reason.as_ref().as_ref()],
+                                        );
+                                        warn!(
+                                            self.log(),
+                                            "Builder returned invalid payload";
+                                            "info" => "using local payload",
+                                            "reason" => %reason,
+                                            "relay_block_hash" => ?header.block_hash(),
+                                            "parent_hash" => ?parent_hash,
+                                        );
+                                        Ok(ProvenancedPayload::Local(local))
+                                    }
                                 }
                             }
                         }
-                        (Ok(Some(relay)), Err(local_error)) => {
+                        (Ok(Some((builder, relay))), Err(local_error)) => {
                             let header = &relay.data.message.header;
 
                             info!(
                                 self.log(),
                                 "Received builder payload with local error";
+                                "relay" => %builder.server(),
                                 "relay_block_hash" => ?header.block_hash(),
                                 "local_error" => ?local_error,
                                 "parent_hash" => ?parent_hash,
@@ -900,23 +1315,51 @@ impl<T: EthSpec> ExecutionLayer<T> {
                                 current_fork,
                                 spec,
                             ) {
-                                Ok(()) => Ok(ProvenancedPayload::Builder(
-                                    BlockProposalContents::Payload {
-                                        payload: relay.data.message.header,
-                                        block_value: relay.data.message.value,
-                                        _phantom: PhantomData,
-                                    },
-                                )),
+                                Ok(()) => {
+                                    self.cache_builder_bid(
+                                        &builder,
+                                        &relay,
+                                        slot,
+                                        None,
+                                        relay_duration,
+                                        true,
+                                    );
+                                    Ok(ProvenancedPayload::Builder(
+                                        BlockProposalContents::Payload {
+                                            payload: relay.data.message.header,
+                                            block_value: relay.data.message.value,
+                                            _phantom: PhantomData,
+                                        },
+                                    ))
+                                }
                                 // If the payload is valid then use it. The local EE failed
                                 // to produce a payload so we have no alternative.
-                                Err(e) if !e.payload_invalid() => Ok(ProvenancedPayload::Builder(
-                                    BlockProposalContents::Payload {
-                                        payload: relay.data.message.header,
-                                        block_value: relay.data.message.value,
-                                        _phantom: PhantomData,
-                                    },
-                                )),
+                                Err(e) if !e.payload_invalid() => {
+                                    self.cache_builder_bid(
+                                        &builder,
+                                        &relay,
+                                        slot,
+                                        None,
+                                        relay_duration,
+                                        true,
+                                    );
+                                    Ok(ProvenancedPayload::Builder(
+                                        BlockProposalContents::Payload {
+                                            payload: relay.data.message.header,
+                                            block_value: relay.data.message.value,
+                                            _phantom: PhantomData,
+                                        },
+                                    ))
+                                }
                                 Err(reason) => {
+                                    self.cache_builder_bid(
+                                        &builder,
+                                        &relay,
+                                        slot,
+                                        None,
+                                        relay_duration,
+                                        false,
+                                    );
                                     metrics::inc_counter_vec(
                                         &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
                                         &[reason.as_ref().as_ref()],
@@ -958,6 +1401,22 @@ impl<T: EthSpec> ExecutionLayer<T> {
                             Err(Error::CannotProduceHeader)
                         }
                     };
+
+                    let source = match &result {
+                        Ok(ProvenancedPayload::Local(_)) => Some(ProposalPayloadSource::Local),
+                        Ok(ProvenancedPayload::Builder(_)) => Some(ProposalPayloadSource::Builder),
+                        Err(_) => None,
+                    };
+                    self.inner.proposal_decisions.record(ProposalDecision {
+                        slot,
+                        parent_hash,
+                        local_block_value,
+                        builder_bid_value,
+                        margin_percent: self.inner.builder_bid_margin_percent,
+                        source,
+                    });
+
+                    return result;
                 }
                 ChainHealth::Unhealthy(condition) => info!(
                     self.log(),
@@ -1159,11 +1618,88 @@ impl<T: EthSpec> ExecutionLayer<T> {
         }
         *self.inner.last_new_payload_errored.write().await = result.is_err();
 
+        let payload = execution_payload.clone();
+        self.spawn_cross_check(
+            metrics::NEW_PAYLOAD,
+            execution_payload.block_hash(),
+            result.as_ref().ok().map(|status| status.status),
+            move |engine| {
+                let payload = payload.clone();
+                async move {
+                    engine
+                        .request(|engine| engine.api.new_payload(payload))
+                        .await
+                        .map(|status| status.status)
+                }
+            },
+        );
+
         process_payload_status(execution_payload.block_hash(), result, self.log())
             .map_err(Box::new)
             .map_err(Error::EngineError)
     }
 
+    /// Makes the request produced by `request` to each of the cross-check engines in the
+    /// background, comparing the status each returns to `canonical_status`, the status returned
+    /// by the canonical engine.
+    ///
+    /// The requests to each engine are made in the order in which this function is called.
+    fn spawn_cross_check<F, G>(
+        &self,
+        method: &'static str,
+        block_hash: ExecutionBlockHash,
+        canonical_status: Option<PayloadStatusV1Status>,
+        request: F,
+    ) where
+        F: Fn(Arc<Engine>) -> G,
+        G: Future<Output = Result<PayloadStatusV1Status, EngineError>> + Send + 'static,
+    {
+        for cross_check in &self.inner.cross_check_engines {
+            let endpoint = cross_check.engine.api.url.to_string();
+            let response = request(cross_check.engine.clone());
+            let log = self.log().clone();
+            let check_endpoint = endpoint.clone();
+            let check = async move {
+                let result = response.await;
+                let outcome = match (canonical_status, &result) {
+                    (_, Err(_)) => CrossCheckOutcome::Error,
+                    (None, Ok(_)) => CrossCheckOutcome::Inconclusive,
+                    (Some(canonical), Ok(status)) => CrossCheckOutcome::compare(canonical, *status),
+                };
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_CROSS_CHECK_OUTCOME,
+                    &[method, &check_endpoint, outcome.into()],
+                );
+                if outcome == CrossCheckOutcome::Disagree {
+                    warn!(
+                        log,
+                        "Execution engines disagree";
+                        "method" => method,
+                        "block_hash" => ?block_hash,
+                        "canonical_status" => ?canonical_status,
+                        "status" => ?result,
+                        "endpoint" => check_endpoint,
+                    );
+                }
+            };
+
+            if cross_check.queue.try_send(Box::pin(check)).is_err() {
+                debug!(
+                    self.log(),
+                    "Dropping cross-check request";
+                    "reason" => "too many requests waiting",
+                    "method" => method,
+                    "block_hash" => ?block_hash,
+                    "endpoint" => &endpoint,
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_CROSS_CHECK_OUTCOME,
+                    &[method, &endpoint, CrossCheckOutcome::Dropped.into()],
+                );
+            }
+        }
+    }
+
     /// Update engine sync status.
     pub async fn upcheck(&self) {
         self.engine().upcheck().await;
@@ -1300,6 +1836,31 @@ impl<T: EthSpec> ExecutionLayer<T> {
             );
         }
 
+        // Only the canonical engine is asked to build payloads.
+        let log = self.log().clone();
+        self.spawn_cross_check(
+            metrics::FORKCHOICE_UPDATED,
+            head_block_hash,
+            result
+                .as_ref()
+                .ok()
+                .map(|response| response.payload_status.status),
+            move |engine| {
+                let log = log.clone();
+                async move {
+                    engine.set_latest_forkchoice_state(forkchoice_state).await;
+                    engine
+                        .request(|engine| async move {
+                            engine
+                                .notify_forkchoice_updated(forkchoice_state, None, &log)
+                                .await
+                        })
+                        .await
+                        .map(|response| response.payload_status.status)
+                }
+            },
+        );
+
         process_payload_status(
             head_block_hash,
             result.map(|response| response.payload_status),
@@ -1583,26 +2144,60 @@ impl<T: EthSpec> ExecutionLayer<T> {
             return Ok(Some(payload));
         }
 
-        // Use efficient payload bodies by range method if supported.
+        if let Some(body) = self.payload_bodies().await.get(&hash).cloned() {
+            metrics::inc_counter_vec(&metrics::EXECUTION_LAYER_PAYLOAD_BODIES_CACHE, &["hit"]);
+            return body
+                .to_payload(header.clone())
+                .map(Some)
+                .map_err(Error::InvalidPayloadBody);
+        }
+        metrics::inc_counter_vec(&metrics::EXECUTION_LAYER_PAYLOAD_BODIES_CACHE, &["miss"]);
+
         let capabilities = self.get_engine_capabilities(None).await?;
+        if !capabilities.get_payload_bodies_by_range_v1
+            && !capabilities.get_payload_bodies_by_hash_v1
+        {
+            // Fall back to eth_blockByHash.
+            return self.get_payload_by_hash_legacy(hash, fork).await;
+        }
+
+        // Use efficient payload bodies by range method if supported. The execution node only
+        // serves its canonical chain by range, so if the body doesn't match the header it is
+        // requested by hash instead.
         if capabilities.get_payload_bodies_by_range_v1 {
             let mut payload_bodies = self.get_payload_bodies_by_range(block_number, 1).await?;
-
-            if payload_bodies.len() != 1 {
-                return Ok(None);
+            if payload_bodies.len() == 1 {
+                if let Some(body) = payload_bodies.pop().flatten() {
+                    let payload = body
+                        .clone()
+                        .to_payload(header.clone())
+                        .map_err(Error::InvalidPayloadBody)?;
+                    if ExecutionPayloadHeader::from(payload.to_ref()) == *header {
+                        self.payload_bodies().await.put(hash, body);
+                        return Ok(Some(payload));
+                    }
+                }
             }
+        }
 
-            let opt_payload_body = payload_bodies.pop().flatten();
-            opt_payload_body
-                .map(|body| {
-                    body.to_payload(header.clone())
-                        .map_err(Error::InvalidPayloadBody)
-                })
-                .transpose()
-        } else {
-            // Fall back to eth_blockByHash.
-            self.get_payload_by_hash_legacy(hash, fork).await
+        if capabilities.get_payload_bodies_by_hash_v1 {
+            let mut payload_bodies = self.get_payload_bodies_by_hash(vec![hash]).await?;
+            if payload_bodies.len() == 1 {
+                if let Some(body) = payload_bodies.pop().flatten() {
+                    let payload = body
+                        .clone()
+                        .to_payload(header.clone())
+                        .map_err(Error::InvalidPayloadBody)?;
+                    // A mismatched payload is still returned so that the caller can report it.
+                    if ExecutionPayloadHeader::from(payload.to_ref()) == *header {
+                        self.payload_bodies().await.put(hash, body);
+                    }
+                    return Ok(Some(payload));
+                }
+            }
         }
+
+        Ok(None)
     }
 
     pub async fn get_block_by_number(
@@ -1731,14 +2326,35 @@ impl<T: EthSpec> ExecutionLayer<T> {
             "root" => ?block_root,
         );
 
-        if let Some(builder) = self.builder() {
+        let builders = self.builders();
+        if !builders.is_empty() {
+            // Only the builder which made the bid can reveal the payload. If it is unknown, ask
+            // every builder and use the first payload revealed.
+            let bid_relay = self
+                .inner
+                .builder_bid_cache
+                .get(block.slot())
+                .filter(|bid| bid.selected)
+                .map(|bid| bid.relay);
+            let targets = match bid_relay.and_then(|relay| {
+                builders
+                    .iter()
+                    .find(|builder| builder.server().to_string() == relay)
+            }) {
+                Some(builder) => vec![builder.clone()],
+                None => builders,
+            };
+
             let (payload_result, duration) =
                 timed_future(metrics::POST_BLINDED_PAYLOAD_BUILDER, async {
-                    builder
-                        .post_builder_blinded_blocks(block)
-                        .await
-                        .map_err(Error::Builder)
-                        .map(|d| d.data)
+                    select_ok(
+                        targets
+                            .iter()
+                            .map(|builder| Box::pin(builder.post_builder_blinded_blocks(block))),
+                    )
+                    .await
+                    .map_err(Error::Builder)
+                    .map(|(d, _)| d.data)
                 })
                 .await;
 
@@ -1888,6 +2504,15 @@ impl fmt::Display for InvalidBuilderPayload {
     }
 }
 
+/// The most valuable bid received from the builders, along with the builder which made it.
+type BuilderHeaderResult<T, Payload> = Result<
+    Option<(
+        Arc<BuilderHttpClient>,
+        ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
+    )>,
+    builder_client::Error,
+>;
+
 /// Perform some cursory, non-exhaustive validation of the bid returned from the builder.
 fn verify_builder_bid<T: EthSpec, Payload: AbstractExecPayload<T>>(
     bid: &ForkVersionedResponse<SignedBuilderBid<T, Payload>>,
@@ -2134,4 +2759,100 @@ mod test {
             })
             .await;
     }
+
+    #[tokio::test]
+    async fn reconstructs_payload_from_body() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone())
+            .move_to_terminal_block()
+            .produce_valid_execution_payload_on_head()
+            .await;
+
+        let payload = match mock.server.execution_block_generator().latest_block() {
+            Some(crate::test_utils::Block::PoS(payload)) => payload,
+            _ => panic!("head should be a PoS block"),
+        };
+        let header = ExecutionPayloadHeader::from(payload.to_ref());
+
+        let reconstructed = mock
+            .el
+            .get_payload_for_header(&header, ForkName::Merge)
+            .await
+            .unwrap();
+        assert_eq!(reconstructed.as_ref(), Some(&payload));
+        assert!(mock
+            .el
+            .payload_bodies()
+            .await
+            .contains(&header.block_hash()));
+
+        // The second reconstruction is served from the cache.
+        let reconstructed = mock
+            .el
+            .get_payload_for_header(&header, ForkName::Merge)
+            .await
+            .unwrap();
+        assert_eq!(reconstructed, Some(payload));
+    }
+
+    #[test]
+    fn builder_bid_margin() {
+        let value = |gwei: u64| Uint256::from(gwei) * Uint256::from(1_000_000_000);
+
+        // Without a margin any bid above the local value is used.
+        assert!(builder_bid_exceeds_local(value(101), value(100), 0));
+        assert!(!builder_bid_exceeds_local(value(100), value(100), 0));
+
+        assert!(!builder_bid_exceeds_local(value(110), value(100), 10));
+        assert!(builder_bid_exceeds_local(value(111), value(100), 10));
+        assert!(builder_bid_exceeds_local(value(1), Uint256::zero(), 50));
+    }
+
+    #[test]
+    fn cross_check_outcomes() {
+        use PayloadStatusV1Status::*;
+
+        assert_eq!(
+            CrossCheckOutcome::compare(Valid, Valid),
+            CrossCheckOutcome::Agree
+        );
+        assert_eq!(
+            CrossCheckOutcome::compare(Valid, Invalid),
+            CrossCheckOutcome::Disagree
+        );
+        assert_eq!(
+            CrossCheckOutcome::compare(InvalidBlockHash, Valid),
+            CrossCheckOutcome::Disagree
+        );
+        assert_eq!(
+            CrossCheckOutcome::compare(Valid, Syncing),
+            CrossCheckOutcome::Inconclusive
+        );
+        assert_eq!(
+            CrossCheckOutcome::compare(Invalid, Accepted),
+            CrossCheckOutcome::Inconclusive
+        );
+    }
+
+    #[tokio::test]
+    async fn cross_check_requests_are_made_in_order() {
+        let runtime = TestRuntime::default();
+        let queue = spawn_request_queue(&runtime.task_executor);
+        let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+        // Earlier requests take longer, so they would finish last if they were made concurrently.
+        for i in 0..3u64 {
+            let done_tx = done_tx.clone();
+            queue
+                .try_send(Box::pin(async move {
+                    sleep(Duration::from_millis(30 - i * 10)).await;
+                    done_tx.send(i).unwrap();
+                }))
+                .unwrap();
+        }
+
+        for i in 0..3 {
+            assert_eq!(done_rx.recv().await, Some(i));
+        }
+    }
 }
@@ -59,6 +59,16 @@ lazy_static::lazy_static! {
         "Indicates the payload status returned for a particular method",
         &["method", "status"]
     );
+    pub static ref EXECUTION_LAYER_CROSS_CHECK_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_cross_check_outcome",
+        "The outcome of comparing the payload status returned by a cross-check engine with the canonical engine",
+        &["method", "endpoint", "outcome"]
+    );
+    pub static ref EXECUTION_LAYER_PAYLOAD_BODIES_CACHE: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_payload_bodies_cache",
+        "Hits and misses of the cache of payload bodies used to reconstruct full blocks",
+        &["outcome"]
+    );
     pub static ref EXECUTION_LAYER_GET_PAYLOAD_OUTCOME: Result<IntCounterVec> = try_create_int_counter_vec(
         "execution_layer_get_payload_outcome",
         "The success/failure outcomes from calling get_payload",
@@ -0,0 +1,31 @@
+use eth2::types::ProposalDecision;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+
+/// The number of proposal decisions retained.
+pub const RECENT_PROPOSAL_DECISIONS: usize = 64;
+
+/// A bounded record of the most recent choices between local and builder payloads.
+///
+/// This allows the values of both payloads to be analysed after the fact, e.g. to tune the
+/// builder bid margin.
+#[derive(Default)]
+pub struct ProposalDecisionLog {
+    decisions: Mutex<VecDeque<ProposalDecision>>,
+}
+
+impl ProposalDecisionLog {
+    /// Records a decision, dropping the oldest decision if the log is full.
+    pub fn record(&self, decision: ProposalDecision) {
+        let mut decisions = self.decisions.lock();
+        if decisions.len() >= RECENT_PROPOSAL_DECISIONS {
+            decisions.pop_front();
+        }
+        decisions.push_back(decision);
+    }
+
+    /// Returns the retained decisions, most recent first.
+    pub fn recent(&self) -> Vec<ProposalDecision> {
+        self.decisions.lock().iter().rev().cloned().collect()
+    }
+}
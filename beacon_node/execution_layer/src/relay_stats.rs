@@ -0,0 +1,164 @@
+//! Reliability statistics for each builder relay.
+//!
+//! A relay which repeatedly fails to respond, or responds with invalid bids, is not queried for a
+//! while so that it cannot delay or degrade block proposals.
+use eth2::types::RelayStats;
+use parking_lot::Mutex;
+use sensitive_url::SensitiveUrl;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// The number of consecutive failures after which a relay is backed off.
+pub const RELAY_FAILURE_THRESHOLD: u64 = 3;
+
+/// The duration for which a relay is not queried once it has been backed off.
+pub const RELAY_BACKOFF: Duration = Duration::from_secs(384);
+
+/// The outcome of a `getHeader` request to a relay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeaderOutcome {
+    Bid,
+    NoBid,
+    InvalidBid,
+    Error,
+}
+
+#[derive(Default)]
+struct Entry {
+    stats: RelayStats,
+    total_response_ms: u64,
+    backoff_until: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct RelayStatsTracker {
+    relays: Mutex<HashMap<String, Entry>>,
+}
+
+impl RelayStatsTracker {
+    /// Records the outcome of a `getHeader` request to `relay`.
+    pub fn record(
+        &self,
+        relay: &SensitiveUrl,
+        outcome: HeaderOutcome,
+        response_time: Duration,
+        now: Instant,
+    ) {
+        let mut relays = self.relays.lock();
+        let entry = relays.entry(relay.full.to_string()).or_default();
+        let stats = &mut entry.stats;
+
+        stats.header_requests += 1;
+        entry.total_response_ms += response_time.as_millis() as u64;
+        stats.average_response_ms = entry.total_response_ms / stats.header_requests;
+
+        match outcome {
+            HeaderOutcome::Bid => stats.bids += 1,
+            HeaderOutcome::NoBid => stats.no_bids += 1,
+            HeaderOutcome::InvalidBid => stats.invalid_bids += 1,
+            HeaderOutcome::Error => stats.errors += 1,
+        }
+
+        match outcome {
+            HeaderOutcome::Bid | HeaderOutcome::NoBid => {
+                stats.consecutive_failures = 0;
+                entry.backoff_until = None;
+            }
+            HeaderOutcome::InvalidBid | HeaderOutcome::Error => {
+                stats.consecutive_failures += 1;
+                if stats.consecutive_failures >= RELAY_FAILURE_THRESHOLD {
+                    entry.backoff_until = Some(now + RELAY_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Records that the bid from `relay` was selected.
+    pub fn record_selected(&self, relay: &SensitiveUrl) {
+        self.relays
+            .lock()
+            .entry(relay.full.to_string())
+            .or_default()
+            .stats
+            .selected += 1;
+    }
+
+    /// Returns `false` if `relay` should not be queried because of repeated failures.
+    pub fn is_available(&self, relay: &SensitiveUrl, now: Instant) -> bool {
+        self.relays
+            .lock()
+            .get(relay.full.as_str())
+            .and_then(|entry| entry.backoff_until)
+            .map_or(true, |backoff_until| now >= backoff_until)
+    }
+
+    /// Returns the number of failures of `relay` per request, used to prefer reliable relays when
+    /// bids are of equal value.
+    pub fn failure_rate(&self, relay: &SensitiveUrl) -> f64 {
+        self.relays
+            .lock()
+            .get(relay.full.as_str())
+            .map_or(0.0, |entry| {
+                let stats = &entry.stats;
+                (stats.errors + stats.invalid_bids) as f64 / stats.header_requests.max(1) as f64
+            })
+    }
+
+    /// Returns the statistics of each of `relays`.
+    pub fn stats<'a>(
+        &self,
+        relays: impl IntoIterator<Item = &'a SensitiveUrl>,
+        now: Instant,
+    ) -> Vec<RelayStats> {
+        let tracked = self.relays.lock();
+        relays
+            .into_iter()
+            .map(|relay| {
+                let entry = tracked.get(relay.full.as_str());
+                RelayStats {
+                    relay: relay.to_string(),
+                    backed_off: entry
+                        .and_then(|entry| entry.backoff_until)
+                        .map_or(false, |backoff_until| now < backoff_until),
+                    ..entry.map(|entry| entry.stats.clone()).unwrap_or_default()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off_failing_relays() {
+        let tracker = RelayStatsTracker::default();
+        let relay = SensitiveUrl::parse("http://relay.example.com").unwrap();
+        let other = SensitiveUrl::parse("http://other.example.com").unwrap();
+        let now = Instant::now();
+        let response_time = Duration::from_millis(100);
+
+        for _ in 0..RELAY_FAILURE_THRESHOLD - 1 {
+            tracker.record(&relay, HeaderOutcome::Error, response_time, now);
+        }
+        assert!(tracker.is_available(&relay, now));
+
+        tracker.record(&relay, HeaderOutcome::InvalidBid, response_time, now);
+        assert!(!tracker.is_available(&relay, now));
+        assert!(tracker.is_available(&relay, now + RELAY_BACKOFF));
+        assert!(tracker.is_available(&other, now));
+
+        let stats = tracker.stats([&relay, &other], now);
+        assert_eq!(stats[0].header_requests, RELAY_FAILURE_THRESHOLD);
+        assert_eq!(stats[0].consecutive_failures, RELAY_FAILURE_THRESHOLD);
+        assert_eq!(stats[0].average_response_ms, 100);
+        assert!(stats[0].backed_off);
+        assert_eq!(stats[1].header_requests, 0);
+
+        // A successful response ends the back off.
+        tracker.record(&relay, HeaderOutcome::NoBid, response_time, now);
+        assert!(tracker.is_available(&relay, now));
+        assert_eq!(tracker.stats([&relay], now)[0].consecutive_failures, 0);
+    }
+}
@@ -5,7 +5,7 @@ use crate::test_utils::DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI;
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value as JsonValue;
 use std::sync::Arc;
-use types::{EthSpec, ForkName};
+use types::{EthSpec, ExecutionBlockHash, ForkName};
 
 pub const GENERIC_ERROR_CODE: i64 = -1234;
 pub const BAD_PARAMS_ERROR_CODE: i64 = -32602;
@@ -380,38 +380,23 @@ pub async fn handle_rpc<T: EthSpec>(
                     .read()
                     .execution_block_with_txs_by_number(block_num);
 
-                match maybe_block {
-                    Some(block) => {
-                        let transactions = Transactions::<T>::new(
-                            block
-                                .transactions()
-                                .iter()
-                                .map(|transaction| VariableList::new(transaction.rlp().to_vec()))
-                                .collect::<Result<_, _>>()
-                                .map_err(|e| {
-                                    (
-                                        format!("failed to deserialize transaction: {:?}", e),
-                                        GENERIC_ERROR_CODE,
-                                    )
-                                })?,
-                        )
-                        .map_err(|e| {
-                            (
-                                format!("failed to deserialize transactions: {:?}", e),
-                                GENERIC_ERROR_CODE,
-                            )
-                        })?;
-
-                        response.push(Some(JsonExecutionPayloadBodyV1::<T> {
-                            transactions,
-                            withdrawals: block
-                                .withdrawals()
-                                .ok()
-                                .map(|withdrawals| VariableList::from(withdrawals.clone())),
-                        }));
-                    }
-                    None => response.push(None),
-                }
+                response.push(maybe_block.map(payload_body).transpose()?);
+            }
+
+            Ok(serde_json::to_value(response).unwrap())
+        }
+        ENGINE_GET_PAYLOAD_BODIES_BY_HASH_V1 => {
+            let hashes = get_param::<Vec<ExecutionBlockHash>>(params, 0)
+                .map_err(|s| (s, BAD_PARAMS_ERROR_CODE))?;
+
+            let mut response = vec![];
+            for hash in hashes {
+                let maybe_block = ctx
+                    .execution_block_generator
+                    .read()
+                    .execution_block_with_txs_by_hash(hash);
+
+                response.push(maybe_block.map(payload_body).transpose()?);
             }
 
             Ok(serde_json::to_value(response).unwrap())
@@ -423,6 +408,39 @@ pub async fn handle_rpc<T: EthSpec>(
     }
 }
 
+/// Returns the body of `block`, as served by the `engine_getPayloadBodies*` methods.
+fn payload_body<T: EthSpec>(
+    block: ExecutionBlockWithTransactions<T>,
+) -> Result<JsonExecutionPayloadBodyV1<T>, (String, i64)> {
+    let transactions = Transactions::<T>::new(
+        block
+            .transactions()
+            .iter()
+            .map(|transaction| VariableList::new(transaction.rlp().to_vec()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| {
+                (
+                    format!("failed to deserialize transaction: {:?}", e),
+                    GENERIC_ERROR_CODE,
+                )
+            })?,
+    )
+    .map_err(|e| {
+        (
+            format!("failed to deserialize transactions: {:?}", e),
+            GENERIC_ERROR_CODE,
+        )
+    })?;
+
+    Ok(JsonExecutionPayloadBodyV1::<T> {
+        transactions,
+        withdrawals: block
+            .withdrawals()
+            .ok()
+            .map(|withdrawals| VariableList::from(withdrawals.clone())),
+    })
+}
+
 fn get_param<T: DeserializeOwned>(params: &JsonValue, index: usize) -> Result<T, String> {
     params
         .get(index)
@@ -1,24 +1,42 @@
+use crate::epoch_rewards::ndjson_line;
+use crate::task_spawner::{Priority, TaskSpawner};
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
+    EpochAttestationPerformance, ValidatorAttestationPerformance,
 };
+use eth2::types::{ErrorMessage, ValidatorId};
+use slog::{warn, Logger};
 use state_processing::{
     per_epoch_processing::altair::participation_cache::Error as ParticipationCacheError,
     per_epoch_processing::EpochProcessingSummary, BlockReplayError, BlockReplayer,
 };
+use std::convert::Infallible;
+use std::str::FromStr;
 use std::sync::Arc;
-use types::{BeaconState, BeaconStateError, EthSpec, Hash256};
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use types::{BeaconState, BeaconStateError, Epoch, EthSpec, Hash256};
+use warp::http::{Response, StatusCode};
+use warp::hyper::Body;
 use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
 
 const MAX_REQUEST_RANGE_EPOCHS: usize = 100;
+/// Streamed responses are not held in memory, so they may cover a longer range.
+const MAX_STREAMED_REQUEST_RANGE_EPOCHS: usize = 1_000;
 const BLOCK_ROOT_CHUNK_SIZE: usize = 100;
+/// The number of epochs which may be computed ahead of the client reading them.
+const STREAM_BUFFER_SIZE: usize = 4;
 
 #[derive(Debug)]
 enum AttestationPerformanceError {
     BlockReplay(BlockReplayError),
     BeaconState(BeaconStateError),
+    BeaconChain(BeaconChainError),
     ParticipationCache(ParticipationCacheError),
-    UnableToFindValidator(usize),
+    NoBlockRoots,
+    /// The consumer of the results no longer requires them.
+    Cancelled,
 }
 
 impl From<BlockReplayError> for AttestationPerformanceError {
@@ -33,29 +51,52 @@ impl From<BeaconStateError> for AttestationPerformanceError {
     }
 }
 
+impl From<BeaconChainError> for AttestationPerformanceError {
+    fn from(e: BeaconChainError) -> Self {
+        Self::BeaconChain(e)
+    }
+}
+
 impl From<ParticipationCacheError> for AttestationPerformanceError {
     fn from(e: ParticipationCacheError) -> Self {
         Self::ParticipationCache(e)
     }
 }
 
-pub fn get_attestation_performance<T: BeaconChainTypes>(
-    target: String,
-    query: AttestationPerformanceQuery,
-    chain: Arc<BeaconChain<T>>,
-) -> Result<Vec<AttestationPerformance>, warp::Rejection> {
-    let spec = &chain.spec;
-    // We increment by 2 here so that when we build the state from the `prior_slot` it is
-    // still 1 epoch ahead of the first epoch we want to analyse.
-    // This ensures the `.is_previous_epoch_X` functions on `EpochProcessingSummary` return results
-    // for the correct epoch.
-    let start_epoch = query.start_epoch + 2;
-    let start_slot = start_epoch.start_slot(T::EthSpec::slots_per_epoch());
-    let prior_slot = start_slot - 1;
+impl AttestationPerformanceError {
+    fn message(&self) -> String {
+        match self {
+            Self::NoBlockRoots => {
+                "No blocks roots could be loaded. Ensure the beacon node is synced.".to_string()
+            }
+            e => format!("{:?}", e),
+        }
+    }
 
-    let end_epoch = query.end_epoch + 2;
-    let end_slot = end_epoch.end_slot(T::EthSpec::slots_per_epoch());
+    fn into_rejection(self) -> warp::Rejection {
+        match self {
+            Self::BeaconChain(e) => beacon_chain_error(e),
+            e => custom_server_error(e.message()),
+        }
+    }
+}
 
+/// A validated request for the attestation performance of `indices`.
+struct PerformanceRequest {
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    indices: Vec<u64>,
+}
+
+/// Validate `query` and resolve `target` to a list of validator indices.
+///
+/// `target` may be `global`, a validator index or a validator public key.
+fn prepare_request<T: BeaconChainTypes>(
+    target: &str,
+    query: &AttestationPerformanceQuery,
+    max_range_epochs: usize,
+    chain: &BeaconChain<T>,
+) -> Result<PerformanceRequest, warp::Rejection> {
     // Ensure end_epoch is smaller than the current epoch - 1.
     let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
     if query.end_epoch >= current_epoch - 1 {
@@ -66,7 +107,7 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
     }
 
     // Check query is valid.
-    if start_epoch > end_epoch {
+    if query.start_epoch > query.end_epoch {
         return Err(custom_bad_request(format!(
             "start_epoch must not be larger than end_epoch. start: {}, end: {}",
             query.start_epoch, query.end_epoch
@@ -75,52 +116,177 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
 
     // The response size can grow exceptionally large therefore we should check that the
     // query is within permitted bounds to prevent potential OOM errors.
-    if (end_epoch - start_epoch).as_usize() > MAX_REQUEST_RANGE_EPOCHS {
+    if (query.end_epoch - query.start_epoch).as_usize() > max_range_epochs {
         return Err(custom_bad_request(format!(
             "end_epoch must not exceed start_epoch by more than {} epochs. start: {}, end: {}",
-            MAX_REQUEST_RANGE_EPOCHS, query.start_epoch, query.end_epoch
+            max_range_epochs, query.start_epoch, query.end_epoch
         )));
     }
 
-    // Either use the global validator set, or the specified index.
+    // Either use the global validator set, or the specified validator.
     //
     // Does no further validation of the indices, so in the event an index has not yet been
     // activated or does not yet exist (according to the head state), it will return all fields as
     // `false`.
-    let index_range = if target.to_lowercase() == "global" {
+    let indices = if target.to_lowercase() == "global" {
         chain
             .with_head(|head| Ok((0..head.beacon_state.validators().len() as u64).collect()))
             .map_err(beacon_chain_error)?
     } else {
-        vec![target.parse::<u64>().map_err(|_| {
+        let validator_id = ValidatorId::from_str(target).map_err(|_| {
             custom_bad_request(format!(
-                "Invalid validator index: {:?}",
+                "Invalid validator index or pubkey: {:?}",
                 target.to_lowercase()
             ))
-        })?]
+        })?;
+        match validator_id {
+            ValidatorId::Index(index) => vec![index],
+            ValidatorId::PublicKey(pubkey) => {
+                let index = chain
+                    .validator_index(&pubkey)
+                    .map_err(beacon_chain_error)?
+                    .ok_or_else(|| {
+                        custom_bad_request(format!("Unknown validator pubkey: {:?}", pubkey))
+                    })?;
+                vec![index as u64]
+            }
+        }
     };
 
+    Ok(PerformanceRequest {
+        start_epoch: query.start_epoch,
+        end_epoch: query.end_epoch,
+        indices,
+    })
+}
+
+pub fn get_attestation_performance<T: BeaconChainTypes>(
+    target: String,
+    query: AttestationPerformanceQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Vec<AttestationPerformance>, warp::Rejection> {
+    let request = prepare_request(&target, &query, MAX_REQUEST_RANGE_EPOCHS, &chain)?;
+
+    // Allocate an AttestationPerformance vector for each validator in the range.
+    let mut perfs: Vec<AttestationPerformance> =
+        AttestationPerformance::initialize(request.indices.clone());
+
+    compute_attestation_performance(&request, &chain, |epoch_performance| {
+        for (perf, validator) in perfs.iter_mut().zip(epoch_performance.validators) {
+            perf.epochs
+                .insert(epoch_performance.epoch.as_u64(), validator.statistics);
+        }
+        true
+    })
+    .map_err(AttestationPerformanceError::into_rejection)?;
+
+    Ok(perfs)
+}
+
+/// Stream the attestation performance of `target` for each epoch of `query` as newline-delimited
+/// JSON.
+///
+/// The computation runs in the background, sending each epoch to the client as soon as it is
+/// known. Errors which occur once streaming has begun are sent as a final `ErrorMessage` line.
+pub fn stream_attestation_performance<T: BeaconChainTypes>(
+    target: String,
+    query: AttestationPerformanceQuery,
+    task_spawner: TaskSpawner<T::EthSpec>,
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) -> Result<Response<Body>, warp::Rejection> {
+    let request = prepare_request(&target, &query, MAX_STREAMED_REQUEST_RANGE_EPOCHS, &chain)?;
+
+    let (tx, rx) = mpsc::channel(STREAM_BUFFER_SIZE);
+    let task_tx = tx.clone();
+
+    tokio::spawn(async move {
+        let response = task_spawner
+            .blocking_response_task(Priority::P1, move || {
+                let result = compute_attestation_performance(&request, &chain, |performance| {
+                    // Stop computing if the client has gone away.
+                    task_tx.blocking_send(ndjson_line(&performance)).is_ok()
+                });
+
+                match result {
+                    Ok(()) | Err(AttestationPerformanceError::Cancelled) => {}
+                    Err(e) => {
+                        warn!(
+                            log,
+                            "Failed to compute attestation performance";
+                            "start_epoch" => request.start_epoch,
+                            "end_epoch" => request.end_epoch,
+                            "error" => ?e,
+                        );
+                        let message = ErrorMessage {
+                            code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+                            message: e.message(),
+                            stacktraces: vec![],
+                        };
+                        let _ = task_tx.blocking_send(ndjson_line(&message));
+                    }
+                }
+                Ok(warp::reply())
+            })
+            .await;
+
+        // The task never ran, e.g. because the beacon processor is overloaded.
+        if !response.status().is_success() {
+            let message = ErrorMessage {
+                code: response.status().as_u16(),
+                message: "unable to schedule attestation performance computation".to_string(),
+                stacktraces: vec![],
+            };
+            let _ = tx.send(ndjson_line(&message)).await;
+        }
+    });
+
+    let body = Body::wrap_stream(ReceiverStream::new(rx).map(Ok::<_, Infallible>));
+    Response::builder()
+        .status(200)
+        .header("Content-Type", "application/x-ndjson")
+        .body(body)
+        .map_err(|e| custom_server_error(format!("failed to create response: {}", e)))
+}
+
+/// Replay the blocks of the epochs in `request`, passing the attestation performance of each
+/// epoch to `on_epoch` in order.
+///
+/// Blocks are loaded in chunks so that only a bounded number are held in memory at once. The
+/// replay stops early with `AttestationPerformanceError::Cancelled` if `on_epoch` returns `false`.
+fn compute_attestation_performance<T: BeaconChainTypes>(
+    request: &PerformanceRequest,
+    chain: &BeaconChain<T>,
+    mut on_epoch: impl FnMut(EpochAttestationPerformance) -> bool,
+) -> Result<(), AttestationPerformanceError> {
+    let spec = &chain.spec;
+    // We increment by 2 here so that when we build the state from the `prior_slot` it is
+    // still 1 epoch ahead of the first epoch we want to analyse.
+    // This ensures the `.is_previous_epoch_X` functions on `EpochProcessingSummary` return results
+    // for the correct epoch.
+    let start_epoch = request.start_epoch + 2;
+    let start_slot = start_epoch.start_slot(T::EthSpec::slots_per_epoch());
+    let prior_slot = start_slot - 1;
+
+    let end_epoch = request.end_epoch + 2;
+    let end_slot = end_epoch.end_slot(T::EthSpec::slots_per_epoch());
+
     // Load block roots.
     let mut block_roots: Vec<Hash256> = chain
-        .forwards_iter_block_roots_until(start_slot, end_slot)
-        .map_err(beacon_chain_error)?
+        .forwards_iter_block_roots_until(start_slot, end_slot)?
         .map(|res| res.map(|(root, _)| root))
-        .collect::<Result<Vec<Hash256>, _>>()
-        .map_err(beacon_chain_error)?;
+        .collect::<Result<Vec<Hash256>, _>>()?;
     block_roots.dedup();
 
     // Load first block so we can get its parent.
-    let first_block_root = block_roots.first().ok_or_else(|| {
-        custom_server_error(
-            "No blocks roots could be loaded. Ensure the beacon node is synced.".to_string(),
-        )
-    })?;
+    let first_block_root = block_roots
+        .first()
+        .ok_or(AttestationPerformanceError::NoBlockRoots)?;
     let first_block = chain
         .get_blinded_block(first_block_root)
         .and_then(|maybe_block| {
             maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(*first_block_root))
-        })
-        .map_err(beacon_chain_error)?;
+        })?;
 
     // Load the block of the prior slot which will be used to build the starting state.
     let prior_block = chain
@@ -128,19 +294,15 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         .and_then(|maybe_block| {
             maybe_block
                 .ok_or_else(|| BeaconChainError::MissingBeaconBlock(first_block.parent_root()))
-        })
-        .map_err(beacon_chain_error)?;
+        })?;
 
     // Load state for block replay.
     let state_root = prior_block.state_root();
     let state = chain
         .get_state(&state_root, Some(prior_slot))
-        .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
-        .map_err(beacon_chain_error)?;
-
-    // Allocate an AttestationPerformance vector for each validator in the range.
-    let mut perfs: Vec<AttestationPerformance> =
-        AttestationPerformance::initialize(index_range.clone());
+        .and_then(|maybe_state| {
+            maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root))
+        })?;
 
     let post_slot_hook = |state: &mut BeaconState<T::EthSpec>,
                           summary: Option<EpochProcessingSummary<T::EthSpec>>,
@@ -149,39 +311,33 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         // If a `summary` was not output then an epoch boundary was not crossed
         // so we move onto the next slot.
         if let Some(summary) = summary {
-            for (position, i) in index_range.iter().enumerate() {
-                let index = *i as usize;
-
-                let val = perfs
-                    .get_mut(position)
-                    .ok_or(AttestationPerformanceError::UnableToFindValidator(index))?;
+            // We are two epochs ahead since the summary is generated for
+            // `state.previous_epoch()` then `summary.is_previous_epoch_X` functions return
+            // data for the epoch before that.
+            let epoch = state.previous_epoch() - 1;
 
-                // We are two epochs ahead since the summary is generated for
-                // `state.previous_epoch()` then `summary.is_previous_epoch_X` functions return
-                // data for the epoch before that.
-                let epoch = state.previous_epoch().as_u64() - 1;
-
-                let is_active = summary.is_active_unslashed_in_previous_epoch(index);
-
-                let received_source_reward = summary.is_previous_epoch_source_attester(index)?;
-
-                let received_head_reward = summary.is_previous_epoch_head_attester(index)?;
-
-                let received_target_reward = summary.is_previous_epoch_target_attester(index)?;
-
-                let inclusion_delay = summary
-                    .previous_epoch_inclusion_info(index)
-                    .map(|info| info.delay);
-
-                let perf = AttestationPerformanceStatistics {
-                    active: is_active,
-                    head: received_head_reward,
-                    target: received_target_reward,
-                    source: received_source_reward,
-                    delay: inclusion_delay,
-                };
+            let validators = request
+                .indices
+                .iter()
+                .map(|&index| {
+                    let i = index as usize;
+                    Ok(ValidatorAttestationPerformance {
+                        index,
+                        statistics: AttestationPerformanceStatistics {
+                            active: summary.is_active_unslashed_in_previous_epoch(i),
+                            head: summary.is_previous_epoch_head_attester(i)?,
+                            target: summary.is_previous_epoch_target_attester(i)?,
+                            source: summary.is_previous_epoch_source_attester(i)?,
+                            delay: summary
+                                .previous_epoch_inclusion_info(i)
+                                .map(|info| info.delay),
+                        },
+                    })
+                })
+                .collect::<Result<Vec<_>, AttestationPerformanceError>>()?;
 
-                val.epochs.insert(epoch, perf);
+            if !on_epoch(EpochAttestationPerformance { epoch, validators }) {
+                return Err(AttestationPerformanceError::Cancelled);
             }
         }
         Ok(())
@@ -200,21 +356,16 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         let blocks = block_root_chunks
             .iter()
             .map(|root| {
-                chain
-                    .get_blinded_block(root)
-                    .and_then(|maybe_block| {
-                        maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(*root))
-                    })
-                    .map_err(beacon_chain_error)
+                chain.get_blinded_block(root).and_then(|maybe_block| {
+                    maybe_block.ok_or(BeaconChainError::MissingBeaconBlock(*root))
+                })
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        replayer = replayer
-            .apply_blocks(blocks, None)
-            .map_err(|e| custom_server_error(format!("{:?}", e)))?;
+        replayer = replayer.apply_blocks(blocks, None)?;
     }
 
     drop(replayer);
 
-    Ok(perfs)
+    Ok(())
 }
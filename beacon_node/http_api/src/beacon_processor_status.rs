@@ -0,0 +1,55 @@
+use beacon_processor::{BeaconProcessorStatus, PriorityWeights};
+use eth2::lighthouse::{
+    BeaconProcessorPriorityWeights, BeaconProcessorQueue,
+    BeaconProcessorStatus as BeaconProcessorStatusResponse,
+};
+use std::sync::Arc;
+
+pub fn status(
+    status: Arc<BeaconProcessorStatus>,
+) -> Result<BeaconProcessorStatusResponse, warp::Rejection> {
+    let snapshot = status.snapshot();
+
+    Ok(BeaconProcessorStatusResponse {
+        queues: snapshot
+            .queues
+            .into_iter()
+            .map(|queue| BeaconProcessorQueue {
+                name: queue.name.to_string(),
+                length: queue.length,
+                max_length: queue.max_length,
+                dropped: queue.dropped,
+            })
+            .collect(),
+        active_workers: snapshot.active_workers,
+        max_workers: snapshot.max_workers,
+        priority_weights: weights_response(status.priority_weights()),
+    })
+}
+
+pub fn set_priority_weights(
+    status: Arc<BeaconProcessorStatus>,
+    weights: BeaconProcessorPriorityWeights,
+) -> Result<BeaconProcessorPriorityWeights, warp::Rejection> {
+    status
+        .set_priority_weights(PriorityWeights {
+            attestations: weights.attestations,
+            sync_committee: weights.sync_committee,
+            rpc: weights.rpc,
+            api: weights.api,
+            backfill: weights.backfill,
+        })
+        .map_err(warp_utils::reject::custom_bad_request)?;
+
+    Ok(weights_response(status.priority_weights()))
+}
+
+fn weights_response(weights: PriorityWeights) -> BeaconProcessorPriorityWeights {
+    BeaconProcessorPriorityWeights {
+        attestations: weights.attestations,
+        sync_committee: weights.sync_committee,
+        rpc: weights.rpc,
+        api: weights.api,
+        backfill: weights.backfill,
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{state_id::checkpoint_slot_and_execution_optimistic, ExecutionOptimistic};
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
 use eth2::types::BlockId as CoreBlockId;
+use ssz::Encode;
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -185,6 +186,51 @@ impl BlockId {
         }
     }
 
+    /// Return the SSZ bytes of the `SignedBlindedBeaconBlock` identified by `self`, along with
+    /// its slot.
+    ///
+    /// Blocks other than the head are read directly from the database without being decoded.
+    pub fn blinded_block_ssz<T: BeaconChainTypes>(
+        &self,
+        chain: &BeaconChain<T>,
+    ) -> Result<(Vec<u8>, Slot, ExecutionOptimistic, Finalized), warp::Rejection> {
+        if let CoreBlockId::Head = &self.0 {
+            let (block, execution_optimistic, finalized) = self.blinded_block(chain)?;
+            return Ok((
+                block.as_ssz_bytes(),
+                block.slot(),
+                execution_optimistic,
+                finalized,
+            ));
+        }
+
+        let (root, execution_optimistic, finalized) = self.root(chain)?;
+        let ssz_bytes = chain
+            .get_blinded_block_ssz(&root)
+            .map_err(warp_utils::reject::beacon_chain_error)?
+            .ok_or_else(|| {
+                warp_utils::reject::custom_not_found(format!("beacon block with root {}", root))
+            })?;
+        let block_slot = SignedBlindedBeaconBlock::<T::EthSpec>::slot_from_ssz_bytes(&ssz_bytes)
+            .map_err(|e| {
+                warp_utils::reject::custom_server_error(format!(
+                    "invalid block in database: {:?}",
+                    e
+                ))
+            })?;
+
+        if let CoreBlockId::Slot(slot) = &self.0 {
+            if block_slot != *slot {
+                return Err(warp_utils::reject::custom_not_found(format!(
+                    "slot {} was skipped",
+                    slot
+                )));
+            }
+        }
+
+        Ok((ssz_bytes, block_slot, execution_optimistic, finalized))
+    }
+
     /// Return the `SignedBeaconBlock` identified by `self`.
     pub async fn full_block<T: BeaconChainTypes>(
         &self,
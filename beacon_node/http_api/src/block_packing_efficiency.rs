@@ -2,13 +2,20 @@ use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
+use integer_sqrt::IntegerSquareRoot;
 use parking_lot::Mutex;
+use safe_arith::SafeArith;
+use state_processing::common::{altair::BaseRewardPerIncrement, base};
 use state_processing::{
     per_epoch_processing::EpochProcessingSummary, BlockReplayError, BlockReplayer,
 };
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use types::consts::altair::{
+    PROPOSER_WEIGHT, TIMELY_HEAD_WEIGHT, TIMELY_SOURCE_WEIGHT, TIMELY_TARGET_WEIGHT,
+    WEIGHT_DENOMINATOR,
+};
 use types::{
     BeaconCommittee, BeaconState, BeaconStateError, BlindedPayload, ChainSpec, Epoch, EthSpec,
     Hash256, OwnedBeaconCommittee, RelativeEpoch, SignedBeaconBlock, Slot,
@@ -22,6 +29,7 @@ const BLOCK_ROOT_CHUNK_SIZE: usize = 100;
 enum PackingEfficiencyError {
     BlockReplay(BlockReplayError),
     BeaconState(BeaconStateError),
+    Arith(safe_arith::ArithError),
     CommitteeStoreError(Slot),
     InvalidAttestationError,
 }
@@ -38,6 +46,12 @@ impl From<BeaconStateError> for PackingEfficiencyError {
     }
 }
 
+impl From<safe_arith::ArithError> for PackingEfficiencyError {
+    fn from(e: safe_arith::ArithError) -> Self {
+        Self::Arith(e)
+    }
+}
+
 struct CommitteeStore {
     current_epoch_committees: Vec<OwnedBeaconCommittee>,
     previous_epoch_committees: Vec<OwnedBeaconCommittee>,
@@ -56,9 +70,13 @@ struct PackingEfficiencyHandler<T: EthSpec> {
     current_slot: Slot,
     current_epoch: Epoch,
     prior_skip_slots: u64,
-    available_attestations: HashSet<UniqueAttestation>,
+    /// Attestations which could be included in the next block, mapped to the index of the
+    /// attesting validator.
+    available_attestations: HashMap<UniqueAttestation, usize>,
     included_attestations: HashMap<UniqueAttestation, u64>,
     committee_store: CommitteeStore,
+    /// The total effective balance of the validators in the committees of the current epoch.
+    total_active_balance: u64,
     _phantom: PhantomData<T>,
 }
 
@@ -72,9 +90,10 @@ impl<T: EthSpec> PackingEfficiencyHandler<T> {
             current_slot: start_epoch.start_slot(T::slots_per_epoch()),
             current_epoch: start_epoch,
             prior_skip_slots: 0,
-            available_attestations: HashSet::new(),
+            available_attestations: HashMap::new(),
             included_attestations: HashMap::new(),
             committee_store: CommitteeStore::new(),
+            total_active_balance: 0,
             _phantom: PhantomData,
         };
 
@@ -99,7 +118,7 @@ impl<T: EthSpec> PackingEfficiencyHandler<T> {
     fn prune_available_attestations(&mut self) {
         let slot = self.current_slot;
         self.available_attestations
-            .retain(|x| x.slot >= (slot.as_u64().saturating_sub(T::slots_per_epoch())));
+            .retain(|x, _| x.slot >= (slot.as_u64().saturating_sub(T::slots_per_epoch())));
     }
 
     fn apply_block(
@@ -141,13 +160,14 @@ impl<T: EthSpec> PackingEfficiencyHandler<T> {
     fn add_attestations(&mut self, slot: Slot) -> Result<(), PackingEfficiencyError> {
         let committees = self.get_committees_at_slot(slot)?;
         for committee in committees {
-            for position in 0..committee.committee.len() {
+            for (position, validator_index) in committee.committee.iter().enumerate() {
                 let unique_attestation = UniqueAttestation {
                     slot,
                     committee_index: committee.index,
                     committee_position: position,
                 };
-                self.available_attestations.insert(unique_attestation);
+                self.available_attestations
+                    .insert(unique_attestation, *validator_index);
             }
         }
 
@@ -178,6 +198,17 @@ impl<T: EthSpec> PackingEfficiencyHandler<T> {
                 .collect::<Vec<_>>()
         };
 
+        // Every active validator is a member of exactly one committee per epoch.
+        let mut total_active_balance = 0;
+        for committee in &new_committees {
+            for validator_index in &committee.committee {
+                total_active_balance
+                    .safe_add_assign(state.get_effective_balance(*validator_index)?)?;
+            }
+        }
+        self.total_active_balance =
+            std::cmp::max(total_active_balance, spec.effective_balance_increment);
+
         self.committee_store.previous_epoch_committees =
             self.committee_store.current_epoch_committees.clone();
 
@@ -186,6 +217,69 @@ impl<T: EthSpec> PackingEfficiencyHandler<T> {
         Ok(())
     }
 
+    /// Estimate the reward which the proposer of a block at `block_slot` forwent by not including
+    /// the attestations which are still available.
+    ///
+    /// Each available attestation is assumed to have been produced on time with correct votes, so
+    /// the estimate is an upper bound.
+    fn estimated_missed_reward(
+        &self,
+        state: &BeaconState<T>,
+        block_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<u64, PackingEfficiencyError> {
+        let base_reward_per_increment = match state {
+            BeaconState::Base(_) => None,
+            _ => Some(BaseRewardPerIncrement::new(self.total_active_balance, spec)?.as_u64()),
+        };
+        let max_source_inclusion_delay = T::slots_per_epoch().integer_sqrt();
+
+        let mut missed_reward = 0;
+        for (attestation, validator_index) in &self.available_attestations {
+            let effective_balance = state.get_effective_balance(*validator_index)?;
+            let inclusion_delay = block_slot
+                .as_u64()
+                .saturating_sub(attestation.slot.as_u64());
+
+            let reward = if let Some(base_reward_per_increment) = base_reward_per_increment {
+                let base_reward = effective_balance
+                    .safe_div(spec.effective_balance_increment)?
+                    .safe_mul(base_reward_per_increment)?;
+
+                // The participation flags which a timely attestation would set when included
+                // after `inclusion_delay` slots.
+                let mut weight = 0;
+                if inclusion_delay <= max_source_inclusion_delay {
+                    weight.safe_add_assign(TIMELY_SOURCE_WEIGHT)?;
+                }
+                if inclusion_delay <= T::slots_per_epoch() {
+                    weight.safe_add_assign(TIMELY_TARGET_WEIGHT)?;
+                }
+                if inclusion_delay == spec.min_attestation_inclusion_delay {
+                    weight.safe_add_assign(TIMELY_HEAD_WEIGHT)?;
+                }
+
+                let proposer_reward_denominator = WEIGHT_DENOMINATOR
+                    .safe_sub(PROPOSER_WEIGHT)?
+                    .safe_mul(WEIGHT_DENOMINATOR)?
+                    .safe_div(PROPOSER_WEIGHT)?;
+                base_reward
+                    .safe_mul(weight)?
+                    .safe_div(proposer_reward_denominator)?
+            } else {
+                base::get_base_reward_from_effective_balance::<T>(
+                    effective_balance,
+                    self.total_active_balance,
+                    spec,
+                )?
+                .safe_div(spec.proposer_reward_quotient)?
+            };
+            missed_reward.safe_add_assign(reward)?;
+        }
+
+        Ok(missed_reward)
+    }
+
     fn get_committees_at_slot(
         &self,
         slot: Slot,
@@ -308,7 +402,7 @@ pub fn get_block_packing_efficiency<T: BeaconChainTypes>(
         Ok(())
     };
 
-    let pre_block_hook = |_state: &mut BeaconState<T::EthSpec>,
+    let pre_block_hook = |state: &mut BeaconState<T::EthSpec>,
                           block: &SignedBeaconBlock<_, BlindedPayload<_>>|
      -> Result<(), PackingEfficiencyError> {
         let slot = block.slot();
@@ -328,12 +422,16 @@ pub fn get_block_packing_efficiency<T: BeaconChainTypes>(
         // Get all attestations included in the block.
         let included = handler.lock().apply_block(block)?;
 
+        // Any attestations which remain available could have been included.
+        let estimated_missed_reward = handler.lock().estimated_missed_reward(state, slot, spec)?;
+
         let efficiency = BlockPackingEfficiency {
             slot,
             block_hash: block.canonical_root(),
             proposer_info,
             available_attestations: available_count,
             included_attestations: included,
+            estimated_missed_reward,
             prior_skip_slots: handler.lock().prior_skip_slots,
         };
 
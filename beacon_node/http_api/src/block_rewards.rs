@@ -1,3 +1,4 @@
+use crate::replay_error::ReplayError;
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
 use eth2::lighthouse::{BlockReward, BlockRewardsQuery};
 use lru::LruCache;
@@ -8,6 +9,7 @@ use types::beacon_block::BlindedBeaconBlock;
 use warp_utils::reject::{
     beacon_chain_error, beacon_state_error, custom_bad_request, custom_server_error,
 };
+use warp_utils::task::CancellationToken;
 
 const STATE_CACHE_SIZE: usize = 2;
 
@@ -16,6 +18,7 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
     query: BlockRewardsQuery,
     chain: Arc<BeaconChain<T>>,
     log: Logger,
+    cancellation: CancellationToken,
 ) -> Result<Vec<BlockReward>, warp::Rejection> {
     let start_slot = query.start_slot;
     let end_slot = query.end_slot;
@@ -33,6 +36,8 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
         .map_err(beacon_chain_error)?
         .ok_or_else(|| custom_bad_request(format!("block at end slot {} unknown", end_slot)))?;
 
+    cancellation.check()?;
+
     let blocks = chain
         .store
         .load_blocks_to_replay(start_slot, end_slot, end_block_root)
@@ -43,6 +48,8 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
         .map_err(beacon_chain_error)?
         .ok_or_else(|| custom_bad_request(format!("prior state at slot {} unknown", prior_slot)))?;
 
+    cancellation.check()?;
+
     let mut state = chain
         .get_state(&state_root, Some(prior_slot))
         .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
@@ -55,8 +62,11 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
     let mut reward_cache = Default::default();
     let mut block_rewards = Vec::with_capacity(blocks.len());
 
-    let block_replayer = BlockReplayer::new(state, &chain.spec)
+    cancellation.check()?;
+
+    let block_replayer = BlockReplayer::<_, ReplayError, _>::new(state, &chain.spec)
         .pre_block_hook(Box::new(|state, block| {
+            ReplayError::check(&cancellation)?;
             state.build_all_committee_caches(&chain.spec)?;
 
             // Compute block reward.
@@ -73,12 +83,13 @@ pub fn get_block_rewards<T: BeaconChainTypes>(
         .state_root_iter(
             chain
                 .forwards_iter_state_roots_until(prior_slot, end_slot)
-                .map_err(beacon_chain_error)?,
+                .map_err(beacon_chain_error)?
+                .map(|result| result.map_err(ReplayError::from)),
         )
         .no_signature_verification()
         .minimal_block_root_verification()
         .apply_blocks(blocks, None)
-        .map_err(beacon_chain_error)?;
+        .map_err(|e| e.into_rejection(&cancellation))?;
 
     if block_replayer.state_root_miss() {
         warn!(
@@ -99,12 +110,14 @@ pub fn compute_block_rewards<T: BeaconChainTypes>(
     blocks: Vec<BlindedBeaconBlock<T::EthSpec>>,
     chain: Arc<BeaconChain<T>>,
     log: Logger,
+    cancellation: CancellationToken,
 ) -> Result<Vec<BlockReward>, warp::Rejection> {
     let mut block_rewards = Vec::with_capacity(blocks.len());
     let mut state_cache = LruCache::new(STATE_CACHE_SIZE);
     let mut reward_cache = Default::default();
 
     for block in blocks {
+        cancellation.check()?;
         let parent_root = block.parent_root();
 
         // Check LRU cache for a constructed state from a previous iteration.
@@ -133,6 +146,8 @@ pub fn compute_block_rewards<T: BeaconChainTypes>(
                     ))
                 })?;
 
+            cancellation.check()?;
+
             let parent_state = chain
                 .get_state(&parent_block.state_root(), Some(parent_block.slot()))
                 .map_err(beacon_chain_error)?
@@ -143,12 +158,13 @@ pub fn compute_block_rewards<T: BeaconChainTypes>(
                     ))
                 })?;
 
-            let block_replayer = BlockReplayer::new(parent_state, &chain.spec)
+            let block_replayer = BlockReplayer::<_, ReplayError, _>::new(parent_state, &chain.spec)
                 .no_signature_verification()
+                .pre_slot_hook(Box::new(|_| ReplayError::check(&cancellation)))
                 .state_root_iter([Ok((parent_block.state_root(), parent_block.slot()))].into_iter())
                 .minimal_block_root_verification()
                 .apply_blocks(vec![], Some(block.slot()))
-                .map_err(beacon_chain_error)?;
+                .map_err(|e| e.into_rejection(&cancellation))?;
 
             if block_replayer.state_root_miss() {
                 warn!(
@@ -1,8 +1,14 @@
-use beacon_chain::store::{metadata::CURRENT_SCHEMA_VERSION, AnchorInfo};
+use beacon_chain::store::{
+    metadata::{CURRENT_SCHEMA_VERSION, MIN_SUPPORTED_SCHEMA_VERSION},
+    AnchorInfo,
+};
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::lighthouse::DatabaseInfo;
+use eth2::lighthouse::{BackupStatus, DatabaseBackupRequest, DatabaseInfo};
+use slog::{error, info, Logger};
+use ssz::BYTES_PER_LENGTH_OFFSET;
+use std::path::PathBuf;
 use std::sync::Arc;
-use types::SignedBlindedBeaconBlock;
+use types::{ChainSpec, EthSpec, SignedBeaconBlock, SignedBlindedBeaconBlock};
 
 pub fn info<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
@@ -14,6 +20,8 @@ pub fn info<T: BeaconChainTypes>(
 
     Ok(DatabaseInfo {
         schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
+        min_schema_version: MIN_SUPPORTED_SCHEMA_VERSION.as_u64(),
+        max_schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
         config,
         split,
         anchor,
@@ -33,3 +41,138 @@ pub fn historical_blocks<T: BeaconChainTypes>(
     })?;
     Ok(anchor)
 }
+
+/// Import historical blocks from the SSZ encoding of a list of `SignedBeaconBlock`s, such as the
+/// blocks of an era file or a dump from a trusted node.
+///
+/// Only the blinded blocks are stored, the execution payloads are retrieved from the execution
+/// layer when the blocks are served.
+pub fn historical_blocks_ssz<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    bytes: &[u8],
+    log: Logger,
+) -> Result<AnchorInfo, warp::Rejection> {
+    let blocks = decode_block_list::<T::EthSpec>(bytes, &chain.spec)
+        .map_err(|e| warp_utils::reject::custom_bad_request(format!("invalid SSZ: {e}")))?;
+
+    info!(
+        log,
+        "Importing historical blocks";
+        "count" => blocks.len(),
+        "source" => "http_api_ssz"
+    );
+
+    let blinded_blocks = blocks
+        .into_iter()
+        .map(|block| Arc::new(block.into()))
+        .collect();
+    historical_blocks(chain, blinded_blocks)
+}
+
+/// Decode an SSZ list of `SignedBeaconBlock`s.
+///
+/// The blocks are variable-length so the list starts with the offset of each block.
+fn decode_block_list<E: EthSpec>(
+    bytes: &[u8],
+    spec: &ChainSpec,
+) -> Result<Vec<SignedBeaconBlock<E>>, String> {
+    if bytes.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let read_offset = |i: usize| -> Result<usize, String> {
+        let start = i * BYTES_PER_LENGTH_OFFSET;
+        let offset_bytes = bytes
+            .get(start..start + BYTES_PER_LENGTH_OFFSET)
+            .ok_or_else(|| format!("missing offset {i}"))?;
+        let mut array = [0; BYTES_PER_LENGTH_OFFSET];
+        array.copy_from_slice(offset_bytes);
+        Ok(u32::from_le_bytes(array) as usize)
+    };
+
+    let first_offset = read_offset(0)?;
+    if first_offset == 0 || first_offset % BYTES_PER_LENGTH_OFFSET != 0 {
+        return Err(format!("invalid first offset {first_offset}"));
+    }
+    let num_blocks = first_offset / BYTES_PER_LENGTH_OFFSET;
+
+    let mut offsets = (0..num_blocks)
+        .map(read_offset)
+        .collect::<Result<Vec<_>, _>>()?;
+    offsets.push(bytes.len());
+
+    offsets
+        .windows(2)
+        .enumerate()
+        .map(|(i, window)| {
+            let block_bytes = bytes
+                .get(window[0]..window[1])
+                .ok_or_else(|| format!("invalid offset for block {i}"))?;
+            SignedBeaconBlock::from_ssz_bytes(block_bytes, spec)
+                .map_err(|e| format!("invalid block {i}: {e:?}"))
+        })
+        .collect()
+}
+
+pub fn backup_status<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+) -> Result<Option<BackupStatus>, warp::Rejection> {
+    Ok(chain.store.backup_status())
+}
+
+/// Returns `true` if `name` is safe to use as the name of a directory within the backup directory.
+fn is_valid_backup_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Start a backup of the hot database into a sub-directory of `backup_dir` in the background. Its
+/// progress can be followed with `backup_status`.
+pub fn start_backup<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    backup_dir: Option<PathBuf>,
+    request: DatabaseBackupRequest,
+    log: Logger,
+) -> Result<&'static str, warp::Rejection> {
+    let backup_dir = backup_dir.ok_or_else(|| {
+        warp_utils::reject::custom_not_found(
+            "database backups are disabled, see --http-database-backup-dir".to_string(),
+        )
+    })?;
+    if !is_valid_backup_name(&request.name) {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "invalid backup name {:?}, names may only contain alphanumeric, '-' or '_' characters",
+            request.name
+        )));
+    }
+    let target_dir = backup_dir.join(&request.name);
+
+    if chain
+        .store
+        .backup_status()
+        .map_or(false, |status| !status.complete)
+    {
+        return Err(warp_utils::reject::custom_bad_request(
+            "a backup is already in progress".to_string(),
+        ));
+    }
+
+    let store = chain.store.clone();
+    chain.task_executor.spawn_blocking(
+        move || {
+            if let Err(e) = store.backup_hot_db(&target_dir, request.max_bytes_per_second) {
+                error!(
+                    log,
+                    "Hot database backup failed";
+                    "error" => ?e,
+                    "target_dir" => ?target_dir,
+                );
+            }
+        },
+        "database_backup",
+    );
+
+    Ok("started")
+}
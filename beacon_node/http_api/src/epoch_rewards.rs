@@ -0,0 +1,118 @@
+use crate::task_spawner::{Priority, TaskSpawner};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::EpochRewardsQuery;
+use eth2::types::{ErrorMessage, ValidatorId};
+use serde::Serialize;
+use slog::{warn, Logger};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, StreamExt};
+use warp::http::{Response, StatusCode};
+use warp::hyper::Body;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// The number of epochs which may be computed ahead of the client reading them.
+const EPOCH_REWARDS_BUFFER_SIZE: usize = 4;
+
+/// Stream the rewards of `validators` for each epoch of `query` as newline-delimited JSON.
+///
+/// The computation runs in the background, sending each epoch to the client as soon as it is
+/// known. Errors which occur once streaming has begun are sent as a final `ErrorMessage` line.
+pub fn stream_epoch_rewards<T: BeaconChainTypes>(
+    query: EpochRewardsQuery,
+    validators: Vec<ValidatorId>,
+    task_spawner: TaskSpawner<T::EthSpec>,
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) -> Result<Response<Body>, warp::Rejection> {
+    let EpochRewardsQuery {
+        start_epoch,
+        end_epoch,
+    } = query;
+    if start_epoch > end_epoch {
+        return Err(custom_bad_request(format!(
+            "invalid start and end epochs: {}, {}",
+            start_epoch, end_epoch
+        )));
+    }
+
+    let (tx, rx) = mpsc::channel(EPOCH_REWARDS_BUFFER_SIZE);
+    let task_tx = tx.clone();
+
+    tokio::spawn(async move {
+        let response = task_spawner
+            .blocking_response_task(Priority::P1, move || {
+                let result = chain.compute_epoch_rewards_batch(
+                    start_epoch,
+                    end_epoch,
+                    validators,
+                    |rewards| {
+                        // Stop computing if the client has gone away.
+                        task_tx.blocking_send(ndjson_line(&rewards)).is_ok()
+                    },
+                );
+
+                if let Err(e) = result {
+                    warn!(
+                        log,
+                        "Failed to compute epoch rewards";
+                        "start_epoch" => start_epoch,
+                        "end_epoch" => end_epoch,
+                        "error" => ?e,
+                    );
+                    let _ = task_tx.blocking_send(ndjson_line(&error_message(e)));
+                }
+                Ok(warp::reply())
+            })
+            .await;
+
+        // The task never ran, e.g. because the beacon processor is overloaded.
+        if !response.status().is_success() {
+            let message = ErrorMessage {
+                code: response.status().as_u16(),
+                message: "unable to schedule rewards computation".to_string(),
+                stacktraces: vec![],
+            };
+            let _ = tx.send(ndjson_line(&message)).await;
+        }
+    });
+
+    let body = Body::wrap_stream(ReceiverStream::new(rx).map(Ok::<_, Infallible>));
+    Response::builder()
+        .status(200)
+        .header("Content-Type", "application/x-ndjson")
+        .body(body)
+        .map_err(|e| custom_server_error(format!("failed to create response: {}", e)))
+}
+
+pub(crate) fn ndjson_line<T: Serialize>(item: &T) -> Vec<u8> {
+    let mut line = serde_json::to_vec(item).unwrap_or_default();
+    line.push(b'\n');
+    line
+}
+
+fn error_message(e: BeaconChainError) -> ErrorMessage {
+    let (code, message) = match e {
+        BeaconChainError::MissingBeaconState(root) => {
+            (StatusCode::NOT_FOUND, format!("missing state {root:?}"))
+        }
+        BeaconChainError::NoStateForSlot(slot) => (
+            StatusCode::NOT_FOUND,
+            format!("missing state at slot {slot}"),
+        ),
+        BeaconChainError::ValidatorPubkeyUnknown(pubkey) => (
+            StatusCode::BAD_REQUEST,
+            format!("validator pubkey is unknown: {pubkey:?}"),
+        ),
+        e => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("unexpected error: {:?}", e),
+        ),
+    };
+    ErrorMessage {
+        code: code.as_u16(),
+        message,
+        stacktraces: vec![],
+    }
+}
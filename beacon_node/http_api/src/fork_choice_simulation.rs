@@ -0,0 +1,29 @@
+use beacon_chain::fork_choice_simulation::{
+    Error, ForkChoiceSimulation, ForkChoiceSimulationRequest,
+};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use std::sync::Arc;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// Run a copy of fork choice with the hypothetical block and attestations of `request`.
+pub fn simulate_fork_choice<T: BeaconChainTypes>(
+    request: ForkChoiceSimulationRequest<T::EthSpec>,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ForkChoiceSimulation, warp::Rejection> {
+    chain.simulate_fork_choice(&request).map_err(|e| match e {
+        Error::UnknownParent(parent_root) => {
+            custom_bad_request(format!("block parent {:?} is unknown", parent_root))
+        }
+        Error::BlockProcessing(e) => {
+            custom_bad_request(format!("block could not be processed: {:?}", e))
+        }
+        Error::InvalidBlock(e) => {
+            custom_bad_request(format!("block rejected by fork choice: {:?}", e))
+        }
+        Error::InvalidAttestation { index, error } => custom_bad_request(format!(
+            "attestation {} rejected by fork choice: {:?}",
+            index, error
+        )),
+        Error::BeaconChainError(e) => beacon_chain_error(e),
+    })
+}
@@ -7,14 +7,21 @@
 
 mod attestation_performance;
 mod attester_duties;
+mod beacon_processor_status;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod builder_states;
 mod database;
+mod epoch_rewards;
+mod fork_choice_simulation;
+mod light_client;
 mod metrics;
+mod peer_scores;
 mod proposer_duties;
 mod publish_blocks;
+mod replay_error;
+mod ssz_response;
 mod standard_block_rewards;
 mod state_id;
 mod sync_committee_rewards;
@@ -24,14 +31,17 @@ pub mod test_utils;
 mod ui;
 mod validator;
 mod validator_inclusion;
+mod validators;
 mod version;
 
 use beacon_chain::{
-    attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
-    validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, ProduceBlockVerification, WhenSlotSkipped,
+    attestation_verification::VerifiedAttestation,
+    observed_operations::ObservationOutcome,
+    validator_monitor::{is_valid_cohort, timestamp_now, MetricAggregation, MAX_COHORT_LEN},
+    AttestationError as AttnError, BeaconChain, BeaconChainError, BeaconChainTypes,
+    ProduceBlockVerification, WhenSlotSkipped,
 };
-use beacon_processor::BeaconProcessorSend;
+use beacon_processor::{BeaconProcessorSend, BeaconProcessorStatus};
 pub use block_id::BlockId;
 use builder_states::get_next_withdrawals;
 use bytes::Bytes;
@@ -40,26 +50,33 @@ use eth2::types::{
     self as api_types, BroadcastValidation, EndpointVersion, ForkChoice, ForkChoiceNode,
     SkipRandaoVerification, ValidatorId, ValidatorStatus,
 };
-use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
+use lighthouse_network::{
+    types::SyncState, EnrExt, NetworkGlobals, OperatorAction, PeerId, PubsubMessage,
+};
 use lighthouse_version::version_with_platform;
-use logging::SSELoggingComponents;
+use logging::{RuntimeLogFilter, SSELoggingComponents};
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
 use operation_pool::ReceivedPreCapella;
+use otlp::{OtlpExporter, Span, TraceContext, TRACEPARENT_HEADER};
 use parking_lot::RwLock;
 pub use publish_blocks::{
     publish_blinded_block, publish_block, reconstruct_block, ProvenancedBlock,
 };
+use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
+use ssz_response::{beacon_state_ssz_response, ssz_response, SSZ_CONTENT_TYPE};
 pub use state_id::StateId;
 use std::borrow::Cow;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use sysinfo::{System, SystemExt};
 use system_health::observe_system_health_bn;
 use task_spawner::{Priority, TaskSpawner};
@@ -74,10 +91,10 @@ use tokio_stream::{
 use types::{
     Attestation, AttestationData, AttestationShufflingId, AttesterSlashing, BeaconStateError,
     BlindedPayload, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName, FullPayload,
-    ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
-    SignedBeaconBlock, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    Hash256, IndexedAttestation, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedBlindedBeaconBlock,
+    SignedBlsToExecutionChange, SignedContributionAndProof, SignedValidatorRegistrationData,
+    SignedVoluntaryExit, Slot, SyncCommitteeMessage, SyncContributionData,
 };
 use validator::pubkey_to_validator_index;
 use version::{
@@ -99,6 +116,18 @@ const API_PREFIX: &str = "eth";
 /// finalized head.
 const SYNC_TOLERANCE_EPOCHS: u64 = 8;
 
+/// The maximum time spent computing rewards for a single request.
+///
+/// Rewards are computed by replaying blocks on top of historic states, which can occupy a worker
+/// thread for a long time.
+const REWARDS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The maximum time spent reading a state and computing the response for a single request.
+///
+/// States which are not cached may be reconstructed by replaying blocks from the freezer
+/// database.
+const STATE_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// A custom type which allows for both unsecured and TLS-enabled HTTP servers.
 type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
 
@@ -121,8 +150,13 @@ pub struct Context<T: BeaconChainTypes> {
     pub network_senders: Option<NetworkSenders<T::EthSpec>>,
     pub network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     pub beacon_processor_send: Option<BeaconProcessorSend<T::EthSpec>>,
+    pub beacon_processor_status: Option<Arc<BeaconProcessorStatus>>,
     pub eth1_service: Option<eth1::Service>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    /// Allows the log filters to be adjusted via the API.
+    pub runtime_log_filter: Option<RuntimeLogFilter>,
+    /// Records the spans of traced block proposals, if tracing is enabled.
+    pub otlp_exporter: Option<OtlpExporter>,
     pub log: Logger,
 }
 
@@ -141,6 +175,18 @@ pub struct Config {
     pub enable_beacon_processor: bool,
     #[serde(with = "eth2::types::serde_status_code")]
     pub duplicate_block_status_code: StatusCode,
+    /// Bearer token required to register validators with the validator monitor, or `None` to
+    /// disable registration via the API.
+    pub validator_monitor_registration_token: Option<String>,
+    /// Bearer token required by endpoints which change the node's behaviour at runtime, such as
+    /// `POST lighthouse/logging`, or `None` to disable them.
+    pub admin_token: Option<String>,
+    /// Directory into which backups of the hot database are written, or `None` to disable backups
+    /// via the API.
+    pub database_backup_dir: Option<PathBuf>,
+    /// The OTLP/HTTP collector to which the spans of traced block proposals are exported, or
+    /// `None` to disable tracing.
+    pub otlp_endpoint: Option<SensitiveUrl>,
 }
 
 impl Default for Config {
@@ -157,6 +203,10 @@ impl Default for Config {
             sse_capacity_multiplier: 1,
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
+            validator_monitor_registration_token: None,
+            admin_token: None,
+            database_backup_dir: None,
+            otlp_endpoint: None,
         }
     }
 }
@@ -213,70 +263,6 @@ pub fn slog_logging(
     })
 }
 
-/// Creates a `warp` logging wrapper which we use for Prometheus metrics (not necessarily logging,
-/// per say).
-pub fn prometheus_metrics() -> warp::filters::log::Log<impl Fn(warp::filters::log::Info) + Clone> {
-    warp::log::custom(move |info| {
-        // Here we restrict the `info.path()` value to some predefined values. Without this, we end
-        // up with a new metric type each time someone includes something unique in the path (e.g.,
-        // a block hash).
-        let path = {
-            let equals = |s: &'static str| -> Option<&'static str> {
-                if info.path() == format!("/{}/{}", API_PREFIX, s) {
-                    Some(s)
-                } else {
-                    None
-                }
-            };
-
-            let starts_with = |s: &'static str| -> Option<&'static str> {
-                if info.path().starts_with(&format!("/{}/{}", API_PREFIX, s)) {
-                    Some(s)
-                } else {
-                    None
-                }
-            };
-
-            // First line covers `POST /v1/beacon/blocks` only
-            equals("v1/beacon/blocks")
-                .or_else(|| starts_with("v1/validator/blocks"))
-                .or_else(|| starts_with("v2/validator/blocks"))
-                .or_else(|| starts_with("v1/validator/blinded_blocks"))
-                .or_else(|| starts_with("v1/validator/duties/attester"))
-                .or_else(|| starts_with("v1/validator/duties/proposer"))
-                .or_else(|| starts_with("v1/validator/duties/sync"))
-                .or_else(|| starts_with("v1/validator/attestation_data"))
-                .or_else(|| starts_with("v1/validator/aggregate_attestation"))
-                .or_else(|| starts_with("v1/validator/aggregate_and_proofs"))
-                .or_else(|| starts_with("v1/validator/sync_committee_contribution"))
-                .or_else(|| starts_with("v1/validator/contribution_and_proofs"))
-                .or_else(|| starts_with("v1/validator/beacon_committee_subscriptions"))
-                .or_else(|| starts_with("v1/validator/sync_committee_subscriptions"))
-                .or_else(|| starts_with("v1/beacon/pool/attestations"))
-                .or_else(|| starts_with("v1/beacon/pool/sync_committees"))
-                .or_else(|| starts_with("v1/beacon/blocks/head/root"))
-                .or_else(|| starts_with("v1/validator/prepare_beacon_proposer"))
-                .or_else(|| starts_with("v1/validator/register_validator"))
-                .or_else(|| starts_with("v1/beacon/"))
-                .or_else(|| starts_with("v2/beacon/"))
-                .or_else(|| starts_with("v1/config/"))
-                .or_else(|| starts_with("v1/debug/"))
-                .or_else(|| starts_with("v2/debug/"))
-                .or_else(|| starts_with("v1/events/"))
-                .or_else(|| starts_with("v1/node/"))
-                .or_else(|| starts_with("v1/validator/"))
-                .unwrap_or("other")
-        };
-
-        metrics::inc_counter_vec(&metrics::HTTP_API_PATHS_TOTAL, &[path]);
-        metrics::inc_counter_vec(
-            &metrics::HTTP_API_STATUS_CODES_TOTAL,
-            &[&info.status().to_string()],
-        );
-        metrics::observe_timer_vec(&metrics::HTTP_API_PATHS_TIMES, &[path], info.elapsed());
-    })
-}
-
 /// Creates a server that will serve requests using information from `ctx`.
 ///
 /// The server will shut down gracefully when the `shutdown` future resolves.
@@ -359,6 +345,32 @@ pub fn serve<T: BeaconChainTypes>(
             }
         });
 
+    // Create a `warp` filter that provides access to the beacon processor's status.
+    let inner_beacon_processor_status = ctx.beacon_processor_status.clone();
+    let beacon_processor_status_filter = warp::any()
+        .map(move || inner_beacon_processor_status.clone())
+        .and_then(|beacon_processor_status| async move {
+            match beacon_processor_status {
+                Some(status) => Ok(status),
+                None => Err(warp_utils::reject::custom_not_found(
+                    "beacon processor is not initialized.".to_string(),
+                )),
+            }
+        });
+
+    // Create a `warp` filter that provides access to the runtime-adjustable log filters.
+    let inner_runtime_log_filter = ctx.runtime_log_filter.clone();
+    let runtime_log_filter_filter = warp::any()
+        .map(move || inner_runtime_log_filter.clone())
+        .and_then(|runtime_log_filter| async move {
+            match runtime_log_filter {
+                Some(runtime_log_filter) => Ok(runtime_log_filter),
+                None => Err(warp_utils::reject::custom_not_found(
+                    "runtime log filters are not available.".to_string(),
+                )),
+            }
+        });
+
     // Create a `warp` filter for the data_dir.
     let inner_data_dir = ctx.config.data_dir.clone();
     let data_dir_filter = warp::any().map(move || inner_data_dir.clone());
@@ -515,6 +527,32 @@ pub fn serve<T: BeaconChainTypes>(
 
     let duplicate_block_status_code = ctx.config.duplicate_block_status_code;
 
+    // Create a `warp` filter that starts a span named `name` for requests which carry the
+    // `traceparent` header of a traced block proposal. No spans are started if tracing is disabled.
+    let otlp_exporter = ctx.otlp_exporter.clone();
+    let trace_filter = move |name: &'static str| {
+        let otlp_exporter = otlp_exporter.clone();
+        warp::header::optional::<String>(TRACEPARENT_HEADER).map(
+            move |traceparent: Option<String>| {
+                let parent = TraceContext::from_traceparent(&traceparent?)?;
+                Some(otlp_exporter.as_ref()?.start_span(name, Some(parent)))
+            },
+        )
+    };
+
+    // Create a `warp` filter that rejects requests without the validator monitor registration
+    // token.
+    let validator_monitor_auth_filter = warp_utils::auth::bearer_token_filter(
+        ctx.config.validator_monitor_registration_token.clone(),
+        "validator monitor registration is disabled",
+    );
+
+    // Create a `warp` filter that rejects requests without the admin token.
+    let admin_auth_filter = warp_utils::auth::bearer_token_filter(
+        ctx.config.admin_token.clone(),
+        "admin endpoints are disabled, see --http-admin-token-file",
+    );
+
     /*
      *
      * Start of HTTP method definitions.
@@ -644,50 +682,55 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let query = query_res?;
-                    let (data, execution_optimistic, finalized) = state_id
-                        .map_state_and_execution_optimistic_and_finalized(
-                            &chain,
-                            |state, execution_optimistic, finalized| {
-                                Ok((
-                                    state
-                                        .validators()
-                                        .iter()
-                                        .zip(state.balances().iter())
-                                        .enumerate()
-                                        // filter by validator id(s) if provided
-                                        .filter(|(index, (validator, _))| {
-                                            query.id.as_ref().map_or(true, |ids| {
-                                                ids.iter().any(|id| match id {
-                                                    ValidatorId::PublicKey(pubkey) => {
-                                                        &validator.pubkey == pubkey
-                                                    }
-                                                    ValidatorId::Index(param_index) => {
-                                                        *param_index == *index as u64
-                                                    }
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    STATE_READ_TIMEOUT,
+                    move |cancellation| {
+                        let query = query_res?;
+                        let (data, execution_optimistic, finalized) = state_id
+                            .map_state_and_execution_optimistic_and_finalized(
+                                &chain,
+                                |state, execution_optimistic, finalized| {
+                                    cancellation.check()?;
+                                    Ok((
+                                        state
+                                            .validators()
+                                            .iter()
+                                            .zip(state.balances().iter())
+                                            .enumerate()
+                                            // filter by validator id(s) if provided
+                                            .filter(|(index, (validator, _))| {
+                                                query.id.as_ref().map_or(true, |ids| {
+                                                    ids.iter().any(|id| match id {
+                                                        ValidatorId::PublicKey(pubkey) => {
+                                                            &validator.pubkey == pubkey
+                                                        }
+                                                        ValidatorId::Index(param_index) => {
+                                                            *param_index == *index as u64
+                                                        }
+                                                    })
                                                 })
                                             })
-                                        })
-                                        .map(|(index, (_, balance))| {
-                                            Some(api_types::ValidatorBalanceData {
-                                                index: index as u64,
-                                                balance: *balance,
+                                            .map(|(index, (_, balance))| {
+                                                Some(api_types::ValidatorBalanceData {
+                                                    index: index as u64,
+                                                    balance: *balance,
+                                                })
                                             })
-                                        })
-                                        .collect::<Vec<_>>(),
-                                    execution_optimistic,
-                                    finalized,
-                                ))
-                            },
-                        )?;
+                                            .collect::<Vec<_>>(),
+                                        execution_optimistic,
+                                        finalized,
+                                    ))
+                                },
+                            )?;
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
-                })
+                        Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                            data,
+                            execution_optimistic: Some(execution_optimistic),
+                            finalized: Some(finalized),
+                        })
+                    },
+                )
             },
         );
 
@@ -702,72 +745,81 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query_res: Result<api_types::ValidatorsQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let query = query_res?;
-                    let (data, execution_optimistic, finalized) = state_id
-                        .map_state_and_execution_optimistic_and_finalized(
-                            &chain,
-                            |state, execution_optimistic, finalized| {
-                                let epoch = state.current_epoch();
-                                let far_future_epoch = chain.spec.far_future_epoch;
-
-                                Ok((
-                                    state
-                                        .validators()
-                                        .iter()
-                                        .zip(state.balances().iter())
-                                        .enumerate()
-                                        // filter by validator id(s) if provided
-                                        .filter(|(index, (validator, _))| {
-                                            query.id.as_ref().map_or(true, |ids| {
-                                                ids.iter().any(|id| match id {
-                                                    ValidatorId::PublicKey(pubkey) => {
-                                                        &validator.pubkey == pubkey
-                                                    }
-                                                    ValidatorId::Index(param_index) => {
-                                                        *param_index == *index as u64
-                                                    }
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    STATE_READ_TIMEOUT,
+                    move |cancellation| {
+                        let query = query_res?;
+                        let (data, execution_optimistic, finalized) = state_id
+                            .map_state_and_execution_optimistic_and_finalized(
+                                &chain,
+                                |state, execution_optimistic, finalized| {
+                                    cancellation.check()?;
+                                    let epoch = state.current_epoch();
+                                    let far_future_epoch = chain.spec.far_future_epoch;
+
+                                    Ok((
+                                        state
+                                            .validators()
+                                            .iter()
+                                            .zip(state.balances().iter())
+                                            .enumerate()
+                                            // filter by validator id(s) if provided
+                                            .filter(|(index, (validator, _))| {
+                                                query.id.as_ref().map_or(true, |ids| {
+                                                    ids.iter().any(|id| match id {
+                                                        ValidatorId::PublicKey(pubkey) => {
+                                                            &validator.pubkey == pubkey
+                                                        }
+                                                        ValidatorId::Index(param_index) => {
+                                                            *param_index == *index as u64
+                                                        }
+                                                    })
                                                 })
                                             })
-                                        })
-                                        // filter by status(es) if provided and map the result
-                                        .filter_map(|(index, (validator, balance))| {
-                                            let status = api_types::ValidatorStatus::from_validator(
-                                                validator,
-                                                epoch,
-                                                far_future_epoch,
-                                            );
-
-                                            let status_matches =
-                                                query.status.as_ref().map_or(true, |statuses| {
-                                                    statuses.contains(&status)
-                                                        || statuses.contains(&status.superstatus())
-                                                });
+                                            // filter by status(es) if provided and map the result
+                                            .filter_map(|(index, (validator, balance))| {
+                                                let status =
+                                                    api_types::ValidatorStatus::from_validator(
+                                                        validator,
+                                                        epoch,
+                                                        far_future_epoch,
+                                                    );
+
+                                                let status_matches = query.status.as_ref().map_or(
+                                                    true,
+                                                    |statuses| {
+                                                        statuses.contains(&status)
+                                                            || statuses
+                                                                .contains(&status.superstatus())
+                                                    },
+                                                );
 
-                                            if status_matches {
-                                                Some(api_types::ValidatorData {
-                                                    index: index as u64,
-                                                    balance: *balance,
-                                                    status,
-                                                    validator: validator.clone(),
-                                                })
-                                            } else {
-                                                None
-                                            }
-                                        })
-                                        .collect::<Vec<_>>(),
-                                    execution_optimistic,
-                                    finalized,
-                                ))
-                            },
-                        )?;
+                                                if status_matches {
+                                                    Some(api_types::ValidatorData {
+                                                        index: index as u64,
+                                                        balance: *balance,
+                                                        status,
+                                                        validator: validator.clone(),
+                                                    })
+                                                } else {
+                                                    None
+                                                }
+                                            })
+                                            .collect::<Vec<_>>(),
+                                        execution_optimistic,
+                                        finalized,
+                                    ))
+                                },
+                            )?;
 
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
-                })
+                        Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                            data,
+                            execution_optimistic: Some(execution_optimistic),
+                            finalized: Some(finalized),
+                        })
+                    },
+                )
             },
         );
 
@@ -854,175 +906,185 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query: api_types::CommitteesQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let (data, execution_optimistic, finalized) = state_id
-                        .map_state_and_execution_optimistic_and_finalized(
-                            &chain,
-                            |state, execution_optimistic, finalized| {
-                                let current_epoch = state.current_epoch();
-                                let epoch = query.epoch.unwrap_or(current_epoch);
-
-                                // Attempt to obtain the committee_cache from the beacon chain
-                                let decision_slot = (epoch.saturating_sub(2u64))
-                                    .end_slot(T::EthSpec::slots_per_epoch());
-                                // Find the decision block and skip to another method on any kind
-                                // of failure
-                                let shuffling_id = if let Ok(Some(shuffling_decision_block)) =
-                                    chain.block_root_at_slot(decision_slot, WhenSlotSkipped::Prev)
-                                {
-                                    Some(AttestationShufflingId {
-                                        shuffling_epoch: epoch,
-                                        shuffling_decision_block,
-                                    })
-                                } else {
-                                    None
-                                };
-
-                                // Attempt to read from the chain cache if there exists a
-                                // shuffling_id
-                                let maybe_cached_shuffling = if let Some(shuffling_id) =
-                                    shuffling_id.as_ref()
-                                {
-                                    chain
-                                        .shuffling_cache
-                                        .try_write_for(std::time::Duration::from_secs(1))
-                                        .and_then(|mut cache_write| cache_write.get(shuffling_id))
-                                        .and_then(|cache_item| cache_item.wait().ok())
-                                } else {
-                                    None
-                                };
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    STATE_READ_TIMEOUT,
+                    move |cancellation| {
+                        let (data, execution_optimistic, finalized) = state_id
+                            .map_state_and_execution_optimistic_and_finalized(
+                                &chain,
+                                |state, execution_optimistic, finalized| {
+                                    cancellation.check()?;
+                                    let current_epoch = state.current_epoch();
+                                    let epoch = query.epoch.unwrap_or(current_epoch);
+
+                                    // Attempt to obtain the committee_cache from the beacon chain
+                                    let decision_slot = (epoch.saturating_sub(2u64))
+                                        .end_slot(T::EthSpec::slots_per_epoch());
+                                    // Find the decision block and skip to another method on any kind
+                                    // of failure
+                                    let shuffling_id = if let Ok(Some(shuffling_decision_block)) =
+                                        chain.block_root_at_slot(
+                                            decision_slot,
+                                            WhenSlotSkipped::Prev,
+                                        ) {
+                                        Some(AttestationShufflingId {
+                                            shuffling_epoch: epoch,
+                                            shuffling_decision_block,
+                                        })
+                                    } else {
+                                        None
+                                    };
+
+                                    // Attempt to read from the chain cache if there exists a
+                                    // shuffling_id
+                                    let maybe_cached_shuffling =
+                                        if let Some(shuffling_id) = shuffling_id.as_ref() {
+                                            chain
+                                                .shuffling_cache
+                                                .try_write_for(std::time::Duration::from_secs(1))
+                                                .and_then(|mut cache_write| {
+                                                    cache_write.get(shuffling_id)
+                                                })
+                                                .and_then(|cache_item| cache_item.wait().ok())
+                                        } else {
+                                            None
+                                        };
 
-                                let committee_cache = if let Some(ref shuffling) =
-                                    maybe_cached_shuffling
-                                {
-                                    Cow::Borrowed(&**shuffling)
-                                } else {
-                                    let possibly_built_cache =
-                                        match RelativeEpoch::from_epoch(current_epoch, epoch) {
-                                            Ok(relative_epoch)
-                                                if state.committee_cache_is_initialized(
-                                                    relative_epoch,
-                                                ) =>
-                                            {
-                                                state
-                                                    .committee_cache(relative_epoch)
-                                                    .map(Cow::Borrowed)
+                                    let committee_cache = if let Some(ref shuffling) =
+                                        maybe_cached_shuffling
+                                    {
+                                        Cow::Borrowed(&**shuffling)
+                                    } else {
+                                        let possibly_built_cache =
+                                            match RelativeEpoch::from_epoch(current_epoch, epoch) {
+                                                Ok(relative_epoch)
+                                                    if state.committee_cache_is_initialized(
+                                                        relative_epoch,
+                                                    ) =>
+                                                {
+                                                    state
+                                                        .committee_cache(relative_epoch)
+                                                        .map(Cow::Borrowed)
+                                                }
+                                                _ => CommitteeCache::initialized(
+                                                    state,
+                                                    epoch,
+                                                    &chain.spec,
+                                                )
+                                                .map(Cow::Owned),
                                             }
-                                            _ => CommitteeCache::initialized(
-                                                state,
-                                                epoch,
-                                                &chain.spec,
-                                            )
-                                            .map(Cow::Owned),
-                                        }
-                                        .map_err(|e| {
-                                            match e {
-                                                BeaconStateError::EpochOutOfBounds => {
-                                                    let max_sprp =
-                                                        T::EthSpec::slots_per_historical_root()
-                                                            as u64;
-                                                    let first_subsequent_restore_point_slot =
-                                                        ((epoch.start_slot(
-                                                            T::EthSpec::slots_per_epoch(),
-                                                        ) / max_sprp)
-                                                            + 1)
-                                                            * max_sprp;
-                                                    if epoch < current_epoch {
-                                                        warp_utils::reject::custom_bad_request(
-                                                            format!(
+                                            .map_err(
+                                                |e| match e {
+                                                    BeaconStateError::EpochOutOfBounds => {
+                                                        let max_sprp =
+                                                            T::EthSpec::slots_per_historical_root()
+                                                                as u64;
+                                                        let first_subsequent_restore_point_slot =
+                                                            ((epoch.start_slot(
+                                                                T::EthSpec::slots_per_epoch(),
+                                                            ) / max_sprp)
+                                                                + 1)
+                                                                * max_sprp;
+                                                        if epoch < current_epoch {
+                                                            warp_utils::reject::custom_bad_request(
+                                                                format!(
                                                                 "epoch out of bounds, \
                                                                  try state at slot {}",
                                                                 first_subsequent_restore_point_slot,
                                                             ),
-                                                        )
-                                                    } else {
-                                                        warp_utils::reject::custom_bad_request(
-                                                            "epoch out of bounds, \
+                                                            )
+                                                        } else {
+                                                            warp_utils::reject::custom_bad_request(
+                                                                "epoch out of bounds, \
                                                              too far in future"
-                                                                .into(),
-                                                        )
+                                                                    .into(),
+                                                            )
+                                                        }
                                                     }
+                                                    _ => warp_utils::reject::beacon_chain_error(
+                                                        e.into(),
+                                                    ),
+                                                },
+                                            )?;
+
+                                        // Attempt to write to the beacon cache (only if the cache
+                                        // size is not the default value).
+                                        if chain.config.shuffling_cache_size
+                                            != beacon_chain::shuffling_cache::DEFAULT_CACHE_SIZE
+                                        {
+                                            if let Some(shuffling_id) = shuffling_id {
+                                                if let Some(mut cache_write) =
+                                                    chain.shuffling_cache.try_write_for(
+                                                        std::time::Duration::from_secs(1),
+                                                    )
+                                                {
+                                                    cache_write.insert_committee_cache(
+                                                        shuffling_id,
+                                                        &*possibly_built_cache,
+                                                    );
                                                 }
-                                                _ => {
-                                                    warp_utils::reject::beacon_chain_error(e.into())
-                                                }
-                                            }
-                                        })?;
-
-                                    // Attempt to write to the beacon cache (only if the cache
-                                    // size is not the default value).
-                                    if chain.config.shuffling_cache_size
-                                        != beacon_chain::shuffling_cache::DEFAULT_CACHE_SIZE
-                                    {
-                                        if let Some(shuffling_id) = shuffling_id {
-                                            if let Some(mut cache_write) = chain
-                                                .shuffling_cache
-                                                .try_write_for(std::time::Duration::from_secs(1))
-                                            {
-                                                cache_write.insert_committee_cache(
-                                                    shuffling_id,
-                                                    &*possibly_built_cache,
-                                                );
                                             }
                                         }
-                                    }
-                                    possibly_built_cache
-                                };
+                                        possibly_built_cache
+                                    };
 
-                                // Use either the supplied slot or all slots in the epoch.
-                                let slots =
-                                    query.slot.map(|slot| vec![slot]).unwrap_or_else(|| {
-                                        epoch.slot_iter(T::EthSpec::slots_per_epoch()).collect()
-                                    });
+                                    // Use either the supplied slot or all slots in the epoch.
+                                    let slots =
+                                        query.slot.map(|slot| vec![slot]).unwrap_or_else(|| {
+                                            epoch.slot_iter(T::EthSpec::slots_per_epoch()).collect()
+                                        });
 
-                                // Use either the supplied committee index or all available indices.
-                                let indices =
-                                    query.index.map(|index| vec![index]).unwrap_or_else(|| {
-                                        (0..committee_cache.committees_per_slot()).collect()
-                                    });
+                                    // Use either the supplied committee index or all available indices.
+                                    let indices =
+                                        query.index.map(|index| vec![index]).unwrap_or_else(|| {
+                                            (0..committee_cache.committees_per_slot()).collect()
+                                        });
 
-                                let mut response = Vec::with_capacity(slots.len() * indices.len());
+                                    let mut response =
+                                        Vec::with_capacity(slots.len() * indices.len());
 
-                                for slot in slots {
-                                    // It is not acceptable to query with a slot that is not within the
-                                    // specified epoch.
-                                    if slot.epoch(T::EthSpec::slots_per_epoch()) != epoch {
-                                        return Err(warp_utils::reject::custom_bad_request(
-                                            format!("{} is not in epoch {}", slot, epoch),
-                                        ));
-                                    }
+                                    for slot in slots {
+                                        // It is not acceptable to query with a slot that is not within the
+                                        // specified epoch.
+                                        if slot.epoch(T::EthSpec::slots_per_epoch()) != epoch {
+                                            return Err(warp_utils::reject::custom_bad_request(
+                                                format!("{} is not in epoch {}", slot, epoch),
+                                            ));
+                                        }
 
-                                    for &index in &indices {
-                                        let committee = committee_cache
-                                            .get_beacon_committee(slot, index)
-                                            .ok_or_else(|| {
-                                                warp_utils::reject::custom_bad_request(format!(
+                                        for &index in &indices {
+                                            let committee = committee_cache
+                                                .get_beacon_committee(slot, index)
+                                                .ok_or_else(|| {
+                                                    warp_utils::reject::custom_bad_request(format!(
                                                     "committee index {} does not exist in epoch {}",
                                                     index, epoch
                                                 ))
-                                            })?;
-
-                                        response.push(api_types::CommitteeData {
-                                            index,
-                                            slot,
-                                            validators: committee
-                                                .committee
-                                                .iter()
-                                                .map(|i| *i as u64)
-                                                .collect(),
-                                        });
+                                                })?;
+
+                                            response.push(api_types::CommitteeData {
+                                                index,
+                                                slot,
+                                                validators: committee
+                                                    .committee
+                                                    .iter()
+                                                    .map(|i| *i as u64)
+                                                    .collect(),
+                                            });
+                                        }
                                     }
-                                }
 
-                                Ok((response, execution_optimistic, finalized))
-                            },
-                        )?;
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
-                })
+                                    Ok((response, execution_optimistic, finalized))
+                                },
+                            )?;
+                        Ok(api_types::ExecutionOptimisticFinalizedResponse {
+                            data,
+                            execution_optimistic: Some(execution_optimistic),
+                            finalized: Some(finalized),
+                        })
+                    },
+                )
             },
         );
 
@@ -1037,58 +1099,65 @@ pub fn serve<T: BeaconChainTypes>(
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              query: api_types::SyncCommitteesQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let (sync_committee, execution_optimistic, finalized) = state_id
-                        .map_state_and_execution_optimistic_and_finalized(
-                            &chain,
-                            |state, execution_optimistic, finalized| {
-                                let current_epoch = state.current_epoch();
-                                let epoch = query.epoch.unwrap_or(current_epoch);
-                                Ok((
-                                    state
-                                        .get_built_sync_committee(epoch, &chain.spec)
-                                        .map(|committee| committee.clone())
-                                        .map_err(|e| match e {
-                                            BeaconStateError::SyncCommitteeNotKnown { .. } => {
-                                                warp_utils::reject::custom_bad_request(format!(
-                                                    "state at epoch {} has no \
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    STATE_READ_TIMEOUT,
+                    move |cancellation| {
+                        let (sync_committee, execution_optimistic, finalized) = state_id
+                            .map_state_and_execution_optimistic_and_finalized(
+                                &chain,
+                                |state, execution_optimistic, finalized| {
+                                    cancellation.check()?;
+                                    let current_epoch = state.current_epoch();
+                                    let epoch = query.epoch.unwrap_or(current_epoch);
+                                    Ok((
+                                        state
+                                            .get_built_sync_committee(epoch, &chain.spec)
+                                            .map(|committee| committee.clone())
+                                            .map_err(|e| match e {
+                                                BeaconStateError::SyncCommitteeNotKnown {
+                                                    ..
+                                                } => {
+                                                    warp_utils::reject::custom_bad_request(format!(
+                                                        "state at epoch {} has no \
                                                      sync committee for epoch {}",
-                                                    current_epoch, epoch
-                                                ))
-                                            }
-                                            BeaconStateError::IncorrectStateVariant => {
-                                                warp_utils::reject::custom_bad_request(format!(
+                                                        current_epoch, epoch
+                                                    ))
+                                                }
+                                                BeaconStateError::IncorrectStateVariant => {
+                                                    warp_utils::reject::custom_bad_request(format!(
                                                     "state at epoch {} is not activated for Altair",
                                                     current_epoch,
                                                 ))
-                                            }
-                                            e => warp_utils::reject::beacon_state_error(e),
-                                        })?,
-                                    execution_optimistic,
-                                    finalized,
-                                ))
-                            },
-                        )?;
+                                                }
+                                                e => warp_utils::reject::beacon_state_error(e),
+                                            })?,
+                                        execution_optimistic,
+                                        finalized,
+                                    ))
+                                },
+                            )?;
 
-                    let validators = chain
-                        .validator_indices(sync_committee.pubkeys.iter())
-                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                        let validators = chain
+                            .validator_indices(sync_committee.pubkeys.iter())
+                            .map_err(warp_utils::reject::beacon_chain_error)?;
 
-                    let validator_aggregates = validators
-                        .chunks_exact(T::EthSpec::sync_subcommittee_size())
-                        .map(|indices| api_types::SyncSubcommittee {
-                            indices: indices.to_vec(),
-                        })
-                        .collect();
+                        let validator_aggregates = validators
+                            .chunks_exact(T::EthSpec::sync_subcommittee_size())
+                            .map(|indices| api_types::SyncSubcommittee {
+                                indices: indices.to_vec(),
+                            })
+                            .collect();
 
-                    let response = api_types::SyncCommitteeByValidatorIndices {
-                        validators,
-                        validator_aggregates,
-                    };
+                        let response = api_types::SyncCommitteeByValidatorIndices {
+                            validators,
+                            validator_aggregates,
+                        };
 
-                    Ok(api_types::GenericResponse::from(response)
-                        .add_execution_optimistic_finalized(execution_optimistic, finalized))
-                })
+                        Ok(api_types::GenericResponse::from(response)
+                            .add_execution_optimistic_finalized(execution_optimistic, finalized))
+                    },
+                )
             },
         );
 
@@ -1288,12 +1357,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |block: Arc<SignedBeaconBlock<T::EthSpec>>,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     publish_blocks::publish_block(
                         None,
@@ -1303,6 +1374,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         BroadcastValidation::default(),
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1318,12 +1390,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |block_bytes: Bytes,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     let block =
                         SignedBeaconBlock::<T::EthSpec>::from_ssz_bytes(&block_bytes, &chain.spec)
@@ -1340,6 +1414,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         BroadcastValidation::default(),
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1356,13 +1431,15 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |validation_level: api_types::BroadcastValidationQuery,
                   block: Arc<SignedBeaconBlock<T::EthSpec>>,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     publish_blocks::publish_block(
                         None,
@@ -1372,6 +1449,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         validation_level.broadcast_validation,
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1388,13 +1466,15 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |validation_level: api_types::BroadcastValidationQuery,
                   block_bytes: Bytes,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     let block =
                         SignedBeaconBlock::<T::EthSpec>::from_ssz_bytes(&block_bytes, &chain.spec)
@@ -1411,6 +1491,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         validation_level.broadcast_validation,
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1431,12 +1512,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |block: SignedBlindedBeaconBlock<T::EthSpec>,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     publish_blocks::publish_blinded_block(
                         block,
@@ -1445,6 +1528,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         BroadcastValidation::default(),
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1461,12 +1545,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |block_bytes: Bytes,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     let block = SignedBlindedBeaconBlock::<T::EthSpec>::from_ssz_bytes(
                         &block_bytes,
@@ -1482,6 +1568,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         BroadcastValidation::default(),
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1498,13 +1585,15 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |validation_level: api_types::BroadcastValidationQuery,
                   block: SignedBlindedBeaconBlock<T::EthSpec>,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     publish_blocks::publish_blinded_block(
                         block,
@@ -1513,6 +1602,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         validation_level.broadcast_validation,
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1529,13 +1619,15 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(network_tx_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_publish_block"))
         .then(
             move |validation_level: api_types::BroadcastValidationQuery,
                   block_bytes: Bytes,
                   task_spawner: TaskSpawner<T::EthSpec>,
                   chain: Arc<BeaconChain<T>>,
                   network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-                  log: Logger| {
+                  log: Logger,
+                  span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
                     let block = SignedBlindedBeaconBlock::<T::EthSpec>::from_ssz_bytes(
                         &block_bytes,
@@ -1551,6 +1643,7 @@ pub fn serve<T: BeaconChainTypes>(
                         log,
                         validation_level.broadcast_validation,
                         duplicate_block_status_code,
+                        span,
                     )
                     .await
                 })
@@ -1596,16 +1689,9 @@ pub fn serve<T: BeaconChainTypes>(
                         .map_err(inconsistent_fork_rejection)?;
 
                     match accept_header {
-                        Some(api_types::Accept::Ssz) => Response::builder()
-                            .status(200)
-                            .header("Content-Type", "application/octet-stream")
-                            .body(block.as_ssz_bytes().into())
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            }),
+                        Some(api_types::Accept::Ssz) => {
+                            ssz_response(block.as_ssz_bytes(), SSZ_CONTENT_TYPE)
+                        }
                         _ => execution_optimistic_finalized_fork_versioned_response(
                             endpoint_version,
                             fork_name,
@@ -1675,83 +1761,162 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>,
              accept_header: Option<api_types::Accept>| {
                 task_spawner.blocking_response_task(Priority::P1, move || {
+                    if let Some(api_types::Accept::Ssz) = accept_header {
+                        // Serve the block's bytes straight from the database.
+                        let (ssz_bytes, slot, _execution_optimistic, _finalized) =
+                            block_id.blinded_block_ssz(&chain)?;
+                        let fork_name = chain.spec.fork_name_at_slot::<T::EthSpec>(slot);
+                        return ssz_response(ssz_bytes, SSZ_CONTENT_TYPE)
+                            .map(|resp| add_consensus_version_header(resp, fork_name));
+                    }
+
                     let (block, execution_optimistic, finalized) =
                         block_id.blinded_block(&chain)?;
                     let fork_name = block
                         .fork_name(&chain.spec)
                         .map_err(inconsistent_fork_rejection)?;
 
-                    match accept_header {
-                        Some(api_types::Accept::Ssz) => Response::builder()
-                            .status(200)
-                            .header("Content-Type", "application/octet-stream")
-                            .body(block.as_ssz_bytes().into())
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            }),
-                        _ => {
-                            // Post as a V2 endpoint so we return the fork version.
-                            execution_optimistic_finalized_fork_versioned_response(
-                                V2,
-                                fork_name,
-                                execution_optimistic,
-                                finalized,
-                                block,
-                            )
-                            .map(|res| warp::reply::json(&res).into_response())
-                        }
-                    }
+                    // Post as a V2 endpoint so we return the fork version.
+                    execution_optimistic_finalized_fork_versioned_response(
+                        V2,
+                        fork_name,
+                        execution_optimistic,
+                        finalized,
+                        block,
+                    )
+                    .map(|res| warp::reply::json(&res).into_response())
                     .map(|resp| add_consensus_version_header(resp, fork_name))
                 })
             },
         );
 
     /*
-     * beacon/pool
+     * beacon/light_client
      */
 
-    let beacon_pool_path = eth_v1
+    let beacon_light_client_path = eth_v1
         .and(warp::path("beacon"))
-        .and(warp::path("pool"))
+        .and(warp::path("light_client"))
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone());
 
-    // POST beacon/pool/attestations
-    let post_beacon_pool_attestations = beacon_pool_path
+    // GET beacon/light_client/bootstrap/{block_root}
+    let get_beacon_light_client_bootstrap = beacon_light_client_path
         .clone()
-        .and(warp::path("attestations"))
+        .and(warp::path("bootstrap"))
+        .and(
+            warp::path::param::<String>().and_then(|block_root: String| async move {
+                Hash256::from_str(block_root.strip_prefix("0x").unwrap_or(&block_root)).map_err(
+                    |_| warp_utils::reject::custom_bad_request("Invalid block root".to_string()),
+                )
+            }),
+        )
         .and(warp::path::end())
-        .and(warp::body::json())
-        .and(network_tx_filter.clone())
-        .and(log_filter.clone())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             attestations: Vec<Attestation<T::EthSpec>>,
-             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
-             log: Logger| {
-                task_spawner.blocking_json_task(Priority::P0, move || {
-                    let seen_timestamp = timestamp_now();
-                    let mut failures = Vec::new();
-                    let mut num_already_known = 0;
-
-                    for (index, attestation) in attestations.as_slice().iter().enumerate() {
-                        let attestation = match chain
-                            .verify_unaggregated_attestation_for_gossip(attestation, None)
-                        {
-                            Ok(attestation) => attestation,
-                            Err(AttnError::PriorAttestationKnown { .. }) => {
-                                num_already_known += 1;
+             block_root: Hash256,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    light_client::get_light_client_bootstrap(chain, block_root, accept_header)
+                })
+            },
+        );
 
-                                // Skip to the next attestation since an attestation for this
-                                // validator is already known in this epoch.
-                                //
-                                // There's little value for the network in validating a second
-                                // attestation for another validator since it is either:
-                                //
+    // GET beacon/light_client/updates?start_period,count
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             query: api_types::LightClientUpdatesQuery,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    light_client::get_light_client_updates(chain, query, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/finality_update
+    let get_beacon_light_client_finality_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("finality_update"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    light_client::get_light_client_finality_update(chain, accept_header)
+                })
+            },
+        );
+
+    // GET beacon/light_client/optimistic_update
+    let get_beacon_light_client_optimistic_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("optimistic_update"))
+        .and(warp::path::end())
+        .and(warp::header::optional::<api_types::Accept>("accept"))
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             accept_header: Option<api_types::Accept>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    light_client::get_light_client_optimistic_update(chain, accept_header)
+                })
+            },
+        );
+
+    /*
+     * beacon/pool
+     */
+
+    let beacon_pool_path = eth_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("pool"))
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone());
+
+    // POST beacon/pool/attestations
+    let post_beacon_pool_attestations = beacon_pool_path
+        .clone()
+        .and(warp::path("attestations"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(network_tx_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             attestations: Vec<Attestation<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let seen_timestamp = timestamp_now();
+                    let mut failures = Vec::new();
+                    let mut num_already_known = 0;
+
+                    for (index, attestation) in attestations.as_slice().iter().enumerate() {
+                        let attestation = match chain
+                            .verify_unaggregated_attestation_for_gossip(attestation, None)
+                        {
+                            Ok(attestation) => attestation,
+                            Err(AttnError::PriorAttestationKnown { .. }) => {
+                                num_already_known += 1;
+
+                                // Skip to the next attestation since an attestation for this
+                                // validator is already known in this epoch.
+                                //
+                                // There's little value for the network in validating a second
+                                // attestation for another validator since it is either:
+                                //
                                 // 1. A duplicate.
                                 // 2. Slashable.
                                 // 3. Invalid.
@@ -2262,15 +2427,26 @@ pub fn serve<T: BeaconChainTypes>(
             |task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
              block_id: BlockId| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let (rewards, execution_optimistic, finalized) =
-                        standard_block_rewards::compute_beacon_block_rewards(chain, block_id)?;
-                    Ok(rewards)
-                        .map(api_types::GenericResponse::from)
-                        .map(|resp| {
-                            resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                        })
-                })
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    REWARDS_TIMEOUT,
+                    move |cancellation| {
+                        let (rewards, execution_optimistic, finalized) =
+                            standard_block_rewards::compute_beacon_block_rewards(
+                                chain,
+                                block_id,
+                                cancellation,
+                            )?;
+                        Ok(rewards)
+                            .map(api_types::GenericResponse::from)
+                            .map(|resp| {
+                                resp.add_execution_optimistic_finalized(
+                                    execution_optimistic,
+                                    finalized,
+                                )
+                            })
+                    },
+                )
             },
         );
 
@@ -2350,42 +2526,47 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>,
              epoch: Epoch,
              validators: Vec<ValidatorId>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let attestation_rewards = chain
-                        .compute_attestation_rewards(epoch, validators)
-                        .map_err(|e| match e {
-                            BeaconChainError::MissingBeaconState(root) => {
-                                warp_utils::reject::custom_not_found(format!(
-                                    "missing state {root:?}",
-                                ))
-                            }
-                            BeaconChainError::NoStateForSlot(slot) => {
-                                warp_utils::reject::custom_not_found(format!(
-                                    "missing state at slot {slot}"
-                                ))
-                            }
-                            BeaconChainError::BeaconStateError(
-                                BeaconStateError::UnknownValidator(validator_index),
-                            ) => warp_utils::reject::custom_bad_request(format!(
-                                "validator is unknown: {validator_index}"
-                            )),
-                            BeaconChainError::ValidatorPubkeyUnknown(pubkey) => {
-                                warp_utils::reject::custom_bad_request(format!(
-                                    "validator pubkey is unknown: {pubkey:?}"
-                                ))
-                            }
-                            e => warp_utils::reject::custom_server_error(format!(
-                                "unexpected error: {:?}",
-                                e
-                            )),
-                        })?;
-                    let execution_optimistic =
-                        chain.is_optimistic_or_invalid_head().unwrap_or_default();
-
-                    Ok(attestation_rewards)
-                        .map(api_types::GenericResponse::from)
-                        .map(|resp| resp.add_execution_optimistic(execution_optimistic))
-                })
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    REWARDS_TIMEOUT,
+                    move |cancellation| {
+                        cancellation.check()?;
+                        let attestation_rewards = chain
+                            .compute_attestation_rewards(epoch, validators)
+                            .map_err(|e| match e {
+                                BeaconChainError::MissingBeaconState(root) => {
+                                    warp_utils::reject::custom_not_found(format!(
+                                        "missing state {root:?}",
+                                    ))
+                                }
+                                BeaconChainError::NoStateForSlot(slot) => {
+                                    warp_utils::reject::custom_not_found(format!(
+                                        "missing state at slot {slot}"
+                                    ))
+                                }
+                                BeaconChainError::BeaconStateError(
+                                    BeaconStateError::UnknownValidator(validator_index),
+                                ) => warp_utils::reject::custom_bad_request(format!(
+                                    "validator is unknown: {validator_index}"
+                                )),
+                                BeaconChainError::ValidatorPubkeyUnknown(pubkey) => {
+                                    warp_utils::reject::custom_bad_request(format!(
+                                        "validator pubkey is unknown: {pubkey:?}"
+                                    ))
+                                }
+                                e => warp_utils::reject::custom_server_error(format!(
+                                    "unexpected error: {:?}",
+                                    e
+                                )),
+                            })?;
+                        let execution_optimistic =
+                            chain.is_optimistic_or_invalid_head().unwrap_or_default();
+
+                        Ok(attestation_rewards)
+                            .map(api_types::GenericResponse::from)
+                            .map(|resp| resp.add_execution_optimistic(execution_optimistic))
+                    },
+                )
             },
         );
 
@@ -2403,18 +2584,29 @@ pub fn serve<T: BeaconChainTypes>(
              block_id: BlockId,
              validators: Vec<ValidatorId>,
              log: Logger| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let (rewards, execution_optimistic, finalized) =
-                        sync_committee_rewards::compute_sync_committee_rewards(
-                            chain, block_id, validators, log,
-                        )?;
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    REWARDS_TIMEOUT,
+                    move |cancellation| {
+                        let (rewards, execution_optimistic, finalized) =
+                            sync_committee_rewards::compute_sync_committee_rewards(
+                                chain,
+                                block_id,
+                                validators,
+                                log,
+                                cancellation,
+                            )?;
 
-                    Ok(rewards)
-                        .map(api_types::GenericResponse::from)
-                        .map(|resp| {
-                            resp.add_execution_optimistic_finalized(execution_optimistic, finalized)
-                        })
-                })
+                        Ok(rewards)
+                            .map(api_types::GenericResponse::from)
+                            .map(|resp| {
+                                resp.add_execution_optimistic_finalized(
+                                    execution_optimistic,
+                                    finalized,
+                                )
+                            })
+                    },
+                )
             },
         );
 
@@ -2511,19 +2703,8 @@ pub fn serve<T: BeaconChainTypes>(
                         let fork_name = state
                             .fork_name(&chain.spec)
                             .map_err(inconsistent_fork_rejection)?;
-                        Response::builder()
-                            .status(200)
-                            .header("Content-Type", "application/octet-stream")
-                            .body(state.as_ssz_bytes().into())
-                            .map(|resp: warp::reply::Response| {
-                                add_consensus_version_header(resp, fork_name)
-                            })
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            })
+                        beacon_state_ssz_response(state, SSZ_CONTENT_TYPE)
+                            .map(|resp| add_consensus_version_header(resp, fork_name))
                     }
                     _ => state_id.map_state_and_execution_optimistic_and_finalized(
                         &chain,
@@ -3006,6 +3187,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(log_filter.clone())
+        .and(trace_filter("bn_produce_block"))
         .then(
             |endpoint_version: EndpointVersion,
              slot: Slot,
@@ -3013,8 +3195,12 @@ pub fn serve<T: BeaconChainTypes>(
              accept_header: Option<api_types::Accept>,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             log: Logger| {
+             log: Logger,
+             mut span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
+                    if let Some(span) = &mut span {
+                        span.set_attribute("slot", slot);
+                    }
                     debug!(
                         log,
                         "Block production request from HTTP API";
@@ -3092,13 +3278,18 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::header::optional::<api_types::Accept>("accept"))
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(trace_filter("bn_produce_blinded_block"))
         .then(
             |slot: Slot,
              query: api_types::ValidatorBlocksQuery,
              accept_header: Option<api_types::Accept>,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
+             chain: Arc<BeaconChain<T>>,
+             mut span: Option<Span>| {
                 task_spawner.spawn_async_with_rejection(Priority::P0, async move {
+                    if let Some(span) = &mut span {
+                        span.set_attribute("slot", slot);
+                    }
                     let randao_reveal = query.randao_reveal.decompress().map_err(|e| {
                         warp_utils::reject::custom_bad_request(format!(
                             "randao reveal is not a valid BLS signature: {:?}",
@@ -3669,28 +3860,42 @@ pub fn serve<T: BeaconChainTypes>(
                         // send the response back to our original HTTP request
                         // task via a channel.
                         let builder_future = async move {
-                            let arc_builder = chain
+                            let builders = chain
                                 .execution_layer
                                 .as_ref()
                                 .ok_or(BeaconChainError::ExecutionLayerMissing)
                                 .map_err(warp_utils::reject::beacon_chain_error)?
-                                .builder();
-                            let builder = arc_builder
-                                .as_ref()
-                                .ok_or(BeaconChainError::BuilderMissing)
-                                .map_err(warp_utils::reject::beacon_chain_error)?;
+                                .builders();
+                            if builders.is_empty() {
+                                return Err(warp_utils::reject::beacon_chain_error(
+                                    BeaconChainError::BuilderMissing,
+                                ));
+                            }
 
-                            builder
-                                .post_builder_validators(&filtered_registration_data)
-                                .await
-                                .map(|resp| warp::reply::json(&resp).into_response())
-                                .map_err(|e| {
+                            // Register with every builder. The request succeeds if any builder
+                            // accepts the registrations, otherwise the first error is returned.
+                            let mut results =
+                                futures::future::join_all(builders.iter().map(|builder| {
+                                    builder.post_builder_validators(&filtered_registration_data)
+                                }))
+                                .await;
+                            for (builder, result) in builders.iter().zip(&results) {
+                                if let Err(e) = result {
                                     warn!(
                                         log,
                                         "Relay error when registering validator(s)";
                                         "num_registrations" => filtered_registration_data.len(),
+                                        "relay" => %builder.server(),
                                         "error" => ?e
                                     );
+                                }
+                            }
+                            let index = results.iter().position(Result::is_ok).unwrap_or(0);
+
+                            results
+                                .swap_remove(index)
+                                .map(|resp| warp::reply::json(&resp).into_response())
+                                .map_err(|e| {
                                     // Forward the HTTP status code if we are able to, otherwise fall back
                                     // to a server error.
                                     if let eth2::Error::ServerMessage(message) = e {
@@ -3790,7 +3995,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .then(
             |epoch: Epoch,
-             indices: Vec<u64>,
+             indices: api_types::ValidatorIndexData,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_json_task(Priority::P0, move || {
@@ -3809,6 +4014,7 @@ pub fn serve<T: BeaconChainTypes>(
                     }
 
                     let liveness: Vec<api_types::StandardLivenessResponseData> = indices
+                        .0
                         .iter()
                         .cloned()
                         .map(|index| {
@@ -3962,6 +4168,91 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/validator_monitor/metric_aggregation
+    let get_lighthouse_validator_monitor_metric_aggregation = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("metric_aggregation"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        chain.validator_monitor.read().metric_aggregation(),
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/validator_monitor/metric_aggregation
+    let post_lighthouse_validator_monitor_metric_aggregation = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("metric_aggregation"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |metric_aggregation: MetricAggregation,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    if let MetricAggregation::IndexRange(0) = metric_aggregation {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "index range size must be non-zero".to_string(),
+                        ));
+                    }
+                    let mut validator_monitor = chain.validator_monitor.write();
+                    info!(
+                        log,
+                        "Updated validator monitor metric aggregation";
+                        "previous" => %validator_monitor.metric_aggregation(),
+                        "new" => %metric_aggregation,
+                    );
+                    validator_monitor.set_metric_aggregation(metric_aggregation);
+                    Ok(())
+                })
+            },
+        );
+
+    // POST lighthouse/validator_monitor/validators
+    let post_lighthouse_validator_monitor_validators = warp::path("lighthouse")
+        .and(warp::path("validator_monitor"))
+        .and(warp::path("validators"))
+        .and(warp::path::end())
+        .and(validator_monitor_auth_filter)
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |validators: Vec<eth2::lighthouse::ValidatorMonitorRegistration>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    if let Some(cohort) = validators
+                        .iter()
+                        .filter_map(|validator| validator.cohort.as_ref())
+                        .find(|cohort| !is_valid_cohort(cohort))
+                    {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "invalid cohort {:?}, cohorts must be at most {} alphanumeric, \
+                             '-' or '_' characters",
+                            cohort, MAX_COHORT_LEN
+                        )));
+                    }
+
+                    let mut validator_monitor = chain.validator_monitor.write();
+                    for validator in validators {
+                        validator_monitor.register_validator(validator.pubkey, validator.cohort);
+                    }
+                    Ok(())
+                })
+            },
+        );
+
     // GET lighthouse/syncing
     let get_lighthouse_syncing = warp::path("lighthouse")
         .and(warp::path("syncing"))
@@ -3996,6 +4287,24 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/nat/status
+    let get_lighthouse_nat_status = warp::path("lighthouse")
+        .and(warp::path("nat"))
+        .and(warp::path("status"))
+        .and(task_spawner_filter.clone())
+        .and(warp::path::end())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.nat_status.read().clone(),
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/peers
     let get_lighthouse_peers = warp::path("lighthouse")
         .and(warp::path("peers"))
@@ -4019,13 +4328,49 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/rpc/requests
+    let get_lighthouse_rpc_requests = warp::path("lighthouse")
+        .and(warp::path("rpc"))
+        .and(warp::path("requests"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.rpc_requests.read().recent(),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/peers/handshake_failures
+    let get_lighthouse_peers_handshake_failures = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("handshake_failures"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.handshake_failures.read().recent(),
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/peers/connected
     let get_lighthouse_peers_connected = warp::path("lighthouse")
         .and(warp::path("peers"))
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4043,15 +4388,138 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
-    // GET lighthouse/proto_array
-    let get_lighthouse_proto_array = warp::path("lighthouse")
-        .and(warp::path("proto_array"))
+    // GET lighthouse/peers/scores?format
+    let get_lighthouse_peer_scores = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("scores"))
         .and(warp::path::end())
+        .and(warp::query::<eth2::lighthouse::PeerScoresQuery>())
         .and(task_spawner_filter.clone())
-        .and(chain_filter.clone())
+        .and(network_globals.clone())
         .then(
-            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_response_task(Priority::P1, move || {
+            |query: eth2::lighthouse::PeerScoresQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let scores = peer_scores::peer_scores(&network_globals);
+                    match query.format {
+                        eth2::lighthouse::PeerScoresFormat::Json => {
+                            Ok(warp::reply::json(&api_types::GenericResponse::from(scores))
+                                .into_response())
+                        }
+                        eth2::lighthouse::PeerScoresFormat::Csv => {
+                            peer_scores::csv_response(peer_scores::peer_scores_csv(&scores))
+                        }
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/peers/{peer_id}/stats
+    let get_lighthouse_peer_stats = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("stats"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |requested_peer_id: String,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let peer_id = PeerId::from_str(&requested_peer_id).map_err(|e| {
+                        warp_utils::reject::custom_bad_request(format!("invalid peer id: {}", e))
+                    })?;
+
+                    let peers = network_globals.peers.read();
+                    let peer_info = peers.peer_info(&peer_id).ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(format!("unknown peer: {}", peer_id))
+                    })?;
+                    let score = peer_info.score().score();
+
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::PeerStats {
+                            peer_id: peer_id.to_string(),
+                            score: score.is_finite().then_some(score),
+                            gossipsub_score: peer_info.score().gossipsub_score(),
+                            rpc: peer_info.rpc_stats().protocols().clone(),
+                        },
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/peers/{peer_id}/{action}
+    let post_lighthouse_peer_action = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::addr::remote())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .and(network_tx_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |requested_peer_id: String,
+             action: String,
+             remote_addr: Option<SocketAddr>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let peer_id = PeerId::from_str(&requested_peer_id).map_err(|e| {
+                        warp_utils::reject::custom_bad_request(format!("invalid peer id: {}", e))
+                    })?;
+                    let action = OperatorAction::from_str(&action).map_err(|_| {
+                        warp_utils::reject::custom_bad_request(format!(
+                            "invalid peer action: {}",
+                            action
+                        ))
+                    })?;
+                    // Unknown peers may be protected, so that they are trusted once they
+                    // connect.
+                    if action != OperatorAction::Protect
+                        && network_globals.peers.read().peer_info(&peer_id).is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "unknown peer: {}",
+                            peer_id
+                        )));
+                    }
+
+                    let requested_by =
+                        remote_addr.map_or_else(|| "unknown".to_string(), |addr| addr.to_string());
+                    info!(
+                        log,
+                        "Peer action requested via HTTP API";
+                        "peer_id" => %peer_id,
+                        "action" => %action,
+                        "requested_by" => &requested_by,
+                    );
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::OperatorAction {
+                            peer_id,
+                            action,
+                            requested_by,
+                        },
+                    )
+                })
+            },
+        );
+
+    // GET lighthouse/proto_array
+    let get_lighthouse_proto_array = warp::path("lighthouse")
+        .and(warp::path("proto_array"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     Ok::<_, warp::Rejection>(warp::reply::json(
                         &api_types::GenericResponseRef::from(
                             chain
@@ -4065,6 +4533,20 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/block_provenance
+    let get_lighthouse_block_provenance = warp::path("lighthouse")
+        .and(warp::path("block_provenance"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(chain.block_provenance()))
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -4196,16 +4678,29 @@ pub fn serve<T: BeaconChainTypes>(
                 task_spawner.blocking_response_task(Priority::P1, move || {
                     // This debug endpoint provides no indication of optimistic status.
                     let (state, _execution_optimistic, _finalized) = state_id.state(&chain)?;
-                    Response::builder()
-                        .status(200)
-                        .header("Content-Type", "application/ssz")
-                        .body(state.as_ssz_bytes())
-                        .map_err(|e| {
-                            warp_utils::reject::custom_server_error(format!(
-                                "failed to create response: {}",
-                                e
-                            ))
-                        })
+                    beacon_state_ssz_response(state, "application/ssz")
+                })
+            },
+        );
+
+    // GET lighthouse/beacon/states/{state_id}/validators?offset,limit,status,min_balance,max_balance
+    let get_lighthouse_beacon_states_validators = warp::path("lighthouse")
+        .and(warp::path("beacon"))
+        .and(warp::path("states"))
+        .and(warp::path::param::<StateId>())
+        .and(warp::path("validators"))
+        .and(warp::path::end())
+        .and(multi_key_query::<eth2::lighthouse::ValidatorsPageQuery>())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |state_id: StateId,
+             query_res: Result<eth2::lighthouse::ValidatorsPageQuery, warp::Rejection>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let query = query_res?;
+                    validators::get_validators_page(state_id, query, chain)
                 })
             },
         );
@@ -4262,6 +4757,72 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/database/reconstruction_status
+    let get_lighthouse_database_reconstruction_status = database_path
+        .and(warp::path("reconstruction_status"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(chain.store.reconstruction_status())
+                })
+            },
+        );
+
+    // GET lighthouse/checkpoint_sync
+    let get_lighthouse_checkpoint_sync = warp::path("lighthouse")
+        .and(warp::path("checkpoint_sync"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    chain.checkpoint_sync_report.clone().ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "node was not checkpoint synced from a URL".to_string(),
+                        )
+                    })
+                })
+            },
+        );
+
+    // GET lighthouse/database/backup
+    let get_lighthouse_database_backup = database_path
+        .and(warp::path("backup"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner
+                    .blocking_json_task(Priority::P1, move || database::backup_status(chain))
+            },
+        );
+
+    // POST lighthouse/database/backup
+    let database_backup_dir = ctx.config.database_backup_dir.clone();
+    let post_lighthouse_database_backup = database_path
+        .and(warp::path("backup"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            move |request: eth2::lighthouse::DatabaseBackupRequest,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>,
+                  log: Logger| {
+                let database_backup_dir = database_backup_dir.clone();
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    database::start_backup(chain, database_backup_dir, request, log)
+                })
+            },
+        );
+
     // POST lighthouse/database/historical_blocks
     let post_lighthouse_database_historical_blocks = database_path
         .and(warp::path("historical_blocks"))
@@ -4287,6 +4848,99 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/historical_blocks (SSZ)
+    let post_lighthouse_database_historical_blocks_ssz = database_path
+        .and(warp::path("historical_blocks"))
+        .and(warp::path::end())
+        .and(warp::body::bytes())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |body: Bytes,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    database::historical_blocks_ssz(chain, &body, log)
+                })
+            },
+        );
+
+    // GET lighthouse/beacon_processor/status
+    let get_lighthouse_beacon_processor_status = warp::path("lighthouse")
+        .and(warp::path("beacon_processor"))
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(beacon_processor_status_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, status: Arc<BeaconProcessorStatus>| {
+                // Use the highest priority so that the queues can be inspected under load.
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    beacon_processor_status::status(status).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/beacon_processor/priority_weights
+    let post_lighthouse_beacon_processor_priority_weights = warp::path("lighthouse")
+        .and(warp::path("beacon_processor"))
+        .and(warp::path("priority_weights"))
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(beacon_processor_status_filter)
+        .and(log_filter.clone())
+        .then(
+            |weights: eth2::lighthouse::BeaconProcessorPriorityWeights,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             status: Arc<BeaconProcessorStatus>,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P0, move || {
+                    let weights = beacon_processor_status::set_priority_weights(status, weights)?;
+                    info!(
+                        log,
+                        "Beacon processor priority weights updated";
+                        "attestations" => weights.attestations,
+                        "sync_committee" => weights.sync_committee,
+                        "rpc" => weights.rpc,
+                        "api" => weights.api,
+                        "backfill" => weights.backfill,
+                    );
+                    Ok(api_types::GenericResponse::from(weights))
+                })
+            },
+        );
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(runtime_log_filter_filter)
+        .and(log_filter.clone())
+        .then(
+            |request: eth2::lighthouse::LoggingRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             runtime_log_filter: RuntimeLogFilter,
+             log: Logger| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let status = warp_utils::log_filter::update(&runtime_log_filter, request)?;
+                    info!(
+                        log,
+                        "Log filters updated";
+                        "filter" => &status.filter,
+                        "debug_capture" => ?status.debug_capture,
+                    );
+                    Ok(api_types::GenericResponse::from(status))
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4297,9 +4951,13 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone())
         .and(log_filter.clone())
         .then(|query, task_spawner: TaskSpawner<T::EthSpec>, chain, log| {
-            task_spawner.blocking_json_task(Priority::P1, move || {
-                block_rewards::get_block_rewards(query, chain, log)
-            })
+            task_spawner.blocking_json_task_with_timeout(
+                Priority::P1,
+                REWARDS_TIMEOUT,
+                move |cancellation| {
+                    block_rewards::get_block_rewards(query, chain, log, cancellation)
+                },
+            )
         });
 
     // POST lighthouse/analysis/block_rewards
@@ -4313,13 +4971,56 @@ pub fn serve<T: BeaconChainTypes>(
         .and(log_filter.clone())
         .then(
             |blocks, task_spawner: TaskSpawner<T::EthSpec>, chain, log| {
+                task_spawner.blocking_json_task_with_timeout(
+                    Priority::P1,
+                    REWARDS_TIMEOUT,
+                    move |cancellation| {
+                        block_rewards::compute_block_rewards(blocks, chain, log, cancellation)
+                    },
+                )
+            },
+        );
+
+    // POST lighthouse/analysis/fork_choice
+    let post_lighthouse_analysis_fork_choice = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("fork_choice"))
+        .and(warp::body::json())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
-                    block_rewards::compute_block_rewards(blocks, chain, log)
+                    fork_choice_simulation::simulate_fork_choice(request, chain)
                 })
             },
         );
 
-    // GET lighthouse/analysis/attestation_performance/{index}
+    // POST lighthouse/analysis/rewards
+    let post_lighthouse_analysis_rewards = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("rewards"))
+        .and(warp::query::<eth2::lighthouse::EpochRewardsQuery>())
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(
+            |query, validators, task_spawner: TaskSpawner<T::EthSpec>, chain, log| async move {
+                task_spawner::convert_rejection(epoch_rewards::stream_epoch_rewards(
+                    query,
+                    validators,
+                    task_spawner,
+                    chain,
+                    log,
+                ))
+                .await
+            },
+        );
+
+    // GET lighthouse/analysis/attestation_performance/{index_or_pubkey}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
         .and(warp::path("attestation_performance"))
@@ -4328,18 +5029,46 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(log_filter.clone())
         .then(
-            |target, query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    attestation_performance::get_attestation_performance(target, query, chain)
-                })
+            |target,
+             query: eth2::lighthouse::AttestationPerformanceQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             log| async move {
+                if query.stream {
+                    task_spawner::convert_rejection(
+                        attestation_performance::stream_attestation_performance(
+                            target,
+                            query,
+                            task_spawner,
+                            chain,
+                            log,
+                        ),
+                    )
+                    .await
+                } else {
+                    task_spawner
+                        .blocking_json_task(Priority::P1, move || {
+                            attestation_performance::get_attestation_performance(
+                                target, query, chain,
+                            )
+                        })
+                        .await
+                }
             },
         );
 
-    // GET lighthouse/analysis/block_packing_efficiency
+    // GET lighthouse/analysis/block_packing
+    //
+    // Also served at the original `block_packing_efficiency` path.
     let get_lighthouse_block_packing_efficiency = warp::path("lighthouse")
         .and(warp::path("analysis"))
-        .and(warp::path("block_packing_efficiency"))
+        .and(
+            warp::path("block_packing")
+                .or(warp::path("block_packing_efficiency"))
+                .unify(),
+        )
         .and(warp::query::<eth2::lighthouse::BlockPackingEfficiencyQuery>())
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
@@ -4371,6 +5100,83 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/proposal_decisions
+    let get_lighthouse_proposal_decisions = warp::path("lighthouse")
+        .and(warp::path("proposal_decisions"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    Ok(api_types::GenericResponse::from(
+                        execution_layer.recent_proposal_decisions(),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/builder_relays
+    let get_lighthouse_builder_relays = warp::path("lighthouse")
+        .and(warp::path("builder_relays"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    Ok(api_types::GenericResponse::from(
+                        execution_layer.relay_stats(),
+                    ))
+                })
+            },
+        );
+
+    // POST lighthouse/slasher/check_attestation
+    let post_lighthouse_slasher_check_attestation = warp::path("lighthouse")
+        .and(warp::path("slasher"))
+        .and(warp::path("check_attestation"))
+        .and(warp::path::end())
+        .and(admin_auth_filter.clone())
+        .and(warp::body::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |attestation: IndexedAttestation<T::EthSpec>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let slasher = chain.slasher.as_ref().ok_or_else(|| {
+                        warp_utils::reject::custom_not_found("slasher is not enabled".to_string())
+                    })?;
+                    let current_epoch = chain
+                        .epoch()
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    let slashings = slasher
+                        .check_attestation(&attestation, current_epoch)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "slasher error: {:?}",
+                                e
+                            ))
+                        })?;
+                    Ok(api_types::GenericResponse::from(
+                        slashings.into_iter().collect::<Vec<_>>(),
+                    ))
+                })
+            },
+        );
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -4403,6 +5209,12 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::ChainReorg => {
                                     event_handler.subscribe_reorgs()
                                 }
+                                api_types::EventTopic::ChainReorgDetail => {
+                                    event_handler.subscribe_reorg_details()
+                                }
+                                api_types::EventTopic::DependentRootChange => {
+                                    event_handler.subscribe_dependent_root_change()
+                                }
                                 api_types::EventTopic::ContributionAndProof => {
                                     event_handler.subscribe_contributions()
                                 }
@@ -4415,6 +5227,15 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::BlockReward => {
                                     event_handler.subscribe_block_reward()
                                 }
+                                api_types::EventTopic::BuilderBid => {
+                                    event_handler.subscribe_builder_bid()
+                                }
+                                api_types::EventTopic::PayloadRevealed => {
+                                    event_handler.subscribe_payload_revealed()
+                                }
+                                api_types::EventTopic::PayloadDelivered => {
+                                    event_handler.subscribe_payload_delivered()
+                                }
                             };
 
                             receivers.push(
@@ -4519,6 +5340,10 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_beacon_block)
                 .uor(get_beacon_block_attestations)
                 .uor(get_beacon_blinded_block)
+                .uor(get_beacon_light_client_bootstrap)
+                .uor(get_beacon_light_client_updates)
+                .uor(get_beacon_light_client_finality_update)
+                .uor(get_beacon_light_client_optimistic_update)
                 .uor(get_beacon_block_root)
                 .uor(get_beacon_pool_attestations)
                 .uor(get_beacon_pool_attester_slashings)
@@ -4549,23 +5374,37 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_health)
                 .uor(get_lighthouse_ui_health)
                 .uor(get_lighthouse_ui_validator_count)
+                .uor(get_lighthouse_validator_monitor_metric_aggregation)
                 .uor(get_lighthouse_syncing)
                 .uor(get_lighthouse_nat)
+                .uor(get_lighthouse_nat_status)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peers_handshake_failures)
+                .uor(get_lighthouse_rpc_requests)
+                .uor(get_lighthouse_peer_scores)
+                .uor(get_lighthouse_peer_stats)
                 .uor(get_lighthouse_proto_array)
+                .uor(get_lighthouse_block_provenance)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
                 .uor(get_lighthouse_eth1_syncing)
                 .uor(get_lighthouse_eth1_block_cache)
                 .uor(get_lighthouse_eth1_deposit_cache)
                 .uor(get_lighthouse_beacon_states_ssz)
+                .uor(get_lighthouse_beacon_states_validators)
                 .uor(get_lighthouse_staking)
                 .uor(get_lighthouse_database_info)
+                .uor(get_lighthouse_database_reconstruction_status)
+                .uor(get_lighthouse_checkpoint_sync)
+                .uor(get_lighthouse_database_backup)
+                .uor(get_lighthouse_beacon_processor_status)
                 .uor(get_lighthouse_block_rewards)
                 .uor(get_lighthouse_attestation_performance)
                 .uor(get_lighthouse_block_packing_efficiency)
                 .uor(get_lighthouse_merge_readiness)
+                .uor(get_lighthouse_proposal_decisions)
+                .uor(get_lighthouse_builder_relays)
                 .uor(get_events)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
@@ -4580,7 +5419,8 @@ pub fn serve<T: BeaconChainTypes>(
                         post_beacon_blocks_ssz
                             .uor(post_beacon_blocks_v2_ssz)
                             .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
+                            .uor(post_beacon_blinded_blocks_v2_ssz)
+                            .uor(post_lighthouse_database_historical_blocks_ssz),
                     )
                     .uor(post_beacon_blocks)
                     .uor(post_beacon_blinded_blocks)
@@ -4604,17 +5444,29 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_register_validator)
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
+                    .uor(post_lighthouse_peer_action)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_database_backup)
                     .uor(post_lighthouse_database_historical_blocks)
+                    .uor(post_lighthouse_beacon_processor_priority_weights)
+                    .uor(post_lighthouse_logging)
                     .uor(post_lighthouse_block_rewards)
+                    .uor(post_lighthouse_analysis_fork_choice)
+                    .uor(post_lighthouse_analysis_rewards)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
+                    .uor(post_lighthouse_validator_monitor_metric_aggregation)
+                    .uor(post_lighthouse_validator_monitor_validators)
+                    .uor(post_lighthouse_slasher_check_attestation)
                     .recover(warp_utils::reject::handle_rejection),
             ),
         )
         .recover(warp_utils::reject::handle_rejection)
         .with(slog_logging(log.clone()))
-        .with(prometheus_metrics())
+        .with(warp_utils::metrics::response_times(
+            "http_api",
+            &[API_PREFIX, "lighthouse"],
+        ))
         // Add a `Server` header.
         .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
         .with(cors_builder.build())
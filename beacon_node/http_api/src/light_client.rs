@@ -0,0 +1,160 @@
+use crate::ssz_response::{ssz_response, SSZ_CONTENT_TYPE};
+use crate::version::add_consensus_version_header;
+use beacon_chain::light_client_server_cache::MAX_REQUEST_LIGHT_CLIENT_UPDATES;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::{self as api_types, LightClientUpdatesQuery};
+use ssz::Encode;
+use std::sync::Arc;
+use types::{ChainSpec, ForkName, ForkVersionedResponse, Hash256, Slot};
+use warp::reply::Response;
+use warp::Reply;
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_not_found};
+
+/// Reject the request unless the node was started with `--light-client-server`.
+fn check_light_client_server<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<(), warp::Rejection> {
+    if chain.config.enable_light_client_server {
+        Ok(())
+    } else {
+        Err(custom_not_found(
+            "The light client server is disabled. Use --light-client-server on the CLI."
+                .to_string(),
+        ))
+    }
+}
+
+/// Respond with `data` as SSZ or JSON, depending on the `accept_header`.
+fn fork_versioned_light_client_response<T: Encode + serde::Serialize>(
+    data: T,
+    fork_name: ForkName,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response, warp::Rejection> {
+    match accept_header {
+        Some(api_types::Accept::Ssz) => ssz_response(data.as_ssz_bytes(), SSZ_CONTENT_TYPE),
+        _ => Ok(warp::reply::json(&ForkVersionedResponse {
+            version: Some(fork_name),
+            data,
+        })
+        .into_response()),
+    }
+    .map(|resp| add_consensus_version_header(resp, fork_name))
+}
+
+/// `GET beacon/light_client/bootstrap/{block_root}`
+pub fn get_light_client_bootstrap<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    block_root: Hash256,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response, warp::Rejection> {
+    check_light_client_server(&chain)?;
+
+    let (bootstrap, fork_name) = chain
+        .get_light_client_bootstrap(&block_root)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| {
+            custom_not_found(format!(
+                "no light client bootstrap for block {:?}",
+                block_root
+            ))
+        })?;
+
+    fork_versioned_light_client_response(bootstrap, fork_name, accept_header)
+}
+
+/// `GET beacon/light_client/updates?start_period,count`
+pub fn get_light_client_updates<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    query: LightClientUpdatesQuery,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response, warp::Rejection> {
+    check_light_client_server(&chain)?;
+
+    if query.count > MAX_REQUEST_LIGHT_CLIENT_UPDATES {
+        return Err(custom_bad_request(format!(
+            "count must not exceed {}",
+            MAX_REQUEST_LIGHT_CLIENT_UPDATES
+        )));
+    }
+
+    let updates = chain
+        .light_client_server_cache
+        .get_light_client_updates(query.start_period, query.count);
+
+    match accept_header {
+        Some(api_types::Accept::Ssz) => {
+            // Each update is prefixed by its length and the fork digest of its attested slot, as
+            // in the `LightClientUpdatesByRange` RPC response.
+            let mut bytes = vec![];
+            for update in &updates {
+                let fork_digest = fork_digest(&chain, update.attested_header.slot);
+                let update_bytes = update.as_ssz_bytes();
+                let chunk_len = (fork_digest.len() + update_bytes.len()) as u64;
+                bytes.extend_from_slice(&chunk_len.to_le_bytes());
+                bytes.extend_from_slice(&fork_digest);
+                bytes.extend_from_slice(&update_bytes);
+            }
+            ssz_response(bytes, SSZ_CONTENT_TYPE)
+        }
+        _ => {
+            let responses = updates
+                .into_iter()
+                .map(|update| ForkVersionedResponse {
+                    version: Some(
+                        chain
+                            .spec
+                            .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot),
+                    ),
+                    data: update,
+                })
+                .collect::<Vec<_>>();
+            Ok(warp::reply::json(&responses).into_response())
+        }
+    }
+}
+
+/// `GET beacon/light_client/finality_update`
+pub fn get_light_client_finality_update<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response, warp::Rejection> {
+    check_light_client_server(&chain)?;
+
+    let update = chain
+        .light_client_server_cache
+        .get_latest_finality_update()
+        .ok_or_else(|| custom_not_found("no light client finality update available".to_string()))?;
+    let fork_name = chain
+        .spec
+        .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot);
+
+    fork_versioned_light_client_response(update, fork_name, accept_header)
+}
+
+/// `GET beacon/light_client/optimistic_update`
+pub fn get_light_client_optimistic_update<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    accept_header: Option<api_types::Accept>,
+) -> Result<Response, warp::Rejection> {
+    check_light_client_server(&chain)?;
+
+    let update = chain
+        .light_client_server_cache
+        .get_latest_optimistic_update()
+        .ok_or_else(|| {
+            custom_not_found("no light client optimistic update available".to_string())
+        })?;
+    let fork_name = chain
+        .spec
+        .fork_name_at_slot::<T::EthSpec>(update.attested_header.slot);
+
+    fork_versioned_light_client_response(update, fork_name, accept_header)
+}
+
+fn fork_digest<T: BeaconChainTypes>(chain: &BeaconChain<T>, slot: Slot) -> [u8; 4] {
+    let fork_name = chain.spec.fork_name_at_slot::<T::EthSpec>(slot);
+    ChainSpec::compute_fork_digest(
+        chain.spec.fork_version_for_name(fork_name),
+        chain.genesis_validators_root,
+    )
+}
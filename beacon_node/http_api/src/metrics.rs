@@ -1,22 +1,6 @@
 pub use lighthouse_metrics::*;
 
 lazy_static::lazy_static! {
-    pub static ref HTTP_API_PATHS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
-        "http_api_paths_total",
-        "Count of HTTP requests received",
-        &["path"]
-    );
-    pub static ref HTTP_API_STATUS_CODES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
-        "http_api_status_codes_total",
-        "Count of HTTP status codes returned",
-        &["status"]
-    );
-    pub static ref HTTP_API_PATHS_TIMES: Result<HistogramVec> = try_create_histogram_vec(
-        "http_api_paths_times",
-        "Duration to process HTTP requests per path",
-        &["path"]
-    );
-
     pub static ref HTTP_API_BEACON_PROPOSER_CACHE_TIMES: Result<Histogram> = try_create_histogram(
         "http_api_beacon_proposer_cache_build_times",
         "Duration to process HTTP requests per path",
@@ -29,6 +13,18 @@ lazy_static::lazy_static! {
         "http_api_beacon_proposer_cache_misses_total",
         "Count of times the proposer cache has been missed",
     );
+    pub static ref HTTP_API_PROPOSER_LOOKAHEAD_CACHE_HITS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_proposer_lookahead_cache_hits_total",
+        "Count of next-epoch proposer duties requests served from the proposer cache",
+    );
+    pub static ref HTTP_API_PROPOSER_LOOKAHEAD_CACHE_MISSES_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_proposer_lookahead_cache_misses_total",
+        "Count of next-epoch proposer duties requests which missed the proposer cache",
+    );
+    pub static ref HTTP_API_PROPOSER_LOOKAHEAD_CACHE_INVALIDATIONS_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_proposer_lookahead_cache_invalidations_total",
+        "Count of next-epoch proposer duties requests served for a different dependent root than the previous request",
+    );
     pub static ref HTTP_API_BLOCK_BROADCAST_DELAY_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "http_api_block_broadcast_delay_times",
         "Time between start of the slot and when the block was broadcast",
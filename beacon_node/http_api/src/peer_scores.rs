@@ -0,0 +1,101 @@
+use eth2::lighthouse::{PeerPenalty, PeerScore};
+use lighthouse_network::NetworkGlobals;
+use std::time::Instant;
+use types::EthSpec;
+use warp::http::Response;
+use warp::hyper::Body;
+
+const CSV_HEADER: &str = "peer_id,client,connection_status,score,lighthouse_score,\
+gossipsub_score,ignore_negative_gossipsub_score,score_state,trusted,banned,decay_delay_secs,\
+penalties,last_penalty";
+
+/// Returns the score components of every known peer.
+pub fn peer_scores<T: EthSpec>(network_globals: &NetworkGlobals<T>) -> Vec<PeerScore> {
+    let now = Instant::now();
+    network_globals
+        .peers
+        .read()
+        .peers()
+        .map(|(peer_id, peer_info)| {
+            let score = peer_info.score();
+            let total = score.score();
+            PeerScore {
+                peer_id: peer_id.to_string(),
+                client: peer_info.client().kind.as_ref().to_string(),
+                connection_status: peer_info.connection_status().as_ref().to_string(),
+                score: total.is_finite().then_some(total),
+                lighthouse_score: score.lighthouse_score(),
+                gossipsub_score: score.gossipsub_score(),
+                ignore_negative_gossipsub_score: score.ignores_negative_gossipsub_score(),
+                score_state: score.state_name(),
+                trusted: peer_info.is_trusted(),
+                banned: peer_info.is_banned(),
+                decay_delay_secs: score.decay_delay().map(|delay| delay.as_secs()),
+                penalties: peer_info
+                    .recent_actions()
+                    .iter()
+                    .map(|reported| PeerPenalty {
+                        action: reported.action.as_ref().to_string(),
+                        source: reported.source.to_string(),
+                        msg: reported.msg.to_string(),
+                        seconds_ago: now.saturating_duration_since(reported.time).as_secs(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Renders `scores` as CSV, with one row per peer.
+///
+/// Only the number of penalties and the message of the most recent penalty are included.
+pub fn peer_scores_csv(scores: &[PeerScore]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for peer in scores {
+        let row = [
+            peer.peer_id.clone(),
+            csv_field(&peer.client),
+            csv_field(&peer.connection_status),
+            optional_field(peer.score),
+            optional_field(peer.lighthouse_score),
+            optional_field(peer.gossipsub_score),
+            peer.ignore_negative_gossipsub_score.to_string(),
+            csv_field(&peer.score_state),
+            peer.trusted.to_string(),
+            peer.banned.to_string(),
+            optional_field(peer.decay_delay_secs),
+            peer.penalties.len().to_string(),
+            peer.penalties
+                .last()
+                .map(|penalty| csv_field(&penalty.msg))
+                .unwrap_or_default(),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+pub fn csv_response(csv: String) -> Result<Response<Body>, warp::Rejection> {
+    Response::builder()
+        .status(200)
+        .header("Content-Type", "text/csv")
+        .body(Body::from(csv))
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+        })
+}
+
+fn optional_field<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Quotes `value` if it contains characters with a special meaning in CSV.
+fn csv_field(value: &str) -> String {
+    if value.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
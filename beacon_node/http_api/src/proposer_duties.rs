@@ -1,5 +1,6 @@
 //! Contains the handler for the `GET validator/duties/proposer/{epoch}` endpoint.
 
+use crate::metrics;
 use crate::state_id::StateId;
 use beacon_chain::{
     beacon_proposer_cache::{compute_proposer_duties_from_head, ensure_state_is_in_epoch},
@@ -55,16 +56,33 @@ pub fn proposer_duties<T: BeaconChainTypes>(
             .safe_add(1)
             .map_err(warp_utils::reject::arith_error)?
     {
-        let (proposers, dependent_root, execution_status, _fork) =
-            compute_proposer_duties_from_head(request_epoch, chain)
-                .map_err(warp_utils::reject::beacon_chain_error)?;
-        convert_to_api_response(
-            chain,
-            request_epoch,
-            dependent_root,
-            execution_status.is_optimistic_or_invalid(),
-            proposers,
-        )
+        // The duties for the next epoch are keyed by the head block root, which is their
+        // dependent root until another block is imported. A new head (including a re-org)
+        // therefore misses the cache and the duties are recomputed.
+        let duties = if let Some(duties) = try_proposer_duties_from_cache(request_epoch, chain)? {
+            metrics::inc_counter(&metrics::HTTP_API_PROPOSER_LOOKAHEAD_CACHE_HITS_TOTAL);
+            duties
+        } else {
+            metrics::inc_counter(&metrics::HTTP_API_PROPOSER_LOOKAHEAD_CACHE_MISSES_TOTAL);
+            debug!(
+                log,
+                "Proposer lookahead cache miss";
+                "request_epoch" => request_epoch,
+            );
+            compute_and_cache_proposer_duties(request_epoch, chain)?
+        };
+
+        // A re-org back to a previous head hits the cache, so the dependent root must be checked
+        // for cache hits as well as misses.
+        if chain
+            .beacon_proposer_cache
+            .lock()
+            .update_lookahead(request_epoch, duties.dependent_root)
+        {
+            // The duties for this epoch were previously served for a different dependent root.
+            metrics::inc_counter(&metrics::HTTP_API_PROPOSER_LOOKAHEAD_CACHE_INVALIDATIONS_TOTAL);
+        }
+        Ok(duties)
     } else if request_epoch
         > current_epoch
             .safe_add(1)
@@ -146,27 +164,27 @@ fn try_proposer_duties_from_cache<T: BeaconChainTypes>(
 ///
 /// ## Notes
 ///
-/// The `current_epoch` value should equal the current epoch on the slot clock, otherwise we risk
-/// washing out the proposer cache at the expense of block processing.
+/// The `request_epoch` value should equal the current or next epoch on the slot clock, otherwise
+/// we risk washing out the proposer cache at the expense of block processing.
 fn compute_and_cache_proposer_duties<T: BeaconChainTypes>(
-    current_epoch: Epoch,
+    request_epoch: Epoch,
     chain: &BeaconChain<T>,
 ) -> Result<ApiDuties, warp::reject::Rejection> {
     let (indices, dependent_root, execution_status, fork) =
-        compute_proposer_duties_from_head(current_epoch, chain)
+        compute_proposer_duties_from_head(request_epoch, chain)
             .map_err(warp_utils::reject::beacon_chain_error)?;
 
     // Prime the proposer shuffling cache with the newly-learned value.
     chain
         .beacon_proposer_cache
         .lock()
-        .insert(current_epoch, dependent_root, indices.clone(), fork)
+        .insert(request_epoch, dependent_root, indices.clone(), fork)
         .map_err(BeaconChainError::from)
         .map_err(warp_utils::reject::beacon_chain_error)?;
 
     convert_to_api_response(
         chain,
-        current_epoch,
+        request_epoch,
         dependent_root,
         execution_status.is_optimistic_or_invalid(),
         indices,
@@ -1,18 +1,21 @@
 use crate::metrics;
 use beacon_chain::validator_monitor::{get_block_delay_ms, timestamp_now};
 use beacon_chain::{
-    BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, IntoGossipVerifiedBlock,
-    NotifyExecutionLayer,
+    BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockSource,
+    IntoGossipVerifiedBlock, NotifyExecutionLayer,
+};
+use eth2::types::{
+    BroadcastValidation, ErrorMessage, EventKind, SsePayloadDelivered, SsePayloadRevealed,
 };
-use eth2::types::{BroadcastValidation, ErrorMessage};
 use execution_layer::ProvenancedPayload;
 use lighthouse_network::PubsubMessage;
 use network::NetworkMessage;
+use otlp::Span;
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::marker::PhantomData;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::UnboundedSender;
 use tree_hash::TreeHash;
 use types::{
@@ -41,6 +44,10 @@ impl<T: BeaconChainTypes, B: IntoGossipVerifiedBlock<T>> ProvenancedBlock<T, B>
 }
 
 /// Handles a request from the HTTP API for full blocks.
+///
+/// If `span` is provided, the gossip publication and import of the block are recorded as its
+/// children.
+#[allow(clippy::too_many_arguments)]
 pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlock<T>>(
     block_root: Option<Hash256>,
     provenanced_block: ProvenancedBlock<T, B>,
@@ -49,6 +56,7 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlock<T>>(
     log: Logger,
     validation_level: BroadcastValidation,
     duplicate_status_code: StatusCode,
+    mut span: Option<Span>,
 ) -> Result<Response, Rejection> {
     let seen_timestamp = timestamp_now();
     let (block, is_locally_built_block) = match provenanced_block {
@@ -58,12 +66,19 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlock<T>>(
     let beacon_block = block.inner();
     let delay = get_block_delay_ms(seen_timestamp, beacon_block.message(), &chain.slot_clock);
     debug!(log, "Signed block received in HTTP API"; "slot" => beacon_block.slot());
+    if let Some(span) = &mut span {
+        span.set_attribute("slot", beacon_block.slot());
+    }
 
     /* actually publish a block */
+    let trace_parent = span.as_ref().map(|span| (span.exporter(), span.context()));
     let publish_block = move |block: Arc<SignedBeaconBlock<T::EthSpec>>,
                               sender,
                               log,
                               seen_timestamp| {
+        let _span = trace_parent
+            .as_ref()
+            .map(|(exporter, parent)| exporter.start_span("bn_gossip_publish", Some(*parent)));
         let publish_timestamp = timestamp_now();
         let publish_delay = publish_timestamp
             .checked_sub(seen_timestamp)
@@ -145,15 +160,23 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlock<T>>(
         }
     };
 
-    match chain
+    chain.observe_block_source(block_root, BlockSource::HttpApi, None, seen_timestamp);
+
+    let mut import_span = span.as_ref().map(|span| span.child("bn_import_block"));
+    let import_result = chain
         .process_block(
             block_root,
             gossip_verified_block,
             NotifyExecutionLayer::Yes,
             publish_fn,
         )
-        .await
-    {
+        .await;
+    if let (Some(span), Err(e)) = (&mut import_span, &import_result) {
+        span.set_error(format!("{:?}", e));
+    }
+    drop(import_span);
+
+    match import_result {
         Ok(root) => {
             info!(
                 log,
@@ -188,6 +211,8 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlock<T>>(
                     "local",
                     &log,
                 )
+            } else {
+                register_payload_delivered(&chain, &beacon_block, root);
             }
             Ok(warp::reply().into_response())
         }
@@ -226,6 +251,7 @@ pub async fn publish_blinded_block<T: BeaconChainTypes>(
     log: Logger,
     validation_level: BroadcastValidation,
     duplicate_status_code: StatusCode,
+    span: Option<Span>,
 ) -> Result<Response, Rejection> {
     let block_root = block.canonical_root();
     let full_block: ProvenancedBlock<T, Arc<SignedBeaconBlock<T::EthSpec>>> =
@@ -238,6 +264,7 @@ pub async fn publish_blinded_block<T: BeaconChainTypes>(
         log,
         validation_level,
         duplicate_status_code,
+        span,
     )
     .await
 }
@@ -292,6 +319,7 @@ pub async fn reconstruct_block<T: BeaconChainTypes>(
                 &log,
             );
 
+            let reveal_start = Instant::now();
             let full_payload = el
                 .propose_blinded_beacon_block(block_root, &block)
                 .await
@@ -302,6 +330,24 @@ pub async fn reconstruct_block<T: BeaconChainTypes>(
                     ))
                 })?;
             info!(log, "Successfully published a block to the builder network"; "block_hash" => ?full_payload.block_hash());
+
+            if let Some(event_handler) = chain.event_handler.as_ref() {
+                if event_handler.has_payload_revealed_subscribers() {
+                    if let Some(bid) = el
+                        .get_builder_bid(block.slot())
+                        .filter(|bid| bid.block_hash == full_payload.block_hash())
+                    {
+                        event_handler.register(EventKind::PayloadRevealed(SsePayloadRevealed {
+                            slot: block.slot(),
+                            block_root,
+                            block_hash: bid.block_hash,
+                            value: bid.value,
+                            relay: bid.relay,
+                            relay_response_ms: reveal_start.elapsed().as_millis() as u64,
+                        }));
+                    }
+                }
+            }
             ProvenancedPayload::Builder(full_payload)
         };
 
@@ -331,6 +377,40 @@ pub async fn reconstruct_block<T: BeaconChainTypes>(
     })
 }
 
+/// Notify subscribers that a block containing a builder payload has been published and imported.
+///
+/// The bid value and relay are taken from the execution layer's record of the bid, so nothing is
+/// emitted if the bid which produced this payload is unknown (e.g. after a restart).
+fn register_payload_delivered<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block: &SignedBeaconBlock<T::EthSpec>,
+    block_root: Hash256,
+) {
+    if let (Some(event_handler), Some(el), Ok(payload)) = (
+        chain.event_handler.as_ref(),
+        chain.execution_layer.as_ref(),
+        block.message().execution_payload(),
+    ) {
+        if !event_handler.has_payload_delivered_subscribers() {
+            return;
+        }
+        let block_hash = payload.block_hash();
+
+        if let Some(bid) = el
+            .get_builder_bid(block.slot())
+            .filter(|bid| bid.block_hash == block_hash)
+        {
+            event_handler.register(EventKind::PayloadDelivered(SsePayloadDelivered {
+                slot: block.slot(),
+                block_root,
+                block_hash,
+                value: bid.value,
+                relay: bid.relay,
+            }));
+        }
+    }
+}
+
 /// If the `seen_timestamp` is some time after the start of the slot for
 /// `block`, create some logs to indicate that the block was published late.
 fn late_block_logging<T: BeaconChainTypes, P: AbstractExecPayload<T::EthSpec>>(
@@ -0,0 +1,54 @@
+use beacon_chain::BeaconChainError;
+use state_processing::BlockReplayError;
+use types::BeaconStateError;
+use warp_utils::reject::beacon_chain_error;
+use warp_utils::task::CancellationToken;
+
+/// An error from replaying blocks on behalf of a request which may be cancelled.
+#[derive(Debug)]
+pub enum ReplayError {
+    BeaconChain(BeaconChainError),
+    /// The `CancellationToken` of the request was cancelled during the replay.
+    Cancelled,
+}
+
+impl From<BeaconChainError> for ReplayError {
+    fn from(e: BeaconChainError) -> Self {
+        Self::BeaconChain(e)
+    }
+}
+
+impl From<BlockReplayError> for ReplayError {
+    fn from(e: BlockReplayError) -> Self {
+        Self::BeaconChain(e.into())
+    }
+}
+
+impl From<BeaconStateError> for ReplayError {
+    fn from(e: BeaconStateError) -> Self {
+        Self::BeaconChain(e.into())
+    }
+}
+
+impl ReplayError {
+    /// Returns an error if `cancellation` has been cancelled, for use within replay hooks.
+    pub fn check(cancellation: &CancellationToken) -> Result<(), Self> {
+        if cancellation.is_cancelled() {
+            Err(Self::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Converts the error into a rejection, reporting a cancellation as either a timeout or a
+    /// cancelled request according to `cancellation`.
+    pub fn into_rejection(self, cancellation: &CancellationToken) -> warp::Rejection {
+        match self {
+            Self::BeaconChain(e) => beacon_chain_error(e),
+            Self::Cancelled => cancellation
+                .check()
+                .err()
+                .unwrap_or_else(warp::reject::reject),
+        }
+    }
+}
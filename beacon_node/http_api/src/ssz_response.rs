@@ -0,0 +1,395 @@
+use bytes::Bytes;
+use ssz::{encode_length, Encode, BYTES_PER_LENGTH_OFFSET};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::io;
+use std::ops::Range;
+use types::{BeaconState, BeaconStateError, EthSpec, ParticipationFlags, Validator};
+use warp::http::Response;
+use warp::hyper::Body;
+
+/// The size of each chunk of an SSZ response body.
+///
+/// Beacon states are hundreds of megabytes on mainnet, so they are sent with chunked transfer
+/// encoding rather than handed to the HTTP server as a single buffer.
+const SSZ_CHUNK_SIZE: usize = 1 << 20;
+
+/// The content type of SSZ responses on the standard API.
+pub const SSZ_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Build a response which streams `ssz_bytes` to the client in chunks.
+///
+/// The chunks share the underlying buffer, so the SSZ bytes are never copied. This is intended
+/// for objects which are already encoded, e.g. blocks read from the database.
+pub fn ssz_response(
+    ssz_bytes: Vec<u8>,
+    content_type: &'static str,
+) -> Result<Response<Body>, warp::Rejection> {
+    let bytes = Bytes::from(ssz_bytes);
+    let chunks = (0..bytes.len())
+        .step_by(SSZ_CHUNK_SIZE)
+        .map(|start| {
+            let end = std::cmp::min(start + SSZ_CHUNK_SIZE, bytes.len());
+            Ok::<_, Infallible>(bytes.slice(start..end))
+        })
+        .collect::<Vec<_>>();
+
+    build_response(
+        Body::wrap_stream(futures::stream::iter(chunks)),
+        content_type,
+    )
+}
+
+/// Build a response which streams the SSZ encoding of `state` to the client.
+///
+/// The state is encoded as it is sent, one chunk at a time, so that its full encoding is never
+/// held in memory.
+pub fn beacon_state_ssz_response<E: EthSpec>(
+    state: BeaconState<E>,
+    content_type: &'static str,
+) -> Result<Response<Body>, warp::Rejection> {
+    let chunks = BeaconStateChunks::new(state, SSZ_CHUNK_SIZE).map_err(|e| {
+        warp_utils::reject::custom_server_error(format!("failed to encode state: {:?}", e))
+    })?;
+
+    build_response(
+        Body::wrap_stream(futures::stream::iter(chunks)),
+        content_type,
+    )
+}
+
+fn build_response(
+    body: Body,
+    content_type: &'static str,
+) -> Result<Response<Body>, warp::Rejection> {
+    Response::builder()
+        .status(200)
+        .header("Content-Type", content_type)
+        .body(body)
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!("failed to create response: {}", e))
+        })
+}
+
+/// The lists of a beacon state which hold a value for every validator.
+///
+/// These make up almost all of the size of a state, so they are encoded a few items at a time as
+/// the response is sent.
+#[derive(Debug, Clone, Copy)]
+enum ValidatorList {
+    Validators,
+    Balances,
+    PreviousEpochParticipation,
+    CurrentEpochParticipation,
+    InactivityScores,
+}
+
+impl ValidatorList {
+    fn item_len(self) -> usize {
+        match self {
+            ValidatorList::Validators => <Validator as Encode>::ssz_fixed_len(),
+            ValidatorList::Balances | ValidatorList::InactivityScores => {
+                <u64 as Encode>::ssz_fixed_len()
+            }
+            ValidatorList::PreviousEpochParticipation
+            | ValidatorList::CurrentEpochParticipation => {
+                <ParticipationFlags as Encode>::ssz_fixed_len()
+            }
+        }
+    }
+
+    fn len<E: EthSpec>(self, state: &BeaconState<E>) -> Result<usize, BeaconStateError> {
+        Ok(match self {
+            ValidatorList::Validators => state.validators().len(),
+            ValidatorList::Balances => state.balances().len(),
+            ValidatorList::PreviousEpochParticipation => {
+                state.previous_epoch_participation()?.len()
+            }
+            ValidatorList::CurrentEpochParticipation => state.current_epoch_participation()?.len(),
+            ValidatorList::InactivityScores => state.inactivity_scores()?.len(),
+        })
+    }
+
+    /// Appends the encoding of the items in `range` to `buf`.
+    fn encode<E: EthSpec>(
+        self,
+        state: &BeaconState<E>,
+        range: Range<usize>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), BeaconStateError> {
+        match self {
+            ValidatorList::Validators => append_items(&state.validators()[..], range, buf),
+            ValidatorList::Balances => append_items(&state.balances()[..], range, buf),
+            ValidatorList::PreviousEpochParticipation => {
+                append_items(&state.previous_epoch_participation()?[..], range, buf)
+            }
+            ValidatorList::CurrentEpochParticipation => {
+                append_items(&state.current_epoch_participation()?[..], range, buf)
+            }
+            ValidatorList::InactivityScores => {
+                append_items(&state.inactivity_scores()?[..], range, buf)
+            }
+        }
+    }
+}
+
+fn append_items<T: Encode>(
+    items: &[T],
+    range: Range<usize>,
+    buf: &mut Vec<u8>,
+) -> Result<(), BeaconStateError> {
+    let start = range.start;
+    for item in items
+        .get(range)
+        .ok_or(BeaconStateError::UnknownValidator(start))?
+    {
+        item.ssz_append(buf);
+    }
+    Ok(())
+}
+
+/// A field of a beacon state.
+enum Field {
+    /// The encoding of a fixed-length field.
+    Fixed(Vec<u8>),
+    /// The encoding of a variable-length field.
+    Variable(Vec<u8>),
+    /// A list with an item for every validator, which is encoded later.
+    List { list: ValidatorList, len: usize },
+}
+
+impl Field {
+    fn new<T: Encode>(item: &T) -> Self {
+        if <T as Encode>::is_ssz_fixed_len() {
+            Field::Fixed(item.as_ssz_bytes())
+        } else {
+            Field::Variable(item.as_ssz_bytes())
+        }
+    }
+
+    fn list<E: EthSpec>(
+        state: &BeaconState<E>,
+        list: ValidatorList,
+    ) -> Result<Self, BeaconStateError> {
+        Ok(Field::List {
+            list,
+            len: list.len(state)?,
+        })
+    }
+
+    /// The number of bytes the field occupies in the fixed part of the container.
+    fn fixed_len(&self) -> usize {
+        match self {
+            Field::Fixed(bytes) => bytes.len(),
+            Field::Variable(_) | Field::List { .. } => BYTES_PER_LENGTH_OFFSET,
+        }
+    }
+}
+
+/// Returns the fields of `state` in the order in which they are encoded.
+fn beacon_state_fields<E: EthSpec>(state: &BeaconState<E>) -> Result<Vec<Field>, BeaconStateError> {
+    let mut fields = vec![
+        Field::new(&state.genesis_time()),
+        Field::new(&state.genesis_validators_root()),
+        Field::new(&state.slot()),
+        Field::new(&state.fork()),
+        Field::new(state.latest_block_header()),
+        Field::new(state.block_roots()),
+        Field::new(state.state_roots()),
+        Field::new(state.historical_roots()),
+        Field::new(state.eth1_data()),
+        Field::new(state.eth1_data_votes()),
+        Field::new(&state.eth1_deposit_index()),
+        Field::list(state, ValidatorList::Validators)?,
+        Field::list(state, ValidatorList::Balances)?,
+        Field::new(state.randao_mixes()),
+        Field::new(state.slashings()),
+    ];
+
+    match state {
+        BeaconState::Base(inner) => {
+            fields.push(Field::new(&inner.previous_epoch_attestations));
+            fields.push(Field::new(&inner.current_epoch_attestations));
+        }
+        BeaconState::Altair(_) | BeaconState::Merge(_) | BeaconState::Capella(_) => {
+            fields.push(Field::list(
+                state,
+                ValidatorList::PreviousEpochParticipation,
+            )?);
+            fields.push(Field::list(
+                state,
+                ValidatorList::CurrentEpochParticipation,
+            )?);
+        }
+    }
+
+    fields.push(Field::new(state.justification_bits()));
+    fields.push(Field::new(&state.previous_justified_checkpoint()));
+    fields.push(Field::new(&state.current_justified_checkpoint()));
+    fields.push(Field::new(&state.finalized_checkpoint()));
+
+    match state {
+        BeaconState::Base(_) => {}
+        BeaconState::Altair(inner) => {
+            fields.push(Field::list(state, ValidatorList::InactivityScores)?);
+            fields.push(Field::new(&*inner.current_sync_committee));
+            fields.push(Field::new(&*inner.next_sync_committee));
+        }
+        BeaconState::Merge(inner) => {
+            fields.push(Field::list(state, ValidatorList::InactivityScores)?);
+            fields.push(Field::new(&*inner.current_sync_committee));
+            fields.push(Field::new(&*inner.next_sync_committee));
+            fields.push(Field::new(&inner.latest_execution_payload_header));
+        }
+        BeaconState::Capella(inner) => {
+            fields.push(Field::list(state, ValidatorList::InactivityScores)?);
+            fields.push(Field::new(&*inner.current_sync_committee));
+            fields.push(Field::new(&*inner.next_sync_committee));
+            fields.push(Field::new(&inner.latest_execution_payload_header));
+            fields.push(Field::new(&inner.next_withdrawal_index));
+            fields.push(Field::new(&inner.next_withdrawal_validator_index));
+            fields.push(Field::new(&inner.historical_summaries));
+        }
+    }
+
+    Ok(fields)
+}
+
+/// A part of the encoding of a beacon state.
+enum Segment {
+    Encoded(Vec<u8>),
+    /// The items in `remaining` of one of the validator lists, which have not been encoded yet.
+    List {
+        list: ValidatorList,
+        remaining: Range<usize>,
+    },
+}
+
+/// An iterator over the SSZ encoding of a beacon state, in chunks of roughly `chunk_size` bytes.
+struct BeaconStateChunks<E: EthSpec> {
+    state: BeaconState<E>,
+    segments: VecDeque<Segment>,
+    chunk_size: usize,
+}
+
+impl<E: EthSpec> BeaconStateChunks<E> {
+    fn new(state: BeaconState<E>, chunk_size: usize) -> Result<Self, BeaconStateError> {
+        let fields = beacon_state_fields(&state)?;
+
+        // The fixed-length fields and the offsets of the variable-length fields are encoded up
+        // front, followed by the variable-length fields in order.
+        let fixed_len = fields.iter().map(Field::fixed_len).sum::<usize>();
+        let mut fixed_part = Vec::with_capacity(fixed_len);
+        let mut segments = VecDeque::with_capacity(fields.len());
+        let mut offset = fixed_len;
+        for field in fields {
+            match field {
+                Field::Fixed(bytes) => fixed_part.extend_from_slice(&bytes),
+                Field::Variable(bytes) => {
+                    fixed_part.extend_from_slice(&encode_length(offset));
+                    offset += bytes.len();
+                    segments.push_back(Segment::Encoded(bytes));
+                }
+                Field::List { list, len } => {
+                    fixed_part.extend_from_slice(&encode_length(offset));
+                    offset += len * list.item_len();
+                    segments.push_back(Segment::List {
+                        list,
+                        remaining: 0..len,
+                    });
+                }
+            }
+        }
+        segments.push_front(Segment::Encoded(fixed_part));
+
+        Ok(Self {
+            state,
+            segments,
+            chunk_size,
+        })
+    }
+}
+
+impl<E: EthSpec> Iterator for BeaconStateChunks<E> {
+    type Item = Result<Bytes, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        while chunk.len() < self.chunk_size {
+            match self.segments.pop_front() {
+                None => break,
+                Some(Segment::Encoded(bytes)) => {
+                    if chunk.is_empty() {
+                        chunk = bytes;
+                    } else {
+                        chunk.extend_from_slice(&bytes);
+                    }
+                }
+                Some(Segment::List { list, remaining }) => {
+                    let items = std::cmp::max((self.chunk_size - chunk.len()) / list.item_len(), 1);
+                    let end = std::cmp::min(remaining.start.saturating_add(items), remaining.end);
+                    if let Err(e) = list.encode(&self.state, remaining.start..end, &mut chunk) {
+                        self.segments.clear();
+                        return Some(Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!("failed to encode state: {:?}", e),
+                        )));
+                    }
+                    if end < remaining.end {
+                        self.segments.push_front(Segment::List {
+                            list,
+                            remaining: end..remaining.end,
+                        });
+                    }
+                }
+            }
+        }
+
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(Ok(chunk.into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::{
+        BeaconStateAltair, BeaconStateBase, BeaconStateCapella, BeaconStateMerge, MinimalEthSpec,
+    };
+
+    fn streamed_bytes<E: EthSpec>(state: BeaconState<E>, chunk_size: usize) -> Vec<u8> {
+        BeaconStateChunks::new(state, chunk_size)
+            .unwrap()
+            .flat_map(|chunk| chunk.unwrap().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn streamed_states_match_their_encoding() {
+        type E = MinimalEthSpec;
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let states: Vec<BeaconState<E>> = vec![
+            BeaconState::Base(BeaconStateBase::random_for_test(&mut rng)),
+            BeaconState::Altair(BeaconStateAltair::random_for_test(&mut rng)),
+            BeaconState::Merge(BeaconStateMerge::random_for_test(&mut rng)),
+            BeaconState::Capella(BeaconStateCapella::random_for_test(&mut rng)),
+        ];
+
+        for state in states {
+            let expected = state.as_ssz_bytes();
+            // Include chunk sizes smaller than a single validator.
+            for chunk_size in [1, 100, 4096, SSZ_CHUNK_SIZE] {
+                assert_eq!(
+                    streamed_bytes(state.clone(), chunk_size),
+                    expected,
+                    "chunk size {}",
+                    chunk_size
+                );
+            }
+        }
+    }
+}
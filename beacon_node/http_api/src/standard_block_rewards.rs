@@ -5,11 +5,13 @@ use beacon_chain::{BeaconChain, BeaconChainTypes};
 use eth2::lighthouse::StandardBlockReward;
 use std::sync::Arc;
 use warp_utils::reject::beacon_chain_error;
+use warp_utils::task::CancellationToken;
 //// The difference between block_rewards and beacon_block_rewards is the later returns block
 //// reward format that satisfies beacon-api specs
 pub fn compute_beacon_block_rewards<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     block_id: BlockId,
+    cancellation: CancellationToken,
 ) -> Result<(StandardBlockReward, ExecutionOptimistic, bool), warp::Rejection> {
     let (block, execution_optimistic, finalized) = block_id.blinded_block(&chain)?;
 
@@ -17,7 +19,8 @@ pub fn compute_beacon_block_rewards<T: BeaconChainTypes>(
 
     let block_root = block.canonical_root();
 
-    let mut state = get_state_before_applying_block(chain.clone(), &block)?;
+    let mut state = get_state_before_applying_block(chain.clone(), &block, &cancellation)?;
+    cancellation.check()?;
 
     let rewards = chain
         .compute_beacon_block_reward(block_ref, block_root, &mut state)
@@ -1,3 +1,4 @@
+use crate::replay_error::ReplayError;
 use crate::{BlockId, ExecutionOptimistic};
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::SyncCommitteeReward;
@@ -7,16 +8,20 @@ use state_processing::BlockReplayer;
 use std::sync::Arc;
 use types::{BeaconState, SignedBlindedBeaconBlock};
 use warp_utils::reject::{beacon_chain_error, custom_not_found};
+use warp_utils::task::CancellationToken;
 
 pub fn compute_sync_committee_rewards<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     block_id: BlockId,
     validators: Vec<ValidatorId>,
     log: Logger,
+    cancellation: CancellationToken,
 ) -> Result<(Option<Vec<SyncCommitteeReward>>, ExecutionOptimistic, bool), warp::Rejection> {
+    cancellation.check()?;
     let (block, execution_optimistic, finalized) = block_id.blinded_block(&chain)?;
 
-    let mut state = get_state_before_applying_block(chain.clone(), &block)?;
+    let mut state = get_state_before_applying_block(chain.clone(), &block, &cancellation)?;
+    cancellation.check()?;
 
     let reward_payload = chain
         .compute_sync_committee_rewards(block.message(), &mut state)
@@ -50,6 +55,7 @@ pub fn compute_sync_committee_rewards<T: BeaconChainTypes>(
 pub fn get_state_before_applying_block<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     block: &SignedBlindedBeaconBlock<T::EthSpec>,
+    cancellation: &CancellationToken,
 ) -> Result<BeaconState<T::EthSpec>, warp::reject::Rejection> {
     let parent_block: SignedBlindedBeaconBlock<T::EthSpec> = chain
         .get_blinded_block(&block.parent_root())
@@ -58,6 +64,9 @@ pub fn get_state_before_applying_block<T: BeaconChainTypes>(
         })
         .map_err(|e| custom_not_found(format!("Parent block is not available! {:?}", e)))?;
 
+    // Loading the state can't be interrupted, so don't start it once the request is cancelled.
+    cancellation.check()?;
+
     let parent_state = chain
         .get_state(&parent_block.state_root(), Some(parent_block.slot()))
         .and_then(|maybe_state| {
@@ -66,12 +75,16 @@ pub fn get_state_before_applying_block<T: BeaconChainTypes>(
         })
         .map_err(|e| custom_not_found(format!("Parent state is not available! {:?}", e)))?;
 
-    let replayer = BlockReplayer::new(parent_state, &chain.spec)
+    cancellation.check()?;
+
+    // Skipped slots may span several epochs, so stop between slots if the request is cancelled.
+    let replayer = BlockReplayer::<_, ReplayError, _>::new(parent_state, &chain.spec)
         .no_signature_verification()
+        .pre_slot_hook(Box::new(|_| ReplayError::check(cancellation)))
         .state_root_iter([Ok((parent_block.state_root(), parent_block.slot()))].into_iter())
         .minimal_block_root_verification()
         .apply_blocks(vec![], Some(block.slot()))
-        .map_err(beacon_chain_error)?;
+        .map_err(|e| e.into_rejection(cancellation))?;
 
     Ok(replayer.into_state())
 }
@@ -1,9 +1,11 @@
 use beacon_processor::{BeaconProcessorSend, BlockingOrAsync, Work, WorkEvent};
 use serde::Serialize;
 use std::future::Future;
+use std::time::Duration;
 use tokio::sync::{mpsc::error::TrySendError, oneshot};
 use types::EthSpec;
 use warp::reply::{Reply, Response};
+use warp_utils::task::CancellationToken;
 
 /// Maps a request to a queue in the `BeaconProcessor`.
 #[derive(Clone, Copy)]
@@ -106,6 +108,67 @@ impl<E: EthSpec> TaskSpawner<E> {
         self.blocking_response_task(priority, func).await
     }
 
+    /// Executes a "blocking" (non-async) task which returns a JSON-serializable object, returning
+    /// an error response if it does not complete within `timeout`.
+    ///
+    /// `func` is given a `CancellationToken` which is cancelled if the timeout elapses or the
+    /// client disconnects, including whilst the task is still queued.
+    pub async fn blocking_json_task_with_timeout<F, T>(
+        self,
+        priority: Priority,
+        timeout: Duration,
+        func: F,
+    ) -> Response
+    where
+        F: FnOnce(CancellationToken) -> Result<T, warp::Rejection> + Send + Sync + 'static,
+        T: Serialize + Send + 'static,
+    {
+        if let Some(beacon_processor_send) = &self.beacon_processor_send {
+            let token = CancellationToken::new();
+            let _guard = token.drop_guard();
+
+            let (tx, rx) = oneshot::channel();
+            let task_token = token.clone();
+            let process_fn = move || {
+                // Don't start the task if it is no longer required.
+                let func_result = task_token
+                    .check()
+                    .and_then(|()| func(task_token))
+                    .map(|t| warp::reply::json(&t).into_response());
+                let _ = tx.send(func_result);
+            };
+
+            let result = match tokio::time::timeout(
+                timeout,
+                send_to_beacon_processor(
+                    beacon_processor_send,
+                    priority,
+                    BlockingOrAsync::Blocking(Box::new(process_fn)),
+                    rx,
+                ),
+            )
+            .await
+            {
+                Ok(result) => result.and_then(|x| x),
+                Err(_) => {
+                    token.cancel();
+                    Err(warp_utils::reject::request_timeout(format!(
+                        "request timed out after {}ms",
+                        timeout.as_millis()
+                    )))
+                }
+            };
+            convert_rejection(result).await
+        } else {
+            // There is no beacon processor so spawn a task directly on the
+            // tokio executor.
+            convert_rejection(
+                warp_utils::task::blocking_json_task_with_timeout(timeout, func).await,
+            )
+            .await
+        }
+    }
+
     /// Executes an async task which may return a `Rejection`, which will be converted to a response.
     pub async fn spawn_async_with_rejection(
         self,
@@ -190,3 +253,97 @@ async fn send_to_beacon_processor<E: EthSpec, T>(
         error_message.to_string(),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use beacon_processor::BlockingFn;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Arc};
+    use tokio::runtime::Runtime;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    /// Returns a `TaskSpawner` along with the receiver for the work it sends to the
+    /// `BeaconProcessor`, so that tests control when the work is run.
+    fn task_spawner() -> (TaskSpawner<E>, tokio::sync::mpsc::Receiver<WorkEvent<E>>) {
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        (TaskSpawner::new(Some(BeaconProcessorSend(tx))), rx)
+    }
+
+    fn blocking_fn(event: WorkEvent<E>) -> BlockingFn {
+        match event.work {
+            Work::ApiRequestP0(BlockingOrAsync::Blocking(func))
+            | Work::ApiRequestP1(BlockingOrAsync::Blocking(func)) => func,
+            _ => panic!("expected a blocking API request"),
+        }
+    }
+
+    #[test]
+    fn task_with_timeout_completes() {
+        let runtime = Runtime::new().unwrap();
+        let (task_spawner, mut rx) = task_spawner();
+        let response = runtime.block_on(async move {
+            tokio::spawn(async move { blocking_fn(rx.recv().await.unwrap())() });
+            task_spawner
+                .blocking_json_task_with_timeout(Priority::P1, Duration::from_secs(10), |token| {
+                    token.check()?;
+                    Ok(42)
+                })
+                .await
+        });
+        assert_eq!(response.status(), eth2::StatusCode::OK);
+    }
+
+    #[test]
+    fn task_queued_past_timeout_is_not_started() {
+        let runtime = Runtime::new().unwrap();
+        let (task_spawner, mut rx) = task_spawner();
+        let started = Arc::new(AtomicBool::new(false));
+        let task_started = started.clone();
+        let response = runtime.block_on(task_spawner.blocking_json_task_with_timeout(
+            Priority::P1,
+            Duration::from_millis(10),
+            move |_| {
+                task_started.store(true, Ordering::SeqCst);
+                Ok(())
+            },
+        ));
+        assert_eq!(response.status(), eth2::StatusCode::GATEWAY_TIMEOUT);
+
+        // The beacon processor only gets to the task after the request has timed out.
+        blocking_fn(rx.try_recv().unwrap())();
+        assert!(!started.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn running_task_is_cancelled_on_timeout() {
+        let runtime = Runtime::new().unwrap();
+        let (task_spawner, mut rx) = task_spawner();
+        let (tx, cancelled) = mpsc::channel();
+        let response = runtime.block_on(async move {
+            tokio::spawn(async move {
+                let func = blocking_fn(rx.recv().await.unwrap());
+                tokio::task::spawn_blocking(func).await
+            });
+            task_spawner
+                .blocking_json_task_with_timeout(
+                    Priority::P1,
+                    Duration::from_millis(10),
+                    move |token| {
+                        while !token.is_cancelled() {
+                            std::thread::sleep(Duration::from_millis(1));
+                        }
+                        tx.send(()).unwrap();
+                        token.check()
+                    },
+                )
+                .await
+        });
+        assert_eq!(response.status(), eth2::StatusCode::GATEWAY_TIMEOUT);
+        cancelled
+            .recv_timeout(Duration::from_secs(10))
+            .expect("task should observe cancellation");
+    }
+}
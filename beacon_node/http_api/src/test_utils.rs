@@ -18,28 +18,33 @@ use lighthouse_network::{
     types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield, SyncState},
     ConnectedPoint, Enr, NetworkGlobals, PeerId, PeerManager,
 };
-use logging::test_logger;
+use logging::{test_logger, LogDirectives, RuntimeLogFilter};
 use network::{NetworkReceivers, NetworkSenders};
+use otlp::{OtlpExporter, SpanData};
 use sensitive_url::SensitiveUrl;
-use slog::Logger;
+use slog::{Level, Logger};
 use std::future::Future;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use store::MemoryStore;
 use task_executor::test_utils::TestRuntime;
+use tokio::sync::mpsc;
 use types::{ChainSpec, EthSpec};
 
 pub const TCP_PORT: u16 = 42;
 pub const UDP_PORT: u16 = 42;
 pub const SEQ_NUMBER: u64 = 0;
 pub const EXTERNAL_ADDR: &str = "/ip4/0.0.0.0/tcp/9000";
+pub const VALIDATOR_MONITOR_TOKEN: &str = "validator-monitor-token";
+pub const ADMIN_TOKEN: &str = "admin-token";
 
 /// HTTP API tester that allows interaction with the underlying beacon chain harness.
 pub struct InteractiveTester<E: EthSpec> {
     pub harness: BeaconChainHarness<EphemeralHarnessType<E>>,
     pub client: BeaconNodeHttpClient,
     pub network_rx: NetworkReceivers<E>,
+    pub otlp_rx: mpsc::Receiver<SpanData>,
 }
 
 /// The result of calling `create_api_server`.
@@ -51,6 +56,8 @@ pub struct ApiServer<E: EthSpec, SFut: Future<Output = ()>> {
     pub network_rx: NetworkReceivers<E>,
     pub local_enr: Enr,
     pub external_peer_id: PeerId,
+    /// Receives the spans recorded for requests which carry a `traceparent` header.
+    pub otlp_rx: mpsc::Receiver<SpanData>,
 }
 
 type Initializer<E> = Box<
@@ -96,6 +103,7 @@ impl<E: EthSpec> InteractiveTester<E> {
             server,
             listening_socket,
             network_rx,
+            otlp_rx,
             ..
         } = create_api_server(
             harness.chain.clone(),
@@ -120,6 +128,7 @@ impl<E: EthSpec> InteractiveTester<E> {
             harness,
             client,
             network_rx,
+            otlp_rx,
         }
     }
 }
@@ -187,6 +196,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         beacon_processor_rx,
         work_reprocessing_tx,
         work_reprocessing_rx,
+        status: beacon_processor_status,
     } = BeaconProcessorChannels::new(&beacon_processor_config);
 
     let beacon_processor_send = beacon_processor_tx;
@@ -195,6 +205,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         executor: test_runtime.task_executor.clone(),
         current_workers: 0,
         config: beacon_processor_config,
+        status: beacon_processor_status.clone(),
         log: log.clone(),
     }
     .spawn_manager(
@@ -207,19 +218,26 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     )
     .unwrap();
 
+    let (otlp_exporter, otlp_rx) = OtlpExporter::channel();
+
     let ctx = Arc::new(Context {
         config: Config {
             enabled: true,
             listen_port: port,
             data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+            validator_monitor_registration_token: Some(VALIDATOR_MONITOR_TOKEN.to_string()),
+            admin_token: Some(ADMIN_TOKEN.to_string()),
             ..Config::default()
         },
         chain: Some(chain),
         network_senders: Some(network_senders),
         network_globals: Some(network_globals),
         beacon_processor_send: Some(beacon_processor_send),
+        beacon_processor_status: Some(beacon_processor_status),
         eth1_service: Some(eth1_service),
         sse_logging_components: None,
+        runtime_log_filter: Some(RuntimeLogFilter::new(LogDirectives::new(Level::Info), None)),
+        otlp_exporter: Some(otlp_exporter),
         log,
     });
 
@@ -231,5 +249,6 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         network_rx: network_receivers,
         local_enr: enr,
         external_peer_id: peer_id,
+        otlp_rx,
     }
 }
@@ -0,0 +1,94 @@
+use crate::state_id::StateId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{ValidatorsPage, ValidatorsPageQuery};
+use eth2::types::{ExecutionOptimisticFinalizedResponse, ValidatorData, ValidatorStatus};
+use std::sync::Arc;
+use warp_utils::reject::custom_bad_request;
+
+/// The number of validators returned when no `limit` is given.
+pub const DEFAULT_VALIDATORS_PAGE_LIMIT: u64 = 1_000;
+/// The maximum number of validators which may be requested in a single page.
+pub const MAX_VALIDATORS_PAGE_LIMIT: u64 = 10_000;
+
+/// Returns a single page of the validators in the state identified by `state_id`.
+///
+/// Filtering happens before pagination, and only the validators on the requested page are
+/// cloned out of the state.
+pub fn get_validators_page<T: BeaconChainTypes>(
+    state_id: StateId,
+    query: ValidatorsPageQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<ExecutionOptimisticFinalizedResponse<ValidatorsPage>, warp::Rejection> {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(DEFAULT_VALIDATORS_PAGE_LIMIT);
+    if limit > MAX_VALIDATORS_PAGE_LIMIT {
+        return Err(custom_bad_request(format!(
+            "limit {} exceeds the maximum of {}",
+            limit, MAX_VALIDATORS_PAGE_LIMIT
+        )));
+    }
+    if let (Some(min_balance), Some(max_balance)) = (query.min_balance, query.max_balance) {
+        if min_balance > max_balance {
+            return Err(custom_bad_request(format!(
+                "min_balance {} is greater than max_balance {}",
+                min_balance, max_balance
+            )));
+        }
+    }
+
+    let (data, execution_optimistic, finalized) = state_id
+        .map_state_and_execution_optimistic_and_finalized(
+            &chain,
+            |state, execution_optimistic, finalized| {
+                let epoch = state.current_epoch();
+                let far_future_epoch = chain.spec.far_future_epoch;
+
+                let mut total = 0;
+                let mut validators = vec![];
+
+                for (index, (validator, &balance)) in state
+                    .validators()
+                    .iter()
+                    .zip(state.balances().iter())
+                    .enumerate()
+                {
+                    if query.min_balance.map_or(false, |min| balance < min)
+                        || query.max_balance.map_or(false, |max| balance > max)
+                    {
+                        continue;
+                    }
+
+                    let status =
+                        ValidatorStatus::from_validator(validator, epoch, far_future_epoch);
+                    let status_matches = query.status.as_ref().map_or(true, |statuses| {
+                        statuses.contains(&status) || statuses.contains(&status.superstatus())
+                    });
+                    if !status_matches {
+                        continue;
+                    }
+
+                    if total >= offset && (validators.len() as u64) < limit {
+                        validators.push(ValidatorData {
+                            index: index as u64,
+                            balance,
+                            status,
+                            validator: validator.clone(),
+                        });
+                    }
+                    total += 1;
+                }
+
+                Ok((
+                    ValidatorsPage { total, validators },
+                    execution_optimistic,
+                    finalized,
+                ))
+            },
+        )?;
+
+    Ok(ExecutionOptimisticFinalizedResponse {
+        data,
+        execution_optimistic: Some(execution_optimistic),
+        finalized: Some(finalized),
+    })
+}
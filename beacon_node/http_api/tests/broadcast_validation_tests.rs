@@ -372,6 +372,7 @@ pub async fn consensus_partial_pass_only_consensus() {
         test_logger,
         validation_level.unwrap(),
         StatusCode::ACCEPTED,
+        None,
     )
     .await;
 
@@ -650,6 +651,7 @@ pub async fn equivocation_consensus_late_equivocation() {
         test_logger,
         validation_level.unwrap(),
         StatusCode::ACCEPTED,
+        None,
     )
     .await;
 
@@ -1303,6 +1305,7 @@ pub async fn blinded_equivocation_consensus_late_equivocation() {
         test_logger,
         validation_level.unwrap(),
         StatusCode::ACCEPTED,
+        None,
     )
     .await;
 
@@ -1,14 +1,15 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use beacon_chain::{
     chain_config::{DisallowedReOrgOffsets, ReOrgThreshold},
-    test_utils::{AttestationStrategy, BlockStrategy, SyncCommitteeStrategy},
-    ChainConfig,
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, SyncCommitteeStrategy},
+    ChainConfig, WhenSlotSkipped,
 };
 use eth2::types::{DepositContractData, StateId};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
 use http_api::test_utils::InteractiveTester;
+use otlp::TraceContext;
 use parking_lot::Mutex;
-use slot_clock::SlotClock;
+use slot_clock::{SlotClock, TestingSlotClock};
 use state_processing::{
     per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
 };
@@ -814,3 +815,161 @@ pub async fn fork_choice_before_proposal() {
     // D's parent is B.
     assert_eq!(block_d.parent_root(), block_root_b.into());
 }
+
+// Test that publishing a block with a `traceparent` header records the publication and import of
+// the block as part of the caller's trace.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+pub async fn publish_block_with_trace_context() {
+    let validator_count = 24;
+    let mut tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+
+    harness.advance_slot();
+    let slot = harness.get_current_slot();
+    let (block, _) = harness.make_block(harness.get_current_state(), slot).await;
+
+    let trace_context = TraceContext::new_root();
+    tester
+        .client
+        .with_trace_context(Some(trace_context))
+        .post_beacon_blocks(&block)
+        .await
+        .unwrap();
+
+    let mut spans = HashMap::new();
+    while let Ok(span) = tester.otlp_rx.try_recv() {
+        spans.insert(span.name, span);
+    }
+
+    let publish_span = &spans["bn_publish_block"];
+    assert_eq!(publish_span.context.trace_id, trace_context.trace_id);
+    assert_eq!(publish_span.parent_span_id, Some(trace_context.span_id));
+    assert!(publish_span
+        .attributes
+        .contains(&("slot", slot.to_string())));
+
+    let import_span = &spans["bn_import_block"];
+    assert_eq!(import_span.context.trace_id, trace_context.trace_id);
+    assert_eq!(
+        import_span.parent_span_id,
+        Some(publish_span.context.span_id)
+    );
+    assert_eq!(import_span.error, None);
+
+    // Requests without a `traceparent` header are not traced.
+    harness.advance_slot();
+    let slot = harness.get_current_slot();
+    let (block, _) = harness.make_block(harness.get_current_state(), slot).await;
+    tester.client.post_beacon_blocks(&block).await.unwrap();
+    assert!(tester.otlp_rx.try_recv().is_err());
+}
+
+// Test that a checkpoint synced node can be filled in from an SSZ list of the full blocks before
+// its checkpoint, such as the blocks of an era file.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn import_historical_blocks_ssz() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let spec = E::default_spec();
+
+    // Build a chain with full history on one harness, representing a trusted node.
+    let source = BeaconChainHarness::builder(E::default())
+        .spec(spec.clone())
+        .deterministic_keypairs(validator_count)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    source.advance_slot();
+    source
+        .extend_chain(
+            4 * E::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let checkpoint = source.finalized_checkpoint();
+    let checkpoint_slot = checkpoint.epoch.start_slot(E::slots_per_epoch());
+    let wss_block = source
+        .chain
+        .store
+        .get_full_block(&checkpoint.root)
+        .unwrap()
+        .unwrap();
+    assert_eq!(wss_block.slot(), checkpoint_slot);
+    let wss_state = source
+        .chain
+        .get_state(&wss_block.state_root(), Some(checkpoint_slot))
+        .unwrap()
+        .unwrap();
+    let genesis_state = source
+        .chain
+        .get_state(&source.chain.genesis_state_root, Some(Slot::new(0)))
+        .unwrap()
+        .unwrap();
+
+    // The slot clock must be set to a time ahead of the checkpoint state.
+    let slot_clock = TestingSlotClock::new(
+        Slot::new(0),
+        Duration::from_secs(source.chain.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+    slot_clock.set_slot(source.get_current_slot().as_u64());
+
+    // Checkpoint sync a second node from the finalized checkpoint.
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        Some(spec),
+        validator_count,
+        Some(Box::new(move |builder| {
+            builder
+                .deterministic_keypairs(validator_count)
+                .fresh_ephemeral_store()
+                .override_store_mutator(Box::new(move |builder| {
+                    builder
+                        .weak_subjectivity_state(wss_state, wss_block, genesis_state)
+                        .unwrap()
+                }))
+                .testing_slot_clock(slot_clock)
+        })),
+        None,
+    )
+    .await;
+    let chain = &tester.harness.chain;
+    assert_eq!(
+        chain.store.get_anchor_info().unwrap().oldest_block_slot,
+        checkpoint_slot
+    );
+
+    // Supply every block before the checkpoint, except the genesis block which the node has.
+    let historical_blocks = (1..checkpoint_slot.as_u64())
+        .map(|slot| {
+            let block_root = source
+                .chain
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .unwrap()
+                .unwrap();
+            source
+                .chain
+                .store
+                .get_full_block(&block_root)
+                .unwrap()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let anchor = tester
+        .client
+        .post_lighthouse_database_historical_blocks_ssz(&historical_blocks)
+        .await
+        .unwrap();
+    assert_eq!(anchor.oldest_block_slot, 0);
+    assert_eq!(chain.store.get_anchor_info(), Some(anchor));
+
+    // Only the blinded blocks are stored.
+    for block in &historical_blocks {
+        assert_eq!(
+            chain.get_blinded_block(&block.canonical_root()).unwrap(),
+            Some(block.clone_as_blinded())
+        );
+    }
+}
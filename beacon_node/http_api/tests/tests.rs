@@ -1,10 +1,14 @@
-use beacon_chain::test_utils::RelativeSyncCommittee;
+use beacon_chain::test_utils::{RelativeSyncCommittee, SyncCommitteeStrategy};
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
     BeaconChain, ChainConfig, StateSkipConfig, WhenSlotSkipped,
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{
+        BeaconProcessorPriorityWeights, BlockSource, DebugCaptureRequest,
+        ForkChoiceSimulationRequest, LoggingRequest, NatStatus, ValidatorMonitorRegistration,
+    },
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{BlockId as CoreBlockId, ForkChoiceNode, StateId as CoreStateId, *},
@@ -17,11 +21,11 @@ use execution_layer::test_utils::{
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
 use http_api::{
-    test_utils::{create_api_server, ApiServer},
+    test_utils::{create_api_server, ApiServer, ADMIN_TOKEN, VALIDATOR_MONITOR_TOKEN},
     BlockId, StateId,
 };
-use lighthouse_network::{Enr, EnrExt, PeerId};
-use network::NetworkReceivers;
+use lighthouse_network::{Enr, EnrExt, OperatorAction, PeerId};
+use network::{NetworkMessage, NetworkReceivers};
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
@@ -78,6 +82,7 @@ struct ApiTester {
 struct ApiTesterConfig {
     spec: ChainSpec,
     retain_historic_states: bool,
+    light_client_server: bool,
     builder_threshold: Option<u128>,
 }
 
@@ -88,6 +93,7 @@ impl Default for ApiTesterConfig {
         Self {
             spec,
             retain_historic_states: false,
+            light_client_server: false,
             builder_threshold: None,
         }
     }
@@ -98,6 +104,11 @@ impl ApiTesterConfig {
         self.retain_historic_states = true;
         self
     }
+
+    fn light_client_server(mut self) -> Self {
+        self.light_client_server = true;
+        self
+    }
 }
 
 impl ApiTester {
@@ -125,6 +136,7 @@ impl ApiTester {
             .spec(spec.clone())
             .chain_config(ChainConfig {
                 reconstruct_historic_states: config.retain_historic_states,
+                enable_light_client_server: config.light_client_server,
                 ..ChainConfig::default()
             })
             .logger(logging::test_logger())
@@ -252,6 +264,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            ..
         } = create_api_server(chain.clone(), &harness.runtime, log).await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
@@ -347,6 +360,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            ..
         } = create_api_server(chain.clone(), &harness.runtime, log).await;
 
         harness.runtime.task_executor.spawn(server, "api_server");
@@ -403,6 +417,7 @@ impl ApiTester {
         let mut config = ApiTesterConfig {
             builder_threshold: Some(0),
             retain_historic_states: false,
+            light_client_server: false,
             spec: E::default_spec(),
         };
         config.spec.altair_fork_epoch = Some(Epoch::new(0));
@@ -1577,6 +1592,101 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_beacon_light_client_data(self) -> Self {
+        // Import two blocks carrying sync aggregates, the second attesting to the first.
+        for _ in 0..2 {
+            self.harness
+                .extend_chain_with_sync(
+                    1,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                    SyncCommitteeStrategy::AllValidators,
+                )
+                .await;
+            self.harness.advance_slot();
+        }
+
+        let head = self.chain.head_snapshot();
+        let attested_root = head.beacon_block.message().parent_root();
+        let attested_slot = self
+            .chain
+            .get_blinded_block(&attested_root)
+            .unwrap()
+            .unwrap()
+            .slot();
+
+        let bootstrap = self
+            .client
+            .get_light_client_bootstrap::<E>(head.beacon_block_root)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bootstrap.version, Some(ForkName::Altair));
+        assert_eq!(
+            bootstrap.data.header,
+            head.beacon_block.message().block_header()
+        );
+
+        let optimistic_update = self
+            .client
+            .get_light_client_optimistic_update::<E>()
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(optimistic_update.attested_header.slot, attested_slot);
+        assert_eq!(optimistic_update.signature_slot, head.beacon_block.slot());
+
+        let finality_update = self
+            .client
+            .get_light_client_finality_update::<E>()
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(finality_update.attested_header.slot, attested_slot);
+        assert_eq!(
+            finality_update.finalized_header.canonical_root(),
+            head.beacon_state.finalized_checkpoint().root
+        );
+
+        let period = attested_slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(&self.chain.spec)
+            .unwrap();
+        let updates = self
+            .client
+            .get_light_client_updates::<E>(period, 1)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].version, Some(ForkName::Altair));
+
+        // Requests for more than the maximum number of updates are rejected.
+        assert_eq!(
+            self.client
+                .get_light_client_updates::<E>(period, 129)
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+
+        self
+    }
+
+    pub async fn test_get_beacon_light_client_data_disabled(self) -> Self {
+        assert!(self
+            .client
+            .get_light_client_finality_update::<E>()
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
     pub async fn test_post_beacon_pool_attestations_valid(mut self) -> Self {
         self.client
             .post_beacon_pool_attestations(self.attestations.as_slice())
@@ -2040,6 +2150,39 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_ssz_responses_are_chunked(self) -> Self {
+        let urls = vec![
+            self.client
+                .get_debug_beacon_states_path(CoreStateId::Head)
+                .unwrap(),
+            self.client
+                .get_beacon_blinded_blocks_path(CoreBlockId::Head)
+                .unwrap(),
+            self.client
+                .get_beacon_blinded_blocks_path(CoreBlockId::Finalized)
+                .unwrap(),
+        ];
+
+        for url in urls {
+            let response = self
+                .client
+                .get_response(url.clone(), |b| b.accept(Accept::Ssz))
+                .await
+                .unwrap();
+            let headers = response.headers();
+            assert_eq!(
+                headers.get("transfer-encoding").unwrap(),
+                "chunked",
+                "{}",
+                url
+            );
+            assert!(headers.get("content-length").is_none(), "{}", url);
+            assert!(response.fork_name_from_header().unwrap().is_some());
+        }
+
+        self
+    }
+
     pub async fn test_get_debug_beacon_heads(self) -> Self {
         let result = self
             .client
@@ -2292,16 +2435,16 @@ impl ApiTester {
                 .await
                 .unwrap();
 
-            // Check that current-epoch requests prime the proposer cache, whilst non-current
-            // requests don't.
-            if epoch == current_epoch {
+            // Check that current and next-epoch requests prime the proposer cache, whilst
+            // historic requests don't.
+            if epoch == current_epoch || epoch == current_epoch + 1 {
                 assert!(
                     self.chain
                         .beacon_proposer_cache
                         .lock()
                         .get_epoch::<E>(dependent_root, epoch)
                         .is_some(),
-                    "a current-epoch request should prime the proposer cache"
+                    "a current or next-epoch request should prime the proposer cache"
                 );
             } else {
                 assert!(
@@ -2310,7 +2453,7 @@ impl ApiTester {
                         .lock()
                         .get_epoch::<E>(dependent_root, epoch)
                         .is_none(),
-                    "a non-current-epoch request should not prime the proposer cache"
+                    "a historic request should not prime the proposer cache"
                 );
             }
 
@@ -2350,8 +2493,8 @@ impl ApiTester {
 
             assert_eq!(result, expected);
 
-            // If it's the current epoch, check the function with a primed proposer cache.
-            if epoch == current_epoch {
+            // If it's the current or next epoch, check the function with a primed proposer cache.
+            if epoch == current_epoch || epoch == current_epoch + 1 {
                 // This is technically a double-check, but it's defensive.
                 assert!(
                     self.chain
@@ -2381,6 +2524,88 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_events_dependent_root_change(self) -> Self {
+        let harness = &self.harness;
+        let slots_per_epoch = E::slots_per_epoch();
+
+        // Extend the chain until the current slot is the last of an epoch.
+        while (harness.chain.slot().unwrap() + 1) % slots_per_epoch != 0 {
+            harness
+                .extend_chain(
+                    1,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                )
+                .await;
+            harness.advance_slot();
+        }
+
+        // The attester duties of the next epoch are not affected by the re-org below.
+        let fork_point = harness.chain.head_snapshot();
+        let previous_duty_dependent_root = fork_point
+            .beacon_state
+            .attester_shuffling_decision_root(fork_point.beacon_block_root, RelativeEpoch::Next)
+            .unwrap();
+        let decision_slot = harness.chain.slot().unwrap();
+        let epoch = (decision_slot + 1).epoch(slots_per_epoch);
+
+        // Build the old head from the proposer shuffling decision block for `epoch` and a block
+        // in `epoch` on top of it.
+        let (decision_block, decision_state) = harness
+            .make_block(fork_point.beacon_state.clone(), decision_slot)
+            .await;
+        self.client
+            .post_beacon_blocks(&decision_block)
+            .await
+            .unwrap();
+        harness.advance_slot();
+        let (old_head_block, _) = harness.make_block(decision_state, decision_slot + 1).await;
+        self.client
+            .post_beacon_blocks(&old_head_block)
+            .await
+            .unwrap();
+        assert_eq!(
+            self.chain.head_beacon_block_root(),
+            old_head_block.canonical_root()
+        );
+
+        let mut events_future = self
+            .client
+            .get_events::<E>(&[EventTopic::DependentRootChange])
+            .await
+            .unwrap();
+
+        // Re-org both blocks out with a block in the same epoch which skips the decision slot, so
+        // the proposer duties of `epoch` depend on the fork point instead.
+        harness.advance_slot();
+        let (new_head_block, _) = harness
+            .make_block(fork_point.beacon_state.clone(), decision_slot + 2)
+            .await;
+        self.client
+            .post_beacon_blocks(&new_head_block)
+            .await
+            .unwrap();
+        assert_eq!(
+            self.chain.head_beacon_block_root(),
+            new_head_block.canonical_root()
+        );
+
+        let expected = EventKind::DependentRootChange(SseDependentRootChange {
+            slot: new_head_block.slot(),
+            epoch,
+            block: new_head_block.canonical_root(),
+            old_previous_duty_dependent_root: Some(previous_duty_dependent_root),
+            previous_duty_dependent_root,
+            old_current_duty_dependent_root: Some(decision_block.canonical_root()),
+            current_duty_dependent_root: fork_point.beacon_block_root,
+            execution_optimistic: false,
+        });
+        let events = poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        assert_eq!(events.as_slice(), &[expected]);
+
+        self
+    }
+
     pub async fn test_get_validator_duties_early(self) -> Self {
         let current_epoch = self.chain.epoch().unwrap();
         let next_epoch = current_epoch + 1;
@@ -2410,13 +2635,15 @@ impl ApiTester {
             .await
             .expect("should get proposer duties for the next epoch outside of tolerance");
 
+        // Outside of tolerance the request is for the next epoch, which is cached under the
+        // dependent root of the head.
         assert!(
             self.chain
                 .beacon_proposer_cache
                 .lock()
                 .get_epoch::<E>(dependent_root, current_epoch)
-                .is_none(),
-            "should not prime the proposer cache outside of tolerance"
+                .is_some(),
+            "should prime the proposer cache for the next epoch outside of tolerance"
         );
 
         assert_eq!(
@@ -3968,6 +4195,118 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_builder_bid_event(self) -> Self {
+        let bid_value = Uint256::from(DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1);
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(bid_value));
+
+        let mut events_future = self
+            .client
+            .get_events::<E>(&[EventTopic::BuilderBid])
+            .await
+            .unwrap();
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let payload: BlindedPayload<E> = self
+            .client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data
+            .body()
+            .execution_payload()
+            .unwrap()
+            .into();
+
+        let events = poll_events(&mut events_future, 1, Duration::from_millis(10000)).await;
+        match events.as_slice() {
+            [EventKind::BuilderBid(bid)] => {
+                assert_eq!(bid.slot, slot);
+                assert_eq!(bid.value, bid_value);
+                assert_eq!(bid.block_hash, payload.block_hash());
+                assert_eq!(
+                    bid.local_block_value.map(|value| value.0),
+                    Some(Uint256::from(DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI))
+                );
+                assert!(bid.selected);
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+        self
+    }
+
+    pub async fn test_payload_revealed_and_delivered_events(self) -> Self {
+        let bid_value = Uint256::from(DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1);
+        self.mock_builder
+            .as_ref()
+            .unwrap()
+            .add_operation(Operation::Value(bid_value));
+
+        let mut events_future = self
+            .client
+            .get_events::<E>(&[EventTopic::PayloadRevealed, EventTopic::PayloadDelivered])
+            .await
+            .unwrap();
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let sk = &self.validator_keypairs()[proposer_index as usize].sk;
+
+        let block = self
+            .client
+            .get_validator_blinded_blocks::<E, BlindedPayload<E>>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data;
+        let block_hash = block.body().execution_payload().unwrap().block_hash();
+        let signed_block = block.sign(sk, &fork, genesis_validators_root, &self.chain.spec);
+        let block_root = signed_block.canonical_root();
+
+        // The builder reveals the payload and the block is published with it.
+        self.client
+            .post_beacon_blinded_blocks(&signed_block)
+            .await
+            .unwrap();
+
+        let events = poll_events(&mut events_future, 2, Duration::from_millis(10000)).await;
+        let revealed = events
+            .iter()
+            .find_map(|event| match event {
+                EventKind::PayloadRevealed(revealed) => Some(revealed),
+                _ => None,
+            })
+            .expect("payload revealed event");
+        assert_eq!(revealed.slot, slot);
+        assert_eq!(revealed.block_root, block_root);
+        assert_eq!(revealed.block_hash, block_hash);
+        assert_eq!(revealed.value, bid_value);
+
+        let delivered = events
+            .iter()
+            .find_map(|event| match event {
+                EventKind::PayloadDelivered(delivered) => Some(delivered),
+                _ => None,
+            })
+            .expect("payload delivered event");
+        assert_eq!(delivered.slot, slot);
+        assert_eq!(delivered.block_root, block_root);
+        assert_eq!(delivered.block_hash, block_hash);
+        assert_eq!(delivered.value, bid_value);
+        assert_eq!(delivered.relay, revealed.relay);
+
+        self
+    }
+
     pub async fn test_local_payload_chosen_when_equally_profitable(self) -> Self {
         // Mutate value.
         self.mock_builder
@@ -4001,6 +4340,31 @@ impl ApiTester {
             .unwrap()
             .get_payload_by_root(&payload.tree_hash_root())
             .is_some());
+
+        // Both values should've been recorded.
+        let decision = self
+            .client
+            .get_lighthouse_proposal_decisions()
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .next()
+            .unwrap();
+        assert_eq!(decision.slot, slot);
+        assert_eq!(decision.source, Some(ProposalPayloadSource::Local));
+        assert_eq!(
+            decision.local_block_value,
+            Some(SseBlockValue(Uint256::from(
+                DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI
+            )))
+        );
+        assert_eq!(
+            decision.builder_bid_value,
+            Some(SseBlockValue(Uint256::from(
+                DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI - 1
+            )))
+        );
         self
     }
 
@@ -4040,6 +4404,35 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_builder_relays(self) -> Self {
+        let relay = self
+            .chain
+            .execution_layer
+            .as_ref()
+            .unwrap()
+            .builder()
+            .unwrap()
+            .server()
+            .to_string();
+
+        let stats = self
+            .client
+            .get_lighthouse_builder_relays()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(stats.len(), 1);
+        let stats = &stats[0];
+        assert_eq!(stats.relay, relay);
+        assert!(stats.header_requests > 0);
+        assert_eq!(stats.bids, stats.header_requests);
+        assert!(stats.selected > 0);
+        assert_eq!(stats.errors, 0);
+        assert_eq!(stats.consecutive_failures, 0);
+        assert!(!stats.backed_off);
+        self
+    }
+
     pub async fn test_builder_works_post_capella(self) -> Self {
         // Ensure builder payload is chosen
         self.mock_builder
@@ -4148,54 +4541,428 @@ impl ApiTester {
         self
     }
 
-    pub async fn test_get_lighthouse_proto_array(self) -> Self {
-        self.client.get_lighthouse_proto_array().await.unwrap();
-
-        self
-    }
-
-    pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
-        let epoch = self.chain.epoch().unwrap() - 1;
-        self.client
-            .get_lighthouse_validator_inclusion_global(epoch)
+    pub async fn test_get_lighthouse_rpc_requests(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_rpc_requests()
             .await
-            .unwrap();
+            .unwrap()
+            .data;
+
+        // The network service is not running, so no requests are sent.
+        assert!(result.is_empty());
 
         self
     }
 
-    pub async fn test_get_lighthouse_validator_inclusion(self) -> Self {
-        let epoch = self.chain.epoch().unwrap() - 1;
-        self.client
-            .get_lighthouse_validator_inclusion(epoch, ValidatorId::Index(0))
+    pub async fn test_get_lighthouse_peers_handshake_failures(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_peers_handshake_failures()
             .await
-            .unwrap();
-
-        self
-    }
+            .unwrap()
+            .data;
 
-    pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
-        self.client.get_lighthouse_eth1_syncing().await.unwrap();
+        // The network service is not running, so no peers are disconnected.
+        assert!(result.is_empty());
 
         self
     }
 
-    pub async fn test_get_lighthouse_eth1_block_cache(self) -> Self {
-        let blocks = self.client.get_lighthouse_eth1_block_cache().await.unwrap();
+    pub async fn test_get_lighthouse_nat(self) -> Self {
+        // The response remains a plain boolean.
+        self.client.get_lighthouse_nat().await.unwrap();
 
-        assert!(blocks.data.is_empty());
+        // UPnP is not enabled by the test harness.
+        let status = self.client.get_lighthouse_nat_status().await.unwrap().data;
+        assert_eq!(status, NatStatus::default());
 
         self
     }
 
-    pub async fn test_get_lighthouse_eth1_deposit_cache(self) -> Self {
-        let deposits = self
+    pub async fn test_get_lighthouse_peer_stats(self) -> Self {
+        let result = self
             .client
-            .get_lighthouse_eth1_deposit_cache()
+            .get_lighthouse_peer_stats(&self.external_peer_id.to_string())
             .await
-            .unwrap();
-
-        assert!(deposits.data.is_empty());
+            .unwrap()
+            .unwrap()
+            .data;
+
+        assert_eq!(result.peer_id, self.external_peer_id.to_string());
+        assert!(result.gossipsub_score.is_some());
+        assert!(result.rpc.is_empty());
+
+        // An unknown peer is not found.
+        assert!(self
+            .client
+            .get_lighthouse_peer_stats(&PeerId::random().to_string())
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_peer_scores(self) -> Self {
+        let scores = self.client.get_lighthouse_peer_scores().await.unwrap().data;
+
+        let score = scores
+            .iter()
+            .find(|score| score.peer_id == self.external_peer_id.to_string())
+            .unwrap();
+        assert_eq!(score.connection_status, "connected");
+        assert_eq!(score.score_state, "Healthy");
+        assert!(score.gossipsub_score.is_some());
+        assert!(!score.banned);
+        assert!(score.penalties.is_empty());
+
+        let csv = self.client.get_lighthouse_peer_scores_csv().await.unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("peer_id,"));
+        assert!(lines.any(|line| line.starts_with(&self.external_peer_id.to_string())));
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_analysis_rewards(self) -> Self {
+        let validators = vec![ValidatorId::Index(0), ValidatorId::Index(1)];
+        let result = self
+            .client
+            .post_lighthouse_analysis_rewards(Epoch::new(1), Epoch::new(2), &validators)
+            .await
+            .unwrap();
+
+        assert_eq!(result.len(), 2);
+        for (rewards, epoch) in result.iter().zip(1..) {
+            let expected = self
+                .chain
+                .compute_attestation_rewards(Epoch::new(epoch), validators.clone())
+                .unwrap();
+            assert_eq!(rewards.epoch, epoch);
+            assert_eq!(rewards.attestation_rewards, expected);
+        }
+
+        // An inverted range is rejected.
+        assert_eq!(
+            self.client
+                .post_lighthouse_analysis_rewards(Epoch::new(2), Epoch::new(1), &validators)
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400)
+        );
+
+        // Epochs without a state end the stream with an error.
+        let far_future = Epoch::new(CHAIN_LENGTH);
+        assert!(self
+            .client
+            .post_lighthouse_analysis_rewards(far_future, far_future, &validators)
+            .await
+            .is_err());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_attestation_performance(self) -> Self {
+        let (start_epoch, end_epoch) = (Epoch::new(0), Epoch::new(1));
+        let global = self
+            .client
+            .get_lighthouse_analysis_attestation_performance(
+                start_epoch,
+                end_epoch,
+                "global".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(global.len(), VALIDATOR_COUNT);
+
+        // A validator may be identified by its pubkey.
+        let pubkey = self.validator_keypairs()[1].pk.compress().as_hex_string();
+        let by_pubkey = self
+            .client
+            .get_lighthouse_analysis_attestation_performance(start_epoch, end_epoch, pubkey)
+            .await
+            .unwrap();
+        assert_eq!(by_pubkey, vec![global[1].clone()]);
+
+        // The streamed response contains the same statistics, one epoch at a time.
+        let streamed = self
+            .client
+            .get_lighthouse_analysis_attestation_performance_stream(
+                start_epoch,
+                end_epoch,
+                "global",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            streamed.iter().map(|e| e.epoch).collect::<Vec<_>>(),
+            vec![start_epoch, end_epoch]
+        );
+        for epoch_performance in streamed {
+            for validator in epoch_performance.validators {
+                assert_eq!(
+                    global[validator.index as usize].epochs[&epoch_performance.epoch.as_u64()],
+                    validator.statistics
+                );
+            }
+        }
+
+        // Unknown pubkeys are rejected.
+        let unknown = Keypair::random().pk.compress().as_hex_string();
+        assert_eq!(
+            self.client
+                .get_lighthouse_analysis_attestation_performance(start_epoch, end_epoch, unknown)
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400)
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_block_packing(self) -> Self {
+        let packing = self
+            .client
+            .get_lighthouse_analysis_block_packing(Epoch::new(1), Epoch::new(2))
+            .await
+            .unwrap();
+
+        let expected_slots = (SLOTS_PER_EPOCH..3 * SLOTS_PER_EPOCH)
+            .filter(|slot| !SKIPPED_SLOTS.contains(slot))
+            .map(Slot::new)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            packing.iter().map(|block| block.slot).collect::<Vec<_>>(),
+            expected_slots
+        );
+        for block in packing {
+            assert!(block.included_attestations <= block.available_attestations);
+            // Any attestation left out of a block could have earned its proposer a reward.
+            assert_eq!(
+                block.estimated_missed_reward == 0,
+                block.included_attestations == block.available_attestations,
+                "{:?}",
+                block
+            );
+        }
+
+        // Epoch 0 has no prior epoch from which to start.
+        assert_eq!(
+            self.client
+                .get_lighthouse_analysis_block_packing(Epoch::new(0), Epoch::new(1))
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(400)
+        );
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_peer_action(mut self) -> Self {
+        let peer_id = self.external_peer_id.to_string();
+
+        for action in [
+            OperatorAction::Ban,
+            OperatorAction::Unban,
+            OperatorAction::Disconnect,
+            OperatorAction::Protect,
+            OperatorAction::Unprotect,
+        ] {
+            self.client
+                .post_lighthouse_peer_action(&peer_id, action)
+                .await
+                .unwrap();
+
+            match self.network_rx.network_recv.recv().await {
+                Some(NetworkMessage::OperatorAction {
+                    peer_id: received_peer_id,
+                    action: received_action,
+                    ..
+                }) => {
+                    assert_eq!(received_peer_id, self.external_peer_id);
+                    assert_eq!(received_action, action);
+                }
+                other => panic!("unexpected network message: {:?}", other),
+            }
+        }
+
+        // Unknown peers are not found.
+        assert_eq!(
+            self.client
+                .post_lighthouse_peer_action(&PeerId::random().to_string(), OperatorAction::Ban)
+                .await
+                .unwrap_err()
+                .status()
+                .map(Into::into),
+            Some(404)
+        );
+
+        // Unknown peers can be protected so that they are trusted once they connect.
+        let unknown_peer_id = PeerId::random();
+        self.client
+            .post_lighthouse_peer_action(&unknown_peer_id.to_string(), OperatorAction::Protect)
+            .await
+            .unwrap();
+        match self.network_rx.network_recv.recv().await {
+            Some(NetworkMessage::OperatorAction {
+                peer_id, action, ..
+            }) => {
+                assert_eq!(peer_id, unknown_peer_id);
+                assert_eq!(action, OperatorAction::Protect);
+            }
+            other => panic!("unexpected network message: {:?}", other),
+        }
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_analysis_fork_choice(self) -> Self {
+        let head_root = self.chain.head_snapshot().beacon_block_root;
+        let block_root = self.next_block.canonical_root();
+
+        // Without any changes the head is unchanged.
+        let result = self
+            .client
+            .post_lighthouse_analysis_fork_choice(&ForkChoiceSimulationRequest::<E> {
+                slot: None,
+                block: None,
+                block_delay_ms: 0,
+                attestations: vec![],
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.original_head_root, head_root);
+        assert_eq!(result.simulated_head_root, head_root);
+        assert!(!result.head_changed);
+
+        // A timely child of the head becomes the head.
+        let result = self
+            .client
+            .post_lighthouse_analysis_fork_choice(&ForkChoiceSimulationRequest::<E> {
+                slot: None,
+                block: Some(self.next_block.clone()),
+                block_delay_ms: 0,
+                attestations: vec![],
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.original_head_root, head_root);
+        assert_eq!(result.simulated_head_root, block_root);
+        assert_eq!(result.simulated_head_slot, self.next_block.slot());
+        assert!(result.head_changed);
+
+        // The live fork choice is not modified.
+        assert_eq!(self.chain.head_snapshot().beacon_block_root, head_root);
+        assert!(!self
+            .chain
+            .canonical_head
+            .fork_choice_read_lock()
+            .contains_block(&block_root));
+
+        // A block with an unknown parent is rejected.
+        let mut orphan = self.next_block.clone().deconstruct().0;
+        *orphan.parent_root_mut() = Hash256::repeat_byte(0xff);
+        let orphan = SignedBeaconBlock::from_block(orphan, Signature::empty());
+        let err = self
+            .client
+            .post_lighthouse_analysis_fork_choice(&ForkChoiceSimulationRequest::<E> {
+                slot: None,
+                block: Some(orphan),
+                block_delay_ms: 0,
+                attestations: vec![],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_proto_array(self) -> Self {
+        self.client.get_lighthouse_proto_array().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_block_provenance(self) -> Self {
+        let block_root = self.next_block.canonical_root();
+        self.client
+            .post_beacon_blocks(&self.next_block)
+            .await
+            .unwrap();
+
+        let provenance = self
+            .client
+            .get_lighthouse_block_provenance()
+            .await
+            .unwrap()
+            .data;
+
+        // Blocks imported by the harness have no recorded source.
+        assert!(provenance
+            .iter()
+            .filter(|entry| entry.block_root != block_root)
+            .all(|entry| entry.source == BlockSource::Unknown));
+
+        let published = provenance.last().unwrap();
+        assert_eq!(published.block_root, block_root);
+        assert_eq!(published.slot, self.next_block.slot());
+        assert_eq!(published.source, BlockSource::HttpApi);
+        assert_eq!(published.peer_id, None);
+        assert!(published.import_delay_ms.is_some());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        self.client
+            .get_lighthouse_validator_inclusion_global(epoch)
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_inclusion(self) -> Self {
+        let epoch = self.chain.epoch().unwrap() - 1;
+        self.client
+            .get_lighthouse_validator_inclusion(epoch, ValidatorId::Index(0))
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_eth1_syncing(self) -> Self {
+        self.client.get_lighthouse_eth1_syncing().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_eth1_block_cache(self) -> Self {
+        let blocks = self.client.get_lighthouse_eth1_block_cache().await.unwrap();
+
+        assert!(blocks.data.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_eth1_deposit_cache(self) -> Self {
+        let deposits = self
+            .client
+            .get_lighthouse_eth1_deposit_cache()
+            .await
+            .unwrap();
+
+        assert!(deposits.data.is_empty());
 
         self
     }
@@ -4220,6 +4987,75 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_beacon_states_validators(self) -> Self {
+        for state_id in self.interesting_state_ids() {
+            for statuses in self.interesting_validator_statuses() {
+                let statuses_opt = (!statuses.is_empty()).then_some(statuses.as_slice());
+                let all_validators = self
+                    .client
+                    .get_beacon_states_validators(state_id.0, None, statuses_opt)
+                    .await
+                    .unwrap()
+                    .map(|res| res.data);
+
+                for (offset, limit) in [(None, None), (Some(3), Some(5)), (Some(1_000), None)] {
+                    for (min_balance, max_balance) in [(None, None), (Some(1), Some(u64::MAX))] {
+                        let result = self
+                            .client
+                            .get_lighthouse_beacon_states_validators(
+                                state_id.0,
+                                offset,
+                                limit,
+                                statuses_opt,
+                                min_balance,
+                                max_balance,
+                            )
+                            .await
+                            .unwrap()
+                            .map(|res| res.data);
+
+                        let expected = all_validators.as_ref().map(|validators| {
+                            let matching = validators
+                                .iter()
+                                .filter(|validator| {
+                                    min_balance.map_or(true, |min| validator.balance >= min)
+                                        && max_balance.map_or(true, |max| validator.balance <= max)
+                                })
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            eth2::lighthouse::ValidatorsPage {
+                                total: matching.len() as u64,
+                                validators: matching
+                                    .into_iter()
+                                    .skip(offset.unwrap_or(0) as usize)
+                                    .take(limit.unwrap_or(1_000) as usize)
+                                    .collect(),
+                            }
+                        });
+
+                        assert_eq!(result, expected, "{:?}", state_id);
+                    }
+                }
+            }
+        }
+
+        // Requesting more than the maximum page size is an error.
+        let result = self
+            .client
+            .get_lighthouse_beacon_states_validators(
+                CoreStateId::Head,
+                None,
+                Some(10_001),
+                None,
+                None,
+                None,
+            )
+            .await;
+        assert_eq!(result.unwrap_err().status().unwrap(), 400);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_staking(self) -> Self {
         let result = self.client.get_lighthouse_staking().await.unwrap();
 
@@ -4237,6 +5073,170 @@ impl ApiTester {
             info.schema_version,
             store::metadata::CURRENT_SCHEMA_VERSION.as_u64()
         );
+        assert_eq!(
+            info.min_schema_version,
+            store::metadata::MIN_SUPPORTED_SCHEMA_VERSION.as_u64()
+        );
+        assert_eq!(info.max_schema_version, info.schema_version);
+
+        self
+    }
+
+    pub async fn test_lighthouse_beacon_processor_status(self) -> Self {
+        let default_weights = BeaconProcessorPriorityWeights {
+            attestations: 100,
+            sync_committee: 100,
+            rpc: 100,
+            api: 100,
+            backfill: 100,
+        };
+        let weights = BeaconProcessorPriorityWeights {
+            attestations: 50,
+            backfill: 1,
+            ..default_weights
+        };
+
+        let err = self
+            .client
+            .post_lighthouse_beacon_processor_priority_weights(&weights, "wrong")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+        let invalid_weights = BeaconProcessorPriorityWeights { rpc: 0, ..weights };
+        let err = self
+            .client
+            .post_lighthouse_beacon_processor_priority_weights(&invalid_weights, ADMIN_TOKEN)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+        let updated = self
+            .client
+            .post_lighthouse_beacon_processor_priority_weights(&weights, ADMIN_TOKEN)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(updated, weights);
+
+        let status = self
+            .client
+            .get_lighthouse_beacon_processor_status()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.priority_weights, weights);
+        // The test server runs the beacon processor with two workers.
+        assert_eq!(status.max_workers, 2);
+        assert!(status.active_workers <= status.max_workers);
+        for name in ["gossip_block", "attestation", "status", "api_request_p0"] {
+            assert!(status.queues.iter().any(|queue| queue.name == name));
+        }
+        for queue in &status.queues {
+            assert!(queue.length <= queue.max_length);
+        }
+
+        self.client
+            .post_lighthouse_beacon_processor_priority_weights(&default_weights, ADMIN_TOKEN)
+            .await
+            .unwrap();
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_logging(self) -> Self {
+        // The endpoint requires the admin token.
+        let err = self
+            .client
+            .post_lighthouse_logging(&LoggingRequest::default(), "wrong")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+        let status = self
+            .client
+            .post_lighthouse_logging(&LoggingRequest::default(), ADMIN_TOKEN)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.filter, "info");
+        assert_eq!(status.debug_capture, None);
+
+        let err = self
+            .client
+            .post_lighthouse_logging(
+                &LoggingRequest {
+                    filter: Some("info,network=loud".to_string()),
+                    debug_capture: None,
+                },
+                ADMIN_TOKEN,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+        let status = self
+            .client
+            .post_lighthouse_logging(
+                &LoggingRequest {
+                    filter: Some("warn, network=debug".to_string()),
+                    debug_capture: None,
+                },
+                ADMIN_TOKEN,
+            )
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.filter, "warn,network=debug");
+
+        // The test server has no log directory, so a capture must be rejected without changing
+        // the filter.
+        let err = self
+            .client
+            .post_lighthouse_logging(
+                &LoggingRequest {
+                    filter: Some("info".to_string()),
+                    debug_capture: Some(DebugCaptureRequest {
+                        duration_secs: 60,
+                        level: None,
+                    }),
+                },
+                ADMIN_TOKEN,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+        let status = self
+            .client
+            .post_lighthouse_logging(&LoggingRequest::default(), ADMIN_TOKEN)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.filter, "warn,network=debug");
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_database_reconstruction_status(self) -> Self {
+        let status = self
+            .client
+            .get_lighthouse_database_reconstruction_status()
+            .await
+            .unwrap();
+
+        assert_eq!(status, self.chain.store.reconstruction_status());
+        assert!(status.complete);
+        assert!(!status.in_progress);
+        assert_eq!(status.slots_remaining, Some(0));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_checkpoint_sync(self) -> Self {
+        // The test harness starts from genesis.
+        let report = self.client.get_lighthouse_checkpoint_sync().await.unwrap();
+        assert_eq!(report, None);
 
         self
     }
@@ -4251,6 +5251,119 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_historical_blocks_ssz(self) -> Self {
+        let head_block = self.chain.head_snapshot().beacon_block.as_ref().clone();
+
+        // The blocks are decoded, but can't be imported because the node synced from genesis.
+        let err = self
+            .client
+            .post_lighthouse_database_historical_blocks_ssz(&[head_block])
+            .await
+            .unwrap_err();
+        match err {
+            Error::ServerMessage(msg) => {
+                assert_eq!(msg.code, 500);
+                assert!(msg.message.contains("NoAnchorInfo"), "{}", msg.message);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_validator_monitor_validators(self) -> Self {
+        let pubkey = self.chain.validator_pubkey_bytes(0).unwrap().unwrap();
+        let registration = |cohort: &str| ValidatorMonitorRegistration {
+            pubkey,
+            cohort: Some(cohort.to_string()),
+        };
+
+        // An incorrect token is rejected.
+        assert_eq!(
+            self.client
+                .post_lighthouse_validator_monitor_validators(&[registration("vc-1")], "wrong")
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::FORBIDDEN)
+        );
+
+        // Cohorts which aren't suitable as metric labels are rejected.
+        assert_eq!(
+            self.client
+                .post_lighthouse_validator_monitor_validators(
+                    &[registration("not a label")],
+                    VALIDATOR_MONITOR_TOKEN
+                )
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+
+        self.client
+            .post_lighthouse_validator_monitor_validators(
+                &[registration("vc-1")],
+                VALIDATOR_MONITOR_TOKEN,
+            )
+            .await
+            .unwrap();
+
+        let validator_monitor = self.chain.validator_monitor.read();
+        let validator = validator_monitor.get_monitored_validator(0).unwrap();
+        assert_eq!(validator.cohort.as_deref(), Some("vc-1"));
+        drop(validator_monitor);
+
+        // Registering again without a cohort keeps the existing cohort.
+        self.client
+            .post_lighthouse_validator_monitor_validators(
+                &[ValidatorMonitorRegistration {
+                    pubkey,
+                    cohort: None,
+                }],
+                VALIDATOR_MONITOR_TOKEN,
+            )
+            .await
+            .unwrap();
+
+        let validator_monitor = self.chain.validator_monitor.read();
+        let validator = validator_monitor.get_monitored_validator(0).unwrap();
+        assert_eq!(validator.cohort.as_deref(), Some("vc-1"));
+        drop(validator_monitor);
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_slasher_check_attestation_disabled(self) -> Self {
+        let attestation = IndexedAttestation {
+            attesting_indices: vec![0].into(),
+            data: self.attestations[0].data.clone(),
+            signature: AggregateSignature::empty(),
+        };
+
+        // The endpoint requires the admin token.
+        assert_eq!(
+            self.client
+                .post_lighthouse_slasher_check_attestation(&attestation, "wrong")
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::FORBIDDEN)
+        );
+
+        // The slasher is not enabled for the tester.
+        assert_eq!(
+            self.client
+                .post_lighthouse_slasher_check_attestation(&attestation, ADMIN_TOKEN)
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::NOT_FOUND)
+        );
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -4431,6 +5544,11 @@ impl ApiTester {
             .get_events::<E>(&[EventTopic::ChainReorg])
             .await
             .unwrap();
+        let mut chain_reorg_detail_event_future = self
+            .client
+            .get_events::<E>(&[EventTopic::ChainReorgDetail])
+            .await
+            .unwrap();
 
         let expected_reorg = EventKind::ChainReorg(SseChainReorg {
             slot: self.reorg_block.slot(),
@@ -4458,6 +5576,36 @@ impl ApiTester {
         .await;
         assert_eq!(reorg_event.as_slice(), &[expected_reorg]);
 
+        let reorg_detail_event = poll_events(
+            &mut chain_reorg_detail_event_future,
+            1,
+            Duration::from_millis(10000),
+        )
+        .await;
+        let detail = match reorg_detail_event.as_slice() {
+            [EventKind::ChainReorgDetail(detail)] => detail,
+            other => panic!("unexpected events: {:?}", other),
+        };
+        assert_eq!(detail.depth, 1);
+        assert_eq!(detail.old_head_block, self.next_block.canonical_root());
+        assert_eq!(detail.new_head_block, self.reorg_block.canonical_root());
+        assert_eq!(
+            detail.old_branch.blocks,
+            vec![SseReorgBlock {
+                block: self.next_block.canonical_root(),
+                slot: self.next_block.slot(),
+                proposer_index: Some(self.next_block.message().proposer_index()),
+            }]
+        );
+        assert_eq!(
+            detail.new_branch.blocks.last().map(|block| block.block),
+            Some(self.reorg_block.canonical_root())
+        );
+        assert_eq!(
+            detail.weight_difference,
+            detail.new_branch.weight as i64 - detail.old_branch.weight as i64
+        );
+
         self
     }
 
@@ -4746,6 +5894,32 @@ async fn post_beacon_blocks_valid() {
     ApiTester::new().await.test_post_beacon_blocks_valid().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_light_client_data() {
+    let mut config = ApiTesterConfig::default().light_client_server();
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_beacon_light_client_data()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn beacon_light_client_data_disabled() {
+    ApiTester::new_with_hard_forks(true, false)
+        .await
+        .test_get_beacon_light_client_data_disabled()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_block_provenance() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_block_provenance()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_beacon_blocks_ssz_valid() {
     ApiTester::new()
@@ -4860,6 +6034,8 @@ async fn debug_get() {
         .await
         .test_get_debug_beacon_states()
         .await
+        .test_ssz_responses_are_chunked()
+        .await
         .test_get_debug_beacon_heads()
         .await
         .test_get_debug_fork_choice()
@@ -4928,6 +6104,14 @@ async fn get_validator_duties_proposer_with_skip_slots() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_dependent_root_change() {
+    ApiTester::new()
+        .await
+        .test_get_events_dependent_root_change()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn block_production() {
     ApiTester::new().await.test_block_production().await;
@@ -5251,6 +6435,22 @@ async fn builder_inadequate_builder_threshold() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_bid_event() {
+    ApiTester::new_mev_tester_no_builder_threshold()
+        .await
+        .test_builder_bid_event()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn payload_revealed_and_delivered_events() {
+    ApiTester::new_mev_tester_no_builder_threshold()
+        .await
+        .test_payload_revealed_and_delivered_events()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_payload_chosen_by_profit() {
     ApiTester::new_mev_tester_no_builder_threshold()
@@ -5260,6 +6460,8 @@ async fn builder_payload_chosen_by_profit() {
         .test_local_payload_chosen_when_equally_profitable()
         .await
         .test_local_payload_chosen_when_more_profitable()
+        .await
+        .test_get_lighthouse_builder_relays()
         .await;
 }
 
@@ -5268,6 +6470,7 @@ async fn builder_works_post_capella() {
     let mut config = ApiTesterConfig {
         builder_threshold: Some(0),
         retain_historic_states: false,
+        light_client_server: false,
         spec: E::default_spec(),
     };
     config.spec.altair_fork_epoch = Some(Epoch::new(0));
@@ -5300,6 +6503,26 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_nat()
+        .await
+        .test_get_lighthouse_rpc_requests()
+        .await
+        .test_get_lighthouse_peers_handshake_failures()
+        .await
+        .test_get_lighthouse_peer_stats()
+        .await
+        .test_get_lighthouse_peer_scores()
+        .await
+        .test_post_lighthouse_peer_action()
+        .await
+        .test_post_lighthouse_analysis_rewards()
+        .await
+        .test_get_lighthouse_analysis_attestation_performance()
+        .await
+        .test_get_lighthouse_analysis_block_packing()
+        .await
+        .test_post_lighthouse_analysis_fork_choice()
+        .await
         .test_get_lighthouse_proto_array()
         .await
         .test_get_lighthouse_validator_inclusion()
@@ -5314,13 +6537,29 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_beacon_states_ssz()
         .await
+        .test_get_lighthouse_beacon_states_validators()
+        .await
         .test_get_lighthouse_staking()
         .await
         .test_get_lighthouse_database_info()
         .await
+        .test_lighthouse_beacon_processor_status()
+        .await
+        .test_post_lighthouse_logging()
+        .await
+        .test_get_lighthouse_database_reconstruction_status()
+        .await
+        .test_get_lighthouse_checkpoint_sync()
+        .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_database_historical_blocks_ssz()
+        .await
         .test_post_lighthouse_liveness()
+        .await
+        .test_post_lighthouse_slasher_check_attestation_disabled()
+        .await
+        .test_post_lighthouse_validator_monitor_validators()
         .await;
 }
 
@@ -132,6 +132,10 @@ pub fn serve<T: BeaconChainTypes>(
                     }),
             )
         })
+        .with(warp_utils::metrics::response_times(
+            "http_metrics",
+            &["metrics"],
+        ))
         // Add a `Server` header.
         .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
         .with(cors_builder.build());
@@ -14,7 +14,7 @@ use std::net::{Ipv4Addr, Ipv6Addr};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
-use types::{ForkContext, ForkName};
+use types::{ForkContext, ForkName, SubnetId, SyncSubnetId};
 
 /// The cache time is set to accommodate the circulation time of an attestation.
 ///
@@ -117,6 +117,14 @@ pub struct Config {
     /// Subscribe to all subnets for the duration of the runtime.
     pub subscribe_all_subnets: bool,
 
+    /// Attestation subnets to subscribe to for the duration of the runtime, regardless of
+    /// validator duties. These are advertised in our ENR as long-lived subscriptions.
+    pub backbone_attestation_subnets: Vec<SubnetId>,
+
+    /// Sync committee subnets to subscribe to for the duration of the runtime, regardless of
+    /// validator duties. These are advertised in our ENR as long-lived subscriptions.
+    pub backbone_sync_subnets: Vec<SyncSubnetId>,
+
     /// Import/aggregate all attestations recieved on subscribed subnets for the duration of the
     /// runtime.
     pub import_all_attestations: bool,
@@ -144,6 +152,17 @@ pub struct Config {
     /// Whether light client protocols should be enabled.
     pub enable_light_client_server: bool,
 
+    /// Whether to offer the Lighthouse-specific `ssz_zstd` RPC encoding for blocks.
+    pub enable_rpc_zstd: bool,
+
+    /// The outbound bandwidth, in bytes per second, beyond which requests for finalized blocks
+    /// are served at the lowest priority.
+    pub outbound_bandwidth_budget: Option<u64>,
+
+    /// Whether discovered peers that advertise a QUIC address should be dialed before those that
+    /// only advertise TCP.
+    pub prefer_quic_peers: bool,
+
     /// Configuration for the outbound rate limiter (requests made by this node).
     pub outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
 
@@ -152,6 +171,17 @@ pub struct Config {
 
     /// Configuration for the inbound rate limiter (requests received by this node).
     pub inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
+
+    /// The maximum fraction of the target peer count which may connect to us from a single IP
+    /// subnet.
+    pub max_subnet_peer_fraction: Option<f32>,
+
+    /// The maximum fraction of the target peer count which may connect to us from a single
+    /// autonomous system. Requires `asn_database`.
+    pub max_asn_peer_fraction: Option<f32>,
+
+    /// Path to an IP-to-ASN database used to enforce `max_asn_peer_fraction`.
+    pub asn_database: Option<PathBuf>,
 }
 
 impl Config {
@@ -360,15 +390,23 @@ impl Default for Config {
             network_load: 3,
             private: false,
             subscribe_all_subnets: false,
+            backbone_attestation_subnets: vec![],
+            backbone_sync_subnets: vec![],
             import_all_attestations: false,
             shutdown_after_sync: false,
             topics: Vec::new(),
             proposer_only: false,
             metrics_enabled: false,
             enable_light_client_server: false,
+            enable_rpc_zstd: false,
+            outbound_bandwidth_budget: None,
+            prefer_quic_peers: false,
             outbound_rate_limiter_config: None,
             invalid_block_storage: None,
             inbound_rate_limiter_config: None,
+            max_subnet_peer_fraction: None,
+            max_asn_peer_fraction: None,
+            asn_database: None,
         }
     }
 }
@@ -15,7 +15,7 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 use std::str::FromStr;
-use types::{EnrForkId, EthSpec};
+use types::{EnrForkId, EthSpec, Slot};
 
 use super::enr_ext::{EnrExt, QUIC6_ENR_KEY, QUIC_ENR_KEY};
 
@@ -25,6 +25,8 @@ pub const ETH2_ENR_KEY: &str = "eth2";
 pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
 /// The ENR field specifying the sync committee subnet bitfield.
 pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
+/// The ENR field specifying the earliest slot from which the node can serve blocks.
+pub const EARLIEST_AVAILABLE_SLOT_ENR_KEY: &str = "eas";
 
 /// Extension trait for ENR's within Eth2.
 pub trait Eth2Enr {
@@ -39,6 +41,9 @@ pub trait Eth2Enr {
     ) -> Result<EnrSyncCommitteeBitfield<TSpec>, &'static str>;
 
     fn eth2(&self) -> Result<EnrForkId, &'static str>;
+
+    /// The earliest slot from which the node can serve blocks, if it advertises one.
+    fn earliest_available_slot(&self) -> Result<Slot, &'static str>;
 }
 
 impl Eth2Enr for Enr {
@@ -69,6 +74,14 @@ impl Eth2Enr for Enr {
 
         EnrForkId::from_ssz_bytes(eth2_bytes).map_err(|_| "Could not decode EnrForkId")
     }
+
+    fn earliest_available_slot(&self) -> Result<Slot, &'static str> {
+        let slot_bytes = self
+            .get(EARLIEST_AVAILABLE_SLOT_ENR_KEY)
+            .ok_or("ENR earliest available slot non-existent")?;
+
+        Slot::from_ssz_bytes(slot_bytes).map_err(|_| "Could not decode the ENR eas field")
+    }
 }
 
 /// Either use the given ENR or load an ENR from file if it exists and matches the current NodeId
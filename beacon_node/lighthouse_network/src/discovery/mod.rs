@@ -18,7 +18,10 @@ pub use enr::{
 pub use enr_ext::{peer_id_to_node_id, CombinedKeyExt, EnrExt};
 pub use libp2p::identity::{Keypair, PublicKey};
 
-use enr::{ATTESTATION_BITFIELD_ENR_KEY, ETH2_ENR_KEY, SYNC_COMMITTEE_BITFIELD_ENR_KEY};
+use enr::{
+    ATTESTATION_BITFIELD_ENR_KEY, EARLIEST_AVAILABLE_SLOT_ENR_KEY, ETH2_ENR_KEY,
+    SYNC_COMMITTEE_BITFIELD_ENR_KEY,
+};
 use futures::prelude::*;
 use futures::stream::FuturesUnordered;
 use libp2p::swarm::behaviour::{DialFailure, FromSwarm};
@@ -44,7 +47,7 @@ use std::{
     time::{Duration, Instant},
 };
 use tokio::sync::mpsc;
-use types::{EnrForkId, EthSpec};
+use types::{EnrForkId, EthSpec, Slot};
 
 mod subnet_predicate;
 pub use subnet_predicate::subnet_predicate;
@@ -549,6 +552,28 @@ impl<TSpec: EthSpec> Discovery<TSpec> {
         enr::save_enr_to_disk(Path::new(&self.enr_dir), &self.local_enr(), &self.log);
     }
 
+    /// Updates the `eas` field of our local ENR with the earliest slot from which we can serve
+    /// blocks.
+    pub fn update_enr_earliest_available_slot(&mut self, slot: Slot) -> Result<(), String> {
+        // Re-signing the ENR increments its sequence number, so only do so if the slot changed.
+        if self.local_enr().earliest_available_slot() == Ok(slot) {
+            return Ok(());
+        }
+
+        debug!(self.log, "Updating the ENR earliest available slot"; "slot" => slot);
+
+        self.discv5
+            .enr_insert(EARLIEST_AVAILABLE_SLOT_ENR_KEY, &slot.as_ssz_bytes())
+            .map_err(|e| format!("{:?}", e))?;
+
+        // replace the global version
+        *self.network_globals.local_enr.write() = self.discv5.local_enr();
+
+        // persist modified enr to disk
+        enr::save_enr_to_disk(Path::new(&self.enr_dir), &self.local_enr(), &self.log);
+        Ok(())
+    }
+
     // Bans a peer and it's associated seen IP addresses.
     pub fn ban_peer(&mut self, peer_id: &PeerId, ip_addresses: Vec<IpAddr>) {
         // first try and convert the peer_id to a node_id.
@@ -1097,8 +1122,11 @@ mod tests {
     }
 
     async fn build_discovery() -> Discovery<E> {
+        build_discovery_with_config(NetworkConfig::default()).await
+    }
+
+    async fn build_discovery_with_config(mut config: NetworkConfig) -> Discovery<E> {
         let keypair = secp256k1::Keypair::generate();
-        let mut config = NetworkConfig::default();
         config.set_listening_addr(crate::ListenAddress::unused_v4_ports());
         let enr_key: CombinedKey = CombinedKey::from_secp256k1(&keypair);
         let enr: Enr = build_enr::<E>(&enr_key, &config, &EnrForkId::default()).unwrap();
@@ -1159,6 +1187,38 @@ mod tests {
         assert_eq!(discovery.queued_queries.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_update_enr_earliest_available_slot() {
+        let network_dir = tempfile::tempdir().unwrap();
+        let config = NetworkConfig {
+            network_dir: network_dir.path().to_path_buf(),
+            ..NetworkConfig::default()
+        };
+        let mut discovery = build_discovery_with_config(config).await;
+        assert!(discovery.local_enr().earliest_available_slot().is_err());
+
+        let slot = Slot::new(64);
+        discovery.update_enr_earliest_available_slot(slot).unwrap();
+        let seq = discovery.local_enr().seq();
+        assert_eq!(discovery.local_enr().earliest_available_slot(), Ok(slot));
+        assert_eq!(
+            discovery
+                .network_globals
+                .local_enr()
+                .earliest_available_slot(),
+            Ok(slot)
+        );
+
+        // Advertising the same slot again must not re-sign the ENR.
+        discovery.update_enr_earliest_available_slot(slot).unwrap();
+        assert_eq!(discovery.local_enr().seq(), seq);
+
+        let slot = Slot::new(32);
+        discovery.update_enr_earliest_available_slot(slot).unwrap();
+        assert_eq!(discovery.local_enr().earliest_available_slot(), Ok(slot));
+        assert!(discovery.local_enr().seq() > seq);
+    }
+
     fn make_enr(subnet_ids: Vec<usize>) -> Enr {
         let mut builder = EnrBuilder::new("v4");
         let keypair = secp256k1::Keypair::generate();
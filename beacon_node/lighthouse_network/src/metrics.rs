@@ -24,6 +24,24 @@ lazy_static! {
         "Count of libp2p peers currently connected via QUIC"
     );
 
+    pub static ref TRANSPORT_HANDSHAKE_FAILURES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_transport_handshake_failures_total",
+        "Count of connections that failed before being established, per transport and direction",
+        &["transport", "direction"]
+    );
+
+    pub static ref HANDSHAKE_FAILURES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_handshake_failures_total",
+        "Count of peers disconnected during the handshake, per reason",
+        &["reason"]
+    );
+
+    pub static ref INBOUND_CONNECTIONS_REFUSED: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_inbound_connections_refused_total",
+        "Count of inbound connections refused by the admission policy",
+        &["reason"]
+    );
+
     pub static ref PEER_CONNECT_EVENT_COUNT: Result<IntCounter> = try_create_int_counter(
         "libp2p_peer_connect_event_total",
         "Count of libp2p peer connect events (not the current number of connected peers)"
@@ -0,0 +1,360 @@
+//! Admission control for inbound connections.
+//!
+//! An attacker attempting to eclipse a node will typically control a limited number of IP
+//! ranges, often hosted by a single provider. To make this harder we limit the number of
+//! connected peers which may share an IP subnet or autonomous system (ASN).
+
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The IPv4 prefix length used to group peers into subnets.
+pub const IPV4_SUBNET_PREFIX_LEN: u32 = 24;
+/// The IPv6 prefix length used to group peers into subnets.
+pub const IPV6_SUBNET_PREFIX_LEN: u32 = 48;
+
+/// The reason an inbound connection was refused.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdmissionError {
+    /// Too many peers are already connected from the same IP subnet.
+    SubnetLimit { connected: usize, limit: usize },
+    /// Too many peers are already connected from the same autonomous system.
+    AsnLimit {
+        asn: u32,
+        connected: usize,
+        limit: usize,
+    },
+}
+
+impl AdmissionError {
+    /// A short label for use in metrics.
+    pub fn as_label(&self) -> &'static str {
+        match self {
+            AdmissionError::SubnetLimit { .. } => "subnet",
+            AdmissionError::AsnLimit { .. } => "asn",
+        }
+    }
+}
+
+impl fmt::Display for AdmissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdmissionError::SubnetLimit { connected, limit } => write!(
+                f,
+                "subnet peer limit reached ({} connected, limit {})",
+                connected, limit
+            ),
+            AdmissionError::AsnLimit {
+                asn,
+                connected,
+                limit,
+            } => write!(
+                f,
+                "peer limit for AS{} reached ({} connected, limit {})",
+                asn, connected, limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AdmissionError {}
+
+/// An in-memory IP to ASN lookup table.
+///
+/// The table is loaded from a file in the tab-separated format published by
+/// <https://iptoasn.com>, one range per line:
+///
+/// ```text
+/// range_start range_end AS_number country_code AS_description
+/// ```
+///
+/// Ranges with an AS number of `0` (i.e. "not routed") are ignored.
+#[derive(Default, Clone)]
+pub struct AsnDatabase {
+    /// Sorted, non-overlapping ranges of `(start, end, asn)`.
+    ranges: Vec<(IpAddr, IpAddr, u32)>,
+}
+
+impl AsnDatabase {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read ASN database {}: {}", path.display(), e))?;
+        Self::from_str(&contents)
+    }
+
+    /// Returns the ASN which announces `ip`, if known.
+    pub fn lookup(&self, ip: &IpAddr) -> Option<u32> {
+        let index = match self
+            .ranges
+            .binary_search_by(|(start, _, _)| cmp_ip(start, ip))
+        {
+            Ok(index) => index,
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        // The range at `index` has the greatest start address not exceeding `ip`.
+        let (_, end, asn) = self.ranges.get(index)?;
+        cmp_ip(ip, end).is_le().then_some(*asn)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl fmt::Debug for AsnDatabase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsnDatabase")
+            .field("ranges", &self.ranges.len())
+            .finish()
+    }
+}
+
+impl FromStr for AsnDatabase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges = vec![];
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let mut next_field = |name: &str| {
+                fields
+                    .next()
+                    .ok_or_else(|| format!("ASN database line {}: missing {}", i + 1, name))
+            };
+            let start = IpAddr::from_str(next_field("range start")?)
+                .map_err(|e| format!("ASN database line {}: {}", i + 1, e))?;
+            let end = IpAddr::from_str(next_field("range end")?)
+                .map_err(|e| format!("ASN database line {}: {}", i + 1, e))?;
+            let asn = u32::from_str(next_field("AS number")?)
+                .map_err(|e| format!("ASN database line {}: {}", i + 1, e))?;
+
+            if start.is_ipv4() != end.is_ipv4() || cmp_ip(&start, &end).is_gt() {
+                return Err(format!("ASN database line {}: invalid range", i + 1));
+            }
+            if asn != 0 {
+                ranges.push((start, end, asn));
+            }
+        }
+        ranges.sort_by(|(a, _, _), (b, _, _)| cmp_ip(a, b));
+        Ok(Self { ranges })
+    }
+}
+
+/// Orders IPv4 addresses before IPv6 addresses, and numerically within each family.
+fn cmp_ip(a: &IpAddr, b: &IpAddr) -> std::cmp::Ordering {
+    match (a, b) {
+        (IpAddr::V4(a), IpAddr::V4(b)) => a.cmp(b),
+        (IpAddr::V6(a), IpAddr::V6(b)) => a.cmp(b),
+        (IpAddr::V4(_), IpAddr::V6(_)) => std::cmp::Ordering::Less,
+        (IpAddr::V6(_), IpAddr::V4(_)) => std::cmp::Ordering::Greater,
+    }
+}
+
+/// Returns the subnet `ip` belongs to, with the host bits masked out.
+pub fn subnet_of(ip: &IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(ip) => {
+            let mask = u32::MAX << (32 - IPV4_SUBNET_PREFIX_LEN);
+            IpAddr::V4(Ipv4Addr::from(u32::from(*ip) & mask))
+        }
+        IpAddr::V6(ip) => {
+            let mask = u128::MAX << (128 - IPV6_SUBNET_PREFIX_LEN);
+            IpAddr::V6(Ipv6Addr::from(u128::from(*ip) & mask))
+        }
+    }
+}
+
+/// Limits on the share of peers which may come from a single network location.
+#[derive(Debug, Default, Clone)]
+pub struct AdmissionPolicy {
+    /// Maximum fraction of the target peer count which may share an IP subnet.
+    pub max_subnet_peer_fraction: Option<f32>,
+    /// Maximum fraction of the target peer count which may belong to a single ASN.
+    pub max_asn_peer_fraction: Option<f32>,
+    /// Used to resolve the ASN of a peer. ASN limits are not enforced without a database.
+    pub asn_database: Option<AsnDatabase>,
+}
+
+impl AdmissionPolicy {
+    /// Returns `true` if no limits are configured.
+    pub fn is_disabled(&self) -> bool {
+        self.max_subnet_peer_fraction.is_none()
+            && (self.max_asn_peer_fraction.is_none() || self.asn_database.is_none())
+    }
+
+    /// Check whether a peer connecting from `ip` may be admitted, given the IP addresses of each
+    /// currently connected peer.
+    ///
+    /// A connected peer counts once towards a subnet or ASN if any of its addresses belongs to it.
+    pub fn check(
+        &self,
+        ip: &IpAddr,
+        connected_peers: &[Vec<IpAddr>],
+        target_peers: usize,
+    ) -> Result<(), AdmissionError> {
+        if let Some(fraction) = self.max_subnet_peer_fraction {
+            let limit = limit_from_fraction(fraction, target_peers);
+            let subnet = subnet_of(ip);
+            let connected = connected_peers
+                .iter()
+                .filter(|ips| ips.iter().any(|other| subnet_of(other) == subnet))
+                .count();
+            if connected >= limit {
+                return Err(AdmissionError::SubnetLimit { connected, limit });
+            }
+        }
+
+        if let (Some(fraction), Some(db)) = (self.max_asn_peer_fraction, &self.asn_database) {
+            if let Some(asn) = db.lookup(ip) {
+                let limit = limit_from_fraction(fraction, target_peers);
+                let connected = connected_peers
+                    .iter()
+                    .filter(|ips| ips.iter().any(|other| db.lookup(other) == Some(asn)))
+                    .count();
+                if connected >= limit {
+                    return Err(AdmissionError::AsnLimit {
+                        asn,
+                        connected,
+                        limit,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a fraction of the target peer count to a peer limit, always allowing at least one
+/// peer.
+fn limit_from_fraction(fraction: f32, target_peers: usize) -> usize {
+    std::cmp::max(1, (fraction * target_peers as f32).floor() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DB: &str = "\
+        1.0.0.0\t1.0.0.255\t13335\tUS\tCLOUDFLARENET\n\
+        1.0.1.0\t1.0.3.255\t0\tNone\tNot routed\n\
+        8.8.8.0\t8.8.8.255\t15169\tUS\tGOOGLE\n\
+        2001:4860::\t2001:4860:ffff:ffff:ffff:ffff:ffff:ffff\t15169\tUS\tGOOGLE\n";
+
+    fn ip(s: &str) -> IpAddr {
+        IpAddr::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn asn_lookup() {
+        let db = AsnDatabase::from_str(DB).unwrap();
+        assert_eq!(db.len(), 3);
+        assert_eq!(db.lookup(&ip("1.0.0.1")), Some(13335));
+        assert_eq!(db.lookup(&ip("1.0.0.255")), Some(13335));
+        assert_eq!(db.lookup(&ip("1.0.2.1")), None);
+        assert_eq!(db.lookup(&ip("8.8.8.8")), Some(15169));
+        assert_eq!(db.lookup(&ip("9.9.9.9")), None);
+        assert_eq!(db.lookup(&ip("0.0.0.1")), None);
+        assert_eq!(db.lookup(&ip("2001:4860::8888")), Some(15169));
+        assert_eq!(db.lookup(&ip("2001:4861::1")), None);
+    }
+
+    #[test]
+    fn invalid_asn_database() {
+        assert!(AsnDatabase::from_str("1.0.0.0\t1.0.0.255").is_err());
+        assert!(AsnDatabase::from_str("1.0.0.255\t1.0.0.0\t1").is_err());
+        assert!(AsnDatabase::from_str("1.0.0.0\t::1\t1").is_err());
+    }
+
+    #[test]
+    fn subnet_limit() {
+        let policy = AdmissionPolicy {
+            max_subnet_peer_fraction: Some(0.1),
+            ..Default::default()
+        };
+        let connected = vec![
+            vec![ip("10.0.0.1")],
+            vec![ip("10.0.0.2")],
+            vec![ip("10.0.1.1")],
+        ];
+
+        // 10% of 20 peers allows two peers per subnet.
+        assert_eq!(
+            policy.check(&ip("10.0.0.3"), &connected, 20),
+            Err(AdmissionError::SubnetLimit {
+                connected: 2,
+                limit: 2
+            })
+        );
+        assert!(policy.check(&ip("10.0.1.2"), &connected, 20).is_ok());
+        // At least one peer is always allowed per subnet.
+        assert!(policy.check(&ip("10.0.2.1"), &connected, 1).is_ok());
+    }
+
+    #[test]
+    fn asn_limit() {
+        let policy = AdmissionPolicy {
+            max_asn_peer_fraction: Some(0.05),
+            asn_database: Some(AsnDatabase::from_str(DB).unwrap()),
+            ..Default::default()
+        };
+        let connected = vec![vec![ip("8.8.8.8")], vec![ip("1.0.0.1")]];
+
+        assert_eq!(
+            policy.check(&ip("2001:4860::1"), &connected, 20),
+            Err(AdmissionError::AsnLimit {
+                asn: 15169,
+                connected: 1,
+                limit: 1
+            })
+        );
+        // Peers from unknown ASNs are not limited.
+        assert!(policy.check(&ip("9.9.9.9"), &connected, 20).is_ok());
+    }
+
+    #[test]
+    fn peers_with_several_ips_count_once() {
+        let policy = AdmissionPolicy {
+            max_subnet_peer_fraction: Some(0.1),
+            max_asn_peer_fraction: Some(0.1),
+            asn_database: Some(AsnDatabase::from_str(DB).unwrap()),
+        };
+        let mut connected = vec![
+            vec![ip("8.8.8.8"), ip("8.8.8.9"), ip("2001:4860::1")],
+            vec![ip("1.0.0.1")],
+        ];
+
+        // 10% of 20 peers allows two peers per subnet and ASN. Only one peer is connected from
+        // each, however many of its addresses are seen.
+        assert!(policy.check(&ip("8.8.8.10"), &connected, 20).is_ok());
+        assert!(policy.check(&ip("2001:4860::2"), &connected, 20).is_ok());
+
+        connected.push(vec![ip("8.8.8.11")]);
+        assert_eq!(
+            policy.check(&ip("8.8.8.10"), &connected, 20),
+            Err(AdmissionError::SubnetLimit {
+                connected: 2,
+                limit: 2
+            })
+        );
+        assert_eq!(
+            policy.check(&ip("2001:4860::2"), &connected, 20),
+            Err(AdmissionError::AsnLimit {
+                asn: 15169,
+                connected: 2,
+                limit: 2
+            })
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use super::admission::AdmissionPolicy;
+
 /// The time in seconds between re-status's peers.
 pub const DEFAULT_STATUS_INTERVAL: u64 = 300;
 
@@ -20,6 +22,8 @@ pub struct Config {
     pub metrics_enabled: bool,
     /// Target number of peers to connect to.
     pub target_peer_count: usize,
+    /// Whether to dial discovered peers with a QUIC address first.
+    pub prefer_quic_peers: bool,
 
     /* RPC related configurations */
     /// Time in seconds between status requests sent to peers.
@@ -30,6 +34,10 @@ pub struct Config {
     pub ping_interval_inbound: u64,
     /// Interval between PING events for peers dialed by us.
     pub ping_interval_outbound: u64,
+
+    /* Connection admission configurations */
+    /// Limits on the number of inbound peers from a single IP subnet or ASN.
+    pub admission_policy: AdmissionPolicy,
 }
 
 impl Default for Config {
@@ -38,9 +46,11 @@ impl Default for Config {
             discovery_enabled: true,
             metrics_enabled: false,
             target_peer_count: DEFAULT_TARGET_PEERS,
+            prefer_quic_peers: false,
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
             ping_interval_outbound: DEFAULT_PING_INTERVAL_OUTBOUND,
+            admission_policy: AdmissionPolicy::default(),
         }
     }
 }
@@ -0,0 +1,172 @@
+//! Limits on the number of established connections.
+//!
+//! Trusted peers occupy reserved slots in addition to the target peers. Peers can be trusted and
+//! untrusted at runtime, so these limits are enforced by the peer manager and recomputed whenever
+//! the set of trusted peers changes, rather than being fixed when the swarm is built.
+
+use std::fmt;
+
+use super::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
+use crate::service::utils::MAX_CONNECTIONS_PER_PEER;
+
+/// The kind of connection limit that was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LimitKind {
+    EstablishedIncoming,
+    EstablishedOutgoing,
+    EstablishedPerPeer,
+    EstablishedTotal,
+}
+
+/// A connection was denied because it would exceed one of the [`ConnectionLimits`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LimitExceeded {
+    pub kind: LimitKind,
+    pub limit: usize,
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            LimitKind::EstablishedIncoming => "established incoming",
+            LimitKind::EstablishedOutgoing => "established outgoing",
+            LimitKind::EstablishedPerPeer => "per-peer established",
+            LimitKind::EstablishedTotal => "established",
+        };
+        write!(f, "{} connection limit of {} reached", kind, self.limit)
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// The maximum number of established connections we allow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConnectionLimits {
+    pub max_established_incoming: usize,
+    pub max_established_outgoing: usize,
+    pub max_established_per_peer: usize,
+    pub max_established: usize,
+}
+
+impl ConnectionLimits {
+    /// Computes the limits for `target_peers`, with an additional slot in each limit for each of
+    /// the `reserved_slots` held by trusted peers.
+    pub fn new(target_peers: usize, reserved_slots: usize) -> Self {
+        let target_peers = target_peers as f32;
+        ConnectionLimits {
+            max_established_incoming: (target_peers
+                * (1.0 + PEER_EXCESS_FACTOR - MIN_OUTBOUND_ONLY_FACTOR))
+                .ceil() as usize
+                + reserved_slots,
+            max_established_outgoing: (target_peers * (1.0 + PEER_EXCESS_FACTOR)).ceil() as usize
+                + reserved_slots,
+            max_established_per_peer: MAX_CONNECTIONS_PER_PEER,
+            max_established: (target_peers * (1.0 + PEER_EXCESS_FACTOR + PRIORITY_PEER_EXCESS))
+                .ceil() as usize
+                + reserved_slots,
+        }
+    }
+
+    /// Checks whether a new connection may be established, given the number of connections
+    /// already established in each direction and with the same peer.
+    pub fn check(
+        &self,
+        incoming: usize,
+        outgoing: usize,
+        per_peer: usize,
+        is_incoming: bool,
+    ) -> Result<(), LimitExceeded> {
+        if per_peer >= self.max_established_per_peer {
+            return Err(LimitExceeded {
+                kind: LimitKind::EstablishedPerPeer,
+                limit: self.max_established_per_peer,
+            });
+        }
+        let (established, limit, kind) = if is_incoming {
+            (
+                incoming,
+                self.max_established_incoming,
+                LimitKind::EstablishedIncoming,
+            )
+        } else {
+            (
+                outgoing,
+                self.max_established_outgoing,
+                LimitKind::EstablishedOutgoing,
+            )
+        };
+        if established >= limit {
+            return Err(LimitExceeded { kind, limit });
+        }
+        if incoming + outgoing >= self.max_established {
+            return Err(LimitExceeded {
+                kind: LimitKind::EstablishedTotal,
+                limit: self.max_established,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_slots_raise_every_limit() {
+        let limits = ConnectionLimits::new(50, 0);
+        let reserved = ConnectionLimits::new(50, 3);
+        assert_eq!(
+            reserved.max_established_incoming,
+            limits.max_established_incoming + 3
+        );
+        assert_eq!(
+            reserved.max_established_outgoing,
+            limits.max_established_outgoing + 3
+        );
+        assert_eq!(reserved.max_established, limits.max_established + 3);
+    }
+
+    #[test]
+    fn check_limits() {
+        let limits = ConnectionLimits::new(10, 0);
+        let incoming = limits.max_established_incoming;
+        let outgoing = limits.max_established_outgoing;
+
+        assert_eq!(limits.check(incoming - 1, 0, 0, true), Ok(()));
+        assert_eq!(
+            limits.check(incoming, 0, 0, true),
+            Err(LimitExceeded {
+                kind: LimitKind::EstablishedIncoming,
+                limit: incoming
+            })
+        );
+        assert_eq!(limits.check(incoming, 0, 0, false), Ok(()));
+        assert_eq!(
+            limits.check(0, outgoing, 0, false),
+            Err(LimitExceeded {
+                kind: LimitKind::EstablishedOutgoing,
+                limit: outgoing
+            })
+        );
+        assert_eq!(
+            limits.check(
+                limits.max_established - outgoing + 1,
+                outgoing - 1,
+                0,
+                false
+            ),
+            Err(LimitExceeded {
+                kind: LimitKind::EstablishedTotal,
+                limit: limits.max_established
+            })
+        );
+        assert_eq!(
+            limits.check(0, 0, MAX_CONNECTIONS_PER_PEER, true),
+            Err(LimitExceeded {
+                kind: LimitKind::EstablishedPerPeer,
+                limit: MAX_CONNECTIONS_PER_PEER
+            })
+        );
+    }
+}
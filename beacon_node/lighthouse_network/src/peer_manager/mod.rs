@@ -12,13 +12,13 @@ use libp2p::identify::Info as IdentifyInfo;
 use lru_cache::LRUTimeCache;
 use peerdb::{client::ClientKind, BanOperation, BanResult, ScoreUpdateResult};
 use rand::seq::SliceRandom;
-use slog::{debug, error, trace, warn};
+use slog::{debug, error, info, trace, warn};
 use smallvec::SmallVec;
 use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use strum::IntoEnumIterator;
+use strum::{Display, EnumString, IntoEnumIterator};
 use types::{EthSpec, SyncSubnetId};
 
 pub use libp2p::core::Multiaddr;
@@ -27,14 +27,19 @@ pub use libp2p::identity::Keypair;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 pub mod peerdb;
 
+use connection_limits::ConnectionLimits;
+use libp2p::swarm::ConnectionId;
 pub use peerdb::peer_info::{
     ConnectionDirection, PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo,
 };
+pub use peerdb::rpc_stats::{ProtocolStats, RpcStats};
 use peerdb::score::{PeerAction, ReportSource};
 pub use peerdb::sync_status::{SyncInfo, SyncStatus};
 use std::collections::{hash_map::Entry, HashMap};
 use std::net::IpAddr;
+pub mod admission;
 pub mod config;
+pub mod connection_limits;
 mod network_behaviour;
 
 /// The heartbeat performs regular updates such as updating reputations and performing discovery
@@ -77,6 +82,8 @@ pub struct PeerManager<TSpec: EthSpec> {
     status_peers: HashSetDelay<PeerId>,
     /// The target number of peers we would like to connect to.
     target_peers: usize,
+    /// Whether discovered peers with a QUIC address are dialed first.
+    prefer_quic_peers: bool,
     /// Peers queued to be dialed.
     peers_to_dial: Vec<Enr>,
     /// The number of temporarily banned peers. This is used to prevent instantaneous
@@ -104,6 +111,18 @@ pub struct PeerManager<TSpec: EthSpec> {
     discovery_enabled: bool,
     /// Keeps track if the current instance is reporting metrics or not.
     metrics_enabled: bool,
+    /// Limits on the number of inbound peers sharing a network location.
+    admission_policy: admission::AdmissionPolicy,
+    /// Limits on the number of established connections, including the slots reserved for trusted
+    /// peers.
+    connection_limits: ConnectionLimits,
+    /// The established inbound connections and their peers.
+    established_inbound: HashMap<ConnectionId, PeerId>,
+    /// The established outbound connections and their peers.
+    established_outbound: HashMap<ConnectionId, PeerId>,
+    /// Set when the node is shutting down, after which no new peers are dialed and any new
+    /// connections are closed.
+    shutting_down: bool,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
@@ -135,6 +154,23 @@ pub enum PeerManagerEvent {
     DiscoverSubnetPeers(Vec<SubnetDiscovery>),
 }
 
+/// An action on a peer requested by the node operator.
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum OperatorAction {
+    /// Disconnect and ban the peer.
+    Ban,
+    /// Lift a ban on the peer and reset its score.
+    Unban,
+    /// Disconnect the peer without banning it.
+    Disconnect,
+    /// Treat the peer as trusted, so that it is never scored down, banned or pruned and occupies
+    /// a reserved connection slot.
+    Protect,
+    /// Stop treating the peer as trusted.
+    Unprotect,
+}
+
 impl<TSpec: EthSpec> PeerManager<TSpec> {
     // NOTE: Must be run inside a tokio executor.
     pub fn new(
@@ -146,14 +182,21 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             discovery_enabled,
             metrics_enabled,
             target_peer_count,
+            prefer_quic_peers,
             status_interval,
             ping_interval_inbound,
             ping_interval_outbound,
+            admission_policy,
         } = cfg;
 
         // Set up the peer manager heartbeat interval
         let heartbeat = tokio::time::interval(tokio::time::Duration::from_secs(HEARTBEAT_INTERVAL));
 
+        let connection_limits = ConnectionLimits::new(
+            target_peer_count,
+            network_globals.peers.read().reserved_slot_count(),
+        );
+
         Ok(PeerManager {
             network_globals,
             events: SmallVec::new(),
@@ -162,11 +205,17 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             outbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_outbound)),
             status_peers: HashSetDelay::new(Duration::from_secs(status_interval)),
             target_peers: target_peer_count,
+            prefer_quic_peers,
             temporary_banned_peers: LRUTimeCache::new(PEER_RECONNECTION_TIMEOUT),
             sync_committee_subnets: Default::default(),
             heartbeat,
             discovery_enabled,
             metrics_enabled,
+            admission_policy,
+            connection_limits,
+            established_inbound: Default::default(),
+            established_outbound: Default::default(),
+            shutting_down: false,
             log: log.clone(),
         })
     }
@@ -196,6 +245,102 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         );
     }
 
+    /// Sends a goodbye to all connected and dialing peers without penalising them, and stops making
+    /// new connections.
+    ///
+    /// This is used when the node shuts down. Returns the number of peers being disconnected.
+    pub fn disconnect_all_peers(&mut self, reason: GoodbyeReason) -> usize {
+        self.shutting_down = true;
+        let peers = self
+            .network_globals
+            .peers
+            .read()
+            .connected_or_dialing_peers()
+            .cloned()
+            .collect::<Vec<_>>();
+        for peer_id in &peers {
+            self.disconnect_peer(*peer_id, reason.clone());
+        }
+        peers.len()
+    }
+
+    /// Restores the scores of peers persisted by a previous run.
+    pub fn restore_peer_scores(&mut self, scores: Vec<(PeerId, f64)>) {
+        let restored = self.network_globals.peers.write().restore_scores(scores);
+        if restored > 0 {
+            debug!(self.log, "Restored peer scores"; "peers" => restored);
+        }
+    }
+
+    /// Applies an action requested by the node operator to a known peer. `requested_by` identifies
+    /// the requester in the logs.
+    pub fn operator_action(
+        &mut self,
+        peer_id: &PeerId,
+        action: OperatorAction,
+        requested_by: &str,
+    ) {
+        info!(
+            self.log,
+            "Operator requested peer action";
+            "peer_id" => %peer_id,
+            "action" => %action,
+            "requested_by" => requested_by,
+        );
+
+        match action {
+            OperatorAction::Ban => {
+                // A trusted peer cannot be scored down, so remove its protection first.
+                self.network_globals
+                    .peers
+                    .write()
+                    .set_trusted(peer_id, false);
+                self.goodbye_peer(peer_id, GoodbyeReason::Banned, ReportSource::Operator);
+            }
+            OperatorAction::Unban => {
+                let result = self.network_globals.peers.write().unban_peer(peer_id);
+                self.handle_score_action(peer_id, result, None);
+            }
+            OperatorAction::Disconnect => {
+                if self.network_globals.peers.read().is_connected(peer_id) {
+                    self.disconnect_peer(*peer_id, GoodbyeReason::Unknown);
+                }
+            }
+            OperatorAction::Protect => {
+                let result = self.network_globals.peers.write().unban_peer(peer_id);
+                self.handle_score_action(peer_id, result, None);
+                self.network_globals
+                    .peers
+                    .write()
+                    .set_trusted(peer_id, true);
+            }
+            OperatorAction::Unprotect => {
+                self.network_globals
+                    .peers
+                    .write()
+                    .set_trusted(peer_id, false);
+            }
+        }
+
+        // The action may have changed the set of trusted peers, and with it the number of
+        // reserved slots.
+        self.update_connection_limits();
+    }
+
+    /// Recomputes the connection limits from the current number of trusted peers.
+    fn update_connection_limits(&mut self) {
+        let reserved_slots = self.network_globals.peers.read().reserved_slot_count();
+        let connection_limits = ConnectionLimits::new(self.target_peers, reserved_slots);
+        if connection_limits != self.connection_limits {
+            debug!(self.log, "Updated connection limits"; "reserved_slots" => reserved_slots,
+                "max_established_incoming" => connection_limits.max_established_incoming,
+                "max_established_outgoing" => connection_limits.max_established_outgoing,
+                "max_established" => connection_limits.max_established,
+            );
+            self.connection_limits = connection_limits;
+        }
+    }
+
     /// Reports a peer for some action.
     ///
     /// If the peer doesn't exist, log a warning and insert defaults.
@@ -315,8 +460,16 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     /// This function decides whether or not to dial these peers.
     #[allow(clippy::mutable_key_type)]
     pub fn peers_discovered(&mut self, results: HashMap<Enr, Option<Instant>>) {
+        if self.shutting_down {
+            return;
+        }
         let mut to_dial_peers = 0;
         let connected_or_dialing = self.network_globals.connected_or_dialing_peers();
+        let mut results = results.into_iter().collect::<Vec<_>>();
+        if self.prefer_quic_peers {
+            // Dial peers supporting QUIC first, in case we reach our peer limit.
+            results.sort_by_key(|(enr, _)| enr.multiaddr_quic().is_empty());
+        }
         for (enr, min_ttl) in results {
             // There are two conditions in deciding whether to dial this peer.
             // 1. If we are less than our max connections. Discovery queries are executed to reach
@@ -352,6 +505,13 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         self.status_peers.insert(*peer_id);
     }
 
+    /// Updates the RPC statistics of a known peer.
+    pub fn update_rpc_stats(&mut self, peer_id: &PeerId, update: impl FnOnce(&mut RpcStats)) {
+        if let Some(peer_info) = self.network_globals.peers.write().peer_info_mut(peer_id) {
+            update(peer_info.rpc_stats_mut());
+        }
+    }
+
     /// Insert the sync subnet into list of long lived sync committee subnets that we need to
     /// maintain adequate number of peers for.
     pub fn add_sync_subnet(&mut self, subnet_id: SyncSubnetId, min_ttl: Instant) {
@@ -425,13 +585,16 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
 
     /// Reports whether the peer limit is reached in which case we stop allowing new incoming
     /// connections.
+    ///
+    /// Trusted peers occupy reserved slots and do not count towards the limit.
     pub fn peer_limit_reached(&self, count_dialing: bool) -> bool {
+        let peers = self.network_globals.peers.read();
         if count_dialing {
             // This is an incoming connection so limit by the standard max peers
-            self.network_globals.connected_or_dialing_peers() >= self.max_peers()
+            peers.connected_or_dialing_untrusted_peers().count() >= self.max_peers()
         } else {
             // We dialed this peer, allow up to max_outbound_dialing_peers
-            self.network_globals.connected_peers() >= self.max_outbound_dialing_peers()
+            peers.connected_untrusted_peer_ids().count() >= self.max_outbound_dialing_peers()
         }
     }
 
@@ -954,8 +1117,14 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     /// 4. Randomly remove peers if all the above are satisfied
     ///
     fn prune_excess_peers(&mut self) {
-        // The current number of connected peers.
-        let connected_peer_count = self.network_globals.connected_peers();
+        // The current number of connected peers, excluding trusted peers which occupy reserved
+        // slots.
+        let connected_peer_count = self
+            .network_globals
+            .peers
+            .read()
+            .connected_untrusted_peer_ids()
+            .count();
         if connected_peer_count <= self.target_peers {
             // No need to prune peers
             return;
@@ -1176,7 +1345,9 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
     /// NOTE: Discovery will only add a new query if one isn't already queued.
     fn heartbeat(&mut self) {
         // Optionally run a discovery query if we need more peers.
-        self.maintain_peer_count(0);
+        if !self.shutting_down {
+            self.maintain_peer_count(0);
+        }
 
         // Cleans up the connection state of dialing peers.
         // Libp2p dials peer-ids, but sometimes the response is from another peer-id or libp2p
@@ -1304,6 +1475,7 @@ enum ConnectingType {
 
 #[cfg(test)]
 mod tests {
+    use super::connection_limits::LimitKind;
     use super::*;
     use slog::{o, Drain};
     use types::MainnetEthSpec as E;
@@ -1345,6 +1517,77 @@ mod tests {
         PeerManager::new(config, Arc::new(globals), &log).unwrap()
     }
 
+    #[tokio::test]
+    async fn test_connection_limits_follow_trusted_peers() {
+        let trusted_peer = PeerId::random();
+        let peer = PeerId::random();
+        let mut peer_manager = build_peer_manager_with_trusted_peers(vec![trusted_peer], 10).await;
+
+        // Configured trusted peers reserve slots from the start.
+        assert_eq!(peer_manager.connection_limits, ConnectionLimits::new(10, 1));
+
+        // Protecting a peer at runtime reserves another slot, but only once.
+        peer_manager.operator_action(&peer, OperatorAction::Protect, "test");
+        assert_eq!(peer_manager.connection_limits, ConnectionLimits::new(10, 2));
+        peer_manager.operator_action(&peer, OperatorAction::Protect, "test");
+        assert_eq!(peer_manager.connection_limits, ConnectionLimits::new(10, 2));
+
+        // Removing the trust releases the slots again.
+        peer_manager.operator_action(&peer, OperatorAction::Unprotect, "test");
+        assert_eq!(peer_manager.connection_limits, ConnectionLimits::new(10, 1));
+        peer_manager.operator_action(&trusted_peer, OperatorAction::Ban, "test");
+        assert_eq!(peer_manager.connection_limits, ConnectionLimits::new(10, 0));
+    }
+
+    #[tokio::test]
+    async fn test_trusted_peers_added_at_runtime_raise_connection_limits() {
+        let mut peer_manager = build_peer_manager(10).await;
+
+        // Saturate the inbound connections.
+        let max_incoming = peer_manager.connection_limits.max_established_incoming;
+        peer_manager.established_inbound.extend(
+            (0..max_incoming).map(|id| (ConnectionId::new_unchecked(id), PeerId::random())),
+        );
+        let trusted_peer = PeerId::random();
+        assert!(peer_manager
+            .check_connection_limits(&trusted_peer, true)
+            .is_err());
+        assert!(peer_manager
+            .check_connection_limits(&trusted_peer, false)
+            .is_ok());
+
+        // A trusted peer added at runtime can connect in its reserved slot.
+        peer_manager.operator_action(&trusted_peer, OperatorAction::Protect, "test");
+        assert!(peer_manager
+            .check_connection_limits(&trusted_peer, true)
+            .is_ok());
+
+        // Once it is no longer trusted the slot is gone.
+        peer_manager.operator_action(&trusted_peer, OperatorAction::Unprotect, "test");
+        assert!(peer_manager
+            .check_connection_limits(&trusted_peer, true)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_connections_are_refused() {
+        let mut peer_manager = build_peer_manager(10).await;
+        let peer = PeerId::random();
+        peer_manager
+            .established_outbound
+            .insert(ConnectionId::new_unchecked(0), peer);
+
+        assert_eq!(
+            peer_manager
+                .check_connection_limits(&peer, true)
+                .map_err(|e| e.kind),
+            Err(LimitKind::EstablishedPerPeer)
+        );
+        assert!(peer_manager
+            .check_connection_limits(&PeerId::random(), true)
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_peer_manager_disconnects_correctly_during_heartbeat() {
         // Create 6 peers to connect to with a target of 3.
@@ -1401,10 +1644,11 @@ mod tests {
 
         peer_manager.heartbeat();
 
-        // Check that we disconnected from two peers.
+        // Check that we disconnected from two peers. The trusted peer occupies a reserved slot
+        // in addition to the target.
         // Check that one outbound-only peer was removed because it had the worst score
         // and that we did not disconnect the other outbound peer due to the minimum outbound quota.
-        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
+        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 4);
         assert!(peer_manager
             .network_globals
             .peers
@@ -1433,7 +1677,7 @@ mod tests {
             .is_connected(&trusted_peer));
 
         // Check that if we are at target number of peers, we do not disconnect any.
-        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
+        assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 4);
     }
 
     #[tokio::test]
@@ -2300,13 +2544,14 @@ mod tests {
                 // The minimum number of connected peers cannot be less than the target peer count
                 // or submitted peers.
 
-                let expected_peer_count = target_peer_count.min(peer_conditions.len());
-                // Trusted peers could make this larger however.
+                // Trusted peers occupy reserved slots in addition to the target.
                 let no_of_trusted_peers = peer_conditions
                     .iter()
                     .filter(|condition| condition.trusted)
                     .count();
-                let expected_peer_count = expected_peer_count.max(no_of_trusted_peers);
+                let expected_peer_count = target_peer_count
+                    .min(peer_conditions.len() - no_of_trusted_peers)
+                    + no_of_trusted_peers;
 
                 let target_peer_condition =
                     peer_manager.network_globals.connected_or_dialing_peers()
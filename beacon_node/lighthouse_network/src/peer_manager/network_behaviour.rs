@@ -1,22 +1,31 @@
 //! Implementation of [`NetworkBehaviour`] for the [`PeerManager`].
 
+use std::net::IpAddr;
 use std::task::{Context, Poll};
 
 use futures::StreamExt;
 use libp2p::core::{multiaddr, ConnectedPoint};
 use libp2p::identity::PeerId;
-use libp2p::swarm::behaviour::{ConnectionClosed, ConnectionEstablished, DialFailure, FromSwarm};
+use libp2p::swarm::behaviour::{
+    ConnectionClosed, ConnectionEstablished, DialFailure, FromSwarm, ListenFailure,
+};
 use libp2p::swarm::dial_opts::{DialOpts, PeerCondition};
 use libp2p::swarm::dummy::ConnectionHandler;
-use libp2p::swarm::{ConnectionId, NetworkBehaviour, PollParameters, ToSwarm};
+use libp2p::swarm::{
+    ConnectionDenied, ConnectionId, DialError, ListenError, NetworkBehaviour, PollParameters,
+    ToSwarm,
+};
+use libp2p::Multiaddr;
 use slog::{debug, error};
 use types::EthSpec;
 
 use crate::discovery::enr_ext::EnrExt;
 use crate::rpc::GoodbyeReason;
-use crate::types::SyncState;
+use crate::types::{HandshakeFailureReason, SyncState};
 use crate::{metrics, ClearDialError};
 
+use super::admission::AdmissionError;
+use super::connection_limits::{LimitExceeded, LimitKind};
 use super::peerdb::BanResult;
 use super::{ConnectingType, PeerManager, PeerManagerEvent, ReportSource};
 
@@ -124,10 +133,16 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
         match event {
             FromSwarm::ConnectionEstablished(ConnectionEstablished {
                 peer_id,
+                connection_id,
                 endpoint,
                 other_established,
                 ..
             }) => {
+                if endpoint.is_dialer() {
+                    self.established_outbound.insert(connection_id, peer_id);
+                } else {
+                    self.established_inbound.insert(connection_id, peer_id);
+                }
                 // NOTE: We still need to handle the [`ConnectionEstablished`] because the
                 // [`NetworkBehaviour::handle_established_inbound_connection`] and
                 // [`NetworkBehaviour::handle_established_outbound_connection`] are fallible. This
@@ -137,19 +152,43 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
             }
             FromSwarm::ConnectionClosed(ConnectionClosed {
                 peer_id,
+                connection_id,
                 endpoint,
-
                 remaining_established,
                 ..
-            }) => self.on_connection_closed(peer_id, endpoint, remaining_established),
+            }) => {
+                self.established_inbound.remove(&connection_id);
+                self.established_outbound.remove(&connection_id);
+                self.on_connection_closed(peer_id, endpoint, remaining_established)
+            }
             FromSwarm::DialFailure(DialFailure {
                 peer_id,
                 error,
                 connection_id: _,
             }) => {
                 debug!(self.log, "Failed to dial peer"; "peer_id"=> ?peer_id, "error" => %ClearDialError(error));
+                match error {
+                    DialError::Transport(errors) => {
+                        for (address, _) in errors {
+                            self.on_handshake_failure(address, "outbound");
+                        }
+                    }
+                    DialError::Denied { cause } if is_duplicate_connection(cause) => {
+                        self.on_duplicate_connection(peer_id.as_ref(), "outbound");
+                    }
+                    _ => {}
+                }
                 self.on_dial_failure(peer_id);
             }
+            FromSwarm::ListenFailure(ListenFailure {
+                send_back_addr,
+                error: ListenError::Transport(_),
+                ..
+            }) => self.on_handshake_failure(send_back_addr, "inbound"),
+            FromSwarm::ListenFailure(ListenFailure {
+                error: ListenError::Denied { cause },
+                ..
+            }) if is_duplicate_connection(cause) => self.on_duplicate_connection(None, "inbound"),
             FromSwarm::ExternalAddrConfirmed(_) => {
                 // TODO: we likely want to check this against our assumed external tcp
                 // address
@@ -172,27 +211,98 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
     fn handle_established_inbound_connection(
         &mut self,
         _connection_id: ConnectionId,
-        _peer: PeerId,
+        peer_id: PeerId,
         _local_addr: &libp2p::Multiaddr,
-        _remote_addr: &libp2p::Multiaddr,
+        remote_addr: &libp2p::Multiaddr,
     ) -> Result<libp2p::swarm::THandler<Self>, libp2p::swarm::ConnectionDenied> {
-        // TODO: we might want to check if we accept this peer or not in the future.
+        if let Err(e) = self.check_connection_limits(&peer_id, true) {
+            debug!(self.log, "Refusing inbound connection"; "peer_id" => %peer_id,
+                "multiaddr" => %remote_addr, "reason" => %e);
+            return Err(libp2p::swarm::ConnectionDenied::new(e));
+        }
+        if let Err(e) = self.check_admission(&peer_id, remote_addr) {
+            debug!(self.log, "Refusing inbound connection"; "peer_id" => %peer_id,
+                "multiaddr" => %remote_addr, "reason" => %e);
+            metrics::inc_counter_vec(&metrics::INBOUND_CONNECTIONS_REFUSED, &[e.as_label()]);
+            return Err(libp2p::swarm::ConnectionDenied::new(e));
+        }
         Ok(ConnectionHandler)
     }
 
     fn handle_established_outbound_connection(
         &mut self,
         _connection_id: ConnectionId,
-        _peer: PeerId,
-        _addr: &libp2p::Multiaddr,
+        peer: PeerId,
+        addr: &libp2p::Multiaddr,
         _role_override: libp2p::core::Endpoint,
     ) -> Result<libp2p::swarm::THandler<Self>, libp2p::swarm::ConnectionDenied> {
-        // TODO: we might want to check if we accept this peer or not in the future.
+        if let Err(e) = self.check_connection_limits(&peer, false) {
+            debug!(self.log, "Refusing outbound connection"; "peer_id" => %peer,
+                "multiaddr" => %addr, "reason" => %e);
+            return Err(libp2p::swarm::ConnectionDenied::new(e));
+        }
         Ok(ConnectionHandler)
     }
 }
 
 impl<TSpec: EthSpec> PeerManager<TSpec> {
+    /// Checks a new connection with `peer_id` against the established connection limits.
+    pub(super) fn check_connection_limits(
+        &self,
+        peer_id: &PeerId,
+        is_incoming: bool,
+    ) -> Result<(), LimitExceeded> {
+        let per_peer = self
+            .established_inbound
+            .values()
+            .chain(self.established_outbound.values())
+            .filter(|established| *established == peer_id)
+            .count();
+        self.connection_limits.check(
+            self.established_inbound.len(),
+            self.established_outbound.len(),
+            per_peer,
+            is_incoming,
+        )
+    }
+
+    /// Checks an inbound connection from `remote_addr` against the admission policy.
+    ///
+    /// Trusted peers are always admitted.
+    fn check_admission(
+        &self,
+        peer_id: &PeerId,
+        remote_addr: &libp2p::Multiaddr,
+    ) -> Result<(), AdmissionError> {
+        if self.admission_policy.is_disabled() {
+            return Ok(());
+        }
+        let ip = match remote_addr.iter().find_map(|protocol| match protocol {
+            multiaddr::Protocol::Ip4(ip) => Some(IpAddr::from(ip)),
+            multiaddr::Protocol::Ip6(ip) => Some(IpAddr::from(ip)),
+            _ => None,
+        }) {
+            Some(ip) => ip,
+            None => return Ok(()),
+        };
+
+        let peers = self.network_globals.peers.read();
+        if peers
+            .peer_info(peer_id)
+            .map_or(false, |info| info.is_trusted())
+        {
+            return Ok(());
+        }
+        let connected_peers = peers
+            .connected_peers()
+            .filter(|(connected_peer_id, _)| *connected_peer_id != peer_id)
+            .map(|(_, info)| info.seen_ip_addresses().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        self.admission_policy
+            .check(&ip, &connected_peers, self.target_peers)
+    }
+
     fn on_connection_established(
         &mut self,
         peer_id: PeerId,
@@ -263,17 +373,24 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             BanResult::NotBanned => {}
         }
 
+        // Close any new connections while shutting down.
+        if self.shutting_down {
+            self.disconnect_peer(peer_id, GoodbyeReason::ClientShutdown);
+            return;
+        }
+
         // Count dialing peers in the limit if the peer dialed us.
         let count_dialing = endpoint.is_listener();
+        // Peers with a future duty and trusted peers, which occupy reserved slots, are exempt
+        // from the connection limits.
+        let exempt_from_limit = {
+            let peers = self.network_globals.peers.read();
+            peers.peer_info(&peer_id).map_or(false, |peer| {
+                peer.has_future_duty() || peers.has_reserved_slot(peer)
+            })
+        };
         // Check the connection limits
-        if self.peer_limit_reached(count_dialing)
-            && self
-                .network_globals
-                .peers
-                .read()
-                .peer_info(&peer_id)
-                .map_or(true, |peer| !peer.has_future_duty())
-        {
+        if self.peer_limit_reached(count_dialing) && !exempt_from_limit {
             // Gracefully disconnect the peer.
             self.disconnect_peer(peer_id, GoodbyeReason::TooManyPeers);
             return;
@@ -353,6 +470,30 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         }
     }
 
+    /// A connection over the transport of `address` failed before it was established.
+    fn on_handshake_failure(&self, address: &Multiaddr, direction: &str) {
+        if self.metrics_enabled {
+            if let Some(transport) = transport_name(address) {
+                metrics::inc_counter_vec(
+                    &metrics::TRANSPORT_HANDSHAKE_FAILURES,
+                    &[transport, direction],
+                );
+            }
+        }
+    }
+
+    /// A connection was denied because the peer is already connected.
+    fn on_duplicate_connection(&self, peer_id: Option<&PeerId>, direction: &str) {
+        self.network_globals.record_handshake_failure(
+            peer_id,
+            HandshakeFailureReason::DuplicateConnection,
+            format!(
+                "Denied {} connection to an already connected peer",
+                direction
+            ),
+        );
+    }
+
     /// A dial attempt has failed.
     ///
     /// NOTE: It can be the case that we are dialing a peer and during the dialing process the peer
@@ -366,3 +507,24 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         }
     }
 }
+
+/// Returns `true` if a connection was denied because it would exceed the limit of connections per
+/// peer.
+fn is_duplicate_connection(cause: &ConnectionDenied) -> bool {
+    match cause.downcast_ref::<LimitExceeded>() {
+        Some(LimitExceeded {
+            kind: LimitKind::EstablishedPerPeer,
+            ..
+        }) => true,
+        Some(_) | None => false,
+    }
+}
+
+/// Returns the name of the transport used by `address`, if it is one we support.
+fn transport_name(address: &Multiaddr) -> Option<&'static str> {
+    address.iter().find_map(|protocol| match protocol {
+        multiaddr::Protocol::QuicV1 => Some("quic"),
+        multiaddr::Protocol::Tcp(_) => Some("tcp"),
+        _ => None,
+    })
+}
@@ -1,17 +1,18 @@
 use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId};
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
-use score::{PeerAction, ReportSource, Score, ScoreState};
+use score::{PeerAction, ReportSource, ReportedAction, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::net::IpAddr;
 use std::time::Instant;
 use sync_status::SyncStatus;
-use types::EthSpec;
+use types::{EthSpec, Slot};
 
 pub mod client;
 pub mod peer_info;
+pub mod rpc_stats;
 pub mod score;
 pub mod sync_status;
 
@@ -27,6 +28,9 @@ const ALLOWED_NEGATIVE_GOSSIPSUB_FACTOR: f32 = 0.1;
 /// The time we allow peers to be in the dialing state in our PeerDb before we revert them to a
 /// disconnected state.
 const DIAL_TIMEOUT: u64 = 15;
+/// Peers with a lighthouse score at or below this value have their score persisted across
+/// restarts.
+const PERSISTED_SCORE_THRESHOLD: f64 = -1.0;
 
 /// Storage of known peers, their reputation and information
 pub struct PeerDB<TSpec: EthSpec> {
@@ -136,6 +140,18 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Returns the lighthouse scores of the peers which have been penalised, so that they can be
+    /// restored after a restart.
+    pub fn penalised_peer_scores(&self) -> Vec<(PeerId, f64)> {
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, info)| {
+                let score = info.score().lighthouse_score()?;
+                (score <= PERSISTED_SCORE_THRESHOLD).then_some((*peer_id, score))
+            })
+            .collect()
+    }
+
     /// Returns the current [`BanResult`] of the peer. This doesn't check the connection state, rather the
     /// underlying score of the peer. A peer may be banned but still in the connected state
     /// temporarily.
@@ -202,6 +218,39 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             .map(|(peer_id, _)| peer_id)
     }
 
+    /// Connected or dialing peers, excluding trusted peers which occupy reserved slots.
+    pub fn connected_or_dialing_untrusted_peers(&self) -> impl Iterator<Item = &PeerId> {
+        self.peers
+            .iter()
+            .filter(|(_, info)| {
+                (info.is_connected() || info.is_dialing()) && !self.has_reserved_slot(info)
+            })
+            .map(|(peer_id, _)| peer_id)
+    }
+
+    /// Connected peers, excluding trusted peers which occupy reserved slots.
+    pub fn connected_untrusted_peer_ids(&self) -> impl Iterator<Item = &PeerId> {
+        self.peers
+            .iter()
+            .filter(|(_, info)| info.is_connected() && !self.has_reserved_slot(info))
+            .map(|(peer_id, _)| peer_id)
+    }
+
+    /// The number of trusted peers which occupy reserved slots, whether connected or not.
+    pub fn reserved_slot_count(&self) -> usize {
+        self.peers
+            .values()
+            .filter(|info| self.has_reserved_slot(info))
+            .count()
+    }
+
+    /// Returns true if the peer is trusted and therefore does not count towards the peer limits.
+    ///
+    /// When peer scoring is disabled every peer is treated as trusted, so no slots are reserved.
+    pub fn has_reserved_slot(&self, info: &PeerInfo<TSpec>) -> bool {
+        info.is_trusted() && !self.disable_peer_scoring
+    }
+
     /// Connected outbound-only peers
     pub fn connected_outbound_only_peers(&self) -> impl Iterator<Item = &PeerId> {
         self.peers
@@ -349,6 +398,30 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// TESTING ONLY. Adds a peer with an incoming connection whose ENR advertises
+    /// `earliest_available_slot`, if one is given.
+    #[doc(hidden)]
+    pub fn __add_connected_peer_testing_only(
+        &mut self,
+        earliest_available_slot: Option<Slot>,
+    ) -> PeerId {
+        use crate::discovery::enr::EARLIEST_AVAILABLE_SLOT_ENR_KEY;
+        use crate::{CombinedKeyExt, EnrExt};
+        use ssz::Encode;
+
+        let keypair = libp2p::identity::secp256k1::Keypair::generate();
+        let enr_key = discv5::enr::CombinedKey::from_secp256k1(&keypair);
+        let mut builder = discv5::enr::EnrBuilder::new("v4");
+        if let Some(slot) = earliest_available_slot {
+            builder.add_value(EARLIEST_AVAILABLE_SLOT_ENR_KEY, &slot.as_ssz_bytes());
+        }
+        let enr = builder.build(&enr_key).unwrap();
+        let peer_id = enr.peer_id();
+
+        self.connect_ingoing(&peer_id, "/ip4/0.0.0.0".parse().unwrap(), Some(enr));
+        peer_id
+    }
+
     /// Allows the sync module to update sync status' of peers. Returns None, if the peer doesn't
     /// exist and returns Some(bool) representing if the sync state was modified.
     pub fn update_sync_status(
@@ -520,14 +593,21 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         msg: &'static str,
     ) -> ScoreUpdateResult {
         metrics::inc_counter_vec(&metrics::REPORT_PEER_MSGS, &[msg]);
+        let source: &'static str = source.into();
 
         match self.peers.get_mut(peer_id) {
             Some(info) => {
                 let previous_state = info.score_state();
                 info.apply_peer_action_to_score(action);
+                info.record_action(ReportedAction {
+                    action,
+                    source,
+                    msg,
+                    time: Instant::now(),
+                });
                 metrics::inc_counter_vec(
                     &metrics::PEER_ACTION_EVENTS_PER_CLIENT,
-                    &[info.client().kind.as_ref(), action.as_ref(), source.into()],
+                    &[info.client().kind.as_ref(), action.as_ref(), source],
                 );
                 let result =
                     Self::handle_score_transition(previous_state, peer_id, info, &self.log);
@@ -580,6 +660,58 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Lifts a ban on a peer by resetting its score.
+    ///
+    /// If the peer was banned, the result contains its IP addresses which are no longer banned.
+    #[must_use = "Unbanned peers need to be handled in libp2p"]
+    pub(super) fn unban_peer(&mut self, peer_id: &PeerId) -> ScoreUpdateResult {
+        match self.peers.get_mut(peer_id) {
+            Some(info) if info.score_state() == ScoreState::Banned => info.clear_score(),
+            _ => return ScoreUpdateResult::NoAction,
+        }
+
+        self.update_connection_state(peer_id, NewConnectionState::Unbanned);
+        let seen_ip_addresses = self
+            .peers
+            .get(peer_id)
+            .map(|info| {
+                info.seen_ip_addresses()
+                    .filter(|ip| !self.is_ip_banned(ip))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        ScoreUpdateResult::Unbanned(seen_ip_addresses)
+    }
+
+    /// Restores the scores of peers persisted by a previous run. Peers which are already known are
+    /// not modified.
+    ///
+    /// Returns the number of scores restored.
+    // VISIBILITY: Only the peer manager can change the score of a peer.
+    pub(super) fn restore_scores(&mut self, scores: Vec<(PeerId, f64)>) -> usize {
+        let mut restored = 0;
+        for (peer_id, score) in scores {
+            if let Entry::Vacant(entry) = self.peers.entry(peer_id) {
+                entry.insert(PeerInfo::default()).restore_score(score);
+                restored += 1;
+            }
+        }
+        restored
+    }
+
+    /// Marks a peer as trusted or not. An unknown peer is added to the db when it becomes
+    /// trusted.
+    // VISIBILITY: Only the peer manager can change whether a peer is trusted.
+    pub(super) fn set_trusted(&mut self, peer_id: &PeerId, trusted: bool) {
+        match self.peers.get_mut(peer_id) {
+            Some(info) => info.set_trusted(trusted),
+            None if trusted => {
+                self.peers.insert(*peer_id, PeerInfo::trusted_peer_info());
+            }
+            None => {}
+        }
+    }
+
     /// Update min ttl of a peer.
     // VISIBILITY: Only the peer manager can update the min_ttl
     pub(super) fn update_min_ttl(&mut self, peer_id: &PeerId, min_ttl: Instant) {
@@ -2027,4 +2159,111 @@ mod tests {
             Score::max_score().score()
         );
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_unban_and_protect_peer() {
+        let mut pdb = get_db();
+        let peer = PeerId::random();
+        pdb.connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        // Unbanning a peer which isn't banned has no effect.
+        assert!(matches!(pdb.unban_peer(&peer), ScoreUpdateResult::NoAction));
+
+        let _ = pdb.report_peer(&peer, PeerAction::Fatal, ReportSource::Operator, "");
+        pdb.inject_disconnect(&peer);
+        assert!(pdb.ban_status(&peer).is_banned());
+
+        assert!(matches!(
+            pdb.unban_peer(&peer),
+            ScoreUpdateResult::Unbanned(_)
+        ));
+        assert!(!pdb.ban_status(&peer).is_banned());
+        assert_eq!(pdb.score(&peer), Score::default().score());
+
+        // A protected peer can't be scored down until its protection is removed.
+        pdb.set_trusted(&peer, true);
+        add_score(&mut pdb, &peer, -50.0);
+        assert_eq!(pdb.score(&peer), Score::max_score().score());
+
+        pdb.set_trusted(&peer, false);
+        assert!(!pdb.peer_info(&peer).unwrap().is_trusted());
+        assert_eq!(pdb.score(&peer), Score::default().score());
+    }
+
+    #[test]
+    fn test_trusted_peers_have_reserved_slots() {
+        let mut pdb = get_db();
+        let peer = PeerId::random();
+        let trusted_peer = PeerId::random();
+
+        // Unknown peers are added when they become trusted.
+        pdb.set_trusted(&trusted_peer, true);
+        assert!(pdb.peer_info(&trusted_peer).unwrap().is_trusted());
+
+        pdb.connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.connect_ingoing(&trusted_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        assert_eq!(pdb.connected_or_dialing_peers().count(), 2);
+        assert_eq!(
+            pdb.connected_or_dialing_untrusted_peers()
+                .collect::<Vec<_>>(),
+            vec![&peer]
+        );
+        assert_eq!(
+            pdb.connected_untrusted_peer_ids().collect::<Vec<_>>(),
+            vec![&peer]
+        );
+        assert_eq!(pdb.reserved_slot_count(), 1);
+
+        // Without peer scoring every peer is trusted, so none have reserved slots.
+        let log = build_log(slog::Level::Debug, false);
+        let mut pdb: PeerDB<M> = PeerDB::new(vec![trusted_peer], true, &log);
+        pdb.connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.connect_ingoing(&trusted_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        assert_eq!(pdb.connected_untrusted_peer_ids().count(), 2);
+        assert_eq!(pdb.reserved_slot_count(), 0);
+    }
+
+    #[test]
+    fn test_can_serve_slot() {
+        let mut pdb = get_db();
+        let no_eas_peer = pdb.__add_connected_peer_testing_only(None);
+        let backfilling_peer = pdb.__add_connected_peer_testing_only(Some(Slot::new(64)));
+
+        // Peers that do not advertise an earliest available slot are assumed to serve all blocks.
+        let info = pdb.peer_info(&no_eas_peer).unwrap();
+        assert!(info.can_serve_slot(Slot::new(0)));
+
+        let info = pdb.peer_info(&backfilling_peer).unwrap();
+        assert!(!info.can_serve_slot(Slot::new(0)));
+        assert!(!info.can_serve_slot(Slot::new(63)));
+        assert!(info.can_serve_slot(Slot::new(64)));
+        assert!(info.can_serve_slot(Slot::new(100)));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_restore_penalised_peer_scores() {
+        let mut pdb = get_db();
+        let penalised_peer = PeerId::random();
+        let good_peer = PeerId::random();
+
+        pdb.connect_ingoing(&penalised_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.connect_ingoing(&good_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        add_score(&mut pdb, &penalised_peer, -20.0);
+        add_score(&mut pdb, &good_peer, 5.0);
+
+        let scores = pdb.penalised_peer_scores();
+        assert_eq!(scores, vec![(penalised_peer, -20.0)]);
+
+        // Scores are restored into a fresh db, but do not override known peers.
+        let mut pdb = get_db();
+        pdb.connect_ingoing(&good_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        assert_eq!(
+            pdb.restore_scores(vec![(penalised_peer, -20.0), (good_peer, -20.0)]),
+            1
+        );
+        assert_eq!(pdb.score(&penalised_peer), -20.0);
+        assert_eq!(pdb.score(&good_peer), 0.0);
+    }
 }
@@ -1,5 +1,6 @@
 use super::client::Client;
-use super::score::{PeerAction, Score, ScoreState};
+use super::rpc_stats::RpcStats;
+use super::score::{PeerAction, ReportedAction, Score, ScoreState};
 use super::sync_status::SyncStatus;
 use crate::discovery::Eth2Enr;
 use crate::{rpc::MetaData, types::Subnet};
@@ -9,13 +10,16 @@ use serde::{
     ser::{SerializeStruct, Serializer},
     Serialize,
 };
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::net::IpAddr;
 use std::time::Instant;
 use strum::AsRefStr;
-use types::EthSpec;
+use types::{EthSpec, Slot};
 use PeerConnectionStatus::*;
 
+/// The number of recently reported `PeerAction`s kept for each peer.
+const MAX_RECENT_ACTIONS: usize = 16;
+
 /// Information about a given connected peer.
 #[derive(Clone, Debug, Serialize)]
 #[serde(bound = "T: EthSpec")]
@@ -51,6 +55,13 @@ pub struct PeerInfo<T: EthSpec> {
     connection_direction: Option<ConnectionDirection>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
+    /// Statistics of the RPC requests exchanged with the peer, served by the
+    /// `lighthouse/peers/{peer_id}/stats` endpoint rather than with the rest of the peer info.
+    #[serde(skip)]
+    rpc_stats: RpcStats,
+    /// The most recent `PeerAction`s reported against the peer, oldest first.
+    #[serde(skip)]
+    recent_actions: VecDeque<ReportedAction>,
 }
 
 impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
@@ -68,6 +79,8 @@ impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
             is_trusted: false,
             connection_direction: None,
             enr: None,
+            rpc_stats: RpcStats::default(),
+            recent_actions: VecDeque::new(),
         }
     }
 }
@@ -140,6 +153,15 @@ impl<T: EthSpec> PeerInfo<T> {
         self.enr.as_ref()
     }
 
+    /// Returns false if the peer's ENR advertises that it cannot serve blocks at `slot`. Peers
+    /// that do not advertise their earliest available slot are assumed to serve all blocks.
+    pub fn can_serve_slot(&self, slot: Slot) -> bool {
+        self.enr
+            .as_ref()
+            .and_then(|enr| enr.earliest_available_slot().ok())
+            .map_or(true, |earliest_slot| earliest_slot <= slot)
+    }
+
     /// An iterator over all the subnets this peer is subscribed to.
     pub fn subnets(&self) -> impl Iterator<Item = &Subnet> {
         self.subnets.iter()
@@ -244,6 +266,16 @@ impl<T: EthSpec> PeerInfo<T> {
         })
     }
 
+    /// Returns the RPC statistics of the peer.
+    pub fn rpc_stats(&self) -> &RpcStats {
+        &self.rpc_stats
+    }
+
+    /// Returns the most recent `PeerAction`s reported against the peer, oldest first.
+    pub fn recent_actions(&self) -> &VecDeque<ReportedAction> {
+        &self.recent_actions
+    }
+
     /// Returns the connection status of the peer.
     pub fn connection_status(&self) -> &PeerConnectionStatus {
         &self.connection_status
@@ -393,17 +425,60 @@ impl<T: EthSpec> PeerInfo<T> {
         }
     }
 
+    /// Records a `PeerAction` reported against the peer.
+    pub(in crate::peer_manager) fn record_action(&mut self, reported_action: ReportedAction) {
+        if self.recent_actions.len() >= MAX_RECENT_ACTIONS {
+            self.recent_actions.pop_front();
+        }
+        self.recent_actions.push_back(reported_action);
+    }
+
     /// Updates the gossipsub score with a new score. Optionally ignore the gossipsub score.
     pub(super) fn update_gossipsub_score(&mut self, new_score: f64, ignore: bool) {
         self.score.update_gossipsub_score(new_score, ignore);
     }
 
+    /// Marks the peer as trusted or not. Trusted peers have the maximum score, while the score of
+    /// a peer which is no longer trusted starts afresh.
+    // VISIBILITY: Only the peer manager can change whether a peer is trusted.
+    pub(in crate::peer_manager) fn set_trusted(&mut self, trusted: bool) {
+        if trusted != self.is_trusted {
+            self.is_trusted = trusted;
+            self.score = if trusted {
+                Score::max_score()
+            } else {
+                Score::default()
+            };
+        }
+    }
+
+    /// Restores the score of a non-trusted peer persisted by a previous run.
+    // VISIBILITY: The peer manager is able to modify the score of a peer.
+    pub(in crate::peer_manager) fn restore_score(&mut self, score: f64) {
+        if !self.is_trusted {
+            self.score.restore_lighthouse_score(score)
+        }
+    }
+
+    /// Resets the score of a non-trusted peer.
+    // VISIBILITY: Only the peer manager can reset the score of a peer.
+    pub(in crate::peer_manager) fn clear_score(&mut self) {
+        if !self.is_trusted {
+            self.score = Score::default();
+        }
+    }
+
     #[cfg(test)]
     /// Resets the peers score.
     pub fn reset_score(&mut self) {
         self.score.test_reset();
     }
 
+    /// Returns a mutable reference to the RPC statistics of the peer.
+    pub(in crate::peer_manager) fn rpc_stats_mut(&mut self) -> &mut RpcStats {
+        &mut self.rpc_stats
+    }
+
     /// Modifies the status to Dialing
     /// Returns an error if the current state is unexpected.
     pub(super) fn set_dialing_peer(&mut self) -> Result<(), &'static str> {
@@ -483,7 +558,8 @@ pub enum ConnectionDirection {
 }
 
 /// Connection Status of the peer.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
 pub enum PeerConnectionStatus {
     /// The peer is connected.
     Connected {
@@ -0,0 +1,149 @@
+//! Per-peer statistics of the RPC requests and responses exchanged with a peer.
+
+use crate::rpc::Protocol;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// The maximum number of outstanding requests tracked per protocol for latency measurements.
+const MAX_PENDING_REQUESTS: usize = 64;
+
+/// RPC counters for a single protocol.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolStats {
+    /// Requests we have sent to the peer.
+    pub requests_sent: u64,
+    /// Requests the peer has sent to us.
+    pub requests_received: u64,
+    /// Response chunks we have sent to the peer.
+    pub responses_sent: u64,
+    /// Response chunks the peer has sent to us.
+    pub responses_received: u64,
+    /// Errors on requests in either direction.
+    pub errors: u64,
+    /// The SSZ-encoded size of the response chunks sent to the peer.
+    pub bytes_sent: u64,
+    /// The SSZ-encoded size of the response chunks received from the peer.
+    pub bytes_received: u64,
+    /// The average time between sending a request and its response completing, in milliseconds.
+    pub average_latency_ms: Option<u64>,
+    #[serde(skip)]
+    total_latency: Duration,
+    #[serde(skip)]
+    latency_samples: u32,
+}
+
+impl ProtocolStats {
+    fn observe_latency(&mut self, latency: Duration) {
+        self.total_latency += latency;
+        self.latency_samples = self.latency_samples.saturating_add(1);
+        self.average_latency_ms =
+            Some((self.total_latency / self.latency_samples).as_millis() as u64);
+    }
+}
+
+/// RPC statistics for a peer, keyed by protocol name.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RpcStats {
+    protocols: HashMap<String, ProtocolStats>,
+    /// The time at which each outstanding request was sent, in the order they were sent.
+    ///
+    /// Responses are matched to requests in order, so latencies are approximate if several
+    /// requests of the same protocol are outstanding at once.
+    #[serde(skip)]
+    pending_requests: HashMap<Protocol, VecDeque<Instant>>,
+}
+
+impl RpcStats {
+    /// Returns the statistics of each protocol which has been used with the peer.
+    pub fn protocols(&self) -> &HashMap<String, ProtocolStats> {
+        &self.protocols
+    }
+
+    fn protocol_mut(&mut self, protocol: Protocol) -> &mut ProtocolStats {
+        self.protocols.entry(protocol.to_string()).or_default()
+    }
+
+    pub fn on_request_sent(&mut self, protocol: Protocol) {
+        self.protocol_mut(protocol).requests_sent += 1;
+        let pending = self.pending_requests.entry(protocol).or_default();
+        if pending.len() >= MAX_PENDING_REQUESTS {
+            pending.pop_front();
+        }
+        pending.push_back(Instant::now());
+    }
+
+    pub fn on_request_received(&mut self, protocol: Protocol) {
+        self.protocol_mut(protocol).requests_received += 1;
+    }
+
+    pub fn on_response_sent(&mut self, protocol: Protocol, bytes: usize) {
+        let stats = self.protocol_mut(protocol);
+        stats.responses_sent += 1;
+        stats.bytes_sent += bytes as u64;
+    }
+
+    pub fn on_response_received(&mut self, protocol: Protocol, bytes: usize) {
+        let stats = self.protocol_mut(protocol);
+        stats.responses_received += 1;
+        stats.bytes_received += bytes as u64;
+    }
+
+    /// Records the completion of the oldest outstanding request of `protocol`.
+    pub fn on_request_completed(&mut self, protocol: Protocol) {
+        if let Some(sent) = self
+            .pending_requests
+            .get_mut(&protocol)
+            .and_then(VecDeque::pop_front)
+        {
+            self.protocol_mut(protocol).observe_latency(sent.elapsed());
+        }
+    }
+
+    /// Records an error on a request. If `outbound`, the request was sent by us and is no longer
+    /// outstanding.
+    pub fn on_error(&mut self, protocol: Protocol, outbound: bool) {
+        self.protocol_mut(protocol).errors += 1;
+        if outbound {
+            if let Some(pending) = self.pending_requests.get_mut(&protocol) {
+                pending.pop_front();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpc_stats() {
+        let mut stats = RpcStats::default();
+
+        stats.on_request_sent(Protocol::BlocksByRange);
+        stats.on_request_sent(Protocol::BlocksByRange);
+        stats.on_response_received(Protocol::BlocksByRange, 100);
+        stats.on_response_received(Protocol::BlocksByRange, 50);
+        stats.on_request_completed(Protocol::BlocksByRange);
+        stats.on_error(Protocol::BlocksByRange, true);
+        // Nothing is outstanding, so no latency is recorded.
+        stats.on_request_completed(Protocol::BlocksByRange);
+
+        stats.on_request_received(Protocol::Status);
+        stats.on_response_sent(Protocol::Status, 84);
+
+        let blocks_by_range = &stats.protocols()["beacon_blocks_by_range"];
+        assert_eq!(blocks_by_range.requests_sent, 2);
+        assert_eq!(blocks_by_range.responses_received, 2);
+        assert_eq!(blocks_by_range.bytes_received, 150);
+        assert_eq!(blocks_by_range.errors, 1);
+        assert_eq!(blocks_by_range.latency_samples, 1);
+        assert!(blocks_by_range.average_latency_ms.is_some());
+
+        let status = &stats.protocols()["status"];
+        assert_eq!(status.requests_received, 1);
+        assert_eq!(status.responses_sent, 1);
+        assert_eq!(status.bytes_sent, 84);
+        assert_eq!(status.average_latency_ms, None);
+    }
+}
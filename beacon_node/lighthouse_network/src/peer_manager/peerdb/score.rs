@@ -73,6 +73,7 @@ pub enum ReportSource {
     Processor,
     SyncService,
     PeerManager,
+    Operator,
 }
 
 impl From<ReportSource> for &'static str {
@@ -83,6 +84,7 @@ impl From<ReportSource> for &'static str {
             ReportSource::Processor => "processor",
             ReportSource::SyncService => "sync",
             ReportSource::PeerManager => "peer_manager",
+            ReportSource::Operator => "operator",
         }
     }
 }
@@ -98,6 +100,16 @@ impl std::fmt::Display for PeerAction {
     }
 }
 
+/// A `PeerAction` that was reported against a peer.
+#[derive(Debug, Clone)]
+pub struct ReportedAction {
+    pub action: PeerAction,
+    /// The `ReportSource` of the report.
+    pub source: &'static str,
+    pub msg: &'static str,
+    pub time: Instant,
+}
+
 /// The expected state of the peer given the peer's score.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub(crate) enum ScoreState {
@@ -199,6 +211,13 @@ impl RealScore {
         }
     }
 
+    /// Sets the lighthouse score to one persisted by a previous run.
+    ///
+    /// Bans are not persisted, so the restored score is kept above the ban threshold.
+    pub fn restore_lighthouse_score(&mut self, score: f64) {
+        self.set_lighthouse_score(score.clamp(MIN_SCORE_BEFORE_BAN + 1.0, MAX_SCORE));
+    }
+
     /// Add an f64 to the score abiding by the limits.
     #[cfg(test)]
     pub fn test_add(&mut self, score: f64) {
@@ -259,6 +278,23 @@ impl RealScore {
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.gossipsub_score >= 0.0
     }
+
+    pub fn gossipsub_score(&self) -> f64 {
+        self.gossipsub_score
+    }
+
+    pub fn lighthouse_score(&self) -> f64 {
+        self.lighthouse_score
+    }
+
+    pub fn ignores_negative_gossipsub_score(&self) -> bool {
+        self.ignore_negative_gossipsub_score
+    }
+
+    /// Returns the time remaining before a banned score starts to decay.
+    pub fn decay_delay(&self) -> Option<Duration> {
+        self.last_updated.checked_duration_since(Instant::now())
+    }
 }
 
 #[derive(PartialEq, Clone, Debug, Serialize)]
@@ -290,6 +326,7 @@ macro_rules! apply {
 apply!(apply_peer_action, peer_action: PeerAction);
 apply!(update);
 apply!(update_gossipsub_score, new_score: f64, ignore: bool);
+apply!(restore_lighthouse_score, score: f64);
 #[cfg(test)]
 apply!(test_add, score: f64);
 #[cfg(test)]
@@ -324,6 +361,43 @@ impl Score {
             Self::Real(score) => score.is_good_gossipsub_peer(),
         }
     }
+
+    /// Returns the last known gossipsub score, or `None` for trusted peers.
+    pub fn gossipsub_score(&self) -> Option<f64> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some(score.gossipsub_score()),
+        }
+    }
+
+    /// Returns the score accumulated from `PeerAction`s, or `None` for trusted peers.
+    pub fn lighthouse_score(&self) -> Option<f64> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => Some(score.lighthouse_score()),
+        }
+    }
+
+    /// Returns true if a negative gossipsub score is currently being ignored.
+    pub fn ignores_negative_gossipsub_score(&self) -> bool {
+        match self {
+            Self::Max => false,
+            Self::Real(score) => score.ignores_negative_gossipsub_score(),
+        }
+    }
+
+    /// Returns the time remaining before a banned score starts to decay.
+    pub fn decay_delay(&self) -> Option<Duration> {
+        match self {
+            Self::Max => None,
+            Self::Real(score) => score.decay_delay(),
+        }
+    }
+
+    /// Returns the name of the expected state of the peer given it's score.
+    pub fn state_name(&self) -> String {
+        self.state().to_string()
+    }
 }
 
 impl Eq for Score {}
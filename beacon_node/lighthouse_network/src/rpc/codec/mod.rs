@@ -1,8 +1,10 @@
 pub(crate) mod base;
 pub(crate) mod ssz_snappy;
+pub(crate) mod ssz_zstd;
 
 use self::base::{BaseInboundCodec, BaseOutboundCodec};
 use self::ssz_snappy::{SSZSnappyInboundCodec, SSZSnappyOutboundCodec};
+use self::ssz_zstd::{SSZZstdInboundCodec, SSZZstdOutboundCodec};
 use crate::rpc::protocol::RPCError;
 use crate::rpc::{InboundRequest, OutboundRequest, RPCCodedResponse};
 use libp2p::bytes::BytesMut;
@@ -12,10 +14,12 @@ use types::EthSpec;
 // Known types of codecs
 pub enum InboundCodec<TSpec: EthSpec> {
     SSZSnappy(BaseInboundCodec<SSZSnappyInboundCodec<TSpec>, TSpec>),
+    SSZZstd(BaseInboundCodec<SSZZstdInboundCodec<TSpec>, TSpec>),
 }
 
 pub enum OutboundCodec<TSpec: EthSpec> {
     SSZSnappy(BaseOutboundCodec<SSZSnappyOutboundCodec<TSpec>, TSpec>),
+    SSZZstd(BaseOutboundCodec<SSZZstdOutboundCodec<TSpec>, TSpec>),
 }
 
 impl<T: EthSpec> Encoder<RPCCodedResponse<T>> for InboundCodec<T> {
@@ -24,6 +28,7 @@ impl<T: EthSpec> Encoder<RPCCodedResponse<T>> for InboundCodec<T> {
     fn encode(&mut self, item: RPCCodedResponse<T>, dst: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
             InboundCodec::SSZSnappy(codec) => codec.encode(item, dst),
+            InboundCodec::SSZZstd(codec) => codec.encode(item, dst),
         }
     }
 }
@@ -35,6 +40,7 @@ impl<TSpec: EthSpec> Decoder for InboundCodec<TSpec> {
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         match self {
             InboundCodec::SSZSnappy(codec) => codec.decode(src),
+            InboundCodec::SSZZstd(codec) => codec.decode(src),
         }
     }
 }
@@ -49,6 +55,7 @@ impl<TSpec: EthSpec> Encoder<OutboundRequest<TSpec>> for OutboundCodec<TSpec> {
     ) -> Result<(), Self::Error> {
         match self {
             OutboundCodec::SSZSnappy(codec) => codec.encode(item, dst),
+            OutboundCodec::SSZZstd(codec) => codec.encode(item, dst),
         }
     }
 }
@@ -60,6 +67,7 @@ impl<T: EthSpec> Decoder for OutboundCodec<T> {
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         match self {
             OutboundCodec::SSZSnappy(codec) => codec.decode(src),
+            OutboundCodec::SSZZstd(codec) => codec.decode(src),
         }
     }
 }
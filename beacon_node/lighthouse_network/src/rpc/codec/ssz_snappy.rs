@@ -22,7 +22,7 @@ use types::{
 };
 use unsigned_varint::codec::Uvi;
 
-const CONTEXT_BYTES_LEN: usize = 4;
+pub(super) const CONTEXT_BYTES_LEN: usize = 4;
 
 /* Inbound Codec */
 
@@ -357,7 +357,7 @@ impl<TSpec: EthSpec> OutboundCodec<OutboundRequest<TSpec>> for SSZSnappyOutbound
 /// Handle errors that we get from decoding an RPC message from the stream.
 /// `num_bytes_read` is the number of bytes the snappy decoder has read from the underlying stream.
 /// `max_compressed_len` is the maximum compressed size for a given uncompressed size.
-fn handle_error<T>(
+pub(super) fn handle_error<T>(
     err: std::io::Error,
     num_bytes: u64,
     max_compressed_len: u64,
@@ -382,7 +382,7 @@ fn handle_error<T>(
 
 /// Returns `Some(context_bytes)` for encoding RPC responses that require context bytes.
 /// Returns `None` when context bytes are not required.
-fn context_bytes<T: EthSpec>(
+pub(super) fn context_bytes<T: EthSpec>(
     protocol: &ProtocolId,
     fork_context: &ForkContext,
     resp: &RPCCodedResponse<T>,
@@ -422,7 +422,7 @@ fn context_bytes<T: EthSpec>(
 /// Returns `Ok(Some(length))` by decoding the bytes if required.
 /// Returns `Ok(None)` if more bytes are needed to decode the length-prefix.
 /// Returns an `RPCError` for a decoding error.
-fn handle_length(
+pub(super) fn handle_length(
     uvi_codec: &mut Uvi<usize>,
     len: &mut Option<usize>,
     bytes: &mut BytesMut,
@@ -445,7 +445,7 @@ fn handle_length(
 /// Decodes an `InboundRequest` from the byte stream.
 /// `decoded_buffer` should be an ssz-encoded bytestream with
 // length = length-prefix received in the beginning of the stream.
-fn handle_rpc_request<T: EthSpec>(
+pub(super) fn handle_rpc_request<T: EthSpec>(
     versioned_protocol: SupportedProtocol,
     decoded_buffer: &[u8],
 ) -> Result<Option<InboundRequest<T>>, RPCError> {
@@ -507,7 +507,7 @@ fn handle_rpc_request<T: EthSpec>(
 ///
 /// For BlocksByRange/BlocksByRoot reponses, decodes the appropriate response
 /// according to the received `ForkName`.
-fn handle_rpc_response<T: EthSpec>(
+pub(super) fn handle_rpc_response<T: EthSpec>(
     versioned_protocol: SupportedProtocol,
     decoded_buffer: &[u8],
     fork_name: Option<ForkName>,
@@ -590,7 +590,7 @@ fn handle_rpc_response<T: EthSpec>(
 }
 
 /// Takes the context bytes and a fork_context and returns the corresponding fork_name.
-fn context_bytes_to_fork_name(
+pub(super) fn context_bytes_to_fork_name(
     context_bytes: [u8; CONTEXT_BYTES_LEN],
     fork_context: Arc<ForkContext>,
 ) -> Result<ForkName, RPCError> {
@@ -0,0 +1,468 @@
+//! The `ssz_zstd` encoding.
+//!
+//! This is a Lighthouse extension to the `ssz_snappy` encoding of the specification. Messages are
+//! framed identically, with the length prefix of the uncompressed bytes followed by a single zstd
+//! frame in place of the snappy frames. It is only offered for the protocols that carry blocks,
+//! where the better compression ratio of zstd is worth the extra CPU time.
+use super::ssz_snappy::{
+    context_bytes, context_bytes_to_fork_name, handle_error, handle_length, handle_rpc_request,
+    handle_rpc_response, CONTEXT_BYTES_LEN,
+};
+use crate::rpc::methods::*;
+use crate::rpc::{
+    codec::base::OutboundCodec,
+    protocol::{Encoding, ProtocolId, RPCError, SupportedProtocol, ERROR_TYPE_MAX, ERROR_TYPE_MIN},
+};
+use crate::rpc::{InboundRequest, OutboundRequest, RPCCodedResponse, RPCResponse};
+use libp2p::bytes::BytesMut;
+use ssz::{Decode, Encode};
+use ssz_types::VariableList;
+use std::io::{Cursor, Read};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio_util::codec::{Decoder, Encoder};
+use types::{EthSpec, ForkContext, ForkName};
+use unsigned_varint::codec::Uvi;
+
+/// The compression level used for outgoing messages. This is the zstd default, which compresses
+/// blocks considerably better than snappy whilst remaining cheap enough to serve sync requests.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// The largest window a peer may require us to allocate when decompressing. This comfortably
+/// covers the maximum RPC message size.
+const MAX_WINDOW_LOG: u32 = 24;
+
+/* Inbound Codec */
+
+pub struct SSZZstdInboundCodec<TSpec: EthSpec> {
+    protocol: ProtocolId,
+    inner: Uvi<usize>,
+    len: Option<usize>,
+    /// Maximum bytes that can be sent in one req/resp chunked responses.
+    max_packet_size: usize,
+    fork_context: Arc<ForkContext>,
+    phantom: PhantomData<TSpec>,
+}
+
+impl<T: EthSpec> SSZZstdInboundCodec<T> {
+    pub fn new(
+        protocol: ProtocolId,
+        max_packet_size: usize,
+        fork_context: Arc<ForkContext>,
+    ) -> Self {
+        // this encoding only applies to ssz_zstd.
+        debug_assert_eq!(protocol.encoding, Encoding::SSZZstd);
+
+        SSZZstdInboundCodec {
+            inner: Uvi::default(),
+            protocol,
+            len: None,
+            phantom: PhantomData,
+            fork_context,
+            max_packet_size,
+        }
+    }
+}
+
+// Encoder for inbound streams: Encodes RPC Responses sent to peers.
+impl<TSpec: EthSpec> Encoder<RPCCodedResponse<TSpec>> for SSZZstdInboundCodec<TSpec> {
+    type Error = RPCError;
+
+    fn encode(
+        &mut self,
+        item: RPCCodedResponse<TSpec>,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        let bytes = match &item {
+            RPCCodedResponse::Success(resp) => match &resp {
+                RPCResponse::Status(res) => res.as_ssz_bytes(),
+                RPCResponse::BlocksByRange(res) => res.as_ssz_bytes(),
+                RPCResponse::BlocksByRoot(res) => res.as_ssz_bytes(),
+                RPCResponse::LightClientBootstrap(res) => res.as_ssz_bytes(),
+                RPCResponse::Pong(res) => res.data.as_ssz_bytes(),
+                RPCResponse::MetaData(res) => match self.protocol.versioned_protocol {
+                    SupportedProtocol::MetaDataV1 => res.metadata_v1().as_ssz_bytes(),
+                    SupportedProtocol::MetaDataV2 => res.metadata_v2().as_ssz_bytes(),
+                    _ => unreachable!(
+                        "We only send metadata responses on negotiating metadata requests"
+                    ),
+                },
+            },
+            RPCCodedResponse::Error(_, err) => err.as_ssz_bytes(),
+            RPCCodedResponse::StreamTermination(_) => {
+                unreachable!("Code error - attempting to encode a stream termination")
+            }
+        };
+        // SSZ encoded bytes should be within `max_packet_size`
+        if bytes.len() > self.max_packet_size {
+            return Err(RPCError::InternalError(
+                "attempting to encode data > max_packet_size",
+            ));
+        }
+
+        // Add context bytes if required
+        if let Some(ref context_bytes) = context_bytes(&self.protocol, &self.fork_context, &item) {
+            dst.extend_from_slice(context_bytes);
+        }
+
+        encode_frame(&mut self.inner, &bytes, dst)
+    }
+}
+
+// Decoder for inbound streams: Decodes RPC requests from peers
+impl<TSpec: EthSpec> Decoder for SSZZstdInboundCodec<TSpec> {
+    type Item = InboundRequest<TSpec>;
+    type Error = RPCError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if self.protocol.versioned_protocol == SupportedProtocol::MetaDataV1 {
+            return Ok(Some(InboundRequest::MetaData(MetadataRequest::new_v1())));
+        }
+        if self.protocol.versioned_protocol == SupportedProtocol::MetaDataV2 {
+            return Ok(Some(InboundRequest::MetaData(MetadataRequest::new_v2())));
+        }
+        let length = match handle_length(&mut self.inner, &mut self.len, src)? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        let ssz_limits = self.protocol.rpc_request_limits();
+        if ssz_limits.is_out_of_bounds(length, self.max_packet_size) {
+            return Err(RPCError::InvalidData(format!(
+                "RPC request length for protocol {:?} is out of bounds, length {}",
+                self.protocol.versioned_protocol, length
+            )));
+        }
+
+        match decode_frame(src, length)? {
+            Some(decoded_buffer) => {
+                self.len = None;
+                handle_rpc_request(self.protocol.versioned_protocol, &decoded_buffer)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/* Outbound Codec: Codec for initiating RPC requests */
+pub struct SSZZstdOutboundCodec<TSpec: EthSpec> {
+    inner: Uvi<usize>,
+    len: Option<usize>,
+    protocol: ProtocolId,
+    /// Maximum bytes that can be sent in one req/resp chunked responses.
+    max_packet_size: usize,
+    /// The fork name corresponding to the received context bytes.
+    fork_name: Option<ForkName>,
+    fork_context: Arc<ForkContext>,
+    phantom: PhantomData<TSpec>,
+}
+
+impl<TSpec: EthSpec> SSZZstdOutboundCodec<TSpec> {
+    pub fn new(
+        protocol: ProtocolId,
+        max_packet_size: usize,
+        fork_context: Arc<ForkContext>,
+    ) -> Self {
+        // this encoding only applies to ssz_zstd.
+        debug_assert_eq!(protocol.encoding, Encoding::SSZZstd);
+
+        SSZZstdOutboundCodec {
+            inner: Uvi::default(),
+            protocol,
+            max_packet_size,
+            len: None,
+            fork_name: None,
+            fork_context,
+            phantom: PhantomData,
+        }
+    }
+}
+
+// Encoder for outbound streams: Encodes RPC Requests to peers
+impl<TSpec: EthSpec> Encoder<OutboundRequest<TSpec>> for SSZZstdOutboundCodec<TSpec> {
+    type Error = RPCError;
+
+    fn encode(
+        &mut self,
+        item: OutboundRequest<TSpec>,
+        dst: &mut BytesMut,
+    ) -> Result<(), Self::Error> {
+        let bytes = match item {
+            OutboundRequest::Status(req) => req.as_ssz_bytes(),
+            OutboundRequest::Goodbye(req) => req.as_ssz_bytes(),
+            OutboundRequest::BlocksByRange(r) => match r {
+                OldBlocksByRangeRequest::V1(req) => req.as_ssz_bytes(),
+                OldBlocksByRangeRequest::V2(req) => req.as_ssz_bytes(),
+            },
+            OutboundRequest::BlocksByRoot(r) => match r {
+                BlocksByRootRequest::V1(req) => req.block_roots.as_ssz_bytes(),
+                BlocksByRootRequest::V2(req) => req.block_roots.as_ssz_bytes(),
+            },
+            OutboundRequest::Ping(req) => req.as_ssz_bytes(),
+            OutboundRequest::MetaData(_) => return Ok(()), // no metadata to encode
+        };
+        // SSZ encoded bytes should be within `max_packet_size`
+        if bytes.len() > self.max_packet_size {
+            return Err(RPCError::InternalError(
+                "attempting to encode data > max_packet_size",
+            ));
+        }
+
+        encode_frame(&mut self.inner, &bytes, dst)
+    }
+}
+
+// Decoder for outbound streams: Decodes RPC responses from peers.
+impl<TSpec: EthSpec> Decoder for SSZZstdOutboundCodec<TSpec> {
+    type Item = RPCResponse<TSpec>;
+    type Error = RPCError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // Read the context bytes if required
+        if self.protocol.has_context_bytes() && self.fork_name.is_none() {
+            if src.len() >= CONTEXT_BYTES_LEN {
+                let context_bytes = src.split_to(CONTEXT_BYTES_LEN);
+                let mut result = [0; CONTEXT_BYTES_LEN];
+                result.copy_from_slice(context_bytes.as_ref());
+                self.fork_name = Some(context_bytes_to_fork_name(
+                    result,
+                    self.fork_context.clone(),
+                )?);
+            } else {
+                return Ok(None);
+            }
+        }
+        let length = match handle_length(&mut self.inner, &mut self.len, src)? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        let ssz_limits = self
+            .protocol
+            .rpc_response_limits::<TSpec>(&self.fork_context);
+        if ssz_limits.is_out_of_bounds(length, self.max_packet_size) {
+            return Err(RPCError::InvalidData(format!(
+                "RPC response length is out of bounds, length {}",
+                length
+            )));
+        }
+
+        match decode_frame(src, length)? {
+            Some(decoded_buffer) => {
+                self.len = None;
+                // Safe to `take` from `self.fork_name` as we have all the bytes we need to
+                // decode an ssz object at this point.
+                let fork_name = self.fork_name.take();
+                handle_rpc_response(self.protocol.versioned_protocol, &decoded_buffer, fork_name)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl<TSpec: EthSpec> OutboundCodec<OutboundRequest<TSpec>> for SSZZstdOutboundCodec<TSpec> {
+    type CodecErrorType = ErrorType;
+
+    fn decode_error(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> Result<Option<Self::CodecErrorType>, RPCError> {
+        let length = match handle_length(&mut self.inner, &mut self.len, src)? {
+            Some(len) => len,
+            None => return Ok(None),
+        };
+
+        if length > self.max_packet_size || length > *ERROR_TYPE_MAX || length < *ERROR_TYPE_MIN {
+            return Err(RPCError::InvalidData(format!(
+                "RPC Error length is out of bounds, length {}",
+                length
+            )));
+        }
+
+        match decode_frame(src, length)? {
+            Some(decoded_buffer) => {
+                self.len = None;
+                Ok(Some(ErrorType(VariableList::from_ssz_bytes(
+                    &decoded_buffer,
+                )?)))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Writes the length prefix of `bytes` followed by `bytes` compressed as a single zstd frame.
+fn encode_frame(
+    uvi_codec: &mut Uvi<usize>,
+    bytes: &[u8],
+    dst: &mut BytesMut,
+) -> Result<(), RPCError> {
+    // Inserts the length prefix of the uncompressed bytes into dst
+    // encoded as a unsigned varint
+    uvi_codec.encode(bytes.len(), dst).map_err(RPCError::from)?;
+
+    let compressed = zstd::bulk::compress(bytes, COMPRESSION_LEVEL).map_err(RPCError::from)?;
+    dst.extend_from_slice(&compressed);
+    Ok(())
+}
+
+/// Decompresses a zstd frame of `length` uncompressed bytes from the start of `src`, removing it
+/// from `src`.
+///
+/// Returns `Ok(None)` if more bytes are needed to complete the frame.
+fn decode_frame(src: &mut BytesMut, length: usize) -> Result<Option<Vec<u8>>, RPCError> {
+    // Calculate worst case compression length for given uncompressed length
+    let max_compressed_len = zstd::zstd_safe::compress_bound(length) as u64;
+
+    // Create a limit reader as a wrapper that reads only upto `max_compressed_len` from `src`.
+    let limit_reader = Cursor::new(src.as_ref()).take(max_compressed_len);
+    let mut reader = zstd::stream::read::Decoder::with_buffer(limit_reader)
+        .map_err(RPCError::from)?
+        .single_frame();
+    reader
+        .window_log_max(MAX_WINDOW_LOG)
+        .map_err(RPCError::from)?;
+    let mut decoded_buffer = vec![0; length];
+
+    // Read one byte past `length` so that the end of the frame is consumed, and frames holding
+    // more than `length` bytes are rejected.
+    let result = reader
+        .read_exact(&mut decoded_buffer)
+        .and_then(|()| reader.read(&mut [0]));
+    // `n` is how many bytes the reader read in the compressed stream
+    let n = reader.get_ref().get_ref().position();
+    match result {
+        Ok(0) => {
+            let _read_bytes = src.split_to(n as usize);
+            Ok(Some(decoded_buffer))
+        }
+        Ok(_) => Err(RPCError::InvalidData(format!(
+            "zstd frame is longer than its length prefix {}",
+            length
+        ))),
+        Err(e) => handle_error(e, n, max_compressed_len),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::codec::ssz_snappy::SSZSnappyInboundCodec;
+    use crate::rpc::protocol::max_rpc_size;
+    use types::{
+        BeaconBlock, BeaconBlockAltair, Epoch, Hash256, Signature, SignedBeaconBlock, Slot,
+    };
+
+    type Spec = types::MainnetEthSpec;
+
+    fn fork_context() -> Arc<ForkContext> {
+        let mut chain_spec = Spec::default_spec();
+        chain_spec.altair_fork_epoch = Some(Epoch::new(0));
+        Arc::new(ForkContext::new::<Spec>(
+            Slot::new(0),
+            Hash256::zero(),
+            &chain_spec,
+        ))
+    }
+
+    fn altair_block() -> SignedBeaconBlock<Spec> {
+        let full_block =
+            BeaconBlock::Altair(BeaconBlockAltair::<Spec>::full(&Spec::default_spec()));
+        SignedBeaconBlock::from_block(full_block, Signature::empty())
+    }
+
+    fn max_packet_size(fork_context: &ForkContext) -> usize {
+        max_rpc_size(fork_context, Spec::default_spec().max_chunk_size as usize)
+    }
+
+    fn encode_block_response(encoding: Encoding) -> BytesMut {
+        let fork_context = fork_context();
+        let protocol = ProtocolId::new(SupportedProtocol::BlocksByRangeV2, encoding.clone());
+        let response =
+            RPCCodedResponse::Success(RPCResponse::BlocksByRange(Arc::new(altair_block())));
+        let mut buf = BytesMut::new();
+        match encoding {
+            Encoding::SSZSnappy => SSZSnappyInboundCodec::<Spec>::new(
+                protocol,
+                max_packet_size(&fork_context),
+                fork_context,
+            )
+            .encode(response, &mut buf)
+            .unwrap(),
+            Encoding::SSZZstd => SSZZstdInboundCodec::<Spec>::new(
+                protocol,
+                max_packet_size(&fork_context),
+                fork_context,
+            )
+            .encode(response, &mut buf)
+            .unwrap(),
+        }
+        buf
+    }
+
+    fn outbound_codec() -> SSZZstdOutboundCodec<Spec> {
+        let fork_context = fork_context();
+        let protocol = ProtocolId::new(SupportedProtocol::BlocksByRangeV2, Encoding::SSZZstd);
+        SSZZstdOutboundCodec::new(protocol, max_packet_size(&fork_context), fork_context)
+    }
+
+    #[test]
+    fn block_response_round_trip() {
+        let mut buf = encode_block_response(Encoding::SSZZstd);
+        let decoded = outbound_codec().decode(&mut buf).unwrap();
+
+        assert_eq!(
+            decoded,
+            Some(RPCResponse::BlocksByRange(Arc::new(altair_block())))
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn block_response_is_smaller_than_snappy() {
+        let zstd = encode_block_response(Encoding::SSZZstd);
+        let snappy = encode_block_response(Encoding::SSZSnappy);
+        assert!(zstd.len() < snappy.len());
+    }
+
+    #[test]
+    fn partial_block_response_waits_for_more_bytes() {
+        let encoded = encode_block_response(Encoding::SSZZstd);
+        let (first, second) = encoded.split_at(encoded.len() / 2);
+
+        let mut codec = outbound_codec();
+        let mut buf = BytesMut::from(first);
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+
+        buf.extend_from_slice(second);
+        assert_eq!(
+            codec.decode(&mut buf).unwrap(),
+            Some(RPCResponse::BlocksByRange(Arc::new(altair_block())))
+        );
+    }
+
+    #[test]
+    fn block_by_root_request_round_trip() {
+        let fork_context = fork_context();
+        let protocol = ProtocolId::new(SupportedProtocol::BlocksByRootV2, Encoding::SSZZstd);
+        let request = BlocksByRootRequest::new(vec![Hash256::repeat_byte(1)].into());
+
+        let mut buf = BytesMut::new();
+        SSZZstdOutboundCodec::<Spec>::new(
+            protocol.clone(),
+            max_packet_size(&fork_context),
+            fork_context.clone(),
+        )
+        .encode(OutboundRequest::BlocksByRoot(request.clone()), &mut buf)
+        .unwrap();
+
+        let decoded = SSZZstdInboundCodec::<Spec>::new(
+            protocol,
+            max_packet_size(&fork_context),
+            fork_context,
+        )
+        .decode(&mut buf)
+        .unwrap();
+        assert_eq!(decoded, Some(InboundRequest::BlocksByRoot(request)));
+    }
+}
@@ -812,6 +812,7 @@ where
                         req: req.clone(),
                         fork_context: self.fork_context.clone(),
                         max_rpc_size: self.listen_protocol().upgrade().max_rpc_size,
+                        enable_zstd: self.listen_protocol().upgrade().enable_zstd,
                     },
                     (),
                 )
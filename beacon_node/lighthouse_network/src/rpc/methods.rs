@@ -476,6 +476,20 @@ impl<T: EthSpec> RPCResponse<T> {
             RPCResponse::LightClientBootstrap(_) => Protocol::LightClientBootstrap,
         }
     }
+
+    /// The length of the SSZ encoding of this response chunk.
+    pub fn ssz_bytes_len(&self) -> usize {
+        match self {
+            RPCResponse::Status(status) => status.ssz_bytes_len(),
+            RPCResponse::BlocksByRange(block) | RPCResponse::BlocksByRoot(block) => {
+                block.ssz_bytes_len()
+            }
+            RPCResponse::Pong(ping) => ping.data.ssz_bytes_len(),
+            RPCResponse::MetaData(MetaData::V1(metadata)) => metadata.ssz_bytes_len(),
+            RPCResponse::MetaData(MetaData::V2(metadata)) => metadata.ssz_bytes_len(),
+            RPCResponse::LightClientBootstrap(bootstrap) => bootstrap.ssz_bytes_len(),
+        }
+    }
 }
 
 impl std::fmt::Display for RPCResponseErrorCode {
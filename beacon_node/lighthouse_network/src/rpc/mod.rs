@@ -10,10 +10,13 @@ use libp2p::swarm::{
     handler::ConnectionHandler, ConnectionId, NetworkBehaviour, NotifyHandler, PollParameters,
     ToSwarm,
 };
-use libp2p::swarm::{FromSwarm, SubstreamProtocol, THandlerInEvent};
+use libp2p::swarm::{
+    ConnectionClosed, ConnectionEstablished, FromSwarm, SubstreamProtocol, THandlerInEvent,
+};
 use libp2p::PeerId;
 use rate_limiter::{RPCRateLimiter as RateLimiter, RateLimitedErr};
 use slog::{crit, debug, o};
+use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -123,8 +126,12 @@ pub struct RPC<Id: ReqId, TSpec: EthSpec> {
     self_limiter: Option<SelfRateLimiter<Id, TSpec>>,
     /// Queue of events to be processed.
     events: Vec<BehaviourAction<Id, TSpec>>,
+    /// The open connections of each connected peer.
+    connections: HashMap<PeerId, HashSet<ConnectionId>>,
     fork_context: Arc<ForkContext>,
     enable_light_client_server: bool,
+    /// Offer the `SSZZstd` encoding for the protocols that support it.
+    enable_zstd: bool,
     /// Slog logger for RPC behaviour.
     log: slog::Logger,
     /// Networking constant values
@@ -135,6 +142,7 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
     pub fn new(
         fork_context: Arc<ForkContext>,
         enable_light_client_server: bool,
+        enable_zstd: bool,
         inbound_rate_limiter_config: Option<InboundRateLimiterConfig>,
         outbound_rate_limiter_config: Option<OutboundRateLimiterConfig>,
         log: slog::Logger,
@@ -156,8 +164,10 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
             limiter: inbound_limiter,
             self_limiter,
             events: Vec::new(),
+            connections: HashMap::new(),
             fork_context,
             enable_light_client_server,
+            enable_zstd,
             log,
             network_params,
         }
@@ -184,8 +194,32 @@ impl<Id: ReqId, TSpec: EthSpec> RPC<Id, TSpec> {
     /// The peer must be connected for this to succeed.
     pub fn send_request(&mut self, peer_id: PeerId, request_id: Id, req: OutboundRequest<TSpec>) {
         let event = if let Some(self_limiter) = self.self_limiter.as_mut() {
+            let protocol = req.versioned_protocol().protocol();
             match self_limiter.allows(peer_id, request_id, req) {
                 Ok(event) => event,
+                Err(self_limiter::Error::QueueFull) => {
+                    // Report the request as failed so that the caller can retry it elsewhere. The
+                    // request was never handed to a connection, so any of the peer's connections
+                    // is reported.
+                    let conn_id = match self
+                        .connections
+                        .get(&peer_id)
+                        .and_then(|connections| connections.iter().next().copied())
+                    {
+                        Some(conn_id) => conn_id,
+                        // The peer is disconnecting, which the caller is informed of separately.
+                        None => return,
+                    };
+                    ToSwarm::GenerateEvent(RPCMessage {
+                        peer_id,
+                        conn_id,
+                        event: Err(HandlerErr::Outbound {
+                            id: request_id,
+                            proto: protocol,
+                            error: RPCError::InternalError("Self rate limiter queue is full"),
+                        }),
+                    })
+                }
                 Err(_e) => {
                     // Request is logged and queued internally in the self rate limiter.
                     return;
@@ -233,6 +267,7 @@ where
                 fork_context: self.fork_context.clone(),
                 max_rpc_size: max_rpc_size(&self.fork_context, self.network_params.max_chunk_size),
                 enable_light_client_server: self.enable_light_client_server,
+                enable_zstd: self.enable_zstd,
                 phantom: PhantomData,
                 ttfb_timeout: self.network_params.ttfb_timeout,
             },
@@ -263,6 +298,7 @@ where
                 fork_context: self.fork_context.clone(),
                 max_rpc_size: max_rpc_size(&self.fork_context, self.network_params.max_chunk_size),
                 enable_light_client_server: self.enable_light_client_server,
+                enable_zstd: self.enable_zstd,
                 phantom: PhantomData,
                 ttfb_timeout: self.network_params.ttfb_timeout,
             },
@@ -284,9 +320,36 @@ where
 
     fn on_swarm_event(&mut self, event: FromSwarm<Self::ConnectionHandler>) {
         match event {
-            FromSwarm::ConnectionClosed(_)
-            | FromSwarm::ConnectionEstablished(_)
-            | FromSwarm::AddressChange(_)
+            FromSwarm::ConnectionEstablished(ConnectionEstablished {
+                peer_id,
+                connection_id,
+                ..
+            }) => {
+                self.connections
+                    .entry(peer_id)
+                    .or_default()
+                    .insert(connection_id);
+            }
+            FromSwarm::ConnectionClosed(ConnectionClosed {
+                peer_id,
+                connection_id,
+                remaining_established,
+                ..
+            }) => {
+                if let Some(connections) = self.connections.get_mut(&peer_id) {
+                    connections.remove(&connection_id);
+                }
+                if remaining_established == 0 {
+                    self.connections.remove(&peer_id);
+                    // Drop the requests that were waiting on the self rate limiter, since they
+                    // will never be sent. Sync fails its in-flight requests itself once it is
+                    // informed of the disconnection.
+                    if let Some(self_limiter) = self.self_limiter.as_mut() {
+                        self_limiter.peer_disconnected(peer_id);
+                    }
+                }
+            }
+            FromSwarm::AddressChange(_)
             | FromSwarm::DialFailure(_)
             | FromSwarm::ListenFailure(_)
             | FromSwarm::NewListener(_)
@@ -1,10 +1,13 @@
 use super::methods::*;
-use super::protocol::ProtocolId;
 use super::protocol::SupportedProtocol;
+use super::protocol::{with_zstd_protocols, ProtocolId};
 use super::RPCError;
 use crate::rpc::protocol::Encoding;
 use crate::rpc::{
-    codec::{base::BaseOutboundCodec, ssz_snappy::SSZSnappyOutboundCodec, OutboundCodec},
+    codec::{
+        base::BaseOutboundCodec, ssz_snappy::SSZSnappyOutboundCodec,
+        ssz_zstd::SSZZstdOutboundCodec, OutboundCodec,
+    },
     methods::ResponseTermination,
 };
 use futures::future::BoxFuture;
@@ -27,6 +30,8 @@ pub struct OutboundRequestContainer<TSpec: EthSpec> {
     pub req: OutboundRequest<TSpec>,
     pub fork_context: Arc<ForkContext>,
     pub max_rpc_size: usize,
+    /// Prefer the `SSZZstd` encoding for the protocols that support it.
+    pub enable_zstd: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,7 +50,11 @@ impl<TSpec: EthSpec> UpgradeInfo for OutboundRequestContainer<TSpec> {
 
     // add further protocols as we support more encodings/versions
     fn protocol_info(&self) -> Self::InfoIter {
-        self.req.supported_protocols()
+        if self.enable_zstd {
+            with_zstd_protocols(self.req.supported_protocols())
+        } else {
+            self.req.supported_protocols()
+        }
     }
 }
 
@@ -158,6 +167,14 @@ where
                 ));
                 OutboundCodec::SSZSnappy(ssz_snappy_codec)
             }
+            Encoding::SSZZstd => {
+                let ssz_zstd_codec = BaseOutboundCodec::new(SSZZstdOutboundCodec::new(
+                    protocol,
+                    self.max_rpc_size,
+                    self.fork_context.clone(),
+                ));
+                OutboundCodec::SSZZstd(ssz_zstd_codec)
+            }
         };
 
         let mut socket = Framed::new(socket, codec);
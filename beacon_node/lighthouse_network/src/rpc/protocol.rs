@@ -1,6 +1,9 @@
 use super::methods::*;
 use crate::rpc::{
-    codec::{base::BaseInboundCodec, ssz_snappy::SSZSnappyInboundCodec, InboundCodec},
+    codec::{
+        base::BaseInboundCodec, ssz_snappy::SSZSnappyInboundCodec, ssz_zstd::SSZZstdInboundCodec,
+        InboundCodec,
+    },
     methods::{MaxErrorLen, ResponseTermination, MAX_ERROR_LEN},
     MaxRequestBlocks, MAX_REQUEST_BLOCKS,
 };
@@ -176,6 +179,8 @@ pub enum Protocol {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Encoding {
     SSZSnappy,
+    /// A Lighthouse-specific alternative to `SSZSnappy`, see `codec::ssz_zstd`.
+    SSZZstd,
 }
 
 /// All valid protocol name and version combinations.
@@ -238,12 +243,35 @@ impl SupportedProtocol {
             ProtocolId::new(Self::MetaDataV1, Encoding::SSZSnappy),
         ]
     }
+
+    /// Returns `true` if the protocol may be negotiated with the `SSZZstd` encoding.
+    fn supports_zstd(&self) -> bool {
+        matches!(
+            self,
+            SupportedProtocol::BlocksByRangeV2 | SupportedProtocol::BlocksByRootV2
+        )
+    }
+}
+
+/// Prepends an `SSZZstd` variant of each protocol in `protocols` that supports it, so that it is
+/// preferred during negotiation whilst peers without it fall back to the original encoding.
+pub fn with_zstd_protocols(protocols: Vec<ProtocolId>) -> Vec<ProtocolId> {
+    let mut zstd_protocols = protocols
+        .iter()
+        .filter(|protocol| {
+            protocol.encoding == Encoding::SSZSnappy && protocol.versioned_protocol.supports_zstd()
+        })
+        .map(|protocol| ProtocolId::new(protocol.versioned_protocol, Encoding::SSZZstd))
+        .collect::<Vec<_>>();
+    zstd_protocols.extend(protocols);
+    zstd_protocols
 }
 
 impl std::fmt::Display for Encoding {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let repr = match self {
             Encoding::SSZSnappy => "ssz_snappy",
+            Encoding::SSZZstd => "ssz_zstd",
         };
         f.write_str(repr)
     }
@@ -254,6 +282,8 @@ pub struct RPCProtocol<TSpec: EthSpec> {
     pub fork_context: Arc<ForkContext>,
     pub max_rpc_size: usize,
     pub enable_light_client_server: bool,
+    /// Offer the `SSZZstd` encoding for the protocols that support it.
+    pub enable_zstd: bool,
     pub phantom: PhantomData<TSpec>,
     pub ttfb_timeout: Duration,
 }
@@ -271,6 +301,9 @@ impl<TSpec: EthSpec> UpgradeInfo for RPCProtocol<TSpec> {
                 Encoding::SSZSnappy,
             ));
         }
+        if self.enable_zstd {
+            supported_protocols = with_zstd_protocols(supported_protocols);
+        }
         supported_protocols
     }
 }
@@ -439,6 +472,14 @@ where
                     ));
                     InboundCodec::SSZSnappy(ssz_snappy_codec)
                 }
+                Encoding::SSZZstd => {
+                    let ssz_zstd_codec = BaseInboundCodec::new(SSZZstdInboundCodec::new(
+                        protocol,
+                        self.max_rpc_size,
+                        self.fork_context.clone(),
+                    ));
+                    InboundCodec::SSZZstd(ssz_zstd_codec)
+                }
             };
             let mut timed_socket = TimeoutStream::new(socket);
             timed_socket.set_read_timeout(Some(self.ttfb_timeout));
@@ -17,6 +17,11 @@ use super::{
     BehaviourAction, OutboundRequest, Protocol, RPCSend, ReqId,
 };
 
+/// The maximum number of requests that can be queued for a single peer and protocol. Requests
+/// beyond this limit are rejected so that the caller can retry them with another peer instead of
+/// piling work onto a peer that is already being throttled.
+pub(crate) const MAX_QUEUED_REQUESTS_PER_PEER: usize = 8;
+
 /// A request that was rate limited or waiting on rate limited requests for the same peer and
 /// protocol.
 struct QueuedRequest<Id: ReqId, TSpec: EthSpec> {
@@ -40,12 +45,13 @@ pub(crate) struct SelfRateLimiter<Id: ReqId, TSpec: EthSpec> {
 }
 
 /// Error returned when the rate limiter does not accept a request.
-// NOTE: this is currently not used, but might be useful for debugging.
 pub enum Error {
     /// There are queued requests for this same peer and protocol.
     PendingRequests,
     /// Request was tried but rate limited.
     RateLimited,
+    /// The queue for this peer and protocol is full and the request was dropped.
+    QueueFull,
 }
 
 impl<Id: ReqId, TSpec: EthSpec> SelfRateLimiter<Id, TSpec> {
@@ -75,6 +81,10 @@ impl<Id: ReqId, TSpec: EthSpec> SelfRateLimiter<Id, TSpec> {
         let protocol = req.versioned_protocol().protocol();
         // First check that there are not already other requests waiting to be sent.
         if let Some(queued_requests) = self.delayed_requests.get_mut(&(peer_id, protocol)) {
+            if queued_requests.len() >= MAX_QUEUED_REQUESTS_PER_PEER {
+                debug!(self.log, "Self rate limiting queue full, dropping request"; "protocol" => %protocol, "peer_id" => %peer_id);
+                return Err(Error::QueueFull);
+            }
             queued_requests.push_back(QueuedRequest { req, request_id });
 
             return Err(Error::PendingRequests);
@@ -160,6 +170,26 @@ impl<Id: ReqId, TSpec: EthSpec> SelfRateLimiter<Id, TSpec> {
         }
     }
 
+    /// Drops all the requests queued for a peer, returning the number of requests dropped.
+    pub fn peer_disconnected(&mut self, peer_id: PeerId) -> usize {
+        let mut dropped = 0;
+        self.delayed_requests
+            .retain(|(queued_peer_id, _protocol), queued_requests| {
+                if *queued_peer_id == peer_id {
+                    dropped += queued_requests.len();
+                    false
+                } else {
+                    true
+                }
+            });
+        if dropped > 0 {
+            debug!(self.log, "Dropped queued requests of disconnected peer"; "peer_id" => %peer_id, "dropped" => dropped);
+        }
+        // The expired keys of `next_peer_request` are ignored once the queue is gone, so there is
+        // no need to remove them here.
+        dropped
+    }
+
     pub fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<BehaviourAction<Id, TSpec>> {
         // First check the requests that were self rate limited, since those might add events to
         // the queue. Also do this this before rate limiter prunning to avoid removing and
@@ -179,3 +209,51 @@ impl<Id: ReqId, TSpec: EthSpec> SelfRateLimiter<Id, TSpec> {
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::config::{OutboundRateLimiterConfig, RateLimiterConfig};
+    use crate::rpc::methods::Ping;
+    use types::MainnetEthSpec;
+
+    fn ping() -> OutboundRequest<MainnetEthSpec> {
+        OutboundRequest::Ping(Ping { data: 0 })
+    }
+
+    fn limiter() -> SelfRateLimiter<u64, MainnetEthSpec> {
+        let config = OutboundRateLimiterConfig(RateLimiterConfig::default());
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        SelfRateLimiter::new(config, log).unwrap()
+    }
+
+    #[tokio::test]
+    async fn queue_is_bounded_and_cleared_on_disconnect() {
+        let mut limiter = limiter();
+        let peer_id = PeerId::random();
+
+        // The default ping quota allows two requests before throttling.
+        let mut sent = 0;
+        let mut queued = 0;
+        let mut rejected = 0;
+        for request_id in 0..(MAX_QUEUED_REQUESTS_PER_PEER as u64 + 4) {
+            match limiter.allows(peer_id, request_id, ping()) {
+                Ok(_) => sent += 1,
+                Err(Error::RateLimited) | Err(Error::PendingRequests) => queued += 1,
+                Err(Error::QueueFull) => rejected += 1,
+            }
+        }
+        assert_eq!(sent, 2);
+        assert_eq!(queued, MAX_QUEUED_REQUESTS_PER_PEER);
+        assert_eq!(rejected, 2);
+
+        // Requests for other peers are unaffected.
+        assert!(limiter.allows(PeerId::random(), 100, ping()).is_ok());
+
+        assert_eq!(
+            limiter.peer_disconnected(peer_id),
+            MAX_QUEUED_REQUESTS_PER_PEER
+        );
+        assert_eq!(limiter.peer_disconnected(peer_id), 0);
+    }
+}
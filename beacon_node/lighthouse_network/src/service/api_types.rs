@@ -12,6 +12,7 @@ use crate::rpc::{
     },
     OutboundRequest, SubstreamId,
 };
+use crate::types::RpcTraceId;
 
 /// Identifier of requests sent by a peer.
 pub type PeerRequestId = (ConnectionId, SubstreamId);
@@ -19,7 +20,12 @@ pub type PeerRequestId = (ConnectionId, SubstreamId);
 /// Identifier of a request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RequestId<AppReqId> {
-    Application(AppReqId),
+    /// A request sent by the application, along with the trace id used to follow it through the
+    /// RPC behaviour and handler.
+    Application {
+        id: AppReqId,
+        trace_id: RpcTraceId,
+    },
     Internal,
 }
 
@@ -114,9 +120,12 @@ impl<AppReqId: std::fmt::Debug> slog::Value for RequestId<AppReqId> {
     ) -> slog::Result {
         match self {
             RequestId::Internal => slog::Value::serialize("Behaviour", record, key, serializer),
-            RequestId::Application(ref id) => {
-                slog::Value::serialize(&format_args!("{:?}", id), record, key, serializer)
-            }
+            RequestId::Application { ref id, trace_id } => slog::Value::serialize(
+                &format_args!("{:?} (trace_id: {})", id, trace_id),
+                record,
+                key,
+                serializer,
+            ),
         }
     }
 }
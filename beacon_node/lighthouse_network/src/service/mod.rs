@@ -5,17 +5,18 @@ use crate::discovery::{
     subnet_predicate, DiscoveredPeers, Discovery, FIND_NODE_QUERY_CLOSEST_PEERS,
 };
 use crate::peer_manager::{
-    config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
-    ConnectionDirection, PeerManager, PeerManagerEvent,
+    admission::{AdmissionPolicy, AsnDatabase},
+    config::Config as PeerManagerCfg,
+    peerdb::score::{PeerAction, ReportSource},
+    ConnectionDirection, OperatorAction, PeerManager, PeerManagerEvent,
 };
-use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
 use crate::rpc::methods::MetadataRequest;
 use crate::rpc::*;
 use crate::service::behaviour::BehaviourEvent;
 pub use crate::service::behaviour::Gossipsub;
 use crate::types::{
     fork_core_topics, subnet_from_topic_hash, GossipEncoding, GossipKind, GossipTopic,
-    SnappyTransform, Subnet, SubnetDiscovery,
+    RpcRequestOutcome, SnappyTransform, Subnet, SubnetDiscovery,
 };
 use crate::EnrExt;
 use crate::Eth2Enr;
@@ -42,7 +43,7 @@ use types::ForkName;
 use types::{
     consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, EthSpec, ForkContext, Slot, SubnetId,
 };
-use utils::{build_transport, strip_peer_id, Context as ServiceContext, MAX_CONNECTIONS_PER_PEER};
+use utils::{build_transport, strip_peer_id, Context as ServiceContext, TransportBandwidth};
 
 pub mod api_types;
 mod behaviour;
@@ -124,6 +125,8 @@ pub struct Network<AppReqId: ReqId, TSpec: EthSpec> {
     gossip_cache: GossipCache,
     /// The bandwidth logger for the underlying libp2p transport.
     pub bandwidth: Arc<BandwidthSinks>,
+    /// The bandwidth loggers of the TCP and QUIC transports.
+    pub transport_bandwidth: TransportBandwidth,
     /// This node's PeerId.
     pub local_peer_id: PeerId,
     /// Logger for behaviour actions.
@@ -270,6 +273,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         let eth2_rpc = RPC::new(
             ctx.fork_context.clone(),
             config.enable_light_client_server,
+            config.enable_rpc_zstd,
             config.inbound_rate_limiter_config.clone(),
             config.outbound_rate_limiter_config.clone(),
             log.clone(),
@@ -307,32 +311,35 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         };
 
         let peer_manager = {
+            let asn_database = config
+                .asn_database
+                .as_ref()
+                .map(|path| AsnDatabase::open(path))
+                .transpose()?;
+            if let Some(asn_database) = &asn_database {
+                info!(log, "Loaded ASN database"; "ranges" => asn_database.len());
+            }
             let peer_manager_cfg = PeerManagerCfg {
                 discovery_enabled: !config.disable_discovery,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
+                prefer_quic_peers: config.prefer_quic_peers,
+                admission_policy: AdmissionPolicy {
+                    max_subnet_peer_fraction: config.max_subnet_peer_fraction,
+                    max_asn_peer_fraction: config.max_asn_peer_fraction,
+                    asn_database,
+                },
                 ..Default::default()
             };
             PeerManager::new(peer_manager_cfg, network_globals.clone(), &log)?
         };
 
         let connection_limits = {
+            // The limits on established connections depend on the number of trusted peers, which
+            // can change at runtime, so they are enforced by the peer manager.
             let limits = libp2p::connection_limits::ConnectionLimits::default()
                 .with_max_pending_incoming(Some(5))
-                .with_max_pending_outgoing(Some(16))
-                .with_max_established_incoming(Some(
-                    (config.target_peers as f32
-                        * (1.0 + PEER_EXCESS_FACTOR - MIN_OUTBOUND_ONLY_FACTOR))
-                        .ceil() as u32,
-                ))
-                .with_max_established_outgoing(Some(
-                    (config.target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR)).ceil() as u32,
-                ))
-                .with_max_established(Some(
-                    (config.target_peers as f32 * (1.0 + PEER_EXCESS_FACTOR + PRIORITY_PEER_EXCESS))
-                        .ceil() as u32,
-                ))
-                .with_max_established_per_peer(Some(MAX_CONNECTIONS_PER_PEER));
+                .with_max_pending_outgoing(Some(16));
 
             libp2p::connection_limits::Behaviour::new(limits)
         };
@@ -351,9 +358,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             }
         };
 
-        let (swarm, bandwidth) = {
+        let (swarm, bandwidth, transport_bandwidth) = {
             // Set up the transport - tcp/ws with noise and mplex
-            let (transport, bandwidth) =
+            let (transport, bandwidth, transport_bandwidth) =
                 build_transport(local_keypair.clone(), !config.disable_quic_support)
                     .map_err(|e| format!("Failed to build transport: {:?}", e))?;
 
@@ -378,6 +385,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 .per_connection_event_buffer_size(4)
                 .build(),
                 bandwidth,
+                transport_bandwidth,
             )
         };
 
@@ -391,6 +399,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             update_gossipsub_scores,
             gossip_cache,
             bandwidth,
+            transport_bandwidth,
             local_peer_id,
             log,
         };
@@ -790,17 +799,37 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
 
     /// Send a request to a peer over RPC.
     pub fn send_request(&mut self, peer_id: PeerId, request_id: AppReqId, request: Request) {
+        let request: OutboundRequest<TSpec> = request.into();
+        self.record_rpc_request_sent(&peer_id, &request);
+        let protocol = request.versioned_protocol().protocol();
+        let trace_id = self.network_globals.rpc_requests.write().start(
+            &peer_id,
+            protocol.as_ref(),
+            format!("{:?}", request_id),
+        );
+        debug!(
+            self.log,
+            "Sending RPC request";
+            "trace_id" => trace_id,
+            "request_id" => ?request_id,
+            "protocol" => %protocol,
+            "peer_id" => %peer_id,
+        );
         self.eth2_rpc_mut().send_request(
             peer_id,
-            RequestId::Application(request_id),
-            request.into(),
+            RequestId::Application {
+                id: request_id,
+                trace_id,
+            },
+            request,
         )
     }
 
     /// Send a successful response to a peer over RPC.
     pub fn send_response(&mut self, peer_id: PeerId, id: PeerRequestId, response: Response<TSpec>) {
-        self.eth2_rpc_mut()
-            .send_response(peer_id, id, response.into())
+        let response: RPCCodedResponse<TSpec> = response.into();
+        self.record_rpc_response_sent(&peer_id, &response);
+        self.eth2_rpc_mut().send_response(peer_id, id, response)
     }
 
     /// Inform the peer that their request produced an error.
@@ -844,6 +873,29 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             .goodbye_peer(peer_id, reason, source);
     }
 
+    /// Sends a goodbye to all peers without penalising them and stops making new connections.
+    ///
+    /// Returns the number of peers being disconnected.
+    pub fn disconnect_all_peers(&mut self, reason: GoodbyeReason) -> usize {
+        self.peer_manager_mut().disconnect_all_peers(reason)
+    }
+
+    /// Restores the scores of peers persisted by a previous run.
+    pub fn restore_peer_scores(&mut self, scores: Vec<(PeerId, f64)>) {
+        self.peer_manager_mut().restore_peer_scores(scores);
+    }
+
+    /// Applies an action requested by the node operator to a peer.
+    pub fn operator_action(
+        &mut self,
+        peer_id: &PeerId,
+        action: OperatorAction,
+        requested_by: &str,
+    ) {
+        self.peer_manager_mut()
+            .operator_action(peer_id, action, requested_by);
+    }
+
     /// Returns an iterator over all enr entries in the DHT.
     pub fn enr_entries(&self) -> Vec<Enr> {
         self.discovery().table_entries_enr()
@@ -928,6 +980,16 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         self.enr_fork_id = enr_fork_id;
     }
 
+    /// Updates the local ENR's "eas" field with the earliest slot from which blocks can be served.
+    pub fn update_earliest_available_slot(&mut self, slot: Slot) {
+        if let Err(e) = self
+            .discovery_mut()
+            .update_enr_earliest_available_slot(slot)
+        {
+            warn!(self.log, "Could not update ENR earliest available slot"; "error" => e);
+        }
+    }
+
     /* Private internal functions */
 
     /// Updates the current meta data of the node to match the local ENR.
@@ -969,8 +1031,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         };
         trace!(self.log, "Sending Ping"; "peer_id" => %peer_id);
         let id = RequestId::Internal;
-        self.eth2_rpc_mut()
-            .send_request(peer_id, id, OutboundRequest::Ping(ping));
+        let request = OutboundRequest::Ping(ping);
+        self.record_rpc_request_sent(&peer_id, &request);
+        self.eth2_rpc_mut().send_request(peer_id, id, request);
     }
 
     /// Sends a Pong response to the peer.
@@ -980,6 +1043,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         };
         trace!(self.log, "Sending Pong"; "request_id" => id.1, "peer_id" => %peer_id);
         let event = RPCCodedResponse::Success(RPCResponse::Pong(ping));
+        self.record_rpc_response_sent(&peer_id, &event);
         self.eth2_rpc_mut().send_response(peer_id, id, event);
     }
 
@@ -987,6 +1051,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
     fn send_meta_data_request(&mut self, peer_id: PeerId) {
         // We always prefer sending V2 requests
         let event = OutboundRequest::MetaData(MetadataRequest::new_v2());
+        self.record_rpc_request_sent(&peer_id, &event);
         self.eth2_rpc_mut()
             .send_request(peer_id, RequestId::Internal, event);
     }
@@ -1004,9 +1069,125 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             MetadataRequest::V2(_) => metadata,
         };
         let event = RPCCodedResponse::Success(RPCResponse::MetaData(metadata));
+        self.record_rpc_response_sent(&peer_id, &event);
         self.eth2_rpc_mut().send_response(peer_id, id, event);
     }
 
+    /// Records a request sent to the peer in its RPC statistics.
+    fn record_rpc_request_sent(&mut self, peer_id: &PeerId, request: &OutboundRequest<TSpec>) {
+        let protocol = request.versioned_protocol().protocol();
+        self.peer_manager_mut()
+            .update_rpc_stats(peer_id, |stats| stats.on_request_sent(protocol));
+    }
+
+    /// Records a response chunk sent to the peer in its RPC statistics.
+    fn record_rpc_response_sent(&mut self, peer_id: &PeerId, response: &RPCCodedResponse<TSpec>) {
+        if let RPCCodedResponse::Success(response) = response {
+            let (protocol, bytes) = (response.protocol(), response.ssz_bytes_len());
+            self.peer_manager_mut()
+                .update_rpc_stats(peer_id, |stats| stats.on_response_sent(protocol, bytes));
+        }
+    }
+
+    /// Records an RPC event received from the peer in its RPC statistics.
+    fn record_rpc_received(
+        &mut self,
+        peer_id: &PeerId,
+        event: &Result<RPCReceived<RequestId<AppReqId>, TSpec>, HandlerErr<RequestId<AppReqId>>>,
+    ) {
+        self.peer_manager_mut()
+            .update_rpc_stats(peer_id, |stats| match event {
+                Err(HandlerErr::Inbound { proto, .. }) => stats.on_error(*proto, false),
+                Err(HandlerErr::Outbound { proto, .. }) => stats.on_error(*proto, true),
+                Ok(RPCReceived::Request(_, request)) => {
+                    stats.on_request_received(request.versioned_protocol().protocol())
+                }
+                Ok(RPCReceived::Response(_, response)) => {
+                    let protocol = response.protocol();
+                    stats.on_response_received(protocol, response.ssz_bytes_len());
+                    // Streamed responses complete on their stream termination.
+                    if !matches!(protocol, Protocol::BlocksByRange | Protocol::BlocksByRoot) {
+                        stats.on_request_completed(protocol);
+                    }
+                }
+                Ok(RPCReceived::EndOfStream(_, termination)) => {
+                    stats.on_request_completed(match termination {
+                        ResponseTermination::BlocksByRange => Protocol::BlocksByRange,
+                        ResponseTermination::BlocksByRoot => Protocol::BlocksByRoot,
+                    })
+                }
+            });
+    }
+
+    /// Records an RPC event for an outbound application request against its trace.
+    fn record_rpc_trace(
+        &mut self,
+        peer_id: &PeerId,
+        event: &Result<RPCReceived<RequestId<AppReqId>, TSpec>, HandlerErr<RequestId<AppReqId>>>,
+    ) {
+        let mut rpc_requests = self.network_globals.rpc_requests.write();
+        match event {
+            Err(HandlerErr::Outbound {
+                id: RequestId::Application { trace_id, .. },
+                proto,
+                error,
+            }) => {
+                let outcome = if matches!(error, RPCError::StreamTimeout) {
+                    RpcRequestOutcome::TimedOut
+                } else {
+                    RpcRequestOutcome::Failed
+                };
+                let duration_ms = rpc_requests.finish(*trace_id, outcome, Some(error.to_string()));
+                debug!(
+                    self.log,
+                    "RPC request failed";
+                    "trace_id" => trace_id,
+                    "protocol" => %proto,
+                    "peer_id" => %peer_id,
+                    "error" => %error,
+                    "duration_ms" => duration_ms,
+                );
+            }
+            Ok(RPCReceived::Response(RequestId::Application { trace_id, .. }, response)) => {
+                let protocol = response.protocol();
+                rpc_requests.on_chunk(*trace_id, response.ssz_bytes_len());
+                trace!(
+                    self.log,
+                    "RPC response chunk received";
+                    "trace_id" => trace_id,
+                    "protocol" => %protocol,
+                    "peer_id" => %peer_id,
+                );
+                // Streamed responses complete on their stream termination.
+                if !matches!(protocol, Protocol::BlocksByRange | Protocol::BlocksByRoot) {
+                    let duration_ms =
+                        rpc_requests.finish(*trace_id, RpcRequestOutcome::Completed, None);
+                    debug!(
+                        self.log,
+                        "RPC request completed";
+                        "trace_id" => trace_id,
+                        "protocol" => %protocol,
+                        "peer_id" => %peer_id,
+                        "duration_ms" => duration_ms,
+                    );
+                }
+            }
+            Ok(RPCReceived::EndOfStream(RequestId::Application { trace_id, .. }, termination)) => {
+                let duration_ms =
+                    rpc_requests.finish(*trace_id, RpcRequestOutcome::Completed, None);
+                debug!(
+                    self.log,
+                    "RPC request completed";
+                    "trace_id" => trace_id,
+                    "termination" => ?termination,
+                    "peer_id" => %peer_id,
+                    "duration_ms" => duration_ms,
+                );
+            }
+            _ => {}
+        }
+    }
+
     // RPC Propagation methods
     /// Queues the response to be sent upwards as long at it was requested outside the Behaviour.
     #[must_use = "return the response"]
@@ -1017,7 +1198,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
         response: Response<TSpec>,
     ) -> Option<NetworkEvent<AppReqId, TSpec>> {
         match id {
-            RequestId::Application(id) => Some(NetworkEvent::ResponseReceived {
+            RequestId::Application { id, .. } => Some(NetworkEvent::ResponseReceived {
                 peer_id,
                 id,
                 response,
@@ -1197,6 +1378,9 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
             return None;
         }
 
+        self.record_rpc_received(&peer_id, &event.event);
+        self.record_rpc_trace(&peer_id, &event.event);
+
         let handler_id = event.conn_id;
         // The METADATA and PING RPC responses are handled within the behaviour and not propagated
         match event.event {
@@ -1227,7 +1411,7 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                             ConnectionDirection::Outgoing,
                         );
                         // inform failures of requests comming outside the behaviour
-                        if let RequestId::Application(id) = id {
+                        if let RequestId::Application { id, .. } = id {
                             Some(NetworkEvent::RPCFailed { peer_id, id })
                         } else {
                             None
@@ -1399,6 +1583,19 @@ impl<AppReqId: ReqId, TSpec: EthSpec> Network<AppReqId, TSpec> {
                 Some(NetworkEvent::PeerConnectedOutgoing(peer_id))
             }
             PeerManagerEvent::PeerDisconnected(peer_id) => {
+                let failed = self
+                    .network_globals
+                    .rpc_requests
+                    .write()
+                    .on_peer_disconnected(&peer_id);
+                if failed > 0 {
+                    debug!(
+                        self.log,
+                        "Failed pending RPC requests to disconnected peer";
+                        "peer_id" => %peer_id,
+                        "requests" => failed,
+                    );
+                }
                 Some(NetworkEvent::PeerDisconnected(peer_id))
             }
             PeerManagerEvent::Banned(peer_id, associated_ips) => {
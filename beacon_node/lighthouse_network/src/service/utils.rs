@@ -25,7 +25,7 @@ use types::{ChainSpec, EnrForkId, EthSpec, ForkContext, SubnetId, SyncSubnetId};
 
 pub const NETWORK_KEY_FILENAME: &str = "key";
 /// The maximum simultaneous libp2p connections per peer.
-pub const MAX_CONNECTIONS_PER_PEER: u32 = 1;
+pub const MAX_CONNECTIONS_PER_PEER: usize = 1;
 /// The filename to store our local metadata.
 pub const METADATA_FILENAME: &str = "metadata";
 
@@ -39,12 +39,22 @@ pub struct Context<'a> {
 
 type BoxedTransport = Boxed<(PeerId, StreamMuxerBox)>;
 
+/// The bandwidth loggers of the individual transports.
+pub struct TransportBandwidth {
+    pub tcp: Arc<BandwidthSinks>,
+    /// `None` if QUIC support is disabled.
+    pub quic: Option<Arc<BandwidthSinks>>,
+}
+
 /// The implementation supports TCP/IP, QUIC (experimental) over UDP, noise as the encryption layer, and
 /// mplex/yamux as the multiplexing layer (when using TCP).
+///
+/// Along with the transport, this returns the bandwidth logger of the whole transport and those of
+/// each of TCP and QUIC.
 pub fn build_transport(
     local_private_key: Keypair,
     quic_support: bool,
-) -> std::io::Result<(BoxedTransport, Arc<BandwidthSinks>)> {
+) -> std::io::Result<(BoxedTransport, Arc<BandwidthSinks>, TransportBandwidth)> {
     // mplex config
     let mut mplex_config = libp2p_mplex::MplexConfig::new();
     mplex_config.set_max_buffer_size(256);
@@ -63,25 +73,37 @@ pub fn build_transport(
             mplex_config,
         ))
         .timeout(Duration::from_secs(10));
+    let (tcp, tcp_bandwidth) = tcp.with_bandwidth_logging();
 
-    let (transport, bandwidth) = if quic_support {
+    let (transport, bandwidth, quic_bandwidth) = if quic_support {
         // Enables Quic
         // The default quic configuration suits us for now.
         let quic_config = libp2p_quic::Config::new(&local_private_key);
-        tcp.or_transport(libp2p_quic::tokio::Transport::new(quic_config))
+        let (quic, quic_bandwidth) =
+            libp2p_quic::tokio::Transport::new(quic_config).with_bandwidth_logging();
+        let (transport, bandwidth) = tcp
+            .or_transport(quic)
             .map(|either_output, _| match either_output {
-                Either::Left((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
-                Either::Right((peer_id, muxer)) => (peer_id, StreamMuxerBox::new(muxer)),
+                Either::Left(output) => output,
+                Either::Right(output) => output,
             })
-            .with_bandwidth_logging()
+            .with_bandwidth_logging();
+        (transport, bandwidth, Some(quic_bandwidth))
     } else {
-        tcp.with_bandwidth_logging()
+        (tcp, tcp_bandwidth.clone(), None)
     };
 
     // // Enables DNS over the transport.
     let transport = libp2p::dns::TokioDnsConfig::system(transport)?.boxed();
 
-    Ok((transport, bandwidth))
+    Ok((
+        transport,
+        bandwidth,
+        TransportBandwidth {
+            tcp: tcp_bandwidth,
+            quic: quic_bandwidth,
+        },
+    ))
 }
 
 // Useful helper functions for debugging. Currently not used in the client.
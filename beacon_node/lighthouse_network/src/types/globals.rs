@@ -1,7 +1,10 @@
 //! A collection of variables that are accessible outside of the network thread itself.
+use crate::metrics;
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV2};
-use crate::types::{BackFillState, SyncState};
+use crate::types::{
+    BackFillState, HandshakeFailureLog, HandshakeFailureReason, NatStatus, RpcRequestLog, SyncState,
+};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
@@ -26,6 +29,14 @@ pub struct NetworkGlobals<TSpec: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// Whether the outbound bandwidth is close to the configured budget.
+    pub near_bandwidth_budget: RwLock<bool>,
+    /// The state of the UPnP port mappings.
+    pub nat_status: RwLock<NatStatus>,
+    /// The most recent outbound RPC requests.
+    pub rpc_requests: RwLock<RpcRequestLog>,
+    /// The most recent peers disconnected during the handshake.
+    pub handshake_failures: RwLock<HandshakeFailureLog>,
 }
 
 impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
@@ -45,6 +56,10 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            near_bandwidth_budget: RwLock::new(false),
+            nat_status: RwLock::new(NatStatus::default()),
+            rpc_requests: RwLock::new(RpcRequestLog::default()),
+            handshake_failures: RwLock::new(HandshakeFailureLog::default()),
         }
     }
 
@@ -54,6 +69,19 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
         self.local_enr.read().clone()
     }
 
+    /// Records that a peer was disconnected during the handshake.
+    pub fn record_handshake_failure(
+        &self,
+        peer_id: Option<&PeerId>,
+        reason: HandshakeFailureReason,
+        detail: String,
+    ) {
+        metrics::inc_counter_vec(&metrics::HANDSHAKE_FAILURES, &[reason.into()]);
+        self.handshake_failures
+            .write()
+            .record(peer_id, reason, detail);
+    }
+
     /// Returns the local libp2p PeerID.
     pub fn local_peer_id(&self) -> PeerId {
         *self.peer_id.read()
@@ -94,6 +122,11 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
         self.backfill_state.read().clone()
     }
 
+    /// Returns true if the outbound bandwidth is close to the configured budget.
+    pub fn is_near_bandwidth_budget(&self) -> bool {
+        *self.near_bandwidth_budget.read()
+    }
+
     /// Returns a `Client` type if one is known for the `PeerId`.
     pub fn client(&self, peer_id: &PeerId) -> Client {
         self.peers
@@ -0,0 +1,119 @@
+//! A record of the most recent peers which were disconnected during the handshake.
+//!
+//! Failures for many different reasons suggest that the network is unhealthy, whilst most peers
+//! failing for the same reason, e.g. an incompatible fork digest, suggests that the local node is
+//! misconfigured.
+use crate::PeerId;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+use strum::IntoStaticStr;
+
+/// The number of handshake failures retained.
+pub const RECENT_HANDSHAKE_FAILURES: usize = 256;
+
+/// The reason a peer was disconnected during the handshake.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, IntoStaticStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum HandshakeFailureReason {
+    /// The peer's fork digest differs from ours, i.e. it follows a different network or fork
+    /// schedule.
+    IncompatibleFork,
+    /// The peer's head is too far ahead of our current slot, i.e. either clock is wrong or the
+    /// genesis time differs.
+    ClockOrGenesisMismatch,
+    /// The peer has finalized a different chain.
+    DifferentFinalizedChain,
+    /// The peer's finalized chain previously failed to sync.
+    FailedChain,
+    /// The peer was already connected.
+    DuplicateConnection,
+}
+
+/// A peer which was disconnected during the handshake.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HandshakeFailure {
+    /// The peer, if it was known at the time of the failure.
+    pub peer_id: Option<String>,
+    pub reason: HandshakeFailureReason,
+    /// Details of the failure, such as the fork digests of both nodes.
+    pub detail: String,
+    /// Unix timestamp, in milliseconds, of the failure.
+    pub time: u64,
+}
+
+/// A bounded buffer of the most recent handshake failures.
+#[derive(Default)]
+pub struct HandshakeFailureLog {
+    failures: VecDeque<HandshakeFailure>,
+}
+
+impl HandshakeFailureLog {
+    /// Records a handshake failure, dropping the oldest failure if the buffer is full.
+    pub fn record(
+        &mut self,
+        peer_id: Option<&PeerId>,
+        reason: HandshakeFailureReason,
+        detail: String,
+    ) {
+        if self.failures.len() >= RECENT_HANDSHAKE_FAILURES {
+            self.failures.pop_front();
+        }
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        self.failures.push_back(HandshakeFailure {
+            peer_id: peer_id.map(ToString::to_string),
+            reason,
+            detail,
+            time,
+        });
+    }
+
+    /// Returns the retained failures, most recent first.
+    pub fn recent(&self) -> Vec<HandshakeFailure> {
+        self.failures.iter().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retains_most_recent_failures() {
+        let mut log = HandshakeFailureLog::default();
+        let peer_id = PeerId::random();
+
+        log.record(
+            Some(&peer_id),
+            HandshakeFailureReason::IncompatibleFork,
+            "first".into(),
+        );
+        for _ in 0..RECENT_HANDSHAKE_FAILURES {
+            log.record(None, HandshakeFailureReason::DuplicateConnection, "".into());
+        }
+        log.record(
+            Some(&peer_id),
+            HandshakeFailureReason::DifferentFinalizedChain,
+            "last".into(),
+        );
+
+        let recent = log.recent();
+        assert_eq!(recent.len(), RECENT_HANDSHAKE_FAILURES);
+        assert!(recent.iter().all(|failure| failure.detail != "first"));
+        assert_eq!(
+            recent[0].reason,
+            HandshakeFailureReason::DifferentFinalizedChain
+        );
+        assert_eq!(recent[0].peer_id, Some(peer_id.to_string()));
+    }
+
+    #[test]
+    fn reason_labels() {
+        let label: &'static str = HandshakeFailureReason::ClockOrGenesisMismatch.into();
+        assert_eq!(label, "clock_or_genesis_mismatch");
+    }
+}
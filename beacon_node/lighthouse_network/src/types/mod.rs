@@ -1,6 +1,9 @@
 pub mod error;
 mod globals;
+mod handshake_failures;
+mod nat_status;
 mod pubsub;
+mod rpc_trace;
 mod subnet;
 mod sync_state;
 mod topics;
@@ -13,7 +16,14 @@ pub type EnrSyncCommitteeBitfield<T> = BitVector<<T as EthSpec>::SyncCommitteeSu
 pub type Enr = discv5::enr::Enr<discv5::enr::CombinedKey>;
 
 pub use globals::NetworkGlobals;
+pub use handshake_failures::{
+    HandshakeFailure, HandshakeFailureLog, HandshakeFailureReason, RECENT_HANDSHAKE_FAILURES,
+};
+pub use nat_status::{NatMapping, NatStatus};
 pub use pubsub::{PubsubMessage, SnappyTransform};
+pub use rpc_trace::{
+    RpcRequestLog, RpcRequestOutcome, RpcRequestTrace, RpcTraceId, RECENT_RPC_REQUESTS,
+};
 pub use subnet::{Subnet, SubnetDiscovery};
 pub use sync_state::{BackFillState, SyncState};
 pub use topics::{
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// The state of the port mappings requested from the local router via UPnP.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NatStatus {
+    /// Whether port mappings are requested at all.
+    pub upnp_enabled: bool,
+    /// Whether a UPnP gateway responded to the most recent attempt.
+    pub gateway_found: bool,
+    /// The external IP address reported by the gateway.
+    pub external_ip: Option<IpAddr>,
+    /// The port mappings requested during the most recent attempt.
+    pub mappings: Vec<NatMapping>,
+    /// Unix timestamp, in seconds, of the most recent attempt to establish or renew the mappings.
+    pub last_renewal: Option<u64>,
+    /// The reason the most recent attempt failed, if it did.
+    pub last_renewal_error: Option<String>,
+}
+
+/// A single port mapping requested from the local router.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NatMapping {
+    /// Either `tcp` or `udp`.
+    pub protocol: String,
+    /// The use of the port, e.g. `libp2p`, `quic` or `discovery`.
+    pub service: String,
+    /// The port, which is the same internally and externally.
+    pub port: u16,
+    /// Whether the gateway accepted the mapping.
+    pub established: bool,
+    /// The lease duration granted in seconds, where 0 is a permanent lease.
+    pub lease_duration: u32,
+}
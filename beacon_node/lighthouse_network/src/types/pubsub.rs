@@ -165,31 +165,47 @@ impl<T: EthSpec> PubsubMessage<T> {
                         ))))
                     }
                     GossipKind::BeaconBlock => {
-                        let beacon_block =
-                            match fork_context.from_context_bytes(gossip_topic.fork_digest) {
-                                Some(ForkName::Base) => SignedBeaconBlock::<T>::Base(
-                                    SignedBeaconBlockBase::from_ssz_bytes(data)
-                                        .map_err(|e| format!("{:?}", e))?,
-                                ),
-                                Some(ForkName::Altair) => SignedBeaconBlock::<T>::Altair(
-                                    SignedBeaconBlockAltair::from_ssz_bytes(data)
-                                        .map_err(|e| format!("{:?}", e))?,
-                                ),
-                                Some(ForkName::Merge) => SignedBeaconBlock::<T>::Merge(
-                                    SignedBeaconBlockMerge::from_ssz_bytes(data)
-                                        .map_err(|e| format!("{:?}", e))?,
-                                ),
-                                Some(ForkName::Capella) => SignedBeaconBlock::<T>::Capella(
-                                    SignedBeaconBlockCapella::from_ssz_bytes(data)
-                                        .map_err(|e| format!("{:?}", e))?,
-                                ),
-                                None => {
-                                    return Err(format!(
-                                        "Unknown gossipsub fork digest: {:?}",
-                                        gossip_topic.fork_digest
-                                    ))
-                                }
-                            };
+                        let topic_fork = *fork_context
+                            .from_context_bytes(gossip_topic.fork_digest)
+                            .ok_or_else(|| {
+                                format!(
+                                    "Unknown gossipsub fork digest: {:?}",
+                                    gossip_topic.fork_digest
+                                )
+                            })?;
+
+                        // Read the slot without decoding the body, so that a block published on
+                        // the topic of another fork is rejected before its lists are allocated.
+                        let view = SignedBeaconBlock::<T>::from_ssz_bytes_view(data)
+                            .map_err(|e| format!("{:?}", e))?;
+                        let block_fork = fork_context.fork_name_at_slot(view.slot());
+                        if block_fork != topic_fork {
+                            return Err(format!(
+                                "Block from slot {} of fork {} published on {} topic",
+                                view.slot(),
+                                block_fork,
+                                topic_fork
+                            ));
+                        }
+
+                        let beacon_block = match topic_fork {
+                            ForkName::Base => SignedBeaconBlock::<T>::Base(
+                                SignedBeaconBlockBase::from_ssz_bytes(data)
+                                    .map_err(|e| format!("{:?}", e))?,
+                            ),
+                            ForkName::Altair => SignedBeaconBlock::<T>::Altair(
+                                SignedBeaconBlockAltair::from_ssz_bytes(data)
+                                    .map_err(|e| format!("{:?}", e))?,
+                            ),
+                            ForkName::Merge => SignedBeaconBlock::<T>::Merge(
+                                SignedBeaconBlockMerge::from_ssz_bytes(data)
+                                    .map_err(|e| format!("{:?}", e))?,
+                            ),
+                            ForkName::Capella => SignedBeaconBlock::<T>::Capella(
+                                SignedBeaconBlockCapella::from_ssz_bytes(data)
+                                    .map_err(|e| format!("{:?}", e))?,
+                            ),
+                        };
                         Ok(PubsubMessage::BeaconBlock(Arc::new(beacon_block)))
                     }
                     GossipKind::VoluntaryExit => {
@@ -320,3 +336,72 @@ impl<T: EthSpec> std::fmt::Display for PubsubMessage<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, BeaconBlockAltair, ChainSpec, Epoch, Hash256, MinimalEthSpec, Slot};
+
+    type E = MinimalEthSpec;
+
+    fn spec() -> ChainSpec {
+        let mut spec = E::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(1));
+        spec
+    }
+
+    fn block_topic(fork_context: &ForkContext, fork_name: ForkName) -> TopicHash {
+        let fork_digest = fork_context.to_context_bytes(fork_name).unwrap();
+        let topic = GossipTopic::new(
+            GossipKind::BeaconBlock,
+            GossipEncoding::default(),
+            fork_digest,
+        );
+        TopicHash::from_raw(String::from(topic))
+    }
+
+    fn altair_block(slot: Slot, spec: &ChainSpec) -> SignedBeaconBlock<E> {
+        let mut block = BeaconBlockAltair::empty(spec);
+        block.slot = slot;
+        SignedBeaconBlock::from_block(BeaconBlock::Altair(block), types::Signature::empty())
+    }
+
+    #[test]
+    fn decode_block() {
+        let spec = spec();
+        let fork_context = ForkContext::new::<E>(Slot::new(0), Hash256::zero(), &spec);
+        let block = altair_block(E::slots_per_epoch().into(), &spec);
+
+        let decoded = PubsubMessage::<E>::decode(
+            &block_topic(&fork_context, ForkName::Altair),
+            &block.as_ssz_bytes(),
+            &fork_context,
+        )
+        .unwrap();
+        assert_eq!(decoded, PubsubMessage::BeaconBlock(Arc::new(block)));
+    }
+
+    #[test]
+    fn decode_block_from_other_fork() {
+        let spec = spec();
+        let fork_context = ForkContext::new::<E>(Slot::new(0), Hash256::zero(), &spec);
+
+        // An Altair block from a slot before the Altair fork.
+        let block = altair_block(Slot::new(1), &spec);
+        assert!(PubsubMessage::<E>::decode(
+            &block_topic(&fork_context, ForkName::Altair),
+            &block.as_ssz_bytes(),
+            &fork_context,
+        )
+        .is_err());
+
+        // An Altair block on the topic of the base fork.
+        let block = altair_block(E::slots_per_epoch().into(), &spec);
+        assert!(PubsubMessage::<E>::decode(
+            &block_topic(&fork_context, ForkName::Base),
+            &block.as_ssz_bytes(),
+            &fork_context,
+        )
+        .is_err());
+    }
+}
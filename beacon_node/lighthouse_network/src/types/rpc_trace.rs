@@ -0,0 +1,248 @@
+//! A record of the most recent outbound RPC requests, for debugging stalled sync.
+//!
+//! Each outbound application request is assigned a trace id which accompanies the request through
+//! the RPC behaviour and handler. Response chunks, stream terminations, errors and timeouts are
+//! recorded against that id and logged with it, so a request can be followed from the sync
+//! layer to its outcome.
+use crate::PeerId;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// The number of outbound RPC requests retained.
+pub const RECENT_RPC_REQUESTS: usize = 256;
+
+/// A unique identifier assigned to each outbound application RPC request.
+pub type RpcTraceId = u64;
+
+/// The state of an outbound RPC request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcRequestOutcome {
+    /// No termination, error or timeout has been received yet.
+    Pending,
+    Completed,
+    Failed,
+    TimedOut,
+}
+
+/// An outbound RPC request and its progress.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RpcRequestTrace {
+    pub trace_id: RpcTraceId,
+    pub peer_id: String,
+    pub protocol: String,
+    /// The id assigned by the application, e.g. the sync chain or lookup which sent the request.
+    pub app_request_id: String,
+    /// Unix timestamp, in milliseconds, at which the request was sent.
+    pub sent_at: u64,
+    pub chunks_received: u64,
+    pub bytes_received: u64,
+    pub outcome: RpcRequestOutcome,
+    pub error: Option<String>,
+    /// The time taken to complete or fail the request, or the time elapsed so far if it is still
+    /// pending.
+    pub duration_ms: u64,
+}
+
+/// A bounded buffer of the most recent outbound RPC requests.
+#[derive(Default)]
+pub struct RpcRequestLog {
+    next_trace_id: RpcTraceId,
+    requests: VecDeque<(Instant, RpcRequestTrace)>,
+}
+
+impl RpcRequestLog {
+    /// Records a new outbound request, returning its trace id.
+    ///
+    /// The oldest request is dropped if the buffer is full.
+    pub fn start(
+        &mut self,
+        peer_id: &PeerId,
+        protocol: &str,
+        app_request_id: String,
+    ) -> RpcTraceId {
+        let trace_id = self.next_trace_id;
+        self.next_trace_id = self.next_trace_id.wrapping_add(1);
+
+        if self.requests.len() >= RECENT_RPC_REQUESTS {
+            self.requests.pop_front();
+        }
+        let sent_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        self.requests.push_back((
+            Instant::now(),
+            RpcRequestTrace {
+                trace_id,
+                peer_id: peer_id.to_string(),
+                protocol: protocol.to_string(),
+                app_request_id,
+                sent_at,
+                chunks_received: 0,
+                bytes_received: 0,
+                outcome: RpcRequestOutcome::Pending,
+                error: None,
+                duration_ms: 0,
+            },
+        ));
+        trace_id
+    }
+
+    /// Records a response chunk for the request.
+    pub fn on_chunk(&mut self, trace_id: RpcTraceId, bytes: usize) {
+        if let Some((_, trace)) = self.get_pending_mut(trace_id) {
+            trace.chunks_received += 1;
+            trace.bytes_received += bytes as u64;
+        }
+    }
+
+    /// Records the outcome of the request, which is no longer pending.
+    ///
+    /// Returns the time taken by the request in milliseconds if it was being tracked.
+    pub fn finish(
+        &mut self,
+        trace_id: RpcTraceId,
+        outcome: RpcRequestOutcome,
+        error: Option<String>,
+    ) -> Option<u64> {
+        let (started, trace) = self.get_pending_mut(trace_id)?;
+        trace.outcome = outcome;
+        trace.error = error;
+        trace.duration_ms = started.elapsed().as_millis() as u64;
+        Some(trace.duration_ms)
+    }
+
+    /// Fails every pending request to a peer which has disconnected, since no further responses
+    /// will be received for them.
+    ///
+    /// Returns the number of requests which were pending.
+    pub fn on_peer_disconnected(&mut self, peer_id: &PeerId) -> usize {
+        let peer_id = peer_id.to_string();
+        let mut pending = 0;
+        for (started, trace) in self.requests.iter_mut().filter(|(_, trace)| {
+            trace.peer_id == peer_id && trace.outcome == RpcRequestOutcome::Pending
+        }) {
+            trace.outcome = RpcRequestOutcome::Failed;
+            trace.error = Some("Peer disconnected".to_string());
+            trace.duration_ms = started.elapsed().as_millis() as u64;
+            pending += 1;
+        }
+        pending
+    }
+
+    /// Returns the retained requests, most recent first.
+    pub fn recent(&self) -> Vec<RpcRequestTrace> {
+        self.requests
+            .iter()
+            .rev()
+            .map(|(started, trace)| {
+                let mut trace = trace.clone();
+                if trace.outcome == RpcRequestOutcome::Pending {
+                    trace.duration_ms = started.elapsed().as_millis() as u64;
+                }
+                trace
+            })
+            .collect()
+    }
+
+    fn get_pending_mut(&mut self, trace_id: RpcTraceId) -> Option<&mut (Instant, RpcRequestTrace)> {
+        // Recent requests are the most likely to receive responses.
+        self.requests.iter_mut().rev().find(|(_, trace)| {
+            trace.trace_id == trace_id && trace.outcome == RpcRequestOutcome::Pending
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_request_progress() {
+        let mut log = RpcRequestLog::default();
+        let peer_id = PeerId::random();
+
+        let completed = log.start(&peer_id, "beacon_blocks_by_range", "RangeSync".into());
+        let failed = log.start(&peer_id, "beacon_blocks_by_root", "SingleBlock".into());
+        let pending = log.start(&peer_id, "status", "Router".into());
+        assert_ne!(completed, failed);
+
+        log.on_chunk(completed, 100);
+        log.on_chunk(completed, 50);
+        assert!(log
+            .finish(completed, RpcRequestOutcome::Completed, None)
+            .is_some());
+        assert!(log
+            .finish(
+                failed,
+                RpcRequestOutcome::TimedOut,
+                Some("Stream Timeout".into())
+            )
+            .is_some());
+        // Requests can only finish once.
+        assert!(log
+            .finish(completed, RpcRequestOutcome::Failed, None)
+            .is_none());
+
+        let recent = log.recent();
+        assert_eq!(
+            recent.iter().map(|t| t.trace_id).collect::<Vec<_>>(),
+            vec![pending, failed, completed]
+        );
+        assert_eq!(recent[0].outcome, RpcRequestOutcome::Pending);
+        assert_eq!(recent[1].outcome, RpcRequestOutcome::TimedOut);
+        assert_eq!(recent[1].error.as_deref(), Some("Stream Timeout"));
+        assert_eq!(recent[2].outcome, RpcRequestOutcome::Completed);
+        assert_eq!(recent[2].chunks_received, 2);
+        assert_eq!(recent[2].bytes_received, 150);
+    }
+
+    #[test]
+    fn fails_pending_requests_on_disconnect() {
+        let mut log = RpcRequestLog::default();
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+
+        let completed = log.start(&peer_id, "status", "Router".into());
+        let pending = log.start(&peer_id, "beacon_blocks_by_range", "RangeSync".into());
+        let other = log.start(&other_peer_id, "beacon_blocks_by_range", "RangeSync".into());
+        log.finish(completed, RpcRequestOutcome::Completed, None);
+
+        assert_eq!(log.on_peer_disconnected(&peer_id), 1);
+        // Only pending requests are finished, and only once.
+        assert_eq!(log.on_peer_disconnected(&peer_id), 0);
+
+        let recent = log.recent();
+        assert_eq!(
+            recent.iter().map(|t| t.trace_id).collect::<Vec<_>>(),
+            vec![other, pending, completed]
+        );
+        assert_eq!(recent[0].outcome, RpcRequestOutcome::Pending);
+        assert_eq!(recent[1].outcome, RpcRequestOutcome::Failed);
+        assert_eq!(recent[1].error.as_deref(), Some("Peer disconnected"));
+        assert_eq!(recent[2].outcome, RpcRequestOutcome::Completed);
+        assert!(log
+            .finish(pending, RpcRequestOutcome::Completed, None)
+            .is_none());
+    }
+
+    #[test]
+    fn drops_oldest_requests() {
+        let mut log = RpcRequestLog::default();
+        let peer_id = PeerId::random();
+
+        let first = log.start(&peer_id, "status", "Router".into());
+        for _ in 0..RECENT_RPC_REQUESTS {
+            log.start(&peer_id, "status", "Router".into());
+        }
+
+        let recent = log.recent();
+        assert_eq!(recent.len(), RECENT_RPC_REQUESTS);
+        assert!(recent.iter().all(|trace| trace.trace_id != first));
+        assert!(log
+            .finish(first, RpcRequestOutcome::Completed, None)
+            .is_none());
+    }
+}
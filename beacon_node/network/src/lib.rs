@@ -11,6 +11,8 @@ mod metrics;
 mod nat;
 mod network_beacon_processor;
 mod persisted_dht;
+mod persisted_peer_scores;
+mod persisted_trusted_peers;
 mod router;
 mod status;
 mod subnet_service;
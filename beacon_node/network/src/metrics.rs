@@ -7,8 +7,8 @@ use beacon_chain::{
 use fnv::FnvHashMap;
 pub use lighthouse_metrics::*;
 use lighthouse_network::{
-    peer_manager::peerdb::client::ClientKind, types::GossipKind, BandwidthSinks, GossipTopic,
-    Gossipsub, NetworkGlobals,
+    peer_manager::peerdb::client::ClientKind, service::utils::TransportBandwidth,
+    types::GossipKind, BandwidthSinks, GossipTopic, Gossipsub, NetworkGlobals,
 };
 use std::sync::Arc;
 use strum::IntoEnumIterator;
@@ -229,6 +229,16 @@ lazy_static! {
         "libp2p_total_bandwidth",
         "The total inbound/outbound bandwidth over libp2p"
     );
+    pub static ref INBOUND_LIBP2P_TRANSPORT_BYTES: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "libp2p_transport_inbound_bytes",
+        "The inbound bandwidth over libp2p per transport",
+        &["transport"]
+    );
+    pub static ref OUTBOUND_LIBP2P_TRANSPORT_BYTES: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "libp2p_transport_outbound_bytes",
+        "The outbound bandwidth over libp2p per transport",
+        &["transport"]
+    );
 
 
     /*
@@ -282,6 +292,18 @@ lazy_static! {
         "Count of times when a gossip block arrived from the network later than the attestation deadline.",
     );
 
+    /*
+     * Light client server publication.
+     */
+    pub static ref LIGHT_CLIENT_FINALITY_UPDATES_PUBLISHED: Result<IntCounter> = try_create_int_counter(
+        "light_client_finality_updates_published_total",
+        "Count of light client finality updates produced by this node and published on gossip."
+    );
+    pub static ref LIGHT_CLIENT_OPTIMISTIC_UPDATES_PUBLISHED: Result<IntCounter> = try_create_int_counter(
+        "light_client_optimistic_updates_published_total",
+        "Count of light client optimistic updates produced by this node and published on gossip."
+    );
+
     /*
      * Light client update reprocessing queue metrics.
      */
@@ -291,13 +313,31 @@ lazy_static! {
     );
 }
 
-pub fn update_bandwidth_metrics(bandwidth: Arc<BandwidthSinks>) {
+pub fn update_bandwidth_metrics(
+    bandwidth: Arc<BandwidthSinks>,
+    transport_bandwidth: &TransportBandwidth,
+) {
     set_gauge(&INBOUND_LIBP2P_BYTES, bandwidth.total_inbound() as i64);
     set_gauge(&OUTBOUND_LIBP2P_BYTES, bandwidth.total_outbound() as i64);
     set_gauge(
         &TOTAL_LIBP2P_BANDWIDTH,
         (bandwidth.total_inbound() + bandwidth.total_outbound()) as i64,
     );
+
+    let transports = std::iter::once(("tcp", &transport_bandwidth.tcp))
+        .chain(transport_bandwidth.quic.as_ref().map(|quic| ("quic", quic)));
+    for (transport, bandwidth) in transports {
+        set_gauge_vec(
+            &INBOUND_LIBP2P_TRANSPORT_BYTES,
+            &[transport],
+            bandwidth.total_inbound() as i64,
+        );
+        set_gauge_vec(
+            &OUTBOUND_LIBP2P_TRANSPORT_BYTES,
+            &[transport],
+            bandwidth.total_outbound() as i64,
+        );
+    }
 }
 
 pub fn register_finality_update_error(error: &LightClientFinalityUpdateError) {
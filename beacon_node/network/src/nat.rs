@@ -5,12 +5,24 @@
 
 use crate::{NetworkConfig, NetworkMessage};
 use if_addrs::get_if_addrs;
-use slog::{debug, info};
-use std::net::{IpAddr, SocketAddr, SocketAddrV4};
+use lighthouse_network::types::{NatMapping, NatStatus};
+use lighthouse_network::NetworkGlobals;
+use slog::{debug, info, warn};
+use std::net::{IpAddr, SocketAddrV4};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
 use tokio::sync::mpsc;
 use types::EthSpec;
 
+/// The lease requested for each UPnP port mapping, in seconds.
+const UPNP_LEASE_DURATION: u32 = 3_600;
+/// The interval at which the UPnP port mappings are renewed. This is well within the lease so that
+/// the mappings do not lapse, and also re-creates mappings lost if the router restarts.
+const UPNP_RENEWAL_INTERVAL: Duration = Duration::from_secs(UPNP_LEASE_DURATION as u64 / 2);
+
 /// Configuration required to construct the UPnP port mappings.
+#[derive(Clone)]
 pub struct UPnPConfig {
     /// The local TCP port.
     tcp_port: u16,
@@ -25,7 +37,7 @@ pub struct UPnPConfig {
 }
 
 /// Contains mappings that managed to be established.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct EstablishedUPnPMappings {
     /// A TCP port mapping for libp2p.
     pub tcp_port: Option<u16>,
@@ -59,118 +71,208 @@ impl UPnPConfig {
     }
 }
 
-/// Attempts to construct external port mappings with UPnP.
-pub fn construct_upnp_mappings<T: EthSpec>(
+/// Establishes external port mappings with UPnP and renews them every `UPNP_RENEWAL_INTERVAL`
+/// until shutdown, recording the outcome of each attempt in `network_globals`.
+///
+/// The network service is notified whenever the set of established mappings changes.
+pub async fn maintain_upnp_mappings<T: EthSpec>(
     config: UPnPConfig,
     network_send: mpsc::UnboundedSender<NetworkMessage<T>>,
+    network_globals: Arc<NetworkGlobals<T>>,
+    executor: TaskExecutor,
     log: slog::Logger,
 ) {
-    info!(log, "UPnP Attempting to initialise routes");
-    match igd::search_gateway(Default::default()) {
-        Err(e) => info!(log, "UPnP not available"; "error" => %e),
-        Ok(gateway) => {
-            // Need to find the local listening address matched with the router subnet
-            let interfaces = match get_if_addrs() {
-                Ok(v) => v,
-                Err(e) => {
-                    info!(log, "UPnP failed to get local interfaces"; "error" => %e);
-                    return;
-                }
-            };
-            let local_ip = interfaces.iter().find_map(|interface| {
-                // Just use the first IP of the first interface that is not a loopback and not an
-                // ipv6 address.
-                if !interface.is_loopback() {
-                    interface.ip().is_ipv4().then(|| interface.ip())
-                } else {
-                    None
-                }
-            });
+    let mut established = EstablishedUPnPMappings::default();
+    loop {
+        let mapping_config = config.clone();
+        let mapping_log = log.clone();
+        let (mappings, status) = match executor.spawn_blocking_handle(
+            move || construct_upnp_mappings(mapping_config, &mapping_log),
+            "UPnP",
+        ) {
+            Some(handle) => match handle.await {
+                Ok(result) => result,
+                Err(_) => return,
+            },
+            // The runtime is shutting down.
+            None => return,
+        };
 
-            let local_ip = match local_ip {
-                None => {
-                    info!(log, "UPnP failed to find local IP address");
-                    return;
-                }
-                Some(v) => v,
-            };
-
-            debug!(log, "UPnP Local IP Discovered"; "ip" => ?local_ip);
-
-            let mut mappings = EstablishedUPnPMappings::default();
-
-            match local_ip {
-                IpAddr::V4(address) => {
-                    let libp2p_socket = SocketAddrV4::new(address, config.tcp_port);
-                    let external_ip = gateway.get_external_ip();
-                    // We add specific port mappings rather than getting the router to arbitrary assign
-                    // one.
-                    // I've found this to be more reliable. If multiple users are behind a single
-                    // router, they should ideally try to set different port numbers.
-                    mappings.tcp_port = add_port_mapping(
-                        &gateway,
-                        igd::PortMappingProtocol::TCP,
-                        libp2p_socket,
-                        "tcp",
-                        &log,
-                    ).map(|_| {
-                        let external_socket = external_ip.as_ref().map(|ip| SocketAddr::new((*ip).into(), config.tcp_port)).map_err(|_| ());
-                        info!(log, "UPnP TCP route established"; "external_socket" => format!("{}:{}", external_socket.as_ref().map(|ip| ip.to_string()).unwrap_or_else(|_| "".into()), config.tcp_port));
-                        config.tcp_port
-                    }).ok();
-
-                    let set_udp_mapping = |udp_port| {
-                        let udp_socket = SocketAddrV4::new(address, udp_port);
-                        add_port_mapping(
-                            &gateway,
-                            igd::PortMappingProtocol::UDP,
-                            udp_socket,
-                            "udp",
-                            &log,
-                        ).map(|_| {
-                            info!(log, "UPnP UDP route established"; "external_socket" => format!("{}:{}", external_ip.as_ref().map(|ip| ip.to_string()).unwrap_or_else(|_| "".into()), udp_port));
-                        })
-                    };
-
-                    // Set the discovery UDP port mapping
-                    if !config.disable_discovery && set_udp_mapping(config.disc_port).is_ok() {
-                        mappings.udp_disc_port = Some(config.disc_port);
-                    }
+        if let Some(error) = &status.last_renewal_error {
+            warn!(log, "UPnP port mapping renewal failed"; "error" => error);
+        }
+        *network_globals.nat_status.write() = status;
 
-                    // Set the quic UDP port mapping
-                    if !config.disable_quic_support && set_udp_mapping(config.quic_port).is_ok() {
-                        mappings.udp_quic_port = Some(config.quic_port);
-                    }
+        // Report any updates to the network service.
+        if mappings.is_some() && mappings != established {
+            network_send
+                .send(NetworkMessage::UPnPMappingEstablished {
+                    mappings: mappings.clone(),
+                })
+                .unwrap_or_else(
+                    |e| debug!(log, "Could not send message to the network service"; "error" => %e),
+                );
+        }
+        established = mappings;
 
-                    // report any updates to the network service.
-                    if mappings.is_some() {
-                        network_send.send(NetworkMessage::UPnPMappingEstablished{ mappings })
-                .unwrap_or_else(|e| debug!(log, "Could not send message to the network service"; "error" => %e));
-                    }
-                }
-                _ => debug!(log, "UPnP no routes constructed. IPv6 not supported"),
-            }
+        tokio::time::sleep(UPNP_RENEWAL_INTERVAL).await;
+    }
+}
+
+/// Attempts to construct external port mappings with UPnP, returning the mappings that were
+/// established and the status of the attempt.
+fn construct_upnp_mappings(
+    config: UPnPConfig,
+    log: &slog::Logger,
+) -> (EstablishedUPnPMappings, NatStatus) {
+    let mut mappings = EstablishedUPnPMappings::default();
+    let mut status = NatStatus {
+        upnp_enabled: true,
+        last_renewal: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|duration| duration.as_secs()),
+        ..NatStatus::default()
+    };
+
+    debug!(log, "UPnP Attempting to initialise routes");
+    let gateway = match igd::search_gateway(Default::default()) {
+        Ok(gateway) => gateway,
+        Err(e) => {
+            info!(log, "UPnP not available"; "error" => %e);
+            status.last_renewal_error = Some(format!("UPnP not available: {}", e));
+            return (mappings, status);
         }
     };
+    status.gateway_found = true;
+
+    // Need to find the local listening address matched with the router subnet
+    let interfaces = match get_if_addrs() {
+        Ok(v) => v,
+        Err(e) => {
+            info!(log, "UPnP failed to get local interfaces"; "error" => %e);
+            status.last_renewal_error = Some(format!("failed to get local interfaces: {}", e));
+            return (mappings, status);
+        }
+    };
+    let local_ip = interfaces.iter().find_map(|interface| {
+        // Just use the first IP of the first interface that is not a loopback and not an
+        // ipv6 address.
+        if !interface.is_loopback() {
+            interface.ip().is_ipv4().then(|| interface.ip())
+        } else {
+            None
+        }
+    });
+
+    let address = match local_ip {
+        Some(IpAddr::V4(address)) => address,
+        _ => {
+            info!(log, "UPnP failed to find local IP address");
+            status.last_renewal_error = Some("failed to find a local IPv4 address".to_string());
+            return (mappings, status);
+        }
+    };
+
+    debug!(log, "UPnP Local IP Discovered"; "ip" => ?address);
+
+    let external_ip = gateway.get_external_ip().ok();
+    status.external_ip = external_ip.map(IpAddr::V4);
+    let external_ip_string = external_ip
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(String::new);
+
+    let mut set_mapping = |protocol, protocol_string, service: &str, port| {
+        let socket = SocketAddrV4::new(address, port);
+        let lease_duration = add_port_mapping(&gateway, protocol, socket, protocol_string, log);
+        if lease_duration.is_ok() {
+            debug!(
+                log,
+                "UPnP route established";
+                "protocol" => protocol_string,
+                "service" => service,
+                "external_socket" => format!("{}:{}", external_ip_string, port),
+            );
+        }
+        status.mappings.push(NatMapping {
+            protocol: protocol_string.to_string(),
+            service: service.to_string(),
+            port,
+            established: lease_duration.is_ok(),
+            lease_duration: lease_duration.unwrap_or(0),
+        });
+        lease_duration.is_ok().then_some(port)
+    };
+
+    // We add specific port mappings rather than getting the router to arbitrary assign
+    // one.
+    // I've found this to be more reliable. If multiple users are behind a single
+    // router, they should ideally try to set different port numbers.
+    mappings.tcp_port = set_mapping(
+        igd::PortMappingProtocol::TCP,
+        "tcp",
+        "libp2p",
+        config.tcp_port,
+    );
+
+    // Set the discovery UDP port mapping
+    if !config.disable_discovery {
+        mappings.udp_disc_port = set_mapping(
+            igd::PortMappingProtocol::UDP,
+            "udp",
+            "discovery",
+            config.disc_port,
+        );
+    }
+
+    // Set the quic UDP port mapping
+    if !config.disable_quic_support {
+        mappings.udp_quic_port = set_mapping(
+            igd::PortMappingProtocol::UDP,
+            "udp",
+            "quic",
+            config.quic_port,
+        );
+    }
+
+    if status.mappings.iter().any(|mapping| !mapping.established) {
+        status.last_renewal_error = Some("the gateway rejected some port mappings".to_string());
+    }
+
+    (mappings, status)
 }
 
-/// Sets up a port mapping for a protocol returning the mapped port if successful.
+/// Sets up a port mapping for a protocol, returning the lease duration granted if successful.
+///
+/// A lease of `UPNP_LEASE_DURATION` is requested, falling back to a permanent lease if the gateway
+/// does not support temporary leases.
 fn add_port_mapping(
     gateway: &igd::Gateway,
     protocol: igd::PortMappingProtocol,
     socket: SocketAddrV4,
     protocol_string: &'static str,
     log: &slog::Logger,
-) -> Result<(), ()> {
+) -> Result<u32, ()> {
     // We add specific port mappings rather than getting the router to arbitrary assign
     // one.
     // I've found this to be more reliable. If multiple users are behind a single
     // router, they should ideally try to set different port numbers.
     let mapping_string = &format!("lighthouse-{}", protocol_string);
-    for _ in 0..2 {
-        match gateway.add_port(protocol, socket.port(), socket, 0, mapping_string) {
+    let mut lease_duration = UPNP_LEASE_DURATION;
+    for _ in 0..3 {
+        match gateway.add_port(
+            protocol,
+            socket.port(),
+            socket,
+            lease_duration,
+            mapping_string,
+        ) {
             Err(e) => {
                 match e {
+                    igd::AddPortError::OnlyPermanentLeasesSupported => {
+                        debug!(log, "UPnP gateway only supports permanent leases"; "protocol" => protocol_string, "port" => socket.port());
+                        lease_duration = 0;
+                    }
                     igd::AddPortError::PortInUse => {
                         // Try and remove and re-create
                         debug!(log, "UPnP port in use, attempting to remap"; "protocol" => protocol_string, "port" => socket.port());
@@ -185,13 +287,13 @@ fn add_port_mapping(
                         }
                     }
                     e => {
-                        info!(log, "UPnP TCP route not set"; "error" => %e);
+                        info!(log, "UPnP route not set"; "protocol" => protocol_string, "error" => %e);
                         return Err(());
                     }
                 }
             }
             Ok(_) => {
-                return Ok(());
+                return Ok(lease_duration);
             }
         }
     }
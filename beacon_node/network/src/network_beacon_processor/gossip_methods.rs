@@ -13,8 +13,8 @@ use beacon_chain::{
     observed_operations::ObservationOutcome,
     sync_committee_verification::{self, Error as SyncCommitteeError},
     validator_monitor::get_block_delay_ms,
-    BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError, GossipVerifiedBlock,
-    NotifyExecutionLayer,
+    BeaconChainError, BeaconChainTypes, BlockError, BlockSource, ForkChoiceError,
+    GossipVerifiedBlock, NotifyExecutionLayer,
 };
 use lighthouse_network::{Client, MessageAcceptance, MessageId, PeerAction, PeerId, ReportSource};
 use operation_pool::ReceivedPreCapella;
@@ -694,6 +694,12 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             Some(peer_id.to_string()),
             Some(peer_client.to_string()),
         );
+        self.chain.observe_block_source(
+            block_root,
+            BlockSource::Gossip,
+            Some(peer_id.to_string()),
+            seen_duration,
+        );
 
         let verified_block = match verification_result {
             Ok(verified_block) => {
@@ -393,10 +393,13 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     }
 
     /// Create a new work event to import `blocks` as a beacon chain segment.
+    ///
+    /// `peer_id` is the peer which served the blocks, if they were all served by one peer.
     pub fn send_chain_segment(
         self: &Arc<Self>,
         process_id: ChainSegmentProcessId,
         blocks: Vec<Arc<SignedBeaconBlock<T::EthSpec>>>,
+        peer_id: Option<PeerId>,
     ) -> Result<(), Error<T::EthSpec>> {
         let is_backfill = matches!(&process_id, ChainSegmentProcessId::BackSyncBatchId { .. });
         let processor = self.clone();
@@ -412,7 +415,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 NotifyExecutionLayer::Yes
             };
             processor
-                .process_chain_segment(process_id, blocks, notify_execution_layer)
+                .process_chain_segment(process_id, blocks, peer_id, notify_execution_layer)
                 .await;
         };
         let process_fn = Box::pin(process_fn);
@@ -447,12 +450,17 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     }
 
     /// Create a new work event to process `BlocksByRangeRequest`s from the RPC network.
+    ///
+    /// Requests for finalized history are deprioritized whilst the node is near its outbound
+    /// bandwidth budget, so that serving syncing peers does not delay gossip.
     pub fn send_blocks_by_range_request(
         self: &Arc<Self>,
         peer_id: PeerId,
         request_id: PeerRequestId,
         request: BlocksByRangeRequest,
     ) -> Result<(), Error<T::EthSpec>> {
+        let deprioritize = self.network_globals.is_near_bandwidth_budget()
+            && self.is_historical_range_request(&request);
         let processor = self.clone();
         let process_fn = move |send_idle_on_drop| {
             let executor = processor.executor.clone();
@@ -465,12 +473,30 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             )
         };
 
+        let work = if deprioritize {
+            Work::HistoricalBlocksByRangeRequest(Box::new(process_fn))
+        } else {
+            Work::BlocksByRangeRequest(Box::new(process_fn))
+        };
+
         self.try_send(BeaconWorkEvent {
             drop_during_sync: false,
-            work: Work::BlocksByRangeRequest(Box::new(process_fn)),
+            work,
         })
     }
 
+    /// Returns true if the `request` starts before the finalized checkpoint.
+    fn is_historical_range_request(&self, request: &BlocksByRangeRequest) -> bool {
+        let finalized_slot = self
+            .chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+        Slot::new(*request.start_slot()) < finalized_slot
+    }
+
     /// Create a new work event to process `BlocksByRootRequest`s from the RPC network.
     pub fn send_blocks_by_roots_request(
         self: &Arc<Self>,
@@ -550,6 +576,7 @@ impl<E: EthSpec> NetworkBeaconProcessor<TestBeaconChainType<E>> {
             beacon_processor_rx,
             work_reprocessing_tx,
             work_reprocessing_rx: _work_reprocessing_rx,
+            status: _,
         } = <_>::default();
 
         let (network_tx, _network_rx) = mpsc::unbounded_channel();
@@ -7,6 +7,7 @@ use beacon_processor::SendOnDrop;
 use itertools::process_results;
 use lighthouse_network::rpc::StatusMessage;
 use lighthouse_network::rpc::*;
+use lighthouse_network::types::HandshakeFailureReason;
 use lighthouse_network::{PeerId, PeerRequestId, ReportSource, Response, SyncInfo};
 use slog::{debug, error, warn};
 use slot_clock::SlotClock;
@@ -58,20 +59,23 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     /* Processing functions */
 
     /// Process a `Status` message to determine if a peer is relevant to us. If the peer is
-    /// irrelevant the reason is returned.
+    /// irrelevant the reason is returned, along with a description.
     fn check_peer_relevance(
         &self,
         remote: &StatusMessage,
-    ) -> Result<Option<String>, BeaconChainError> {
+    ) -> Result<Option<(HandshakeFailureReason, String)>, BeaconChainError> {
         let local = self.chain.status_message();
         let start_slot = |epoch: Epoch| epoch.start_slot(T::EthSpec::slots_per_epoch());
 
         let irrelevant_reason = if local.fork_digest != remote.fork_digest {
             // The node is on a different network/fork
-            Some(format!(
-                "Incompatible forks Ours:{} Theirs:{}",
-                hex::encode(local.fork_digest),
-                hex::encode(remote.fork_digest)
+            Some((
+                HandshakeFailureReason::IncompatibleFork,
+                format!(
+                    "Incompatible forks Ours:{} Theirs:{}",
+                    hex::encode(local.fork_digest),
+                    hex::encode(remote.fork_digest)
+                ),
             ))
         } else if remote.head_slot
             > self
@@ -83,7 +87,10 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             // The remote's head is on a slot that is significantly ahead of what we consider the
             // current slot. This could be because they are using a different genesis time, or that
             // their or our system's clock is incorrect.
-            Some("Different system clocks or genesis time".to_string())
+            Some((
+                HandshakeFailureReason::ClockOrGenesisMismatch,
+                "Different system clocks or genesis time".to_string(),
+            ))
         } else if remote.finalized_epoch <= local.finalized_epoch
             && remote.finalized_root != Hash256::zero()
             && local.finalized_root != Hash256::zero()
@@ -95,7 +102,10 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             // The remote's finalized epoch is less than or equal to ours, but the block root is
             // different to the one in our chain. Therefore, the node is on a different chain and we
             // should not communicate with them.
-            Some("Different finalized chain".to_string())
+            Some((
+                HandshakeFailureReason::DifferentFinalizedChain,
+                "Different finalized chain".to_string(),
+            ))
         } else {
             None
         };
@@ -105,8 +115,10 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
     pub fn process_status(&self, peer_id: PeerId, status: StatusMessage) {
         match self.check_peer_relevance(&status) {
-            Ok(Some(irrelevant_reason)) => {
-                debug!(self.log, "Handshake Failure"; "peer" => %peer_id, "reason" => irrelevant_reason);
+            Ok(Some((reason, description))) => {
+                debug!(self.log, "Handshake Failure"; "peer" => %peer_id, "reason" => &description);
+                self.network_globals
+                    .record_handshake_failure(Some(&peer_id), reason, description);
                 self.goodbye_peer(peer_id, GoodbyeReason::IrrelevantNetwork);
             }
             Ok(None) => {
@@ -316,7 +328,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 return self.send_error_response(
                     peer_id,
                     RPCResponseErrorCode::ResourceUnavailable,
-                    "Backfilling".into(),
+                    format!("Backfilling, earliest available slot {}", oldest_block_slot),
                     request_id,
                 );
             }
@@ -8,15 +8,16 @@ use crate::sync::{
     ChainId,
 };
 use beacon_chain::{
-    observed_block_producers::Error as ObserveError, validator_monitor::get_block_delay_ms,
-    BeaconChainError, BeaconChainTypes, BlockError, ChainSegmentResult, HistoricalBlockError,
-    NotifyExecutionLayer,
+    observed_block_producers::Error as ObserveError,
+    validator_monitor::{get_block_delay_ms, timestamp_now},
+    BeaconChainError, BeaconChainTypes, BlockError, BlockSource, ChainSegmentResult,
+    HistoricalBlockError, NotifyExecutionLayer, SegmentSource,
 };
 use beacon_processor::{
     work_reprocessing_queue::{QueuedRpcBlock, ReprocessQueueMessage},
     AsyncFn, BlockingFn, DuplicateCache,
 };
-use lighthouse_network::PeerAction;
+use lighthouse_network::{PeerAction, PeerId};
 use slog::{debug, error, info, warn};
 use slot_clock::SlotClock;
 use std::sync::Arc;
@@ -208,6 +209,8 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
         let slot = block.slot();
         let parent_root = block.message().parent_root();
+        self.chain
+            .observe_block_source(block_root, BlockSource::RpcRoot, None, seen_timestamp);
         let result = self
             .chain
             .process_block(block_root, block, NotifyExecutionLayer::Yes, || Ok(()))
@@ -251,12 +254,21 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
     /// Attempt to import the chain segment (`blocks`) to the beacon chain, informing the sync
     /// thread if more blocks are needed to process it.
+    ///
+    /// `peer_id` is the peer which served the segment, if it was served by a single peer.
     pub async fn process_chain_segment(
         &self,
         sync_type: ChainSegmentProcessId,
         downloaded_blocks: Vec<Arc<SignedBeaconBlock<T::EthSpec>>>,
+        peer_id: Option<PeerId>,
         notify_execution_layer: NotifyExecutionLayer,
     ) {
+        let seen_timestamp = timestamp_now();
+        let segment_source = |source| SegmentSource {
+            source,
+            peer_id: peer_id.map(|peer_id| peer_id.to_string()),
+            seen_timestamp,
+        };
         let result = match sync_type {
             // this a request from the range sync
             ChainSegmentProcessId::RangeBatchId(chain_id, epoch) => {
@@ -265,7 +277,11 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 let sent_blocks = downloaded_blocks.len();
 
                 match self
-                    .process_blocks(downloaded_blocks.iter(), notify_execution_layer)
+                    .process_blocks(
+                        downloaded_blocks.iter(),
+                        segment_source(BlockSource::RpcRange),
+                        notify_execution_layer,
+                    )
                     .await
                 {
                     (_, Ok(_)) => {
@@ -344,7 +360,11 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 // parent blocks are ordered from highest slot to lowest, so we need to process in
                 // reverse
                 match self
-                    .process_blocks(downloaded_blocks.iter().rev(), notify_execution_layer)
+                    .process_blocks(
+                        downloaded_blocks.iter().rev(),
+                        segment_source(BlockSource::RpcRoot),
+                        notify_execution_layer,
+                    )
                     .await
                 {
                     (imported_blocks, Err(e)) => {
@@ -374,12 +394,13 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
     async fn process_blocks<'a>(
         &self,
         downloaded_blocks: impl Iterator<Item = &'a Arc<SignedBeaconBlock<T::EthSpec>>>,
+        segment_source: SegmentSource,
         notify_execution_layer: NotifyExecutionLayer,
     ) -> (usize, Result<(), ChainSegmentFailed>) {
         let blocks: Vec<Arc<_>> = downloaded_blocks.cloned().collect();
         match self
             .chain
-            .process_chain_segment(blocks, notify_execution_layer)
+            .process_chain_segment_from(blocks, segment_source, notify_execution_layer)
             .await
         {
             ChainSegmentResult::Successful { imported_blocks } => {
@@ -73,14 +73,13 @@ impl Drop for TestRig {
 
 impl TestRig {
     pub async fn new(chain_length: u64) -> Self {
-        Self::new_parametric(
-            chain_length,
-            BeaconProcessorConfig::default().enable_backfill_rate_limiting,
-        )
-        .await
+        Self::new_parametric(chain_length, BeaconProcessorConfig::default()).await
     }
 
-    pub async fn new_parametric(chain_length: u64, enable_backfill_rate_limiting: bool) -> Self {
+    pub async fn new_parametric(
+        chain_length: u64,
+        beacon_processor_config: BeaconProcessorConfig,
+    ) -> Self {
         // This allows for testing voluntary exits without building out a massive chain.
         let mut spec = E::default_spec();
         spec.shard_committee_period = 2;
@@ -175,13 +174,12 @@ impl TestRig {
 
         let log = harness.logger().clone();
 
-        let mut beacon_processor_config = BeaconProcessorConfig::default();
-        beacon_processor_config.enable_backfill_rate_limiting = enable_backfill_rate_limiting;
         let BeaconProcessorChannels {
             beacon_processor_tx,
             beacon_processor_rx,
             work_reprocessing_tx,
             work_reprocessing_rx,
+            status,
         } = BeaconProcessorChannels::new(&beacon_processor_config);
 
         let (sync_tx, _sync_rx) = mpsc::unbounded_channel();
@@ -220,6 +218,7 @@ impl TestRig {
             executor,
             current_workers: 0,
             config: beacon_processor_config,
+            status,
             log: log.clone(),
         }
         .spawn_manager(
@@ -301,6 +300,7 @@ impl TestRig {
             .send_chain_segment(
                 ChainSegmentProcessId::BackSyncBatchId(Epoch::default()),
                 Vec::default(),
+                None,
             )
             .unwrap();
     }
@@ -621,6 +621,38 @@ async fn import_gossip_attestation() {
     );
 }
 
+/// Ensure that attestations arriving within the batch window are processed by a single worker.
+#[tokio::test]
+async fn import_gossip_attestations_within_batch_window() {
+    let beacon_processor_config = BeaconProcessorConfig {
+        attestation_batch_window: Some(Duration::from_millis(500)),
+        ..BeaconProcessorConfig::default()
+    };
+    let mut rig = TestRig::new_parametric(SMALL_CHAIN, beacon_processor_config).await;
+
+    let initial_attns = rig.chain.naive_aggregation_pool.read().num_items();
+
+    // The rig only has one attestation per slot, so the second copy is rejected as a duplicate
+    // after the batch is verified.
+    rig.enqueue_unaggregated_attestation();
+    rig.enqueue_unaggregated_attestation();
+
+    rig.assert_event_journal(&[
+        GOSSIP_ATTESTATION,
+        GOSSIP_ATTESTATION,
+        ATTESTATION_BATCH_READY,
+        WORKER_FREED,
+        NOTHING_TO_DO,
+    ])
+    .await;
+
+    assert_eq!(
+        rig.chain.naive_aggregation_pool.read().num_items(),
+        initial_attns + 1,
+        "op pool should have one more attestation"
+    );
+}
+
 enum BlockImportMethod {
     Gossip,
     Rpc,
@@ -942,8 +974,11 @@ async fn test_backfill_sync_processing() {
 /// Ensure that backfill batches get processed as fast as they can when rate-limiting is disabled.
 #[tokio::test]
 async fn test_backfill_sync_processing_rate_limiting_disabled() {
-    let enable_backfill_rate_limiting = false;
-    let mut rig = TestRig::new_parametric(SMALL_CHAIN, enable_backfill_rate_limiting).await;
+    let beacon_processor_config = BeaconProcessorConfig {
+        enable_backfill_rate_limiting: false,
+        ..BeaconProcessorConfig::default()
+    };
+    let mut rig = TestRig::new_parametric(SMALL_CHAIN, beacon_processor_config).await;
 
     for _ in 0..3 {
         rig.enqueue_backfill_batch();
@@ -0,0 +1,89 @@
+use lighthouse_network::PeerId;
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `PeerScores`. All zero because `PeerScores` has its own column.
+pub const PEER_SCORES_DB_KEY: Hash256 = Hash256::zero();
+
+/// Load the scores of the peers which were penalised before the last shutdown.
+pub fn load_peer_scores<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Vec<(PeerId, f64)> {
+    match store.get_item(&PEER_SCORES_DB_KEY) {
+        Ok(Some(p)) => {
+            let p: PersistedPeerScores = p;
+            p.scores
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attempt to persist the scores of penalised peers to `self.store`.
+pub fn persist_peer_scores<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    scores: Vec<(PeerId, f64)>,
+) -> Result<(), store::Error> {
+    store.put_item(&PEER_SCORES_DB_KEY, &PersistedPeerScores { scores })
+}
+
+/// Wrapper around peer scores for persistence to disk.
+pub struct PersistedPeerScores {
+    pub scores: Vec<(PeerId, f64)>,
+}
+
+impl StoreItem for PersistedPeerScores {
+    fn db_column() -> DBColumn {
+        DBColumn::PeerScores
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        let mut stream = rlp::RlpStream::new_list(self.scores.len());
+        for (peer_id, score) in &self.scores {
+            stream.begin_list(2);
+            stream.append(&peer_id.to_bytes());
+            stream.append(&score.to_bits());
+        }
+        stream.out().to_vec()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let rlp_error = |e| StoreError::RlpError(format!("{}", e));
+        let scores = rlp::Rlp::new(bytes)
+            .iter()
+            .map(|item| {
+                let peer_id_bytes: Vec<u8> = item.val_at(0).map_err(rlp_error)?;
+                let score_bits: u64 = item.val_at(1).map_err(rlp_error)?;
+                let peer_id = PeerId::from_bytes(&peer_id_bytes)
+                    .map_err(|e| StoreError::RlpError(format!("{}", e)))?;
+                Ok((peer_id, f64::from_bits(score_bits)))
+            })
+            .collect::<Result<_, StoreError>>()?;
+        Ok(PersistedPeerScores { scores })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::{HotColdDB, MemoryStore};
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_peer_scores() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+        assert!(load_peer_scores(store.clone()).is_empty());
+
+        let scores = vec![(PeerId::random(), -10.5), (PeerId::random(), -42.0)];
+        persist_peer_scores(store.clone(), scores.clone()).unwrap();
+        assert_eq!(load_peer_scores(store), scores);
+    }
+}
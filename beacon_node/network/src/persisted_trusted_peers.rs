@@ -0,0 +1,87 @@
+use lighthouse_network::PeerId;
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, HotColdDB, ItemStore, StoreItem};
+use types::{EthSpec, Hash256};
+
+/// 32-byte key for accessing the `TrustedPeers`. All zero because `TrustedPeers` has its own
+/// column.
+pub const TRUSTED_PEERS_DB_KEY: Hash256 = Hash256::zero();
+
+/// Load the peers which were marked as trusted at runtime.
+pub fn load_trusted_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Vec<PeerId> {
+    match store.get_item(&TRUSTED_PEERS_DB_KEY) {
+        Ok(Some(p)) => {
+            let p: PersistedTrustedPeers = p;
+            p.peer_ids
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Attempt to persist the peers which were marked as trusted at runtime to `self.store`.
+pub fn persist_trusted_peers<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    peer_ids: Vec<PeerId>,
+) -> Result<(), store::Error> {
+    store.put_item(&TRUSTED_PEERS_DB_KEY, &PersistedTrustedPeers { peer_ids })
+}
+
+/// Wrapper around the trusted peers for persistence to disk.
+pub struct PersistedTrustedPeers {
+    pub peer_ids: Vec<PeerId>,
+}
+
+impl StoreItem for PersistedTrustedPeers {
+    fn db_column() -> DBColumn {
+        DBColumn::TrustedPeers
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        let peer_ids = self
+            .peer_ids
+            .iter()
+            .map(|peer_id| peer_id.to_bytes())
+            .collect::<Vec<_>>();
+        rlp::encode_list::<Vec<u8>, _>(&peer_ids).to_vec()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        let rlp = rlp::Rlp::new(bytes);
+        let peer_ids = rlp
+            .as_list::<Vec<u8>>()
+            .map_err(|e| StoreError::RlpError(format!("{}", e)))?
+            .into_iter()
+            .map(|bytes| {
+                PeerId::from_bytes(&bytes).map_err(|e| StoreError::RlpError(format!("{}", e)))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(PersistedTrustedPeers { peer_ids })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::{HotColdDB, MemoryStore};
+    use types::{ChainSpec, MinimalEthSpec};
+
+    #[test]
+    fn test_persisted_trusted_peers() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let store: HotColdDB<
+            MinimalEthSpec,
+            MemoryStore<MinimalEthSpec>,
+            MemoryStore<MinimalEthSpec>,
+        > = HotColdDB::open_ephemeral(StoreConfig::default(), ChainSpec::minimal(), log).unwrap();
+        let store = Arc::new(store);
+        assert!(load_trusted_peers(store.clone()).is_empty());
+
+        let peer_ids = vec![PeerId::random(), PeerId::random()];
+        persist_trusted_peers(store.clone(), peer_ids.clone()).unwrap();
+        assert_eq!(load_trusted_peers(store), peer_ids);
+    }
+}
@@ -2,6 +2,8 @@ use super::sync::manager::RequestId as SyncId;
 use crate::nat::EstablishedUPnPMappings;
 use crate::network_beacon_processor::InvalidBlockStorage;
 use crate::persisted_dht::{clear_dht, load_dht, persist_dht};
+use crate::persisted_peer_scores::{load_peer_scores, persist_peer_scores};
+use crate::persisted_trusted_peers::{load_trusted_peers, persist_trusted_peers};
 use crate::router::{Router, RouterMessage};
 use crate::subnet_service::SyncCommitteeService;
 use crate::{error, metrics};
@@ -20,14 +22,21 @@ use lighthouse_network::types::GossipKind;
 use lighthouse_network::{prometheus_client::registry::Registry, MessageAcceptance};
 use lighthouse_network::{
     rpc::{GoodbyeReason, RPCResponseErrorCode},
-    Context, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request, Response, Subnet,
+    Context, OperatorAction, PeerAction, PeerRequestId, PubsubMessage, ReportSource, Request,
+    Response, Subnet,
 };
 use lighthouse_network::{
     types::{core_topics_to_subscribe, GossipEncoding, GossipTopic},
     MessageId, NetworkEvent, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
-use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
+use slot_clock::SlotClock;
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
@@ -42,8 +51,16 @@ mod tests;
 
 /// The interval (in seconds) that various network metrics will update.
 const METRIC_UPDATE_INTERVAL: u64 = 5;
+/// The interval (in seconds) at which the outbound bandwidth is compared to the budget.
+const BANDWIDTH_BUDGET_UPDATE_INTERVAL: u64 = 2;
+/// The percentage of the outbound bandwidth budget beyond which the node is considered to be near
+/// its budget.
+const NEAR_BANDWIDTH_BUDGET_PERCENT: u64 = 90;
 /// Number of slots before the fork when we should subscribe to the new fork topics.
 const SUBSCRIBE_DELAY_SLOTS: u64 = 2;
+/// The maximum time to wait for in-flight requests to be answered and for peers to disconnect on
+/// shutdown. Must be shorter than the time the environment waits for services to shut down.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
 /// Delay after a fork where we unsubscribe from pre-fork topics.
 const UNSUBSCRIBE_DELAY_EPOCHS: u64 = 2;
 /// Size of the queue for validator subnet subscriptions. The number is chosen so that we may be
@@ -112,6 +129,13 @@ pub enum NetworkMessage<T: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Applies an action requested by the node operator to a peer.
+    OperatorAction {
+        peer_id: PeerId,
+        action: OperatorAction,
+        /// Identifies who requested the action, for the logs.
+        requested_by: String,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -191,12 +215,17 @@ pub struct NetworkService<T: BeaconChainTypes> {
     /// Stores potentially created UPnP mappings to be removed on shutdown. (TCP port and UDP
     /// ports).
     upnp_mappings: EstablishedUPnPMappings,
+    /// Peers marked as trusted by the operator at runtime, which are persisted to the store.
+    trusted_peers: HashSet<PeerId>,
     /// A delay that expires when a new fork takes place.
     next_fork_update: Pin<Box<OptionFuture<Sleep>>>,
     /// A delay that expires when we need to subscribe to a new fork's topics.
     next_fork_subscriptions: Pin<Box<OptionFuture<Sleep>>>,
     /// A delay that expires when we need to unsubscribe from old fork topics.
     next_unsubscribe: Pin<Box<OptionFuture<Sleep>>>,
+    /// A delay that expires when the light client updates of the current slot should be
+    /// published.
+    next_light_client_publish: Pin<Box<OptionFuture<Sleep>>>,
     /// Subscribe to all the subnets once synced.
     subscribe_all_subnets: bool,
     /// Shutdown beacon node after sync is complete.
@@ -205,10 +234,24 @@ pub struct NetworkService<T: BeaconChainTypes> {
     metrics_enabled: bool,
     /// A timer for updating various network metrics.
     metrics_update: tokio::time::Interval,
+    /// The outbound bandwidth budget in bytes per second, if any.
+    outbound_bandwidth_budget: Option<u64>,
+    /// A timer for comparing the outbound bandwidth to the budget.
+    bandwidth_budget_update: tokio::time::Interval,
+    /// The total outbound bytes at the last bandwidth budget update.
+    last_outbound_bytes: Option<(Instant, u64)>,
+    /// A timer for advertising the earliest slot from which we can serve blocks.
+    earliest_available_slot_update: tokio::time::Interval,
     /// gossipsub_parameter_update timer
     gossipsub_parameter_update: tokio::time::Interval,
     /// enable_light_client_server indicator
     enable_light_client_server: bool,
+    /// The requests received from peers which have not been answered completely.
+    pending_inbound_requests: HashSet<(PeerId, PeerRequestId)>,
+    /// Set once the exit signal has fired, after which inbound requests are refused.
+    shutting_down: bool,
+    /// Stops the router and the beacon processor once the network has drained on shutdown.
+    drained_signal: Option<exit_future::Signal>,
     /// The logger for the network service.
     fork_context: Arc<ForkContext>,
     log: slog::Logger,
@@ -223,7 +266,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         gossipsub_registry: Option<&'_ mut Registry>,
         beacon_processor_send: BeaconProcessorSend<T::EthSpec>,
         beacon_processor_reprocess_tx: mpsc::Sender<ReprocessQueueMessage>,
-    ) -> error::Result<(Arc<NetworkGlobals<T::EthSpec>>, NetworkSenders<T::EthSpec>)> {
+    ) -> error::Result<(
+        Arc<NetworkGlobals<T::EthSpec>>,
+        NetworkSenders<T::EthSpec>,
+        exit_future::Exit,
+    )> {
         let network_log = executor.log().clone();
         // build the channels for external comms
         let (network_senders, network_recievers) = NetworkSenders::new();
@@ -234,24 +281,6 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             "Backfill is disabled. DO NOT RUN IN PRODUCTION"
         );
 
-        // try and construct UPnP port mappings if required.
-        if let Some(upnp_config) = crate::nat::UPnPConfig::from_config(config) {
-            let upnp_log = network_log.new(o!("service" => "UPnP"));
-            let upnp_network_send = network_senders.network_send();
-            if config.upnp_enabled {
-                executor.spawn_blocking(
-                    move || {
-                        crate::nat::construct_upnp_mappings(
-                            upnp_config,
-                            upnp_network_send,
-                            upnp_log,
-                        )
-                    },
-                    "UPnP",
-                );
-            }
-        }
-
         // get a reference to the beacon chain store
         let store = beacon_chain.store.clone();
 
@@ -261,6 +290,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // keep track of when our fork_id needs to be updated
         let next_fork_update = Box::pin(next_fork_delay(&beacon_chain).into());
         let next_fork_subscriptions = Box::pin(next_fork_subscriptions_delay(&beacon_chain).into());
+        let next_light_client_publish = Box::pin(
+            next_light_client_publish_delay(&beacon_chain, config.enable_light_client_server)
+                .into(),
+        );
         let next_unsubscribe = Box::pin(None.into());
 
         let current_slot = beacon_chain
@@ -289,6 +322,25 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         let (mut libp2p, network_globals) =
             Network::new(executor.clone(), service_context, &network_log).await?;
 
+        // try and construct UPnP port mappings if required, renewing them periodically.
+        if let Some(upnp_config) = crate::nat::UPnPConfig::from_config(config) {
+            let upnp_log = network_log.new(o!("service" => "UPnP"));
+            let upnp_network_send = network_senders.network_send();
+            if config.upnp_enabled {
+                network_globals.nat_status.write().upnp_enabled = true;
+                executor.spawn(
+                    crate::nat::maintain_upnp_mappings(
+                        upnp_config,
+                        upnp_network_send,
+                        network_globals.clone(),
+                        executor.clone(),
+                        upnp_log,
+                    ),
+                    "UPnP",
+                );
+            }
+        }
+
         // Repopulate the DHT with stored ENR's if discovery is not disabled.
         if !config.disable_discovery {
             let enrs_to_load = load_dht::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone());
@@ -301,6 +353,27 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             }
         }
 
+        // Restore the peers which were marked as trusted at runtime.
+        let trusted_peers =
+            load_trusted_peers::<T::EthSpec, T::HotStore, T::ColdStore>(store.clone())
+                .into_iter()
+                .collect::<HashSet<_>>();
+        if !trusted_peers.is_empty() {
+            debug!(
+                network_log,
+                "Restoring trusted peers"; "peers" => trusted_peers.len()
+            );
+        }
+        for peer_id in &trusted_peers {
+            libp2p.operator_action(peer_id, OperatorAction::Protect, "persisted trusted peers");
+        }
+
+        // Restore the scores of peers which were penalised before the last shutdown, so that
+        // misbehaving peers cannot escape their penalties by waiting for a restart.
+        libp2p.restore_peer_scores(load_peer_scores::<T::EthSpec, T::HotStore, T::ColdStore>(
+            store.clone(),
+        ));
+
         let invalid_block_storage = config
             .invalid_block_storage
             .clone()
@@ -309,12 +382,17 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
         // launch derived network services
 
+        // The router keeps running after the exit signal until the network has drained, so that
+        // in-flight requests can still be answered. The returned `Exit` does the same for the
+        // beacon processor.
+        let (drained_signal, drained) = exit_future::signal();
+
         // router task
         let router_send = Router::spawn(
             beacon_chain.clone(),
             network_globals.clone(),
             network_senders.network_send(),
-            executor.clone(),
+            executor.with_exit(drained.clone()),
             invalid_block_storage,
             beacon_processor_send,
             beacon_processor_reprocess_tx,
@@ -335,6 +413,15 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         // create a timer for updating network metrics
         let metrics_update = tokio::time::interval(Duration::from_secs(METRIC_UPDATE_INTERVAL));
 
+        // create a timer for comparing the outbound bandwidth to the budget
+        let bandwidth_budget_update =
+            tokio::time::interval(Duration::from_secs(BANDWIDTH_BUDGET_UPDATE_INTERVAL));
+
+        // create a timer for advertising the earliest available slot once per epoch
+        let earliest_available_slot_update = tokio::time::interval(Duration::from_secs(
+            beacon_chain.spec.seconds_per_slot * T::EthSpec::slots_per_epoch(),
+        ));
+
         // create a timer for updating gossipsub parameters
         let gossipsub_parameter_update = tokio::time::interval(Duration::from_secs(60));
 
@@ -356,14 +443,23 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             store,
             network_globals: network_globals.clone(),
             upnp_mappings: EstablishedUPnPMappings::default(),
+            trusted_peers,
             next_fork_update,
             next_fork_subscriptions,
             next_unsubscribe,
+            next_light_client_publish,
             subscribe_all_subnets: config.subscribe_all_subnets,
             shutdown_after_sync: config.shutdown_after_sync,
             metrics_enabled: config.metrics_enabled,
             metrics_update,
+            outbound_bandwidth_budget: config.outbound_bandwidth_budget,
+            bandwidth_budget_update,
+            last_outbound_bytes: None,
+            earliest_available_slot_update,
             gossipsub_parameter_update,
+            pending_inbound_requests: HashSet::new(),
+            shutting_down: false,
+            drained_signal: Some(drained_signal),
             fork_context,
             log: network_log,
             enable_light_client_server: config.enable_light_client_server,
@@ -371,7 +467,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
         network_service.spawn_service(executor);
 
-        Ok((network_globals, network_senders))
+        Ok((network_globals, network_senders, drained))
     }
 
     /// Returns the required fork digests that gossipsub needs to subscribe to based on the current slot.
@@ -419,11 +515,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
     fn spawn_service(mut self, executor: task_executor::TaskExecutor) {
         let mut shutdown_sender = executor.shutdown_sender();
+        let mut exit = executor.exit();
 
         // spawn on the current executor
         let service_fut = async move {
             loop {
                 tokio::select! {
+                    _ = &mut exit => break,
+
                     _ = self.metrics_update.tick(), if self.metrics_enabled => {
                         // update various network metrics
                         metrics::update_gossip_metrics::<T::EthSpec>(
@@ -434,6 +533,12 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                         metrics::update_sync_metrics(&self.network_globals);
                     }
 
+                    _ = self.bandwidth_budget_update.tick(), if self.outbound_bandwidth_budget.is_some() => {
+                        self.update_bandwidth_budget();
+                    }
+
+                    _ = self.earliest_available_slot_update.tick() => self.update_earliest_available_slot(),
+
                     _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
 
                     // handle a message sent to the network
@@ -452,6 +557,8 @@ impl<T: BeaconChainTypes> NetworkService<T> {
 
                     Some(_) = &mut self.next_fork_update => self.update_next_fork(),
 
+                    Some(_) = &mut self.next_light_client_publish => self.publish_light_client_updates(),
+
                     Some(_) = &mut self.next_unsubscribe => {
                         let new_enr_fork_id = self.beacon_chain.enr_fork_id();
                         self.libp2p.unsubscribe_from_fork_topics_except(new_enr_fork_id.fork_digest);
@@ -472,10 +579,85 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                         }
                     }
                 }
-                metrics::update_bandwidth_metrics(self.libp2p.bandwidth.clone());
+                metrics::update_bandwidth_metrics(
+                    self.libp2p.bandwidth.clone(),
+                    &self.libp2p.transport_bandwidth,
+                );
             }
+            self.shutdown_gracefully(&mut shutdown_sender).await;
         };
-        executor.spawn(service_fut, "network");
+        executor.spawn_graceful(service_fut, "network");
+    }
+
+    /// Answers the requests which were received before the exit signal, then says goodbye to all
+    /// peers and keeps driving the swarm until they have disconnected. Both steps end once
+    /// `SHUTDOWN_DRAIN_TIMEOUT` has elapsed. Finally the router and the beacon processor are
+    /// stopped and the scores of penalised peers are persisted.
+    ///
+    /// New requests are refused while shutting down, so that the drain can finish.
+    async fn shutdown_gracefully(&mut self, shutdown_sender: &mut Sender<ShutdownReason>) {
+        self.shutting_down = true;
+        let deadline = tokio::time::sleep(SHUTDOWN_DRAIN_TIMEOUT);
+        tokio::pin!(deadline);
+
+        if !self.pending_inbound_requests.is_empty() {
+            info!(
+                self.log,
+                "Answering in-flight requests";
+                "requests" => self.pending_inbound_requests.len(),
+            );
+        }
+        let mut timed_out = false;
+        while !self.pending_inbound_requests.is_empty() {
+            tokio::select! {
+                _ = &mut deadline => {
+                    debug!(
+                        self.log,
+                        "Timed out waiting for in-flight requests";
+                        "requests" => self.pending_inbound_requests.len(),
+                    );
+                    timed_out = true;
+                    break;
+                }
+                // The router and the beacon processor are still running, and send their
+                // responses via the network channel.
+                Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, shutdown_sender).await,
+                event = self.libp2p.next_event() => self.on_libp2p_event(event, shutdown_sender).await,
+            }
+        }
+
+        let peers = self
+            .libp2p
+            .disconnect_all_peers(GoodbyeReason::ClientShutdown);
+        info!(self.log, "Disconnecting from peers"; "peers" => peers);
+
+        while !timed_out && self.network_globals.connected_or_dialing_peers() > 0 {
+            tokio::select! {
+                _ = &mut deadline => {
+                    debug!(
+                        self.log,
+                        "Timed out waiting for peers to disconnect";
+                        "peers" => self.network_globals.connected_or_dialing_peers(),
+                    );
+                    break;
+                }
+                Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, shutdown_sender).await,
+                event = self.libp2p.next_event() => self.on_libp2p_event(event, shutdown_sender).await,
+            }
+        }
+
+        if let Some(drained_signal) = self.drained_signal.take() {
+            let _ = drained_signal.fire();
+        }
+
+        let scores = self.network_globals.peers.read().penalised_peer_scores();
+        match persist_peer_scores::<T::EthSpec, T::HotStore, T::ColdStore>(
+            self.store.clone(),
+            scores,
+        ) {
+            Ok(()) => debug!(self.log, "Saved peer scores"),
+            Err(e) => error!(self.log, "Failed to persist peer scores"; "error" => ?e),
+        }
     }
 
     /// Handle an event received from the network.
@@ -492,6 +674,8 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 // No action required for this event.
             }
             NetworkEvent::PeerDisconnected(peer_id) => {
+                self.pending_inbound_requests
+                    .retain(|(pending_peer_id, _)| *pending_peer_id != peer_id);
                 self.send_to_router(RouterMessage::PeerDisconnected(peer_id));
             }
             NetworkEvent::RequestReceived {
@@ -499,11 +683,21 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 id,
                 request,
             } => {
-                self.send_to_router(RouterMessage::RPCRequestReceived {
-                    peer_id,
-                    id,
-                    request,
-                });
+                if self.shutting_down {
+                    self.libp2p.send_error_reponse(
+                        peer_id,
+                        id,
+                        RPCResponseErrorCode::ResourceUnavailable,
+                        "Shutting down".into(),
+                    );
+                } else {
+                    self.pending_inbound_requests.insert((peer_id, id));
+                    self.send_to_router(RouterMessage::RPCRequestReceived {
+                        peer_id,
+                        id,
+                        request,
+                    });
+                }
             }
             NetworkEvent::ResponseReceived {
                 peer_id,
@@ -601,6 +795,13 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 response,
                 id,
             } => {
+                // Streamed responses are complete once the stream is terminated.
+                if !matches!(
+                    response,
+                    Response::BlocksByRange(Some(_)) | Response::BlocksByRoot(Some(_))
+                ) {
+                    self.pending_inbound_requests.remove(&(peer_id, id));
+                }
                 self.libp2p.send_response(peer_id, id, response);
             }
             NetworkMessage::SendErrorResponse {
@@ -609,6 +810,7 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 id,
                 reason,
             } => {
+                self.pending_inbound_requests.remove(&(peer_id, id));
                 self.libp2p.send_error_reponse(peer_id, id, error, reason);
             }
             NetworkMessage::UPnPMappingEstablished { mappings } => {
@@ -668,6 +870,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::OperatorAction {
+                peer_id,
+                action,
+                requested_by,
+            } => {
+                self.libp2p.operator_action(&peer_id, action, &requested_by);
+                self.update_trusted_peers(peer_id, action);
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
@@ -793,6 +1003,27 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Records a change to the trusted status of a peer requested by the operator, persisting
+    /// the trusted peers so that they remain trusted after a restart.
+    fn update_trusted_peers(&mut self, peer_id: PeerId, action: OperatorAction) {
+        let changed = match action {
+            OperatorAction::Protect => self.trusted_peers.insert(peer_id),
+            OperatorAction::Unprotect | OperatorAction::Ban => self.trusted_peers.remove(&peer_id),
+            OperatorAction::Unban | OperatorAction::Disconnect => false,
+        };
+        if !changed {
+            return;
+        }
+
+        let peer_ids = self.trusted_peers.iter().copied().collect();
+        if let Err(e) = persist_trusted_peers::<T::EthSpec, T::HotStore, T::ColdStore>(
+            self.store.clone(),
+            peer_ids,
+        ) {
+            error!(self.log, "Failed to persist trusted peers"; "error" => ?e);
+        }
+    }
+
     fn update_gossipsub_parameters(&mut self) {
         if let Ok(slot) = self.beacon_chain.slot() {
             let active_validators_opt = self
@@ -912,6 +1143,89 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    /// Compares the outbound bandwidth since the last update to the budget, and records in the
+    /// network globals whether the node is near it.
+    fn update_bandwidth_budget(&mut self) {
+        let budget = match self.outbound_bandwidth_budget {
+            Some(budget) => budget,
+            None => return,
+        };
+        let now = Instant::now();
+        let total_outbound = self.libp2p.bandwidth.total_outbound();
+
+        if let Some((last_update, last_outbound)) = self.last_outbound_bytes {
+            let near_budget = is_near_bandwidth_budget(
+                total_outbound.saturating_sub(last_outbound),
+                now.duration_since(last_update),
+                budget,
+            );
+            let was_near_budget = std::mem::replace(
+                &mut *self.network_globals.near_bandwidth_budget.write(),
+                near_budget,
+            );
+            if near_budget != was_near_budget {
+                debug!(
+                    self.log,
+                    "Outbound bandwidth budget status changed";
+                    "near_budget" => near_budget,
+                    "budget_bytes_per_sec" => budget,
+                );
+            }
+        }
+        self.last_outbound_bytes = Some((now, total_outbound));
+    }
+
+    /// Advertises the earliest slot from which we can serve blocks, which is later than genesis
+    /// whilst backfilling after a checkpoint sync.
+    fn update_earliest_available_slot(&mut self) {
+        let earliest_available_slot = self
+            .beacon_chain
+            .store
+            .get_anchor_info()
+            .map_or(self.beacon_chain.spec.genesis_slot, |anchor| {
+                anchor.oldest_block_slot
+            });
+        self.libp2p
+            .update_earliest_available_slot(earliest_available_slot);
+    }
+
+    /// Publish the light client updates produced for the current slot, if any.
+    fn publish_light_client_updates(&mut self) {
+        if let Ok(current_slot) = self.beacon_chain.slot() {
+            let mut messages = vec![];
+
+            if let Some(update) = self
+                .beacon_chain
+                .light_client_finality_update_to_publish(current_slot)
+            {
+                metrics::inc_counter(&metrics::LIGHT_CLIENT_FINALITY_UPDATES_PUBLISHED);
+                messages.push(PubsubMessage::LightClientFinalityUpdate(Box::new(update)));
+            }
+            if let Some(update) = self
+                .beacon_chain
+                .light_client_optimistic_update_to_publish(current_slot)
+            {
+                metrics::inc_counter(&metrics::LIGHT_CLIENT_OPTIMISTIC_UPDATES_PUBLISHED);
+                messages.push(PubsubMessage::LightClientOptimisticUpdate(Box::new(update)));
+            }
+
+            if !messages.is_empty() {
+                debug!(
+                    self.log,
+                    "Publishing light client updates";
+                    "count" => messages.len(),
+                    "slot" => current_slot,
+                );
+                self.libp2p.publish(messages);
+            }
+        }
+
+        self.next_light_client_publish = Box::pin(
+            next_light_client_publish_delay(&self.beacon_chain, self.enable_light_client_server)
+                .into(),
+        );
+    }
+
     fn subscribed_core_topics(&self) -> bool {
         let core_topics = core_topics_to_subscribe(self.fork_context.current_fork());
         let core_topics: HashSet<&GossipKind> = HashSet::from_iter(&core_topics);
@@ -923,6 +1237,13 @@ impl<T: BeaconChainTypes> NetworkService<T> {
     }
 }
 
+/// Returns true if sending `sent_bytes` over `elapsed` is close to a budget of `budget` bytes per
+/// second.
+fn is_near_bandwidth_budget(sent_bytes: u64, elapsed: Duration, budget: u64) -> bool {
+    let budget_bytes = budget as f64 * elapsed.as_secs_f64();
+    sent_bytes as f64 * 100.0 >= budget_bytes * NEAR_BANDWIDTH_BUDGET_PERCENT as f64
+}
+
 /// Returns a `Sleep` that triggers after the next change in the beacon chain fork version.
 /// If there is no scheduled fork, `None` is returned.
 fn next_fork_delay<T: BeaconChainTypes>(
@@ -933,6 +1254,26 @@ fn next_fork_delay<T: BeaconChainTypes>(
         .map(|(_, until_fork)| tokio::time::sleep(until_fork))
 }
 
+/// Returns a `Sleep` that triggers one third of the way through the next slot, when the block of
+/// that slot should have been imported and light clients accept updates signed in that slot.
+/// Returns `None` if the light client server is disabled.
+fn next_light_client_publish_delay<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    enable_light_client_server: bool,
+) -> Option<tokio::time::Sleep> {
+    if !enable_light_client_server {
+        return None;
+    }
+
+    let slot_clock = &beacon_chain.slot_clock;
+    let one_third_slot = slot_clock.slot_duration() / 3;
+    let delay = match slot_clock.millis_from_current_slot_start() {
+        Some(into_slot) if into_slot < one_third_slot => one_third_slot - into_slot,
+        _ => slot_clock.duration_to_next_slot()? + one_third_slot,
+    };
+    Some(tokio::time::sleep(delay))
+}
+
 /// Returns a `Sleep` that triggers `SUBSCRIBE_DELAY_SLOTS` before the next fork.
 /// Returns `None` if there are no scheduled forks or we are already past `current_slot + SUBSCRIBE_DELAY_SLOTS > fork_slot`.
 fn next_fork_subscriptions_delay<T: BeaconChainTypes>(
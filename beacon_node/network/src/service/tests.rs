@@ -3,15 +3,19 @@
 mod tests {
     use crate::persisted_dht::load_dht;
     use crate::{NetworkConfig, NetworkService};
-    use beacon_chain::test_utils::BeaconChainHarness;
-    use beacon_processor::BeaconProcessorChannels;
-    use lighthouse_network::Enr;
+    use beacon_chain::test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy};
+    use beacon_processor::{BeaconProcessor, BeaconProcessorChannels};
+    use lighthouse_network::multiaddr::Protocol;
+    use lighthouse_network::rpc::BlocksByRangeRequest;
+    use lighthouse_network::service::Network;
+    use lighthouse_network::{Context, Enr, NetworkEvent, Request, Response};
     use slog::{o, Drain, Level, Logger};
     use sloggers::{null::NullLoggerBuilder, Build};
     use std::str::FromStr;
     use std::sync::Arc;
+    use std::time::Duration;
     use tokio::runtime::Runtime;
-    use types::MinimalEthSpec;
+    use types::{ForkContext, MinimalEthSpec};
 
     fn get_logger(actual_log: bool) -> Logger {
         if actual_log {
@@ -73,9 +77,10 @@ mod tests {
                 beacon_processor_rx: _beacon_processor_rx,
                 work_reprocessing_tx,
                 work_reprocessing_rx: _work_reprocessing_rx,
+                status: _,
             } = <_>::default();
 
-            let _network_service = NetworkService::start(
+            let (_network_globals, _network_senders, _network_drained) = NetworkService::start(
                 beacon_chain.clone(),
                 &config,
                 executor,
@@ -102,4 +107,185 @@ mod tests {
             "should have persisted the second ENR to store"
         );
     }
+
+    #[test]
+    fn in_flight_blocks_by_range_completes_during_shutdown() {
+        type E = MinimalEthSpec;
+        const NUM_BLOCKS: u64 = 8;
+
+        let log = get_logger(false);
+        let runtime = Arc::new(Runtime::new().unwrap());
+
+        let harness = BeaconChainHarness::builder(MinimalEthSpec)
+            .default_spec()
+            .deterministic_keypairs(8)
+            .fresh_ephemeral_store()
+            .build();
+        let chain = harness.chain.clone();
+
+        let (signal, exit) = exit_future::signal();
+        let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+        let executor = task_executor::TaskExecutor::new(
+            Arc::downgrade(&runtime),
+            exit,
+            log.clone(),
+            shutdown_tx,
+        );
+
+        // The client runs on its own exit signal so that it outlives the service.
+        let (_client_signal, client_exit) = exit_future::signal();
+        let (client_shutdown_tx, _) = futures::channel::mpsc::channel(1);
+        let client_executor = task_executor::TaskExecutor::new(
+            Arc::downgrade(&runtime),
+            client_exit,
+            log.clone(),
+            client_shutdown_tx,
+        );
+
+        let build_config = |name: &str| {
+            let mut config = NetworkConfig::default();
+            config.set_ipv4_listening_address(std::net::Ipv4Addr::LOCALHOST, 0, 0, 0);
+            config.upnp_enabled = false;
+            config.disable_discovery = true;
+            config.network_dir = tempfile::Builder::new()
+                .prefix(name)
+                .tempdir()
+                .unwrap()
+                .into_path();
+            config
+        };
+        let service_config = build_config("network_service");
+        let client_config = build_config("network_client");
+
+        runtime.block_on(async move {
+            harness.advance_slot();
+            harness
+                .extend_chain(
+                    NUM_BLOCKS as usize,
+                    BlockStrategy::OnCanonicalHead,
+                    AttestationStrategy::AllValidators,
+                )
+                .await;
+
+            let BeaconProcessorChannels {
+                beacon_processor_tx,
+                beacon_processor_rx,
+                work_reprocessing_tx,
+                work_reprocessing_rx,
+                status,
+            } = <_>::default();
+
+            let (network_globals, _network_senders, network_drained) = NetworkService::start(
+                chain.clone(),
+                &service_config,
+                executor.clone(),
+                None,
+                beacon_processor_tx,
+                work_reprocessing_tx.clone(),
+            )
+            .await
+            .unwrap();
+
+            BeaconProcessor {
+                network_globals: network_globals.clone(),
+                executor: executor.with_exit(network_drained),
+                current_workers: 0,
+                config: <_>::default(),
+                status,
+                log: log.clone(),
+            }
+            .spawn_manager(
+                beacon_processor_rx,
+                work_reprocessing_tx,
+                work_reprocessing_rx,
+                None,
+                chain.slot_clock.clone(),
+                chain.spec.maximum_gossip_clock_disparity(),
+            )
+            .unwrap();
+
+            let fork_context = Arc::new(ForkContext::new::<E>(
+                chain.slot().unwrap(),
+                chain.genesis_validators_root,
+                &chain.spec,
+            ));
+            let (mut client, _) = Network::<usize, E>::new(
+                client_executor,
+                Context {
+                    config: &client_config,
+                    enr_fork_id: chain.enr_fork_id(),
+                    fork_context,
+                    chain_spec: &chain.spec,
+                    gossipsub_registry: None,
+                },
+                &log,
+            )
+            .await
+            .unwrap();
+
+            // Wait for the service to report the port it is listening on.
+            let port = loop {
+                let port = network_globals
+                    .listen_multiaddrs()
+                    .iter()
+                    .flat_map(|addr| addr.iter())
+                    .find_map(|protocol| match protocol {
+                        Protocol::Tcp(port) => Some(port),
+                        _ => None,
+                    });
+                if let Some(port) = port {
+                    break port;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            };
+            client
+                .testing_dial(format!("/ip4/127.0.0.1/tcp/{}", port).parse().unwrap())
+                .unwrap();
+
+            let mut received_blocks = 0;
+            let mut exit_signal = Some(signal);
+            let completed = tokio::time::timeout(Duration::from_secs(30), async {
+                loop {
+                    match client.next_event().await {
+                        NetworkEvent::PeerConnectedOutgoing(peer_id) => client.send_request(
+                            peer_id,
+                            0,
+                            Request::BlocksByRange(BlocksByRangeRequest::new(1, NUM_BLOCKS)),
+                        ),
+                        NetworkEvent::ResponseReceived {
+                            response: Response::BlocksByRange(Some(_)),
+                            ..
+                        } => {
+                            received_blocks += 1;
+                            // Shut the service down while the request is still being answered.
+                            drop(exit_signal.take());
+                        }
+                        NetworkEvent::ResponseReceived {
+                            response: Response::BlocksByRange(None),
+                            ..
+                        } => return,
+                        NetworkEvent::RPCFailed { .. } => panic!("request failed during shutdown"),
+                        _ => {}
+                    }
+                }
+            })
+            .await;
+
+            assert!(completed.is_ok(), "request should complete");
+            assert!(exit_signal.is_none(), "service should have been shut down");
+            assert_eq!(received_blocks, NUM_BLOCKS);
+        });
+    }
+
+    #[test]
+    fn test_near_bandwidth_budget() {
+        use crate::service::is_near_bandwidth_budget;
+        use std::time::Duration;
+
+        let two_secs = Duration::from_secs(2);
+        assert!(!is_near_bandwidth_budget(0, two_secs, 1_000));
+        assert!(!is_near_bandwidth_budget(1_700, two_secs, 1_000));
+        assert!(is_near_bandwidth_budget(1_800, two_secs, 1_000));
+        assert!(is_near_bandwidth_budget(5_000, two_secs, 1_000));
+    }
 }
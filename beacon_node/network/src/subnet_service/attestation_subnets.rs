@@ -86,6 +86,10 @@ pub struct AttestationService<T: BeaconChainTypes> {
     /// We are always subscribed to all subnets.
     subscribe_all_subnets: bool,
 
+    /// Subnets we are always subscribed to as long lived subscriptions, in addition to the
+    /// deterministic long lived subnets.
+    backbone_subnets: HashSet<SubnetId>,
+
     /// Our Discv5 node_id.
     node_id: NodeId,
 
@@ -119,6 +123,22 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             slog::info!(log, "Deterministic long lived subnets enabled"; "subnets_per_node" => beacon_chain.spec.subnets_per_node, "subscription_duration_in_epochs" => beacon_chain.spec.epochs_per_subnet_subscription);
         }
 
+        let backbone_subnets = config
+            .backbone_attestation_subnets
+            .iter()
+            .copied()
+            .filter(|subnet_id| {
+                let valid = **subnet_id < beacon_chain.spec.attestation_subnet_count;
+                if !valid {
+                    warn!(log, "Ignoring invalid backbone subnet"; "subnet" => ?subnet_id);
+                }
+                valid
+            })
+            .collect::<HashSet<_>>();
+        if !config.subscribe_all_subnets && !backbone_subnets.is_empty() {
+            info!(log, "Subscribing to backbone subnets"; "subnets" => ?backbone_subnets.iter().collect::<Vec<_>>());
+        }
+
         let track_validators = !config.import_all_attestations;
         let aggregate_validators_on_subnet =
             track_validators.then(|| HashSetDelay::new(slot_duration));
@@ -132,6 +152,7 @@ impl<T: BeaconChainTypes> AttestationService<T> {
             waker: None,
             discovery_disabled: config.disable_discovery,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            backbone_subnets,
             node_id,
             next_long_lived_subscription_event: {
                 // Set a dummy sleep. Calculating the current subnet subscriptions will update this
@@ -144,6 +165,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
 
         // If we are not subscribed to all subnets, handle the deterministic set of subnets
         if !config.subscribe_all_subnets {
+            // Subscribe to the backbone subnets even if the deterministic subnets cannot be
+            // computed yet, e.g. prior to genesis.
+            if !service.backbone_subnets.is_empty() {
+                service.update_long_lived_subnets(service.backbone_subnets.clone());
+            }
             service.recompute_long_lived_subnets();
         }
 
@@ -333,7 +359,11 @@ impl<T: BeaconChainTypes> AttestationService<T> {
                 )
             })?;
 
-        self.update_long_lived_subnets(subnets.collect());
+        self.update_long_lived_subnets(
+            subnets
+                .chain(self.backbone_subnets.iter().copied())
+                .collect(),
+        );
 
         Ok(next_subscription_event)
     }
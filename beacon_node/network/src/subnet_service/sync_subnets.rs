@@ -1,20 +1,21 @@
 //! This service keeps track of which sync committee subnet the beacon node should be subscribed to at any
 //! given time. It schedules subscriptions to sync committee subnets and requests peer discoveries.
 
-use std::collections::{hash_map::Entry, HashMap, VecDeque};
+use std::collections::{hash_map::Entry, HashMap, HashSet, VecDeque};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
 use futures::prelude::*;
-use slog::{debug, error, o, trace, warn};
+use slog::{debug, error, info, o, trace, warn};
 
 use super::SubnetServiceMessage;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use delay_map::HashSetDelay;
 use lighthouse_network::{NetworkConfig, Subnet, SubnetDiscovery};
 use slot_clock::SlotClock;
+use types::consts::altair::SYNC_COMMITTEE_SUBNET_COUNT;
 use types::{Epoch, EthSpec, SyncCommitteeSubscription, SyncSubnetId};
 
 use crate::metrics;
@@ -54,6 +55,9 @@ pub struct SyncCommitteeService<T: BeaconChainTypes> {
     /// We are always subscribed to all subnets.
     subscribe_all_subnets: bool,
 
+    /// Subnets we are always subscribed to, regardless of sync committee duties.
+    backbone_subnets: HashSet<SyncSubnetId>,
+
     /// Whether this node is a block proposer-only node.
     proposer_only: bool,
 
@@ -77,27 +81,47 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
         let default_timeout =
             epoch_duration_secs.saturating_mul(spec.epochs_per_sync_committee_period.as_u64());
 
-        SyncCommitteeService {
+        // Backbone subnets are redundant if we are subscribed to all subnets.
+        let backbone_subnets = if config.subscribe_all_subnets {
+            HashSet::new()
+        } else {
+            config
+                .backbone_sync_subnets
+                .iter()
+                .copied()
+                .filter(|subnet_id| {
+                    let valid = **subnet_id < SYNC_COMMITTEE_SUBNET_COUNT;
+                    if !valid {
+                        warn!(log, "Ignoring invalid backbone sync subnet"; "subnet" => **subnet_id);
+                    }
+                    valid
+                })
+                .collect::<HashSet<_>>()
+        };
+
+        let mut service = SyncCommitteeService {
             events: VecDeque::with_capacity(10),
             beacon_chain,
             subscriptions: HashMap::new(),
             unsubscriptions: HashSetDelay::new(Duration::from_secs(default_timeout)),
             waker: None,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            backbone_subnets,
             discovery_disabled: config.disable_discovery,
             proposer_only: config.proposer_only,
             log,
-        }
+        };
+        service.subscribe_to_backbone_subnets();
+        service
     }
 
     /// Return count of all currently subscribed subnets.
     #[cfg(test)]
     pub fn subscription_count(&self) -> usize {
-        use types::consts::altair::SYNC_COMMITTEE_SUBNET_COUNT;
         if self.subscribe_all_subnets {
             SYNC_COMMITTEE_SUBNET_COUNT as usize
         } else {
-            self.subscriptions.len()
+            self.subscriptions.len() + self.backbone_subnets.len()
         }
     }
 
@@ -183,6 +207,29 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
 
     /* Internal private functions */
 
+    /// Subscribes to the backbone subnets and advertises them in our ENR. These subscriptions
+    /// never expire.
+    fn subscribe_to_backbone_subnets(&mut self) {
+        if self.backbone_subnets.is_empty() {
+            return;
+        }
+        info!(self.log, "Subscribing to backbone sync subnets"; "subnets" => ?self.backbone_subnets.iter().collect::<Vec<_>>());
+
+        for subnet_id in self.backbone_subnets.iter().copied() {
+            let subnet = Subnet::SyncCommittee(subnet_id);
+            self.events
+                .push_back(SubnetServiceMessage::Subscribe(subnet));
+            self.events.push_back(SubnetServiceMessage::EnrAdd(subnet));
+            if !self.discovery_disabled {
+                self.events
+                    .push_back(SubnetServiceMessage::DiscoverPeers(vec![SubnetDiscovery {
+                        subnet,
+                        min_ttl: None,
+                    }]));
+            }
+        }
+    }
+
     /// Checks if there are currently queued discovery requests and the time required to make the
     /// request.
     ///
@@ -241,6 +288,11 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
             return Ok(());
         }
 
+        // Return if we are permanently subscribed to this subnet
+        if self.backbone_subnets.contains(&exact_subnet.subnet_id) {
+            return Ok(());
+        }
+
         // Return if we already have a subscription for exact_subnet
         if self.subscriptions.get(&exact_subnet.subnet_id) == Some(&exact_subnet.until_epoch) {
             return Ok(());
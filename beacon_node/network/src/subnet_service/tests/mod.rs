@@ -118,9 +118,15 @@ lazy_static! {
 
 fn get_attestation_service(
     log_level: Option<slog::Level>,
+) -> AttestationService<TestBeaconChainType> {
+    get_attestation_service_with_config(log_level, NetworkConfig::default())
+}
+
+fn get_attestation_service_with_config(
+    log_level: Option<slog::Level>,
+    config: NetworkConfig,
 ) -> AttestationService<TestBeaconChainType> {
     let log = get_logger(log_level);
-    let config = NetworkConfig::default();
 
     let beacon_chain = CHAIN.chain.clone();
 
@@ -133,8 +139,13 @@ fn get_attestation_service(
 }
 
 fn get_sync_committee_service() -> SyncCommitteeService<TestBeaconChainType> {
+    get_sync_committee_service_with_config(NetworkConfig::default())
+}
+
+fn get_sync_committee_service_with_config(
+    config: NetworkConfig,
+) -> SyncCommitteeService<TestBeaconChainType> {
     let log = get_logger(None);
-    let config = NetworkConfig::default();
 
     let beacon_chain = CHAIN.chain.clone();
 
@@ -695,6 +706,26 @@ mod attestation_service {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn backbone_subnets_are_long_lived() {
+        let backbone_subnets = vec![SubnetId::new(0), SubnetId::new(63)];
+        let config = NetworkConfig {
+            backbone_attestation_subnets: backbone_subnets.clone(),
+            ..NetworkConfig::default()
+        };
+        let attestation_service = get_attestation_service_with_config(None, config);
+        let subnets_per_node = MainnetEthSpec::default_spec().subnets_per_node as usize;
+
+        for subnet_id in &backbone_subnets {
+            assert!(attestation_service
+                .is_subscribed(subnet_id, attestation_subnets::SubscriptionKind::LongLived));
+        }
+        // The deterministic subnets are still subscribed to, and may overlap the backbone.
+        let long_lived = attestation_service.long_lived_subscriptions().len();
+        assert!(long_lived >= subnets_per_node);
+        assert!(long_lived <= subnets_per_node + backbone_subnets.len());
+    }
 }
 
 mod sync_committee_service {
@@ -814,4 +845,45 @@ mod sync_committee_service {
         // Should be unsubscribed at the end.
         assert_eq!(sync_committee_service.subscription_count(), 1);
     }
+
+    #[tokio::test]
+    async fn backbone_subnets_never_unsubscribe() {
+        let subnet_id = SyncSubnetId::new(1);
+        let config = NetworkConfig {
+            backbone_sync_subnets: vec![subnet_id],
+            ..NetworkConfig::default()
+        };
+        let mut sync_committee_service = get_sync_committee_service_with_config(config);
+
+        let events = get_events(&mut sync_committee_service, None, 1).await;
+        assert_eq!(
+            events[..2],
+            [
+                SubnetServiceMessage::Subscribe(Subnet::SyncCommittee(subnet_id)),
+                SubnetServiceMessage::EnrAdd(Subnet::SyncCommittee(subnet_id))
+            ]
+        );
+        matches::assert_matches!(events[2..], [SubnetServiceMessage::DiscoverPeers(_)]);
+
+        // A subscription to a backbone subnet does not schedule an unsubscription.
+        let subscriptions = vec![SyncCommitteeSubscription {
+            validator_index: 1,
+            sync_committee_indices: vec![
+                MainnetEthSpec::sync_subcommittee_size() as u64 * *subnet_id,
+            ],
+            until_epoch: Epoch::new(1),
+        }];
+        sync_committee_service
+            .validator_subscriptions(subscriptions)
+            .unwrap();
+
+        let events = get_events(
+            &mut sync_committee_service,
+            None,
+            (MainnetEthSpec::slots_per_epoch() * 3) as u32,
+        )
+        .await;
+        matches::assert_matches!(events[..], [SubnetServiceMessage::DiscoverPeers(_)]);
+        assert_eq!(sync_committee_service.subscription_count(), 1);
+    }
 }
@@ -16,7 +16,7 @@ use crate::sync::range_sync::{
 };
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use lighthouse_network::types::{BackFillState, NetworkGlobals};
-use lighthouse_network::{PeerAction, PeerId};
+use lighthouse_network::{PeerAction, PeerDB, PeerId};
 use rand::seq::SliceRandom;
 use slog::{crit, debug, error, info, warn};
 use std::collections::{
@@ -24,7 +24,7 @@ use std::collections::{
     HashMap, HashSet,
 };
 use std::sync::Arc;
-use types::{Epoch, EthSpec, SignedBeaconBlock};
+use types::{Epoch, EthSpec, SignedBeaconBlock, Slot};
 
 /// Blocks are downloaded in batches from peers. This constant specifies how many epochs worth of
 /// blocks per batch are requested _at most_. A batch may request less blocks to account for
@@ -533,12 +533,13 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             Ok(v) => v,
         };
 
+        let peer_id = batch.current_peer().copied();
         let process_id = ChainSegmentProcessId::BackSyncBatchId(batch_id);
         self.current_processing_batch = Some(batch_id);
 
         if let Err(e) = network
             .beacon_processor()
-            .send_chain_segment(process_id, blocks)
+            .send_chain_segment(process_id, blocks, peer_id)
         {
             crit!(self.log, "Failed to send backfill segment to processor."; "msg" => "process_batch",
                 "error" => %e, "batch" => self.processing_target);
@@ -914,26 +915,12 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
         };
 
         // Find a peer to request the batch
-        let failed_peers = batch.failed_peers();
-
-        let new_peer = {
-            let mut priorized_peers = self
-                .network_globals
-                .peers
-                .read()
-                .synced_peers()
-                .map(|peer| {
-                    (
-                        failed_peers.contains(peer),
-                        self.active_requests.get(peer).map(|v| v.len()).unwrap_or(0),
-                        *peer,
-                    )
-                })
-                .collect::<Vec<_>>();
-            // Sort peers prioritizing unrelated peers with less active requests.
-            priorized_peers.sort_unstable();
-            priorized_peers.get(0).map(|&(_, _, peer)| peer)
-        };
+        let new_peer = select_batch_peer(
+            &self.network_globals.peers.read(),
+            batch.start_slot(),
+            &batch.failed_peers(),
+            &self.active_requests,
+        );
 
         if let Some(peer) = new_peer {
             self.participating_peers.insert(peer);
@@ -1181,3 +1168,81 @@ enum ResetEpochError {
     /// Backfill is not required.
     NotRequired,
 }
+
+/// Selects the synced peer to request a batch starting at `start_slot` from. Peers whose ENR
+/// advertises that they can serve the batch are preferred, then peers which have not failed the
+/// batch and finally peers with fewer active requests.
+fn select_batch_peer<E: EthSpec>(
+    peers: &PeerDB<E>,
+    start_slot: Slot,
+    failed_peers: &HashSet<PeerId>,
+    active_requests: &HashMap<PeerId, HashSet<BatchId>>,
+) -> Option<PeerId> {
+    peers
+        .synced_peers()
+        .map(|peer| {
+            (
+                !peers
+                    .peer_info(peer)
+                    .map_or(true, |info| info.can_serve_slot(start_slot)),
+                failed_peers.contains(peer),
+                active_requests.get(peer).map(|v| v.len()).unwrap_or(0),
+                *peer,
+            )
+        })
+        .min()
+        .map(|(_, _, _, peer)| peer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::{SyncInfo, SyncStatus};
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use types::{Hash256, MinimalEthSpec as E};
+
+    fn add_synced_peer(peers: &mut PeerDB<E>, earliest_available_slot: Option<Slot>) -> PeerId {
+        let peer_id = peers.__add_connected_peer_testing_only(earliest_available_slot);
+        let info = SyncInfo {
+            head_slot: Slot::new(0),
+            head_root: Hash256::zero(),
+            finalized_epoch: Epoch::new(0),
+            finalized_root: Hash256::zero(),
+        };
+        peers.update_sync_status(&peer_id, SyncStatus::Synced { info });
+        peer_id
+    }
+
+    #[test]
+    fn batch_peer_prefers_peers_which_can_serve_the_batch() {
+        let log = NullLoggerBuilder.build().unwrap();
+        let mut peers = PeerDB::<E>::new(vec![], false, &log);
+        let start_slot = Slot::new(32);
+        let mut failed_peers = HashSet::new();
+        let mut active_requests = HashMap::new();
+
+        let backfilling_peer = add_synced_peer(&mut peers, Some(Slot::new(64)));
+        assert_eq!(
+            select_batch_peer(&peers, start_slot, &failed_peers, &active_requests),
+            Some(backfilling_peer),
+            "a peer which cannot serve the batch is still used as a last resort"
+        );
+
+        // A peer which advertises the batch as available wins, even if it is busier and has
+        // already failed the batch.
+        let serving_peer = add_synced_peer(&mut peers, Some(Slot::new(0)));
+        failed_peers.insert(serving_peer);
+        active_requests.insert(serving_peer, HashSet::from([Epoch::new(1), Epoch::new(2)]));
+        assert_eq!(
+            select_batch_peer(&peers, start_slot, &failed_peers, &active_requests),
+            Some(serving_peer)
+        );
+
+        // Among peers which can serve the batch, those which have not failed it come first.
+        let unknown_peer = add_synced_peer(&mut peers, None);
+        assert_eq!(
+            select_batch_peer(&peers, start_slot, &failed_peers, &active_requests),
+            Some(unknown_peer)
+        );
+    }
+}
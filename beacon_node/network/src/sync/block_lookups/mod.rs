@@ -555,7 +555,9 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                 let (chain_hash, blocks, hashes, request) = parent_lookup.parts_for_processing();
                 let process_id = ChainSegmentProcessId::ParentLookup(chain_hash);
 
-                match beacon_processor.send_chain_segment(process_id, blocks) {
+                // The parents may have been downloaded from different peers, so no single peer
+                // is recorded for the segment.
+                match beacon_processor.send_chain_segment(process_id, blocks, None) {
                     Ok(_) => {
                         self.processing_parent_lookups
                             .insert(chain_hash, (hashes, request));
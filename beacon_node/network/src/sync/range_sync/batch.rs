@@ -153,6 +153,11 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         }
     }
 
+    /// The first slot of the batch.
+    pub fn start_slot(&self) -> Slot {
+        self.start_slot
+    }
+
     /// Gives a list of peers from which this batch has had a failed download or processing
     /// attempt.
     pub fn failed_peers(&self) -> HashSet<PeerId> {
@@ -49,6 +49,7 @@ use crate::sync::network_context::SyncNetworkContext;
 use crate::sync::BatchProcessResult;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use lighthouse_network::rpc::GoodbyeReason;
+use lighthouse_network::types::HandshakeFailureReason;
 use lighthouse_network::PeerId;
 use lighthouse_network::SyncInfo;
 use lru_cache::LRUTimeCache;
@@ -133,6 +134,11 @@ where
                 if self.failed_chains.contains(&remote_info.finalized_root) {
                     debug!(self.log, "Disconnecting peer that belongs to previously failed chain";
                         "failed_root" => %remote_info.finalized_root, "peer_id" => %peer_id);
+                    network.network_globals().record_handshake_failure(
+                        Some(&peer_id),
+                        HandshakeFailureReason::FailedChain,
+                        format!("Previously failed chain {}", remote_info.finalized_root),
+                    );
                     network.goodbye_peer(peer_id, GoodbyeReason::IrrelevantNetwork);
                     return;
                 }
@@ -23,7 +23,7 @@ use crate::attestation_storage::{AttestationMap, CheckpointKey};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
 use crate::sync_aggregate_id::SyncAggregateId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use max_cover::{maximum_cover, maximum_cover_with_budget};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -35,6 +35,7 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::Duration;
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
@@ -77,6 +78,15 @@ pub enum OpPoolError {
     IncorrectOpPoolVariant,
 }
 
+/// The algorithm used to select attestations for inclusion in a block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttestationPacking {
+    /// Select attestations greedily by their marginal reward.
+    Greedy,
+    /// Improve on the greedy selection with a local search, for at most `time_budget`.
+    MaxCover { time_budget: Duration },
+}
+
 #[derive(Default)]
 pub struct AttestationStats {
     /// Total number of attestations for all committeees/indices/votes.
@@ -251,6 +261,26 @@ impl<T: EthSpec> OperationPool<T> {
         prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
         spec: &ChainSpec,
+    ) -> Result<Vec<Attestation<T>>, OpPoolError> {
+        self.get_attestations_with_packing(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            AttestationPacking::Greedy,
+            spec,
+        )
+    }
+
+    /// Get a list of attestations for inclusion in a block, selected using `packing`.
+    ///
+    /// See `get_attestations` for the meaning of the validity filters.
+    pub fn get_attestations_with_packing(
+        &self,
+        state: &BeaconState<T>,
+        prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
+        curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, T>) -> bool + Send,
+        packing: AttestationPacking,
+        spec: &ChainSpec,
     ) -> Result<Vec<Attestation<T>>, OpPoolError> {
         // Attestations for the current fork, which may be from the current or previous epoch.
         let (prev_epoch_key, curr_epoch_key) = CheckpointKey::keys_for_state(state);
@@ -311,15 +341,21 @@ impl<T: EthSpec> OperationPool<T> {
                 if prev_epoch_key == curr_epoch_key {
                     vec![]
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    pack_attestations(
+                        prev_epoch_att,
+                        prev_epoch_limit,
+                        "prev_epoch_attestations",
+                        packing,
+                    )
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(
+                pack_attestations(
                     curr_epoch_att,
                     T::MaxAttestations::to_usize(),
                     "curr_epoch_attestations",
+                    packing,
                 )
             },
         );
@@ -707,6 +743,25 @@ impl<T: EthSpec> OperationPool<T> {
     }
 }
 
+/// Compute a maximum cover of `items` using the algorithm selected by `packing`.
+fn pack_attestations<I, T>(
+    items: I,
+    limit: usize,
+    label: &str,
+    packing: AttestationPacking,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    match packing {
+        AttestationPacking::Greedy => maximum_cover(items, limit, label),
+        AttestationPacking::MaxCover { time_budget } => {
+            maximum_cover_with_budget(items, limit, label, time_budget)
+        }
+    }
+}
+
 /// Filter up to a maximum number of operations out of an iterator.
 fn filter_limit_operations<'a, T: 'a, V: 'a, I, F, G>(
     operations: I,
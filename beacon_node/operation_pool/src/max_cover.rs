@@ -1,5 +1,6 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::time::{Duration, Instant};
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -55,12 +56,39 @@ where
     I: IntoIterator<Item = T>,
     T: MaxCover,
 {
-    // Construct an initial vec of all items, marked available.
-    let mut all_items: Vec<_> = items_iter
+    // Construct an initial vec of all items with a non-zero score.
+    let all_items: Vec<_> = items_iter.into_iter().filter(|x| x.score() != 0).collect();
+
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_NON_ZERO_ITEMS,
+        &[label],
+        all_items.len() as i64,
+    );
+
+    greedy_cover(all_items, limit)
         .into_iter()
-        .map(MaxCoverItem::new)
-        .filter(|x| x.item.score() != 0)
-        .collect();
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Compute an approximate maximum cover by improving on the greedy solution with a local search.
+///
+/// The search repeatedly swaps an item of the solution for an item outside of it whenever doing
+/// so increases the total score, until no such swap exists or `time_budget` has elapsed. The
+/// result is never worse than the solution of `maximum_cover`, and is in the same format.
+pub fn maximum_cover_with_budget<I, T>(
+    items_iter: I,
+    limit: usize,
+    label: &str,
+    time_budget: Duration,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let deadline = Instant::now() + time_budget;
+
+    let all_items: Vec<_> = items_iter.into_iter().filter(|x| x.score() != 0).collect();
 
     metrics::set_int_gauge(
         &metrics::MAX_COVER_NON_ZERO_ITEMS,
@@ -68,36 +96,170 @@ where
         all_items.len() as i64,
     );
 
-    let mut result = vec![];
+    let mut solution = greedy_cover(all_items.clone(), limit)
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let greedy_score = solution_score(&all_items, &solution);
+    let mut best_score = greedy_score;
+
+    // If the greedy algorithm stopped short of the limit then every item is already covered, and
+    // the solution is optimal.
+    if solution.len() == limit {
+        let mut in_solution = vec![false; all_items.len()];
+        for &i in &solution {
+            in_solution[i] = true;
+        }
+
+        'search: loop {
+            let mut improved = false;
+            for position in 0..solution.len() {
+                // The rest of the solution is fixed while the item at `position` is swapped, so
+                // each candidate only needs its marginal score on top of it.
+                let others = solution
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != position)
+                    .map(|(_, &i)| &all_items[i])
+                    .collect::<Vec<_>>();
+                let base_score =
+                    best_score - marginal_score(&all_items[solution[position]], &others);
+
+                for candidate in 0..all_items.len() {
+                    if in_solution[candidate] {
+                        continue;
+                    }
+                    if Instant::now() >= deadline {
+                        metrics::inc_counter_vec(&metrics::MAX_COVER_SEARCH_TIMEOUTS, &[label]);
+                        break 'search;
+                    }
+
+                    let score = base_score + marginal_score(&all_items[candidate], &others);
+                    if score > best_score {
+                        best_score = score;
+                        in_solution[solution[position]] = false;
+                        in_solution[candidate] = true;
+                        solution[position] = candidate;
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+    }
+
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_GREEDY_SCORE,
+        &[label],
+        greedy_score as i64,
+    );
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_OPTIMISED_SCORE,
+        &[label],
+        best_score as i64,
+    );
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_UPPER_BOUND,
+        &[label],
+        upper_bound(&all_items, limit, greedy_score) as i64,
+    );
+
+    // Re-run the greedy algorithm on the chosen items so that they are ordered by, and scored
+    // according to, their marginal contribution, as required by `merge_solutions`.
+    let mut all_items = all_items.into_iter().map(Some).collect::<Vec<_>>();
+    let chosen = solution
+        .into_iter()
+        .filter_map(|i| all_items[i].take())
+        .collect::<Vec<_>>();
+    greedy_cover(chosen, limit)
+        .into_iter()
+        .map(|(_, item)| item)
+        .collect()
+}
+
+/// Select up to `limit` items greedily, returning their indices in `items` along with their
+/// covering sets updated for the items selected before them.
+fn greedy_cover<T: MaxCover>(items: Vec<T>, limit: usize) -> Vec<(usize, T)> {
+    // Construct an initial vec of all items, marked available.
+    let mut all_items: Vec<_> = items.into_iter().map(MaxCoverItem::new).collect();
+
+    let mut selected = vec![];
 
     for _ in 0..limit {
         // Select the item with the maximum score.
         let best = match all_items
-            .iter_mut()
-            .filter(|x| x.available && x.item.score() != 0)
-            .max_by_key(|x| x.item.score())
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| x.available && x.item.score() != 0)
+            .max_by_key(|(_, x)| x.item.score())
         {
-            Some(x) => {
-                x.available = false;
-                x.item.clone()
-            }
-            None => return result,
+            Some((i, _)) => i,
+            None => break,
         };
+        all_items[best].available = false;
 
         // Update the covering sets of the other items, for the inclusion of the selected item.
         // Items covered by the selected item can't be re-covered.
-        all_items
-            .iter_mut()
-            .filter(|x| x.available && x.item.score() != 0)
-            .for_each(|x| {
-                x.item
-                    .update_covering_set(best.intermediate(), best.covering_set())
-            });
-
-        result.push(best);
+        let (before, rest) = all_items.split_at_mut(best);
+        if let Some((best_item, after)) = rest.split_first_mut() {
+            before
+                .iter_mut()
+                .chain(after)
+                .filter(|x| x.available && x.item.score() != 0)
+                .for_each(|x| {
+                    x.item.update_covering_set(
+                        best_item.item.intermediate(),
+                        best_item.item.covering_set(),
+                    )
+                });
+        }
+
+        selected.push(best);
     }
 
-    result
+    // Selected items are never updated again, so they can be moved out as they are.
+    let mut all_items = all_items
+        .into_iter()
+        .map(|x| Some(x.item))
+        .collect::<Vec<_>>();
+    selected
+        .into_iter()
+        .filter_map(|i| all_items[i].take().map(|item| (i, item)))
+        .collect()
+}
+
+/// The total score of the union of the covering sets of `solution`.
+fn solution_score<T: MaxCover>(items: &[T], solution: &[usize]) -> usize {
+    let mut chosen = Vec::with_capacity(solution.len());
+    let mut total = 0;
+    for &i in solution {
+        total += marginal_score(&items[i], &chosen);
+        chosen.push(&items[i]);
+    }
+    total
+}
+
+/// The score `item` adds to a solution made up of `others`.
+fn marginal_score<T: MaxCover>(item: &T, others: &[&T]) -> usize {
+    let mut item = item.clone();
+    for other in others {
+        item.update_covering_set(other.intermediate(), other.covering_set());
+    }
+    item.score()
+}
+
+/// An upper bound on the score of an optimal solution.
+///
+/// The optimum is bounded by the sum of the `limit` highest scores, and by the greedy score
+/// divided by the greedy algorithm's approximation ratio of `1 - 1/e`.
+fn upper_bound<T: MaxCover>(items: &[T], limit: usize, greedy_score: usize) -> usize {
+    let mut scores = items.iter().map(T::score).collect::<Vec<_>>();
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    let top_scores = scores.into_iter().take(limit).sum::<usize>();
+    let greedy_bound = (greedy_score as f64 / (1.0 - 1.0 / std::f64::consts::E)) as usize;
+    std::cmp::min(top_scores, greedy_bound)
 }
 
 /// Perform a greedy merge of two max cover solutions, preferring higher-score values.
@@ -208,6 +370,30 @@ mod test {
         assert_eq!(quality(&cover), 11);
     }
 
+    // The greedy algorithm selects the first set and can then only add one more element, whereas
+    // the last two sets cover all six elements.
+    #[test]
+    fn local_search_improves_greedy() {
+        let sets = vec![
+            HashSet::from_iter(vec![1, 2, 3, 4]),
+            HashSet::from_iter(vec![1, 2, 5]),
+            HashSet::from_iter(vec![3, 4, 6]),
+        ];
+        let greedy = maximum_cover(sets.clone(), 2, "test");
+        assert_eq!(quality(&greedy), 5);
+
+        let cover = maximum_cover_with_budget(sets, 2, "test", Duration::from_secs(10));
+        assert_eq!(quality(&cover), 6);
+        assert_eq!(cover.len(), 2);
+    }
+
+    #[test]
+    fn local_search_respects_budget() {
+        let sets = example_system();
+        let cover = maximum_cover_with_budget(sets.clone(), 1, "test", Duration::ZERO);
+        assert_eq!(cover, maximum_cover(sets, 1, "test"));
+    }
+
     #[test]
     fn intersecting_ok() {
         let sets = vec![
@@ -28,4 +28,24 @@ lazy_static! {
         "Number of non-trivial items considered in a max coverage optimisation",
         &["label"]
     );
+    pub static ref MAX_COVER_GREEDY_SCORE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_greedy_score",
+        "Score of the greedy solution to a max coverage optimisation",
+        &["label"]
+    );
+    pub static ref MAX_COVER_OPTIMISED_SCORE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_optimised_score",
+        "Score of the solution to a max coverage optimisation after local search",
+        &["label"]
+    );
+    pub static ref MAX_COVER_UPPER_BOUND: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_upper_bound",
+        "Upper bound on the score of the optimal solution to a max coverage optimisation",
+        &["label"]
+    );
+    pub static ref MAX_COVER_SEARCH_TIMEOUTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "op_pool_max_cover_search_timeouts_total",
+        "Number of max coverage local searches stopped by their time budget",
+        &["label"]
+    );
 }
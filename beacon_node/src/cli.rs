@@ -39,6 +39,25 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        This will also advertise the beacon node as being long-lived subscribed to all subnets.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("backbone-attestation-subnets")
+                .long("backbone-attestation-subnets")
+                .value_name("SUBNET_IDS")
+                .help("One or more comma-delimited attestation subnet ids to subscribe to regardless \
+                       of validator duties. These subnets are advertised in the ENR as long-lived \
+                       subscriptions, allowing the node to act as a backbone for them. Use \
+                       --subscribe-all-subnets to subscribe to every subnet.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("backbone-sync-subnets")
+                .long("backbone-sync-subnets")
+                .value_name("SUBNET_IDS")
+                .help("One or more comma-delimited sync committee subnet ids to subscribe to \
+                       regardless of validator duties. These subnets are advertised in the ENR as \
+                       long-lived subscriptions, allowing the node to act as a backbone for them.")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("import-all-attestations")
                 .long("import-all-attestations")
@@ -282,7 +301,38 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("trusted-peers")
                 .long("trusted-peers")
                 .value_name("TRUSTED_PEERS")
-                .help("One or more comma-delimited trusted peer ids which always have the highest score according to the peer scoring system.")
+                .help("One or more comma-delimited trusted peer ids which always have the highest score according to the peer scoring system. \
+                       Trusted peers are never disconnected due to their score and occupy reserved connection slots in addition \
+                       to the target peer count.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-subnet-peer-fraction")
+                .long("max-subnet-peer-fraction")
+                .value_name("FRACTION")
+                .help("The maximum fraction of the target peer count which may be connected from \
+                       a single IPv4 /24 or IPv6 /48 subnet. Inbound connections beyond this \
+                       limit are refused. Trusted peers are exempt.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-asn-peer-fraction")
+                .long("max-asn-peer-fraction")
+                .value_name("FRACTION")
+                .help("The maximum fraction of the target peer count which may be connected from \
+                       a single autonomous system. Inbound connections beyond this limit are \
+                       refused. Trusted peers are exempt. Requires --asn-database.")
+                .requires("asn-database")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("asn-database")
+                .long("asn-database")
+                .value_name("PATH")
+                .help("Path to an IP-to-ASN database in the tab-separated format published by \
+                       iptoasn.com, loaded on startup. Used to enforce \
+                       --max-asn-peer-fraction.")
+                .requires("max-asn-peer-fraction")
                 .takes_value(true),
         )
         .arg(
@@ -449,6 +499,51 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Status code to send when a block that is already known is POSTed to the \
                        HTTP API.")
         )
+        .arg(
+            Arg::with_name("http-validator-monitor-token-file")
+                .long("http-validator-monitor-token-file")
+                .requires("enable_http")
+                .value_name("PATH")
+                .help("Path to a file containing a token which allows validator clients to \
+                       register validators with the validator monitor via the HTTP API. The \
+                       token must be sent as a bearer token in the Authorization header. \
+                       Registration via the HTTP API is disabled if this flag is not provided.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-admin-token-file")
+                .long("http-admin-token-file")
+                .requires("enable_http")
+                .value_name("PATH")
+                .help("Path to a file containing a token which allows the HTTP API endpoints \
+                       that change the node's behaviour at runtime to be used, such as \
+                       POST lighthouse/logging. The token must be sent as a bearer token in the \
+                       Authorization header. These endpoints are disabled if this flag is not \
+                       provided.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-database-backup-dir")
+                .long("http-database-backup-dir")
+                .requires("enable_http")
+                .value_name("DIR")
+                .help("Directory into which backups of the hot database requested via the HTTP \
+                       API are written. Each backup is written to a sub-directory named in the \
+                       request. Backups via the HTTP API are disabled if this flag is not \
+                       provided.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("otlp-endpoint")
+                .long("otlp-endpoint")
+                .requires("enable_http")
+                .value_name("URL")
+                .help("An OpenTelemetry collector accepting OTLP/HTTP (e.g. http://localhost:4318). \
+                       Block production and publication requests which carry a W3C traceparent \
+                       header, such as those from a Lighthouse validator client with the same \
+                       flag, are traced and the spans exported to the collector.")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("http-enable-beacon-processor")
                 .long("http-enable-beacon-processor")
@@ -505,6 +600,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Shufflings are dependent on validator count and setting this value to a large number can consume a large amount of memory.")
             .takes_value(true)
         )
+        .arg(
+            Arg::with_name("shuffling-cache-memory-budget")
+            .long("shuffling-cache-memory-budget")
+            .value_name("MEGABYTES")
+            .help("Limits the memory used by the shuffling cache, in megabytes. When the limit is \
+            reached the least-recently used shufflings are evicted, although the shufflings of the \
+            head are always retained. By default only the number of shufflings is limited.")
+            .takes_value(true)
+        )
 
         /*
          * Monitoring metrics
@@ -639,6 +743,27 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Specifies how many states from the freezer database should cache in memory [default: 1]")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("db-backend")
+                .long("db-backend")
+                .value_name("BACKEND")
+                .help("The key-value store used for the hot and freezer databases. The memory \
+                       backend is not persisted and is only intended for testing and simulations.")
+                .takes_value(true)
+                .possible_values(&["leveldb", "memory"])
+                .default_value("leveldb")
+        )
+        .arg(
+            Arg::with_name("hdiff-exponents")
+                .long("hdiff-exponents")
+                .value_name("EXPONENTS")
+                .help("Store epoch boundary states in the hot database as a hierarchy of diffs \
+                       rather than in full. A comma-separated, strictly ascending list of \
+                       exponents, e.g. \"1,3,5\": states at multiples of 2^5 epochs are stored \
+                       in full, states at multiples of 2^3 epochs as a diff against them, and so \
+                       on down to every epoch. Disabled by default.")
+                .takes_value(true)
+        )
         /*
          * Execution Layer Integration
          */
@@ -670,6 +795,29 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("execution-cross-check-endpoints")
+                .long("execution-cross-check-endpoints")
+                .value_name("EXECUTION-ENDPOINTS")
+                .help("Comma-separated list of additional execution endpoints. These are sent the \
+                       same newPayload and forkchoiceUpdated calls as the --execution-endpoint, \
+                       and any disagreement about the validity of a payload is logged and \
+                       recorded in metrics. The --execution-endpoint remains the only endpoint \
+                       used to determine validity and to produce payloads.")
+                .requires("execution-endpoint")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("execution-cross-check-jwts")
+                .long("execution-cross-check-jwts")
+                .value_name("EXECUTION-JWTS")
+                .help("Comma-separated list of file paths which contain the hex-encoded JWT \
+                       secrets for each of the --execution-cross-check-endpoints, in the same \
+                       order. Endpoints without a secret use the secret of the \
+                       --execution-endpoint.")
+                .requires("execution-cross-check-endpoints")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("execution-jwt-secret-key")
                 .long("execution-jwt-secret-key")
@@ -718,7 +866,9 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. Multiple \
+                      comma-separated URLs may be provided, in which case every builder is \
+                      asked for a bid and the most valuable valid bid is used.")
                 .requires("execution-endpoint")
                 .takes_value(true)
         )
@@ -929,11 +1079,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .arg(
             Arg::with_name("checkpoint-sync-url")
                 .long("checkpoint-sync-url")
-                .help("Set the remote beacon node HTTP endpoint to use for checkpoint sync.")
-                .value_name("BEACON_NODE")
+                .help("Set the remote beacon node HTTP endpoint to use for checkpoint sync. \
+                       A comma-separated list of endpoints may be provided, in which case the \
+                       state is downloaded from the first and its root is verified against the \
+                       others before it is imported.")
+                .value_name("BEACON_NODES")
                 .takes_value(true)
                 .conflicts_with("checkpoint-state")
         )
+        .arg(
+            Arg::with_name("checkpoint-sync-quorum")
+                .long("checkpoint-sync-quorum")
+                .help("The number of checkpoint sync endpoints, including the one the state is \
+                       downloaded from, which must agree on the finalized state root before it \
+                       is imported. Defaults to a majority of the endpoints.")
+                .value_name("NUM_ENDPOINTS")
+                .takes_value(true)
+                .requires("checkpoint-sync-url")
+        )
         .arg(
             Arg::with_name("checkpoint-sync-url-timeout")
                 .long("checkpoint-sync-url-timeout")
@@ -984,6 +1147,19 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("INTEGER")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("validator-monitor-metric-aggregation")
+                .long("validator-monitor-metric-aggregation")
+                .help("Determines how the validator monitor groups its aggregate metrics once \
+                    it has stopped collecting per-validator metrics. \"total\" only provides a \
+                    total across all validators, \"status\" adds a series per validator status \
+                    \"index-range:<size>\" adds a series per range of <size> validator \
+                    indices and \"cohort\" adds a series per cohort that validators were \
+                    registered with via the HTTP API. Can be changed at runtime via the HTTP \
+                    API. Defaults to total.")
+                .value_name("AGGREGATION")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("disable-lock-timeouts")
                 .long("disable-lock-timeouts")
@@ -1036,6 +1212,34 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        restrictions.")
                 .conflicts_with("disable-proposer-reorgs")
         )
+        .arg(
+            Arg::with_name("proposer-reorg-threshold-schedule")
+                .long("proposer-reorg-threshold-schedule")
+                .value_name("EPOCH:PERCENT,...")
+                .help("Comma-separated list of thresholds which replace the value of \
+                       --proposer-reorg-threshold from the given epoch onwards, e.g. \
+                       `200000:10,210000:30`.")
+                .takes_value(true)
+                .conflicts_with("disable-proposer-reorgs")
+        )
+        .arg(
+            Arg::with_name("proposer-reorg-max-consecutive")
+                .long("proposer-reorg-max-consecutive")
+                .value_name("N")
+                .help("Maximum number of re-orging blocks to propose in a row. Once this many \
+                       re-orging blocks have been proposed, re-orgs will not be attempted until \
+                       a block is proposed atop the canonical head. Default: no limit.")
+                .takes_value(true)
+                .conflicts_with("disable-proposer-reorgs")
+        )
+        .arg(
+            Arg::with_name("proposer-reorg-dry-run")
+                .long("proposer-reorg-dry-run")
+                .help("Evaluate proposer re-orgs and log the re-orgs that would have been \
+                       attempted, without proposing any re-orging blocks.")
+                .takes_value(false)
+                .conflicts_with("disable-proposer-reorgs")
+        )
         .arg(
             Arg::with_name("prepare-payload-lookahead")
                 .long("prepare-payload-lookahead")
@@ -1064,6 +1268,44 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("250")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("state-root-audit-interval")
+                .long("state-root-audit-interval")
+                .value_name("EPOCHS")
+                .help("Periodically hash the head state from scratch and compare the result \
+                       against the root computed by the tree hash cache, logging a critical \
+                       error if they differ. The audit runs every EPOCHS epochs. This is \
+                       intended for testing and is disabled by default.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("max-epoch-processing-threads")
+                .long("max-epoch-processing-threads")
+                .value_name("THREADS")
+                .help("Maximum number of threads used to process validators in parallel during \
+                       epoch processing. Defaults to the number of CPUs. The result of epoch \
+                       processing does not depend on this value.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("max-cover-attestation-packing-fork")
+                .long("max-cover-attestation-packing-fork")
+                .value_name("FORK")
+                .help("Pack attestations into blocks using a local search which improves on the \
+                       greedy selection from the given fork onwards, e.g. `capella`. This \
+                       increases the rewards from attestations at the cost of slower block \
+                       production. Disabled by default.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("attestation-packing-time-budget")
+                .long("attestation-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("Maximum time spent improving the packing of attestations for each epoch \
+                       when --max-cover-attestation-packing-fork is active.")
+                .default_value("50")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1180,10 +1422,36 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .arg(
             Arg::with_name("light-client-server")
                 .long("light-client-server")
-                .help("Act as a full node supporting light clients on the p2p network \
+                .help("Act as a full node supporting light clients on the p2p network and HTTP API \
                        [experimental]")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("rpc-zstd")
+                .long("rpc-zstd")
+                .help("Offer and prefer the Lighthouse-specific ssz_zstd encoding for block RPC \
+                       requests and responses, falling back to ssz_snappy for peers that do not \
+                       support it. This reduces sync bandwidth at the cost of CPU time and an \
+                       extra negotiation round trip with other peers [experimental]")
+                .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("outbound-bandwidth-budget")
+                .long("outbound-bandwidth-budget")
+                .value_name("BYTES_PER_SEC")
+                .help("The outbound libp2p bandwidth, in bytes per second, that this node aims to \
+                       stay within. When the node is near this budget, BlocksByRange requests for \
+                       finalized blocks are served at the lowest priority so that they do not \
+                       delay gossip.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("prefer-quic-peers")
+                .long("prefer-quic-peers")
+                .help("Dial discovered peers that advertise a QUIC address before those that only \
+                       advertise TCP. Has no effect if QUIC support is disabled.")
+                .takes_value(false)
+        )
         .arg(
             Arg::with_name("gui")
                 .long("gui")
@@ -1191,6 +1459,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                       This enables --http and --validator-monitor-auto and enables SSE logging.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("builder-bid-margin-percent")
+                .long("builder-bid-margin-percent")
+                .value_name("PERCENTAGE")
+                .help("The percentage by which the value of a builder bid must exceed the value of \
+                    the local EE's payload for the builder payload to be used. Both values are \
+                    recorded for each proposal and can be retrieved from the \
+                    /lighthouse/proposal_decisions HTTP API endpoint.")
+                .default_value("0")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("always-prefer-builder-payload")
             .long("always-prefer-builder-payload")
@@ -1199,6 +1478,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             // to local payloads, therefore it fundamentally conflicts with
             // always using the builder.
             .conflicts_with("builder-profit-threshold")
+            .conflicts_with("builder-bid-margin-percent")
         )
         .arg(
             Arg::with_name("invalid-gossip-verified-blocks-path")
@@ -1273,5 +1553,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("64")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("beacon-processor-attestation-batch-window-ms")
+                .long("beacon-processor-attestation-batch-window-ms")
+                .value_name("MILLISECONDS")
+                .help("Holds gossip attestations for up to this many milliseconds when workers are \
+                       available, so that attestations from all subnets arriving in quick \
+                       succession share a single signature verification batch. This may \
+                       significantly reduce CPU usage for nodes subscribed to many subnets, at the \
+                       cost of a small delay. Disabled by default.")
+                .takes_value(true)
+        )
         .group(ArgGroup::with_name("enable_http").args(&["http", "gui", "staking"]).multiple(true))
 }
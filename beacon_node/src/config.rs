@@ -24,7 +24,9 @@ use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::time::Duration;
-use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes, GRAFFITI_BYTES_LEN};
+use types::{
+    Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes, SubnetId, SyncSubnetId, GRAFFITI_BYTES_LEN,
+};
 
 /// Gets the fully-initialized global client.
 ///
@@ -33,17 +35,16 @@ use types::{Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes, GRAFFITI_BYTES_
 /// The output of this function depends primarily upon the given `cli_args`, however it's behaviour
 /// may be influenced by other external services like the contents of the file system or the
 /// response of some remote server.
+///
+/// Unlike `parse_config`, this prepares the data directory: the database is removed if
+/// `--purge-db` is set, the directory is created and the JWT secret given by
+/// `--execution-jwt-secret-key` is written to it.
 pub fn get_config<E: EthSpec>(
     cli_args: &ArgMatches,
     context: &RuntimeContext<E>,
 ) -> Result<ClientConfig, String> {
-    let spec = &context.eth2_config.spec;
     let log = context.log();
-
-    let mut client_config = ClientConfig::default();
-
-    // Update the client's data directory
-    client_config.set_data_dir(get_data_dir(cli_args));
+    let client_config = parse_config(cli_args, context)?;
 
     // If necessary, remove any existing database and configuration
     if client_config.data_dir().exists() && cli_args.is_present("purge-db") {
@@ -72,6 +73,45 @@ pub fn get_config<E: EthSpec>(
     log_dir.pop();
     info!(log, "Data directory initialised"; "datadir" => log_dir.into_os_string().into_string().expect("Datadir should be a valid os string"));
 
+    // Persist a JWT secret key which was passed directly via cli flag to the default file location,
+    // from which `parse_config` set the execution layer to read it.
+    if cli_args.is_present("execution-endpoint") && !cli_args.is_present("execution-jwt") {
+        if let Some(jwt_secret_key) = cli_args.value_of("execution-jwt-secret-key") {
+            use std::fs::File;
+            use std::io::Write;
+            let secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
+            let mut jwt_secret_key_file = File::create(secret_file)
+                .map_err(|e| format!("Error while creating jwt_secret_key file: {:?}", e))?;
+            jwt_secret_key_file
+                .write_all(jwt_secret_key.as_bytes())
+                .map_err(|e| {
+                    format!(
+                        "Error occured while writing to jwt_secret_key file: {:?}",
+                        e
+                    )
+                })?;
+        }
+    }
+
+    Ok(client_config)
+}
+
+/// Parses the config of the beacon node from `cli_args` without modifying the file system, e.g. to
+/// validate it.
+///
+/// See `get_config`, which also prepares the data directory.
+pub fn parse_config<E: EthSpec>(
+    cli_args: &ArgMatches,
+    context: &RuntimeContext<E>,
+) -> Result<ClientConfig, String> {
+    let spec = &context.eth2_config.spec;
+    let log = context.log();
+
+    let mut client_config = ClientConfig::default();
+
+    // Update the client's data directory
+    client_config.set_data_dir(get_data_dir(cli_args));
+
     /*
      * Networking
      */
@@ -156,12 +196,42 @@ pub fn get_config<E: EthSpec>(
 
         client_config.http_api.duplicate_block_status_code =
             parse_required(cli_args, "http-duplicate-block-status")?;
+
+        if let Some(path) =
+            clap_utils::parse_optional::<PathBuf>(cli_args, "http-validator-monitor-token-file")?
+        {
+            client_config.http_api.validator_monitor_registration_token =
+                Some(read_token_file(&path)?);
+        }
+
+        if let Some(path) =
+            clap_utils::parse_optional::<PathBuf>(cli_args, "http-admin-token-file")?
+        {
+            client_config.http_api.admin_token = Some(read_token_file(&path)?);
+        }
+
+        client_config.http_api.database_backup_dir =
+            clap_utils::parse_optional(cli_args, "http-database-backup-dir")?;
+
+        if let Some(endpoint) = cli_args.value_of("otlp-endpoint") {
+            client_config.http_api.otlp_endpoint = Some(
+                SensitiveUrl::parse(endpoint)
+                    .map_err(|e| format!("Invalid --otlp-endpoint: {:?}", e))?,
+            );
+        }
     }
 
     if let Some(cache_size) = clap_utils::parse_optional(cli_args, "shuffling-cache-size")? {
         client_config.chain.shuffling_cache_size = cache_size;
     }
 
+    if let Some(budget_mb) =
+        clap_utils::parse_optional::<usize>(cli_args, "shuffling-cache-memory-budget")?
+    {
+        client_config.chain.shuffling_cache_memory_budget =
+            Some(budget_mb.saturating_mul(1024 * 1024));
+    }
+
     /*
      * Prometheus metrics HTTP server
      */
@@ -314,31 +384,24 @@ pub fn get_config<E: EthSpec>(
             secret_file =
                 parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
 
-        // Check if the JWT secret key is passed directly via cli flag and persist it to the default
-        // file location.
-        } else if let Some(jwt_secret_key) = cli_args.value_of("execution-jwt-secret-key") {
-            use std::fs::File;
-            use std::io::Write;
+        // Check if the JWT secret key is passed directly via cli flag. `get_config` persists it to
+        // the default file location.
+        } else if cli_args.is_present("execution-jwt-secret-key") {
             secret_file = client_config.data_dir().join(DEFAULT_JWT_FILE);
-            let mut jwt_secret_key_file = File::create(secret_file.clone())
-                .map_err(|e| format!("Error while creating jwt_secret_key file: {:?}", e))?;
-            jwt_secret_key_file
-                .write_all(jwt_secret_key.as_bytes())
-                .map_err(|e| {
-                    format!(
-                        "Error occured while writing to jwt_secret_key file: {:?}",
-                        e
-                    )
-                })?;
         } else {
             return Err("Error! Please set either --execution-jwt file_path or --execution-jwt-secret-key directly via cli when using --execution-endpoint".to_string());
         }
 
         // Parse and set the payload builder, if any.
         if let Some(endpoint) = cli_args.value_of("builder") {
-            let payload_builder =
-                parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-            el_config.builder_url = Some(payload_builder);
+            let mut payload_builders = endpoint
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("--builder contains an invalid value {:?}", e))?
+                .into_iter();
+            el_config.builder_url = payload_builders.next();
+            el_config.additional_builder_urls = payload_builders.collect();
 
             el_config.builder_user_agent =
                 clap_utils::parse_optional(cli_args, "builder-user-agent")?;
@@ -347,6 +410,28 @@ pub fn get_config<E: EthSpec>(
         // Set config values from parse values.
         el_config.secret_files = vec![secret_file.clone()];
         el_config.execution_endpoints = vec![execution_endpoint.clone()];
+
+        // Any further endpoints are only used to cross-check the first.
+        if let Some(endpoints) = cli_args.value_of("execution-cross-check-endpoints") {
+            let cross_check_endpoints = endpoints
+                .split(',')
+                .map(SensitiveUrl::parse)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Invalid cross-check execution endpoint: {:?}", e))?;
+            let cross_check_jwts = cli_args
+                .value_of("execution-cross-check-jwts")
+                .map(|jwts| jwts.split(',').map(PathBuf::from).collect::<Vec<_>>())
+                .unwrap_or_default();
+            if cross_check_jwts.len() > cross_check_endpoints.len() {
+                return Err(
+                    "More --execution-cross-check-jwts than --execution-cross-check-endpoints"
+                        .to_string(),
+                );
+            }
+
+            el_config.execution_endpoints.extend(cross_check_endpoints);
+            el_config.secret_files.extend(cross_check_jwts);
+        }
         el_config.suggested_fee_recipient =
             clap_utils::parse_optional(cli_args, "suggested-fee-recipient")?;
         el_config.jwt_id = clap_utils::parse_optional(cli_args, "execution-jwt-id")?;
@@ -354,6 +439,8 @@ pub fn get_config<E: EthSpec>(
         el_config.default_datadir = client_config.data_dir().clone();
         el_config.builder_profit_threshold =
             clap_utils::parse_required(cli_args, "builder-profit-threshold")?;
+        el_config.builder_bid_margin_percent =
+            clap_utils::parse_required(cli_args, "builder-bid-margin-percent")?;
         el_config.always_prefer_builder_payload =
             cli_args.is_present("always-prefer-builder-payload");
 
@@ -402,6 +489,19 @@ pub fn get_config<E: EthSpec>(
             .map_err(|_| "historic-state-cache-size is not a valid integer".to_string())?;
     }
 
+    client_config.store.backend = clap_utils::parse_required(cli_args, "db-backend")?;
+
+    if let Some(exponents) = cli_args.value_of("hdiff-exponents") {
+        let exponents = exponents
+            .split(',')
+            .map(u8::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid --hdiff-exponents value: {:?}", e))?;
+        store::hdiff::verify_exponents(&exponents)
+            .map_err(|e| format!("Invalid --hdiff-exponents value: {}", e))?;
+        client_config.store.hdiff_exponents = exponents;
+    }
+
     client_config.store.compact_on_init = cli_args.is_present("compact-db");
     if let Some(compact_on_prune) = cli_args.value_of("auto-compact-db") {
         client_config.store.compact_on_prune = compact_on_prune
@@ -477,8 +577,18 @@ pub fn get_config<E: EthSpec>(
 
     let genesis_state_url_opt =
         clap_utils::parse_optional::<String>(cli_args, "genesis-state-url")?;
-    let checkpoint_sync_url_opt =
-        clap_utils::parse_optional::<String>(cli_args, "checkpoint-sync-url")?;
+    // The first checkpoint sync URL provides the state, any others are used to verify it.
+    let checkpoint_sync_urls = cli_args
+        .value_of("checkpoint-sync-url")
+        .map(|urls| {
+            urls.split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let checkpoint_sync_url_opt = checkpoint_sync_urls.first().cloned();
 
     // If the `--genesis-state-url` is defined, use that to download the
     // genesis state bytes. If it's not defined, try `--checkpoint-sync-url`.
@@ -519,11 +629,34 @@ pub fn get_config<E: EthSpec>(
                 anchor_state_bytes,
                 anchor_block_bytes,
             }
-        } else if let Some(remote_bn_url) = cli_args.value_of("checkpoint-sync-url") {
-            let url = SensitiveUrl::parse(remote_bn_url)
-                .map_err(|e| format!("Invalid checkpoint sync URL: {:?}", e))?;
+        } else if !checkpoint_sync_urls.is_empty() {
+            let mut urls = checkpoint_sync_urls
+                .iter()
+                .map(|url| {
+                    SensitiveUrl::parse(url)
+                        .map_err(|e| format!("Invalid checkpoint sync URL: {:?}", e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let url = urls.remove(0);
+            let cross_check_urls = urls;
+
+            // By default a majority of all the providers must agree on the state.
+            let num_providers = cross_check_urls.len() + 1;
+            let quorum = clap_utils::parse_optional::<usize>(cli_args, "checkpoint-sync-quorum")?
+                .unwrap_or(num_providers / 2 + 1);
+            if quorum == 0 || quorum > num_providers {
+                return Err(format!(
+                    "--checkpoint-sync-quorum must be between 1 and the number of checkpoint \
+                     sync URLs ({})",
+                    num_providers
+                ));
+            }
 
-            ClientGenesis::CheckpointSyncUrl { url }
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                cross_check_urls,
+                quorum,
+            }
         } else {
             ClientGenesis::GenesisState
         }
@@ -611,6 +744,10 @@ pub fn get_config<E: EthSpec>(
         spec.gossip_max_size as usize,
     );
 
+    // The chain caches light client updates for the network and the HTTP API to serve.
+    client_config.chain.enable_light_client_server =
+        client_config.network.enable_light_client_server;
+
     if cli_args.is_present("slasher") {
         let slasher_dir = if let Some(slasher_dir) = cli_args.value_of("slasher-dir") {
             PathBuf::from(slasher_dir)
@@ -716,6 +853,12 @@ pub fn get_config<E: EthSpec>(
         client_config.validator_monitor_individual_tracking_threshold = count;
     }
 
+    if let Some(metric_aggregation) =
+        clap_utils::parse_optional(cli_args, "validator-monitor-metric-aggregation")?
+    {
+        client_config.chain.validator_monitor_metric_aggregation = metric_aggregation;
+    }
+
     if cli_args.is_present("disable-lock-timeouts") {
         client_config.chain.enable_lock_timeouts = false;
     }
@@ -748,6 +891,35 @@ pub fn get_config<E: EthSpec>(
                 DisallowedReOrgOffsets::new::<E>(disallowed_offsets)
                     .map_err(|e| format!("invalid disallowed-offsets: {e:?}"))?;
         }
+
+        if let Some(schedule_str) =
+            clap_utils::parse_optional::<String>(cli_args, "proposer-reorg-threshold-schedule")?
+        {
+            let mut schedule = schedule_str
+                .split(',')
+                .map(|entry| {
+                    let (epoch, threshold) = entry.split_once(':').ok_or_else(|| {
+                        format!("invalid reorg threshold schedule entry: {entry}")
+                    })?;
+                    let epoch = epoch
+                        .parse()
+                        .map_err(|e| format!("invalid reorg threshold schedule epoch: {e:?}"))?;
+                    let threshold = threshold
+                        .parse()
+                        .map_err(|e| format!("invalid reorg threshold schedule percent: {e:?}"))?;
+                    Ok((Epoch::new(epoch), ReOrgThreshold(threshold)))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            schedule.sort_by_key(|(epoch, _)| *epoch);
+            if schedule.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                return Err("duplicate epoch in reorg threshold schedule".to_string());
+            }
+            client_config.chain.re_org_threshold_schedule = schedule;
+        }
+
+        client_config.chain.re_org_max_consecutive =
+            clap_utils::parse_optional(cli_args, "proposer-reorg-max-consecutive")?;
+        client_config.chain.re_org_dry_run = cli_args.is_present("proposer-reorg-dry-run");
     }
 
     // Note: This overrides any previous flags that enable this option.
@@ -792,6 +964,24 @@ pub fn get_config<E: EthSpec>(
 
     client_config.chain.paranoid_block_proposal = cli_args.is_present("paranoid-block-proposal");
 
+    client_config.chain.state_root_audit_interval =
+        clap_utils::parse_optional(cli_args, "state-root-audit-interval")?;
+
+    if let Some(threads) =
+        clap_utils::parse_optional::<usize>(cli_args, "max-epoch-processing-threads")?
+    {
+        if threads == 0 {
+            return Err("--max-epoch-processing-threads cannot be 0".to_string());
+        }
+        client_config.chain.max_epoch_processing_threads = Some(threads);
+    }
+
+    client_config.chain.max_cover_attestation_packing_fork =
+        clap_utils::parse_optional(cli_args, "max-cover-attestation-packing-fork")?;
+    client_config.chain.attestation_packing_time_budget = Duration::from_millis(
+        clap_utils::parse_required(cli_args, "attestation-packing-time-budget")?,
+    );
+
     /*
      * Builder fallback configs.
      */
@@ -856,6 +1046,9 @@ pub fn get_config<E: EthSpec>(
         .beacon_processor
         .max_gossip_aggregate_batch_size =
         clap_utils::parse_required(cli_args, "beacon-processor-aggregate-batch-size")?;
+    client_config.beacon_processor.attestation_batch_window =
+        clap_utils::parse_optional(cli_args, "beacon-processor-attestation-batch-window-ms")?
+            .map(Duration::from_millis);
 
     Ok(client_config)
 }
@@ -1095,6 +1288,30 @@ pub fn set_network_config(
         config.subscribe_all_subnets = true;
     }
 
+    if let Some(subnets_str) = cli_args.value_of("backbone-attestation-subnets") {
+        config.backbone_attestation_subnets = subnets_str
+            .split(',')
+            .map(|subnet_id| {
+                subnet_id
+                    .parse()
+                    .map(SubnetId::new)
+                    .map_err(|_| format!("Invalid attestation subnet id: {}", subnet_id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+    }
+
+    if let Some(subnets_str) = cli_args.value_of("backbone-sync-subnets") {
+        config.backbone_sync_subnets = subnets_str
+            .split(',')
+            .map(|subnet_id| {
+                subnet_id
+                    .parse()
+                    .map(SyncSubnetId::new)
+                    .map_err(|_| format!("Invalid sync committee subnet id: {}", subnet_id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+    }
+
     if cli_args.is_present("import-all-attestations") {
         config.import_all_attestations = true;
     }
@@ -1363,6 +1580,13 @@ pub fn set_network_config(
     // Light client server config.
     config.enable_light_client_server = cli_args.is_present("light-client-server");
 
+    config.enable_rpc_zstd = cli_args.is_present("rpc-zstd");
+
+    config.outbound_bandwidth_budget =
+        clap_utils::parse_optional(cli_args, "outbound-bandwidth-budget")?;
+
+    config.prefer_quic_peers = cli_args.is_present("prefer-quic-peers");
+
     // The self limiter is disabled by default.
     // This flag can be used both with or without a value. Try to parse it first with a value, if
     // no value is defined but the flag is present, use the default params.
@@ -1400,6 +1624,25 @@ pub fn set_network_config(
             Some(config_str.parse()?)
         }
     };
+
+    config.max_subnet_peer_fraction =
+        clap_utils::parse_optional(cli_args, "max-subnet-peer-fraction")?;
+    config.max_asn_peer_fraction = clap_utils::parse_optional(cli_args, "max-asn-peer-fraction")?;
+    config.asn_database = clap_utils::parse_optional(cli_args, "asn-database")?;
+    for fraction in [config.max_subnet_peer_fraction, config.max_asn_peer_fraction]
+        .into_iter()
+        .flatten()
+    {
+        if fraction <= 0.0 || fraction > 1.0 {
+            return Err(format!(
+                "Peer fraction limits must be in the range (0, 1], got {}",
+                fraction
+            ));
+        }
+    }
+    if config.max_asn_peer_fraction.is_some() && config.asn_database.is_none() {
+        return Err("--max-asn-peer-fraction requires --asn-database".to_string());
+    }
     Ok(())
 }
 
@@ -1423,6 +1666,18 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
+/// Reads a secret token from the file at `path`, ignoring surrounding whitespace.
+fn read_token_file(path: &Path) -> Result<String, String> {
+    let token = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?
+        .trim()
+        .to_string();
+    if token.is_empty() {
+        return Err(format!("{} does not contain a token", path.display()));
+    }
+    Ok(token)
+}
+
 /// Get the `slots_per_restore_point` value to use for the database.
 ///
 /// Return `(sprp, set_explicitly)` where `set_explicitly` is `true` if the user provided the value.
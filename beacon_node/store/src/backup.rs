@@ -0,0 +1,169 @@
+//! Online backups of the hot database.
+//!
+//! A backup directory contains a copy of the hot database taken from a consistent snapshot, and a
+//! manifest describing the freezer data required to restore it. The freezer database is
+//! append-only below the split slot, so it can be copied by other means once the backup completes.
+use crate::hot_cold_store::{HotColdDB, HotStateSummary, Split};
+use crate::metadata::{AnchorInfo, ANCHOR_INFO_KEY, CURRENT_SCHEMA_VERSION, SPLIT_KEY};
+use crate::{Error, ItemStore, LevelDB, StoreConfig};
+use serde_derive::{Deserialize, Serialize};
+use slog::info;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use types::EthSpec;
+
+/// The directory within a backup that holds the copy of the hot database.
+pub const BACKUP_HOT_DB_DIRNAME: &str = "hot_db";
+/// The file within a backup that holds the `BackupManifest`.
+pub const BACKUP_MANIFEST_FILENAME: &str = "manifest.json";
+
+/// The progress of the most recent backup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupStatus {
+    pub target_dir: PathBuf,
+    pub keys_copied: u64,
+    pub bytes_copied: u64,
+    pub complete: bool,
+    /// The reason the backup failed, if it did.
+    pub error: Option<String>,
+}
+
+/// Describes a completed backup.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub schema_version: u64,
+    pub config: StoreConfig,
+    /// The split recorded in the snapshot. A copy of the freezer database taken after the backup
+    /// completed contains all of the data prior to `split.slot` that the backup relies upon.
+    pub split: Split,
+    pub anchor: Option<AnchorInfo>,
+    pub keys: u64,
+    pub bytes: u64,
+}
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: ItemStore<E>,
+    Cold: ItemStore<E>,
+{
+    /// Copy a snapshot of the hot database into `target_dir` without pausing the node, and write
+    /// a `BackupManifest` alongside it.
+    ///
+    /// The copy is throttled to `max_bytes_per_second` if it is set. Only one backup may run at a
+    /// time, its progress is available from `Self::backup_status`.
+    pub fn backup_hot_db(
+        &self,
+        target_dir: &Path,
+        max_bytes_per_second: Option<u64>,
+    ) -> Result<BackupManifest, Error> {
+        {
+            let mut status = self.backup_status.write();
+            if status.as_ref().map_or(false, |status| !status.complete) {
+                return Err(Error::BackupInProgress);
+            }
+            *status = Some(BackupStatus {
+                target_dir: target_dir.to_path_buf(),
+                keys_copied: 0,
+                bytes_copied: 0,
+                complete: false,
+                error: None,
+            });
+        }
+
+        let result = self.write_backup(target_dir, max_bytes_per_second);
+
+        if let Some(status) = self.backup_status.write().as_mut() {
+            status.complete = true;
+            status.error = result.as_ref().err().map(|e| format!("{:?}", e));
+        }
+        result
+    }
+
+    /// The progress of the most recent backup, if any.
+    pub fn backup_status(&self) -> Option<BackupStatus> {
+        self.backup_status.read().clone()
+    }
+
+    fn write_backup(
+        &self,
+        target_dir: &Path,
+        max_bytes_per_second: Option<u64>,
+    ) -> Result<BackupManifest, Error> {
+        info!(
+            self.log,
+            "Starting hot database backup";
+            "target_dir" => ?target_dir,
+            "max_bytes_per_second" => max_bytes_per_second,
+        );
+
+        fs::create_dir_all(target_dir).map_err(|e| Error::BackupIoError(format!("{:?}", e)))?;
+
+        let start = Instant::now();
+        let mut keys = 0;
+        let mut bytes = 0;
+        let hot_db_dir = target_dir.join(BACKUP_HOT_DB_DIRNAME);
+        self.hot_db
+            .export_snapshot(&hot_db_dir, &mut |keys_copied, bytes_copied| {
+                keys = keys_copied;
+                bytes = bytes_copied;
+                if let Some(status) = self.backup_status.write().as_mut() {
+                    status.keys_copied = keys_copied;
+                    status.bytes_copied = bytes_copied;
+                }
+
+                if let Some(max_bytes_per_second) = max_bytes_per_second {
+                    let target_elapsed =
+                        Duration::from_secs_f64(bytes_copied as f64 / max_bytes_per_second as f64);
+                    if let Some(delay) = target_elapsed.checked_sub(start.elapsed()) {
+                        std::thread::sleep(delay);
+                    }
+                }
+            })?;
+
+        // The split and anchor are read from the copy, since a migration may have advanced them in
+        // the live database whilst the snapshot was being copied.
+        let (split, anchor) = read_backup_metadata::<E>(&hot_db_dir)?;
+
+        let manifest = BackupManifest {
+            schema_version: CURRENT_SCHEMA_VERSION.as_u64(),
+            config: self.config.clone(),
+            split,
+            anchor,
+            keys,
+            bytes,
+        };
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| Error::BackupIoError(format!("{:?}", e)))?;
+        fs::write(target_dir.join(BACKUP_MANIFEST_FILENAME), manifest_json)
+            .map_err(|e| Error::BackupIoError(format!("{:?}", e)))?;
+
+        info!(
+            self.log,
+            "Hot database backup complete";
+            "keys" => keys,
+            "bytes" => bytes,
+            "split_slot" => manifest.split.slot,
+            "duration_secs" => start.elapsed().as_secs(),
+        );
+
+        Ok(manifest)
+    }
+}
+
+/// Reads the split and anchor from the copy of the hot database in `hot_db_dir`.
+fn read_backup_metadata<E: EthSpec>(
+    hot_db_dir: &Path,
+) -> Result<(Split, Option<AnchorInfo>), Error> {
+    let db = LevelDB::<E>::open(hot_db_dir)?;
+
+    let mut split: Split = db.get(&SPLIT_KEY)?.ok_or(Error::BackupMissingSplit)?;
+    // The block root is not persisted with the split, see `HotColdDB::load_split`.
+    if let Some(summary) = db.get::<HotStateSummary>(&split.state_root)? {
+        split.block_root = summary.latest_block_root;
+    }
+    let anchor = db.get(&ANCHOR_INFO_KEY)?;
+
+    Ok((split, anchor))
+}
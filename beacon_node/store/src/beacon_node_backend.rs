@@ -0,0 +1,99 @@
+//! The key-value store used by the beacon node, selected at runtime by `StoreConfig::backend`.
+use crate::config::DatabaseBackend;
+use crate::{
+    ColumnIter, ColumnKeyIter, DBColumn, Error, ItemStore, KeyValueStore, KeyValueStoreOp, LevelDB,
+    MemoryStore,
+};
+use parking_lot::MutexGuard;
+use std::path::Path;
+use types::EthSpec;
+
+/// A key-value store whose implementation is chosen when it is opened.
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    /// Not persisted, intended for tests and simulations.
+    Memory(MemoryStore<E>),
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open the database at `path` using `backend`. The path is ignored by the memory backend.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(Self::LevelDb),
+            DatabaseBackend::Memory => Ok(Self::Memory(MemoryStore::open())),
+        }
+    }
+
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Self::LevelDb(_) => DatabaseBackend::LevelDb,
+            Self::Memory(_) => DatabaseBackend::Memory,
+        }
+    }
+}
+
+/// Forward a method call to the store of whichever backend is in use.
+macro_rules! map_backend {
+    ($self:expr, $db:ident => $body:expr) => {
+        match $self {
+            BeaconNodeBackend::LevelDb($db) => $body,
+            BeaconNodeBackend::Memory($db) => $body,
+        }
+    };
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        map_backend!(self, db => db.get_bytes(column, key))
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        map_backend!(self, db => db.put_bytes(column, key, value))
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        map_backend!(self, db => db.put_bytes_sync(column, key, value))
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        map_backend!(self, db => db.sync())
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        map_backend!(self, db => db.key_exists(column, key))
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        map_backend!(self, db => db.key_delete(column, key))
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        map_backend!(self, db => db.do_atomically(batch))
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        map_backend!(self, db => db.begin_rw_transaction())
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        map_backend!(self, db => db.compact())
+    }
+
+    fn iter_column(&self, column: DBColumn) -> ColumnIter {
+        map_backend!(self, db => db.iter_column(column))
+    }
+
+    fn iter_column_keys(&self, column: DBColumn) -> ColumnKeyIter {
+        map_backend!(self, db => db.iter_column_keys(column))
+    }
+
+    fn export_snapshot(
+        &self,
+        path: &Path,
+        on_batch: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), Error> {
+        map_backend!(self, db => db.export_snapshot(path, on_batch))
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
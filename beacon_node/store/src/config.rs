@@ -2,6 +2,7 @@ use crate::{DBColumn, Error, StoreItem};
 use serde_derive::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
+use strum::{EnumString, IntoStaticStr};
 use types::{EthSpec, MinimalEthSpec};
 
 pub const PREV_DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
@@ -9,6 +10,16 @@ pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 8192;
 pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 5;
 pub const DEFAULT_HISTORIC_STATE_CACHE_SIZE: usize = 1;
 
+/// The key-value store backing the hot and freezer databases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, IntoStaticStr)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    LevelDb,
+    /// Not persisted across restarts, intended for tests and simulations.
+    Memory,
+}
+
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoreConfig {
@@ -26,6 +37,13 @@ pub struct StoreConfig {
     pub compact_on_prune: bool,
     /// Whether to prune payloads on initialization and finalization.
     pub prune_payloads: bool,
+    /// Layer exponents of the hierarchy of diffs used to store hot epoch boundary states.
+    ///
+    /// Empty to store every epoch boundary state in full. May be changed between restarts, as
+    /// each diff records the state it was computed against.
+    pub hdiff_exponents: Vec<u8>,
+    /// The key-value store backing the hot and freezer databases.
+    pub backend: DatabaseBackend,
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -50,6 +68,8 @@ impl Default for StoreConfig {
             compact_on_init: false,
             compact_on_prune: true,
             prune_payloads: true,
+            hdiff_exponents: vec![],
+            backend: DatabaseBackend::LevelDb,
         }
     }
 }
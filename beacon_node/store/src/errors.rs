@@ -1,5 +1,6 @@
 use crate::chunked_vector::ChunkError;
 use crate::config::StoreConfigError;
+use crate::hdiff::HDiffError;
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
@@ -45,6 +46,14 @@ pub enum Error {
     SlotClockUnavailableForMigration,
     UnableToDowngrade,
     InconsistentFork(InconsistentFork),
+    /// The database backend cannot produce a snapshot of itself.
+    SnapshotUnsupported,
+    /// A backup was requested whilst another was still running.
+    BackupInProgress,
+    BackupIoError(String),
+    /// The snapshot copied by a backup does not contain a split.
+    BackupMissingSplit,
+    HDiffError(HDiffError),
 }
 
 pub trait HandleUnavailable<T> {
@@ -61,6 +70,12 @@ impl<T> HandleUnavailable<T> for Result<T> {
     }
 }
 
+impl From<HDiffError> for Error {
+    fn from(e: HDiffError) -> Error {
+        Error::HDiffError(e)
+    }
+}
+
 impl From<DecodeError> for Error {
     fn from(e: DecodeError) -> Error {
         Error::SszDecodeError(e)
@@ -0,0 +1,212 @@
+//! Hierarchical state diffs for the hot database.
+//!
+//! Epoch boundary states in the hot database may be stored as a diff against an earlier epoch
+//! boundary state rather than in full. The diffs form a hierarchy described by a list of layer
+//! exponents `e_0 < e_1 < ... < e_n`:
+//!
+//! - States at epochs which are a multiple of `2^e_n` are stored in full.
+//! - A state at any other epoch is diffed against the state at the epoch found by rounding down
+//!   to a multiple of `2^e_(i+1)`, where `e_i` is the largest exponent such that the epoch is a
+//!   multiple of `2^e_i` (or `e_0` if there is none).
+//!
+//! Loading a state therefore applies at most `n + 1` diffs on top of a full state.
+use crate::{DBColumn, Error, StoreItem};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use types::{Epoch, Hash256, Slot};
+
+/// The zstd compression level used for diffs.
+pub const HDIFF_COMPRESSION_LEVEL: i32 = 1;
+
+/// The largest permitted layer exponent.
+pub const MAX_HDIFF_EXPONENT: u8 = 32;
+
+#[derive(Debug, PartialEq)]
+pub enum HDiffError {
+    /// The diff could not be compressed.
+    Compression(String),
+    /// The diff could not be decompressed.
+    Decompression(String),
+    /// The decompressed diff had an unexpected length.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+/// A state stored as the difference from the state at `base_state_root`.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct HotStateDiff {
+    /// The slot of the state which the diff produces.
+    pub slot: Slot,
+    pub base_state_root: Hash256,
+    pub base_slot: Slot,
+    /// The length of the stored bytes of the state which the diff produces.
+    pub target_len: u64,
+    /// Compressed XOR of the stored bytes of the state with those of the base state.
+    pub diff: Vec<u8>,
+}
+
+impl HotStateDiff {
+    /// Compute the diff which produces `target` when applied to `base`.
+    pub fn compute(
+        slot: Slot,
+        base_state_root: Hash256,
+        base_slot: Slot,
+        base: &[u8],
+        target: &[u8],
+    ) -> Result<Self, HDiffError> {
+        let xor = xor_bytes(base, target);
+        let diff = zstd::bulk::compress(&xor, HDIFF_COMPRESSION_LEVEL)
+            .map_err(|e| HDiffError::Compression(e.to_string()))?;
+        Ok(Self {
+            slot,
+            base_state_root,
+            base_slot,
+            target_len: target.len() as u64,
+            diff,
+        })
+    }
+
+    /// Apply the diff to the bytes of the base state, producing the bytes of the target state.
+    pub fn apply(&self, base: &[u8]) -> Result<Vec<u8>, HDiffError> {
+        let target_len = self.target_len as usize;
+        let expected = std::cmp::max(base.len(), target_len);
+        let xor = zstd::bulk::decompress(&self.diff, expected)
+            .map_err(|e| HDiffError::Decompression(e.to_string()))?;
+        if xor.len() != expected {
+            return Err(HDiffError::LengthMismatch {
+                expected,
+                actual: xor.len(),
+            });
+        }
+        let mut target = xor_bytes(base, &xor);
+        target.truncate(target_len);
+        Ok(target)
+    }
+}
+
+impl StoreItem for HotStateDiff {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconStateDiff
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+/// XOR `other` with `base`, treating the shorter of the two as if it were padded with zeroes.
+fn xor_bytes(base: &[u8], other: &[u8]) -> Vec<u8> {
+    let mut result = other.to_vec();
+    if result.len() < base.len() {
+        result.resize(base.len(), 0);
+    }
+    for (byte, base_byte) in result.iter_mut().zip(base) {
+        *byte ^= base_byte;
+    }
+    result
+}
+
+/// Returns the epoch of the state that the state at `epoch` should be diffed against, or `None`
+/// if it should be stored in full.
+///
+/// `exponents` must be sorted in strictly ascending order. An empty list disables diffs.
+pub fn diff_base_epoch(exponents: &[u8], epoch: Epoch) -> Option<Epoch> {
+    let epoch = epoch.as_u64();
+    let is_multiple = |exponent: u8| epoch % (1 << exponent) == 0;
+
+    let snapshot_exponent = *exponents.last()?;
+    if is_multiple(snapshot_exponent) {
+        return None;
+    }
+
+    let layer = exponents
+        .iter()
+        .rposition(|&exponent| is_multiple(exponent))
+        .map_or(0, |layer| layer + 1);
+    let base_exponent = exponents.get(layer).copied().unwrap_or(snapshot_exponent);
+    let base_epoch = epoch - epoch % (1 << base_exponent);
+
+    (base_epoch != epoch).then_some(Epoch::new(base_epoch))
+}
+
+/// Checks that `exponents` are suitable for `diff_base_epoch`.
+pub fn verify_exponents(exponents: &[u8]) -> Result<(), String> {
+    if exponents.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err("hdiff exponents must be strictly ascending".into());
+    }
+    if exponents
+        .iter()
+        .any(|&exponent| exponent > MAX_HDIFF_EXPONENT)
+    {
+        return Err(format!(
+            "hdiff exponents must not exceed {}",
+            MAX_HDIFF_EXPONENT
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_round_trip() {
+        let base = (0..1_000u32).flat_map(u32::to_le_bytes).collect::<Vec<_>>();
+
+        let mut grown = base.clone();
+        grown[17] ^= 0xff;
+        grown.extend_from_slice(&[1, 2, 3]);
+
+        let mut shrunk = base.clone();
+        shrunk.truncate(base.len() - 5);
+        shrunk[3] = 42;
+
+        for target in [base.clone(), grown, shrunk] {
+            let diff = HotStateDiff::compute(
+                Slot::new(64),
+                Hash256::zero(),
+                Slot::new(32),
+                &base,
+                &target,
+            )
+            .unwrap();
+            let decoded = HotStateDiff::from_store_bytes(&diff.as_store_bytes()).unwrap();
+            assert_eq!(decoded.apply(&base).unwrap(), target);
+        }
+    }
+
+    #[test]
+    fn base_epochs() {
+        let exponents = [1, 3, 5];
+        let base = |epoch: u64| diff_base_epoch(&exponents, Epoch::new(epoch)).map(Epoch::as_u64);
+
+        // Snapshots.
+        assert_eq!(base(0), None);
+        assert_eq!(base(32), None);
+        assert_eq!(base(64), None);
+        // Odd epochs diff against the nearest multiple of 2.
+        assert_eq!(base(33), Some(32));
+        assert_eq!(base(35), Some(34));
+        // Multiples of 2 diff against multiples of 8.
+        assert_eq!(base(34), Some(32));
+        assert_eq!(base(42), Some(40));
+        // Multiples of 8 diff against multiples of 32.
+        assert_eq!(base(40), Some(32));
+        assert_eq!(base(56), Some(32));
+
+        assert_eq!(diff_base_epoch(&[], Epoch::new(3)), None);
+    }
+
+    #[test]
+    fn exponents_verification() {
+        assert!(verify_exponents(&[]).is_ok());
+        assert!(verify_exponents(&[2, 5, 9]).is_ok());
+        assert!(verify_exponents(&[5, 2]).is_err());
+        assert!(verify_exponents(&[2, 2]).is_err());
+        assert!(verify_exponents(&[MAX_HDIFF_EXPONENT + 1]).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::backup::BackupStatus;
+use crate::beacon_node_backend::BeaconNodeBackend;
 use crate::chunked_vector::{
     store_updated_vector, BlockRoots, HistoricalRoots, HistoricalSummaries, RandaoMixes, StateRoots,
 };
@@ -6,10 +8,10 @@ use crate::config::{
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
-use crate::impls::beacon_state::{get_full_state, store_full_state};
+use crate::hdiff::{diff_base_epoch, HotStateDiff};
+use crate::impls::beacon_state::{decode_full_state, encode_full_state, store_full_state_bytes};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
-use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
     AnchorInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
@@ -17,12 +19,12 @@ use crate::metadata::{
     SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
 };
 use crate::metrics;
+use crate::reconstruct::ReconstructionProgress;
 use crate::{
     get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
     PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde_derive::{Deserialize, Serialize};
@@ -64,6 +66,10 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
     /// LRU cache of replayed states.
     state_cache: Mutex<LruCache<Slot, BeaconState<E>>>,
+    /// The progress of the most recent backup of the hot database.
+    pub(crate) backup_status: RwLock<Option<BackupStatus>>,
+    /// The progress of the most recent historic state reconstruction.
+    pub(crate) reconstruction_progress: RwLock<Option<ReconstructionProgress>>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -91,6 +97,7 @@ pub enum HotColdDBError {
     MissingColdStateSummary(Hash256),
     MissingHotStateSummary(Hash256),
     MissingEpochBoundaryState(Hash256),
+    MissingStateDiffBase(Hash256),
     MissingSplitState(Hash256, Slot),
     MissingExecutionPayload(Hash256),
     MissingFullBlockExecutionPayloadPruned(Hash256, Slot),
@@ -132,6 +139,8 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
             hot_db: MemoryStore::open(),
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
             state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            backup_status: RwLock::new(None),
+            reconstruction_progress: RwLock::new(None),
             config,
             spec,
             log,
@@ -142,9 +151,11 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
+    /// Both databases use the backend selected by `config.backend`.
+    ///
     /// The `slots_per_restore_point` parameter must be a divisor of `SLOTS_PER_HISTORICAL_ROOT`.
     ///
     /// The `migrate_schema` function is passed in so that the parent `BeaconChain` can provide
@@ -162,10 +173,12 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         let mut db = HotColdDB {
             split: RwLock::new(Split::default()),
             anchor_info: RwLock::new(None),
-            cold_db: LevelDB::open(cold_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            cold_db: BeaconNodeBackend::open(config.backend, cold_path)?,
+            hot_db: BeaconNodeBackend::open(config.backend, hot_path)?,
             block_cache: Mutex::new(LruCache::new(config.block_cache_size)),
             state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
+            backup_status: RwLock::new(None),
+            reconstruction_progress: RwLock::new(None),
             config,
             spec,
             log,
@@ -240,30 +253,14 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
         Ok(db)
     }
+}
 
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db.iter_column_keys(DBColumn::BeaconStateTemporary)
     }
-}
 
-impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
     /// Store a block and update the LRU cache.
     pub fn put_block(
         &self,
@@ -418,6 +415,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         })
     }
 
+    /// Fetch the SSZ bytes of a blinded block from the store, without decoding them.
+    pub fn get_blinded_block_bytes(&self, block_root: &Hash256) -> Result<Option<Vec<u8>>, Error> {
+        self.hot_db
+            .get_bytes(DBColumn::BeaconBlock.into(), block_root.as_bytes())
+    }
+
     /// Fetch a block from the store, ignoring which fork variant it *should* be for.
     pub fn get_block_any_variant<Payload: AbstractExecPayload<E>>(
         &self,
@@ -642,10 +645,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db
             .key_delete(DBColumn::BeaconStateSummary.into(), state_root.as_bytes())?;
 
-        // Delete the full state if it lies on an epoch boundary.
+        // Delete the full state or its diff if it lies on an epoch boundary.
         if slot % E::slots_per_epoch() == 0 {
             self.hot_db
                 .key_delete(DBColumn::BeaconState.into(), state_root.as_bytes())?;
+            self.hot_db
+                .key_delete(DBColumn::BeaconStateDiff.into(), state_root.as_bytes())?;
         }
 
         Ok(())
@@ -801,6 +806,12 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                         let state_key =
                             get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
                         key_value_batch.push(KeyValueStoreOp::DeleteKey(state_key));
+
+                        let diff_key = get_key_for_col(
+                            DBColumn::BeaconStateDiff.into(),
+                            state_root.as_bytes(),
+                        );
+                        key_value_batch.push(KeyValueStoreOp::DeleteKey(diff_key));
                     }
                 }
 
@@ -857,23 +868,44 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Store a post-finalization state efficiently in the hot database.
     ///
-    /// On an epoch boundary, store a full state. On an intermediate slot, store
-    /// just a backpointer to the nearest epoch boundary.
+    /// On an epoch boundary, store a full state, or a diff against an earlier epoch boundary state
+    /// if `hdiff_exponents` are configured. On an intermediate slot, store just a backpointer to
+    /// the nearest epoch boundary.
     pub fn store_hot_state(
         &self,
         state_root: &Hash256,
         state: &BeaconState<E>,
         ops: &mut Vec<KeyValueStoreOp>,
     ) -> Result<(), Error> {
-        // On the epoch boundary, store the full state.
+        // On the epoch boundary, store the full state or its diff.
         if state.slot() % E::slots_per_epoch() == 0 {
-            trace!(
-                self.log,
-                "Storing full state on epoch boundary";
-                "slot" => state.slot().as_u64(),
-                "state_root" => format!("{:?}", state_root)
-            );
-            store_full_state(state_root, state, ops)?;
+            let bytes = encode_full_state(state);
+            match self.compute_hot_state_diff(state, &bytes)? {
+                Some(diff) => {
+                    trace!(
+                        self.log,
+                        "Storing state diff on epoch boundary";
+                        "slot" => state.slot().as_u64(),
+                        "state_root" => format!("{:?}", state_root),
+                        "base_slot" => diff.base_slot.as_u64(),
+                    );
+                    metrics::inc_counter(&metrics::BEACON_STATE_DIFF_WRITE_COUNT);
+                    metrics::inc_counter_by(
+                        &metrics::BEACON_STATE_DIFF_WRITE_BYTES,
+                        diff.diff.len() as u64,
+                    );
+                    ops.push(diff.as_kv_store_op(*state_root));
+                }
+                None => {
+                    trace!(
+                        self.log,
+                        "Storing full state on epoch boundary";
+                        "slot" => state.slot().as_u64(),
+                        "state_root" => format!("{:?}", state_root)
+                    );
+                    store_full_state_bytes(state_root, bytes, ops);
+                }
+            }
         }
 
         // Store a summary of the state.
@@ -886,6 +918,106 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(())
     }
 
+    /// Compute the diff of the epoch boundary `state` against its base state in the hierarchy.
+    ///
+    /// Returns `None` if the state should be stored in full, either because it is a snapshot in
+    /// the hierarchy or because its base state is not available in the hot database.
+    fn compute_hot_state_diff(
+        &self,
+        state: &BeaconState<E>,
+        bytes: &[u8],
+    ) -> Result<Option<HotStateDiff>, Error> {
+        let base_epoch = match diff_base_epoch(&self.config.hdiff_exponents, state.current_epoch())
+        {
+            Some(base_epoch) => base_epoch,
+            None => return Ok(None),
+        };
+        let base_slot = base_epoch.start_slot(E::slots_per_epoch());
+        if base_slot < self.get_split_slot() {
+            return Ok(None);
+        }
+        // The base state lies outside of the `state_roots` of states more than
+        // `SLOTS_PER_HISTORICAL_ROOT` slots after it.
+        let base_state_root = match state.get_state_root(base_slot) {
+            Ok(base_state_root) => *base_state_root,
+            Err(_) => return Ok(None),
+        };
+        let base_bytes = match self.load_hot_state_bytes(&base_state_root)? {
+            Some(base_bytes) => base_bytes,
+            None => return Ok(None),
+        };
+
+        let _timer = metrics::start_timer(&metrics::BEACON_STATE_DIFF_COMPUTE_TIMES);
+        let diff =
+            HotStateDiff::compute(state.slot(), base_state_root, base_slot, &base_bytes, bytes)?;
+        Ok(Some(diff))
+    }
+
+    /// Load the stored bytes of a hot epoch boundary state, applying diffs if it is not stored in
+    /// full.
+    pub fn load_hot_state_bytes(&self, state_root: &Hash256) -> Result<Option<Vec<u8>>, Error> {
+        let mut diffs = vec![];
+        let mut reconstruction_timer = None;
+        let mut root = *state_root;
+        let base_bytes = loop {
+            if let Some(bytes) = self
+                .hot_db
+                .get_bytes(DBColumn::BeaconState.into(), root.as_bytes())?
+            {
+                break bytes;
+            }
+            match self.hot_db.get::<HotStateDiff>(&root)? {
+                Some(diff) => {
+                    reconstruction_timer.get_or_insert_with(|| {
+                        metrics::start_timer(&metrics::BEACON_STATE_DIFF_RECONSTRUCTION_TIMES)
+                    });
+                    root = diff.base_state_root;
+                    diffs.push(diff);
+                }
+                None if diffs.is_empty() => return Ok(None),
+                None => return Err(HotColdDBError::MissingStateDiffBase(root).into()),
+            }
+        };
+
+        let bytes = diffs.iter().rev().try_fold(base_bytes, |bytes, diff| {
+            let _timer = metrics::start_timer(&metrics::BEACON_STATE_DIFF_APPLY_TIMES);
+            diff.apply(&bytes)
+        })?;
+        drop(reconstruction_timer);
+        Ok(Some(bytes))
+    }
+
+    /// Load a hot epoch boundary state, which may be stored in full or as a diff.
+    fn load_hot_full_state(&self, state_root: &Hash256) -> Result<Option<BeaconState<E>>, Error> {
+        let _timer = metrics::start_timer(&metrics::BEACON_STATE_READ_TIMES);
+        self.load_hot_state_bytes(state_root)?
+            .map(|bytes| decode_full_state(&bytes, &self.spec))
+            .transpose()
+    }
+
+    /// Store in full every hot state which is stored as a diff against a state prior to
+    /// `new_split_slot`, and which is not itself prior to `new_split_slot`.
+    ///
+    /// Must be called before the hot states prior to `new_split_slot` are deleted.
+    fn store_diffs_spanning_split_in_full(&self, new_split_slot: Slot) -> Result<(), Error> {
+        let mut ops = vec![];
+        for result in self.hot_db.iter_column(DBColumn::BeaconStateDiff) {
+            let (state_root, bytes) = result?;
+            let diff = HotStateDiff::from_store_bytes(&bytes)?;
+            if diff.slot < new_split_slot || diff.base_slot >= new_split_slot {
+                continue;
+            }
+            if let Some(bytes) = self.load_hot_state_bytes(&state_root)? {
+                store_full_state_bytes(&state_root, bytes, &mut ops);
+                ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                    DBColumn::BeaconStateDiff.into(),
+                    state_root.as_bytes(),
+                )));
+            }
+        }
+        self.hot_db.do_atomically(ops)
+    }
+
     /// Load a post-finalization state from the hot database.
     ///
     /// Will replay blocks from the nearest epoch boundary.
@@ -908,10 +1040,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             epoch_boundary_state_root,
         }) = self.load_hot_state_summary(state_root)?
         {
-            let boundary_state =
-                get_full_state(&self.hot_db, &epoch_boundary_state_root, &self.spec)?.ok_or(
-                    HotColdDBError::MissingEpochBoundaryState(epoch_boundary_state_root),
-                )?;
+            let boundary_state = self
+                .load_hot_full_state(&epoch_boundary_state_root)?
+                .ok_or(HotColdDBError::MissingEpochBoundaryState(
+                    epoch_boundary_state_root,
+                ))?;
 
             // Optimization to avoid even *thinking* about replaying blocks if we're already
             // on an epoch boundary.
@@ -1813,7 +1946,8 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
         cold_db_ops.push(op);
 
         if slot % store.config.slots_per_restore_point == 0 {
-            let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
+            let state: BeaconState<E> = store
+                .load_hot_full_state(&state_root)?
                 .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
 
             store.store_cold_state(&state_root, &state, &mut cold_db_ops)?;
@@ -1830,6 +1964,9 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     block_root_writer.write(&mut cold_db_ops)?;
     store.cold_db.do_atomically(cold_db_ops)?;
 
+    // 2. Store in full the hot states which will outlive the states they are diffed against.
+    store.store_diffs_spanning_split_in_full(finalized_state.slot())?;
+
     // Warning: Critical section.  We have to take care not to put any of the two databases in an
     //          inconsistent state if the OS process dies at any point during the freezeing
     //          procedure.
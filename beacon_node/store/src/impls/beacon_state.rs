@@ -4,43 +4,37 @@ use ssz_derive::Encode;
 use std::convert::TryInto;
 use types::beacon_state::{CloneConfig, CommitteeCache, CACHED_EPOCHS};
 
-pub fn store_full_state<E: EthSpec>(
+/// Encode a state as it is stored in the `BeaconState` column.
+pub fn encode_full_state<E: EthSpec>(state: &BeaconState<E>) -> Vec<u8> {
+    let _overhead_timer = metrics::start_timer(&metrics::BEACON_STATE_WRITE_OVERHEAD_TIMES);
+    StorageContainer::new(state).as_ssz_bytes()
+}
+
+/// Store the bytes of a state produced by `encode_full_state`.
+pub fn store_full_state_bytes(
     state_root: &Hash256,
-    state: &BeaconState<E>,
+    bytes: Vec<u8>,
     ops: &mut Vec<KeyValueStoreOp>,
-) -> Result<(), Error> {
-    let bytes = {
-        let _overhead_timer = metrics::start_timer(&metrics::BEACON_STATE_WRITE_OVERHEAD_TIMES);
-        StorageContainer::new(state).as_ssz_bytes()
-    };
+) {
     metrics::inc_counter_by(&metrics::BEACON_STATE_WRITE_BYTES, bytes.len() as u64);
     metrics::inc_counter(&metrics::BEACON_STATE_WRITE_COUNT);
     let key = get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
     ops.push(KeyValueStoreOp::PutKeyValue(key, bytes));
-    Ok(())
 }
 
-pub fn get_full_state<KV: KeyValueStore<E>, E: EthSpec>(
-    db: &KV,
-    state_root: &Hash256,
+/// Decode a state from the bytes produced by `encode_full_state`.
+pub fn decode_full_state<E: EthSpec>(
+    bytes: &[u8],
     spec: &ChainSpec,
-) -> Result<Option<BeaconState<E>>, Error> {
-    let total_timer = metrics::start_timer(&metrics::BEACON_STATE_READ_TIMES);
+) -> Result<BeaconState<E>, Error> {
+    let overhead_timer = metrics::start_timer(&metrics::BEACON_STATE_READ_OVERHEAD_TIMES);
+    let container = StorageContainer::from_ssz_bytes(bytes, spec)?;
 
-    match db.get_bytes(DBColumn::BeaconState.into(), state_root.as_bytes())? {
-        Some(bytes) => {
-            let overhead_timer = metrics::start_timer(&metrics::BEACON_STATE_READ_OVERHEAD_TIMES);
-            let container = StorageContainer::from_ssz_bytes(&bytes, spec)?;
+    metrics::stop_timer(overhead_timer);
+    metrics::inc_counter(&metrics::BEACON_STATE_READ_COUNT);
+    metrics::inc_counter_by(&metrics::BEACON_STATE_READ_BYTES, bytes.len() as u64);
 
-            metrics::stop_timer(overhead_timer);
-            metrics::stop_timer(total_timer);
-            metrics::inc_counter(&metrics::BEACON_STATE_READ_COUNT);
-            metrics::inc_counter_by(&metrics::BEACON_STATE_READ_BYTES, bytes.len() as u64);
-
-            Ok(Some(container.try_into()?))
-        }
-        None => Ok(None),
-    }
+    container.try_into()
 }
 
 /// A container for storing `BeaconState` components.
@@ -5,6 +5,7 @@ use db_key::Key;
 use leveldb::compaction::Compaction;
 use leveldb::database::batch::{Batch, Writebatch};
 use leveldb::database::kv::KV;
+use leveldb::database::snapshots::Snapshots;
 use leveldb::database::Database;
 use leveldb::error::Error as LevelDBError;
 use leveldb::iterator::{Iterable, KeyIterator, LevelDBIterator};
@@ -13,6 +14,9 @@ use parking_lot::{Mutex, MutexGuard};
 use std::marker::PhantomData;
 use std::path::Path;
 
+/// The number of keys written to the target database at once when exporting a snapshot.
+const SNAPSHOT_EXPORT_BATCH_SIZE: usize = 1024;
+
 /// A wrapped leveldb database.
 pub struct LevelDB<E: EthSpec> {
     db: Database<BytesKey>,
@@ -170,6 +174,7 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
         for (start_key, end_key) in [
             endpoints(DBColumn::BeaconStateTemporary),
             endpoints(DBColumn::BeaconState),
+            endpoints(DBColumn::BeaconStateDiff),
         ] {
             self.db.compact(&start_key, &end_key);
         }
@@ -219,6 +224,42 @@ impl<E: EthSpec> KeyValueStore<E> for LevelDB<E> {
                 }),
         )
     }
+
+    /// Copy a snapshot of the database into a new database at `path`, which must not exist.
+    fn export_snapshot(
+        &self,
+        path: &Path,
+        on_batch: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), Error> {
+        let mut options = Options::new();
+        options.create_if_missing = true;
+        options.error_if_exists = true;
+        let target = Database::<BytesKey>::open(path, options)?;
+
+        let snapshot = self.db.snapshot();
+        let mut batch = Writebatch::new();
+        let mut batch_len = 0;
+        let mut keys_copied = 0;
+        let mut bytes_copied = 0;
+
+        for (key, value) in snapshot.iter(self.read_options()) {
+            keys_copied += 1;
+            bytes_copied += (key.key.len() + value.len()) as u64;
+            batch.put(key, &value);
+            batch_len += 1;
+
+            if batch_len == SNAPSHOT_EXPORT_BATCH_SIZE {
+                target.write(self.write_options(), &batch)?;
+                batch = Writebatch::new();
+                batch_len = 0;
+                on_batch(keys_copied, bytes_copied);
+            }
+        }
+        target.write(self.write_options_sync(), &batch)?;
+        on_batch(keys_copied, bytes_copied);
+
+        Ok(())
+    }
 }
 
 impl<E: EthSpec> ItemStore<E> for LevelDB<E> {}
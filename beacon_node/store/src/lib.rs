@@ -10,6 +10,8 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod backup;
+mod beacon_node_backend;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
@@ -17,6 +19,7 @@ pub mod config;
 pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
+pub mod hdiff;
 pub mod hot_cold_store;
 mod impls;
 mod leveldb_store;
@@ -28,6 +31,7 @@ pub mod reconstruct;
 
 pub mod iter;
 
+pub use self::beacon_node_backend::BeaconNodeBackend;
 pub use self::chunk_writer::ChunkWriter;
 pub use self::config::StoreConfig;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
@@ -39,6 +43,7 @@ pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
 pub use metadata::AnchorInfo;
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
+use std::path::Path;
 use std::sync::Arc;
 use strum::{EnumString, IntoStaticStr};
 pub use types::*;
@@ -90,6 +95,19 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
         // Default impl for non LevelDB databases
         Box::new(std::iter::empty())
     }
+
+    /// Copy a consistent snapshot of the whole database into a new database at `path`.
+    ///
+    /// The copy is written in batches, and `on_batch` is called after each one with the total
+    /// number of keys and bytes copied so far.
+    fn export_snapshot(
+        &self,
+        _path: &Path,
+        _on_batch: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), Error> {
+        // Default impl for non LevelDB databases
+        Err(Error::SnapshotUnsupported)
+    }
 }
 
 pub fn get_key_for_col(column: &str, key: &[u8]) -> Vec<u8> {
@@ -178,6 +196,9 @@ pub enum DBColumn {
     /// For the mapping from state roots to their slots or summaries.
     #[strum(serialize = "bss")]
     BeaconStateSummary,
+    /// For epoch boundary states in the hot database which are stored as diffs.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
     /// For the list of temporary states stored during block import,
     /// and then made non-temporary by the deletion of their state root from this column.
     #[strum(serialize = "bst")]
@@ -209,6 +230,12 @@ pub enum DBColumn {
     BeaconRandaoMixes,
     #[strum(serialize = "dht")]
     DhtEnrs,
+    /// Peers marked as trusted at runtime.
+    #[strum(serialize = "tpr")]
+    TrustedPeers,
+    /// Scores of penalised peers, persisted across restarts.
+    #[strum(serialize = "psc")]
+    PeerScores,
     /// For Optimistically Imported Merge Transition Blocks
     #[strum(serialize = "otb")]
     OptimisticTransitionBlock,
@@ -1,5 +1,5 @@
 use super::{Error, ItemStore, KeyValueStore, KeyValueStoreOp};
-use crate::{ColumnIter, DBColumn};
+use crate::{ColumnIter, ColumnKeyIter, DBColumn};
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
@@ -114,6 +114,13 @@ impl<E: EthSpec> KeyValueStore<E> for MemoryStore<E> {
         }
     }
 
+    fn iter_column_keys(&self, column: DBColumn) -> ColumnKeyIter {
+        Box::new(
+            self.iter_column(column)
+                .map(|result| result.map(|(key, _)| key)),
+        )
+    }
+
     fn begin_rw_transaction(&self) -> MutexGuard<()> {
         self.transaction_mutex.lock()
     }
@@ -5,6 +5,8 @@ use ssz_derive::{Decode, Encode};
 use types::{Checkpoint, Hash256, Slot};
 
 pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(17);
+/// The oldest schema version that the database can be migrated from, or downgraded to.
+pub const MIN_SUPPORTED_SCHEMA_VERSION: SchemaVersion = SchemaVersion(11);
 
 // All the keys that get stored under the `BeaconMeta` column.
 //
@@ -90,6 +90,26 @@ lazy_static! {
         "store_beacon_state_write_bytes_total",
         "Total number of beacon state bytes written to the DB"
     );
+    pub static ref BEACON_STATE_DIFF_WRITE_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_write_total",
+        "Total number of hot beacon states written to the DB as diffs"
+    );
+    pub static ref BEACON_STATE_DIFF_WRITE_BYTES: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_write_bytes_total",
+        "Total number of compressed beacon state diff bytes written to the DB"
+    );
+    pub static ref BEACON_STATE_DIFF_COMPUTE_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_state_diff_compute_seconds",
+        "Time taken to compute the diff of a hot beacon state against its base state"
+    );
+    pub static ref BEACON_STATE_DIFF_APPLY_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_state_diff_apply_seconds",
+        "Time taken to apply a single diff to the bytes of its base state"
+    );
+    pub static ref BEACON_STATE_DIFF_RECONSTRUCTION_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_state_diff_reconstruction_seconds",
+        "Total time taken to reconstruct the bytes of a hot beacon state stored as a diff"
+    );
     /*
      * Beacon Block
      */
@@ -1,14 +1,52 @@
 //! Implementation of historic state reconstruction (given complete block history).
 use crate::hot_cold_store::{HotColdDB, HotColdDBError};
+use crate::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use crate::{Error, ItemStore};
 use itertools::{process_results, Itertools};
+use serde_derive::{Deserialize, Serialize};
 use slog::info;
 use state_processing::{
     per_block_processing, per_slot_processing, BlockSignatureStrategy, ConsensusContext,
     StateProcessingStrategy, VerifyBlockRoot,
 };
 use std::sync::Arc;
-use types::{EthSpec, Hash256};
+use std::time::Instant;
+use types::{EthSpec, Hash256, Slot};
+
+/// The progress of historic state reconstruction.
+///
+/// Progress is derived from the anchor, which is persisted as reconstruction proceeds, so it
+/// survives restarts. The rate of reconstruction is only known since it last started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReconstructionStatus {
+    /// Whether reconstruction is running at the moment.
+    pub in_progress: bool,
+    /// Whether every historic state is available, i.e. there is nothing to reconstruct.
+    pub complete: bool,
+    /// The states up to and including this slot are available.
+    pub state_lower_limit: Option<Slot>,
+    /// The states from this slot onwards are available. `None` if historic states are not being
+    /// retained.
+    pub state_upper_limit: Option<Slot>,
+    /// The number of slots reconstructed since reconstruction last started.
+    pub slots_done: u64,
+    /// The number of slots left to reconstruct.
+    pub slots_remaining: Option<u64>,
+    /// Estimated time until reconstruction completes, in seconds.
+    pub eta_seconds: Option<u64>,
+    /// The reason the most recent reconstruction failed, if it did.
+    pub error: Option<String>,
+}
+
+/// The in-memory record of the most recent reconstruction.
+#[derive(Debug, Clone)]
+pub(crate) struct ReconstructionProgress {
+    start_slot: Slot,
+    end_slot: Slot,
+    started_at: Instant,
+    in_progress: bool,
+    error: Option<String>,
+}
 
 impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
 where
@@ -16,7 +54,89 @@ where
     Hot: ItemStore<E>,
     Cold: ItemStore<E>,
 {
+    /// Reconstruct the historic states between the anchor's state limits, resuming from the
+    /// lower limit of any previous reconstruction. Its progress is available from
+    /// `Self::reconstruction_status`.
     pub fn reconstruct_historic_states(self: &Arc<Self>) -> Result<(), Error> {
+        let (start_slot, end_slot) = match self.get_anchor_info() {
+            Some(anchor) => (anchor.state_lower_limit, anchor.state_upper_limit),
+            // Nothing to do, history is complete.
+            None => return Ok(()),
+        };
+        *self.reconstruction_progress.write() = Some(ReconstructionProgress {
+            start_slot,
+            end_slot,
+            started_at: Instant::now(),
+            in_progress: true,
+            error: None,
+        });
+
+        let result = self.reconstruct_historic_states_from_anchor();
+
+        if let Some(progress) = self.reconstruction_progress.write().as_mut() {
+            progress.in_progress = false;
+            progress.error = result.as_ref().err().map(|e| format!("{:?}", e));
+        }
+        result
+    }
+
+    /// Returns the progress of historic state reconstruction.
+    pub fn reconstruction_status(&self) -> ReconstructionStatus {
+        let anchor = self.get_anchor_info();
+        let progress = self.reconstruction_progress.read().clone();
+
+        let state_lower_limit = anchor.as_ref().map(|anchor| anchor.state_lower_limit);
+        let state_upper_limit = anchor
+            .as_ref()
+            .map(|anchor| anchor.state_upper_limit)
+            .filter(|&slot| slot != STATE_UPPER_LIMIT_NO_RETAIN);
+        let complete = anchor.as_ref().map_or(true, |anchor| {
+            anchor.state_lower_limit >= anchor.state_upper_limit
+        });
+        let slots_remaining = if complete {
+            Some(0)
+        } else {
+            state_upper_limit
+                .zip(state_lower_limit)
+                .map(|(upper, lower)| upper.as_u64().saturating_sub(lower.as_u64()))
+        };
+
+        let in_progress = progress
+            .as_ref()
+            .map_or(false, |progress| progress.in_progress);
+        let slots_done = progress.as_ref().map_or(0, |progress| {
+            // Once complete the anchor is removed, so the lower limit is no longer known.
+            let reached = if complete {
+                progress.end_slot
+            } else {
+                state_lower_limit.unwrap_or(progress.start_slot)
+            };
+            reached
+                .as_u64()
+                .saturating_sub(progress.start_slot.as_u64())
+        });
+        let eta_seconds = progress
+            .as_ref()
+            .filter(|progress| progress.in_progress && slots_done > 0)
+            .zip(slots_remaining)
+            .map(|(progress, remaining)| {
+                let elapsed = progress.started_at.elapsed().as_secs_f64();
+                (elapsed * remaining as f64 / slots_done as f64) as u64
+            });
+
+        ReconstructionStatus {
+            in_progress,
+            complete,
+            state_lower_limit,
+            state_upper_limit,
+            slots_done,
+            slots_remaining,
+            eta_seconds,
+            error: progress.and_then(|progress| progress.error),
+        }
+    }
+
+    fn reconstruct_historic_states_from_anchor(self: &Arc<Self>) -> Result<(), Error> {
         let mut anchor = if let Some(anchor) = self.get_anchor_info() {
             anchor
         } else {
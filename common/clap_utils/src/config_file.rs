@@ -0,0 +1,489 @@
+//! Support for supplying CLI flags via a TOML config file.
+//!
+//! Each key of the file is the long name of a flag. Keys at the top level apply to all
+//! subcommands which support config files, whilst keys in a table named after a subcommand (e.g.
+//! `[beacon_node]`) apply only to that subcommand and take precedence over the top level:
+//!
+//! ```toml
+//! network = "mainnet"
+//! debug-level = "info"
+//!
+//! [beacon_node]
+//! http = true
+//! execution-endpoint = "http://localhost:8551"
+//!
+//! [validator_client]
+//! beacon-nodes = ["http://localhost:5052", "http://backup:5052"]
+//! ```
+//!
+//! The flags are converted to arguments and passed to `clap` alongside those from the command
+//! line, so that they are validated exactly as if they had been typed. Flags given on the command
+//! line take precedence over the file.
+use clap::{App, ArgMatches};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use toml::value::{Table, Value};
+
+pub const CONFIG_FILE_FLAG: &str = "config-file";
+
+/// Flags which may be given more than once, to which each value of a list is passed separately.
+/// All other lists are passed as comma-separated values.
+const REPEATED_FLAGS: &[&str] = &["listen-address", "enr-address"];
+
+/// A subcommand which accepts a config file, along with the aliases by which it may be invoked.
+pub struct ConfigFileSubcommand {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+}
+
+impl ConfigFileSubcommand {
+    fn matches(&self, arg: &str) -> bool {
+        self.name == arg || self.aliases.contains(&arg)
+    }
+}
+
+/// The parsed contents of a config file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigFile {
+    global: Table,
+    subcommands: Table,
+}
+
+impl ConfigFile {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("Unable to read {:?}: {}", path, e))?;
+        contents
+            .parse()
+            .map_err(|e| format!("Invalid config file {:?}: {}", path, e))
+    }
+
+    /// Returns an error if the file contains a table which isn't one of `subcommands`.
+    pub fn check_subcommands(&self, subcommands: &[ConfigFileSubcommand]) -> Result<(), String> {
+        for name in self.subcommands.keys() {
+            if !subcommands.iter().any(|subcommand| subcommand.name == name) {
+                return Err(format!(
+                    "Unknown table [{}], expected one of: {}",
+                    name,
+                    subcommands
+                        .iter()
+                        .map(|subcommand| subcommand.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the flags which apply to `subcommand`, with the values from its table replacing
+    /// those from the top level.
+    pub fn flags_for(&self, subcommand: &str) -> Table {
+        let mut flags = self.global.clone();
+        if let Some(Value::Table(table)) = self.subcommands.get(subcommand) {
+            flags.extend(table.clone());
+        }
+        flags
+    }
+
+    /// Returns the flags for `subcommand` as CLI arguments, excluding any flags named in
+    /// `exclude`.
+    pub fn args_for(&self, subcommand: &str, exclude: &[String]) -> Vec<OsString> {
+        let mut args = vec![];
+        for (name, value) in self.flags_for(subcommand) {
+            if exclude.contains(&name) {
+                continue;
+            }
+            match value {
+                Value::Boolean(true) => args.push(format!("--{}", name).into()),
+                Value::Boolean(false) => {}
+                Value::Array(values) if REPEATED_FLAGS.contains(&name.as_str()) => {
+                    for value in values {
+                        args.push(format!("--{}={}", name, scalar_to_string(&value)).into());
+                    }
+                }
+                Value::Array(values) => {
+                    let values = values.iter().map(scalar_to_string).collect::<Vec<_>>();
+                    args.push(format!("--{}={}", name, values.join(",")).into());
+                }
+                value => args.push(format!("--{}={}", name, scalar_to_string(&value)).into()),
+            }
+        }
+        args
+    }
+}
+
+impl FromStr for ConfigFile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = s.parse::<Value>().map_err(|e| e.to_string())?;
+        let table = match table {
+            Value::Table(table) => table,
+            _ => return Err("expected a table".to_string()),
+        };
+
+        let mut global = Table::new();
+        let mut subcommands = Table::new();
+        for (name, value) in table {
+            match value {
+                Value::Table(table) => {
+                    for (flag, value) in &table {
+                        check_value(flag, value)
+                            .map_err(|e| format!("Invalid value in [{}]: {}", name, e))?;
+                    }
+                    subcommands.insert(name, Value::Table(table));
+                }
+                value => {
+                    check_value(&name, &value)?;
+                    global.insert(name, value);
+                }
+            }
+        }
+        Ok(Self {
+            global,
+            subcommands,
+        })
+    }
+}
+
+/// Checks that `value` can be converted to a CLI argument.
+fn check_value(flag: &str, value: &Value) -> Result<(), String> {
+    if flag == CONFIG_FILE_FLAG {
+        return Err(format!("{} cannot be set from a config file", flag));
+    }
+    let is_scalar = |value: &Value| {
+        matches!(
+            value,
+            Value::String(_) | Value::Integer(_) | Value::Float(_)
+        )
+    };
+    match value {
+        Value::Boolean(_) => Ok(()),
+        Value::Array(values) if values.iter().all(is_scalar) => Ok(()),
+        value if is_scalar(value) => Ok(()),
+        _ => Err(format!(
+            "{} must be a boolean, string, number or list of strings and numbers",
+            flag
+        )),
+    }
+}
+
+fn scalar_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Returns the long names of the flags in `args`, which are given as `--name` or `--name=value`.
+fn long_flag_names(args: &[OsString]) -> Vec<String> {
+    args.iter()
+        .filter_map(|arg| arg.to_str()?.strip_prefix("--"))
+        .map(|arg| arg.split('=').next().unwrap_or(arg).to_string())
+        .collect()
+}
+
+/// Returns the path given to `--config-file`, if any.
+fn config_file_path(args: &[OsString]) -> Option<PathBuf> {
+    let flag = format!("--{}", CONFIG_FILE_FLAG);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let arg_str = arg.to_str();
+        if arg_str == Some(flag.as_str()) {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg_str.and_then(|arg| arg.strip_prefix(&format!("{}=", flag))) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    None
+}
+
+/// Inserts the flags from the config file given by `--config-file` (if any) into the process
+/// arguments `args`, directly after the name of the subcommand.
+///
+/// Flags which are present in `args` in their long form are not inserted, so that they take
+/// precedence over the file. Flags given by their short form or by an alias must not also be set
+/// in the file, or `clap` will reject them as duplicates.
+pub fn merge_config_file_args(
+    args: Vec<OsString>,
+    subcommands: &[ConfigFileSubcommand],
+) -> Result<Vec<OsString>, String> {
+    let path = match config_file_path(&args) {
+        Some(path) => path,
+        None => return Ok(args),
+    };
+    let config_file = ConfigFile::from_file(&path)?;
+    config_file.check_subcommands(subcommands)?;
+
+    let (position, subcommand) = args
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find_map(|(i, arg)| {
+            let arg = arg.to_str()?;
+            let subcommand = subcommands
+                .iter()
+                .find(|subcommand| subcommand.matches(arg))?;
+            Some((i, subcommand))
+        })
+        .ok_or_else(|| {
+            format!(
+                "--{} is only supported by the {} subcommands",
+                CONFIG_FILE_FLAG,
+                subcommands
+                    .iter()
+                    .map(|subcommand| subcommand.name)
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            )
+        })?;
+
+    let file_args = config_file.args_for(subcommand.name, &long_flag_names(&args));
+    let mut merged = args[..=position].to_vec();
+    merged.extend(file_args);
+    merged.extend_from_slice(&args[position + 1..]);
+    Ok(merged)
+}
+
+/// Checks that the config file at `path` is accepted by each of the `subcommands` of `app`, and
+/// returns the effective config of each of them as JSON.
+///
+/// The flags are first matched by `app`, then `build_config` is called with the matches to build
+/// the config of the subcommand exactly as it would be built on startup, so that the values of
+/// the flags are checked as well as their names.
+///
+/// Only the subcommands with a table in the file are checked, unless there are no tables.
+pub fn validate_config_file<'a, 'b>(
+    path: &Path,
+    subcommands: &[ConfigFileSubcommand],
+    app: impl Fn() -> App<'a, 'b>,
+    build_config: impl Fn(&ArgMatches) -> Result<serde_json::Value, String>,
+) -> Result<String, String> {
+    let config_file = ConfigFile::from_file(path)?;
+    config_file.check_subcommands(subcommands)?;
+
+    let mut effective = serde_json::Map::new();
+    for subcommand in subcommands {
+        if !config_file.subcommands.is_empty()
+            && !config_file.subcommands.contains_key(subcommand.name)
+        {
+            continue;
+        }
+
+        let mut args = vec![OsString::from(app().get_name()), subcommand.name.into()];
+        args.extend(config_file.args_for(subcommand.name, &[]));
+        let matches = app()
+            .get_matches_from_safe(args)
+            .map_err(|e| format!("Invalid flags for {}: {}", subcommand.name, e.message))?;
+        let config = build_config(&matches)
+            .map_err(|e| format!("Invalid config for {}: {}", subcommand.name, e))?;
+
+        effective.insert(subcommand.name.to_string(), config);
+    }
+
+    serde_json::to_string_pretty(&effective).map_err(|e| format!("Unable to print config: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, SubCommand};
+
+    const SUBCOMMANDS: &[ConfigFileSubcommand] = &[
+        ConfigFileSubcommand {
+            name: "beacon_node",
+            aliases: &["bn"],
+        },
+        ConfigFileSubcommand {
+            name: "validator_client",
+            aliases: &["vc"],
+        },
+    ];
+
+    const CONFIG: &str = r#"
+        debug-level = "debug"
+        network = "mainnet"
+
+        [beacon_node]
+        debug-level = "info"
+        http = true
+        staking = false
+        target-peers = 80
+        listen-address = ["0.0.0.0", "::"]
+
+        [validator_client]
+        beacon-nodes = ["http://a:5052", "http://b:5052"]
+    "#;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn flags_for_subcommands() {
+        let config_file = ConfigFile::from_str(CONFIG).unwrap();
+        assert_eq!(
+            config_file.args_for("beacon_node", &["target-peers".to_string()]),
+            args(&[
+                "--debug-level=info",
+                "--http",
+                "--listen-address=0.0.0.0",
+                "--listen-address=::",
+                "--network=mainnet",
+            ])
+        );
+        assert_eq!(
+            config_file.args_for("validator_client", &[]),
+            args(&[
+                "--beacon-nodes=http://a:5052,http://b:5052",
+                "--debug-level=debug",
+                "--network=mainnet",
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_config_files() {
+        assert!(ConfigFile::from_str("datadir = 2021-01-01").is_err());
+        assert!(ConfigFile::from_str("[beacon_node]\nbeacon-nodes = [[\"a\"]]").is_err());
+        assert!(ConfigFile::from_str("config-file = \"other.toml\"").is_err());
+
+        let config_file = ConfigFile::from_str("[boot_node]\nport = 9000").unwrap();
+        assert!(config_file.check_subcommands(SUBCOMMANDS).is_err());
+    }
+
+    #[test]
+    fn merge_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, CONFIG).unwrap();
+        let path = path.to_str().unwrap();
+
+        // Arguments are unchanged without a config file.
+        let cli_args = args(&["lighthouse", "bn", "--http"]);
+        assert_eq!(
+            merge_config_file_args(cli_args.clone(), SUBCOMMANDS).unwrap(),
+            cli_args
+        );
+
+        let merged = merge_config_file_args(
+            args(&[
+                "lighthouse",
+                "--network=holesky",
+                "vc",
+                "--config-file",
+                path,
+                "--debug-level",
+                "warn",
+            ]),
+            SUBCOMMANDS,
+        )
+        .unwrap();
+        assert_eq!(
+            merged,
+            args(&[
+                "lighthouse",
+                "--network=holesky",
+                "vc",
+                "--beacon-nodes=http://a:5052,http://b:5052",
+                "--config-file",
+                path,
+                "--debug-level",
+                "warn",
+            ])
+        );
+
+        let config_file_arg = format!("--config-file={}", path);
+        assert!(merge_config_file_args(
+            args(&["lighthouse", "account_manager", &config_file_arg]),
+            SUBCOMMANDS
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn validate() {
+        let app = || {
+            App::new("lighthouse")
+                .arg(
+                    Arg::with_name("debug-level")
+                        .long("debug-level")
+                        .takes_value(true)
+                        .possible_values(&["info", "debug"])
+                        .global(true),
+                )
+                .arg(
+                    Arg::with_name("network")
+                        .long("network")
+                        .takes_value(true)
+                        .global(true),
+                )
+                .subcommand(
+                    SubCommand::with_name("beacon_node")
+                        .arg(Arg::with_name("http").long("http"))
+                        .arg(Arg::with_name("staking").long("staking"))
+                        .arg(
+                            Arg::with_name("target-peers")
+                                .long("target-peers")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("listen-address")
+                                .long("listen-address")
+                                .multiple(true)
+                                .takes_value(true),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("validator_client").arg(
+                        Arg::with_name("beacon-nodes")
+                            .long("beacon-nodes")
+                            .takes_value(true),
+                    ),
+                )
+        };
+
+        // Builds a config from the matches in the same way as a subcommand, including parsing
+        // the values of its flags.
+        let build_config = |matches: &ArgMatches| {
+            let debug_level = matches.value_of("debug-level").unwrap_or("info");
+            match matches.subcommand() {
+                ("beacon_node", Some(matches)) => {
+                    let target_peers = crate::parse_optional::<usize>(matches, "target-peers")?;
+                    Ok(serde_json::json!({
+                        "debug_level": debug_level,
+                        "target_peers": target_peers,
+                    }))
+                }
+                _ => Ok(serde_json::json!({ "debug_level": debug_level })),
+            }
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, CONFIG).unwrap();
+        let effective = validate_config_file(&path, SUBCOMMANDS, app, build_config).unwrap();
+        let effective: serde_json::Value = serde_json::from_str(&effective).unwrap();
+        assert_eq!(
+            effective,
+            serde_json::json!({
+                "beacon_node": { "debug_level": "info", "target_peers": 80 },
+                "validator_client": { "debug_level": "debug" },
+            })
+        );
+
+        for invalid in [
+            "[beacon_node]\nunknown-flag = true",
+            "[beacon_node]\ntarget-peers = true",
+            "[beacon_node]\ntarget-peers = \"many\"",
+            "debug-level = \"loud\"",
+        ] {
+            fs::write(&path, invalid).unwrap();
+            assert!(validate_config_file(&path, SUBCOMMANDS, app, build_config).is_err());
+        }
+    }
+}
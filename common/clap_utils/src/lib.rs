@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use types::{ChainSpec, Config, EthSpec};
 
+pub mod config_file;
 pub mod flags;
 
 pub const BAD_TESTNET_DIR_MESSAGE: &str = "The hard-coded testnet directory was invalid. \
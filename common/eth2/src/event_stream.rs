@@ -0,0 +1,185 @@
+//! A server-sent event stream which survives the loss of the underlying connection.
+
+use crate::types::{EthSpec, EventKind, EventTopic, Slot};
+use crate::{ok_or_error, BeaconNodeHttpClient, Error};
+use futures::Stream;
+use futures_util::StreamExt;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::time::Duration;
+
+type EventStream<T> = Pin<Box<dyn Stream<Item = Result<EventKind<T>, Error>> + Send>>;
+
+/// A message produced by `BeaconNodeHttpClient::get_events_resubscribing`.
+#[derive(Debug)]
+pub enum ResubscribingEvent<T: EthSpec> {
+    /// An event received from the beacon node.
+    Event(EventKind<T>),
+    /// A head event for `slot` was received, but the previous head event was for
+    /// `previous_slot`, more than one slot earlier.
+    ///
+    /// A gap is expected when slots are skipped, but it may also mean that head events were missed
+    /// whilst the stream was disconnected.
+    HeadSlotGap { previous_slot: Slot, slot: Slot },
+    /// The stream was re-established after a disconnection. Events may have been missed.
+    Resubscribed,
+    /// The stream was lost, with the `error` that caused it if there was one. A resubscription
+    /// will be attempted after the reconnect delay.
+    Disconnected(Option<Error>),
+    /// An event could not be decoded. The stream remains connected.
+    InvalidEvent(Error),
+}
+
+struct ResubscribingState<T: EthSpec> {
+    client: BeaconNodeHttpClient,
+    topics: Vec<EventTopic>,
+    reconnect_delay: Duration,
+    events: Option<EventStream<T>>,
+    /// Set whilst the stream is disconnected and should wait before resubscribing.
+    reconnect_pending: bool,
+    /// Set once the first subscription has succeeded.
+    subscribed: bool,
+    last_head_slot: Option<Slot>,
+    queued: VecDeque<ResubscribingEvent<T>>,
+}
+
+impl BeaconNodeHttpClient {
+    /// `GET events?topics`, resubscribing whenever the connection is lost.
+    ///
+    /// The returned stream never ends. Disconnections are reported as
+    /// `ResubscribingEvent::Disconnected` and a resubscription is attempted every
+    /// `reconnect_delay` until it succeeds.
+    pub fn get_events_resubscribing<T: EthSpec>(
+        &self,
+        topics: Vec<EventTopic>,
+        reconnect_delay: Duration,
+    ) -> impl Stream<Item = ResubscribingEvent<T>> {
+        let state = ResubscribingState {
+            client: self.clone(),
+            topics,
+            reconnect_delay,
+            events: None,
+            reconnect_pending: false,
+            subscribed: false,
+            last_head_slot: None,
+            queued: VecDeque::new(),
+        };
+
+        futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(message) = state.queued.pop_front() {
+                    return Some((message, state));
+                }
+
+                let events = if let Some(events) = state.events.as_mut() {
+                    events
+                } else {
+                    if state.reconnect_pending {
+                        tokio::time::sleep(state.reconnect_delay).await;
+                    }
+                    match subscribe(&state.client, &state.topics).await {
+                        Ok(events) => {
+                            state.events = Some(events);
+                            state.reconnect_pending = false;
+                            if state.subscribed {
+                                state.queued.push_back(ResubscribingEvent::Resubscribed);
+                            }
+                            state.subscribed = true;
+                        }
+                        Err(e) => {
+                            state.reconnect_pending = true;
+                            state
+                                .queued
+                                .push_back(ResubscribingEvent::Disconnected(Some(e)));
+                        }
+                    }
+                    continue;
+                };
+
+                match events.next().await {
+                    Some(Ok(event)) => {
+                        if let EventKind::Head(head) = &event {
+                            if let Some(gap) = head_slot_gap(state.last_head_slot, head.slot) {
+                                state.queued.push_back(gap);
+                            }
+                            state.last_head_slot = Some(head.slot);
+                        }
+                        state.queued.push_back(ResubscribingEvent::Event(event));
+                    }
+                    Some(Err(e @ Error::InvalidServerSentEvent(_))) => {
+                        state.queued.push_back(ResubscribingEvent::InvalidEvent(e));
+                    }
+                    Some(Err(e)) => {
+                        state.events = None;
+                        state.reconnect_pending = true;
+                        state
+                            .queued
+                            .push_back(ResubscribingEvent::Disconnected(Some(e)));
+                    }
+                    None => {
+                        state.events = None;
+                        state.reconnect_pending = true;
+                        state
+                            .queued
+                            .push_back(ResubscribingEvent::Disconnected(None));
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Like `BeaconNodeHttpClient::get_events`, but the returned stream does not borrow the client and
+/// error statuses are rejected before any event is read.
+async fn subscribe<T: EthSpec>(
+    client: &BeaconNodeHttpClient,
+    topics: &[EventTopic],
+) -> Result<EventStream<T>, Error> {
+    let path = client.events_path(topics)?;
+    let response = ok_or_error(client.client.get(path).send().await?).await?;
+
+    Ok(Box::pin(response.bytes_stream().map(|next| match next {
+        Ok(bytes) => EventKind::from_sse_bytes(bytes.as_ref()),
+        Err(e) => Err(Error::HttpClient(e.into())),
+    })))
+}
+
+/// Returns a `HeadSlotGap` if a head event for `slot` skips any slots after `previous_slot`.
+fn head_slot_gap<T: EthSpec>(
+    previous_slot: Option<Slot>,
+    slot: Slot,
+) -> Option<ResubscribingEvent<T>> {
+    previous_slot
+        .filter(|previous_slot| slot > *previous_slot + 1)
+        .map(|previous_slot| ResubscribingEvent::HeadSlotGap {
+            previous_slot,
+            slot,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    fn gap(previous_slot: Option<u64>, slot: u64) -> Option<(Slot, Slot)> {
+        match head_slot_gap::<MainnetEthSpec>(previous_slot.map(Slot::new), Slot::new(slot)) {
+            Some(ResubscribingEvent::HeadSlotGap {
+                previous_slot,
+                slot,
+            }) => Some((previous_slot, slot)),
+            Some(other) => panic!("unexpected event {:?}", other),
+            None => None,
+        }
+    }
+
+    #[test]
+    fn head_slot_gaps() {
+        assert_eq!(gap(None, 10), None);
+        assert_eq!(gap(Some(9), 10), None);
+        // Re-orgs to an earlier or equal slot are not gaps.
+        assert_eq!(gap(Some(10), 10), None);
+        assert_eq!(gap(Some(11), 10), None);
+        assert_eq!(gap(Some(7), 10), Some((Slot::new(7), Slot::new(10))));
+    }
+}
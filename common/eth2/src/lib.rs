@@ -7,6 +7,7 @@
 //! Eventually it would be ideal to publish this crate on crates.io, however we have some local
 //! dependencies preventing this presently.
 
+pub mod event_stream;
 #[cfg(feature = "lighthouse")]
 pub mod lighthouse;
 #[cfg(feature = "lighthouse")]
@@ -19,6 +20,7 @@ use self::types::{Error as ResponseError, *};
 use futures::Stream;
 use futures_util::StreamExt;
 use lighthouse_network::PeerId;
+use otlp::{TraceContext, TRACEPARENT_HEADER};
 use pretty_reqwest_error::PrettyReqwestError;
 pub use reqwest;
 use reqwest::{
@@ -148,6 +150,7 @@ pub struct BeaconNodeHttpClient {
     client: reqwest::Client,
     server: SensitiveUrl,
     timeouts: Timeouts,
+    trace_context: Option<TraceContext>,
 }
 
 impl fmt::Display for BeaconNodeHttpClient {
@@ -168,6 +171,7 @@ impl BeaconNodeHttpClient {
             client: reqwest::Client::new(),
             server,
             timeouts,
+            trace_context: None,
         }
     }
 
@@ -180,6 +184,26 @@ impl BeaconNodeHttpClient {
             client,
             server,
             timeouts,
+            trace_context: None,
+        }
+    }
+
+    /// Returns a client which sends `trace_context` in the `traceparent` header of each request,
+    /// so that the server may record its work as part of the same trace.
+    pub fn with_trace_context(&self, trace_context: Option<TraceContext>) -> Self {
+        Self {
+            trace_context,
+            ..self.clone()
+        }
+    }
+
+    /// Adds the `traceparent` header to `builder`, if this client has a trace context.
+    fn trace(&self, builder: RequestBuilder) -> RequestBuilder {
+        match &self.trace_context {
+            Some(trace_context) => {
+                builder.header(TRACEPARENT_HEADER, trace_context.to_traceparent())
+            }
+            None => builder,
         }
     }
 
@@ -207,7 +231,7 @@ impl BeaconNodeHttpClient {
         url: U,
         builder: impl FnOnce(RequestBuilder) -> RequestBuilder,
     ) -> Result<Response, Error> {
-        let response = builder(self.client.get(url)).send().await?;
+        let response = builder(self.trace(self.client.get(url))).send().await?;
         ok_or_error(response).await
     }
 
@@ -320,7 +344,7 @@ impl BeaconNodeHttpClient {
         body: &T,
         timeout: Option<Duration>,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.trace(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -335,7 +359,7 @@ impl BeaconNodeHttpClient {
         body: T,
         timeout: Option<Duration>,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.trace(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -355,7 +379,7 @@ impl BeaconNodeHttpClient {
         timeout: Option<Duration>,
         fork: ForkName,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.trace(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -375,7 +399,7 @@ impl BeaconNodeHttpClient {
         timeout: Option<Duration>,
         fork: ForkName,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.trace(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -980,6 +1004,82 @@ impl BeaconNodeHttpClient {
             .transpose()
     }
 
+    /// `GET beacon/light_client/bootstrap/{block_root}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_light_client_bootstrap<T: EthSpec>(
+        &self,
+        block_root: Hash256,
+    ) -> Result<Option<ForkVersionedResponse<LightClientBootstrap<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("bootstrap")
+            .push(&format!("{:?}", block_root));
+
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/light_client/updates?start_period,count`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_light_client_updates<T: EthSpec>(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Option<Vec<ForkVersionedResponse<LightClientUpdate<T>>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("updates");
+
+        path.query_pairs_mut()
+            .append_pair("start_period", &start_period.to_string())
+            .append_pair("count", &count.to_string());
+
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/light_client/finality_update`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_light_client_finality_update<T: EthSpec>(
+        &self,
+    ) -> Result<Option<ForkVersionedResponse<LightClientFinalityUpdate<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("finality_update");
+
+        self.get_opt(path).await
+    }
+
+    /// `GET beacon/light_client/optimistic_update`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_light_client_optimistic_update<T: EthSpec>(
+        &self,
+    ) -> Result<Option<ForkVersionedResponse<LightClientOptimisticUpdate<T>>>, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("light_client")
+            .push("optimistic_update");
+
+        self.get_opt(path).await
+    }
+
     /// `GET beacon/blocks/{block_id}/root`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1907,8 +2007,12 @@ impl BeaconNodeHttpClient {
             .push("liveness")
             .push(&epoch.to_string());
 
-        self.post_with_timeout_and_response(path, &indices, self.timeouts.liveness)
-            .await
+        self.post_with_timeout_and_response(
+            path,
+            &ValidatorIndexDataRef(&indices),
+            self.timeouts.liveness,
+        )
+        .await
     }
 
     /// `POST validator/duties/attester/{epoch}`
@@ -1986,11 +2090,8 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
-    /// `GET events?topics`
-    pub async fn get_events<T: EthSpec>(
-        &self,
-        topic: &[EventTopic],
-    ) -> Result<impl Stream<Item = Result<EventKind<T>, Error>>, Error> {
+    /// The path of the `GET events?topics` endpoint.
+    fn events_path(&self, topic: &[EventTopic]) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
@@ -2003,6 +2104,16 @@ impl BeaconNodeHttpClient {
             .join(",");
         path.query_pairs_mut().append_pair("topics", &topic_string);
 
+        Ok(path)
+    }
+
+    /// `GET events?topics`
+    pub async fn get_events<T: EthSpec>(
+        &self,
+        topic: &[EventTopic],
+    ) -> Result<impl Stream<Item = Result<EventKind<T>, Error>>, Error> {
+        let path = self.events_path(topic)?;
+
         Ok(self
             .client
             .get(path)
@@ -3,15 +3,20 @@
 mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
+mod block_provenance;
 mod block_rewards;
+mod epoch_rewards;
+mod fork_choice_simulation;
 mod standard_block_rewards;
 mod sync_committee_rewards;
 
 use crate::{
     ok_or_error,
     types::{
-        BeaconState, ChainSpec, DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock,
-        GenericResponse, ValidatorId,
+        option_query_vec, AttesterSlashing, BeaconState, ChainSpec, DepositTreeSnapshot, Epoch,
+        ErrorMessage, EthSpec, ExecutionOptimisticFinalizedResponse, FinalizedExecutionBlock,
+        GenericResponse, IndexedAttestation, ProposalDecision, PublicKeyBytes, RelayStats,
+        SignedBeaconBlock, SignedBlindedBeaconBlock, ValidatorData, ValidatorId, ValidatorStatus,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot, StateId, StatusCode,
 };
@@ -20,17 +25,32 @@ use reqwest::IntoUrl;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use store::{AnchorInfo, Split, StoreConfig};
 
+pub use store::backup::BackupStatus;
+pub use store::reconstruct::ReconstructionStatus;
+
 pub use attestation_performance::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
+    EpochAttestationPerformance, ValidatorAttestationPerformance,
 };
 pub use attestation_rewards::StandardAttestationRewards;
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
+pub use block_provenance::{BlockProvenance, BlockSource};
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use epoch_rewards::{EpochRewards, EpochRewardsQuery};
+pub use fork_choice_simulation::{ForkChoiceSimulation, ForkChoiceSimulationRequest};
+pub use lighthouse_network::{
+    types::{
+        HandshakeFailure, HandshakeFailureReason, NatMapping, NatStatus, RpcRequestOutcome,
+        RpcRequestTrace, SyncState,
+    },
+    OperatorAction, PeerInfo, ProtocolStats,
+};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
 
@@ -50,6 +70,67 @@ pub struct Peer<T: EthSpec> {
     pub peer_info: PeerInfo<T>,
 }
 
+/// Information returned by `peers/{peer_id}/stats`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerStats {
+    pub peer_id: String,
+    /// The peer's overall score, or `None` if the peer is trusted.
+    pub score: Option<f64>,
+    /// The peer's gossipsub score, or `None` if the peer is trusted.
+    pub gossipsub_score: Option<f64>,
+    /// RPC statistics keyed by protocol name.
+    pub rpc: HashMap<String, ProtocolStats>,
+}
+
+/// The score of a peer and its components, returned by `peers/scores`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerScore {
+    pub peer_id: String,
+    pub client: String,
+    pub connection_status: String,
+    /// The peer's overall score, or `None` if the peer is trusted.
+    pub score: Option<f64>,
+    /// The score accumulated from penalties applied by Lighthouse, or `None` if the peer is
+    /// trusted.
+    pub lighthouse_score: Option<f64>,
+    /// The peer's gossipsub score, or `None` if the peer is trusted.
+    pub gossipsub_score: Option<f64>,
+    /// True if a negative gossipsub score is being ignored to allow it to decay.
+    pub ignore_negative_gossipsub_score: bool,
+    /// The state implied by the score, one of `Healthy`, `Disconnected` or `Banned`.
+    pub score_state: String,
+    pub trusted: bool,
+    pub banned: bool,
+    /// The number of seconds before the score of a banned peer starts to decay.
+    pub decay_delay_secs: Option<u64>,
+    /// The most recent penalties applied to the peer, oldest first.
+    pub penalties: Vec<PeerPenalty>,
+}
+
+/// A penalty applied to a peer's score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerPenalty {
+    pub action: String,
+    pub source: String,
+    pub msg: String,
+    pub seconds_ago: u64,
+}
+
+/// The format of the response to `peers/scores`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeerScoresFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PeerScoresQuery {
+    #[serde(default)]
+    pub format: PeerScoresFormat,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -358,14 +439,158 @@ impl From<Eth1Block> for FinalizedExecutionBlock {
     }
 }
 
+/// Query parameters for `GET lighthouse/beacon/states/{state_id}/validators`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorsPageQuery {
+    /// The number of matching validators to skip.
+    pub offset: Option<u64>,
+    /// The maximum number of validators to return.
+    pub limit: Option<u64>,
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub status: Option<Vec<ValidatorStatus>>,
+    /// Only return validators with at least this balance (in Gwei).
+    pub min_balance: Option<u64>,
+    /// Only return validators with at most this balance (in Gwei).
+    pub max_balance: Option<u64>,
+}
+
+/// A page of the validators matching a `ValidatorsPageQuery`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorsPage {
+    /// The number of validators matching the filters, ignoring `offset` and `limit`.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub total: u64,
+    pub validators: Vec<ValidatorData>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     pub schema_version: u64,
+    /// The oldest schema version this node can migrate from, or downgrade to.
+    pub min_schema_version: u64,
+    /// The newest schema version this node can migrate to.
+    pub max_schema_version: u64,
     pub config: StoreConfig,
     pub split: Split,
     pub anchor: Option<AnchorInfo>,
 }
 
+/// The body of a `POST lighthouse/database/backup` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseBackupRequest {
+    /// The name of the sub-directory of the beacon node's `--http-database-backup-dir` to write
+    /// the backup into.
+    pub name: String,
+    /// Limits the rate at which the hot database is copied.
+    #[serde(default)]
+    pub max_bytes_per_second: Option<u64>,
+}
+
+/// An entry in the body of a `POST lighthouse/validator_monitor/validators` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMonitorRegistration {
+    pub pubkey: PublicKeyBytes,
+    /// The cohort used to aggregate the validator's metrics.
+    #[serde(default)]
+    pub cohort: Option<String>,
+}
+
+/// The outcome of verifying a checkpoint sync state against several providers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointSyncReport {
+    /// The provider the state was downloaded from.
+    pub provider: String,
+    pub state_slot: Slot,
+    pub state_root: Hash256,
+    /// The number of providers which were required to agree on `state_root`.
+    pub quorum: usize,
+    /// The providers which agreed on `state_root`, including `provider`.
+    pub agreed: Vec<String>,
+    pub disagreed: Vec<CheckpointSyncDisagreement>,
+}
+
+/// A provider which did not confirm the root of a checkpoint sync state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointSyncDisagreement {
+    pub provider: String,
+    /// The root the provider has at the state's slot, if it returned one.
+    pub state_root: Option<Hash256>,
+    /// Why the provider's response was rejected.
+    pub reason: String,
+}
+
+/// The response of a `GET lighthouse/beacon_processor/status` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconProcessorStatus {
+    /// The work queues, in the order in which they are served by idle workers.
+    pub queues: Vec<BeaconProcessorQueue>,
+    pub active_workers: usize,
+    pub max_workers: usize,
+    pub priority_weights: BeaconProcessorPriorityWeights,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconProcessorQueue {
+    pub name: String,
+    pub length: usize,
+    pub max_length: usize,
+    /// The number of items dropped because the queue was full, since the node started.
+    pub dropped: u64,
+}
+
+/// The percentage of the beacon processor's workers which each class of work may occupy.
+///
+/// Each weight is between 1 and 100. Lowering a weight keeps workers free for block import when
+/// the node is under load.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BeaconProcessorPriorityWeights {
+    pub attestations: u8,
+    pub sync_committee: u8,
+    pub rpc: u8,
+    pub api: u8,
+    pub backfill: u8,
+}
+
+/// The body of a `POST lighthouse/logging` request. Omitted fields are left unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoggingRequest {
+    /// Filter directives for the terminal output and the log file, e.g.
+    /// `info,network=debug,beacon_chain=trace`.
+    #[serde(default)]
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub debug_capture: Option<DebugCaptureRequest>,
+}
+
+/// Writes all logs at or above `level` to a separate file for `duration_secs`.
+///
+/// A duration of zero ends the active capture.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugCaptureRequest {
+    pub duration_secs: u64,
+    /// Defaults to `debug`.
+    #[serde(default)]
+    pub level: Option<String>,
+}
+
+/// The response of a `POST lighthouse/logging` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggingStatus {
+    /// The filter directives which are currently applied to the terminal output.
+    pub filter: String,
+    /// The filter directives which are currently applied to the log file, if there is one.
+    pub logfile_filter: Option<String>,
+    pub debug_capture: Option<DebugCaptureStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DebugCaptureStatus {
+    pub path: PathBuf,
+    pub level: String,
+    pub remaining_secs: u64,
+}
+
 impl BeaconNodeHttpClient {
     /// Perform a HTTP GET request, returning `None` on a 404 error.
     async fn get_bytes_opt<U: IntoUrl>(&self, url: U) -> Result<Option<Vec<u8>>, Error> {
@@ -400,6 +625,73 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/peers/{peer_id}/stats`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_peer_stats(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<GenericResponse<PeerStats>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push(peer_id)
+            .push("stats");
+
+        self.get_opt(path).await
+    }
+
+    /// `GET lighthouse/peers/scores`
+    pub async fn get_lighthouse_peer_scores(
+        &self,
+    ) -> Result<GenericResponse<Vec<PeerScore>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("scores");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/peers/scores?format=csv`
+    pub async fn get_lighthouse_peer_scores_csv(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("scores");
+
+        path.query_pairs_mut().append_pair("format", "csv");
+
+        Ok(self.get_response(path, |b| b).await?.text().await?)
+    }
+
+    /// `POST lighthouse/peers/{peer_id}/{action}`
+    pub async fn post_lighthouse_peer_action(
+        &self,
+        peer_id: &str,
+        action: OperatorAction,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push(peer_id)
+            .push(&action.to_string());
+
+        self.post(path, &()).await
+    }
+
     /// `GET lighthouse/syncing`
     pub async fn get_lighthouse_syncing(&self) -> Result<GenericResponse<SyncState>, Error> {
         let mut path = self.server.full.clone();
@@ -412,6 +704,61 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/nat`
+    pub async fn get_lighthouse_nat(&self) -> Result<GenericResponse<bool>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("nat");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/nat/status`
+    pub async fn get_lighthouse_nat_status(&self) -> Result<GenericResponse<NatStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("nat")
+            .push("status");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/peers/handshake_failures`
+    pub async fn get_lighthouse_peers_handshake_failures(
+        &self,
+    ) -> Result<GenericResponse<Vec<HandshakeFailure>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("handshake_failures");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/rpc/requests`
+    pub async fn get_lighthouse_rpc_requests(
+        &self,
+    ) -> Result<GenericResponse<Vec<RpcRequestTrace>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("rpc")
+            .push("requests");
+
+        self.get(path).await
+    }
+
     /*
      * Note:
      *
@@ -432,6 +779,20 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/block_provenance`
+    pub async fn get_lighthouse_block_provenance(
+        &self,
+    ) -> Result<GenericResponse<Vec<BlockProvenance>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("block_provenance");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/global`
     pub async fn get_lighthouse_validator_inclusion_global(
         &self,
@@ -534,6 +895,60 @@ impl BeaconNodeHttpClient {
             .transpose()
     }
 
+    /// `GET lighthouse/beacon/states/{state_id}/validators?offset,limit,status,min_balance,max_balance`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_beacon_states_validators(
+        &self,
+        state_id: StateId,
+        offset: Option<u64>,
+        limit: Option<u64>,
+        statuses: Option<&[ValidatorStatus]>,
+        min_balance: Option<u64>,
+        max_balance: Option<u64>,
+    ) -> Result<Option<ExecutionOptimisticFinalizedResponse<ValidatorsPage>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon")
+            .push("states")
+            .push(&state_id.to_string())
+            .push("validators");
+
+        if let Some(offset) = offset {
+            path.query_pairs_mut()
+                .append_pair("offset", &offset.to_string());
+        }
+
+        if let Some(limit) = limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
+        if let Some(statuses) = statuses {
+            let status_string = statuses
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            path.query_pairs_mut().append_pair("status", &status_string);
+        }
+
+        if let Some(min_balance) = min_balance {
+            path.query_pairs_mut()
+                .append_pair("min_balance", &min_balance.to_string());
+        }
+
+        if let Some(max_balance) = max_balance {
+            path.query_pairs_mut()
+                .append_pair("max_balance", &max_balance.to_string());
+        }
+
+        self.get_opt(path).await
+    }
+
     /// `GET lighthouse/staking`
     pub async fn get_lighthouse_staking(&self) -> Result<bool, Error> {
         let mut path = self.server.full.clone();
@@ -572,6 +987,257 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `GET lighthouse/database/reconstruction_status`
+    pub async fn get_lighthouse_database_reconstruction_status(
+        &self,
+    ) -> Result<ReconstructionStatus, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("reconstruction_status");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/proposal_decisions`
+    pub async fn get_lighthouse_proposal_decisions(
+        &self,
+    ) -> Result<GenericResponse<Vec<ProposalDecision>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("proposal_decisions");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/builder_relays`
+    pub async fn get_lighthouse_builder_relays(
+        &self,
+    ) -> Result<GenericResponse<Vec<RelayStats>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("builder_relays");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/validator_monitor/validators`
+    ///
+    /// The `token` must match the beacon node's `--http-validator-monitor-token-file`.
+    pub async fn post_lighthouse_validator_monitor_validators(
+        &self,
+        validators: &[ValidatorMonitorRegistration],
+        token: &str,
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validator_monitor")
+            .push("validators");
+
+        let response = self
+            .client
+            .post(path)
+            .bearer_auth(token)
+            .json(validators)
+            .send()
+            .await?;
+        ok_or_error(response).await?;
+        Ok(())
+    }
+
+    /// `POST lighthouse/slasher/check_attestation`
+    ///
+    /// The `token` must match the beacon node's `--http-admin-token-file`.
+    pub async fn post_lighthouse_slasher_check_attestation<T: EthSpec>(
+        &self,
+        attestation: &IndexedAttestation<T>,
+        token: &str,
+    ) -> Result<GenericResponse<Vec<AttesterSlashing<T>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("check_attestation");
+
+        let response = self
+            .client
+            .post(path)
+            .bearer_auth(token)
+            .json(attestation)
+            .send()
+            .await?;
+        Ok(ok_or_error(response).await?.json().await?)
+    }
+
+    /// `GET lighthouse/checkpoint_sync`
+    ///
+    /// Returns `Ok(None)` if the node was not checkpoint synced from a URL since it started.
+    pub async fn get_lighthouse_checkpoint_sync(
+        &self,
+    ) -> Result<Option<CheckpointSyncReport>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("checkpoint_sync");
+
+        self.get_opt(path).await
+    }
+
+    /// `POST lighthouse/database/backup`
+    pub async fn post_lighthouse_database_backup(
+        &self,
+        request: &DatabaseBackupRequest,
+    ) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backup");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `GET lighthouse/database/backup`
+    pub async fn get_lighthouse_database_backup(&self) -> Result<Option<BackupStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backup");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/beacon_processor/status`
+    pub async fn get_lighthouse_beacon_processor_status(
+        &self,
+    ) -> Result<GenericResponse<BeaconProcessorStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon_processor")
+            .push("status");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/beacon_processor/priority_weights`
+    ///
+    /// The `token` must match the beacon node's `--http-admin-token-file`.
+    pub async fn post_lighthouse_beacon_processor_priority_weights(
+        &self,
+        priority_weights: &BeaconProcessorPriorityWeights,
+        token: &str,
+    ) -> Result<GenericResponse<BeaconProcessorPriorityWeights>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("beacon_processor")
+            .push("priority_weights");
+
+        let response = self
+            .client
+            .post(path)
+            .bearer_auth(token)
+            .json(priority_weights)
+            .send()
+            .await?;
+        Ok(ok_or_error(response).await?.json().await?)
+    }
+
+    /// `POST lighthouse/logging`
+    ///
+    /// The `token` must match the beacon node's `--http-admin-token-file`.
+    pub async fn post_lighthouse_logging(
+        &self,
+        request: &LoggingRequest,
+        token: &str,
+    ) -> Result<GenericResponse<LoggingStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        let response = self
+            .client
+            .post(path)
+            .bearer_auth(token)
+            .json(request)
+            .send()
+            .await?;
+        Ok(ok_or_error(response).await?.json().await?)
+    }
+
+    /// `POST lighthouse/database/historical_blocks`
+    pub async fn post_lighthouse_database_historical_blocks<E: EthSpec>(
+        &self,
+        blocks: &[SignedBlindedBeaconBlock<E>],
+    ) -> Result<AnchorInfo, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("historical_blocks");
+
+        self.post_with_response(path, &blocks).await
+    }
+
+    /// `POST lighthouse/database/historical_blocks` with an SSZ list of full blocks.
+    pub async fn post_lighthouse_database_historical_blocks_ssz<E: EthSpec>(
+        &self,
+        blocks: &[SignedBeaconBlock<E>],
+    ) -> Result<AnchorInfo, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("historical_blocks");
+
+        let mut body = vec![];
+        let mut encoder =
+            ssz::SszEncoder::container(&mut body, blocks.len() * ssz::BYTES_PER_LENGTH_OFFSET);
+        for block in blocks {
+            encoder.append(block);
+        }
+        encoder.finalize();
+
+        self.post_generic_with_ssz_body(path, body, None)
+            .await?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -609,7 +1275,7 @@ impl BeaconNodeHttpClient {
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("lighthouse")
             .push("analysis")
-            .push("block_packing_efficiency");
+            .push("block_packing");
 
         path.query_pairs_mut()
             .append_pair("start_epoch", &start_epoch.to_string())
@@ -640,4 +1306,94 @@ impl BeaconNodeHttpClient {
 
         self.get(path).await
     }
+
+    /// `GET` lighthouse/analysis/attestation_performance/{target}?start_epoch,end_epoch,stream
+    ///
+    /// The server streams one JSON object per epoch. If it fails part way through, the stream
+    /// ends with an error message which is returned as `Error::ServerMessage`.
+    pub async fn get_lighthouse_analysis_attestation_performance_stream(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        target: &str,
+    ) -> Result<Vec<EpochAttestationPerformance>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("attestation_performance")
+            .push(target);
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string())
+            .append_pair("stream", "true");
+
+        let body = self.get_response(path, |b| b).await?.text().await?;
+
+        body.lines()
+            .map(|line| {
+                if let Ok(message) = serde_json::from_str::<ErrorMessage>(line) {
+                    return Err(Error::ServerMessage(message));
+                }
+                serde_json::from_str(line).map_err(Error::InvalidJson)
+            })
+            .collect()
+    }
+
+    /// `POST` lighthouse/analysis/fork_choice
+    pub async fn post_lighthouse_analysis_fork_choice<E: EthSpec>(
+        &self,
+        request: &ForkChoiceSimulationRequest<E>,
+    ) -> Result<ForkChoiceSimulation, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("fork_choice");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `POST` lighthouse/analysis/rewards?start_epoch,end_epoch
+    ///
+    /// The server streams one JSON object per epoch. If it fails part way through, the stream
+    /// ends with an error message which is returned as `Error::ServerMessage`.
+    pub async fn post_lighthouse_analysis_rewards(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<Vec<EpochRewards>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("rewards");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string());
+
+        let body = self
+            .post_generic(path, &validators, None)
+            .await?
+            .text()
+            .await?;
+
+        body.lines()
+            .map(|line| {
+                if let Ok(message) = serde_json::from_str::<ErrorMessage>(line) {
+                    return Err(Error::ServerMessage(message));
+                }
+                serde_json::from_str(line).map_err(Error::InvalidJson)
+            })
+            .collect()
+    }
 }
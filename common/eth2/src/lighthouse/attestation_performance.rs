@@ -31,9 +31,28 @@ impl AttestationPerformance {
     }
 }
 
+/// The attestation performance of a single validator in an epoch.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ValidatorAttestationPerformance {
+    pub index: u64,
+    #[serde(flatten)]
+    pub statistics: AttestationPerformanceStatistics,
+}
+
+/// The attestation performance of all requested validators in an epoch, as streamed by the
+/// `/lighthouse/analysis/attestation_performance` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EpochAttestationPerformance {
+    pub epoch: Epoch,
+    pub validators: Vec<ValidatorAttestationPerformance>,
+}
+
 /// Query parameters for the `/lighthouse/analysis/attestation_performance` endpoint.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct AttestationPerformanceQuery {
     pub start_epoch: Epoch,
     pub end_epoch: Epoch,
+    /// Stream one `EpochAttestationPerformance` per epoch as newline-delimited JSON.
+    #[serde(default)]
+    pub stream: bool,
 }
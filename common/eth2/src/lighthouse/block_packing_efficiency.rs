@@ -24,6 +24,10 @@ pub struct BlockPackingEfficiency {
     pub proposer_info: ProposerInfo,
     pub available_attestations: usize,
     pub included_attestations: usize,
+    /// An upper bound on the reward, in Gwei, forgone by the proposer by not including the
+    /// attestations which remained available.
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub estimated_missed_reward: u64,
     pub prior_skip_slots: u64,
 }
 
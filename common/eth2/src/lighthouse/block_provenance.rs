@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot};
+
+/// How a block arrived at this node.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockSource {
+    /// Published on the `beacon_block` gossip topic.
+    Gossip,
+    /// Downloaded by range sync with `BlocksByRange`.
+    RpcRange,
+    /// Downloaded by a single block or parent lookup with `BlocksByRoot`.
+    RpcRoot,
+    /// Published via the HTTP API, e.g. by a connected validator client.
+    HttpApi,
+    /// The block was imported without its source being recorded.
+    Unknown,
+}
+
+/// The provenance of a block imported by this node.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockProvenance {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    pub source: BlockSource,
+    /// The peer which sent us the block, if it was received from the network.
+    pub peer_id: Option<String>,
+    /// The time between the start of the block's slot and it being received, in milliseconds.
+    pub observed_delay_ms: Option<u64>,
+    /// The time between the block being received and it being imported, in milliseconds.
+    pub import_delay_ms: Option<u64>,
+}
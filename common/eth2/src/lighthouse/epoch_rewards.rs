@@ -0,0 +1,22 @@
+use crate::lighthouse::{StandardAttestationRewards, StandardBlockReward};
+use serde::{Deserialize, Serialize};
+use types::Epoch;
+
+/// Query parameters for the `lighthouse/analysis/rewards` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpochRewardsQuery {
+    /// Lower epoch limit, inclusive.
+    pub start_epoch: Epoch,
+    /// Upper epoch limit, inclusive.
+    pub end_epoch: Epoch,
+}
+
+/// The rewards earned by a set of validators during a single epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EpochRewards {
+    pub epoch: Epoch,
+    /// Rewards for attestations made during `epoch`.
+    pub attestation_rewards: StandardAttestationRewards,
+    /// Rewards for blocks proposed by the validators during `epoch`.
+    pub block_rewards: Vec<StandardBlockReward>,
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use types::{EthSpec, Hash256, IndexedAttestation, SignedBeaconBlock, Slot};
+
+/// Hypothetical changes to apply to a copy of fork choice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "E: EthSpec")]
+pub struct ForkChoiceSimulationRequest<E: EthSpec> {
+    /// The slot at which to evaluate the head. Defaults to the current slot, and is never earlier.
+    #[serde(default)]
+    pub slot: Option<Slot>,
+    /// A block to apply before the attestations. Its signatures are not verified.
+    #[serde(default)]
+    pub block: Option<SignedBeaconBlock<E>>,
+    /// The time between the start of the block's slot and it being received, in milliseconds.
+    ///
+    /// Blocks received early in their slot are given the proposer boost.
+    #[serde(default, with = "serde_utils::quoted_u64")]
+    pub block_delay_ms: u64,
+    /// Attestations to apply. Their signatures are not verified.
+    #[serde(default)]
+    pub attestations: Vec<IndexedAttestation<E>>,
+}
+
+/// The head of fork choice before and after applying a `ForkChoiceSimulationRequest`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceSimulation {
+    /// The slot at which the head was evaluated.
+    pub slot: Slot,
+    pub original_head_root: Hash256,
+    pub original_head_slot: Slot,
+    pub simulated_head_root: Hash256,
+    pub simulated_head_slot: Slot,
+    pub head_changed: bool,
+}
@@ -354,6 +354,54 @@ impl ValidatorClientHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/slashing_protection/health`
+    ///
+    /// Returns `Ok(None)` if no health check has completed yet.
+    pub async fn get_lighthouse_slashing_protection_health(
+        &self,
+    ) -> Result<Option<GenericResponse<SlashingProtectionHealth>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slashing_protection")
+            .push("health");
+
+        self.get_opt(path).await
+    }
+
+    /// `GET lighthouse/remotekeys/health`
+    pub async fn get_lighthouse_remotekeys_health(
+        &self,
+    ) -> Result<GenericResponse<Vec<RemoteSignerHealth>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("remotekeys")
+            .push("health");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/clock_skew`
+    ///
+    /// Returns `Ok(None)` if the clock has not been compared with the beacon nodes yet.
+    pub async fn get_lighthouse_clock_skew(
+        &self,
+    ) -> Result<Option<GenericResponse<ClockSkewStatus>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("clock_skew");
+
+        self.get_opt(path).await
+    }
+
     /// `GET lighthouse/spec`
     pub async fn get_lighthouse_spec<T: Serialize + DeserializeOwned>(
         &self,
@@ -398,6 +446,127 @@ impl ValidatorClientHttpClient {
         self.get_opt(path).await
     }
 
+    /// `GET lighthouse/validators/{validator_pubkey}/duties`
+    pub async fn get_lighthouse_validators_pubkey_duties(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> Result<Option<GenericResponse<ValidatorDuties>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_pubkey.to_string())
+            .push("duties");
+
+        self.get_opt(path).await
+    }
+
+    /// `POST lighthouse/validators/{validator_pubkey}/proposal_overrides`
+    pub async fn post_lighthouse_validators_pubkey_proposal_overrides(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        request: &ProposalOverridesRequest,
+    ) -> Result<GenericResponse<ProposalOverridesRequest>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_pubkey.to_string())
+            .push("proposal_overrides");
+
+        self.post(path, request).await
+    }
+
+    /// `GET lighthouse/validators/{validator_pubkey}/history`
+    pub async fn get_lighthouse_validators_pubkey_history(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        limit: Option<usize>,
+    ) -> Result<GenericResponse<Vec<SignedMessageRecord>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_pubkey.to_string())
+            .push("history");
+
+        if let Some(limit) = limit {
+            path.query_pairs_mut()
+                .append_pair("limit", &limit.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/fee_recipients`
+    pub async fn get_lighthouse_fee_recipients(
+        &self,
+    ) -> Result<GenericResponse<Vec<FeeRecipientResolution>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fee_recipients");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/duties/recorded`
+    pub async fn get_lighthouse_duties_recorded(
+        &self,
+    ) -> Result<GenericResponse<Vec<RecordedDuty>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duties")
+            .push("recorded");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/duties/refresh_times`
+    pub async fn get_lighthouse_duties_refresh_times(
+        &self,
+    ) -> Result<GenericResponse<DutyRefreshTimes>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duties")
+            .push("refresh_times");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/duties/recorded/{id}/replay`
+    pub async fn post_lighthouse_duties_recorded_replay(
+        &self,
+        id: u64,
+        request: &ReplayDutyRequest,
+    ) -> Result<GenericResponse<ReplayedDuty>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("duties")
+            .push("recorded")
+            .push(&id.to_string())
+            .push("replay");
+
+        self.post(path, request).await
+    }
+
     /// `POST lighthouse/validators`
     pub async fn post_lighthouse_validators(
         &self,
@@ -413,6 +582,21 @@ impl ValidatorClientHttpClient {
         self.post(path, &validators).await
     }
 
+    /// `POST lighthouse/logging`
+    pub async fn post_lighthouse_logging(
+        &self,
+        request: &LoggingRequest,
+    ) -> Result<GenericResponse<LoggingStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.post(path, request).await
+    }
+
     /// `POST lighthouse/validators/mnemonic`
     pub async fn post_lighthouse_validators_mnemonic(
         &self,
@@ -505,6 +689,37 @@ impl ValidatorClientHttpClient {
         self.delete_with_unsigned_response(path, req).await
     }
 
+    /// `POST lighthouse/migration/export`
+    pub async fn post_lighthouse_migration_export(
+        &self,
+        req: &MigrationExportRequest,
+    ) -> Result<MigrationExportResponse, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("migration")
+            .push("export");
+
+        self.post(path, req).await
+    }
+
+    /// `GET lighthouse/migration/locks`
+    pub async fn get_lighthouse_migration_locks(
+        &self,
+    ) -> Result<GenericResponse<Vec<SignedMigrationRecord>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("migration")
+            .push("locks");
+
+        self.get(path).await
+    }
+
     fn make_keystores_url(&self) -> Result<Url, Error> {
         let mut url = self.server.full.clone();
         url.path_segments_mut()
@@ -684,6 +899,33 @@ impl ValidatorClientHttpClient {
 
         self.post(path, &()).await
     }
+
+    /// `POST /eth/v1/validator/{pubkey}/voluntary_exit?broadcast=true`
+    ///
+    /// Signs a voluntary exit and publishes it to the validator client's beacon nodes.
+    pub async fn post_validator_voluntary_exit_and_broadcast(
+        &self,
+        pubkey: &PublicKeyBytes,
+        epoch: Option<Epoch>,
+    ) -> Result<GenericResponse<SignedVoluntaryExit>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("eth")
+            .push("v1")
+            .push("validator")
+            .push(&pubkey.to_string())
+            .push("voluntary_exit");
+
+        if let Some(epoch) = epoch {
+            path.query_pairs_mut()
+                .append_pair("epoch", &epoch.to_string());
+        }
+        path.query_pairs_mut().append_pair("broadcast", "true");
+
+        self.post(path, &()).await
+    }
 }
 
 /// Returns `Ok(response)` if the response is a `200 OK` response or a
@@ -1,10 +1,13 @@
 use account_utils::ZeroizeString;
 use eth2_keystore::Keystore;
 use graffiti::GraffitiString;
+use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-pub use crate::lighthouse::Health;
+pub use crate::lighthouse::{
+    DebugCaptureRequest, DebugCaptureStatus, Health, LoggingRequest, LoggingStatus,
+};
 pub use crate::lighthouse_vc::std_types::*;
 pub use crate::types::{GenericResponse, VersionData};
 pub use types::*;
@@ -148,9 +151,26 @@ pub struct UpdateGasLimitRequest {
     pub gas_limit: u64,
 }
 
+/// One-off overrides for the block proposed by a validator at a single slot.
+///
+/// Fields which are omitted fall back to the validator's usual configuration.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ProposalOverridesRequest {
+    pub slot: Slot,
+    #[serde(default)]
+    pub graffiti: Option<GraffitiString>,
+    #[serde(default)]
+    pub fee_recipient: Option<Address>,
+    #[serde(default)]
+    pub builder_proposals: Option<bool>,
+}
+
 #[derive(Deserialize)]
 pub struct VoluntaryExitQuery {
     pub epoch: Option<Epoch>,
+    /// Publish the signed exit to the beacon nodes, in addition to returning it.
+    #[serde(default)]
+    pub broadcast: bool,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -168,3 +188,261 @@ pub struct SingleExportKeystoresResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub validating_keystore_password: Option<ZeroizeString>,
 }
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MigrationExportRequest {
+    pub pubkeys: Vec<PublicKeyBytes>,
+    /// The number of epochs before the validators may be enabled on this validator client again.
+    #[serde(default)]
+    pub wait_epochs: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct MigrationExportResponse {
+    pub data: Vec<SingleExportKeystoresResponse>,
+    #[serde(with = "serde_utils::json_str")]
+    pub slashing_protection: Interchange,
+    /// A record for each validator that was disabled by the export.
+    pub migration_records: Vec<SignedMigrationRecord>,
+    /// Set if the records of validators which were only known from their slashing protection data
+    /// couldn't be saved after the export. The records of the deleted validators are always saved
+    /// before they are deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A record that a validator was disabled in order to migrate it to another machine.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MigrationRecord {
+    pub pubkey: PublicKeyBytes,
+    pub disabled_at_epoch: Epoch,
+    /// The first epoch at which the validator may be enabled again.
+    pub reenable_epoch: Epoch,
+    /// Unix timestamp of the start of `reenable_epoch`, in seconds.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub reenable_time: u64,
+}
+
+/// A `MigrationRecord` signed by the validator client's API token.
+///
+/// The signature is made in the same way as the `Signature` header of HTTP responses, across the
+/// JSON encoding of the `record`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SignedMigrationRecord {
+    pub record: MigrationRecord,
+    pub signature: String,
+}
+
+/// The result of the most recent health check of the slashing protection database.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SlashingProtectionHealth {
+    /// `true` if SQLite's `quick_check` found no problems.
+    pub integrity_ok: bool,
+    /// The problems reported by `quick_check`, if any.
+    pub integrity_errors: Vec<String>,
+    pub database_size_bytes: u64,
+    pub wal_size_bytes: u64,
+    /// Unix timestamp of the check, in seconds.
+    pub last_check: u64,
+    /// The most recent backup, if backups are enabled and one has been taken.
+    pub last_backup: Option<PathBuf>,
+    /// Unix timestamp of the most recent backup, in seconds.
+    pub last_backup_time: Option<u64>,
+}
+
+/// The result of the most recent comparison of the local clock with the beacon nodes.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ClockSkewStatus {
+    /// The slot of the local clock at the time of the check.
+    pub local_slot: Slot,
+    /// The current slot of each beacon node which responded.
+    pub beacon_node_slots: Vec<BeaconNodeSlot>,
+    /// `true` if every beacon node which responded disagrees with the local clock by more than the
+    /// configured maximum. Signing is refused whilst this is the case.
+    pub skew_detected: bool,
+    /// Unix timestamp of the check, in seconds.
+    pub last_check: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BeaconNodeSlot {
+    pub beacon_node: String,
+    pub slot: Slot,
+}
+
+/// The result of the most recent health check of the remote signer of a validator.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RemoteSignerHealth {
+    pub pubkey: PublicKeyBytes,
+    pub url: String,
+    /// `true` if the remote signer returned a valid signature.
+    pub reachable: bool,
+    /// `true` if the remote signer's list of public keys does not include `pubkey`.
+    #[serde(default)]
+    pub key_missing: bool,
+    /// The reason the check failed, if it did.
+    pub error: Option<String>,
+    /// Unix timestamp of the check, in seconds.
+    pub last_check: u64,
+}
+
+/// The duties of a single validator which are known to the validator client.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ValidatorDuties {
+    pub pubkey: PublicKeyBytes,
+    /// The index of the validator, if it is known to the beacon node.
+    pub validator_index: Option<u64>,
+    pub attester: Vec<AttesterDutyInfo>,
+    pub proposer: Vec<ProposerDutyInfo>,
+    pub sync_committee: Vec<SyncCommitteeDutyInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AttesterDutyInfo {
+    pub epoch: Epoch,
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_committee_index: u64,
+    pub is_aggregator: bool,
+    pub dependent_root: Hash256,
+    /// Unix timestamp, in seconds, at which the attestation is scheduled to be produced.
+    pub scheduled_time: Option<u64>,
+    /// The beacon node which supplied the duty.
+    pub beacon_node: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ProposerDutyInfo {
+    pub epoch: Epoch,
+    pub slot: Slot,
+    pub dependent_root: Hash256,
+    /// Unix timestamp, in seconds, at which the block is scheduled to be proposed.
+    pub scheduled_time: Option<u64>,
+    /// The beacon node which supplied the duty.
+    pub beacon_node: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SyncCommitteeDutyInfo {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub sync_committee_period: u64,
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub validator_sync_committee_indices: Vec<u64>,
+    /// The beacon node which supplied the duty.
+    pub beacon_node: Option<String>,
+}
+
+/// The times at which duties were last refreshed from a beacon node.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DutyRefreshTimes {
+    /// Unix timestamp, in seconds, at which attester duties were last downloaded.
+    pub attesters: Option<u64>,
+    /// Unix timestamp, in seconds, at which proposer duties were last downloaded.
+    pub proposers: Option<u64>,
+    /// Unix timestamp, in seconds, at which sync committee duties were last checked.
+    pub sync_committees: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedDutyKind {
+    Attestation,
+    Block,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RecordedDuty {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub id: u64,
+    pub kind: RecordedDutyKind,
+    pub slot: Slot,
+    pub validators: Vec<PublicKeyBytes>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ReplayDutyRequest {
+    /// The beacon node to publish the signed objects to, typically a mock. If `None` the signed
+    /// objects are not published.
+    #[serde(default)]
+    pub beacon_node: Option<SensitiveUrl>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ReplayedDuty {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub id: u64,
+    pub kind: RecordedDutyKind,
+    pub slot: Slot,
+    pub signatures: Vec<ReplayedSignature>,
+    /// The beacon node which the signed objects were published to.
+    pub beacon_node: Option<String>,
+    /// The error returned by the beacon node when publishing, if any.
+    pub publication_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ReplayedSignature {
+    pub pubkey: PublicKeyBytes,
+    pub signature: Option<SignatureBytes>,
+    /// The reason the signature could not be produced, if any.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignedMessageKind {
+    Block,
+    Attestation,
+    SyncCommitteeMessage,
+}
+
+/// A message signed by a validator, as recorded in the signing ledger of the validator client.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SignedMessageRecord {
+    pub kind: SignedMessageKind,
+    pub slot: Slot,
+    /// The time taken to produce the signature, in milliseconds.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub signing_latency_ms: u64,
+    /// Whether a beacon node accepted the message.
+    pub published: bool,
+    /// The beacon node which accepted the message, if any.
+    pub beacon_node: Option<String>,
+    /// The error returned when publishing the message, if any.
+    pub publication_error: Option<String>,
+    /// Unix timestamp, in seconds, at which the message was recorded.
+    pub timestamp: u64,
+}
+
+/// A source from which the fee recipient of a validator can be configured.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeRecipientSource {
+    /// The `suggested_fee_recipient` of the validator definition.
+    Definition,
+    /// The `--suggested-fee-recipient-file`, including its default.
+    File,
+    /// The `--suggested-fee-recipient` flag.
+    ProcessDefault,
+}
+
+/// The fee recipient configured for a validator by each source, and the one which is used.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FeeRecipientResolution {
+    pub pubkey: PublicKeyBytes,
+    /// The effective fee recipient, if any source configures one.
+    pub fee_recipient: Option<Address>,
+    /// The source of the effective fee recipient.
+    pub source: Option<FeeRecipientSource>,
+    pub definition: Option<Address>,
+    pub file: Option<Address>,
+    pub process_default: Option<Address>,
+}
+
+#[derive(Deserialize)]
+pub struct SigningHistoryQuery {
+    /// The maximum number of records to return, most recent first.
+    pub limit: Option<usize>,
+}
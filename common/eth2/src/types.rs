@@ -473,6 +473,12 @@ pub struct RandaoQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
@@ -601,7 +607,7 @@ where
     Ok(Vec::from(QueryVec::from(vec)))
 }
 
-fn option_query_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+pub(crate) fn option_query_vec<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
 where
     D: serde::Deserializer<'de>,
     T: FromStr,
@@ -907,6 +913,72 @@ pub struct SseChainReorg {
     pub execution_optimistic: bool,
 }
 
+/// A block on one side of a re-org.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseReorgBlock {
+    pub block: Hash256,
+    pub slot: Slot,
+    /// The proposer of the block, or `None` if the block could not be loaded.
+    pub proposer_index: Option<u64>,
+}
+
+/// The blocks between the common ancestor and one of the heads of a re-org, and the votes for them.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseReorgBranch {
+    /// Oldest first.
+    pub blocks: Vec<SseReorgBlock>,
+    /// The fork choice weight of the branch, in Gwei, including any proposer boost.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub weight: u64,
+    /// The number of validators whose latest message is for a block on the branch.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validators: u64,
+    /// The total effective balance of `validators`, in Gwei.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance: u64,
+}
+
+/// A more detailed counterpart of `SseChainReorg` which describes the weight of, and votes for,
+/// each side of the re-org.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseChainReorgDetail {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub depth: u64,
+    pub old_head_block: Hash256,
+    pub new_head_block: Hash256,
+    pub common_ancestor_block: Hash256,
+    pub common_ancestor_slot: Slot,
+    pub old_branch: SseReorgBranch,
+    pub new_branch: SseReorgBranch,
+    /// The weight of the new branch minus the weight of the old branch, in Gwei.
+    pub weight_difference: i64,
+    /// The block which received proposer boost when the head was computed, if any.
+    pub proposer_boost_block: Option<Hash256>,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub proposer_boost_score: u64,
+    pub execution_optimistic: bool,
+}
+
+/// Emitted when a re-org changes the dependent root of duties which may already have been fetched.
+///
+/// The `previous_duty_dependent_root` determines the attester duties for `epoch`, whilst the
+/// `current_duty_dependent_root` determines the proposer duties for `epoch` and the attester duties
+/// for `epoch + 1`. The old roots are those of the previous head, or `None` if the previous head
+/// does not share the duties.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseDependentRootChange {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    pub block: Hash256,
+    pub old_previous_duty_dependent_root: Option<Hash256>,
+    pub previous_duty_dependent_root: Hash256,
+    pub old_current_duty_dependent_root: Option<Hash256>,
+    pub current_duty_dependent_root: Hash256,
+    pub execution_optimistic: bool,
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct SseLateHead {
     pub slot: Slot,
@@ -922,6 +994,110 @@ pub struct SseLateHead {
     pub execution_optimistic: bool,
 }
 
+/// A bid received from a builder (e.g. a mev-boost relay) during block production.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseBuilderBid {
+    pub slot: Slot,
+    pub parent_hash: ExecutionBlockHash,
+    pub block_hash: ExecutionBlockHash,
+    pub builder_pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub value: Uint256,
+    /// The value of the locally built payload, if the local EL produced one.
+    pub local_block_value: Option<SseBlockValue>,
+    /// The redacted URL of the builder which provided the bid.
+    pub relay: String,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub relay_response_ms: u64,
+    /// `true` if the bid was chosen over the local payload.
+    pub selected: bool,
+}
+
+/// A wrapper to allow an optional `Uint256` to be serialized as a quoted decimal string.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(transparent)]
+pub struct SseBlockValue(#[serde(with = "serde_utils::quoted_u256")] pub Uint256);
+
+/// Reliability statistics for a builder relay.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RelayStats {
+    /// The redacted URL of the relay.
+    pub relay: String,
+    /// The number of `getHeader` requests sent to the relay.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub header_requests: u64,
+    /// The number of valid bids returned.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub bids: u64,
+    /// The number of requests for which the relay had no bid.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub no_bids: u64,
+    /// The number of bids which failed verification.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub invalid_bids: u64,
+    /// The number of requests which failed or timed out.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub errors: u64,
+    /// The number of times the relay's bid was used for a block proposal.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub selected: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub average_response_ms: u64,
+    /// The number of errors and invalid bids since the relay last responded successfully.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub consecutive_failures: u64,
+    /// `true` if the relay is not currently being queried because of repeated failures.
+    pub backed_off: bool,
+}
+
+/// The source of the payload used in a proposal.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalPayloadSource {
+    Local,
+    Builder,
+}
+
+/// The choice between the local payload and a builder bid when producing a blinded block.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct ProposalDecision {
+    pub slot: Slot,
+    pub parent_hash: ExecutionBlockHash,
+    /// The value of the locally built payload, if the local EL produced one.
+    pub local_block_value: Option<SseBlockValue>,
+    /// The value of the builder bid, if the builder returned one.
+    pub builder_bid_value: Option<SseBlockValue>,
+    /// The percentage by which the builder bid had to exceed the local block value.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub margin_percent: u64,
+    /// The payload which was used, or `None` if neither was usable.
+    pub source: Option<ProposalPayloadSource>,
+}
+
+/// A builder revealed the full payload for one of our signed blinded blocks.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SsePayloadRevealed {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub block_hash: ExecutionBlockHash,
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub value: Uint256,
+    pub relay: String,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub relay_response_ms: u64,
+}
+
+/// A block containing a builder payload was published to the network and imported.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SsePayloadDelivered {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub block_hash: ExecutionBlockHash,
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub value: Uint256,
+    pub relay: String,
+}
+
 #[superstruct(
     variants(V1, V2),
     variant_attributes(derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize))
@@ -1004,11 +1180,16 @@ pub enum EventKind<T: EthSpec> {
     Head(SseHead),
     VoluntaryExit(SignedVoluntaryExit),
     ChainReorg(SseChainReorg),
+    ChainReorgDetail(Box<SseChainReorgDetail>),
+    DependentRootChange(SseDependentRootChange),
     ContributionAndProof(Box<SignedContributionAndProof<T>>),
     LateHead(SseLateHead),
     #[cfg(feature = "lighthouse")]
     BlockReward(BlockReward),
     PayloadAttributes(VersionedSsePayloadAttributes),
+    BuilderBid(Box<SseBuilderBid>),
+    PayloadRevealed(SsePayloadRevealed),
+    PayloadDelivered(SsePayloadDelivered),
 }
 
 impl<T: EthSpec> EventKind<T> {
@@ -1020,11 +1201,16 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::VoluntaryExit(_) => "voluntary_exit",
             EventKind::FinalizedCheckpoint(_) => "finalized_checkpoint",
             EventKind::ChainReorg(_) => "chain_reorg",
+            EventKind::ChainReorgDetail(_) => "chain_reorg_detail",
+            EventKind::DependentRootChange(_) => "dependent_root_change",
             EventKind::ContributionAndProof(_) => "contribution_and_proof",
             EventKind::PayloadAttributes(_) => "payload_attributes",
             EventKind::LateHead(_) => "late_head",
             #[cfg(feature = "lighthouse")]
             EventKind::BlockReward(_) => "block_reward",
+            EventKind::BuilderBid(_) => "builder_bid",
+            EventKind::PayloadRevealed(_) => "payload_revealed",
+            EventKind::PayloadDelivered(_) => "payload_delivered",
         }
     }
 
@@ -1056,6 +1242,16 @@ impl<T: EthSpec> EventKind<T> {
             "chain_reorg" => Ok(EventKind::ChainReorg(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Chain Reorg: {:?}", e)),
             )?)),
+            "chain_reorg_detail" => Ok(EventKind::ChainReorgDetail(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Chain Reorg Detail: {:?}", e))
+                })?,
+            ))),
+            "dependent_root_change" => Ok(EventKind::DependentRootChange(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Dependent Root Change: {:?}", e))
+                })?,
+            )),
             "finalized_checkpoint" => Ok(EventKind::FinalizedCheckpoint(
                 serde_json::from_str(data).map_err(|e| {
                     ServerError::InvalidServerSentEvent(format!("Finalized Checkpoint: {:?}", e))
@@ -1086,6 +1282,21 @@ impl<T: EthSpec> EventKind<T> {
             "block_reward" => Ok(EventKind::BlockReward(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Reward: {:?}", e)),
             )?)),
+            "builder_bid" => Ok(EventKind::BuilderBid(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Builder Bid: {:?}", e))
+                })?,
+            ))),
+            "payload_revealed" => Ok(EventKind::PayloadRevealed(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Payload Revealed: {:?}", e))
+                })?,
+            )),
+            "payload_delivered" => Ok(EventKind::PayloadDelivered(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Payload Delivered: {:?}", e))
+                })?,
+            )),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -1109,11 +1320,16 @@ pub enum EventTopic {
     VoluntaryExit,
     FinalizedCheckpoint,
     ChainReorg,
+    ChainReorgDetail,
+    DependentRootChange,
     ContributionAndProof,
     LateHead,
     PayloadAttributes,
     #[cfg(feature = "lighthouse")]
     BlockReward,
+    BuilderBid,
+    PayloadRevealed,
+    PayloadDelivered,
 }
 
 impl FromStr for EventTopic {
@@ -1127,11 +1343,16 @@ impl FromStr for EventTopic {
             "voluntary_exit" => Ok(EventTopic::VoluntaryExit),
             "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
             "chain_reorg" => Ok(EventTopic::ChainReorg),
+            "chain_reorg_detail" => Ok(EventTopic::ChainReorgDetail),
+            "dependent_root_change" => Ok(EventTopic::DependentRootChange),
             "contribution_and_proof" => Ok(EventTopic::ContributionAndProof),
             "payload_attributes" => Ok(EventTopic::PayloadAttributes),
             "late_head" => Ok(EventTopic::LateHead),
             #[cfg(feature = "lighthouse")]
             "block_reward" => Ok(EventTopic::BlockReward),
+            "builder_bid" => Ok(EventTopic::BuilderBid),
+            "payload_revealed" => Ok(EventTopic::PayloadRevealed),
+            "payload_delivered" => Ok(EventTopic::PayloadDelivered),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1146,11 +1367,16 @@ impl fmt::Display for EventTopic {
             EventTopic::VoluntaryExit => write!(f, "voluntary_exit"),
             EventTopic::FinalizedCheckpoint => write!(f, "finalized_checkpoint"),
             EventTopic::ChainReorg => write!(f, "chain_reorg"),
+            EventTopic::ChainReorgDetail => write!(f, "chain_reorg_detail"),
+            EventTopic::DependentRootChange => write!(f, "dependent_root_change"),
             EventTopic::ContributionAndProof => write!(f, "contribution_and_proof"),
             EventTopic::PayloadAttributes => write!(f, "payload_attributes"),
             EventTopic::LateHead => write!(f, "late_head"),
             #[cfg(feature = "lighthouse")]
             EventTopic::BlockReward => write!(f, "block_reward"),
+            EventTopic::BuilderBid => write!(f, "builder_bid"),
+            EventTopic::PayloadRevealed => write!(f, "payload_revealed"),
+            EventTopic::PayloadDelivered => write!(f, "payload_delivered"),
         }
     }
 }
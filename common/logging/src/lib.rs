@@ -12,7 +12,9 @@ use std::time::{Duration, Instant};
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
 pub mod async_record;
+pub mod runtime_log_filter;
 mod sse_logging_components;
+pub use runtime_log_filter::{LogDirectives, RuntimeLogFilter};
 pub use sse_logging_components::SSELoggingComponents;
 
 /// The minimum interval between log messages indicating that a queue is full.
@@ -0,0 +1,487 @@
+//! Log filters which can be adjusted whilst Lighthouse is running, for example via the HTTP API.
+//!
+//! Filters are described by a comma-separated list of directives, each of which is either a
+//! level (applied to all modules without a more specific directive) or a `module=level` pair,
+//! e.g. `info,network=debug,beacon_chain::canonical_head=trace`. A module directive also applies
+//! to all of the module's sub-modules.
+//!
+//! For investigations which would otherwise flood the terminal, a debug capture writes all logs
+//! at or above some level to a separate file for a limited time, regardless of the directives.
+use parking_lot::RwLock;
+use slog::{Drain, Level, Never, OwnedKVList, Record};
+use std::fmt;
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The longest time for which a single debug capture may run.
+pub const MAX_DEBUG_CAPTURE_DURATION: Duration = Duration::from_secs(60 * 60);
+
+const DEBUG_CAPTURE_CHANNEL_SIZE: usize = 2048;
+
+/// Parses one of the level names accepted by `--debug-level`.
+pub fn parse_level(level: &str) -> Result<Level, String> {
+    match level {
+        "info" => Ok(Level::Info),
+        "debug" => Ok(Level::Debug),
+        "trace" => Ok(Level::Trace),
+        "warn" => Ok(Level::Warning),
+        "error" => Ok(Level::Error),
+        "crit" => Ok(Level::Critical),
+        unknown => Err(format!("Unknown log level: {}", unknown)),
+    }
+}
+
+/// The inverse of `parse_level`.
+pub fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+        Level::Warning => "warn",
+        Level::Error => "error",
+        Level::Critical => "crit",
+    }
+}
+
+/// A parsed set of filter directives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogDirectives {
+    default: Level,
+    /// Ordered from the longest module path to the shortest, so that the first matching
+    /// directive is the most specific.
+    modules: Vec<(String, Level)>,
+}
+
+impl LogDirectives {
+    /// Returns directives which apply `level` to all modules.
+    pub fn new(level: Level) -> Self {
+        Self {
+            default: level,
+            modules: vec![],
+        }
+    }
+
+    /// Returns the least severe level which is logged for `module`.
+    pub fn level_for(&self, module: &str) -> Level {
+        self.modules
+            .iter()
+            .find(|(prefix, _)| {
+                module
+                    .strip_prefix(prefix.as_str())
+                    .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// Returns `true` if `record` passes the filter.
+    pub fn enabled(&self, record: &Record) -> bool {
+        record.level().is_at_least(self.level_for(record.module()))
+    }
+}
+
+/// Parses directives such as `info,network=debug`. The default level is `info` if no bare level
+/// is given, and later directives for the same module replace earlier ones.
+impl FromStr for LogDirectives {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut directives = Self::new(Level::Info);
+        for directive in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    let module = module.trim();
+                    if module.is_empty() {
+                        return Err(format!("Missing module in directive: {}", directive));
+                    }
+                    let level = parse_level(level.trim())?;
+                    directives
+                        .modules
+                        .retain(|(existing, _)| existing != module);
+                    directives.modules.push((module.to_string(), level));
+                }
+                None => directives.default = parse_level(directive)?,
+            }
+        }
+        directives
+            .modules
+            .sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        Ok(directives)
+    }
+}
+
+impl fmt::Display for LogDirectives {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", level_name(self.default))?;
+        for (module, level) in &self.modules {
+            write!(f, ",{}={}", module, level_name(*level))?;
+        }
+        Ok(())
+    }
+}
+
+/// The state of an active debug capture.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugCaptureStatus {
+    pub path: PathBuf,
+    pub level: Level,
+    pub remaining: Duration,
+}
+
+struct DebugCapture {
+    path: PathBuf,
+    level: Level,
+    expires_at: Instant,
+    drain: slog_async::Async,
+}
+
+struct Inner {
+    directives: LogDirectives,
+    /// The directives of the log file, if it is filtered by `self`.
+    logfile_directives: Option<LogDirectives>,
+    debug_capture: Option<DebugCapture>,
+}
+
+/// A handle to the runtime-adjustable filter of a logger, which may be cloned and shared between
+/// threads.
+#[derive(Clone)]
+pub struct RuntimeLogFilter {
+    inner: Arc<AssertUnwindSafe<RwLock<Inner>>>,
+    /// The directory to which debug captures are written, if any.
+    capture_dir: Option<PathBuf>,
+}
+
+impl RuntimeLogFilter {
+    pub fn new(directives: LogDirectives, capture_dir: Option<PathBuf>) -> Self {
+        Self {
+            inner: Arc::new(AssertUnwindSafe(RwLock::new(Inner {
+                directives,
+                logfile_directives: None,
+                debug_capture: None,
+            }))),
+            capture_dir,
+        }
+    }
+
+    /// Wraps `drain` so that it only receives the records which pass the current directives.
+    pub fn filter<D: Drain>(&self, drain: D) -> FilteredDrain<D> {
+        FilteredDrain {
+            drain,
+            filter: self.clone(),
+            logfile: false,
+        }
+    }
+
+    /// Wraps the log file `drain` so that it only receives the records which pass the log file
+    /// directives, which are initially `directives`.
+    pub fn filter_logfile<D: Drain>(
+        &self,
+        drain: D,
+        directives: LogDirectives,
+    ) -> FilteredDrain<D> {
+        self.inner.write().logfile_directives = Some(directives);
+        FilteredDrain {
+            drain,
+            filter: self.clone(),
+            logfile: true,
+        }
+    }
+
+    /// Returns a drain which writes to the active debug capture, if any.
+    ///
+    /// This drain should be combined with the unfiltered root drain, e.g. via
+    /// `slog::Duplicate`.
+    pub fn debug_capture_drain(&self) -> DebugCaptureDrain {
+        DebugCaptureDrain {
+            filter: self.clone(),
+        }
+    }
+
+    pub fn directives(&self) -> LogDirectives {
+        self.inner.read().directives.clone()
+    }
+
+    /// Returns the directives of the log file, or `None` if the log file is not filtered by
+    /// `self`.
+    pub fn logfile_directives(&self) -> Option<LogDirectives> {
+        self.inner.read().logfile_directives.clone()
+    }
+
+    /// Replaces the directives of the terminal output and the log file, taking effect for the next
+    /// record which is logged.
+    pub fn set_directives(&self, directives: LogDirectives) {
+        let mut inner = self.inner.write();
+        if inner.logfile_directives.is_some() {
+            inner.logfile_directives = Some(directives.clone());
+        }
+        inner.directives = directives;
+    }
+
+    /// Returns the state of the debug capture, or `None` if there is no active capture.
+    pub fn debug_capture(&self) -> Option<DebugCaptureStatus> {
+        self.stop_expired_debug_capture();
+        self.inner
+            .read()
+            .debug_capture
+            .as_ref()
+            .map(|capture| DebugCaptureStatus {
+                path: capture.path.clone(),
+                level: capture.level,
+                remaining: capture.expires_at.saturating_duration_since(Instant::now()),
+            })
+    }
+
+    /// Starts writing all logs at or above `level` to a new file for `duration`, replacing any
+    /// active capture. Returns the path of the new file.
+    pub fn start_debug_capture(&self, duration: Duration, level: Level) -> Result<PathBuf, String> {
+        if duration.is_zero() || duration > MAX_DEBUG_CAPTURE_DURATION {
+            return Err(format!(
+                "debug capture duration must be between 1 and {} seconds",
+                MAX_DEBUG_CAPTURE_DURATION.as_secs()
+            ));
+        }
+        let capture_dir = self
+            .capture_dir
+            .as_ref()
+            .ok_or("debug capture requires a log file directory")?;
+        create_dir_all(capture_dir)
+            .map_err(|e| format!("Unable to create {:?}: {:?}", capture_dir, e))?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = capture_dir.join(format!("debug-capture-{}.log", timestamp));
+        let file =
+            open_capture_file(&path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+
+        let decorator = slog_term::PlainSyncDecorator::new(file);
+        let drain = slog_term::FullFormat::new(decorator).build().fuse();
+        let drain = slog_async::Async::new(drain)
+            .chan_size(DEBUG_CAPTURE_CHANNEL_SIZE)
+            .build();
+
+        let previous = self.inner.write().debug_capture.replace(DebugCapture {
+            path: path.clone(),
+            level,
+            expires_at: Instant::now() + duration,
+            drain,
+        });
+        // Flush the previous capture outside of the lock.
+        drop(previous);
+
+        Ok(path)
+    }
+
+    /// Ends the active debug capture, if any.
+    pub fn stop_debug_capture(&self) {
+        let previous = self.inner.write().debug_capture.take();
+        drop(previous);
+    }
+
+    fn stop_expired_debug_capture(&self) {
+        let previous = {
+            let mut inner = self.inner.write();
+            match &inner.debug_capture {
+                Some(capture) if capture.expires_at <= Instant::now() => inner.debug_capture.take(),
+                _ => None,
+            }
+        };
+        drop(previous);
+    }
+}
+
+#[cfg(unix)]
+fn open_capture_file(path: &PathBuf) -> std::io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    // Restrict permissions in line with the regular log files.
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn open_capture_file(path: &PathBuf) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// A drain which only passes on the records which pass the directives of a `RuntimeLogFilter`.
+pub struct FilteredDrain<D> {
+    drain: D,
+    filter: RuntimeLogFilter,
+    /// Use the log file directives rather than those of the terminal output.
+    logfile: bool,
+}
+
+impl<D: Drain> Drain for FilteredDrain<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let enabled = {
+            let inner = self.filter.inner.read();
+            match &inner.logfile_directives {
+                Some(logfile_directives) if self.logfile => logfile_directives.enabled(record),
+                _ => inner.directives.enabled(record),
+            }
+        };
+        if enabled {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// A drain which writes records to the debug capture of a `RuntimeLogFilter`, if one is active.
+pub struct DebugCaptureDrain {
+    filter: RuntimeLogFilter,
+}
+
+impl Drain for DebugCaptureDrain {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let expired = match &self.filter.inner.read().debug_capture {
+            Some(capture) if Instant::now() < capture.expires_at => {
+                if record.level().is_at_least(capture.level) {
+                    // A full channel drops the record rather than blocking the logging thread.
+                    let _ = capture.drain.log(record, values);
+                }
+                false
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            self.filter.stop_expired_debug_capture();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slog::{debug, info, o, trace, Logger};
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_directives() {
+        let directives = LogDirectives::from_str("debug, network=trace,network::rpc=warn").unwrap();
+        assert_eq!(directives.level_for("beacon_chain"), Level::Debug);
+        assert_eq!(directives.level_for("network"), Level::Trace);
+        assert_eq!(directives.level_for("network::service"), Level::Trace);
+        assert_eq!(
+            directives.level_for("network::rpc::handler"),
+            Level::Warning
+        );
+        // Module paths only match on `::` boundaries.
+        assert_eq!(directives.level_for("network_utils"), Level::Debug);
+        assert_eq!(
+            directives.to_string(),
+            "debug,network::rpc=warn,network=trace"
+        );
+
+        let directives = LogDirectives::from_str("network=debug,network=error").unwrap();
+        assert_eq!(directives.to_string(), "info,network=error");
+
+        assert!(LogDirectives::from_str("verbose").is_err());
+        assert!(LogDirectives::from_str("=debug").is_err());
+        assert!(LogDirectives::from_str("network=loud").is_err());
+    }
+
+    /// Collects the messages of the records it receives.
+    #[derive(Clone, Default)]
+    struct MessageDrain(Arc<parking_lot::Mutex<Vec<String>>>);
+
+    impl Drain for MessageDrain {
+        type Ok = ();
+        type Err = Never;
+
+        fn log(&self, record: &Record, _: &OwnedKVList) -> Result<(), Never> {
+            self.0.lock().push(record.msg().to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn logfile_filter() {
+        let filter = RuntimeLogFilter::new(LogDirectives::new(Level::Info), None);
+        let terminal = MessageDrain::default();
+        let logfile = MessageDrain::default();
+        let log = Logger::root(
+            slog::Duplicate::new(
+                filter.filter(terminal.clone()),
+                filter.filter_logfile(logfile.clone(), LogDirectives::new(Level::Debug)),
+            )
+            .fuse(),
+            o!(),
+        );
+
+        // The log file has its own initial directives.
+        debug!(log, "first");
+        assert!(terminal.0.lock().is_empty());
+        assert_eq!(*logfile.0.lock(), vec!["first".to_string()]);
+
+        // Replacing the directives applies to both outputs.
+        filter.set_directives(LogDirectives::new(Level::Warning));
+        assert_eq!(
+            filter.logfile_directives(),
+            Some(LogDirectives::new(Level::Warning))
+        );
+        info!(log, "second");
+        assert!(terminal.0.lock().is_empty());
+        assert_eq!(*logfile.0.lock(), vec!["first".to_string()]);
+
+        filter.set_directives(LogDirectives::new(Level::Trace));
+        trace!(log, "third");
+        assert_eq!(*terminal.0.lock(), vec!["third".to_string()]);
+        assert_eq!(
+            *logfile.0.lock(),
+            vec!["first".to_string(), "third".to_string()]
+        );
+    }
+
+    #[test]
+    fn debug_capture() {
+        let dir = TempDir::new().unwrap();
+        let filter = RuntimeLogFilter::new(LogDirectives::new(Level::Info), None);
+        assert!(filter
+            .start_debug_capture(Duration::from_secs(60), Level::Debug)
+            .is_err());
+
+        let filter = RuntimeLogFilter::new(
+            LogDirectives::new(Level::Info),
+            Some(dir.path().to_path_buf()),
+        );
+        assert!(filter
+            .start_debug_capture(MAX_DEBUG_CAPTURE_DURATION * 2, Level::Debug)
+            .is_err());
+
+        let log = Logger::root(filter.debug_capture_drain().fuse(), o!());
+        let path = filter
+            .start_debug_capture(Duration::from_secs(60), Level::Debug)
+            .unwrap();
+        assert_eq!(filter.debug_capture().unwrap().path, path);
+
+        debug!(log, "captured message");
+        trace!(log, "ignored message");
+        filter.stop_debug_capture();
+        info!(log, "message after capture");
+        assert_eq!(filter.debug_capture(), None);
+
+        let contents = fs::read_to_string(path).unwrap();
+        assert!(contents.contains("captured message"));
+        assert!(!contents.contains("ignored message"));
+        assert!(!contents.contains("message after capture"));
+    }
+}
@@ -0,0 +1,481 @@
+//! A minimal OpenTelemetry trace exporter, used to trace block proposals across the validator
+//! client and beacon node.
+//!
+//! Spans are exported to a collector via OTLP/HTTP using the JSON encoding, and trace context is
+//! propagated between processes using the W3C `traceparent` header.
+use pretty_reqwest_error::PrettyReqwestError;
+use reqwest::StatusCode;
+use ring::rand::{SecureRandom, SystemRandom};
+use sensitive_url::SensitiveUrl;
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+/// The W3C trace context header.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// The number of finished spans which may be awaiting export before new spans are dropped.
+const SPAN_CHANNEL_SIZE: usize = 1_024;
+/// The maximum number of spans sent to the collector in one request.
+const MAX_EXPORT_BATCH_SIZE: usize = 128;
+/// The time to wait for further spans before exporting a batch, so that the spans of a single
+/// proposal are usually exported together.
+const EXPORT_BATCH_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug)]
+pub enum Error {
+    /// The `reqwest` client raised an error.
+    HttpClient(PrettyReqwestError),
+    /// The collector endpoint cannot be used as a base URL.
+    InvalidUrl(SensitiveUrl),
+    /// The collector did not accept the exported spans.
+    StatusCode(StatusCode),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        Error::HttpClient(error.into())
+    }
+}
+
+/// The identity of a span, which is sufficient to create children of it in another process.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub span_id: [u8; 8],
+}
+
+impl TraceContext {
+    /// Returns the context of a span which begins a new trace.
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: random_id(),
+            span_id: random_id(),
+        }
+    }
+
+    /// Returns the context of a new span within the same trace.
+    pub fn new_child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: random_id(),
+        }
+    }
+
+    /// Returns the value of the `traceparent` header, with the sampled flag set.
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "00-{}-{}-01",
+            hex_encode(&self.trace_id),
+            hex_encode(&self.span_id)
+        )
+    }
+
+    /// Parses a `traceparent` header, returning `None` if it is malformed or not sampled.
+    pub fn from_traceparent(header: &str) -> Option<Self> {
+        let mut parts = header.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = hex_decode(parts.next()?)?;
+        let span_id = hex_decode(parts.next()?)?;
+        let [flags] = hex_decode::<1>(parts.next()?)?;
+
+        // Future versions may append fields, but version 00 has exactly four.
+        if version.len() != 2
+            || version == "ff"
+            || (version == "00" && parts.next().is_some())
+            || flags & 0x01 == 0
+            || trace_id == [0; 16]
+            || span_id == [0; 8]
+        {
+            return None;
+        }
+
+        Some(Self { trace_id, span_id })
+    }
+}
+
+/// A span which has finished and is awaiting export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanData {
+    pub name: &'static str,
+    pub context: TraceContext,
+    pub parent_span_id: Option<[u8; 8]>,
+    pub start_time: SystemTime,
+    pub end_time: SystemTime,
+    pub attributes: Vec<(&'static str, String)>,
+    pub error: Option<String>,
+}
+
+/// Records spans and passes them to an `OtlpExportTask`.
+///
+/// Cloning is cheap. Spans are dropped rather than delaying the caller if the export task falls
+/// behind.
+#[derive(Clone)]
+pub struct OtlpExporter {
+    tx: mpsc::Sender<SpanData>,
+}
+
+impl OtlpExporter {
+    /// Creates an exporter for the collector at `endpoint` (e.g. `http://localhost:4318`).
+    ///
+    /// The returned task must be spawned for spans to be exported.
+    pub fn new(endpoint: SensitiveUrl, service_name: &str) -> (Self, OtlpExportTask) {
+        let (exporter, rx) = Self::channel();
+        let task = OtlpExportTask {
+            rx,
+            client: reqwest::Client::new(),
+            endpoint,
+            service_name: service_name.to_string(),
+        };
+        (exporter, task)
+    }
+
+    /// Creates an exporter whose finished spans are sent to the returned receiver rather than a
+    /// collector, e.g. for inspection in tests.
+    pub fn channel() -> (Self, mpsc::Receiver<SpanData>) {
+        let (tx, rx) = mpsc::channel(SPAN_CHANNEL_SIZE);
+        (Self { tx }, rx)
+    }
+
+    /// Starts a span which is a child of `parent`, or the root of a new trace if `parent` is
+    /// `None`.
+    ///
+    /// The span ends when it is dropped.
+    pub fn start_span(&self, name: &'static str, parent: Option<TraceContext>) -> Span {
+        let context = parent.map_or_else(TraceContext::new_root, |parent| parent.new_child());
+        Span {
+            tx: self.tx.clone(),
+            data: SpanData {
+                name,
+                context,
+                parent_span_id: parent.map(|parent| parent.span_id),
+                start_time: SystemTime::now(),
+                end_time: SystemTime::now(),
+                attributes: vec![],
+                error: None,
+            },
+        }
+    }
+}
+
+/// A span which is in progress, and is exported when dropped.
+pub struct Span {
+    tx: mpsc::Sender<SpanData>,
+    data: SpanData,
+}
+
+impl Span {
+    pub fn context(&self) -> TraceContext {
+        self.data.context
+    }
+
+    /// Returns the exporter of this span, which may be used to start children of it later.
+    pub fn exporter(&self) -> OtlpExporter {
+        OtlpExporter {
+            tx: self.tx.clone(),
+        }
+    }
+
+    /// Starts a span which is a child of this one.
+    pub fn child(&self, name: &'static str) -> Span {
+        self.exporter().start_span(name, Some(self.context()))
+    }
+
+    pub fn set_attribute(&mut self, key: &'static str, value: impl ToString) {
+        self.data.attributes.push((key, value.to_string()));
+    }
+
+    /// Marks the span as having failed.
+    pub fn set_error(&mut self, error: impl ToString) {
+        self.data.error = Some(error.to_string());
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        self.data.end_time = SystemTime::now();
+        let _ = self.tx.try_send(self.data.clone());
+    }
+}
+
+/// Sends the spans recorded by an `OtlpExporter` to the collector.
+pub struct OtlpExportTask {
+    rx: mpsc::Receiver<SpanData>,
+    client: reqwest::Client,
+    endpoint: SensitiveUrl,
+    service_name: String,
+}
+
+impl OtlpExportTask {
+    /// Exports spans in batches until all of the `OtlpExporter`s and `Span`s have been dropped.
+    ///
+    /// Failed batches are passed to `on_error` and are not retried.
+    pub async fn run(mut self, on_error: impl Fn(Error)) {
+        while let Some(span) = self.rx.recv().await {
+            let mut batch = vec![span];
+            let deadline = tokio::time::Instant::now() + EXPORT_BATCH_DELAY;
+            while batch.len() < MAX_EXPORT_BATCH_SIZE {
+                match tokio::time::timeout_at(deadline, self.rx.recv()).await {
+                    Ok(Some(span)) => batch.push(span),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+
+            if let Err(e) = self.export(&batch).await {
+                on_error(e);
+            }
+        }
+    }
+
+    async fn export(&self, spans: &[SpanData]) -> Result<(), Error> {
+        let mut url = self.endpoint.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.endpoint.clone()))?
+            .pop_if_empty()
+            .push("v1")
+            .push("traces");
+
+        let response = self
+            .client
+            .post(url)
+            .json(&export_request(&self.service_name, spans))
+            .send()
+            .await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(Error::StatusCode(status))
+        }
+    }
+}
+
+/*
+ * The subset of the OTLP JSON encoding used by `OtlpExportTask`.
+ */
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportTraceServiceRequest {
+    resource_spans: Vec<ResourceSpans>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceSpans {
+    resource: Resource,
+    scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Serialize)]
+struct Resource {
+    attributes: Vec<KeyValue>,
+}
+
+#[derive(Serialize)]
+struct ScopeSpans {
+    scope: Scope,
+    spans: Vec<OtlpSpan>,
+}
+
+#[derive(Serialize)]
+struct Scope {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpSpan {
+    trace_id: String,
+    span_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_span_id: Option<String>,
+    name: &'static str,
+    kind: u8,
+    start_time_unix_nano: String,
+    end_time_unix_nano: String,
+    attributes: Vec<KeyValue>,
+    status: Status,
+}
+
+#[derive(Serialize)]
+struct KeyValue {
+    key: String,
+    value: AnyValue,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AnyValue {
+    string_value: String,
+}
+
+#[derive(Serialize)]
+struct Status {
+    code: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+/// `SPAN_KIND_INTERNAL`.
+const SPAN_KIND_INTERNAL: u8 = 1;
+/// `STATUS_CODE_OK`.
+const STATUS_CODE_OK: u8 = 1;
+/// `STATUS_CODE_ERROR`.
+const STATUS_CODE_ERROR: u8 = 2;
+
+fn export_request(service_name: &str, spans: &[SpanData]) -> ExportTraceServiceRequest {
+    let key_value = |key: &str, value: String| KeyValue {
+        key: key.to_string(),
+        value: AnyValue {
+            string_value: value,
+        },
+    };
+    let unix_nanos = |time: SystemTime| {
+        time.duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_nanos())
+            .to_string()
+    };
+
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Resource {
+                attributes: vec![key_value("service.name", service_name.to_string())],
+            },
+            scope_spans: vec![ScopeSpans {
+                scope: Scope { name: "lighthouse" },
+                spans: spans
+                    .iter()
+                    .map(|span| OtlpSpan {
+                        trace_id: hex_encode(&span.context.trace_id),
+                        span_id: hex_encode(&span.context.span_id),
+                        parent_span_id: span.parent_span_id.map(|id| hex_encode(&id)),
+                        name: span.name,
+                        kind: SPAN_KIND_INTERNAL,
+                        start_time_unix_nano: unix_nanos(span.start_time),
+                        end_time_unix_nano: unix_nanos(span.end_time),
+                        attributes: span
+                            .attributes
+                            .iter()
+                            .map(|(key, value)| key_value(key, value.clone()))
+                            .collect(),
+                        status: Status {
+                            code: if span.error.is_some() {
+                                STATUS_CODE_ERROR
+                            } else {
+                                STATUS_CODE_OK
+                            },
+                            message: span.error.clone(),
+                        },
+                    })
+                    .collect(),
+            }],
+        }],
+    }
+}
+
+fn random_id<const N: usize>() -> [u8; N] {
+    let mut id = [0; N];
+    // An all-zero id is invalid, although it is vanishingly unlikely.
+    while id == [0; N] {
+        SystemRandom::new()
+            .fill(&mut id)
+            .expect("system randomness should be available");
+    }
+    id
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hex_decode<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != N * 2 || !s.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut bytes = [0; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traceparent_round_trip() {
+        let context = TraceContext::new_root();
+        let header = context.to_traceparent();
+        assert_eq!(header.len(), 55);
+        assert_eq!(TraceContext::from_traceparent(&header), Some(context));
+
+        let child = context.new_child();
+        assert_eq!(child.trace_id, context.trace_id);
+        assert_ne!(child.span_id, context.span_id);
+    }
+
+    #[test]
+    fn parse_traceparent() {
+        let context = TraceContext::from_traceparent(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .unwrap();
+        assert_eq!(
+            hex_encode(&context.trace_id),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(hex_encode(&context.span_id), "00f067aa0ba902b7");
+
+        for invalid in [
+            "",
+            // Not sampled.
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00",
+            // Invalid version.
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            // All-zero trace id.
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            // Short span id.
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902-01",
+            // Trailing field in version 00.
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-00",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-+0f067aa0ba902b7-01",
+        ] {
+            assert_eq!(TraceContext::from_traceparent(invalid), None, "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn spans_are_recorded_on_drop() {
+        let (exporter, mut task) = OtlpExporter::new(
+            SensitiveUrl::parse("http://localhost:4318").unwrap(),
+            "test",
+        );
+
+        let root = exporter.start_span("root", None);
+        let mut child = root.child("child");
+        child.set_attribute("slot", 42);
+        child.set_error("failed");
+        drop(child);
+        drop(root);
+
+        let child = task.rx.try_recv().unwrap();
+        let root = task.rx.try_recv().unwrap();
+        assert_eq!(root.parent_span_id, None);
+        assert_eq!(child.context.trace_id, root.context.trace_id);
+        assert_eq!(child.parent_span_id, Some(root.context.span_id));
+        assert!(child.start_time <= child.end_time);
+
+        let request = serde_json::to_value(export_request("test", &[child])).unwrap();
+        let span = &request["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(span["name"], "child");
+        assert_eq!(
+            span["parentSpanId"],
+            hex_encode(&root.context.span_id).as_str()
+        );
+        assert_eq!(span["attributes"][0]["key"], "slot");
+        assert_eq!(span["attributes"][0]["value"]["stringValue"], "42");
+        assert_eq!(span["status"]["code"], STATUS_CODE_ERROR);
+    }
+}
@@ -1,12 +1,14 @@
 mod metrics;
+mod shutdown_tracker;
 pub mod test_utils;
 
 use futures::channel::mpsc::Sender;
 use futures::prelude::*;
 use slog::{crit, debug, o, trace};
-use std::sync::Weak;
+use std::sync::{Arc, Weak};
 use tokio::runtime::{Handle, Runtime};
 
+pub use shutdown_tracker::{ShutdownGuard, ShutdownTracker};
 pub use tokio::task::JoinHandle;
 
 /// Provides a reason when Lighthouse is shut down.
@@ -79,6 +81,8 @@ pub struct TaskExecutor {
     ///
     /// The task must provide a reason for shutting down.
     signal_tx: Sender<ShutdownReason>,
+    /// Tracks tasks which keep running after the exit signal in order to shut down gracefully.
+    shutdown_tracker: Arc<ShutdownTracker>,
 
     log: slog::Logger,
 }
@@ -101,16 +105,36 @@ impl TaskExecutor {
             handle_provider: handle.into(),
             exit,
             signal_tx,
+            shutdown_tracker: Arc::new(ShutdownTracker::default()),
             log,
         }
     }
 
+    /// Use `shutdown_tracker` to track the tasks spawned with `spawn_graceful`, so that they can
+    /// be waited for before the runtime is shut down.
+    pub fn with_shutdown_tracker(mut self, shutdown_tracker: Arc<ShutdownTracker>) -> Self {
+        self.shutdown_tracker = shutdown_tracker;
+        self
+    }
+
+    /// Returns a clone of `self` whose tasks are cancelled when `exit` fires, rather than with the
+    /// exit signal of the runtime.
+    ///
+    /// Used for services which must keep running while another service shuts down gracefully.
+    pub fn with_exit(&self, exit: exit_future::Exit) -> Self {
+        TaskExecutor {
+            exit,
+            ..self.clone()
+        }
+    }
+
     /// Clones the task executor adding a service name.
     pub fn clone_with_name(&self, service_name: String) -> Self {
         TaskExecutor {
             handle_provider: self.handle_provider.clone(),
             exit: self.exit.clone(),
             signal_tx: self.signal_tx.clone(),
+            shutdown_tracker: self.shutdown_tracker.clone(),
             log: self.log.new(o!("service" => service_name)),
         }
     }
@@ -184,6 +208,26 @@ impl TaskExecutor {
         }
     }
 
+    /// Spawn a future on the tokio runtime which keeps running after the exit signal fires, so
+    /// that it can shut down gracefully.
+    ///
+    /// The task is responsible for watching `Self::exit` and returning promptly once it fires. The
+    /// runtime is not shut down until the task completes or a timeout is reached.
+    pub fn spawn_graceful(
+        &self,
+        task: impl Future<Output = ()> + Send + 'static,
+        name: &'static str,
+    ) {
+        let guard = self.shutdown_tracker.guard();
+        self.spawn_without_exit(
+            async move {
+                task.await;
+                drop(guard);
+            },
+            name,
+        )
+    }
+
     /// Spawn a future on the tokio runtime. This function does not wrap the task in an `exit_future::Exit`
     /// like [spawn](#method.spawn).
     /// The caller of this function is responsible for wrapping up the task with an `exit_future::Exit` to
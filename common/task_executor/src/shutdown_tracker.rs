@@ -0,0 +1,47 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Tracks the tasks which must be allowed to finish after the exit signal fires, so that the
+/// runtime is not shut down while they are cleaning up.
+#[derive(Default)]
+pub struct ShutdownTracker {
+    active_tasks: AtomicUsize,
+    notify: Notify,
+}
+
+impl ShutdownTracker {
+    /// Returns a guard which keeps the tracker waiting until it is dropped.
+    pub fn guard(self: &Arc<Self>) -> ShutdownGuard {
+        self.active_tasks.fetch_add(1, Ordering::SeqCst);
+        ShutdownGuard(self.clone())
+    }
+
+    /// Returns the number of guards which have not been dropped.
+    pub fn active_tasks(&self) -> usize {
+        self.active_tasks.load(Ordering::SeqCst)
+    }
+
+    /// Waits until all guards have been dropped.
+    pub async fn wait(&self) {
+        loop {
+            // Register for a notification before checking the count so a guard dropped in between
+            // is not missed.
+            let notified = self.notify.notified();
+            if self.active_tasks() == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Held by a task which must finish before the runtime is shut down.
+pub struct ShutdownGuard(Arc<ShutdownTracker>);
+
+impl Drop for ShutdownGuard {
+    fn drop(&mut self) {
+        self.0.active_tasks.fetch_sub(1, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
+    }
+}
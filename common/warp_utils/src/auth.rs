@@ -0,0 +1,54 @@
+//! Filters for endpoints which are only served to clients that know a secret token.
+
+use warp::filters::BoxedFilter;
+use warp::Filter;
+
+/// Returns a filter which rejects requests unless their `Authorization` header is `Bearer
+/// <token>`.
+///
+/// If `token` is `None` the endpoint is disabled and all requests are rejected as not found,
+/// using `disabled_message`.
+pub fn bearer_token_filter(
+    token: Option<String>,
+    disabled_message: &'static str,
+) -> BoxedFilter<()> {
+    warp::any()
+        .map(move || token.clone())
+        .and_then(move |token: Option<String>| async move {
+            token.ok_or_else(|| crate::reject::custom_not_found(disabled_message.to_string()))
+        })
+        .and(warp::header::<String>("Authorization"))
+        .and_then(|token: String, header: String| async move {
+            if is_bearer_token(&header, &token) {
+                Ok(())
+            } else {
+                Err(crate::reject::invalid_auth(header))
+            }
+        })
+        .untuple_one()
+        .boxed()
+}
+
+/// Returns `true` if `header` is `Bearer <token>`.
+///
+/// The token is compared in constant time so that the response time does not reveal how much of
+/// the token was guessed correctly.
+fn is_bearer_token(header: &str, token: &str) -> bool {
+    header.strip_prefix("Bearer ").map_or(false, |candidate| {
+        ring::constant_time::verify_slices_are_equal(candidate.as_bytes(), token.as_bytes()).is_ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bearer_token_comparison() {
+        assert!(is_bearer_token("Bearer secret", "secret"));
+        assert!(!is_bearer_token("Bearer secreT", "secret"));
+        assert!(!is_bearer_token("Bearer secret2", "secret"));
+        assert!(!is_bearer_token("Basic secret", "secret"));
+        assert!(!is_bearer_token("secret", "secret"));
+    }
+}
@@ -1,7 +1,9 @@
 //! This crate contains functions that are common across multiple `warp` HTTP servers in the
 //! Lighthouse project. E.g., the `http_api` and `http_metrics` crates.
 
+pub mod auth;
 pub mod cors;
+pub mod log_filter;
 pub mod metrics;
 pub mod query;
 pub mod reject;
@@ -0,0 +1,58 @@
+//! Handles `POST lighthouse/logging` requests, which are shared by the beacon node and validator
+//! client APIs.
+use eth2::lighthouse::{DebugCaptureStatus, LoggingRequest, LoggingStatus};
+use logging::runtime_log_filter::{level_name, parse_level};
+use logging::{LogDirectives, RuntimeLogFilter};
+use slog::Level;
+use std::time::Duration;
+
+/// Applies the changes in `request` to `filter`, returning the resulting state.
+///
+/// The request is validated in full before anything is changed.
+pub fn update(
+    filter: &RuntimeLogFilter,
+    request: LoggingRequest,
+) -> Result<LoggingStatus, warp::Rejection> {
+    let directives = request
+        .filter
+        .map(|directives| directives.parse::<LogDirectives>())
+        .transpose()
+        .map_err(crate::reject::custom_bad_request)?;
+
+    if let Some(debug_capture) = request.debug_capture {
+        if debug_capture.duration_secs == 0 {
+            filter.stop_debug_capture();
+        } else {
+            let level = debug_capture
+                .level
+                .as_deref()
+                .map_or(Ok(Level::Debug), parse_level)
+                .map_err(crate::reject::custom_bad_request)?;
+            filter
+                .start_debug_capture(Duration::from_secs(debug_capture.duration_secs), level)
+                .map_err(crate::reject::custom_bad_request)?;
+        }
+    }
+
+    if let Some(directives) = directives {
+        filter.set_directives(directives);
+    }
+
+    Ok(status(filter))
+}
+
+pub fn status(filter: &RuntimeLogFilter) -> LoggingStatus {
+    LoggingStatus {
+        filter: filter.directives().to_string(),
+        logfile_filter: filter
+            .logfile_directives()
+            .map(|directives| directives.to_string()),
+        debug_capture: filter
+            .debug_capture()
+            .map(|debug_capture| DebugCaptureStatus {
+                path: debug_capture.path,
+                level: level_name(debug_capture.level).to_string(),
+                remaining_secs: debug_capture.remaining.as_secs(),
+            }),
+    }
+}
@@ -1,5 +1,6 @@
 use eth2::lighthouse::{ProcessHealth, SystemHealth};
 use lighthouse_metrics::*;
+use warp::http::{Method, StatusCode};
 
 lazy_static::lazy_static! {
     pub static ref PROCESS_NUM_THREADS: Result<IntGauge> = try_create_int_gauge(
@@ -76,6 +77,105 @@ lazy_static::lazy_static! {
 
     pub static ref BOOT_TIME: Result<IntGauge> =
         try_create_int_gauge("misc_node_boot_ts_seconds", "Boot time as unix epoch timestamp");
+
+    pub static ref HTTP_RESPONSE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "http_response_times",
+        "Duration to respond to HTTP requests per server, method, route and status class",
+        &["server", "method", "route", "status"]
+    );
+}
+
+/// The label given to requests outside of the known prefixes of a server, to requests which are
+/// rejected with a client error and to requests with an unknown method.
+pub const OTHER_ROUTE: &str = "other";
+/// Replaces path segments which are likely to be parameters, such as slots, roots and pubkeys.
+pub const ROUTE_PARAM: &str = "{param}";
+/// Segments longer than this are always considered to be parameters.
+const MAX_ROUTE_SEGMENT_LEN: usize = 32;
+/// The methods which are given a label of their own.
+const KNOWN_METHODS: &[Method] = &[
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+    Method::HEAD,
+    Method::OPTIONS,
+];
+
+/// Creates a `warp` logging wrapper which records the time taken to respond to each request in
+/// the `HTTP_RESPONSE_TIMES` histogram, labelled with `server`.
+///
+/// Paths are normalized by `route_label` so that the number of distinct labels is bounded.
+/// Requests which don't begin with one of `route_prefixes` (e.g. `eth`) or which are rejected with
+/// a client error (including requests which aren't matched by any route) are labelled as
+/// `OTHER_ROUTE`.
+pub fn response_times(
+    server: &'static str,
+    route_prefixes: &'static [&'static str],
+) -> warp::filters::log::Log<impl Fn(warp::filters::log::Info) + Clone> {
+    warp::log::custom(move |info| {
+        let route = route_label(info.path(), info.status(), route_prefixes);
+        let status_class = format!("{}xx", info.status().as_u16() / 100);
+        observe_timer_vec(
+            &HTTP_RESPONSE_TIMES,
+            &[server, method_label(info.method()), &route, &status_class],
+            info.elapsed(),
+        );
+    })
+}
+
+/// Returns the route label of a request for `path` which was answered with `status`.
+///
+/// Any path may be requested, so the path of a request which is rejected with a client error is
+/// not used as a label. E.g. an invalid `state_id` made of lowercase letters is indistinguishable
+/// from a route identifier, and would otherwise create a new label.
+pub fn route_label(path: &str, status: StatusCode, route_prefixes: &[&str]) -> String {
+    if status.is_client_error() {
+        OTHER_ROUTE.to_string()
+    } else {
+        normalize_route(path, route_prefixes)
+    }
+}
+
+/// Returns the label of `method`, which is `OTHER_ROUTE` for extension methods.
+pub fn method_label(method: &Method) -> &'static str {
+    KNOWN_METHODS
+        .iter()
+        .find(|known| *known == method)
+        .map_or(OTHER_ROUTE, |known| known.as_str())
+}
+
+/// Returns the route pattern of `path`, with each segment which is not a plain lowercase
+/// identifier (e.g. `finalized` or `v1`) replaced by `ROUTE_PARAM`.
+///
+/// For example, `/eth/v1/beacon/states/0x1234/validators/42` becomes
+/// `/eth/v1/beacon/states/{param}/validators/{param}`.
+pub fn normalize_route(path: &str, route_prefixes: &[&str]) -> String {
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    match segments.next() {
+        Some(prefix) if route_prefixes.contains(&prefix) => {
+            let mut route = format!("/{}", prefix);
+            for segment in segments {
+                route.push('/');
+                if is_route_identifier(segment) {
+                    route.push_str(segment);
+                } else {
+                    route.push_str(ROUTE_PARAM);
+                }
+            }
+            route
+        }
+        _ => OTHER_ROUTE.to_string(),
+    }
+}
+
+fn is_route_identifier(segment: &str) -> bool {
+    segment.len() <= MAX_ROUTE_SEGMENT_LEN
+        && segment.starts_with(|c: char| c.is_ascii_lowercase())
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
 }
 
 pub fn scrape_health_metrics() {
@@ -143,3 +243,102 @@ pub fn scrape_system_health_metrics() {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREFIXES: &[&str] = &["eth", "lighthouse"];
+
+    #[test]
+    fn normalizes_route_parameters() {
+        for (path, route) in [
+            ("/eth/v1/node/version", "/eth/v1/node/version"),
+            ("/eth/v2/beacon/blocks/head", "/eth/v2/beacon/blocks/head"),
+            (
+                "/eth/v1/beacon/states/0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3/validators/42",
+                "/eth/v1/beacon/states/{param}/validators/{param}",
+            ),
+            ("/eth/v1/validator/duties/proposer/7", "/eth/v1/validator/duties/proposer/{param}"),
+            (
+                "/lighthouse/peers/16Uiu2HAmAvwuVRy3TWpf6CNqX4xjJDtTpCjXG4xJURzUFrAzaKYv/stats",
+                "/lighthouse/peers/{param}/stats",
+            ),
+            ("/eth/v1/node/version/", "/eth/v1/node/version"),
+        ] {
+            assert_eq!(normalize_route(path, PREFIXES), route);
+        }
+    }
+
+    #[test]
+    fn unknown_prefixes_are_other() {
+        for path in [
+            "/",
+            "",
+            "/metrics",
+            "/wp-admin/install.php",
+            "/ETH/v1/node/version",
+        ] {
+            assert_eq!(normalize_route(path, PREFIXES), OTHER_ROUTE);
+        }
+        assert_eq!(normalize_route("/metrics", &["metrics"]), "/metrics");
+    }
+
+    #[test]
+    fn unmatched_routes_are_other() {
+        let path = "/eth/v1/made/up/route";
+        assert_eq!(route_label(path, StatusCode::OK, PREFIXES), path);
+        assert_eq!(
+            route_label(path, StatusCode::NOT_FOUND, PREFIXES),
+            OTHER_ROUTE
+        );
+        assert_eq!(
+            route_label(path, StatusCode::METHOD_NOT_ALLOWED, PREFIXES),
+            OTHER_ROUTE
+        );
+        assert_eq!(
+            route_label(
+                "/eth/v1/beacon/blocks/42",
+                StatusCode::BAD_REQUEST,
+                PREFIXES
+            ),
+            OTHER_ROUTE
+        );
+    }
+
+    #[test]
+    fn arbitrary_lowercase_ids_are_other() {
+        let labels = [
+            "abc",
+            "notastate",
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "x_1",
+        ]
+        .iter()
+        .flat_map(|id| {
+            [
+                format!("/eth/v1/beacon/states/{}/root", id),
+                format!("/eth/v2/beacon/blocks/{}", id),
+                format!("/lighthouse/peers/{}", id),
+            ]
+        })
+        .flat_map(|path| {
+            [StatusCode::BAD_REQUEST, StatusCode::NOT_FOUND]
+                .map(|status| route_label(&path, status, PREFIXES))
+        })
+        .collect::<std::collections::HashSet<_>>();
+        assert_eq!(labels.len(), 1);
+        assert!(labels.contains(OTHER_ROUTE));
+    }
+
+    #[test]
+    fn unknown_methods_are_other() {
+        for method in KNOWN_METHODS {
+            assert_eq!(method_label(method), method.as_str());
+        }
+        for method in ["CONNECT", "TRACE", "PROPFIND", "FOO"] {
+            let method = Method::from_bytes(method.as_bytes()).unwrap();
+            assert_eq!(method_label(&method), OTHER_ROUTE);
+        }
+    }
+}
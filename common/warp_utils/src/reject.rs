@@ -118,6 +118,24 @@ pub fn not_synced(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(NotSynced(msg))
 }
 
+#[derive(Debug)]
+pub struct RequestTimeout(pub String);
+
+impl Reject for RequestTimeout {}
+
+pub fn request_timeout(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(RequestTimeout(msg))
+}
+
+#[derive(Debug)]
+pub struct RequestCancelled(pub String);
+
+impl Reject for RequestCancelled {}
+
+pub fn request_cancelled(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(RequestCancelled(msg))
+}
+
 #[derive(Debug)]
 pub struct InvalidAuthorization(pub String);
 
@@ -201,6 +219,12 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::NotSynced>() {
         code = StatusCode::SERVICE_UNAVAILABLE;
         message = format!("SERVICE_UNAVAILABLE: beacon node is syncing: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::RequestTimeout>() {
+        code = StatusCode::GATEWAY_TIMEOUT;
+        message = format!("GATEWAY_TIMEOUT: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::RequestCancelled>() {
+        code = StatusCode::SERVICE_UNAVAILABLE;
+        message = format!("SERVICE_UNAVAILABLE: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
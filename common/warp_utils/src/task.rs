@@ -1,6 +1,64 @@
+use crate::reject::{request_cancelled, request_timeout};
 use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use warp::reply::{Reply, Response};
 
+/// A flag which is raised when the result of a task is no longer required.
+///
+/// Blocking tasks cannot be aborted once they are running, so long-running tasks should call
+/// `CancellationToken::check` between expensive steps and return early once it fails.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signal to the task holding this token that it should stop.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns an error if the task has been cancelled, for use with `?` inside a task.
+    pub fn check(&self) -> Result<(), warp::Rejection> {
+        if self.is_cancelled() {
+            Err(request_cancelled("request cancelled".to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns a guard which cancels this token when it is dropped.
+    ///
+    /// Warp drops the future of a request when its client disconnects, so holding the guard
+    /// across an `.await` cancels the token if the response will never be sent.
+    pub fn drop_guard(&self) -> CancelOnDrop {
+        CancelOnDrop {
+            token: self.clone(),
+        }
+    }
+}
+
+/// Cancels a `CancellationToken` when dropped. See `CancellationToken::drop_guard`.
+pub struct CancelOnDrop {
+    token: CancellationToken,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
+
 /// A convenience wrapper around `blocking_task`.
 pub async fn blocking_task<F, T>(func: F) -> Result<T, warp::Rejection>
 where
@@ -35,3 +93,110 @@ where
     })
     .await
 }
+
+/// As per `blocking_task`, but returns an error if `func` does not complete within `timeout`.
+///
+/// `func` is given a `CancellationToken` which is cancelled if the timeout elapses or if the
+/// returned future is dropped (e.g., because the client disconnected). The blocking thread is only
+/// released once `func` returns, so `func` should check the token regularly.
+pub async fn blocking_task_with_timeout<F, T>(
+    timeout: Duration,
+    func: F,
+) -> Result<T, warp::Rejection>
+where
+    F: FnOnce(CancellationToken) -> Result<T, warp::Rejection> + Send + 'static,
+    T: Send + 'static,
+{
+    let token = CancellationToken::new();
+    let _guard = token.drop_guard();
+
+    let task_token = token.clone();
+    let handle = tokio::task::spawn_blocking(move || func(task_token));
+
+    match tokio::time::timeout(timeout, handle).await {
+        Ok(result) => result.unwrap_or_else(|_| Err(warp::reject::reject())),
+        Err(_) => {
+            token.cancel();
+            Err(request_timeout(format!(
+                "request timed out after {}ms",
+                timeout.as_millis()
+            )))
+        }
+    }
+}
+
+/// A convenience wrapper around `blocking_task_with_timeout` for use with `warp` JSON responses.
+pub async fn blocking_json_task_with_timeout<F, T>(
+    timeout: Duration,
+    func: F,
+) -> Result<Response, warp::Rejection>
+where
+    F: FnOnce(CancellationToken) -> Result<T, warp::Rejection> + Send + 'static,
+    T: Serialize + Send + 'static,
+{
+    blocking_task_with_timeout(timeout, |token| {
+        let response = func(token)?;
+        Ok(warp::reply::json(&response).into_response())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use tokio::runtime::Runtime;
+
+    #[test]
+    fn completes_within_timeout() {
+        let result = Runtime::new().unwrap().block_on(blocking_task_with_timeout(
+            Duration::from_secs(10),
+            |token| {
+                token.check()?;
+                Ok(42)
+            },
+        ));
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn cancels_on_timeout() {
+        let (tx, rx) = mpsc::channel();
+        let result = Runtime::new().unwrap().block_on(blocking_task_with_timeout(
+            Duration::from_millis(10),
+            move |token| {
+                while !token.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                tx.send(()).unwrap();
+                token.check()
+            },
+        ));
+        assert!(result
+            .unwrap_err()
+            .find::<crate::reject::RequestTimeout>()
+            .is_some());
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("task should observe cancellation");
+    }
+
+    #[test]
+    fn cancels_when_dropped() {
+        let (tx, rx) = mpsc::channel();
+        let runtime = Runtime::new().unwrap();
+        runtime.block_on(async move {
+            let future = blocking_task_with_timeout(Duration::from_secs(60), move |token| {
+                while !token.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                tx.send(()).unwrap();
+                token.check()
+            });
+            // Poll the future once so that the task is spawned, then drop it as warp would if
+            // the client disconnected.
+            let _ = tokio::time::timeout(Duration::from_millis(10), future).await;
+        });
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("task should observe cancellation");
+    }
+}
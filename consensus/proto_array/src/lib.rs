@@ -9,7 +9,7 @@ pub use crate::justified_balances::JustifiedBalances;
 pub use crate::proto_array::{calculate_committee_fraction, InvalidationOperation};
 pub use crate::proto_array_fork_choice::{
     Block, DisallowedReOrgOffsets, DoNotReOrg, ExecutionStatus, ProposerHeadError,
-    ProposerHeadInfo, ProtoArrayForkChoice, ReOrgThreshold,
+    ProposerHeadInfo, ProtoArrayForkChoice, ReOrgThreshold, ReorgBranch, ReorgInfo,
 };
 pub use error::Error;
 
@@ -11,7 +11,7 @@ use serde_derive::{Deserialize, Serialize};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     fmt,
 };
 use types::{
@@ -264,6 +264,10 @@ pub enum DoNotReOrg {
     HeadNotLate,
     NotProposing,
     ReOrgsDisabled,
+    TooManyConsecutiveReOrgs {
+        count: u64,
+    },
+    DryRun,
 }
 
 impl std::fmt::Display for DoNotReOrg {
@@ -301,6 +305,12 @@ impl std::fmt::Display for DoNotReOrg {
             Self::ReOrgsDisabled => {
                 write!(f, "re-orgs disabled in config")
             }
+            Self::TooManyConsecutiveReOrgs { count } => {
+                write!(f, "already proposed {count} re-orging blocks in a row")
+            }
+            Self::DryRun => {
+                write!(f, "re-orgs are in dry-run mode")
+            }
         }
     }
 }
@@ -335,6 +345,34 @@ impl DisallowedReOrgOffsets {
     }
 }
 
+/// One side of a re-org: the blocks between the common ancestor and a head.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReorgBranch {
+    /// The blocks on the branch, oldest first. The branch is empty if the head is the common
+    /// ancestor.
+    pub blocks: Vec<(Hash256, Slot)>,
+    /// The weight of the oldest block on the branch, i.e. the weight of the whole branch.
+    pub weight: u64,
+    /// The number of validators whose latest message is for a block descending from the oldest
+    /// block on the branch.
+    pub validators: u64,
+    /// The total effective balance of `validators`.
+    pub balance: u64,
+}
+
+/// The blocks, weights and votes involved in a change of head to a block which does not descend
+/// from the previous head.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReorgInfo {
+    pub common_ancestor: Hash256,
+    pub common_ancestor_slot: Slot,
+    pub old_branch: ReorgBranch,
+    pub new_branch: ReorgBranch,
+    /// The block which received proposer boost during the last run of fork choice, if any.
+    pub proposer_boost_root: Option<Hash256>,
+    pub proposer_boost_score: u64,
+}
+
 #[derive(PartialEq)]
 pub struct ProtoArrayForkChoice {
     pub(crate) proto_array: ProtoArray,
@@ -820,6 +858,109 @@ impl ProtoArrayForkChoice {
         self.proto_array.iter_nodes(block_root)
     }
 
+    /// Describes the re-org from `old_head` to `new_head`, or returns `None` if either block is
+    /// unknown or `new_head` descends from `old_head`.
+    pub fn reorg_info(&self, old_head: Hash256, new_head: Hash256) -> Option<ReorgInfo> {
+        let old_chain = self
+            .iter_nodes(&old_head)
+            .map(|node| (node.root, node.slot))
+            .collect::<Vec<_>>();
+        if old_chain.is_empty() {
+            return None;
+        }
+        let old_chain_roots = old_chain
+            .iter()
+            .map(|(root, _)| *root)
+            .collect::<HashSet<_>>();
+
+        let mut new_blocks = vec![];
+        let mut common_ancestor = None;
+        for node in self.iter_nodes(&new_head) {
+            if old_chain_roots.contains(&node.root) {
+                common_ancestor = Some((node.root, node.slot));
+                break;
+            }
+            new_blocks.push((node.root, node.slot));
+        }
+        let (common_ancestor, common_ancestor_slot) = common_ancestor?;
+        if common_ancestor == old_head {
+            return None;
+        }
+
+        let mut old_blocks = old_chain
+            .into_iter()
+            .take_while(|(root, _)| *root != common_ancestor)
+            .collect::<Vec<_>>();
+        old_blocks.reverse();
+        new_blocks.reverse();
+
+        // Nodes are stored after their parents, so a single pass finds every descendant of the
+        // first block on each branch.
+        #[derive(Clone, Copy, PartialEq)]
+        enum Side {
+            Old,
+            New,
+        }
+        let old_root = old_blocks.first().map(|(root, _)| *root);
+        let new_root = new_blocks.first().map(|(root, _)| *root);
+        let nodes = &self.proto_array.nodes;
+        let mut sides: Vec<Option<Side>> = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            let side = if Some(node.root) == old_root {
+                Some(Side::Old)
+            } else if Some(node.root) == new_root {
+                Some(Side::New)
+            } else {
+                node.parent
+                    .and_then(|parent| sides.get(parent).copied())
+                    .flatten()
+            };
+            sides.push(side);
+        }
+        let block_sides = nodes
+            .iter()
+            .zip(sides)
+            .filter_map(|(node, side)| Some((node.root, side?)))
+            .collect::<HashMap<_, _>>();
+
+        let branch = |blocks: Vec<(Hash256, Slot)>, side: Side| {
+            let weight = blocks
+                .first()
+                .and_then(|(root, _)| self.get_weight(root))
+                .unwrap_or(0);
+            let mut validators = 0;
+            let mut balance = 0_u64;
+            for (validator_index, vote) in self.votes.0.iter().enumerate() {
+                if block_sides.get(&vote.next_root) == Some(&side) {
+                    validators += 1;
+                    balance = balance.saturating_add(
+                        self.balances
+                            .effective_balances
+                            .get(validator_index)
+                            .copied()
+                            .unwrap_or(0),
+                    );
+                }
+            }
+            ReorgBranch {
+                blocks,
+                weight,
+                validators,
+                balance,
+            }
+        };
+
+        let proposer_boost = &self.proto_array.previous_proposer_boost;
+        Some(ReorgInfo {
+            common_ancestor,
+            common_ancestor_slot,
+            old_branch: branch(old_blocks, Side::Old),
+            new_branch: branch(new_blocks, Side::New),
+            proposer_boost_root: (!proposer_boost.root.is_zero()).then_some(proposer_boost.root),
+            proposer_boost_score: proposer_boost.score,
+        })
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         SszContainer::from(self).as_ssz_bytes()
     }
@@ -1230,6 +1371,103 @@ mod test_compute_deltas {
         );
     }
 
+    #[test]
+    fn reorg_info() {
+        let get_block_root = Hash256::from_low_u64_be;
+        let genesis_checkpoint = Checkpoint {
+            epoch: Epoch::new(0),
+            root: get_block_root(0),
+        };
+        let junk_shuffling_id =
+            AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+        let execution_status = ExecutionStatus::irrelevant();
+
+        let mut fc = ProtoArrayForkChoice::new::<MainnetEthSpec>(
+            Slot::new(0),
+            Slot::new(0),
+            Hash256::zero(),
+            genesis_checkpoint,
+            genesis_checkpoint,
+            junk_shuffling_id.clone(),
+            junk_shuffling_id.clone(),
+            execution_status,
+        )
+        .unwrap();
+
+        // 0 <- 1 <- 2 <- 3
+        //      ^
+        //      |--- 4 <- 5
+        //           ^
+        //           |--- 6
+        let blocks = [
+            (1, 1, 0),
+            (2, 2, 1),
+            (3, 3, 2),
+            (2, 4, 1),
+            (3, 5, 4),
+            (4, 6, 4),
+        ];
+        for (slot, root, parent_root) in blocks {
+            fc.proto_array
+                .on_block::<MainnetEthSpec>(
+                    Block {
+                        slot: Slot::new(slot),
+                        root: get_block_root(root),
+                        parent_root: Some(get_block_root(parent_root)),
+                        state_root: Hash256::zero(),
+                        target_root: Hash256::zero(),
+                        current_epoch_shuffling_id: junk_shuffling_id.clone(),
+                        next_epoch_shuffling_id: junk_shuffling_id.clone(),
+                        justified_checkpoint: genesis_checkpoint,
+                        finalized_checkpoint: genesis_checkpoint,
+                        execution_status,
+                        unrealized_justified_checkpoint: Some(genesis_checkpoint),
+                        unrealized_finalized_checkpoint: Some(genesis_checkpoint),
+                    },
+                    Slot::new(slot),
+                )
+                .unwrap();
+        }
+
+        fc.balances = JustifiedBalances::from_effective_balances(vec![1, 2, 4, 8, 16]).unwrap();
+        for (validator_index, root) in [(0, 3), (1, 2), (2, 5), (3, 6), (4, 1)] {
+            fc.process_attestation(validator_index, get_block_root(root), Epoch::new(0))
+                .unwrap();
+        }
+
+        let info = fc.reorg_info(get_block_root(3), get_block_root(5)).unwrap();
+        assert_eq!(info.common_ancestor, get_block_root(1));
+        assert_eq!(info.common_ancestor_slot, Slot::new(1));
+        assert_eq!(
+            info.old_branch.blocks,
+            vec![
+                (get_block_root(2), Slot::new(2)),
+                (get_block_root(3), Slot::new(3))
+            ]
+        );
+        assert_eq!(
+            info.new_branch.blocks,
+            vec![
+                (get_block_root(4), Slot::new(2)),
+                (get_block_root(5), Slot::new(3))
+            ]
+        );
+        assert_eq!(
+            (info.old_branch.validators, info.old_branch.balance),
+            (2, 3)
+        );
+        // Votes for block 6 count towards the new branch, which includes all descendants of 4.
+        assert_eq!(
+            (info.new_branch.validators, info.new_branch.balance),
+            (2, 12)
+        );
+        assert_eq!(info.proposer_boost_root, None);
+
+        // A head which descends from the old head is not a re-org.
+        assert_eq!(fc.reorg_info(get_block_root(1), get_block_root(5)), None);
+        assert_eq!(fc.reorg_info(get_block_root(9), get_block_root(5)), None);
+    }
+
     #[test]
     fn zero_hash() {
         let validator_count: usize = 16;
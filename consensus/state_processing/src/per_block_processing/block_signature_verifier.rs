@@ -8,7 +8,7 @@ use rayon::prelude::*;
 use std::borrow::Cow;
 use types::{
     AbstractExecPayload, BeaconState, BeaconStateError, ChainSpec, EthSpec, Hash256,
-    SignedBeaconBlock,
+    SignedBeaconBlock, Slot,
 };
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -17,6 +17,8 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     /// All public keys were found but signature verification failed. The block is invalid.
     SignatureInvalid,
+    /// As for `SignatureInvalid`, where the invalid signature was identified.
+    InvalidSignatureSet(SignatureSetId),
     /// An attestation in the block was invalid. The block is invalid.
     AttestationValidationError(BlockOperationError<AttestationInvalid>),
     /// There was an error attempting to read from a `BeaconState`. Block
@@ -67,6 +69,30 @@ impl From<BlockOperationError<AttestationInvalid>> for Error {
     }
 }
 
+/// The kind of message which a signature in a block belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureSetKind {
+    BlockProposal,
+    RandaoReveal,
+    ProposerSlashing,
+    AttesterSlashing,
+    Attestation,
+    VoluntaryExit,
+    SyncAggregate,
+    BlsToExecutionChange,
+}
+
+/// Identifies a signature included in a `BlockSignatureVerifier`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignatureSetId {
+    /// The slot of the block containing the signature.
+    pub slot: Slot,
+    pub kind: SignatureSetKind,
+    /// The position of the message in its list in the block body, or 0 for messages which are not
+    /// in a list.
+    pub index: usize,
+}
+
 /// Reads the BLS signatures and keys from a `SignedBeaconBlock`, storing them as a `Vec<SignatureSet>`.
 ///
 /// This allows for optimizations related to batch BLS operations (see the
@@ -82,6 +108,8 @@ where
     state: &'a BeaconState<T>,
     spec: &'a ChainSpec,
     sets: ParallelSignatureSets<'a>,
+    /// The id of each of the `sets`, in the same order.
+    ids: Vec<SignatureSetId>,
 }
 
 #[derive(Default)]
@@ -115,9 +143,15 @@ where
             state,
             spec,
             sets: ParallelSignatureSets::default(),
+            ids: vec![],
         }
     }
 
+    fn push(&mut self, slot: Slot, kind: SignatureSetKind, index: usize, set: SignatureSet<'a>) {
+        self.sets.push(set);
+        self.ids.push(SignatureSetId { slot, kind, index });
+    }
+
     /// Verify all* the signatures in the given `SignedBeaconBlock`, returning `Ok(())` if the signatures
     /// are valid.
     ///
@@ -190,7 +224,7 @@ where
             verified_proposer_index,
             self.spec,
         )?;
-        self.sets.push(set);
+        self.push(block.slot(), SignatureSetKind::BlockProposal, 0, set);
         Ok(())
     }
 
@@ -207,7 +241,7 @@ where
             verified_proposer_index,
             self.spec,
         )?;
-        self.sets.push(set);
+        self.push(block.slot(), SignatureSetKind::RandaoReveal, 0, set);
         Ok(())
     }
 
@@ -225,7 +259,8 @@ where
             .body()
             .proposer_slashings()
             .iter()
-            .try_for_each(|proposer_slashing| {
+            .enumerate()
+            .try_for_each(|(index, proposer_slashing)| {
                 let (set_1, set_2) = proposer_slashing_signature_set(
                     self.state,
                     self.get_pubkey.clone(),
@@ -233,8 +268,18 @@ where
                     self.spec,
                 )?;
 
-                self.sets.push(set_1);
-                self.sets.push(set_2);
+                self.push(
+                    block.slot(),
+                    SignatureSetKind::ProposerSlashing,
+                    index,
+                    set_1,
+                );
+                self.push(
+                    block.slot(),
+                    SignatureSetKind::ProposerSlashing,
+                    index,
+                    set_2,
+                );
 
                 Ok(())
             })
@@ -254,7 +299,8 @@ where
             .body()
             .attester_slashings()
             .iter()
-            .try_for_each(|attester_slashing| {
+            .enumerate()
+            .try_for_each(|(index, attester_slashing)| {
                 let (set_1, set_2) = attester_slashing_signature_sets(
                     self.state,
                     self.get_pubkey.clone(),
@@ -262,8 +308,18 @@ where
                     self.spec,
                 )?;
 
-                self.sets.push(set_1);
-                self.sets.push(set_2);
+                self.push(
+                    block.slot(),
+                    SignatureSetKind::AttesterSlashing,
+                    index,
+                    set_1,
+                );
+                self.push(
+                    block.slot(),
+                    SignatureSetKind::AttesterSlashing,
+                    index,
+                    set_2,
+                );
 
                 Ok(())
             })
@@ -284,16 +340,18 @@ where
             .body()
             .attestations()
             .iter()
-            .try_for_each(|attestation| {
+            .enumerate()
+            .try_for_each(|(index, attestation)| {
                 let indexed_attestation = ctxt.get_indexed_attestation(self.state, attestation)?;
 
-                self.sets.push(indexed_attestation_signature_set(
+                let set = indexed_attestation_signature_set(
                     self.state,
                     self.get_pubkey.clone(),
                     &attestation.signature,
                     indexed_attestation,
                     self.spec,
-                )?);
+                )?;
+                self.push(block.slot(), SignatureSetKind::Attestation, index, set);
                 Ok(())
             })
             .map_err(Error::into)
@@ -313,11 +371,12 @@ where
             .body()
             .voluntary_exits()
             .iter()
-            .try_for_each(|exit| {
+            .enumerate()
+            .try_for_each(|(index, exit)| {
                 let exit =
                     exit_signature_set(self.state, self.get_pubkey.clone(), exit, self.spec)?;
 
-                self.sets.push(exit);
+                self.push(block.slot(), SignatureSetKind::VoluntaryExit, index, exit);
 
                 Ok(())
             })
@@ -337,7 +396,12 @@ where
                 self.state,
                 self.spec,
             )? {
-                self.sets.push(signature_set);
+                self.push(
+                    block.slot(),
+                    SignatureSetKind::SyncAggregate,
+                    0,
+                    signature_set,
+                );
             }
         }
         Ok(())
@@ -350,19 +414,25 @@ where
     ) -> Result<()> {
         // To improve performance we might want to decompress the withdrawal pubkeys in parallel.
         if let Ok(bls_to_execution_changes) = block.message().body().bls_to_execution_changes() {
-            for bls_to_execution_change in bls_to_execution_changes {
-                self.sets.push(bls_execution_change_signature_set(
+            for (index, bls_to_execution_change) in bls_to_execution_changes.iter().enumerate() {
+                let set = bls_execution_change_signature_set(
                     self.state,
                     bls_to_execution_change,
                     self.spec,
-                )?);
+                )?;
+                self.push(
+                    block.slot(),
+                    SignatureSetKind::BlsToExecutionChange,
+                    index,
+                    set,
+                );
             }
         }
         Ok(())
     }
 
-    /// Verify all the signatures that have been included in `self`, returning `true` if and only if
-    /// all the signatures are valid.
+    /// Verify all the signatures that have been included in `self`, returning `Ok(())` if and only
+    /// if all the signatures are valid.
     ///
     /// See `ParallelSignatureSets::verify` for more info.
     pub fn verify(self) -> Result<()> {
@@ -372,6 +442,26 @@ where
             Err(Error::SignatureInvalid)
         }
     }
+
+    /// As for `Self::verify`, except that if the batch is invalid each signature is verified
+    /// individually to identify the invalid one.
+    ///
+    /// Verifying the signatures individually is much slower than the batch, so this is only
+    /// intended for diagnostics and must not be used on blocks received from the network.
+    pub fn verify_and_identify_invalid(self) -> Result<()> {
+        if self.sets.verify() {
+            Ok(())
+        } else {
+            match self
+                .sets
+                .find_invalid()
+                .and_then(|position| self.ids.get(position))
+            {
+                Some(id) => Err(Error::InvalidSignatureSet(*id)),
+                None => Err(Error::SignatureInvalid),
+            }
+        }
+    }
 }
 
 impl<'a> ParallelSignatureSets<'a> {
@@ -393,13 +483,23 @@ impl<'a> ParallelSignatureSets<'a> {
     ///
     /// Uses `rayon` to do a map-reduce of Vitalik's method across multiple cores.
     #[must_use]
-    pub fn verify(self) -> bool {
+    pub fn verify(&self) -> bool {
         let num_sets = self.sets.len();
         let num_chunks = std::cmp::max(1, num_sets / rayon::current_num_threads());
         self.sets
-            .into_par_iter()
+            .par_iter()
             .chunks(num_chunks)
-            .map(|chunk| verify_signature_sets(chunk.iter()))
+            .map(|chunk| verify_signature_sets(chunk.into_iter()))
             .reduce(|| true, |current, this| current && this)
     }
+
+    /// Verifies each signature set individually, returning the position of the first invalid set.
+    ///
+    /// This is much slower than `Self::verify`, and is intended to identify the invalid signature
+    /// once `Self::verify` has failed.
+    pub fn find_invalid(&self) -> Option<usize> {
+        self.sets
+            .par_iter()
+            .position_first(|set| !verify_signature_sets(std::iter::once(set)))
+    }
 }
@@ -1,5 +1,8 @@
 #![cfg(all(test, not(feature = "fake_crypto")))]
 
+use crate::per_block_processing::block_signature_verifier::{
+    BlockSignatureVerifier, Error as BlockSignatureVerifierError, SignatureSetId, SignatureSetKind,
+};
 use crate::per_block_processing::errors::{
     AttestationInvalid, AttesterSlashingInvalid, BlockOperationError, BlockProcessingError,
     DepositInvalid, HeaderInvalid, IndexedAttestationInvalid, IntoWithIndex,
@@ -7,12 +10,15 @@ use crate::per_block_processing::errors::{
 };
 use crate::{per_block_processing, StateProcessingStrategy};
 use crate::{
-    per_block_processing::{process_operations, verify_exit::verify_exit},
+    per_block_processing::{
+        process_operations, signature_sets::get_pubkey_from_state, verify_exit::verify_exit,
+    },
     BlockSignatureStrategy, ConsensusContext, VerifyBlockRoot, VerifySignatures,
 };
 use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
 use lazy_static::lazy_static;
 use ssz_types::Bitfield;
+use std::borrow::Cow;
 use test_utils::generate_deterministic_keypairs;
 use types::*;
 
@@ -209,6 +215,53 @@ async fn invalid_randao_reveal_signature() {
     assert_eq!(result, Err(BlockProcessingError::RandaoSignatureInvalid));
 }
 
+#[tokio::test]
+async fn bulk_signature_verification_identifies_invalid_signature() {
+    let spec = MainnetEthSpec::default_spec();
+    let harness = get_harness::<MainnetEthSpec>(EPOCH_OFFSET, VALIDATOR_COUNT).await;
+
+    let state = harness.get_current_state();
+    let slot = state.slot();
+
+    let (signed_block, state) = harness
+        .make_block_with_modifier(state, slot + 1, |block| {
+            *block.body_mut().randao_reveal_mut() = Signature::empty();
+        })
+        .await;
+
+    let get_pubkey = |i| get_pubkey_from_state(&state, i);
+    let decompressor = |pk_bytes: &PublicKeyBytes| pk_bytes.decompress().ok().map(Cow::Owned);
+
+    // The batch alone can only report that some signature is invalid.
+    let mut ctxt = ConsensusContext::new(signed_block.slot());
+    let result = BlockSignatureVerifier::verify_entire_block(
+        &state,
+        get_pubkey,
+        decompressor,
+        &signed_block,
+        &mut ctxt,
+        &spec,
+    );
+    assert_eq!(result, Err(BlockSignatureVerifierError::SignatureInvalid));
+
+    // Verifying each signature individually finds the RANDAO reveal.
+    let mut ctxt = ConsensusContext::new(signed_block.slot());
+    let mut verifier = BlockSignatureVerifier::new(&state, get_pubkey, decompressor, &spec);
+    verifier
+        .include_all_signatures(&signed_block, &mut ctxt)
+        .expect("should include signatures");
+    assert_eq!(
+        verifier.verify_and_identify_invalid(),
+        Err(BlockSignatureVerifierError::InvalidSignatureSet(
+            SignatureSetId {
+                slot: signed_block.slot(),
+                kind: SignatureSetKind::RandaoReveal,
+                index: 0,
+            }
+        ))
+    );
+}
+
 #[tokio::test]
 async fn valid_4_deposits() {
     let spec = MainnetEthSpec::default_spec();
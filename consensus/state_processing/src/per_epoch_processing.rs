@@ -3,6 +3,7 @@
 pub use epoch_processing_summary::EpochProcessingSummary;
 use errors::EpochProcessingError as Error;
 pub use justification_and_finalization_state::JustificationAndFinalizationState;
+pub use parallel::set_max_epoch_processing_threads;
 use safe_arith::SafeArith;
 use types::{BeaconState, ChainSpec, EthSpec};
 
@@ -18,6 +19,7 @@ pub mod epoch_processing_summary;
 pub mod errors;
 pub mod historical_roots_update;
 pub mod justification_and_finalization_state;
+pub mod parallel;
 pub mod registry_updates;
 pub mod resets;
 pub mod slashings;
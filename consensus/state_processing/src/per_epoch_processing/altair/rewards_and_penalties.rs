@@ -1,4 +1,6 @@
+use super::participation_cache::UnslashedParticipatingIndices;
 use super::ParticipationCache;
+use rayon::prelude::*;
 use safe_arith::SafeArith;
 use types::consts::altair::{
     PARTICIPATION_FLAG_WEIGHTS, TIMELY_HEAD_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX,
@@ -10,10 +12,13 @@ use crate::common::{
     altair::{get_base_reward, BaseRewardPerIncrement},
     decrease_balance, increase_balance,
 };
+use crate::per_epoch_processing::parallel::{self, indices_in_range, VALIDATOR_CHUNK_SIZE};
 use crate::per_epoch_processing::{Delta, Error};
 
 /// Apply attester and proposer rewards.
 ///
+/// The deltas are computed in parallel, in chunks of validators.
+///
 /// Spec v1.1.0
 pub fn process_rewards_and_penalties<T: EthSpec>(
     state: &mut BeaconState<T>,
@@ -28,18 +33,37 @@ pub fn process_rewards_and_penalties<T: EthSpec>(
 
     let total_active_balance = participation_cache.current_epoch_total_active_balance();
 
-    for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
-        get_flag_index_deltas(
-            &mut deltas,
-            state,
-            flag_index,
-            total_active_balance,
-            participation_cache,
-            spec,
-        )?;
-    }
+    let flag_index_deltas = (0..PARTICIPATION_FLAG_WEIGHTS.len())
+        .map(|flag_index| {
+            FlagIndexDeltas::new(
+                state,
+                flag_index,
+                total_active_balance,
+                participation_cache,
+                spec,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let inactivity_penalty_deltas = InactivityPenaltyDeltas::new(state, participation_cache, spec)?;
+    let eligible_validator_indices = participation_cache.eligible_validator_indices();
 
-    get_inactivity_penalty_deltas(&mut deltas, state, participation_cache, spec)?;
+    let state_ref: &BeaconState<T> = state;
+    parallel::install(|| {
+        deltas
+            .par_chunks_mut(VALIDATOR_CHUNK_SIZE)
+            .enumerate()
+            .try_for_each(|(chunk_index, deltas)| {
+                let offset = chunk_index.safe_mul(VALIDATOR_CHUNK_SIZE)?;
+                let indices = indices_in_range(
+                    eligible_validator_indices,
+                    offset..offset.safe_add(deltas.len())?,
+                );
+                for flag_index_deltas in &flag_index_deltas {
+                    flag_index_deltas.apply(deltas, offset, indices, state_ref, spec)?;
+                }
+                inactivity_penalty_deltas.apply(deltas, offset, indices, state_ref)
+            })
+    })?;
 
     // Apply the deltas, erroring on overflow above but not on overflow below (saturating at 0
     // instead).
@@ -62,38 +86,20 @@ pub fn get_flag_index_deltas<T: EthSpec>(
     participation_cache: &ParticipationCache,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    let previous_epoch = state.previous_epoch();
-    let unslashed_participating_indices =
-        participation_cache.get_unslashed_participating_indices(flag_index, previous_epoch)?;
-    let weight = get_flag_weight(flag_index)?;
-    let unslashed_participating_balance = unslashed_participating_indices.total_balance()?;
-    let unslashed_participating_increments =
-        unslashed_participating_balance.safe_div(spec.effective_balance_increment)?;
-    let active_increments = total_active_balance.safe_div(spec.effective_balance_increment)?;
-    let base_reward_per_increment = BaseRewardPerIncrement::new(total_active_balance, spec)?;
-
-    for &index in participation_cache.eligible_validator_indices() {
-        let base_reward = get_base_reward(state, index, base_reward_per_increment, spec)?;
-        let mut delta = Delta::default();
-
-        if unslashed_participating_indices.contains(index)? {
-            if !state.is_in_inactivity_leak(previous_epoch, spec)? {
-                let reward_numerator = base_reward
-                    .safe_mul(weight)?
-                    .safe_mul(unslashed_participating_increments)?;
-                delta.reward(
-                    reward_numerator.safe_div(active_increments.safe_mul(WEIGHT_DENOMINATOR)?)?,
-                )?;
-            }
-        } else if flag_index != TIMELY_HEAD_FLAG_INDEX {
-            delta.penalize(base_reward.safe_mul(weight)?.safe_div(WEIGHT_DENOMINATOR)?)?;
-        }
-        deltas
-            .get_mut(index)
-            .ok_or(Error::DeltaOutOfBounds(index))?
-            .combine(delta)?;
-    }
-    Ok(())
+    FlagIndexDeltas::new(
+        state,
+        flag_index,
+        total_active_balance,
+        participation_cache,
+        spec,
+    )?
+    .apply(
+        deltas,
+        0,
+        participation_cache.eligible_validator_indices(),
+        state,
+        spec,
+    )
 }
 
 /// Get the weight for a `flag_index` from the constant list of all weights.
@@ -110,26 +116,137 @@ pub fn get_inactivity_penalty_deltas<T: EthSpec>(
     participation_cache: &ParticipationCache,
     spec: &ChainSpec,
 ) -> Result<(), Error> {
-    let previous_epoch = state.previous_epoch();
-    let matching_target_indices = participation_cache
-        .get_unslashed_participating_indices(TIMELY_TARGET_FLAG_INDEX, previous_epoch)?;
-    for &index in participation_cache.eligible_validator_indices() {
-        let mut delta = Delta::default();
-
-        if !matching_target_indices.contains(index)? {
-            let penalty_numerator = state
-                .get_validator(index)?
-                .effective_balance
-                .safe_mul(state.get_inactivity_score(index)?)?;
-            let penalty_denominator = spec
+    InactivityPenaltyDeltas::new(state, participation_cache, spec)?.apply(
+        deltas,
+        0,
+        participation_cache.eligible_validator_indices(),
+        state,
+    )
+}
+
+/// The values used by `get_flag_index_deltas` which are the same for every validator.
+struct FlagIndexDeltas<'a> {
+    flag_index: usize,
+    unslashed_participating_indices: UnslashedParticipatingIndices<'a>,
+    weight: u64,
+    unslashed_participating_increments: u64,
+    active_increments: u64,
+    base_reward_per_increment: BaseRewardPerIncrement,
+    in_inactivity_leak: bool,
+}
+
+impl<'a> FlagIndexDeltas<'a> {
+    fn new<T: EthSpec>(
+        state: &BeaconState<T>,
+        flag_index: usize,
+        total_active_balance: u64,
+        participation_cache: &'a ParticipationCache,
+        spec: &ChainSpec,
+    ) -> Result<Self, Error> {
+        let previous_epoch = state.previous_epoch();
+        let unslashed_participating_indices =
+            participation_cache.get_unslashed_participating_indices(flag_index, previous_epoch)?;
+        let unslashed_participating_balance = unslashed_participating_indices.total_balance()?;
+        Ok(Self {
+            flag_index,
+            unslashed_participating_indices,
+            weight: get_flag_weight(flag_index)?,
+            unslashed_participating_increments: unslashed_participating_balance
+                .safe_div(spec.effective_balance_increment)?,
+            active_increments: total_active_balance.safe_div(spec.effective_balance_increment)?,
+            base_reward_per_increment: BaseRewardPerIncrement::new(total_active_balance, spec)?,
+            in_inactivity_leak: state.is_in_inactivity_leak(previous_epoch, spec)?,
+        })
+    }
+
+    /// Adds the deltas of the validators in `indices` to `deltas`, which holds the deltas of the
+    /// validators starting at index `offset`.
+    fn apply<T: EthSpec>(
+        &self,
+        deltas: &mut [Delta],
+        offset: usize,
+        indices: &[usize],
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) -> Result<(), Error> {
+        for &index in indices {
+            let base_reward = get_base_reward(state, index, self.base_reward_per_increment, spec)?;
+            let mut delta = Delta::default();
+
+            if self.unslashed_participating_indices.contains(index)? {
+                if !self.in_inactivity_leak {
+                    let reward_numerator = base_reward
+                        .safe_mul(self.weight)?
+                        .safe_mul(self.unslashed_participating_increments)?;
+                    delta.reward(
+                        reward_numerator
+                            .safe_div(self.active_increments.safe_mul(WEIGHT_DENOMINATOR)?)?,
+                    )?;
+                }
+            } else if self.flag_index != TIMELY_HEAD_FLAG_INDEX {
+                delta.penalize(
+                    base_reward
+                        .safe_mul(self.weight)?
+                        .safe_div(WEIGHT_DENOMINATOR)?,
+                )?;
+            }
+            get_delta_mut(deltas, offset, index)?.combine(delta)?;
+        }
+        Ok(())
+    }
+}
+
+/// The values used by `get_inactivity_penalty_deltas` which are the same for every validator.
+struct InactivityPenaltyDeltas<'a> {
+    matching_target_indices: UnslashedParticipatingIndices<'a>,
+    penalty_denominator: u64,
+}
+
+impl<'a> InactivityPenaltyDeltas<'a> {
+    fn new<T: EthSpec>(
+        state: &BeaconState<T>,
+        participation_cache: &'a ParticipationCache,
+        spec: &ChainSpec,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            matching_target_indices: participation_cache.get_unslashed_participating_indices(
+                TIMELY_TARGET_FLAG_INDEX,
+                state.previous_epoch(),
+            )?,
+            penalty_denominator: spec
                 .inactivity_score_bias
-                .safe_mul(spec.inactivity_penalty_quotient_for_state(state))?;
-            delta.penalize(penalty_numerator.safe_div(penalty_denominator)?)?;
+                .safe_mul(spec.inactivity_penalty_quotient_for_state(state))?,
+        })
+    }
+
+    /// Adds the deltas of the validators in `indices` to `deltas`, which holds the deltas of the
+    /// validators starting at index `offset`.
+    fn apply<T: EthSpec>(
+        &self,
+        deltas: &mut [Delta],
+        offset: usize,
+        indices: &[usize],
+        state: &BeaconState<T>,
+    ) -> Result<(), Error> {
+        for &index in indices {
+            let mut delta = Delta::default();
+
+            if !self.matching_target_indices.contains(index)? {
+                let penalty_numerator = state
+                    .get_validator(index)?
+                    .effective_balance
+                    .safe_mul(state.get_inactivity_score(index)?)?;
+                delta.penalize(penalty_numerator.safe_div(self.penalty_denominator)?)?;
+            }
+            get_delta_mut(deltas, offset, index)?.combine(delta)?;
         }
-        deltas
-            .get_mut(index)
-            .ok_or(Error::DeltaOutOfBounds(index))?
-            .combine(delta)?;
+        Ok(())
     }
-    Ok(())
+}
+
+fn get_delta_mut(deltas: &mut [Delta], offset: usize, index: usize) -> Result<&mut Delta, Error> {
+    index
+        .checked_sub(offset)
+        .and_then(|i| deltas.get_mut(i))
+        .ok_or(Error::DeltaOutOfBounds(index))
 }
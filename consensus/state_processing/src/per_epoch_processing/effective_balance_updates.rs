@@ -1,5 +1,7 @@
 use super::errors::EpochProcessingError;
+use super::parallel::{self, VALIDATOR_CHUNK_SIZE};
 use crate::per_epoch_processing::altair::ParticipationCache;
+use rayon::prelude::*;
 use safe_arith::SafeArith;
 use types::beacon_state::BeaconState;
 use types::chain_spec::ChainSpec;
@@ -17,32 +19,51 @@ pub fn process_effective_balance_updates<T: EthSpec>(
     let upward_threshold = hysteresis_increment.safe_mul(spec.hysteresis_upward_multiplier)?;
     let (validators, balances, progressive_balances_cache) =
         state.validators_and_balances_and_progressive_balances_mut();
-    for (index, validator) in validators.iter_mut().enumerate() {
-        let balance = balances
-            .get(index)
-            .copied()
-            .ok_or(BeaconStateError::BalancesOutOfBounds(index))?;
+    let balances: &[u64] = balances;
 
-        if balance.safe_add(downward_threshold)? < validator.effective_balance
-            || validator.effective_balance.safe_add(upward_threshold)? < balance
-        {
-            let old_effective_balance = validator.effective_balance;
-            let new_effective_balance = std::cmp::min(
-                balance.safe_sub(balance.safe_rem(spec.effective_balance_increment)?)?,
-                spec.max_effective_balance,
-            );
+    // The effective balances are updated in parallel, whilst the changes are applied to the
+    // progressive balances afterwards, in order of validator index.
+    let changes = parallel::install(|| {
+        validators
+            .par_chunks_mut(VALIDATOR_CHUNK_SIZE)
+            .enumerate()
+            .map(|(chunk_index, validators)| {
+                let offset = chunk_index.safe_mul(VALIDATOR_CHUNK_SIZE)?;
+                let mut changes = vec![];
+                for (i, validator) in validators.iter_mut().enumerate() {
+                    let index = offset.safe_add(i)?;
+                    let balance = balances
+                        .get(index)
+                        .copied()
+                        .ok_or(BeaconStateError::BalancesOutOfBounds(index))?;
 
-            if let Some(participation_cache) = maybe_participation_cache {
-                update_progressive_balances(
-                    participation_cache,
-                    progressive_balances_cache,
-                    index,
-                    old_effective_balance,
-                    new_effective_balance,
-                )?;
-            }
+                    if balance.safe_add(downward_threshold)? < validator.effective_balance
+                        || validator.effective_balance.safe_add(upward_threshold)? < balance
+                    {
+                        let old_effective_balance = validator.effective_balance;
+                        let new_effective_balance = std::cmp::min(
+                            balance
+                                .safe_sub(balance.safe_rem(spec.effective_balance_increment)?)?,
+                            spec.max_effective_balance,
+                        );
+                        changes.push((index, old_effective_balance, new_effective_balance));
+                        validator.effective_balance = new_effective_balance;
+                    }
+                }
+                Ok(changes)
+            })
+            .collect::<Result<Vec<_>, EpochProcessingError>>()
+    })?;
 
-            validator.effective_balance = new_effective_balance;
+    if let Some(participation_cache) = maybe_participation_cache {
+        for (index, old_effective_balance, new_effective_balance) in changes.into_iter().flatten() {
+            update_progressive_balances(
+                participation_cache,
+                progressive_balances_cache,
+                index,
+                old_effective_balance,
+                new_effective_balance,
+            )?;
         }
     }
     Ok(())
@@ -0,0 +1,73 @@
+//! Control over the threads used to process validators in parallel during epoch processing.
+//!
+//! Validators are processed in chunks of `VALIDATOR_CHUNK_SIZE`. Each chunk only reads and writes
+//! the values of the validators in its own range, so the result of epoch processing is the same
+//! regardless of the number of threads or the order in which the chunks are processed.
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::sync::{Arc, RwLock};
+
+/// The number of validators processed by each parallel task.
+pub const VALIDATOR_CHUNK_SIZE: usize = 4_096;
+
+/// The pool used to process validators in parallel, if the number of threads has been limited.
+///
+/// When `None`, the global `rayon` pool is used.
+static THREAD_POOL: RwLock<Option<Arc<ThreadPool>>> = RwLock::new(None);
+
+/// Limits the number of threads used to process validators in parallel during epoch processing.
+///
+/// Applies to all epoch processing performed after the call, throughout the process.
+pub fn set_max_epoch_processing_threads(num_threads: usize) -> Result<(), String> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .thread_name(|i| format!("epoch_processing_{}", i))
+        .build()
+        .map_err(|e| format!("Unable to build epoch processing thread pool: {:?}", e))?;
+    *THREAD_POOL
+        .write()
+        .map_err(|_| "Epoch processing thread pool lock is poisoned".to_string())? =
+        Some(Arc::new(pool));
+    Ok(())
+}
+
+/// Runs `op` such that any parallel iterators within it use the epoch processing threads.
+pub(crate) fn install<OP, R>(op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    let pool = THREAD_POOL.read().ok().and_then(|pool| pool.clone());
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+/// Returns the subset of the sorted `indices` which lie in `range`.
+pub(crate) fn indices_in_range(indices: &[usize], range: std::ops::Range<usize>) -> &[usize] {
+    let start = indices.partition_point(|&index| index < range.start);
+    let end = indices.partition_point(|&index| index < range.end);
+    indices.get(start..end).unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_partition_indices() {
+        let indices = [0, 3, 4, 9, 10, 11, 20];
+        assert_eq!(indices_in_range(&indices, 0..4), &[0, 3]);
+        assert_eq!(indices_in_range(&indices, 4..10), &[4, 9]);
+        assert_eq!(indices_in_range(&indices, 10..20), &[10, 11]);
+        assert_eq!(indices_in_range(&indices, 12..20), &[] as &[usize]);
+        assert_eq!(indices_in_range(&indices, 20..21), &[20]);
+
+        let chunked = (0..21)
+            .step_by(4)
+            .flat_map(|start| indices_in_range(&indices, start..start + 4))
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(chunked, indices);
+    }
+}
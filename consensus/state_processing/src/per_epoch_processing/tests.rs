@@ -1,10 +1,19 @@
 #![cfg(test)]
-use crate::per_epoch_processing::process_epoch;
+use crate::common::{decrease_balance, increase_balance};
+use crate::per_epoch_processing::altair::rewards_and_penalties::{
+    get_flag_index_deltas, get_inactivity_penalty_deltas,
+};
+use crate::per_epoch_processing::altair::{process_rewards_and_penalties, ParticipationCache};
+use crate::per_epoch_processing::effective_balance_updates::process_effective_balance_updates;
+use crate::per_epoch_processing::parallel::VALIDATOR_CHUNK_SIZE;
+use crate::per_epoch_processing::{process_epoch, Delta};
+use crate::state_advance::complete_state_advance;
 use beacon_chain::test_utils::BeaconChainHarness;
 use beacon_chain::types::{EthSpec, MinimalEthSpec};
 use bls::Hash256;
 use env_logger::{Builder, Env};
-use types::Slot;
+use types::consts::altair::PARTICIPATION_FLAG_WEIGHTS;
+use types::{Epoch, Slot};
 
 #[tokio::test]
 async fn runs_without_error() {
@@ -38,6 +47,103 @@ async fn runs_without_error() {
     process_epoch(&mut new_head_state, &spec).unwrap();
 }
 
+#[tokio::test]
+async fn chunked_processing_matches_sequential() {
+    // Enough validators to fill more than two chunks.
+    let validator_count = 2 * VALIDATOR_CHUNK_SIZE + 100;
+
+    let mut spec = MinimalEthSpec::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec.clone())
+        .deterministic_keypairs(validator_count)
+        .fresh_ephemeral_store()
+        .build();
+
+    let mut state = harness.get_current_state();
+    let target_slot = Epoch::new(2).end_slot(MinimalEthSpec::slots_per_epoch());
+    complete_state_advance(&mut state, None, target_slot, &spec).unwrap();
+
+    // Vary the participation, inactivity scores and balances of the validators so that each chunk
+    // contains rewards, penalties and effective balance changes.
+    let previous_epoch = state.previous_epoch();
+    for (index, flags) in state
+        .get_epoch_participation_mut(previous_epoch)
+        .unwrap()
+        .iter_mut()
+        .enumerate()
+    {
+        for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
+            if (index + flag_index) % 3 != 0 {
+                flags.add_flag(flag_index).unwrap();
+            }
+        }
+    }
+    for (index, score) in state
+        .inactivity_scores_mut()
+        .unwrap()
+        .iter_mut()
+        .enumerate()
+    {
+        *score = (index % 5) as u64;
+    }
+    for index in (0..validator_count).step_by(97) {
+        *state.get_balance_mut(index).unwrap() -= spec.effective_balance_increment * 2;
+    }
+    state.build_all_committee_caches(&spec).unwrap();
+    let balances_before = state.balances().clone();
+    let participation_cache = ParticipationCache::new(&state, &spec).unwrap();
+
+    // Compute the deltas of all validators at once.
+    let mut expected = state.clone();
+    let mut deltas = vec![Delta::default(); validator_count];
+    let total_active_balance = participation_cache.current_epoch_total_active_balance();
+    for flag_index in 0..PARTICIPATION_FLAG_WEIGHTS.len() {
+        get_flag_index_deltas(
+            &mut deltas,
+            &expected,
+            flag_index,
+            total_active_balance,
+            &participation_cache,
+            &spec,
+        )
+        .unwrap();
+    }
+    get_inactivity_penalty_deltas(&mut deltas, &expected, &participation_cache, &spec).unwrap();
+    for (index, delta) in deltas.into_iter().enumerate() {
+        increase_balance(&mut expected, index, delta.rewards).unwrap();
+        decrease_balance(&mut expected, index, delta.penalties).unwrap();
+    }
+
+    let mut actual = state;
+    process_rewards_and_penalties(&mut actual, &participation_cache, &spec).unwrap();
+    assert_eq!(actual.balances(), expected.balances());
+    assert_ne!(actual.balances(), &balances_before);
+
+    // Update the effective balances one validator at a time.
+    let hysteresis_increment = spec.effective_balance_increment / spec.hysteresis_quotient;
+    let downward_threshold = hysteresis_increment * spec.hysteresis_downward_multiplier;
+    let upward_threshold = hysteresis_increment * spec.hysteresis_upward_multiplier;
+    let mut changed = 0;
+    for index in 0..validator_count {
+        let balance = expected.balances()[index];
+        let validator = expected.get_validator_mut(index).unwrap();
+        if balance + downward_threshold < validator.effective_balance
+            || validator.effective_balance + upward_threshold < balance
+        {
+            validator.effective_balance = std::cmp::min(
+                balance - balance % spec.effective_balance_increment,
+                spec.max_effective_balance,
+            );
+            changed += 1;
+        }
+    }
+    assert!(changed > 2);
+
+    process_effective_balance_updates(&mut actual, None, &spec).unwrap();
+    assert_eq!(actual.validators(), expected.validators());
+}
+
 #[cfg(not(debug_assertions))]
 mod release_tests {
     use super::*;
@@ -45,7 +151,7 @@ mod release_tests {
         per_slot_processing::per_slot_processing, EpochProcessingError, SlotProcessingError,
     };
     use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
-    use types::{Epoch, ForkName, InconsistentFork, MainnetEthSpec};
+    use types::{ForkName, InconsistentFork, MainnetEthSpec};
 
     #[tokio::test]
     async fn altair_state_on_base_fork() {
@@ -1,4 +1,4 @@
-use crate::upgrade::{upgrade_to_altair, upgrade_to_bellatrix, upgrade_to_capella};
+use crate::upgrade::UpgradeRegistry;
 use crate::{per_epoch_processing::EpochProcessingSummary, *};
 use safe_arith::{ArithError, SafeArith};
 use types::*;
@@ -47,18 +47,8 @@ pub fn per_slot_processing<T: EthSpec>(
     // Process fork upgrades here. Note that multiple upgrades can potentially run
     // in sequence if they are scheduled in the same Epoch (common in testnets)
     if state.slot().safe_rem(T::slots_per_epoch())? == 0 {
-        // If the Altair fork epoch is reached, perform an irregular state upgrade.
-        if spec.altair_fork_epoch == Some(state.current_epoch()) {
-            upgrade_to_altair(state, spec)?;
-        }
-        // If the Merge fork epoch is reached, perform an irregular state upgrade.
-        if spec.bellatrix_fork_epoch == Some(state.current_epoch()) {
-            upgrade_to_bellatrix(state, spec)?;
-        }
-        // Capella.
-        if spec.capella_fork_epoch == Some(state.current_epoch()) {
-            upgrade_to_capella(state, spec)?;
-        }
+        let epoch = state.current_epoch();
+        UpgradeRegistry::from_spec(spec).upgrade_at_epoch(state, epoch, spec)?;
     }
 
     Ok(summary)
@@ -1,7 +1,9 @@
 pub mod altair;
 pub mod capella;
 pub mod merge;
+pub mod registry;
 
 pub use altair::upgrade_to_altair;
 pub use capella::upgrade_to_capella;
 pub use merge::upgrade_to_bellatrix;
+pub use registry::{ForkUpgrade, UpgradeFn, UpgradeRegistry};
@@ -0,0 +1,142 @@
+//! A registry of the state upgrades performed at each fork.
+//!
+//! The upgrades to the forks known to this crate are registered by `UpgradeRegistry::from_spec`,
+//! which is what `per_slot_processing` uses. Experimental forks, e.g. on devnets, can register
+//! their own upgrade and fork epoch from a separate crate and apply the registry with
+//! `upgrade_at_epoch`, without modifying this crate.
+use super::{upgrade_to_altair, upgrade_to_bellatrix, upgrade_to_capella};
+use types::{BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, ForkName};
+
+/// Transforms a state at the fork epoch into a state of the new fork.
+pub type UpgradeFn<E> = fn(&mut BeaconState<E>, &ChainSpec) -> Result<(), BeaconStateError>;
+
+/// The upgrade to a single fork.
+pub struct ForkUpgrade<E: EthSpec> {
+    pub fork_name: ForkName,
+    /// The epoch at which the upgrade is performed, or `None` if the fork is not scheduled.
+    pub fork_epoch: Option<Epoch>,
+    pub upgrade: UpgradeFn<E>,
+}
+
+/// The upgrades to perform during per-slot processing, keyed by fork.
+pub struct UpgradeRegistry<E: EthSpec> {
+    /// Upgrades scheduled for the same epoch are performed in this order.
+    upgrades: Vec<ForkUpgrade<E>>,
+}
+
+impl<E: EthSpec> UpgradeRegistry<E> {
+    /// Returns a registry without any upgrades.
+    pub fn empty() -> Self {
+        Self { upgrades: vec![] }
+    }
+
+    /// Returns a registry of the upgrades to the forks known to this crate, at the fork epochs in
+    /// `spec`.
+    pub fn from_spec(spec: &ChainSpec) -> Self {
+        let mut registry = Self::empty();
+        registry
+            .register(ForkName::Altair, spec.altair_fork_epoch, upgrade_to_altair)
+            .register(
+                ForkName::Merge,
+                spec.bellatrix_fork_epoch,
+                upgrade_to_bellatrix,
+            )
+            .register(
+                ForkName::Capella,
+                spec.capella_fork_epoch,
+                upgrade_to_capella,
+            );
+        registry
+    }
+
+    /// Registers the upgrade to `fork_name`, replacing any existing upgrade to the same fork.
+    ///
+    /// An upgrade to a fork which is not yet registered is performed after the existing upgrades
+    /// if they are scheduled for the same epoch.
+    pub fn register(
+        &mut self,
+        fork_name: ForkName,
+        fork_epoch: Option<Epoch>,
+        upgrade: UpgradeFn<E>,
+    ) -> &mut Self {
+        let fork_upgrade = ForkUpgrade {
+            fork_name,
+            fork_epoch,
+            upgrade,
+        };
+        match self
+            .upgrades
+            .iter_mut()
+            .find(|existing| existing.fork_name == fork_name)
+        {
+            Some(existing) => *existing = fork_upgrade,
+            None => self.upgrades.push(fork_upgrade),
+        }
+        self
+    }
+
+    /// Returns the upgrade to `fork_name`, if one is registered.
+    pub fn get(&self, fork_name: ForkName) -> Option<&ForkUpgrade<E>> {
+        self.upgrades
+            .iter()
+            .find(|upgrade| upgrade.fork_name == fork_name)
+    }
+
+    /// Performs each of the upgrades scheduled for `epoch` on `state`, in order.
+    pub fn upgrade_at_epoch(
+        &self,
+        state: &mut BeaconState<E>,
+        epoch: Epoch,
+        spec: &ChainSpec,
+    ) -> Result<(), BeaconStateError> {
+        self.upgrades
+            .iter()
+            .filter(|upgrade| upgrade.fork_epoch == Some(epoch))
+            .try_for_each(|upgrade| (upgrade.upgrade)(state, spec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Eth1Data, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn set_fork_epoch(state: &mut BeaconState<E>, _: &ChainSpec) -> Result<(), BeaconStateError> {
+        state.fork_mut().epoch = Epoch::new(42);
+        Ok(())
+    }
+
+    #[test]
+    fn registered_upgrades_replace_defaults() {
+        let mut spec = E::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(1));
+        spec.bellatrix_fork_epoch = None;
+        spec.capella_fork_epoch = None;
+
+        let mut registry = UpgradeRegistry::<E>::from_spec(&spec);
+        assert_eq!(
+            registry.get(ForkName::Altair).unwrap().fork_epoch,
+            Some(Epoch::new(1))
+        );
+        assert_eq!(registry.get(ForkName::Capella).unwrap().fork_epoch, None);
+        assert!(registry.get(ForkName::Base).is_none());
+
+        registry.register(ForkName::Capella, Some(Epoch::new(3)), set_fork_epoch);
+        assert_eq!(
+            registry.get(ForkName::Capella).unwrap().fork_epoch,
+            Some(Epoch::new(3))
+        );
+
+        let mut state = BeaconState::<E>::new(0, Eth1Data::default(), &spec);
+        registry
+            .upgrade_at_epoch(&mut state, Epoch::new(2), &spec)
+            .unwrap();
+        assert_eq!(state.fork().epoch, Epoch::new(0));
+        registry
+            .upgrade_at_epoch(&mut state, Epoch::new(3), &spec)
+            .unwrap();
+        assert_eq!(state.fork().epoch, Epoch::new(42));
+    }
+}
@@ -251,6 +251,13 @@ impl CommitteeCache {
         self.committees_per_slot
     }
 
+    /// Returns an estimate of the memory used by the cache, in bytes.
+    pub fn memory_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.shuffling.capacity() * std::mem::size_of::<usize>()
+            + self.shuffling_positions.capacity() * std::mem::size_of::<NonZeroUsizeOption>()
+    }
+
     /// Returns a slice of `self.shuffling` that represents the `index`'th committee in the epoch.
     ///
     /// Spec v0.12.1
@@ -1011,6 +1011,19 @@ pub struct Config {
     #[serde(default = "default_attestation_subnet_prefix_bits")]
     #[serde(with = "serde_utils::quoted_u8")]
     attestation_subnet_prefix_bits: u8,
+    #[serde(default = "default_attestation_subnet_count")]
+    #[serde(with = "serde_utils::quoted_u64")]
+    attestation_subnet_count: u64,
+    #[serde(default = "default_epochs_per_subnet_subscription")]
+    #[serde(with = "serde_utils::quoted_u64")]
+    epochs_per_subnet_subscription: u64,
+    #[serde(default = "default_attestation_propagation_slot_range")]
+    #[serde(with = "serde_utils::quoted_u64")]
+    attestation_propagation_slot_range: u64,
+    /// The maximum gossip clock disparity, in milliseconds.
+    #[serde(default = "default_maximum_gossip_clock_disparity")]
+    #[serde(with = "serde_utils::quoted_u64")]
+    maximum_gossip_clock_disparity: u64,
 }
 
 fn default_bellatrix_fork_version() -> [u8; 4] {
@@ -1087,6 +1100,22 @@ const fn default_attestation_subnet_prefix_bits() -> u8 {
     6
 }
 
+const fn default_attestation_subnet_count() -> u64 {
+    64
+}
+
+const fn default_epochs_per_subnet_subscription() -> u64 {
+    256
+}
+
+const fn default_attestation_propagation_slot_range() -> u64 {
+    32
+}
+
+const fn default_maximum_gossip_clock_disparity() -> u64 {
+    500
+}
+
 impl Default for Config {
     fn default() -> Self {
         let chain_spec = MainnetEthSpec::default_spec();
@@ -1192,6 +1221,10 @@ impl Config {
             message_domain_valid_snappy: spec.message_domain_valid_snappy,
             attestation_subnet_extra_bits: spec.attestation_subnet_extra_bits,
             attestation_subnet_prefix_bits: spec.attestation_subnet_prefix_bits,
+            attestation_subnet_count: spec.attestation_subnet_count,
+            epochs_per_subnet_subscription: spec.epochs_per_subnet_subscription,
+            attestation_propagation_slot_range: spec.attestation_propagation_slot_range,
+            maximum_gossip_clock_disparity: spec.maximum_gossip_clock_disparity_millis,
         }
     }
 
@@ -1245,6 +1278,10 @@ impl Config {
             message_domain_valid_snappy,
             attestation_subnet_extra_bits,
             attestation_subnet_prefix_bits,
+            attestation_subnet_count,
+            epochs_per_subnet_subscription,
+            attestation_propagation_slot_range,
+            maximum_gossip_clock_disparity,
         } = self;
 
         if preset_base != T::spec_name().to_string().as_str() {
@@ -1291,6 +1328,10 @@ impl Config {
             message_domain_valid_snappy,
             attestation_subnet_extra_bits,
             attestation_subnet_prefix_bits,
+            attestation_subnet_count,
+            epochs_per_subnet_subscription,
+            attestation_propagation_slot_range,
+            maximum_gossip_clock_disparity_millis: maximum_gossip_clock_disparity,
             ..chain_spec.clone()
         })
     }
@@ -1560,6 +1601,10 @@ mod yaml_tests {
         check_default!(message_domain_valid_snappy);
         check_default!(attestation_subnet_extra_bits);
         check_default!(attestation_subnet_prefix_bits);
+        check_default!(attestation_subnet_count);
+        check_default!(epochs_per_subnet_subscription);
+        check_default!(attestation_propagation_slot_range);
+        check_default!(maximum_gossip_clock_disparity);
 
         assert_eq!(chain_spec.bellatrix_fork_epoch, None);
     }
@@ -76,6 +76,8 @@ pub fn get_extra_fields(spec: &ChainSpec) -> HashMap<String, Value> {
     let u8_hex = |v: u8| hex_string(&v.to_le_bytes());
     hashmap! {
         "bls_withdrawal_prefix".to_uppercase() => u8_hex(spec.bls_withdrawal_prefix_byte),
+        "eth1_address_withdrawal_prefix".to_uppercase() =>
+            u8_hex(spec.eth1_address_withdrawal_prefix_byte),
         "domain_beacon_proposer".to_uppercase() => u32_hex(spec.domain_beacon_proposer),
         "domain_beacon_attester".to_uppercase() => u32_hex(spec.domain_beacon_attester),
         "domain_randao".to_uppercase()=> u32_hex(spec.domain_randao),
@@ -91,6 +93,8 @@ pub fn get_extra_fields(spec: &ChainSpec) -> HashMap<String, Value> {
         "domain_sync_committee".to_uppercase() => u32_hex(spec.domain_sync_committee),
         "domain_sync_committee_selection_proof".to_uppercase() =>
             u32_hex(spec.domain_sync_committee_selection_proof),
+        "domain_bls_to_execution_change".to_uppercase() =>
+            u32_hex(spec.domain_bls_to_execution_change),
         "sync_committee_subnet_count".to_uppercase() =>
             altair::SYNC_COMMITTEE_SUBNET_COUNT.to_string().into(),
         "target_aggregators_per_sync_subcommittee".to_uppercase() =>
@@ -101,7 +105,7 @@ pub fn get_extra_fields(spec: &ChainSpec) -> HashMap<String, Value> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::MainnetEthSpec;
+    use crate::{Epoch, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
     use std::fs::File;
     use tempfile::NamedTempFile;
 
@@ -136,4 +140,48 @@ mod test {
             serde_yaml::from_reader(reader).expect("error while deserializing");
         assert_eq!(ConfigAndPreset::Capella(from), yamlconfig);
     }
+
+    /// Serve `spec` as the beacon node does on `/eth/v1/config/spec` and parse it as the validator
+    /// client does, returning the resulting spec.
+    fn api_round_trip<E: EthSpec>(spec: &ChainSpec) -> ChainSpec {
+        let json = serde_json::to_string(&ConfigAndPreset::from_chain_spec::<E>(spec, None))
+            .expect("should serialize");
+        let config: Config = serde_json::from_str(&json).expect("should deserialize");
+        ChainSpec::from_config::<E>(&config).expect("preset should match")
+    }
+
+    #[test]
+    fn config_api_round_trip() {
+        let mainnet = ChainSpec::mainnet();
+        assert_eq!(api_round_trip::<MainnetEthSpec>(&mainnet), mainnet);
+        let minimal = ChainSpec::minimal();
+        assert_eq!(api_round_trip::<MinimalEthSpec>(&minimal), minimal);
+        let gnosis = ChainSpec::gnosis();
+        assert_eq!(api_round_trip::<GnosisEthSpec>(&gnosis), gnosis);
+    }
+
+    #[test]
+    fn config_api_round_trip_runtime_values() {
+        let mut spec = ChainSpec::mainnet();
+        spec.config_name = Some("custom".into());
+        spec.altair_fork_epoch = Some(Epoch::new(1));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(2));
+        spec.capella_fork_epoch = None;
+        spec.seconds_per_slot = 6;
+        spec.subnets_per_node = 4;
+        spec.attestation_subnet_count = 128;
+        spec.epochs_per_subnet_subscription = 64;
+        spec.attestation_propagation_slot_range = 16;
+        spec.maximum_gossip_clock_disparity_millis = 250;
+        spec.ttfb_timeout = 10;
+        assert_eq!(api_round_trip::<MainnetEthSpec>(&spec), spec);
+
+        // A mismatched preset must be rejected rather than silently applied.
+        let json = serde_json::to_string(&ConfigAndPreset::from_chain_spec::<MainnetEthSpec>(
+            &spec, None,
+        ))
+        .unwrap();
+        let config: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(ChainSpec::from_config::<MinimalEthSpec>(&config), None);
+    }
 }
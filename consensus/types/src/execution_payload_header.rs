@@ -52,7 +52,7 @@ pub struct ExecutionPayloadHeader<T: EthSpec> {
     pub state_root: Hash256,
     #[superstruct(getter(copy))]
     pub receipts_root: Hash256,
-    #[serde(with = "ssz_types::serde_utils::hex_fixed_vec")]
+    #[serde(with = "ssz_hex")]
     pub logs_bloom: FixedVector<u8, T::BytesPerLogsBloom>,
     #[superstruct(getter(copy))]
     pub prev_randao: Hash256,
@@ -68,7 +68,7 @@ pub struct ExecutionPayloadHeader<T: EthSpec> {
     #[serde(with = "serde_utils::quoted_u64")]
     #[superstruct(getter(copy))]
     pub timestamp: u64,
-    #[serde(with = "ssz_types::serde_utils::hex_var_list")]
+    #[serde(with = "ssz_hex")]
     pub extra_data: VariableList<u8, T::MaxExtraDataBytes>,
     #[serde(with = "serde_utils::quoted_u256")]
     #[superstruct(getter(copy))]
@@ -9,6 +9,8 @@ pub struct ForkContext {
     current_fork: RwLock<ForkName>,
     fork_to_digest: HashMap<ForkName, [u8; 4]>,
     digest_to_fork: HashMap<[u8; 4], ForkName>,
+    spec: ChainSpec,
+    slots_per_epoch: u64,
 }
 
 impl ForkContext {
@@ -66,6 +68,8 @@ impl ForkContext {
             current_fork: RwLock::new(spec.fork_name_at_slot::<T>(current_slot)),
             fork_to_digest,
             digest_to_fork,
+            spec: spec.clone(),
+            slots_per_epoch: T::slots_per_epoch(),
         }
     }
 
@@ -79,6 +83,12 @@ impl ForkContext {
         *self.current_fork.read()
     }
 
+    /// Returns the fork which is active at `slot`, which may differ from the `current_fork`.
+    pub fn fork_name_at_slot(&self, slot: Slot) -> ForkName {
+        self.spec
+            .fork_name_at_epoch(slot.epoch(self.slots_per_epoch))
+    }
+
     /// Updates the `current_fork` field to a new fork.
     pub fn update_current_fork(&self, new_fork: ForkName) {
         *self.current_fork.write() = new_fork;
@@ -1,4 +1,7 @@
-use super::{BeaconBlockHeader, BeaconState, EthSpec, FixedVector, Hash256, SyncCommittee};
+use super::{
+    BeaconBlockHeader, BeaconState, EthSpec, FixedVector, ForkName, ForkVersionDeserialize,
+    Hash256, SyncCommittee,
+};
 use crate::{light_client_update::*, test_utils::TestRandom};
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
@@ -44,6 +47,26 @@ impl<T: EthSpec> LightClientBootstrap<T> {
     }
 }
 
+impl<T: EthSpec> ForkVersionDeserialize for LightClientBootstrap<T> {
+    fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
+        value: serde_json::value::Value,
+        fork_name: ForkName,
+    ) -> Result<Self, D::Error> {
+        match fork_name {
+            ForkName::Base => Err(serde::de::Error::custom(format!(
+                "LightClientBootstrap failed to deserialize: unsupported fork '{}'",
+                fork_name
+            ))),
+            _ => serde_json::from_value(value).map_err(|e| {
+                serde::de::Error::custom(format!(
+                    "LightClientBootstrap failed to deserialize: {:?}",
+                    e
+                ))
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
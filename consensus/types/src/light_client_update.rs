@@ -1,4 +1,7 @@
-use super::{BeaconBlockHeader, EthSpec, FixedVector, Hash256, Slot, SyncAggregate, SyncCommittee};
+use super::{
+    BeaconBlockHeader, EthSpec, FixedVector, ForkName, ForkVersionDeserialize, Hash256, Slot,
+    SyncAggregate, SyncCommittee,
+};
 use crate::{beacon_state, test_utils::TestRandom, BeaconBlock, BeaconState, ChainSpec};
 use safe_arith::ArithError;
 use serde_derive::{Deserialize, Serialize};
@@ -139,6 +142,26 @@ impl<T: EthSpec> LightClientUpdate<T> {
     }
 }
 
+impl<T: EthSpec> ForkVersionDeserialize for LightClientUpdate<T> {
+    fn deserialize_by_fork<'de, D: serde::Deserializer<'de>>(
+        value: serde_json::value::Value,
+        fork_name: ForkName,
+    ) -> Result<Self, D::Error> {
+        match fork_name {
+            ForkName::Base => Err(serde::de::Error::custom(format!(
+                "LightClientUpdate failed to deserialize: unsupported fork '{}'",
+                fork_name
+            ))),
+            _ => serde_json::from_value(value).map_err(|e| {
+                serde::de::Error::custom(format!(
+                    "LightClientUpdate failed to deserialize: {:?}",
+                    e
+                ))
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +121,19 @@ impl<E: EthSpec, Payload: AbstractExecPayload<E>> SignedBeaconBlock<E, Payload>
         Ok(Self::from_block(message, signature))
     }
 
+    /// Read the slot of an SSZ-encoded block without decoding the rest of it.
+    pub fn slot_from_ssz_bytes(bytes: &[u8]) -> Result<Slot, ssz::DecodeError> {
+        Self::from_ssz_bytes_view(bytes).map(|view| view.slot())
+    }
+
+    /// Returns a view of an SSZ-encoded block which reads the fields of the block header without
+    /// decoding the body.
+    pub fn from_ssz_bytes_view(
+        bytes: &[u8],
+    ) -> Result<SignedBeaconBlockView<'_>, ssz::DecodeError> {
+        SignedBeaconBlockView::from_ssz_bytes(bytes)
+    }
+
     /// Create a new `SignedBeaconBlock` from a `BeaconBlock` and `Signature`.
     pub fn from_block(block: BeaconBlock<E, Payload>, signature: Signature) -> Self {
         match block {
@@ -414,6 +427,88 @@ impl<E: EthSpec> From<SignedBeaconBlock<E>> for SignedBlindedBeaconBlock<E> {
     }
 }
 
+/// A borrowed view of an SSZ-encoded `SignedBeaconBlock`.
+///
+/// The fixed-length fields at the start of the `BeaconBlock` are identical for all forks, so they
+/// can be read without knowing the fork or decoding the body, which avoids allocating the lists in
+/// the body when only the header fields are required.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignedBeaconBlockView<'a> {
+    message: &'a [u8],
+    signature: &'a [u8],
+}
+
+impl<'a> SignedBeaconBlockView<'a> {
+    const SLOT_OFFSET: usize = 0;
+    const PROPOSER_INDEX_OFFSET: usize = 8;
+    const PARENT_ROOT_OFFSET: usize = 16;
+    const STATE_ROOT_OFFSET: usize = 48;
+    /// The length of the fixed-length fields of a `BeaconBlock`, including the body offset.
+    const MESSAGE_FIXED_LEN: usize = 84;
+
+    pub fn from_ssz_bytes(bytes: &'a [u8]) -> Result<Self, ssz::DecodeError> {
+        let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+        builder.register_anonymous_variable_length_item()?;
+        builder.register_type::<Signature>()?;
+
+        let mut decoder = builder.build()?;
+
+        let message: &'a [u8] = decoder.decode_next_with(Ok)?;
+        let signature: &'a [u8] = decoder.decode_next_with(Ok)?;
+
+        if message.len() < Self::MESSAGE_FIXED_LEN {
+            return Err(ssz::DecodeError::InvalidByteLength {
+                len: message.len(),
+                expected: Self::MESSAGE_FIXED_LEN,
+            });
+        }
+
+        Ok(Self { message, signature })
+    }
+
+    pub fn slot(&self) -> Slot {
+        Slot::new(self.read_u64(Self::SLOT_OFFSET))
+    }
+
+    pub fn proposer_index(&self) -> u64 {
+        self.read_u64(Self::PROPOSER_INDEX_OFFSET)
+    }
+
+    pub fn parent_root(&self) -> Hash256 {
+        self.read_root(Self::PARENT_ROOT_OFFSET)
+    }
+
+    pub fn state_root(&self) -> Hash256 {
+        self.read_root(Self::STATE_ROOT_OFFSET)
+    }
+
+    /// Returns the name of the fork of the block, as dictated by its slot.
+    pub fn fork_name<E: EthSpec>(&self, spec: &ChainSpec) -> ForkName {
+        spec.fork_name_at_slot::<E>(self.slot())
+    }
+
+    /// The SSZ bytes of the `BeaconBlock`.
+    pub fn message_bytes(&self) -> &'a [u8] {
+        self.message
+    }
+
+    /// The SSZ bytes of the proposer signature, which have not been checked for validity.
+    pub fn signature_bytes(&self) -> &'a [u8] {
+        self.signature
+    }
+
+    fn read_u64(&self, offset: usize) -> u64 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.message[offset..offset + 8]);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn read_root(&self, offset: usize) -> Hash256 {
+        Hash256::from_slice(&self.message[offset..offset + 32])
+    }
+}
+
 // We can blind borrowed blocks with payloads by converting the payload into a header (without
 // cloning the payload contents).
 impl<E: EthSpec> SignedBeaconBlock<E> {
@@ -481,4 +576,58 @@ mod test {
             assert_eq!(reconstructed, block);
         }
     }
+
+    #[test]
+    fn slot_from_ssz_bytes() {
+        type E = MainnetEthSpec;
+
+        let spec = &E::default_spec();
+        let mut message = BeaconBlockMerge::<E, BlindedPayload<E>>::empty(spec);
+        message.slot = Slot::new(1234);
+        let block =
+            SignedBlindedBeaconBlock::from_block(BeaconBlock::Merge(message), Signature::empty());
+        let bytes = ssz::Encode::as_ssz_bytes(&block);
+
+        assert_eq!(
+            SignedBlindedBeaconBlock::<E>::slot_from_ssz_bytes(&bytes),
+            Ok(Slot::new(1234))
+        );
+        assert!(SignedBlindedBeaconBlock::<E>::slot_from_ssz_bytes(&bytes[..50]).is_err());
+    }
+
+    #[test]
+    fn from_ssz_bytes_view() {
+        type E = MainnetEthSpec;
+
+        let spec = &E::default_spec();
+        let mut message = BeaconBlockCapella::<E>::empty(spec);
+        message.slot = Slot::new(1234);
+        message.proposer_index = 42;
+        message.parent_root = Hash256::repeat_byte(1);
+        message.state_root = Hash256::repeat_byte(2);
+        let block =
+            SignedBeaconBlock::from_block(BeaconBlock::Capella(message), Signature::empty());
+        let bytes = ssz::Encode::as_ssz_bytes(&block);
+
+        let view = SignedBeaconBlock::<E>::from_ssz_bytes_view(&bytes).unwrap();
+        assert_eq!(view.slot(), block.slot());
+        assert_eq!(view.proposer_index(), block.message().proposer_index());
+        assert_eq!(view.parent_root(), block.parent_root());
+        assert_eq!(view.state_root(), block.state_root());
+        let (message, signature) = block.clone().deconstruct();
+        assert_eq!(
+            view.message_bytes(),
+            ssz::Encode::as_ssz_bytes(&message).as_slice()
+        );
+        assert_eq!(view.signature_bytes(), signature.serialize().as_slice());
+
+        // The block must be long enough to contain the header fields.
+        let truncated = SignedBeaconBlock::<E>::from_block(
+            BeaconBlock::Capella(BeaconBlockCapella::empty(spec)),
+            Signature::empty(),
+        );
+        let mut bytes = ssz::Encode::as_ssz_bytes(&truncated);
+        bytes.truncate(100 + 80);
+        assert!(SignedBeaconBlock::<E>::from_ssz_bytes_view(&bytes).is_err());
+    }
 }
@@ -0,0 +1,240 @@
+//! Serialize SSZ byte lists and byte vectors as `0x`-prefixed hex strings.
+//!
+//! Use `#[serde(with = "ssz_hex")]` for a single `VariableList<u8, N>` or `FixedVector<u8, N>`,
+//! and `#[serde(with = "ssz_hex::list")]` for a list of them, such as `Transactions`.
+//!
+//! When deserializing, the length of the hex string is checked against the maximum length of the
+//! type before it is decoded, and lists are checked against their maximum length as each element
+//! is read, so oversized inputs are rejected without decoding them.
+use crate::{FixedVector, Unsigned, VariableList};
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// An SSZ byte list or byte vector which may be represented as a hex string.
+pub trait SszHexBytes: Sized {
+    /// The maximum number of bytes, or the exact number for fixed-length types.
+    fn max_len() -> usize;
+
+    fn as_bytes(&self) -> &[u8];
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, String>;
+}
+
+impl<N: Unsigned> SszHexBytes for FixedVector<u8, N> {
+    fn max_len() -> usize {
+        N::to_usize()
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+        if bytes.len() != N::to_usize() {
+            return Err(format!(
+                "incorrect byte length {}, expected {}",
+                bytes.len(),
+                N::to_usize()
+            ));
+        }
+        FixedVector::new(bytes).map_err(|e| format!("invalid length: {:?}", e))
+    }
+}
+
+impl<N: Unsigned> SszHexBytes for VariableList<u8, N> {
+    fn max_len() -> usize {
+        N::to_usize()
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_bytes(bytes: Vec<u8>) -> Result<Self, String> {
+        VariableList::new(bytes).map_err(|e| format!("invalid length: {:?}", e))
+    }
+}
+
+/// Decodes `hex_str`, checking its length before decoding it.
+fn decode<T: SszHexBytes>(hex_str: &str) -> Result<T, String> {
+    let hex_len = hex_str
+        .strip_prefix("0x")
+        .ok_or_else(|| "hex must have 0x prefix".to_string())?
+        .len();
+    if hex_len > T::max_len().saturating_mul(2) {
+        return Err(format!(
+            "byte length {} exceeds maximum of {}",
+            hex_len / 2,
+            T::max_len()
+        ));
+    }
+    let bytes = serde_utils::hex::decode(hex_str)?;
+    T::from_bytes(bytes)
+}
+
+struct HexVisitor<T>(PhantomData<T>);
+
+impl<'de, T: SszHexBytes> Visitor<'de> for HexVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a 0x-prefixed hex string of at most {} bytes",
+            T::max_len()
+        )
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<T, E> {
+        decode(value).map_err(E::custom)
+    }
+}
+
+/// Wraps `T` so that it can be used as an element of a serialized sequence.
+struct SszHex<T>(T);
+
+impl<T: SszHexBytes> Serialize for SszHex<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.0, serializer)
+    }
+}
+
+impl<'de, T: SszHexBytes> Deserialize<'de> for SszHex<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(SszHex)
+    }
+}
+
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: SszHexBytes,
+{
+    serializer.serialize_str(&serde_utils::hex::encode(value.as_bytes()))
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: SszHexBytes,
+{
+    deserializer.deserialize_str(HexVisitor(PhantomData))
+}
+
+/// Serialize a `VariableList` of byte lists or byte vectors as a list of hex strings.
+///
+/// E.g., `["0x0102", "0x"]`
+pub mod list {
+    use super::*;
+
+    struct ListVisitor<T, N>(PhantomData<(T, N)>);
+
+    impl<'de, T: SszHexBytes, N: Unsigned> Visitor<'de> for ListVisitor<T, N> {
+        type Value = VariableList<T, N>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a list of at most {} hex strings", N::to_usize())
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let max_len = N::to_usize();
+            let mut list = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(max_len));
+            while let Some(SszHex(element)) = seq.next_element()? {
+                if list.len() >= max_len {
+                    return Err(A::Error::custom(format!(
+                        "list length exceeds maximum of {}",
+                        max_len
+                    )));
+                }
+                list.push(element);
+            }
+            VariableList::new(list)
+                .map_err(|e| A::Error::custom(format!("invalid length: {:?}", e)))
+        }
+    }
+
+    pub fn serialize<S, T, N>(value: &VariableList<T, N>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: SszHexBytes,
+        N: Unsigned,
+    {
+        let mut seq = serializer.serialize_seq(Some(value.len()))?;
+        for element in value.iter() {
+            seq.serialize_element(&SszHex(element))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D, T, N>(deserializer: D) -> Result<VariableList<T, N>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: SszHexBytes,
+        N: Unsigned,
+    {
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::typenum::{U2, U4};
+    use serde_derive::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Container {
+        #[serde(with = "crate::ssz_hex")]
+        fixed: FixedVector<u8, U2>,
+        #[serde(with = "crate::ssz_hex")]
+        variable: VariableList<u8, U4>,
+        #[serde(with = "crate::ssz_hex::list")]
+        list: VariableList<VariableList<u8, U4>, U2>,
+    }
+
+    fn from_json(json: &str) -> Result<Container, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn round_trip() {
+        let container = Container {
+            fixed: FixedVector::new(vec![1, 2]).unwrap(),
+            variable: VariableList::new(vec![3]).unwrap(),
+            list: VariableList::new(vec![
+                VariableList::new(vec![4, 5, 6, 7]).unwrap(),
+                VariableList::empty(),
+            ])
+            .unwrap(),
+        };
+        let json = serde_json::to_string(&container).unwrap();
+        assert_eq!(
+            json,
+            r#"{"fixed":"0x0102","variable":"0x03","list":["0x04050607","0x"]}"#
+        );
+        assert_eq!(from_json(&json).unwrap(), container);
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        // Fixed vectors must have the exact length.
+        assert!(from_json(r#"{"fixed":"0x01","variable":"0x","list":[]}"#).is_err());
+        assert!(from_json(r#"{"fixed":"0x010203","variable":"0x","list":[]}"#).is_err());
+        // Variable lists must not exceed their maximum length.
+        assert!(from_json(r#"{"fixed":"0x0102","variable":"0x0102030405","list":[]}"#).is_err());
+        assert!(
+            from_json(r#"{"fixed":"0x0102","variable":"0x","list":["0x","0x","0x"]}"#).is_err()
+        );
+        assert!(
+            from_json(r#"{"fixed":"0x0102","variable":"0x","list":["0x0102030405"]}"#).is_err()
+        );
+        // Hex must be prefixed.
+        assert!(from_json(r#"{"fixed":"0102","variable":"0x","list":[]}"#).is_err());
+    }
+}
@@ -0,0 +1,703 @@
+//! A naive implementation of SSZ merkleization, used as a reference for `tree_hash`.
+//!
+//! The root is computed from the SSZ encoding of a value and a `Schema` describing its type,
+//! following the consensus specs as literally as possible. It shares no code with `tree_hash` and
+//! makes no attempt to be fast.
+use ethereum_hashing::hash;
+
+const BYTES_PER_CHUNK: usize = 32;
+const BYTES_PER_LENGTH_OFFSET: usize = 4;
+
+type Chunk = [u8; BYTES_PER_CHUNK];
+
+/// The SSZ type of a value.
+#[derive(Debug, Clone)]
+pub enum Schema {
+    /// An unsigned integer or boolean of the given number of bytes.
+    Basic(usize),
+    /// A vector of the given number of bytes, e.g. a root or a public key.
+    Bytes(usize),
+    Container(Vec<Schema>),
+    Vector(Box<Schema>, usize),
+    List(Box<Schema>, usize),
+    Bitvector(usize),
+    Bitlist(usize),
+}
+
+impl Schema {
+    pub fn container(fields: impl IntoIterator<Item = Schema>) -> Self {
+        Schema::Container(fields.into_iter().collect())
+    }
+
+    pub fn vector(element: Schema, length: usize) -> Self {
+        Schema::Vector(Box::new(element), length)
+    }
+
+    pub fn list(element: Schema, limit: usize) -> Self {
+        Schema::List(Box::new(element), limit)
+    }
+
+    /// The length of the SSZ encoding of a value, if it is fixed.
+    pub fn fixed_len(&self) -> Option<usize> {
+        match self {
+            Schema::Basic(len) | Schema::Bytes(len) => Some(*len),
+            Schema::Container(fields) => fields.iter().map(Schema::fixed_len).sum(),
+            Schema::Vector(element, length) => element.fixed_len().map(|len| len * length),
+            Schema::Bitvector(bits) => Some((bits + 7) / 8),
+            Schema::List(..) | Schema::Bitlist(_) => None,
+        }
+    }
+
+    /// Computes the `hash_tree_root` of the SSZ-encoded `bytes`.
+    ///
+    /// Panics if `bytes` are not a valid encoding of a value of this type.
+    pub fn hash_tree_root(&self, bytes: &[u8]) -> Chunk {
+        match self {
+            Schema::Basic(len) => {
+                assert_eq!(bytes.len(), *len, "invalid basic value");
+                pack(bytes)[0]
+            }
+            Schema::Bytes(len) => {
+                assert_eq!(bytes.len(), *len, "invalid byte vector");
+                merkleize(pack(bytes), None)
+            }
+            Schema::Container(fields) => {
+                let roots = split_container(fields, bytes)
+                    .into_iter()
+                    .zip(fields)
+                    .map(|(bytes, field)| field.hash_tree_root(bytes))
+                    .collect();
+                merkleize(roots, None)
+            }
+            Schema::Vector(element, length) => {
+                let elements = split_elements(element, bytes);
+                assert_eq!(elements.len(), *length, "invalid vector length");
+                match **element {
+                    Schema::Basic(_) => merkleize(pack(bytes), None),
+                    _ => merkleize(roots(element, &elements), None),
+                }
+            }
+            Schema::List(element, limit) => {
+                let elements = split_elements(element, bytes);
+                assert!(elements.len() <= *limit, "list exceeds limit");
+                let root = match **element {
+                    Schema::Basic(len) => merkleize(
+                        pack(bytes),
+                        Some((limit * len + BYTES_PER_CHUNK - 1) / BYTES_PER_CHUNK),
+                    ),
+                    _ => merkleize(roots(element, &elements), Some(*limit)),
+                };
+                mix_in_length(root, elements.len())
+            }
+            Schema::Bitvector(bits) => {
+                assert_eq!(bytes.len(), (bits + 7) / 8, "invalid bitvector");
+                merkleize(pack(bytes), Some(chunk_count_for_bits(*bits)))
+            }
+            Schema::Bitlist(limit) => {
+                let (bits, length) = bitlist_bits(bytes);
+                assert!(length <= *limit, "bitlist exceeds limit");
+                let root = merkleize(pack(&bits), Some(chunk_count_for_bits(*limit)));
+                mix_in_length(root, length)
+            }
+        }
+    }
+}
+
+fn roots(element: &Schema, elements: &[&[u8]]) -> Vec<Chunk> {
+    elements
+        .iter()
+        .map(|bytes| element.hash_tree_root(bytes))
+        .collect()
+}
+
+fn chunk_count_for_bits(bits: usize) -> usize {
+    (bits + 255) / 256
+}
+
+/// Splits the encoding of a container into the encodings of its fields.
+fn split_container<'a>(fields: &[Schema], bytes: &'a [u8]) -> Vec<&'a [u8]> {
+    let mut position = 0;
+    // Fixed-length fields, or the offset of each variable-length field.
+    let mut parts = vec![];
+    for field in fields {
+        match field.fixed_len() {
+            Some(len) => {
+                parts.push(Ok(&bytes[position..position + len]));
+                position += len;
+            }
+            None => {
+                parts.push(Err(read_offset(bytes, position)));
+                position += BYTES_PER_LENGTH_OFFSET;
+            }
+        }
+    }
+
+    let offsets = parts
+        .iter()
+        .filter_map(|part| part.err())
+        .chain(std::iter::once(bytes.len()))
+        .collect::<Vec<_>>();
+    assert!(
+        offsets.first().map_or(true, |offset| *offset == position),
+        "invalid first offset"
+    );
+
+    let mut variable_parts = offsets
+        .windows(2)
+        .map(|window| &bytes[window[0]..window[1]]);
+    parts
+        .into_iter()
+        .map(|part| part.unwrap_or_else(|_| variable_parts.next().expect("offset exists")))
+        .collect()
+}
+
+/// Splits the encoding of a vector or list into the encodings of its elements.
+fn split_elements<'a>(element: &Schema, bytes: &'a [u8]) -> Vec<&'a [u8]> {
+    if bytes.is_empty() {
+        return vec![];
+    }
+
+    match element.fixed_len() {
+        Some(len) => {
+            assert_eq!(bytes.len() % len, 0, "invalid element length");
+            bytes.chunks(len).collect()
+        }
+        None => {
+            let first_offset = read_offset(bytes, 0);
+            let mut offsets = (0..first_offset / BYTES_PER_LENGTH_OFFSET)
+                .map(|i| read_offset(bytes, i * BYTES_PER_LENGTH_OFFSET))
+                .collect::<Vec<_>>();
+            offsets.push(bytes.len());
+            offsets
+                .windows(2)
+                .map(|window| &bytes[window[0]..window[1]])
+                .collect()
+        }
+    }
+}
+
+fn read_offset(bytes: &[u8], position: usize) -> usize {
+    let mut offset = [0; BYTES_PER_LENGTH_OFFSET];
+    offset.copy_from_slice(&bytes[position..position + BYTES_PER_LENGTH_OFFSET]);
+    u32::from_le_bytes(offset) as usize
+}
+
+/// Returns the bits of a bitlist without the delimiting bit, and the number of bits.
+fn bitlist_bits(bytes: &[u8]) -> (Vec<u8>, usize) {
+    let last = *bytes.last().expect("bitlist has a delimiting bit");
+    assert_ne!(last, 0, "bitlist has a delimiting bit");
+    let delimiter = 7 - last.leading_zeros() as usize;
+    let length = (bytes.len() - 1) * 8 + delimiter;
+
+    let mut bits = bytes.to_vec();
+    *bits.last_mut().expect("bitlist is not empty") ^= 1 << delimiter;
+    bits.truncate((length + 7) / 8);
+    (bits, length)
+}
+
+/// Packs `bytes` into chunks, padding the last chunk with zeros.
+fn pack(bytes: &[u8]) -> Vec<Chunk> {
+    bytes
+        .chunks(BYTES_PER_CHUNK)
+        .map(|bytes| {
+            let mut chunk = [0; BYTES_PER_CHUNK];
+            chunk[..bytes.len()].copy_from_slice(bytes);
+            chunk
+        })
+        .collect()
+}
+
+/// Merkleizes `chunks`, padding them with zero chunks to the next power of two of `limit`, or of
+/// the number of chunks if there is no limit.
+///
+/// Limits such as that of the validator registry are too large to pad in memory, so each layer is
+/// only padded to an even length, with the root of an all-zero subtree of the layer's depth.
+fn merkleize(mut chunks: Vec<Chunk>, limit: Option<usize>) -> Chunk {
+    let count = limit.unwrap_or(chunks.len());
+    assert!(chunks.len() <= count, "too many chunks");
+    let depth = count.max(1).next_power_of_two().trailing_zeros();
+
+    let mut zero_subtree = [0; BYTES_PER_CHUNK];
+    for _ in 0..depth {
+        if chunks.len() % 2 == 1 {
+            chunks.push(zero_subtree);
+        }
+        chunks = chunks
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        zero_subtree = hash_pair(&zero_subtree, &zero_subtree);
+    }
+    chunks.first().copied().unwrap_or(zero_subtree)
+}
+
+fn mix_in_length(root: Chunk, length: usize) -> Chunk {
+    let mut length_chunk = [0; BYTES_PER_CHUNK];
+    length_chunk[..8].copy_from_slice(&(length as u64).to_le_bytes());
+    hash_pair(&root, &length_chunk)
+}
+
+fn hash_pair(left: &Chunk, right: &Chunk) -> Chunk {
+    let mut chunk = [0; BYTES_PER_CHUNK];
+    chunk.copy_from_slice(&hash(&[left.as_slice(), right.as_slice()].concat()));
+    chunk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Schema::{self, *};
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use crate::*;
+    use ssz::Encode;
+    use tree_hash::TreeHash;
+
+    /// The number of random instances of each type to check.
+    const INSTANCES: u8 = 32;
+
+    type E = MainnetEthSpec;
+
+    fn check<T: TestRandom + Encode + TreeHash>(schema: Schema) {
+        for seed in 0..INSTANCES {
+            let mut rng = XorShiftRng::from_seed([seed; 16]);
+            let value = T::random_for_test(&mut rng);
+            assert_eq!(
+                Hash256::from(schema.hash_tree_root(&value.as_ssz_bytes())),
+                value.tree_hash_root(),
+                "{} with seed {}",
+                std::any::type_name::<T>(),
+                seed
+            );
+        }
+    }
+
+    fn max_validators_per_committee() -> usize {
+        <E as EthSpec>::MaxValidatorsPerCommittee::to_usize()
+    }
+
+    fn u64() -> Schema {
+        Basic(8)
+    }
+
+    fn root() -> Schema {
+        Bytes(32)
+    }
+
+    fn version() -> Schema {
+        Bytes(4)
+    }
+
+    fn address() -> Schema {
+        Bytes(20)
+    }
+
+    fn pubkey() -> Schema {
+        Bytes(48)
+    }
+
+    fn signature() -> Schema {
+        Bytes(96)
+    }
+
+    fn checkpoint() -> Schema {
+        Schema::container([u64(), root()])
+    }
+
+    fn attestation_data() -> Schema {
+        Schema::container([u64(), u64(), root(), checkpoint(), checkpoint()])
+    }
+
+    fn beacon_block_header() -> Schema {
+        Schema::container([u64(), u64(), root(), root(), root()])
+    }
+
+    fn signed_beacon_block_header() -> Schema {
+        Schema::container([beacon_block_header(), signature()])
+    }
+
+    fn deposit_data() -> Schema {
+        Schema::container([pubkey(), root(), u64(), signature()])
+    }
+
+    fn voluntary_exit() -> Schema {
+        Schema::container([u64(), u64()])
+    }
+
+    fn indexed_attestation() -> Schema {
+        Schema::container([
+            Schema::list(u64(), max_validators_per_committee()),
+            attestation_data(),
+            signature(),
+        ])
+    }
+
+    fn bls_to_execution_change() -> Schema {
+        Schema::container([u64(), pubkey(), address()])
+    }
+
+    fn proposer_slashing() -> Schema {
+        Schema::container([signed_beacon_block_header(), signed_beacon_block_header()])
+    }
+
+    fn attester_slashing() -> Schema {
+        Schema::container([indexed_attestation(), indexed_attestation()])
+    }
+
+    fn attestation() -> Schema {
+        Schema::container([
+            Bitlist(max_validators_per_committee()),
+            attestation_data(),
+            signature(),
+        ])
+    }
+
+    fn pending_attestation() -> Schema {
+        Schema::container([
+            Bitlist(max_validators_per_committee()),
+            attestation_data(),
+            u64(),
+            u64(),
+        ])
+    }
+
+    fn deposit() -> Schema {
+        Schema::container([Schema::vector(root(), 33), deposit_data()])
+    }
+
+    fn signed_voluntary_exit() -> Schema {
+        Schema::container([voluntary_exit(), signature()])
+    }
+
+    fn sync_aggregate() -> Schema {
+        Schema::container([Bitvector(E::sync_committee_size()), signature()])
+    }
+
+    fn signed_bls_to_execution_change() -> Schema {
+        Schema::container([bls_to_execution_change(), signature()])
+    }
+
+    fn withdrawal() -> Schema {
+        Schema::container([u64(), u64(), address(), u64()])
+    }
+
+    fn eth1_data() -> Schema {
+        Schema::container([root(), u64(), root()])
+    }
+
+    fn fork() -> Schema {
+        Schema::container([version(), version(), u64()])
+    }
+
+    fn validator() -> Schema {
+        Schema::container([
+            pubkey(),
+            root(),
+            u64(),
+            Basic(1),
+            u64(),
+            u64(),
+            u64(),
+            u64(),
+        ])
+    }
+
+    fn sync_committee() -> Schema {
+        Schema::container([Schema::vector(pubkey(), E::sync_committee_size()), pubkey()])
+    }
+
+    fn byte_list(limit: usize) -> Schema {
+        Schema::list(Basic(1), limit)
+    }
+
+    /// The fields shared by execution payloads and their headers, up to and including the block
+    /// hash.
+    fn execution_payload_common() -> Vec<Schema> {
+        vec![
+            root(),
+            address(),
+            root(),
+            root(),
+            Bytes(<E as EthSpec>::BytesPerLogsBloom::to_usize()),
+            root(),
+            u64(),
+            u64(),
+            u64(),
+            u64(),
+            byte_list(<E as EthSpec>::MaxExtraDataBytes::to_usize()),
+            Basic(32),
+            root(),
+        ]
+    }
+
+    fn transactions() -> Schema {
+        Schema::list(
+            byte_list(<E as EthSpec>::MaxBytesPerTransaction::to_usize()),
+            <E as EthSpec>::MaxTransactionsPerPayload::to_usize(),
+        )
+    }
+
+    fn withdrawals() -> Schema {
+        Schema::list(
+            withdrawal(),
+            <E as EthSpec>::MaxWithdrawalsPerPayload::to_usize(),
+        )
+    }
+
+    fn execution_payload_merge() -> Schema {
+        Schema::container(
+            execution_payload_common()
+                .into_iter()
+                .chain([transactions()]),
+        )
+    }
+
+    fn execution_payload_capella() -> Schema {
+        Schema::container(
+            execution_payload_common()
+                .into_iter()
+                .chain([transactions(), withdrawals()]),
+        )
+    }
+
+    fn execution_payload_header_merge() -> Schema {
+        Schema::container(execution_payload_common().into_iter().chain([root()]))
+    }
+
+    fn execution_payload_header_capella() -> Schema {
+        Schema::container(
+            execution_payload_common()
+                .into_iter()
+                .chain([root(), root()]),
+        )
+    }
+
+    /// The fields of the body of a block in every fork.
+    fn beacon_block_body_base_fields() -> Vec<Schema> {
+        vec![
+            signature(),
+            eth1_data(),
+            root(),
+            Schema::list(
+                proposer_slashing(),
+                <E as EthSpec>::MaxProposerSlashings::to_usize(),
+            ),
+            Schema::list(
+                attester_slashing(),
+                <E as EthSpec>::MaxAttesterSlashings::to_usize(),
+            ),
+            Schema::list(attestation(), <E as EthSpec>::MaxAttestations::to_usize()),
+            Schema::list(deposit(), <E as EthSpec>::MaxDeposits::to_usize()),
+            Schema::list(
+                signed_voluntary_exit(),
+                <E as EthSpec>::MaxVoluntaryExits::to_usize(),
+            ),
+        ]
+    }
+
+    fn beacon_block_body_base() -> Schema {
+        Schema::container(beacon_block_body_base_fields())
+    }
+
+    fn beacon_block_body_altair() -> Schema {
+        Schema::container(
+            beacon_block_body_base_fields()
+                .into_iter()
+                .chain([sync_aggregate()]),
+        )
+    }
+
+    fn beacon_block_body_merge() -> Schema {
+        Schema::container(
+            beacon_block_body_base_fields()
+                .into_iter()
+                .chain([sync_aggregate(), execution_payload_merge()]),
+        )
+    }
+
+    fn beacon_block_body_capella() -> Schema {
+        Schema::container(beacon_block_body_base_fields().into_iter().chain([
+            sync_aggregate(),
+            execution_payload_capella(),
+            Schema::list(
+                signed_bls_to_execution_change(),
+                <E as EthSpec>::MaxBlsToExecutionChanges::to_usize(),
+            ),
+        ]))
+    }
+
+    fn beacon_block(body: Schema) -> Schema {
+        Schema::container([u64(), u64(), root(), root(), body])
+    }
+
+    fn validator_registry_list(element: Schema) -> Schema {
+        Schema::list(element, <E as EthSpec>::ValidatorRegistryLimit::to_usize())
+    }
+
+    /// The fields of a state in every fork, up to and including the slashings.
+    fn beacon_state_history_fields() -> Vec<Schema> {
+        let slots_per_historical_root = <E as EthSpec>::SlotsPerHistoricalRoot::to_usize();
+        vec![
+            u64(),
+            root(),
+            u64(),
+            fork(),
+            beacon_block_header(),
+            Schema::vector(root(), slots_per_historical_root),
+            Schema::vector(root(), slots_per_historical_root),
+            Schema::list(root(), <E as EthSpec>::HistoricalRootsLimit::to_usize()),
+            eth1_data(),
+            Schema::list(
+                eth1_data(),
+                <E as EthSpec>::SlotsPerEth1VotingPeriod::to_usize(),
+            ),
+            u64(),
+            validator_registry_list(validator()),
+            validator_registry_list(u64()),
+            Schema::vector(
+                root(),
+                <E as EthSpec>::EpochsPerHistoricalVector::to_usize(),
+            ),
+            Schema::vector(u64(), <E as EthSpec>::EpochsPerSlashingsVector::to_usize()),
+        ]
+    }
+
+    fn finality_fields() -> Vec<Schema> {
+        vec![
+            Bitvector(<E as EthSpec>::JustificationBitsLength::to_usize()),
+            checkpoint(),
+            checkpoint(),
+            checkpoint(),
+        ]
+    }
+
+    /// The fields of a state from Altair onwards, from the epoch participation to the sync
+    /// committees.
+    fn beacon_state_altair_fields() -> Vec<Schema> {
+        let mut fields = beacon_state_history_fields();
+        fields.extend([
+            validator_registry_list(Basic(1)),
+            validator_registry_list(Basic(1)),
+        ]);
+        fields.extend(finality_fields());
+        fields.extend([
+            validator_registry_list(u64()),
+            sync_committee(),
+            sync_committee(),
+        ]);
+        fields
+    }
+
+    fn beacon_state_base() -> Schema {
+        let pending_attestations = Schema::list(
+            pending_attestation(),
+            <E as EthSpec>::MaxPendingAttestations::to_usize(),
+        );
+        let mut fields = beacon_state_history_fields();
+        fields.extend([pending_attestations.clone(), pending_attestations]);
+        fields.extend(finality_fields());
+        Schema::container(fields)
+    }
+
+    fn beacon_state_altair() -> Schema {
+        Schema::container(beacon_state_altair_fields())
+    }
+
+    fn beacon_state_merge() -> Schema {
+        Schema::container(
+            beacon_state_altair_fields()
+                .into_iter()
+                .chain([execution_payload_header_merge()]),
+        )
+    }
+
+    fn beacon_state_capella() -> Schema {
+        Schema::container(beacon_state_altair_fields().into_iter().chain([
+            execution_payload_header_capella(),
+            u64(),
+            u64(),
+            Schema::list(
+                Schema::container([root(), root()]),
+                <E as EthSpec>::HistoricalRootsLimit::to_usize(),
+            ),
+        ]))
+    }
+
+    /// Fails to compile when a fork is added, as a reminder to cover the variants of its blocks,
+    /// block bodies, states and execution payloads below.
+    #[allow(dead_code)]
+    fn covered_forks(fork_name: ForkName) {
+        match fork_name {
+            ForkName::Base | ForkName::Altair | ForkName::Merge | ForkName::Capella => {}
+        }
+    }
+
+    /// Each type with its schema. New types are covered by adding them here.
+    macro_rules! tree_hash_reference_tests {
+        ($($name: ident: $type: ty => $schema: expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    check::<$type>($schema);
+                }
+            )*
+        };
+    }
+
+    tree_hash_reference_tests! {
+        checkpoint_root: Checkpoint => checkpoint(),
+        fork_root: Fork => fork(),
+        fork_data_root: ForkData => Schema::container([version(), root()]),
+        enr_fork_id_root: EnrForkId => Schema::container([version(), version(), u64()]),
+        signing_data_root: SigningData => Schema::container([root(), root()]),
+        eth1_data_root: Eth1Data => eth1_data(),
+        attestation_data_root: AttestationData => attestation_data(),
+        beacon_block_header_root: BeaconBlockHeader => beacon_block_header(),
+        signed_beacon_block_header_root: SignedBeaconBlockHeader => signed_beacon_block_header(),
+        validator_root: Validator => validator(),
+        deposit_message_root: DepositMessage => Schema::container([pubkey(), root(), u64()]),
+        deposit_data_root: DepositData => deposit_data(),
+        deposit_root: Deposit => deposit(),
+        voluntary_exit_root: VoluntaryExit => voluntary_exit(),
+        signed_voluntary_exit_root: SignedVoluntaryExit => signed_voluntary_exit(),
+        proposer_slashing_root: ProposerSlashing => proposer_slashing(),
+        indexed_attestation_root: IndexedAttestation<E> => indexed_attestation(),
+        attester_slashing_root: AttesterSlashing<E> => attester_slashing(),
+        attestation_root: Attestation<E> => attestation(),
+        pending_attestation_root: PendingAttestation<E> => pending_attestation(),
+        sync_aggregate_root: SyncAggregate<E> => sync_aggregate(),
+        sync_committee_message_root: SyncCommitteeMessage => Schema::container([
+            u64(),
+            root(),
+            u64(),
+            signature(),
+        ]),
+        sync_aggregator_selection_data_root: SyncAggregatorSelectionData => Schema::container([
+            u64(),
+            u64(),
+        ]),
+        bls_to_execution_change_root: BlsToExecutionChange => bls_to_execution_change(),
+        signed_bls_to_execution_change_root: SignedBlsToExecutionChange =>
+            signed_bls_to_execution_change(),
+        withdrawal_root: Withdrawal => withdrawal(),
+        sync_committee_root: SyncCommittee<E> => sync_committee(),
+        historical_summary_root: HistoricalSummary => Schema::container([root(), root()]),
+        execution_payload_merge_root: ExecutionPayloadMerge<E> => execution_payload_merge(),
+        execution_payload_capella_root: ExecutionPayloadCapella<E> => execution_payload_capella(),
+        execution_payload_header_merge_root: ExecutionPayloadHeaderMerge<E> =>
+            execution_payload_header_merge(),
+        execution_payload_header_capella_root: ExecutionPayloadHeaderCapella<E> =>
+            execution_payload_header_capella(),
+        beacon_block_body_base_root: BeaconBlockBodyBase<E> => beacon_block_body_base(),
+        beacon_block_body_altair_root: BeaconBlockBodyAltair<E> => beacon_block_body_altair(),
+        beacon_block_body_merge_root: BeaconBlockBodyMerge<E> => beacon_block_body_merge(),
+        beacon_block_body_capella_root: BeaconBlockBodyCapella<E> => beacon_block_body_capella(),
+        beacon_block_base_root: BeaconBlockBase<E> => beacon_block(beacon_block_body_base()),
+        beacon_block_altair_root: BeaconBlockAltair<E> => beacon_block(beacon_block_body_altair()),
+        beacon_block_merge_root: BeaconBlockMerge<E> => beacon_block(beacon_block_body_merge()),
+        beacon_block_capella_root: BeaconBlockCapella<E> =>
+            beacon_block(beacon_block_body_capella()),
+        beacon_state_base_root: BeaconStateBase<E> => beacon_state_base(),
+        beacon_state_altair_root: BeaconStateAltair<E> => beacon_state_altair(),
+        beacon_state_merge_root: BeaconStateMerge<E> => beacon_state_merge(),
+        beacon_state_capella_root: BeaconStateCapella<E> => beacon_state_capella(),
+    }
+}
@@ -1,5 +1,7 @@
 use beacon_chain::{
-    builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
+    builder::Witness,
+    eth1_chain::CachingEth1Backend,
+    schema_change::{migrate_schema, plan_migration},
     slot_clock::SystemTimeSlotClock,
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
@@ -11,8 +13,8 @@ use std::io::Write;
 use std::path::PathBuf;
 use store::{
     errors::Error,
-    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, MIN_SUPPORTED_SCHEMA_VERSION},
+    BeaconNodeBackend, DBColumn, HotColdDB, KeyValueStore,
 };
 use strum::{EnumString, EnumVariantNames, VariantNames};
 use types::EthSpec;
@@ -38,6 +40,15 @@ pub fn migrate_cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .required(true),
         )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .help(
+                    "Report the steps of the migration and the changes they would make, \
+                       without changing the database",
+                )
+                .takes_value(false),
+        )
 }
 
 pub fn inspect_cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -98,6 +109,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Data directory for the freezer database.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("db-backend")
+                .long("db-backend")
+                .value_name("BACKEND")
+                .help("The backend of the databases.")
+                .takes_value(true)
+                .possible_values(&["leveldb"])
+                .default_value("leveldb"),
+        )
         .subcommand(migrate_cli_app())
         .subcommand(version_cli_app())
         .subcommand(inspect_cli_app())
@@ -119,6 +139,7 @@ fn parse_client_config<E: EthSpec>(
     let (sprp, sprp_explicit) = get_slots_per_restore_point::<E>(cli_args)?;
     client_config.store.slots_per_restore_point = sprp;
     client_config.store.slots_per_restore_point_set_explicitly = sprp_explicit;
+    client_config.store.backend = clap_utils::parse_required(cli_args, "db-backend")?;
 
     Ok(client_config)
 }
@@ -133,7 +154,7 @@ pub fn display_db_version<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, from, _| {
@@ -155,6 +176,13 @@ pub fn display_db_version<E: EthSpec>(
         );
     }
 
+    info!(
+        log,
+        "Supported schema versions: {} to {}",
+        MIN_SUPPORTED_SCHEMA_VERSION.as_u64(),
+        CURRENT_SCHEMA_VERSION.as_u64(),
+    );
+
     Ok(())
 }
 
@@ -197,7 +225,7 @@ pub fn inspect_db<E: EthSpec>(
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, _, _| Ok(()),
@@ -261,12 +289,14 @@ pub fn inspect_db<E: EthSpec>(
 
 pub struct MigrateConfig {
     to: SchemaVersion,
+    dry_run: bool,
 }
 
 fn parse_migrate_config(cli_args: &ArgMatches) -> Result<MigrateConfig, String> {
     let to = SchemaVersion(clap_utils::parse_required(cli_args, "to")?);
+    let dry_run = cli_args.is_present("dry-run");
 
-    Ok(MigrateConfig { to })
+    Ok(MigrateConfig { to, dry_run })
 }
 
 pub fn migrate_db<E: EthSpec>(
@@ -281,7 +311,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, db_initial_version, _| {
@@ -293,6 +323,42 @@ pub fn migrate_db<E: EthSpec>(
         log.clone(),
     )?;
 
+    if migrate_config.dry_run {
+        let steps = plan_migration::<Witness<SystemTimeSlotClock, CachingEth1Backend<E>, _, _, _>>(
+            db,
+            client_config.eth1.deposit_contract_deploy_block,
+            from,
+            to,
+            log.clone(),
+        )?;
+        if steps.is_empty() {
+            info!(log, "Database is already at the requested schema version");
+        }
+        for step in steps {
+            match step.changes {
+                Some(changes) => info!(
+                    log,
+                    "Migration step";
+                    "from" => step.from.as_u64(),
+                    "to" => step.to.as_u64(),
+                    "keys_written" => changes.keys_written,
+                    "bytes_written" => changes.bytes_written,
+                    "keys_deleted" => changes.keys_deleted,
+                    "estimated_duration" => ?changes.duration,
+                ),
+                None => info!(
+                    log,
+                    "Migration step";
+                    "from" => step.from.as_u64(),
+                    "to" => step.to.as_u64(),
+                    "info" => "changes depend on the previous step",
+                ),
+            }
+        }
+        info!(log, "Dry run complete, the database was not changed");
+        return Ok(());
+    }
+
     info!(
         log,
         "Migrating database schema";
@@ -319,7 +385,7 @@ pub fn prune_payloads<E: EthSpec>(
     let hot_path = client_config.get_db_path();
     let cold_path = client_config.get_freezer_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, BeaconNodeBackend<E>>::open(
         &hot_path,
         &cold_path,
         |_, _, _| Ok(()),
@@ -362,15 +362,14 @@ fn do_transition<T: EthSpec>(
         };
 
         let t = Instant::now();
-        BlockSignatureVerifier::verify_entire_block(
-            &pre_state,
-            get_pubkey,
-            decompressor,
-            &block,
-            &mut ctxt,
-            spec,
-        )
-        .map_err(|e| format!("Invalid block signature: {:?}", e))?;
+        let mut signature_verifier =
+            BlockSignatureVerifier::new(&pre_state, get_pubkey, decompressor, spec);
+        signature_verifier
+            .include_all_signatures(&block, &mut ctxt)
+            .map_err(|e| format!("Unable to include block signatures: {:?}", e))?;
+        signature_verifier
+            .verify_and_identify_invalid()
+            .map_err(|e| format!("Invalid block signature: {:?}", e))?;
         debug!("Batch verify block signatures: {:?}", t.elapsed());
 
         // Signature verification should prime the indexed attestation cache.
@@ -12,15 +12,15 @@ use eth2_network_config::Eth2NetworkConfig;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::{future, StreamExt};
 
-use logging::SSELoggingComponents;
+use logging::{LogDirectives, RuntimeLogFilter, SSELoggingComponents};
 use serde_derive::{Deserialize, Serialize};
-use slog::{error, info, o, warn, Drain, Duplicate, Level, Logger};
+use slog::{error, info, o, warn, Drain, Duplicate, Logger};
 use sloggers::{file::FileLoggerBuilder, types::Format, types::Severity, Build};
 use std::fs::create_dir_all;
 use std::io::{Result as IOResult, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
-use task_executor::{ShutdownReason, TaskExecutor};
+use task_executor::{ShutdownReason, ShutdownTracker, TaskExecutor};
 use tokio::runtime::{Builder as RuntimeBuilder, Runtime};
 use types::{EthSpec, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
 
@@ -91,6 +91,7 @@ pub struct RuntimeContext<E: EthSpec> {
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub runtime_log_filter: Option<RuntimeLogFilter>,
 }
 
 impl<E: EthSpec> RuntimeContext<E> {
@@ -104,6 +105,7 @@ impl<E: EthSpec> RuntimeContext<E> {
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            runtime_log_filter: self.runtime_log_filter.clone(),
         }
     }
 
@@ -123,6 +125,7 @@ pub struct EnvironmentBuilder<E: EthSpec> {
     runtime: Option<Arc<Runtime>>,
     log: Option<Logger>,
     sse_logging_components: Option<SSELoggingComponents>,
+    runtime_log_filter: Option<RuntimeLogFilter>,
     eth_spec_instance: E,
     eth2_config: Eth2Config,
     eth2_network_config: Option<Eth2NetworkConfig>,
@@ -135,6 +138,7 @@ impl EnvironmentBuilder<MinimalEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            runtime_log_filter: None,
             eth_spec_instance: MinimalEthSpec,
             eth2_config: Eth2Config::minimal(),
             eth2_network_config: None,
@@ -149,6 +153,7 @@ impl EnvironmentBuilder<MainnetEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            runtime_log_filter: None,
             eth_spec_instance: MainnetEthSpec,
             eth2_config: Eth2Config::mainnet(),
             eth2_network_config: None,
@@ -163,6 +168,7 @@ impl EnvironmentBuilder<GnosisEthSpec> {
             runtime: None,
             log: None,
             sse_logging_components: None,
+            runtime_log_filter: None,
             eth_spec_instance: GnosisEthSpec,
             eth2_config: Eth2Config::gnosis(),
             eth2_network_config: None,
@@ -235,17 +241,24 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
                 .build()
         };
 
-        let stdout_drain = match config.debug_level.as_str() {
-            "info" => stdout_drain.filter_level(Level::Info),
-            "debug" => stdout_drain.filter_level(Level::Debug),
-            "trace" => stdout_drain.filter_level(Level::Trace),
-            "warn" => stdout_drain.filter_level(Level::Warning),
-            "error" => stdout_drain.filter_level(Level::Error),
-            "crit" => stdout_drain.filter_level(Level::Critical),
-            unknown => return Err(format!("Unknown debug-level: {}", unknown)),
-        };
-
-        let stdout_logger = Logger::root(stdout_drain.fuse(), o!());
+        let directives = config
+            .debug_level
+            .parse::<LogDirectives>()
+            .map_err(|e| format!("Invalid debug-level: {}", e))?;
+        // Debug captures are written alongside the log file, even if file logging is disabled.
+        let capture_dir = config
+            .path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(PathBuf::from);
+        let runtime_log_filter = RuntimeLogFilter::new(directives, capture_dir);
+        self.runtime_log_filter = Some(runtime_log_filter.clone());
+
+        let stdout_drain = runtime_log_filter.filter(stdout_drain);
+        let stdout_logger = Logger::root(
+            Duplicate::new(stdout_drain, runtime_log_filter.debug_capture_drain()).fuse(),
+            o!(),
+        );
 
         // Disable file logging if values set to 0.
         if config.max_log_size == 0 || config.max_log_number == 0 {
@@ -287,18 +300,13 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             }
         }
 
-        let logfile_level = match config.logfile_debug_level.as_str() {
-            "info" => Severity::Info,
-            "debug" => Severity::Debug,
-            "trace" => Severity::Trace,
-            "warn" => Severity::Warning,
-            "error" => Severity::Error,
-            "crit" => Severity::Critical,
-            unknown => return Err(format!("Unknown loglevel-debug-level: {}", unknown)),
-        };
+        let logfile_level = logging::runtime_log_filter::parse_level(&config.logfile_debug_level)
+            .map_err(|e| format!("Invalid logfile-debug-level: {}", e))?;
 
+        // The file logger passes on every record, the level is applied by the runtime filter so
+        // that it can be changed via the HTTP API.
         let file_logger = FileLoggerBuilder::new(&path)
-            .level(logfile_level)
+            .level(Severity::Trace)
             .channel_size(LOG_CHANNEL_SIZE)
             .format(match config.logfile_format.as_deref() {
                 Some("JSON") => Format::Json,
@@ -310,6 +318,8 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             .restrict_permissions(config.is_restricted)
             .build()
             .map_err(|e| format!("Unable to build file logger: {}", e))?;
+        let file_logger =
+            runtime_log_filter.filter_logfile(file_logger, LogDirectives::new(logfile_level));
 
         let mut log = Logger::root(Duplicate::new(stdout_logger, file_logger).fuse(), o!());
 
@@ -356,8 +366,10 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             signal_rx: Some(signal_rx),
             signal: Some(signal),
             exit,
+            shutdown_tracker: Arc::new(ShutdownTracker::default()),
             log: self.log.ok_or("Cannot build environment without log")?,
             sse_logging_components: self.sse_logging_components,
+            runtime_log_filter: self.runtime_log_filter,
             eth_spec_instance: self.eth_spec_instance,
             eth2_config: self.eth2_config,
             eth2_network_config: self.eth2_network_config.map(Arc::new),
@@ -375,8 +387,11 @@ pub struct Environment<E: EthSpec> {
     signal_tx: Sender<ShutdownReason>,
     signal: Option<exit_future::Signal>,
     exit: exit_future::Exit,
+    /// Tracks the tasks which are shutting down gracefully after the exit signal.
+    shutdown_tracker: Arc<ShutdownTracker>,
     log: Logger,
     sse_logging_components: Option<SSELoggingComponents>,
+    runtime_log_filter: Option<RuntimeLogFilter>,
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
@@ -399,11 +414,13 @@ impl<E: EthSpec> Environment<E> {
                 self.exit.clone(),
                 self.log.clone(),
                 self.signal_tx.clone(),
-            ),
+            )
+            .with_shutdown_tracker(self.shutdown_tracker.clone()),
             eth_spec_instance: self.eth_spec_instance.clone(),
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            runtime_log_filter: self.runtime_log_filter.clone(),
         }
     }
 
@@ -415,11 +432,13 @@ impl<E: EthSpec> Environment<E> {
                 self.exit.clone(),
                 self.log.new(o!("service" => service_name)),
                 self.signal_tx.clone(),
-            ),
+            )
+            .with_shutdown_tracker(self.shutdown_tracker.clone()),
             eth_spec_instance: self.eth_spec_instance.clone(),
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
             sse_logging_components: self.sse_logging_components.clone(),
+            runtime_log_filter: self.runtime_log_filter.clone(),
         }
     }
 
@@ -528,7 +547,26 @@ impl<E: EthSpec> Environment<E> {
     }
 
     /// Shutdown the `tokio` runtime when all tasks are idle.
+    ///
+    /// Tasks which shut down gracefully after the exit signal are given up to
+    /// `MAXIMUM_SHUTDOWN_TIME` seconds to finish first.
     pub fn shutdown_on_idle(self) {
+        let active_tasks = self.shutdown_tracker.active_tasks();
+        if active_tasks > 0 {
+            info!(self.log, "Waiting for services to shut down"; "services" => active_tasks);
+            let result = self.runtime.block_on(tokio::time::timeout(
+                std::time::Duration::from_secs(MAXIMUM_SHUTDOWN_TIME),
+                self.shutdown_tracker.wait(),
+            ));
+            if result.is_err() {
+                warn!(
+                    self.log,
+                    "Services failed to shut down in time";
+                    "services" => self.shutdown_tracker.active_tasks()
+                );
+            }
+        }
+
         match Arc::try_unwrap(self.runtime) {
             Ok(runtime) => {
                 runtime.shutdown_timeout(std::time::Duration::from_secs(MAXIMUM_SHUTDOWN_TIME))
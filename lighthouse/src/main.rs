@@ -2,6 +2,9 @@ mod metrics;
 
 use beacon_node::ProductionBeaconNode;
 use clap::{App, Arg, ArgMatches};
+use clap_utils::config_file::{
+    merge_config_file_args, validate_config_file, ConfigFileSubcommand, CONFIG_FILE_FLAG,
+};
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, get_eth2_network_config};
 use directory::{parse_path_or_default, DEFAULT_BEACON_NODE_DIR, DEFAULT_VALIDATOR_DIR};
 use env_logger::{Builder, Env};
@@ -18,6 +21,18 @@ use task_executor::ShutdownReason;
 use types::{EthSpec, EthSpecId};
 use validator_client::ProductionValidatorClient;
 
+/// The subcommands which accept flags from `--config-file`.
+const CONFIG_FILE_SUBCOMMANDS: &[ConfigFileSubcommand] = &[
+    ConfigFileSubcommand {
+        name: "beacon_node",
+        aliases: &["b", "bn", "beacon"],
+    },
+    ConfigFileSubcommand {
+        name: "validator_client",
+        aliases: &["v", "vc", "validator"],
+    },
+];
+
 fn bls_library_name() -> &'static str {
     if cfg!(feature = "portable") {
         "blst-portable"
@@ -49,38 +64,17 @@ fn build_profile_name() -> String {
         .to_string()
 }
 
-fn main() {
-    // Enable backtraces unless a RUST_BACKTRACE value has already been explicitly provided.
-    if std::env::var("RUST_BACKTRACE").is_err() {
-        std::env::set_var("RUST_BACKTRACE", "1");
-    }
-
-    // Parse the CLI parameters.
-    let matches = App::new("Lighthouse")
-        .version(VERSION.replace("Lighthouse/", "").as_str())
+/// Returns the top-level CLI app, which includes all of the subcommands.
+fn cli_app<'a>(version: &'a str, long_version: &'a str) -> App<'a, 'a> {
+    App::new("Lighthouse")
+        .version(version)
         .author("Sigma Prime <contact@sigmaprime.io>")
         .setting(clap::AppSettings::ColoredHelp)
         .about(
             "Ethereum 2.0 client by Sigma Prime. Provides a full-featured beacon \
              node, a validator client and utilities for managing validator accounts.",
         )
-        .long_version(
-            format!(
-                "{}\n\
-                 BLS library: {}\n\
-                 SHA256 hardware acceleration: {}\n\
-                 Allocator: {}\n\
-                 Profile: {}\n\
-                 Specs: mainnet (true), minimal ({}), gnosis ({})",
-                 VERSION.replace("Lighthouse/", ""),
-                 bls_library_name(),
-                 have_sha_extensions(),
-                 allocator_name(),
-                 build_profile_name(),
-                 cfg!(feature = "spec-minimal"),
-                 cfg!(feature = "gnosis"),
-            ).as_str()
-        )
+        .long_version(long_version)
         .arg(
             Arg::with_name("spec")
                 .short("s")
@@ -91,6 +85,17 @@ fn main() {
                 .takes_value(true)
                 .global(true)
         )
+        .arg(
+            Arg::with_name(CONFIG_FILE_FLAG)
+                .long(CONFIG_FILE_FLAG)
+                .value_name("FILE")
+                .help("Path to a TOML file of flag values for the beacon node or validator client. \
+                    Top-level keys apply to both, whilst keys in a [beacon_node] or \
+                    [validator_client] table apply to that subcommand only. Flags given on the \
+                    command line take precedence over the file. See `lighthouse config validate`.")
+                .takes_value(true)
+                .global(true)
+        )
         .arg(
             Arg::with_name("env_log")
                 .short("l")
@@ -355,7 +360,87 @@ fn main() {
         .subcommand(account_manager::cli_app())
         .subcommand(database_manager::cli_app())
         .subcommand(validator_manager::cli_app())
-        .get_matches();
+        .subcommand(
+            App::new("config")
+                .setting(clap::AppSettings::ColoredHelp)
+                .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                .about("Utilities for Lighthouse config files.")
+                .subcommand(
+                    App::new("validate")
+                        .about(
+                            "Checks that a config file is accepted by each subcommand that it \
+                            configures, and prints the effective flags for each subcommand.",
+                        )
+                        .arg(
+                            Arg::with_name("file")
+                                .value_name("FILE")
+                                .help("Path to the TOML config file.")
+                                .required(true)
+                                .takes_value(true),
+                        ),
+                ),
+        )
+}
+
+fn main() {
+    // Enable backtraces unless a RUST_BACKTRACE value has already been explicitly provided.
+    if std::env::var("RUST_BACKTRACE").is_err() {
+        std::env::set_var("RUST_BACKTRACE", "1");
+    }
+
+    let version = VERSION.replace("Lighthouse/", "");
+    let long_version = format!(
+        "{}\n\
+         BLS library: {}\n\
+         SHA256 hardware acceleration: {}\n\
+         Allocator: {}\n\
+         Profile: {}\n\
+         Specs: mainnet (true), minimal ({}), gnosis ({})",
+        version,
+        bls_library_name(),
+        have_sha_extensions(),
+        allocator_name(),
+        build_profile_name(),
+        cfg!(feature = "spec-minimal"),
+        cfg!(feature = "gnosis"),
+    );
+
+    // Parse the CLI parameters, including any flags from a config file.
+    let args = match merge_config_file_args(std::env::args_os().collect(), CONFIG_FILE_SUBCOMMANDS)
+    {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1)
+        }
+    };
+    let matches = cli_app(&version, &long_version).get_matches_from(args);
+
+    if let Some(config_matches) = matches.subcommand_matches("config") {
+        let result = match config_matches.subcommand() {
+            ("validate", Some(validate_matches)) => {
+                clap_utils::parse_required::<PathBuf>(validate_matches, "file").and_then(|path| {
+                    validate_config_file(
+                        &path,
+                        CONFIG_FILE_SUBCOMMANDS,
+                        || cli_app(&version, &long_version),
+                        effective_config,
+                    )
+                })
+            }
+            (unknown, _) => Err(format!("Unknown config subcommand: {}", unknown)),
+        };
+        match result {
+            Ok(effective_config) => {
+                print!("{}", effective_config);
+                exit(0)
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1)
+            }
+        }
+    }
 
     // Configure the allocator early in the process, before it has the chance to use the default values for
     // anything important.
@@ -433,6 +518,55 @@ fn main() {
     }
 }
 
+/// Builds the config of the beacon node or validator client subcommand in `matches` exactly as it
+/// would be built on startup, without starting it or modifying the file system.
+fn effective_config(matches: &ArgMatches) -> Result<serde_json::Value, String> {
+    let eth2_network_config = get_eth2_network_config(matches)?;
+    match eth2_network_config.eth_spec_id()? {
+        EthSpecId::Mainnet => {
+            effective_config_for_spec(EnvironmentBuilder::mainnet(), matches, eth2_network_config)
+        }
+        #[cfg(feature = "gnosis")]
+        EthSpecId::Gnosis => {
+            effective_config_for_spec(EnvironmentBuilder::gnosis(), matches, eth2_network_config)
+        }
+        #[cfg(feature = "spec-minimal")]
+        EthSpecId::Minimal => {
+            effective_config_for_spec(EnvironmentBuilder::minimal(), matches, eth2_network_config)
+        }
+        #[cfg(not(all(feature = "spec-minimal", feature = "gnosis")))]
+        other => Err(format!(
+            "Eth spec `{}` is not supported by this build of Lighthouse",
+            other
+        )),
+    }
+}
+
+fn effective_config_for_spec<E: EthSpec>(
+    environment_builder: EnvironmentBuilder<E>,
+    matches: &ArgMatches,
+    eth2_network_config: Eth2NetworkConfig,
+) -> Result<serde_json::Value, String> {
+    let environment = environment_builder
+        .null_logger()?
+        .multi_threaded_tokio_runtime()?
+        .eth2_network_config(eth2_network_config)?
+        .build()?;
+    let context = environment.core_context();
+
+    let config = match matches.subcommand() {
+        ("beacon_node", Some(matches)) => {
+            serde_json::to_value(beacon_node::parse_config::<E>(matches, &context)?)
+        }
+        ("validator_client", Some(matches)) => serde_json::to_value(
+            validator_client::Config::parse_cli(matches, context.log())
+                .map_err(|e| format!("Unable to initialize validator config: {}", e))?,
+        ),
+        (subcommand, _) => return Err(format!("The config of {} cannot be validated", subcommand)),
+    };
+    config.map_err(|e| format!("Unable to serialize config: {:?}", e))
+}
+
 fn run<E: EthSpec>(
     environment_builder: EnvironmentBuilder<E>,
     matches: &ArgMatches,
@@ -656,7 +790,23 @@ fn run<E: EthSpec>(
             let shutdown_flag = matches.is_present("immediate-shutdown");
             // Dump configs if `dump-config` or `dump-chain-config` flags are set
             clap_utils::check_dump_configs::<_, E>(matches, &config, &context.eth2_config.spec)?;
-            if !shutdown_flag {
+            if let (false, Some(dry_run_config)) = (shutdown_flag, config.dry_run.clone()) {
+                executor.clone().spawn(
+                    async move {
+                        let result =
+                            validator_client::dry_run::run(context, config, dry_run_config).await;
+                        let reason = match result {
+                            Ok(()) => ShutdownReason::Success("Validator client dry run complete."),
+                            Err(e) => {
+                                crit!(log, "Validator client dry run failed"; "reason" => e);
+                                ShutdownReason::Failure("Validator client dry run failed")
+                            }
+                        };
+                        let _ = executor.shutdown_sender().try_send(reason);
+                    },
+                    "validator_client_dry_run",
+                );
+            } else if !shutdown_flag {
                 executor.clone().spawn(
                     async move {
                         if let Err(e) = ProductionValidatorClient::new(context, config)
@@ -1,10 +1,11 @@
-use beacon_node::ClientConfig as Config;
+use beacon_node::{ClientConfig as Config, ClientGenesis};
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
+    DisallowedReOrgOffsets, ReOrgThreshold, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_THRESHOLD,
 };
+use beacon_node::beacon_chain::validator_monitor::MetricAggregation;
 use beacon_processor::BeaconProcessorConfig;
 use eth1::Eth1Endpoint;
 use lighthouse_network::PeerId;
@@ -20,7 +21,7 @@ use std::time::Duration;
 use tempfile::TempDir;
 use types::{
     Address, Checkpoint, Epoch, ExecutionBlockHash, ForkName, Hash256, MainnetEthSpec,
-    ProgressiveBalancesMode,
+    ProgressiveBalancesMode, SubnetId, SyncSubnetId,
 };
 use unused_port::{unused_tcp4_port, unused_tcp6_port, unused_udp4_port, unused_udp6_port};
 
@@ -144,6 +145,39 @@ fn shuffling_cache_set() {
         .with_config(|config| assert_eq!(config.chain.shuffling_cache_size, 500));
 }
 
+#[test]
+fn shuffling_cache_memory_budget_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.shuffling_cache_memory_budget, None));
+}
+
+#[test]
+fn shuffling_cache_memory_budget_set() {
+    CommandLineTest::new()
+        .flag("shuffling-cache-memory-budget", Some("64"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.shuffling_cache_memory_budget,
+                Some(64 * 1024 * 1024)
+            )
+        });
+}
+
+#[test]
+fn shuffling_cache_memory_budget_saturates() {
+    CommandLineTest::new()
+        .flag(
+            "shuffling-cache-memory-budget",
+            Some(&usize::MAX.to_string()),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.shuffling_cache_memory_budget, Some(usize::MAX))
+        });
+}
+
 #[test]
 fn fork_choice_before_proposal_timeout_default() {
     CommandLineTest::new()
@@ -183,6 +217,80 @@ fn checkpoint_sync_url_timeout_default() {
         });
 }
 
+#[test]
+fn checkpoint_sync_single_url() {
+    CommandLineTest::new()
+        .flag("checkpoint-sync-url", Some("http://localhost:5052"))
+        .run_with_zero_port()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSyncUrl {
+                url,
+                cross_check_urls,
+                quorum,
+            } => {
+                assert_eq!(url.full.as_str(), "http://localhost:5052/");
+                assert!(cross_check_urls.is_empty());
+                assert_eq!(*quorum, 1);
+            }
+            genesis => panic!("unexpected genesis {:?}", genesis),
+        });
+}
+
+#[test]
+fn checkpoint_sync_multiple_urls() {
+    CommandLineTest::new()
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://localhost:5052,http://localhost:5053,http://localhost:5054"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            match &config.genesis {
+                ClientGenesis::CheckpointSyncUrl {
+                    url,
+                    cross_check_urls,
+                    quorum,
+                } => {
+                    assert_eq!(url.full.as_str(), "http://localhost:5052/");
+                    assert_eq!(cross_check_urls.len(), 2);
+                    assert_eq!(*quorum, 2);
+                }
+                genesis => panic!("unexpected genesis {:?}", genesis),
+            }
+            assert_eq!(
+                config.genesis_state_url.as_deref(),
+                Some("http://localhost:5052")
+            );
+        });
+}
+
+#[test]
+fn checkpoint_sync_quorum_flag() {
+    CommandLineTest::new()
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://localhost:5052,http://localhost:5053,http://localhost:5054"),
+        )
+        .flag("checkpoint-sync-quorum", Some("3"))
+        .run_with_zero_port()
+        .with_config(|config| match &config.genesis {
+            ClientGenesis::CheckpointSyncUrl { quorum, .. } => assert_eq!(*quorum, 3),
+            genesis => panic!("unexpected genesis {:?}", genesis),
+        });
+}
+
+#[test]
+#[should_panic]
+fn checkpoint_sync_quorum_exceeds_urls() {
+    CommandLineTest::new()
+        .flag(
+            "checkpoint-sync-url",
+            Some("http://localhost:5052,http://localhost:5053"),
+        )
+        .flag("checkpoint-sync-quorum", Some("3"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn prepare_payload_lookahead_default() {
     CommandLineTest::new()
@@ -238,6 +346,75 @@ fn paranoid_block_proposal_on() {
         .with_config(|config| assert!(config.chain.paranoid_block_proposal));
 }
 
+#[test]
+fn state_root_audit_interval_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.state_root_audit_interval, None));
+}
+
+#[test]
+fn state_root_audit_interval_flag() {
+    CommandLineTest::new()
+        .flag("state-root-audit-interval", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.state_root_audit_interval, Some(4)));
+}
+
+#[test]
+fn max_epoch_processing_threads_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.max_epoch_processing_threads, None));
+}
+
+#[test]
+fn max_epoch_processing_threads_flag() {
+    CommandLineTest::new()
+        .flag("max-epoch-processing-threads", Some("2"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.max_epoch_processing_threads, Some(2)));
+}
+
+#[test]
+#[should_panic]
+fn max_epoch_processing_threads_zero_value() {
+    CommandLineTest::new()
+        .flag("max-epoch-processing-threads", Some("0"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn max_cover_attestation_packing_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.max_cover_attestation_packing_fork, None);
+            assert_eq!(
+                config.chain.attestation_packing_time_budget,
+                Duration::from_millis(50)
+            );
+        });
+}
+
+#[test]
+fn max_cover_attestation_packing_flags() {
+    CommandLineTest::new()
+        .flag("max-cover-attestation-packing-fork", Some("capella"))
+        .flag("attestation-packing-time-budget", Some("200"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.max_cover_attestation_packing_fork,
+                Some(ForkName::Capella)
+            );
+            assert_eq!(
+                config.chain.attestation_packing_time_budget,
+                Duration::from_millis(200)
+            );
+        });
+}
+
 #[test]
 fn count_unrealized_no_arg() {
     CommandLineTest::new()
@@ -499,6 +676,58 @@ fn run_execution_jwt_secret_key_is_persisted() {
         });
 }
 #[test]
+fn execution_cross_check_endpoints_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwt = dir.path().join("jwt-file");
+    let cross_check_jwt = dir.path().join("cross-check-jwt-file");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://localhost:8551/"))
+        .flag("execution-jwt", jwt.as_os_str().to_str())
+        .flag(
+            "execution-cross-check-endpoints",
+            Some("http://localhost:8552/,http://localhost:8553/"),
+        )
+        .flag(
+            "execution-cross-check-jwts",
+            cross_check_jwt.as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            let endpoints = config
+                .execution_endpoints
+                .iter()
+                .map(|url| url.full.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                endpoints,
+                vec![
+                    "http://localhost:8551/",
+                    "http://localhost:8552/",
+                    "http://localhost:8553/"
+                ]
+            );
+            assert_eq!(
+                config.secret_files,
+                vec![jwt.clone(), cross_check_jwt.clone()]
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn execution_cross_check_jwts_exceed_endpoints() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("execution-endpoint", Some("http://localhost:8551/"))
+        .flag("execution-jwt", dir.path().join("jwt").as_os_str().to_str())
+        .flag(
+            "execution-cross-check-endpoints",
+            Some("http://localhost:8552/"),
+        )
+        .flag("execution-cross-check-jwts", Some("/tmp/jwt-a,/tmp/jwt-b"))
+        .run_with_zero_port();
+}
+#[test]
 fn execution_timeout_multiplier_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
     CommandLineTest::new()
@@ -618,9 +847,9 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
+        // Any endpoints after the first are queried alongside it.
         assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        assert_eq!(config.additional_builder_urls, all_builders[1..]);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -755,6 +984,42 @@ fn builder_fallback_flags() {
     );
 }
 
+#[test]
+fn builder_bid_margin_percent() {
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-bid-margin-percent"),
+        Some("15"),
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_bid_margin_percent,
+                15
+            );
+        },
+    );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        None,
+        None,
+        |config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .builder_bid_margin_percent,
+                0
+            );
+        },
+    );
+}
+
 #[test]
 fn builder_user_agent() {
     run_payload_builder_flag_test_with_config(
@@ -909,6 +1174,32 @@ fn network_subscribe_all_subnets_flag() {
         .with_config(|config| assert!(config.network.subscribe_all_subnets));
 }
 #[test]
+fn network_backbone_subnets_flags() {
+    CommandLineTest::new()
+        .flag("backbone-attestation-subnets", Some("0,5,63"))
+        .flag("backbone-sync-subnets", Some("2"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.backbone_attestation_subnets,
+                vec![SubnetId::new(0), SubnetId::new(5), SubnetId::new(63)]
+            );
+            assert_eq!(
+                config.network.backbone_sync_subnets,
+                vec![SyncSubnetId::new(2)]
+            );
+        });
+}
+#[test]
+fn network_backbone_subnets_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.network.backbone_attestation_subnets.is_empty());
+            assert!(config.network.backbone_sync_subnets.is_empty());
+        });
+}
+#[test]
 fn network_import_all_attestations_flag() {
     CommandLineTest::new()
         .flag("import-all-attestations", None)
@@ -1260,6 +1551,43 @@ fn network_load_flag() {
             assert_eq!(config.network.network_load, 4);
         });
 }
+#[test]
+fn max_subnet_peer_fraction_flag() {
+    CommandLineTest::new()
+        .flag("max-subnet-peer-fraction", Some("0.1"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.max_subnet_peer_fraction, Some(0.1));
+            assert_eq!(config.network.max_asn_peer_fraction, None);
+        });
+}
+#[test]
+#[should_panic]
+fn max_asn_peer_fraction_requires_asn_database() {
+    CommandLineTest::new()
+        .flag("max-asn-peer-fraction", Some("0.25"))
+        .run_with_zero_port();
+}
+#[test]
+fn max_asn_peer_fraction_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("ip2asn-combined.tsv");
+    CommandLineTest::new()
+        .flag("max-asn-peer-fraction", Some("0.25"))
+        .flag("asn-database", path.to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.max_asn_peer_fraction, Some(0.25));
+            assert_eq!(config.network.asn_database, Some(path.clone()));
+        });
+}
+#[test]
+#[should_panic]
+fn max_subnet_peer_fraction_out_of_range() {
+    CommandLineTest::new()
+        .flag("max-subnet-peer-fraction", Some("1.5"))
+        .run_with_zero_port();
+}
 
 // Tests for ENR flags.
 #[test]
@@ -1729,6 +2057,45 @@ fn validator_monitor_metrics_threshold_custom() {
             assert_eq!(config.validator_monitor_individual_tracking_threshold, 42)
         });
 }
+#[test]
+fn validator_monitor_metric_aggregation_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.validator_monitor_metric_aggregation,
+                MetricAggregation::Total
+            )
+        });
+}
+#[test]
+fn validator_monitor_metric_aggregation_custom() {
+    CommandLineTest::new()
+        .flag(
+            "validator-monitor-metric-aggregation",
+            Some("index-range:1000"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.validator_monitor_metric_aggregation,
+                MetricAggregation::IndexRange(1000)
+            )
+        });
+}
+
+#[test]
+fn validator_monitor_metric_aggregation_cohort() {
+    CommandLineTest::new()
+        .flag("validator-monitor-metric-aggregation", Some("cohort"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.validator_monitor_metric_aggregation,
+                MetricAggregation::Cohort
+            )
+        });
+}
 
 // Tests for Store flags.
 #[test]
@@ -1799,6 +2166,41 @@ fn historic_state_cache_size_default() {
         });
 }
 #[test]
+fn db_backend_default() {
+    use beacon_node::beacon_chain::store::config::DatabaseBackend;
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::LevelDb));
+}
+#[test]
+fn db_backend_flag() {
+    use beacon_node::beacon_chain::store::config::DatabaseBackend;
+    CommandLineTest::new()
+        .flag("db-backend", Some("memory"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.backend, DatabaseBackend::Memory));
+}
+#[test]
+fn hdiff_exponents_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.store.hdiff_exponents.is_empty()));
+}
+#[test]
+fn hdiff_exponents_flag() {
+    CommandLineTest::new()
+        .flag("hdiff-exponents", Some("1,3,5"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hdiff_exponents, vec![1, 3, 5]));
+}
+#[test]
+#[should_panic]
+fn hdiff_exponents_not_ascending() {
+    CommandLineTest::new()
+        .flag("hdiff-exponents", Some("5,3"))
+        .run_with_zero_port();
+}
+#[test]
 fn auto_compact_db_flag() {
     CommandLineTest::new()
         .flag("auto-compact-db", Some("false"))
@@ -2194,6 +2596,59 @@ fn proposer_re_org_disallowed_offsets_invalid() {
         .run_with_zero_port();
 }
 
+#[test]
+fn proposer_re_org_threshold_schedule() {
+    CommandLineTest::new()
+        .flag("proposer-reorg-threshold", Some("20"))
+        .flag("proposer-reorg-threshold-schedule", Some("200:30,100:10"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let threshold_at = |epoch| config.chain.re_org_threshold_at(Epoch::new(epoch));
+            assert_eq!(threshold_at(99), Some(ReOrgThreshold(20)));
+            assert_eq!(threshold_at(100), Some(ReOrgThreshold(10)));
+            assert_eq!(threshold_at(199), Some(ReOrgThreshold(10)));
+            assert_eq!(threshold_at(200), Some(ReOrgThreshold(30)));
+        });
+}
+
+#[test]
+#[should_panic]
+fn proposer_re_org_threshold_schedule_duplicate_epoch() {
+    CommandLineTest::new()
+        .flag("proposer-reorg-threshold-schedule", Some("100:10,100:30"))
+        .run_with_zero_port();
+}
+
+#[test]
+#[should_panic]
+fn proposer_re_org_threshold_schedule_invalid() {
+    CommandLineTest::new()
+        .flag("proposer-reorg-threshold-schedule", Some("100"))
+        .run_with_zero_port();
+}
+
+#[test]
+fn proposer_re_org_max_consecutive() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.re_org_max_consecutive, None));
+    CommandLineTest::new()
+        .flag("proposer-reorg-max-consecutive", Some("2"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.re_org_max_consecutive, Some(2)));
+}
+
+#[test]
+fn proposer_re_org_dry_run() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.re_org_dry_run));
+    CommandLineTest::new()
+        .flag("proposer-reorg-dry-run", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.re_org_dry_run));
+}
+
 #[test]
 fn monitoring_endpoint() {
     CommandLineTest::new()
@@ -2315,7 +2770,10 @@ fn sync_eth1_chain_disable_deposit_contract_sync_flag() {
 fn light_client_server_default() {
     CommandLineTest::new()
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.network.enable_light_client_server, false));
+        .with_config(|config| {
+            assert_eq!(config.network.enable_light_client_server, false);
+            assert_eq!(config.chain.enable_light_client_server, false);
+        });
 }
 
 #[test]
@@ -2323,7 +2781,45 @@ fn light_client_server_enabled() {
     CommandLineTest::new()
         .flag("light-client-server", None)
         .run_with_zero_port()
-        .with_config(|config| assert_eq!(config.network.enable_light_client_server, true));
+        .with_config(|config| {
+            assert_eq!(config.network.enable_light_client_server, true);
+            assert_eq!(config.chain.enable_light_client_server, true);
+        });
+}
+
+#[test]
+fn rpc_zstd_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.enable_rpc_zstd));
+    CommandLineTest::new()
+        .flag("rpc-zstd", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.enable_rpc_zstd));
+}
+
+#[test]
+fn outbound_bandwidth_budget_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.outbound_bandwidth_budget, None));
+    CommandLineTest::new()
+        .flag("outbound-bandwidth-budget", Some("1000000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.outbound_bandwidth_budget, Some(1_000_000))
+        });
+}
+
+#[test]
+fn prefer_quic_peers_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.prefer_quic_peers));
+    CommandLineTest::new()
+        .flag("prefer-quic-peers", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.prefer_quic_peers));
 }
 
 #[test]
@@ -2426,6 +2922,7 @@ fn beacon_processor() {
         .flag("beacon-processor-reprocess-queue-len", Some("3"))
         .flag("beacon-processor-attestation-batch-size", Some("4"))
         .flag("beacon-processor-aggregate-batch-size", Some("5"))
+        .flag("beacon-processor-attestation-batch-window-ms", Some("6"))
         .flag("disable-backfill-rate-limiting", None)
         .run_with_zero_port()
         .with_config(|config| {
@@ -2437,7 +2934,8 @@ fn beacon_processor() {
                     max_scheduled_work_queue_len: 3,
                     max_gossip_attestation_batch_size: 4,
                     max_gossip_aggregate_batch_size: 5,
-                    enable_backfill_rate_limiting: false
+                    enable_backfill_rate_limiting: false,
+                    attestation_batch_window: Some(Duration::from_millis(6)),
                 }
             )
         });
@@ -2487,6 +2985,117 @@ fn http_duplicate_block_status_override() {
         });
 }
 
+#[test]
+fn otlp_endpoint_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.otlp_endpoint, None));
+}
+
+#[test]
+fn otlp_endpoint_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("otlp-endpoint", Some("http://localhost:4318"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.otlp_endpoint,
+                Some(sensitive_url::SensitiveUrl::parse("http://localhost:4318").unwrap())
+            )
+        });
+}
+
+#[test]
+fn http_validator_monitor_token_file_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.validator_monitor_registration_token, None)
+        });
+}
+
+#[test]
+fn http_validator_monitor_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("validator-monitor-token");
+    File::create(&path)
+        .expect("Unable to create token file")
+        .write_all(b"secret-token\n")
+        .expect("Unable to write token file");
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-validator-monitor-token-file", path.to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.validator_monitor_registration_token,
+                Some("secret-token".to_string())
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn http_validator_monitor_token_file_empty() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("validator-monitor-token");
+    File::create(&path).expect("Unable to create token file");
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-validator-monitor-token-file", path.to_str())
+        .run_with_zero_port();
+}
+
+#[test]
+fn http_admin_token_file_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.admin_token, None));
+}
+
+#[test]
+fn http_admin_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("admin-token");
+    File::create(&path)
+        .expect("Unable to create token file")
+        .write_all(b"admin-token\n")
+        .expect("Unable to write token file");
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-admin-token-file", path.to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.admin_token, Some("admin-token".to_string()))
+        });
+}
+
+#[test]
+fn http_database_backup_dir_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.database_backup_dir, None));
+}
+
+#[test]
+fn http_database_backup_dir_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-database-backup-dir", Some("/var/backups/lighthouse"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.database_backup_dir,
+                Some(PathBuf::from("/var/backups/lighthouse"))
+            )
+        });
+}
+
 #[test]
 fn genesis_state_url_default() {
     CommandLineTest::new()
@@ -2511,3 +3120,126 @@ fn genesis_state_url_value() {
             assert_eq!(config.genesis_state_url_timeout, Duration::from_secs(42));
         });
 }
+
+fn write_config_file(dir: &TempDir, contents: &str) -> PathBuf {
+    let path = dir.path().join("lighthouse.toml");
+    let mut file = File::create(&path).expect("Unable to create config file");
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write config file");
+    path
+}
+
+#[test]
+fn config_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = write_config_file(
+        &dir,
+        r#"
+        genesis-state-url-timeout = 42
+
+        [beacon_node]
+        http = true
+        target-peers = 55
+
+        [validator_client]
+        graffiti = "unused"
+        "#,
+    );
+    CommandLineTest::new()
+        .flag("config-file", path.to_str())
+        .flag("target-peers", Some("60"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.http_api.enabled);
+            assert_eq!(config.genesis_state_url_timeout, Duration::from_secs(42));
+            // Flags on the command line take precedence over the file.
+            assert_eq!(config.network.target_peers, 60);
+        });
+}
+
+#[test]
+fn config_validate_subcommand() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let lighthouse_bin = env!("CARGO_BIN_EXE_lighthouse");
+
+    let datadir = dir.path().join("datadir");
+    let path = write_config_file(
+        &dir,
+        &format!(
+            "datadir = {:?}\n[beacon_node]\ntarget-peers = 55\n",
+            datadir.to_str().unwrap()
+        ),
+    );
+    let output = Command::new(lighthouse_bin)
+        .args(["config", "validate"])
+        .arg(&path)
+        .output()
+        .expect("should run command");
+    assert!(output.status.success());
+    let effective: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("should print the config as JSON");
+    let config: Config = serde_json::from_value(effective["beacon_node"].clone())
+        .expect("should print the beacon node config");
+    assert_eq!(config.network.target_peers, 55);
+    assert_eq!(config.data_dir(), &datadir.join("beacon"));
+    // Only the subcommands with a table are checked.
+    assert!(effective.get("validator_client").is_none());
+
+    for invalid in [
+        "[beacon_node]\nnot-a-flag = true\n",
+        "[beacon_node]\ntarget-peers = true\n",
+        "[beacon_node]\ntarget-peers = \"many\"\n",
+        "[boot_node]\nport = 9000\n",
+    ] {
+        let path = write_config_file(&dir, invalid);
+        let output = Command::new(lighthouse_bin)
+            .args(["config", "validate"])
+            .arg(&path)
+            .output()
+            .expect("should run command");
+        assert!(!output.status.success(), "{} should be invalid", invalid);
+    }
+}
+
+#[test]
+fn config_validate_is_read_only() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let lighthouse_bin = env!("CARGO_BIN_EXE_lighthouse");
+
+    let datadir = dir.path().join("datadir");
+    let chain_db = datadir.join("beacon").join("chain_db");
+    let freezer_db = datadir.join("beacon").join("freezer_db");
+    for db in [&chain_db, &freezer_db] {
+        std::fs::create_dir_all(db).unwrap();
+        std::fs::write(db.join("CURRENT"), "MANIFEST-000001").unwrap();
+    }
+
+    let path = write_config_file(
+        &dir,
+        &format!(
+            "datadir = {:?}\n\
+             [beacon_node]\n\
+             purge-db = true\n\
+             execution-endpoint = \"http://localhost:8551\"\n\
+             execution-jwt-secret-key = \"{}\"\n\
+             [validator_client]\n\
+             graffiti = \"read-only\"\n",
+            datadir.to_str().unwrap(),
+            "ab".repeat(32),
+        ),
+    );
+    let output = Command::new(lighthouse_bin)
+        .args(["config", "validate"])
+        .arg(&path)
+        .output()
+        .expect("should run command");
+    assert!(output.status.success());
+
+    // The databases survive `purge-db`, and neither the JWT secret nor the validator directory is
+    // written.
+    for db in [&chain_db, &freezer_db] {
+        assert!(db.join("CURRENT").exists(), "{:?} should be intact", db);
+    }
+    assert!(!datadir.join("beacon").join("jwt.hex").exists());
+    assert!(!datadir.join("validators").exists());
+}
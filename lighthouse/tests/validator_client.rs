@@ -101,6 +101,36 @@ fn beacon_nodes_flag() {
         });
 }
 
+#[test]
+fn beacon_nodes_dns_flag() {
+    CommandLineTest::new()
+        .flag("beacon-nodes-dns", Some("_eth-bn._tcp.example.com"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes_dns.as_deref(),
+                Some("_eth-bn._tcp.example.com")
+            );
+            assert_eq!(config.beacon_nodes_dns_interval, 60);
+        });
+}
+
+#[test]
+fn beacon_nodes_dns_interval_flag() {
+    CommandLineTest::new()
+        .flag("beacon-nodes-dns", Some("_eth-bn._tcp.example.com"))
+        .flag("beacon-nodes-dns-interval", Some("300"))
+        .run()
+        .with_config(|config| assert_eq!(config.beacon_nodes_dns_interval, 300));
+}
+
+#[test]
+fn beacon_nodes_dns_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(config.beacon_nodes_dns.is_none()));
+}
+
 #[test]
 fn allow_unsynced_flag() {
     // No-op, but doesn't crash.
@@ -158,6 +188,41 @@ fn beacon_nodes_tls_certs_flag() {
         });
 }
 
+#[test]
+fn config_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("lighthouse.toml");
+    let mut file = File::create(&path).expect("Unable to create config file");
+    file.write_all(
+        br#"
+        [beacon_node]
+        graffiti = "unused"
+
+        [validator_client]
+        beacon-nodes = ["http://localhost:1001", "http://localhost:1002"]
+        graffiti = "nice-graffiti"
+        "#,
+    )
+    .expect("Unable to write config file");
+    CommandLineTest::new()
+        .flag("config-file", path.to_str())
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes[0].full.to_string(),
+                "http://localhost:1001/"
+            );
+            assert_eq!(
+                config.beacon_nodes[1].full.to_string(),
+                "http://localhost:1002/"
+            );
+            assert_eq!(
+                config.graffiti.unwrap().to_string(),
+                "0x6e6963652d677261666669746900000000000000000000000000000000000000"
+            );
+        });
+}
+
 // Tests for Graffiti flags.
 #[test]
 fn graffiti_flag() {
@@ -247,6 +312,50 @@ fn fee_recipient_flag() {
             )
         });
 }
+#[test]
+fn fee_recipient_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file =
+        File::create(dir.path().join("fee_recipients.txt")).expect("Unable to create file");
+    let new_key = Keypair::random();
+    let pubkeybytes = PublicKeyBytes::from(new_key.pk);
+    let contents = "default: 0x00000000219ab540356cbb839cbe05303d7705fa";
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag(
+            "suggested-fee-recipient-file",
+            dir.path().join("fee_recipients.txt").as_os_str().to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            // Public key not present so load default.
+            assert_eq!(
+                config
+                    .fee_recipient_file
+                    .as_ref()
+                    .unwrap()
+                    .get_fee_recipient(&pubkeybytes),
+                Some(Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap())
+            )
+        });
+}
+#[test]
+#[should_panic]
+fn fee_recipient_file_with_invalid_checksum() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file =
+        File::create(dir.path().join("fee_recipients.txt")).expect("Unable to create file");
+    let contents = "default: 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag(
+            "suggested-fee-recipient-file",
+            dir.path().join("fee_recipients.txt").as_os_str().to_str(),
+        )
+        .run();
+}
 
 // Tests for HTTP flags.
 #[test]
@@ -345,6 +454,40 @@ fn http_store_keystore_passwords_in_secrets_dir_present() {
         .run()
         .with_config(|config| assert!(config.http_api.store_passwords_in_secrets_dir));
 }
+#[test]
+fn http_remotekey_health_check_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run()
+        .with_config(|config| assert!(config.http_api.remotekey_health_check));
+}
+#[test]
+fn http_skip_remotekey_health_check_present() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-skip-remotekey-health-check", None)
+        .run()
+        .with_config(|config| assert!(!config.http_api.remotekey_health_check));
+}
+#[test]
+fn remote_signer_check_interval_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.remote_signer_check_interval, 60);
+    });
+    CommandLineTest::new()
+        .flag("remote-signer-check-interval", Some("12"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.remote_signer_check_interval, 12);
+        });
+}
+#[test]
+#[should_panic]
+fn remote_signer_check_interval_zero_value() {
+    CommandLineTest::new()
+        .flag("remote-signer-check-interval", Some("0"))
+        .run();
+}
 
 // Tests for Metrics flags.
 #[test]
@@ -561,3 +704,277 @@ fn validator_registration_batch_size_zero_value() {
         .flag("validator-registration-batch-size", Some("0"))
         .run();
 }
+
+#[test]
+fn attestation_inclusion_check_delay() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.attestation_inclusion_check_delay, None);
+        assert_eq!(config.attestation_inclusion_miss_threshold, 3);
+    });
+    CommandLineTest::new()
+        .flag("attestation-inclusion-check-delay", Some("2"))
+        .flag("attestation-inclusion-miss-threshold", Some("5"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.attestation_inclusion_check_delay, Some(2));
+            assert_eq!(config.attestation_inclusion_miss_threshold, 5);
+        });
+}
+
+#[test]
+#[should_panic]
+fn attestation_inclusion_check_delay_zero_value() {
+    CommandLineTest::new()
+        .flag("attestation-inclusion-check-delay", Some("0"))
+        .run();
+}
+
+#[test]
+fn slashing_protection_health_flags() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.slashing_protection_check_interval, 3600);
+        assert_eq!(config.slashing_protection_backup_dir, None);
+        assert_eq!(config.slashing_protection_backups_to_keep, 24);
+    });
+    CommandLineTest::new()
+        .flag("slashing-protection-check-interval", Some("600"))
+        .flag("slashing-protection-backup-dir", Some("/tmp/backups"))
+        .flag("slashing-protection-backups-to-keep", Some("5"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.slashing_protection_check_interval, 600);
+            assert_eq!(
+                config.slashing_protection_backup_dir,
+                Some(PathBuf::from("/tmp/backups"))
+            );
+            assert_eq!(config.slashing_protection_backups_to_keep, 5);
+        });
+}
+
+#[test]
+#[should_panic]
+fn slashing_protection_check_interval_zero_value() {
+    CommandLineTest::new()
+        .flag("slashing-protection-check-interval", Some("0"))
+        .run();
+}
+
+#[test]
+fn subscribe_head_events_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert!(!config.subscribe_head_events);
+    });
+    CommandLineTest::new()
+        .flag("subscribe-head-events", None)
+        .run()
+        .with_config(|config| {
+            assert!(config.subscribe_head_events);
+        });
+}
+
+#[test]
+fn record_duty_inputs_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.record_duty_inputs, None);
+    });
+    CommandLineTest::new()
+        .flag("record-duty-inputs", Some("64"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.record_duty_inputs, Some(64));
+        });
+}
+
+#[test]
+#[should_panic]
+fn record_duty_inputs_zero_value() {
+    CommandLineTest::new()
+        .flag("record-duty-inputs", Some("0"))
+        .run();
+}
+
+#[test]
+fn signing_ledger_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert!(!config.signing_ledger);
+    });
+    CommandLineTest::new()
+        .flag("signing-ledger", None)
+        .run()
+        .with_config(|config| {
+            assert!(config.signing_ledger);
+        });
+}
+
+#[test]
+fn signing_ledger_retention_days_flag() {
+    CommandLineTest::new()
+        .flag("signing-ledger", None)
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.signing_ledger_retention_days, 30);
+        });
+    CommandLineTest::new()
+        .flag("signing-ledger", None)
+        .flag("signing-ledger-retention-days", Some("7"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.signing_ledger_retention_days, 7);
+        });
+}
+
+#[test]
+#[should_panic]
+fn signing_ledger_retention_days_zero_value() {
+    CommandLineTest::new()
+        .flag("signing-ledger", None)
+        .flag("signing-ledger-retention-days", Some("0"))
+        .run();
+}
+
+#[test]
+fn otlp_endpoint_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.otlp_endpoint, None);
+    });
+    CommandLineTest::new()
+        .flag("otlp-endpoint", Some("http://localhost:4318"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.otlp_endpoint,
+                Some(sensitive_url::SensitiveUrl::parse("http://localhost:4318").unwrap())
+            );
+        });
+}
+
+#[test]
+fn stale_duties_epochs_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.stale_duties_epochs, 2);
+    });
+    CommandLineTest::new()
+        .flag("stale-duties-epochs", Some("4"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.stale_duties_epochs, 4);
+        });
+}
+
+#[test]
+#[should_panic]
+fn stale_duties_epochs_zero_value() {
+    CommandLineTest::new()
+        .flag("stale-duties-epochs", Some("0"))
+        .run();
+}
+
+#[test]
+fn max_clock_skew_slots_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.max_clock_skew_slots, None);
+    });
+    CommandLineTest::new()
+        .flag("max-clock-skew-slots", Some("2"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.max_clock_skew_slots, Some(2));
+        });
+}
+
+#[test]
+#[should_panic]
+fn max_clock_skew_slots_zero_value() {
+    CommandLineTest::new()
+        .flag("max-clock-skew-slots", Some("0"))
+        .run();
+}
+
+#[test]
+fn signing_concurrency_limit_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.signing_concurrency_limit, None);
+    });
+    CommandLineTest::new()
+        .flag("signing-concurrency-limit", Some("16"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.signing_concurrency_limit, Some(16));
+        });
+}
+
+#[test]
+#[should_panic]
+fn signing_concurrency_limit_zero_value() {
+    CommandLineTest::new()
+        .flag("signing-concurrency-limit", Some("0"))
+        .run();
+}
+
+#[test]
+#[should_panic]
+fn signing_concurrency_limit_one_value() {
+    CommandLineTest::new()
+        .flag("signing-concurrency-limit", Some("1"))
+        .run();
+}
+
+#[test]
+fn validator_monitor_token_file_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.validator_monitor_token, None);
+        assert_eq!(config.validator_monitor_cohort, None);
+    });
+
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("validator-monitor-token");
+    File::create(&path)
+        .expect("Unable to create token file")
+        .write_all(b"secret-token\n")
+        .expect("Unable to write token file");
+    CommandLineTest::new()
+        .flag("validator-monitor-token-file", path.to_str())
+        .flag("validator-monitor-cohort", Some("vc-1"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.validator_monitor_token,
+                Some("secret-token".to_string())
+            );
+            assert_eq!(config.validator_monitor_cohort, Some("vc-1".to_string()));
+        });
+}
+
+#[test]
+#[should_panic]
+fn validator_monitor_cohort_without_token_file() {
+    CommandLineTest::new()
+        .flag("validator-monitor-cohort", Some("vc-1"))
+        .run();
+}
+
+#[test]
+fn dry_run_flag() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert!(config.dry_run.is_none());
+    });
+    CommandLineTest::new()
+        .flag("dry-run", None)
+        .flag("dry-run-state", Some("/tmp/state.ssz"))
+        .flag("dry-run-output", Some("/tmp/messages.json"))
+        .run()
+        .with_config(|config| {
+            let dry_run = config.dry_run.as_ref().unwrap();
+            assert_eq!(dry_run.state_path, PathBuf::from("/tmp/state.ssz"));
+            assert_eq!(dry_run.output_path, PathBuf::from("/tmp/messages.json"));
+        });
+}
+
+#[test]
+#[should_panic]
+fn dry_run_flag_without_state() {
+    CommandLineTest::new()
+        .flag("dry-run", None)
+        .flag("dry-run-output", Some("/tmp/messages.json"))
+        .run();
+}
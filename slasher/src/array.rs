@@ -483,6 +483,39 @@ pub fn apply_attestation_for_validator<E: EthSpec, T: TargetArrayChunk>(
     Ok(AttesterSlashingStatus::NotSlashable)
 }
 
+/// Check whether `attestation` surrounds or is surrounded by an existing attestation from
+/// `validator_index`, without updating the database.
+pub fn check_attestation_for_validator<E: EthSpec, T: TargetArrayChunk>(
+    db: &SlasherDB<E>,
+    txn: &mut RwTransaction<'_>,
+    validator_index: u64,
+    attestation: &IndexedAttestation<E>,
+    current_epoch: Epoch,
+    config: &Config,
+) -> Result<AttesterSlashingStatus<E>, Error> {
+    let validator_chunk_index = config.validator_chunk_index(validator_index);
+    // Chunks are only modified in memory, so that the check sees the same arrays as an update.
+    let mut updated_chunks = BTreeMap::new();
+    epoch_update_for_validator::<E, T>(
+        db,
+        txn,
+        &mut updated_chunks,
+        validator_chunk_index,
+        validator_index,
+        current_epoch,
+        config,
+    )?;
+    let chunk = get_chunk_for_update(
+        db,
+        txn,
+        &mut updated_chunks,
+        validator_chunk_index,
+        config.chunk_index(attestation.data.source.epoch),
+        config,
+    )?;
+    chunk.check_slashable(db, txn, validator_index, attestation, config)
+}
+
 pub fn update<E: EthSpec>(
     db: &SlasherDB<E>,
     txn: &mut RwTransaction<'_>,
@@ -586,6 +586,31 @@ impl<E: EthSpec> SlasherDB<E> {
         }
     }
 
+    /// Check whether `attestation` is a double vote by `validator_index`, without updating the
+    /// database.
+    pub fn check_attester_record(
+        &self,
+        txn: &mut RwTransaction<'_>,
+        validator_index: u64,
+        attestation: &IndexedAttestation<E>,
+    ) -> Result<AttesterSlashingStatus<E>, Error> {
+        let target_epoch = attestation.data.target.epoch;
+        let max_target = self.get_attester_max_target(validator_index, txn)?;
+
+        if let Some(existing_record) =
+            self.get_attester_record(txn, validator_index, target_epoch, max_target)?
+        {
+            let existing_attestation =
+                self.get_indexed_attestation(txn, existing_record.indexed_attestation_id)?;
+            if attestation.is_double_vote(&existing_attestation) {
+                return Ok(AttesterSlashingStatus::DoubleVote(Box::new(
+                    existing_attestation,
+                )));
+            }
+        }
+        Ok(AttesterSlashingStatus::NotSlashable)
+    }
+
     pub fn get_attestation_for_validator(
         &self,
         txn: &mut RwTransaction<'_>,
@@ -1,3 +1,4 @@
+use crate::array::{MaxTargetChunk, MinTargetChunk};
 use crate::batch_stats::{AttestationStats, BatchStats, BlockStats};
 use crate::metrics::{
     self, SLASHER_NUM_ATTESTATIONS_DEFERRED, SLASHER_NUM_ATTESTATIONS_DROPPED,
@@ -5,8 +6,9 @@ use crate::metrics::{
     SLASHER_NUM_BLOCKS_PROCESSED,
 };
 use crate::{
-    array, AttestationBatch, AttestationQueue, AttesterRecord, BlockQueue, Config, Error,
-    IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch, SlasherDB,
+    array, AttestationBatch, AttestationQueue, AttesterRecord, AttesterSlashingStatus, BlockQueue,
+    Config, Error, IndexedAttestationId, ProposerSlashingStatus, RwTransaction, SimpleBatch,
+    SlasherDB,
 };
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
@@ -75,6 +77,63 @@ impl<E: EthSpec> Slasher<E> {
         self.block_queue.queue(block_header);
     }
 
+    /// Check whether `attestation` is slashable against the attestations in the database.
+    ///
+    /// The attestation is not stored, so this does not affect the slashings found by the slasher.
+    pub fn check_attestation(
+        &self,
+        attestation: &IndexedAttestation<E>,
+        current_epoch: Epoch,
+    ) -> Result<HashSet<AttesterSlashing<E>>, Error> {
+        let mut slashings = HashSet::new();
+
+        // Check attestations from future epochs as if they were being processed in their target
+        // epoch, as the slasher would do.
+        let current_epoch = std::cmp::max(current_epoch, attestation.data.target.epoch);
+
+        // Attestations that the slasher would drop can't be checked.
+        let source_epoch = attestation.data.source.epoch;
+        if source_epoch > attestation.data.target.epoch
+            || source_epoch + self.config.history_length as u64 <= current_epoch
+        {
+            return Ok(slashings);
+        }
+
+        // The transaction is never committed, so the database is left unchanged. It does block
+        // `process_queued` until it is dropped, which is why the HTTP endpoint exposing this
+        // requires the admin token.
+        let mut txn = self.db.begin_rw_txn()?;
+
+        for &validator_index in attestation.attesting_indices.iter() {
+            let mut status =
+                self.db
+                    .check_attester_record(&mut txn, validator_index, attestation)?;
+            if status == AttesterSlashingStatus::NotSlashable {
+                status = array::check_attestation_for_validator::<_, MinTargetChunk>(
+                    &self.db,
+                    &mut txn,
+                    validator_index,
+                    attestation,
+                    current_epoch,
+                    &self.config,
+                )?;
+            }
+            if status == AttesterSlashingStatus::NotSlashable {
+                status = array::check_attestation_for_validator::<_, MaxTargetChunk>(
+                    &self.db,
+                    &mut txn,
+                    validator_index,
+                    attestation,
+                    current_epoch,
+                    &self.config,
+                )?;
+            }
+            slashings.extend(status.into_slashing(attestation));
+        }
+
+        Ok(slashings)
+    }
+
     /// Apply queued blocks and attestations to the on-disk database, and detect slashings!
     pub fn process_queued(&self, current_epoch: Epoch) -> Result<BatchStats, Error> {
         let mut txn = self.db.begin_rw_txn()?;
@@ -148,6 +148,53 @@ fn surrounded_by_single_val_multi_chunk() {
     slasher_test_indiv(&attestations, &slashings, 4 * chunk_size);
 }
 
+// Check attestations against the database without storing them.
+#[test]
+fn check_attestation_without_storing() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(3);
+
+    let v = vec![0, 1];
+    let existing = indexed_att(&v, 1, 2, 0);
+    slasher.accept_attestation(existing.clone());
+    slasher.process_queued(current_epoch).unwrap();
+
+    let check = |attestation: &IndexedAttestation<E>| {
+        slasher
+            .check_attestation(attestation, current_epoch)
+            .unwrap()
+    };
+
+    // Identical and unrelated attestations are not slashable.
+    assert_eq!(check(&existing), hashset! {});
+    assert_eq!(check(&indexed_att(&v, 2, 3, 0)), hashset! {});
+    assert_eq!(check(&indexed_att([2], 0, 3, 0)), hashset! {});
+
+    let double_vote = indexed_att(&v, 1, 2, 1);
+    assert_eq!(
+        check(&double_vote),
+        hashset![att_slashing(&existing, &double_vote)]
+    );
+
+    let surrounding = indexed_att(&v, 0, 3, 0);
+    assert_eq!(
+        check(&surrounding),
+        hashset![att_slashing(&surrounding, &existing)]
+    );
+
+    // Checked attestations are not stored, so they don't lead to slashings.
+    slasher.process_queued(current_epoch).unwrap();
+    assert_eq!(slasher.get_attester_slashings(), hashset! {});
+    assert_eq!(
+        check(&double_vote),
+        hashset![att_slashing(&existing, &double_vote)]
+    );
+
+    drop(slasher);
+}
+
 // Process each attestation individually, and confirm that the slashings produced are as expected.
 fn slasher_test_indiv(
     attestations: &[IndexedAttestation<E>],
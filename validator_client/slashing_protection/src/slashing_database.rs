@@ -9,7 +9,7 @@ use filesystem::restrict_file_permissions;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
 use std::fs::File;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use types::{AttestationData, BeaconBlockHeader, Epoch, Hash256, PublicKeyBytes, SignedRoot, Slot};
 
@@ -34,6 +34,7 @@ pub const VALIDATORS_ENABLED_CID: i64 = 2;
 #[derive(Debug, Clone)]
 pub struct SlashingDatabase {
     conn_pool: Pool,
+    path: PathBuf,
 }
 
 impl SlashingDatabase {
@@ -97,7 +98,10 @@ impl SlashingDatabase {
         Self::apply_schema_migrations(&txn)?;
         txn.commit()?;
 
-        Ok(Self { conn_pool })
+        Ok(Self {
+            conn_pool,
+            path: path.to_path_buf(),
+        })
     }
 
     /// Open an existing `SlashingDatabase` from disk.
@@ -105,7 +109,10 @@ impl SlashingDatabase {
     /// This will automatically check for and apply the latest schema migrations.
     pub fn open(path: &Path) -> Result<Self, NotSafe> {
         let conn_pool = Self::open_conn_pool(path)?;
-        let db = Self { conn_pool };
+        let db = Self {
+            conn_pool,
+            path: path.to_path_buf(),
+        };
         db.with_transaction(Self::apply_schema_migrations)?;
         Ok(db)
     }
@@ -1021,6 +1028,50 @@ impl SlashingDatabase {
         Ok(count)
     }
 
+    /// The path of the database file on disk.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Run SQLite's `quick_check` over the database, returning the problems found.
+    ///
+    /// An empty list means that the database passed the check.
+    pub fn integrity_check(&self) -> Result<Vec<String>, NotSafe> {
+        let conn = self.conn_pool.get()?;
+        let mut stmt = conn.prepare("PRAGMA quick_check")?;
+        let rows = stmt
+            .query_map(params![], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows.into_iter().filter(|row| row != "ok").collect())
+    }
+
+    /// Return the size in bytes of the database file and of its write-ahead log.
+    ///
+    /// The write-ahead log size is zero if there is no log on disk.
+    pub fn file_sizes(&self) -> Result<(u64, u64), NotSafe> {
+        let db_size = self.path.metadata()?.len();
+        let mut wal_path = self.path.clone().into_os_string();
+        wal_path.push("-wal");
+        let wal_size = Path::new(&wal_path)
+            .metadata()
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        Ok((db_size, wal_size))
+    }
+
+    /// Write a consistent copy of the database to `dest`, which must not already exist.
+    ///
+    /// The copy is taken through the open connection, so signing is blocked while it is written.
+    pub fn backup(&self, dest: &Path) -> Result<(), NotSafe> {
+        let dest_str = dest
+            .to_str()
+            .ok_or_else(|| NotSafe::SQLError(format!("Invalid backup path: {:?}", dest)))?;
+        let conn = self.conn_pool.get()?;
+        conn.execute("VACUUM INTO ?1", params![dest_str])?;
+        restrict_file_permissions(dest).map_err(|_| NotSafe::PermissionsError)?;
+        Ok(())
+    }
+
     /// Get a summary of a validator's slashing protection data including minimums and maximums.
     pub fn validator_summary(
         &self,
@@ -1200,6 +1251,28 @@ mod tests {
         SlashingDatabase::open(&file).unwrap_err();
     }
 
+    #[test]
+    fn integrity_check_and_backup() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        db.register_validator(PublicKeyBytes::empty()).unwrap();
+
+        assert_eq!(db.integrity_check().unwrap(), Vec::<String>::new());
+        let (db_size, wal_size) = db.file_sizes().unwrap();
+        assert!(db_size > 0);
+        assert_eq!(wal_size, 0);
+
+        let backup = dir.path().join("backup.sqlite");
+        db.backup(&backup).unwrap();
+        // A backup can't overwrite an existing file.
+        db.backup(&backup).unwrap_err();
+        drop(db);
+
+        let restored = SlashingDatabase::open(&backup).unwrap();
+        assert_eq!(restored.num_validator_rows().unwrap(), 1);
+    }
+
     // Attempting to create the same database twice should error.
     #[test]
     fn double_create_error() {
@@ -0,0 +1,151 @@
+//! Verification that published attestations are actually included on chain.
+//!
+//! A few slots after attesting, the blocks following the attestation slot are searched for the
+//! attestations we published. The inclusion delay is recorded for each validator and failures are
+//! fed back into the `BeaconNodeFallback`, so that we prefer the beacon nodes whose attestations
+//! actually land.
+
+use parking_lot::Mutex;
+use types::{Attestation, AttestationData, EthSpec, Slot};
+
+/// An attestation which has been published but not yet checked for inclusion.
+#[derive(Debug, Clone)]
+pub struct PendingAttestation {
+    pub data: AttestationData,
+    /// The `(validator_index, validator_committee_index)` of each validator which signed `data`.
+    pub validators: Vec<(u64, usize)>,
+    /// The beacon node which accepted the attestations.
+    pub beacon_node: String,
+}
+
+impl PendingAttestation {
+    /// Returns the inclusion delay of each validator's attestation, or `None` if it was not
+    /// included in any of the given `(slot, attestations)` blocks.
+    pub fn inclusion_delays<E: EthSpec>(
+        &self,
+        blocks: &[(Slot, Vec<Attestation<E>>)],
+    ) -> Vec<(u64, Option<u64>)> {
+        self.validators
+            .iter()
+            .map(|&(validator_index, validator_committee_index)| {
+                let delay = blocks
+                    .iter()
+                    .filter(|(slot, attestations)| {
+                        *slot > self.data.slot
+                            && attestations.iter().any(|attestation| {
+                                attestation.data == self.data
+                                    && attestation
+                                        .aggregation_bits
+                                        .get(validator_committee_index)
+                                        .unwrap_or(false)
+                            })
+                    })
+                    .map(|(slot, _)| slot.as_u64().saturating_sub(self.data.slot.as_u64()))
+                    .min();
+                (validator_index, delay)
+            })
+            .collect()
+    }
+}
+
+/// Holds published attestations until they are due to be checked for inclusion.
+pub struct InclusionTracker {
+    /// The number of slots after the attestation slot to search for inclusion.
+    check_delay: u64,
+    pending: Mutex<Vec<PendingAttestation>>,
+}
+
+impl InclusionTracker {
+    pub fn new(check_delay: u64) -> Self {
+        Self {
+            check_delay,
+            pending: Mutex::new(vec![]),
+        }
+    }
+
+    pub fn check_delay(&self) -> u64 {
+        self.check_delay
+    }
+
+    pub fn insert(&self, attestation: PendingAttestation) {
+        self.pending.lock().push(attestation)
+    }
+
+    /// Removes and returns all attestations which are due to be checked at `current_slot`.
+    pub fn take_due(&self, current_slot: Slot) -> Vec<PendingAttestation> {
+        let mut pending = self.pending.lock();
+        let (due, not_due) = pending
+            .drain(..)
+            .partition(|attestation| attestation.data.slot + self.check_delay <= current_slot);
+        *pending = not_due;
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{AggregateSignature, BitList, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    fn attestation(data: &AttestationData, positions: &[usize]) -> Attestation<E> {
+        let mut aggregation_bits = BitList::with_capacity(8).unwrap();
+        for &position in positions {
+            aggregation_bits.set(position, true).unwrap();
+        }
+        Attestation {
+            aggregation_bits,
+            data: data.clone(),
+            signature: AggregateSignature::infinity(),
+        }
+    }
+
+    fn pending(slot: u64) -> PendingAttestation {
+        PendingAttestation {
+            data: AttestationData {
+                slot: Slot::new(slot),
+                ..AttestationData::default()
+            },
+            validators: vec![(10, 0), (11, 1), (12, 2)],
+            beacon_node: "http://localhost:5052/".into(),
+        }
+    }
+
+    #[test]
+    fn inclusion_delays() {
+        let pending = pending(4);
+        let mut other_data = pending.data.clone();
+        other_data.index = 1;
+
+        let blocks = vec![
+            (Slot::new(5), vec![attestation(&pending.data, &[1])]),
+            (
+                Slot::new(6),
+                vec![
+                    attestation(&pending.data, &[0, 1]),
+                    attestation(&other_data, &[2]),
+                ],
+            ),
+        ];
+
+        assert_eq!(
+            pending.inclusion_delays(&blocks),
+            vec![(10, Some(2)), (11, Some(1)), (12, None)]
+        );
+    }
+
+    #[test]
+    fn take_due() {
+        let tracker = InclusionTracker::new(2);
+        tracker.insert(pending(4));
+        tracker.insert(pending(5));
+
+        assert!(tracker.take_due(Slot::new(5)).is_empty());
+        let due = tracker.take_due(Slot::new(6));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].data.slot, 4);
+        assert_eq!(tracker.take_due(Slot::new(7)).len(), 1);
+        assert!(tracker.take_due(Slot::new(8)).is_empty());
+    }
+}
@@ -1,4 +1,7 @@
+use crate::attestation_inclusion::{InclusionTracker, PendingAttestation};
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::duty_recorder::DutyRecorder;
+use crate::signing_ledger::SigningLedger;
 use crate::{
     duties_service::{DutiesService, DutyAndProof},
     http_metrics::metrics,
@@ -6,6 +9,8 @@ use crate::{
     OfflineOnFailure,
 };
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::SignedMessageKind;
+use eth2::types::BlockId;
 use futures::future::join_all;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
@@ -19,6 +24,30 @@ use types::{
     Slot,
 };
 
+/// The maximum number of times that the attestations of a committee will be retried after failing
+/// to produce attestation data.
+const MAX_ATTESTATION_RETRIES: usize = 2;
+/// The delay before retrying, which gives the fallback beacon nodes a moment to update their status.
+const ATTESTATION_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// The reasons for which the attestations of a committee may fail to be produced.
+#[derive(Debug)]
+enum CommitteeFailure {
+    /// The slot clock could not be read.
+    SlotClock,
+    /// No beacon node was able to produce attestation data.
+    AttestationData(String),
+}
+
+impl CommitteeFailure {
+    fn metrics_label(&self) -> &'static str {
+        match self {
+            CommitteeFailure::SlotClock => metrics::SLOT_CLOCK,
+            CommitteeFailure::AttestationData(_) => metrics::ATTESTATION_DATA,
+        }
+    }
+}
+
 /// Builds an `AttestationService`.
 pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     duties_service: Option<Arc<DutiesService<T, E>>>,
@@ -26,6 +55,9 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    inclusion_tracker: Option<Arc<InclusionTracker>>,
+    duty_recorder: Option<Arc<DutyRecorder>>,
+    signing_ledger: Option<Arc<SigningLedger>>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -36,6 +68,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            inclusion_tracker: None,
+            duty_recorder: None,
+            signing_ledger: None,
         }
     }
 
@@ -64,6 +99,25 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    /// Check that attestations are included on chain `check_delay` slots after they are
+    /// published.
+    pub fn attestation_inclusion_check_delay(mut self, check_delay: Option<u64>) -> Self {
+        self.inclusion_tracker = check_delay.map(|delay| Arc::new(InclusionTracker::new(delay)));
+        self
+    }
+
+    /// Record the inputs of each attestation so that it can be replayed.
+    pub fn duty_recorder(mut self, duty_recorder: Option<Arc<DutyRecorder>>) -> Self {
+        self.duty_recorder = duty_recorder;
+        self
+    }
+
+    /// Record each signed attestation in the signing ledger.
+    pub fn signing_ledger(mut self, signing_ledger: Option<Arc<SigningLedger>>) -> Self {
+        self.signing_ledger = signing_ledger;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
         Ok(AttestationService {
             inner: Arc::new(Inner {
@@ -82,6 +136,9 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                inclusion_tracker: self.inclusion_tracker,
+                duty_recorder: self.duty_recorder,
+                signing_ledger: self.signing_ledger,
             }),
         })
     }
@@ -94,6 +151,9 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    inclusion_tracker: Option<Arc<InclusionTracker>>,
+    duty_recorder: Option<Arc<DutyRecorder>>,
+    signing_ledger: Option<Arc<SigningLedger>>,
 }
 
 /// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
@@ -222,6 +282,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         // production.
         self.spawn_slashing_protection_pruning_task(slot, aggregate_production_instant);
 
+        self.spawn_inclusion_check_task(slot);
+
         Ok(())
     }
 
@@ -256,18 +318,62 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         // Step 1.
         //
         // Download, sign and publish an `Attestation` for each validator.
-        let attestation_opt = self
-            .produce_and_publish_attestations(slot, committee_index, &validator_duties)
-            .await
-            .map_err(move |e| {
-                crit!(
-                    log,
-                    "Error during attestation routine";
-                    "error" => format!("{:?}", e),
-                    "committee_index" => committee_index,
-                    "slot" => slot.as_u64(),
-                )
-            })?;
+        //
+        // Errors are only returned before any attestation has been signed, so the whole step may
+        // be retried. Each failure marks the failing beacon nodes as offline, so a retry prefers
+        // any other healthy beacon node. Retries stop once the aggregation deadline approaches.
+        let mut attempts = 0;
+        let attestation_opt = loop {
+            attempts += 1;
+            match self
+                .produce_and_publish_attestations(slot, committee_index, &validator_duties)
+                .await
+            {
+                Ok(attestation_opt) => {
+                    if attempts > 1 {
+                        info!(
+                            log,
+                            "Attestation routine succeeded after retry";
+                            "attempts" => attempts,
+                            "committee_index" => committee_index,
+                            "slot" => slot.as_u64(),
+                        );
+                    }
+                    break attestation_opt;
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::ATTESTATION_COMMITTEE_FAILURES,
+                        &[&committee_index.to_string(), e.metrics_label()],
+                    );
+
+                    let retry_instant = Instant::now() + ATTESTATION_RETRY_DELAY;
+                    if attempts > MAX_ATTESTATION_RETRIES
+                        || retry_instant >= aggregate_production_instant
+                    {
+                        crit!(
+                            log,
+                            "Error during attestation routine";
+                            "error" => format!("{:?}", e),
+                            "attempts" => attempts,
+                            "committee_index" => committee_index,
+                            "slot" => slot.as_u64(),
+                        );
+                        return Err(());
+                    }
+
+                    warn!(
+                        log,
+                        "Retrying attestation routine";
+                        "error" => format!("{:?}", e),
+                        "attempts" => attempts,
+                        "committee_index" => committee_index,
+                        "slot" => slot.as_u64(),
+                    );
+                    sleep_until(retry_instant).await;
+                }
+            }
+        };
 
         drop(attestations_timer);
 
@@ -293,6 +399,10 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             self.produce_and_publish_aggregates(&attestation_data, &validator_duties)
                 .await
                 .map_err(move |e| {
+                    metrics::inc_counter_vec(
+                        &metrics::ATTESTATION_COMMITTEE_FAILURES,
+                        &[&committee_index.to_string(), metrics::AGGREGATES],
+                    );
                     crit!(
                         log,
                         "Error during attestation routine";
@@ -323,7 +433,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         slot: Slot,
         committee_index: CommitteeIndex,
         validator_duties: &[DutyAndProof],
-    ) -> Result<Option<AttestationData>, String> {
+    ) -> Result<Option<AttestationData>, CommitteeFailure> {
         let log = self.context.log();
 
         if validator_duties.is_empty() {
@@ -333,7 +443,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         let current_epoch = self
             .slot_clock
             .now()
-            .ok_or("Unable to determine current slot from clock")?
+            .ok_or(CommitteeFailure::SlotClock)?
             .epoch(E::slots_per_epoch());
 
         let attestation_data = self
@@ -354,7 +464,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 },
             )
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| CommitteeFailure::AttestationData(e.to_string()))?;
+
+        if let Some(duty_recorder) = &self.duty_recorder {
+            duty_recorder.record_attestation(&attestation_data, current_epoch, validator_duties);
+        }
 
         // Create futures to produce signed `Attestation` objects.
         let attestation_data_ref = &attestation_data;
@@ -384,6 +498,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 signature: AggregateSignature::infinity(),
             };
 
+            let signing_start = Instant::now();
             match self
                 .validator_store
                 .sign_attestation(
@@ -394,7 +509,10 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                 )
                 .await
             {
-                Ok(()) => Some((attestation, duty.validator_index)),
+                Ok(()) => Some((
+                    (attestation, duty.validator_index),
+                    (duty.pubkey, signing_start.elapsed()),
+                )),
                 Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                     // A pubkey can be missing when a validator was recently
                     // removed via the API.
@@ -424,14 +542,16 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = join_all(signing_futures)
+        let (signed_attestations, signers): (Vec<_>, Vec<_>) = join_all(signing_futures)
             .await
             .into_iter()
             .flatten()
             .unzip();
+        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) =
+            signed_attestations.into_iter().unzip();
 
         // Post the attestations to the BN.
-        match self
+        let publication = self
             .beacon_nodes
             .first_success(
                 RequireSynced::No,
@@ -444,28 +564,73 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     beacon_node
                         .post_beacon_pool_attestations(attestations)
                         .await
+                        .map(|()| beacon_node.to_string())
                 },
             )
-            .await
-        {
-            Ok(()) => info!(
-                log,
-                "Successfully published attestations";
-                "count" => attestations.len(),
-                "validator_indices" => ?validator_indices,
-                "head_block" => ?attestation_data.beacon_block_root,
-                "committee_index" => attestation_data.index,
-                "slot" => attestation_data.slot.as_u64(),
-                "type" => "unaggregated",
-            ),
-            Err(e) => error!(
+            .await;
+
+        if let Some(signing_ledger) = &self.signing_ledger {
+            signing_ledger.record_or_warn(
+                SignedMessageKind::Attestation,
+                slot,
+                &signers,
+                publication.as_deref().map_err(ToString::to_string),
                 log,
-                "Unable to publish attestations";
-                "error" => %e,
-                "committee_index" => attestation_data.index,
-                "slot" => slot.as_u64(),
-                "type" => "unaggregated",
-            ),
+            );
+        }
+
+        match publication {
+            Ok(beacon_node) => {
+                info!(
+                    log,
+                    "Successfully published attestations";
+                    "count" => attestations.len(),
+                    "validator_indices" => ?validator_indices,
+                    "head_block" => ?attestation_data.beacon_block_root,
+                    "committee_index" => attestation_data.index,
+                    "slot" => attestation_data.slot.as_u64(),
+                    "type" => "unaggregated",
+                );
+
+                if let Some(inclusion_tracker) = &self.inclusion_tracker {
+                    let validators = validator_duties
+                        .iter()
+                        .filter(|duty_and_proof| {
+                            validator_indices.contains(&duty_and_proof.duty.validator_index)
+                        })
+                        .map(|duty_and_proof| {
+                            (
+                                duty_and_proof.duty.validator_index,
+                                duty_and_proof.duty.validator_committee_index as usize,
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    if !validators.is_empty() {
+                        inclusion_tracker.insert(PendingAttestation {
+                            data: attestation_data.clone(),
+                            validators,
+                            beacon_node,
+                        });
+                    }
+                }
+            }
+            Err(e) => {
+                metrics::inc_counter_vec(
+                    &metrics::ATTESTATION_COMMITTEE_FAILURES,
+                    &[
+                        &attestation_data.index.to_string(),
+                        metrics::ATTESTATIONS_HTTP_POST,
+                    ],
+                );
+                error!(
+                    log,
+                    "Unable to publish attestations";
+                    "error" => %e,
+                    "committee_index" => attestation_data.index,
+                    "slot" => slot.as_u64(),
+                    "type" => "unaggregated",
+                )
+            }
         }
 
         Ok(Some(attestation_data))
@@ -624,6 +789,142 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Spawn a task to check whether the attestations which are now due have been included on
+    /// chain, feeding the result back into beacon node selection.
+    fn spawn_inclusion_check_task(&self, current_slot: Slot) {
+        let inclusion_tracker = match &self.inclusion_tracker {
+            Some(inclusion_tracker) => inclusion_tracker,
+            None => return,
+        };
+
+        let due = inclusion_tracker.take_due(current_slot);
+        if due.is_empty() {
+            return;
+        }
+
+        let check_delay = inclusion_tracker.check_delay();
+        let attestation_service = self.clone();
+        self.inner.context.executor.spawn(
+            async move {
+                attestation_service
+                    .check_inclusion(current_slot, check_delay, due)
+                    .await
+            },
+            "attestation_inclusion",
+        );
+    }
+
+    /// Search the blocks up to `current_slot` for each of the `due` attestations, recording the
+    /// inclusion delay of each validator and whether the publishing beacon node's attestations
+    /// are landing.
+    async fn check_inclusion(
+        &self,
+        current_slot: Slot,
+        check_delay: u64,
+        due: Vec<PendingAttestation>,
+    ) {
+        let log = self.context.log();
+
+        let start_slot = match due.iter().map(|pending| pending.data.slot).min() {
+            Some(slot) => slot + 1,
+            None => return,
+        };
+
+        let mut blocks = vec![];
+        for slot in (start_slot.as_u64()..=current_slot.as_u64()).map(Slot::new) {
+            match self
+                .beacon_nodes
+                .first_success(
+                    RequireSynced::Yes,
+                    OfflineOnFailure::No,
+                    |beacon_node| async move {
+                        beacon_node
+                            .get_beacon_blocks_attestations::<E>(BlockId::Slot(slot))
+                            .await
+                    },
+                )
+                .await
+            {
+                Ok(Some(response)) => blocks.push((slot, response.data)),
+                // There is no block at a skipped slot.
+                Ok(None) => (),
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Unable to check attestation inclusion";
+                        "error" => %e,
+                        "slot" => slot.as_u64(),
+                    );
+                    return;
+                }
+            }
+        }
+
+        let per_validator_metrics = self.duties_service.per_validator_metrics();
+
+        for pending in due {
+            let attestation_slot = pending.data.slot;
+            let deadline = attestation_slot + check_delay;
+
+            // Don't hold the beacon node responsible if no blocks were produced in time.
+            if !blocks
+                .iter()
+                .any(|(slot, _)| *slot > attestation_slot && *slot <= deadline)
+            {
+                continue;
+            }
+
+            let mut missed = vec![];
+            for (validator_index, inclusion_delay) in pending.inclusion_delays(&blocks) {
+                match inclusion_delay.filter(|delay| *delay <= check_delay) {
+                    Some(inclusion_delay) => {
+                        metrics::inc_counter_vec(
+                            &metrics::ATTESTATION_INCLUSION_TOTAL,
+                            &[metrics::INCLUDED],
+                        );
+                        if per_validator_metrics {
+                            metrics::set_int_gauge(
+                                &metrics::ATTESTATION_INCLUSION_DELAY,
+                                &[&validator_index.to_string()],
+                                inclusion_delay as i64,
+                            );
+                        }
+                        debug!(
+                            log,
+                            "Attestation included";
+                            "validator_index" => validator_index,
+                            "inclusion_delay" => inclusion_delay,
+                            "slot" => attestation_slot.as_u64(),
+                        );
+                    }
+                    None => {
+                        metrics::inc_counter_vec(
+                            &metrics::ATTESTATION_INCLUSION_TOTAL,
+                            &[metrics::MISSED],
+                        );
+                        missed.push(validator_index);
+                    }
+                }
+            }
+
+            let included = missed.len() < pending.validators.len();
+            if !missed.is_empty() {
+                warn!(
+                    log,
+                    "Attestations not included";
+                    "validator_indices" => ?missed,
+                    "beacon_node" => &pending.beacon_node,
+                    "check_delay_slots" => check_delay,
+                    "committee_index" => pending.data.index,
+                    "slot" => attestation_slot.as_u64(),
+                );
+            }
+
+            self.beacon_nodes
+                .record_attestation_inclusion(&pending.beacon_node, included);
+        }
+    }
+
     /// Spawn a blocking task to run the slashing protection pruning process.
     ///
     /// Start the task at `pruning_instant` to avoid interference with other tasks.
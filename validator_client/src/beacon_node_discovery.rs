@@ -0,0 +1,248 @@
+//! Discovers beacon nodes from the DNS records of a single name, allowing a fleet of beacon nodes
+//! to be scaled without changing the configuration of each validator client.
+//!
+//! Each SRV record for the name is resolved to an `http://<target>:<port>` URL. SRV records are
+//! ordered by priority (lowest first) and then by weight (highest first). Any TXT record for the
+//! name which contains an `http://` or `https://` URL is also used, after the SRV records, which
+//! allows for endpoints with TLS or a path.
+//!
+//! Discovered beacon nodes are always tried after the nodes configured via `--beacon-nodes`.
+use crate::beacon_node_fallback::BeaconNodeFallback;
+use crate::http_metrics::metrics;
+use crate::{build_beacon_node_http_client, Config};
+use environment::RuntimeContext;
+use lighthouse_metrics::set_gauge;
+use sensitive_url::SensitiveUrl;
+use slog::{debug, error, info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use trust_dns_resolver::error::{ResolveError, ResolveErrorKind};
+use trust_dns_resolver::TokioAsyncResolver;
+use types::EthSpec;
+
+/// A single SRV record for a beacon node.
+#[derive(Debug, Clone, PartialEq)]
+struct SrvTarget {
+    priority: u16,
+    weight: u16,
+    host: String,
+    port: u16,
+}
+
+/// Starts a service that periodically resolves `dns_name` and replaces the discovered candidates
+/// of `beacon_nodes` with the result.
+///
+/// If resolution fails the previously discovered candidates are kept.
+pub fn start_beacon_node_discovery_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    dns_name: String,
+    config: Config,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+) {
+    let log = context.log().clone();
+    let slot_duration = Duration::from_secs(context.eth2_config.spec.seconds_per_slot);
+    let interval = Duration::from_secs(config.beacon_nodes_dns_interval);
+
+    let resolver = match TokioAsyncResolver::tokio_from_system_conf() {
+        Ok(resolver) => resolver,
+        Err(e) => {
+            error!(
+                log,
+                "Unable to start beacon node discovery";
+                "error" => %e,
+                "dns_name" => &dns_name,
+            );
+            return;
+        }
+    };
+
+    let future = async move {
+        loop {
+            match resolve_beacon_nodes(&resolver, &dns_name).await {
+                Ok(urls) => {
+                    let discovered = urls
+                        .into_iter()
+                        .filter_map(|url| {
+                            // Discovered nodes are always fallbacks, so use the quicker timeouts.
+                            build_beacon_node_http_client(
+                                url.clone(),
+                                &config,
+                                slot_duration,
+                                !config.use_long_timeouts,
+                            )
+                            .map_err(|e| {
+                                warn!(
+                                    log,
+                                    "Unable to use discovered beacon node";
+                                    "error" => e,
+                                    "node" => %url,
+                                )
+                            })
+                            .ok()
+                        })
+                        .collect::<Vec<_>>();
+                    let num_discovered = discovered.len();
+
+                    let (added, removed) =
+                        beacon_nodes.update_discovered_candidates(discovered).await;
+                    set_gauge(
+                        &metrics::TOTAL_BEACON_NODES_COUNT,
+                        beacon_nodes.num_total() as i64,
+                    );
+
+                    if added > 0 || removed > 0 {
+                        info!(
+                            log,
+                            "Updated discovered beacon nodes";
+                            "discovered" => num_discovered,
+                            "added" => added,
+                            "removed" => removed,
+                            "total" => beacon_nodes.num_total(),
+                        );
+                    } else {
+                        debug!(
+                            log,
+                            "Discovered beacon nodes unchanged";
+                            "discovered" => num_discovered,
+                        );
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Failed to discover beacon nodes";
+                        "error" => e,
+                        "dns_name" => &dns_name,
+                    );
+                }
+            }
+
+            sleep(interval).await;
+        }
+    };
+
+    context.executor.spawn(future, "beacon_node_discovery");
+}
+
+/// Returns the URLs of the beacon nodes published under `dns_name`, in order of preference.
+async fn resolve_beacon_nodes(
+    resolver: &TokioAsyncResolver,
+    dns_name: &str,
+) -> Result<Vec<SensitiveUrl>, String> {
+    let srv_targets = match resolver.srv_lookup(dns_name).await {
+        Ok(lookup) => lookup
+            .iter()
+            .map(|srv| SrvTarget {
+                priority: srv.priority(),
+                weight: srv.weight(),
+                host: srv.target().to_utf8(),
+                port: srv.port(),
+            })
+            .collect(),
+        Err(e) if is_no_records(&e) => vec![],
+        Err(e) => return Err(format!("SRV lookup failed: {}", e)),
+    };
+
+    let txt_records = match resolver.txt_lookup(dns_name).await {
+        Ok(lookup) => lookup
+            .iter()
+            .map(|txt| {
+                txt.txt_data()
+                    .iter()
+                    .map(|data| String::from_utf8_lossy(data).into_owned())
+                    .collect::<String>()
+            })
+            .collect(),
+        Err(e) if is_no_records(&e) => vec![],
+        Err(e) => return Err(format!("TXT lookup failed: {}", e)),
+    };
+
+    Ok(beacon_node_urls(srv_targets, txt_records))
+}
+
+fn is_no_records(e: &ResolveError) -> bool {
+    matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. })
+}
+
+/// Builds the list of beacon node URLs from SRV and TXT records, in order of preference and
+/// without duplicates.
+///
+/// SRV records with a target of `.` (meaning the service is unavailable) and TXT records which do
+/// not contain a valid URL are ignored.
+fn beacon_node_urls(
+    mut srv_targets: Vec<SrvTarget>,
+    txt_records: Vec<String>,
+) -> Vec<SensitiveUrl> {
+    srv_targets.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then_with(|| b.weight.cmp(&a.weight))
+    });
+
+    let srv_urls = srv_targets.into_iter().filter_map(|target| {
+        let host = target.host.trim_end_matches('.');
+        if host.is_empty() {
+            return None;
+        }
+        SensitiveUrl::parse(&format!("http://{}:{}", host, target.port)).ok()
+    });
+    let txt_urls = txt_records
+        .into_iter()
+        .map(|record| record.trim().to_string())
+        .filter(|record| record.starts_with("http://") || record.starts_with("https://"))
+        .filter_map(|record| SensitiveUrl::parse(&record).ok());
+
+    let mut seen = HashSet::new();
+    srv_urls
+        .chain(txt_urls)
+        .filter(|url| seen.insert(url.full.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn srv(priority: u16, weight: u16, host: &str, port: u16) -> SrvTarget {
+        SrvTarget {
+            priority,
+            weight,
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    #[test]
+    fn orders_and_filters_records() {
+        let urls = beacon_node_urls(
+            vec![
+                srv(20, 0, "bn3.example.com.", 5052),
+                srv(10, 10, "bn2.example.com.", 5052),
+                srv(10, 50, "bn1.example.com.", 5052),
+                srv(30, 0, ".", 5052),
+                // Duplicates are ignored.
+                srv(40, 0, "bn1.example.com.", 5052),
+            ],
+            vec![
+                "https://bn4.example.com/api".to_string(),
+                "v=spf1 -all".to_string(),
+                "http://bn2.example.com:5052".to_string(),
+            ],
+        );
+
+        assert_eq!(
+            urls.iter()
+                .map(|url| url.full.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "http://bn1.example.com:5052/",
+                "http://bn2.example.com:5052/",
+                "http://bn3.example.com:5052/",
+                "https://bn4.example.com/api",
+            ]
+        );
+    }
+}
@@ -3,8 +3,11 @@
 //! succeed.
 
 use crate::check_synced::check_synced;
-use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+use crate::http_metrics::metrics::{
+    inc_counter_vec, set_int_gauge, ENDPOINT_ERRORS, ENDPOINT_MISSED_INCLUSIONS, ENDPOINT_REQUESTS,
+};
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::BeaconNodeSlot;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
 use slog::{debug, error, info, warn, Logger};
@@ -13,6 +16,7 @@ use std::fmt;
 use std::fmt::Debug;
 use std::future::Future;
 use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::{sync::RwLock, time::sleep};
@@ -145,7 +149,12 @@ pub enum CandidateError {
 /// for a query.
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
+    /// True if the node was found via DNS discovery rather than being configured.
+    discovered: bool,
     status: RwLock<Result<(), CandidateError>>,
+    /// The number of consecutive attestations published via this node which were not included
+    /// on chain.
+    missed_inclusions: AtomicUsize,
     _phantom: PhantomData<E>,
 }
 
@@ -154,11 +163,21 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
     pub fn new(beacon_node: BeaconNodeHttpClient) -> Self {
         Self {
             beacon_node,
+            discovered: false,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            missed_inclusions: AtomicUsize::new(0),
             _phantom: PhantomData,
         }
     }
 
+    /// Instantiate a new node which was found via DNS discovery.
+    pub fn new_discovered(beacon_node: BeaconNodeHttpClient) -> Self {
+        Self {
+            discovered: true,
+            ..Self::new(beacon_node)
+        }
+    }
+
     /// Returns the status of `self`.
     ///
     /// If `RequiredSynced::No`, any `NotSynced` node will be ignored and mapped to `Ok(())`.
@@ -174,6 +193,29 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         *self.status.write().await = Err(CandidateError::Offline)
     }
 
+    /// Record whether an attestation published via `self` was included on chain.
+    pub fn record_attestation_inclusion(&self, included: bool) {
+        let missed = if included {
+            self.missed_inclusions.store(0, Ordering::Relaxed);
+            0
+        } else {
+            self.missed_inclusions.fetch_add(1, Ordering::Relaxed) + 1
+        };
+        set_int_gauge(
+            &ENDPOINT_MISSED_INCLUSIONS,
+            &[self.beacon_node.as_ref()],
+            missed as i64,
+        );
+    }
+
+    /// Returns `true` if at least `threshold` consecutive attestations published via `self` have
+    /// not been included on chain.
+    fn is_missing_inclusions(&self, threshold: Option<usize>) -> bool {
+        threshold.map_or(false, |threshold| {
+            self.missed_inclusions.load(Ordering::Relaxed) >= threshold
+        })
+    }
+
     /// Perform some queries against the node to determine if it is a good candidate, updating
     /// `self.status` and returning that result.
     pub async fn refresh_status<T: SlotClock>(
@@ -320,9 +362,13 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 /// behaviour, where the failure of one candidate results in the next candidate receiving an
 /// identical query.
 pub struct BeaconNodeFallback<T, E> {
-    candidates: Vec<CandidateBeaconNode<E>>,
+    /// The configured candidates, in order of priority, followed by any discovered candidates.
+    candidates: parking_lot::RwLock<Vec<Arc<CandidateBeaconNode<E>>>>,
     slot_clock: Option<T>,
     disable_run_on_all: bool,
+    /// Candidates with at least this many consecutive attestation inclusion failures are only
+    /// used once all other synced candidates have failed.
+    inclusion_miss_threshold: Option<usize>,
     spec: ChainSpec,
     log: Logger,
 }
@@ -335,9 +381,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         log: Logger,
     ) -> Self {
         Self {
-            candidates,
+            candidates: parking_lot::RwLock::new(candidates.into_iter().map(Arc::new).collect()),
             slot_clock: None,
             disable_run_on_all,
+            inclusion_miss_threshold: None,
             spec,
             log,
         }
@@ -352,15 +399,100 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         self.slot_clock = Some(slot_clock);
     }
 
+    /// Enables de-prioritisation of candidates whose attestations are persistently not included
+    /// on chain.
+    pub fn set_inclusion_miss_threshold(&mut self, threshold: usize) {
+        self.inclusion_miss_threshold = Some(threshold);
+    }
+
+    /// Returns a snapshot of the current candidates, in order of priority.
+    fn candidates(&self) -> Vec<Arc<CandidateBeaconNode<E>>> {
+        self.candidates.read().clone()
+    }
+
+    /// Replaces the discovered candidates with `discovered`, returning the number of candidates
+    /// added and removed.
+    ///
+    /// Discovered candidates that are still present keep their status, and new candidates have
+    /// their status refreshed before they are used. Configured candidates are never removed and
+    /// always take priority over discovered candidates.
+    pub async fn update_discovered_candidates(
+        &self,
+        discovered: Vec<BeaconNodeHttpClient>,
+    ) -> (usize, usize) {
+        let (added, num_removed) = {
+            let mut candidates = self.candidates.write();
+            let (configured, previous): (Vec<_>, Vec<_>) = candidates
+                .drain(..)
+                .partition(|candidate| !candidate.discovered);
+
+            let mut added = vec![];
+            let mut retained: Vec<Arc<CandidateBeaconNode<E>>> = vec![];
+            for beacon_node in discovered {
+                let beacon_node_id = beacon_node.to_string();
+                let is_known = |candidate: &Arc<CandidateBeaconNode<E>>| {
+                    candidate.beacon_node.to_string() == beacon_node_id
+                };
+                if configured.iter().chain(&retained).any(is_known) {
+                    continue;
+                }
+                match previous.iter().find(|candidate| is_known(candidate)) {
+                    Some(candidate) => retained.push(candidate.clone()),
+                    None => {
+                        let candidate = Arc::new(CandidateBeaconNode::new_discovered(beacon_node));
+                        added.push(candidate.clone());
+                        retained.push(candidate);
+                    }
+                }
+            }
+
+            let num_removed = previous.len() + added.len() - retained.len();
+            *candidates = configured.into_iter().chain(retained).collect();
+            (added, num_removed)
+        };
+
+        let futures = added
+            .iter()
+            .map(|candidate| {
+                candidate.refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+            })
+            .collect::<Vec<_>>();
+        let _ = future::join_all(futures).await;
+
+        (added.len(), num_removed)
+    }
+
+    /// Record whether an attestation published via the candidate identified by `beacon_node_id`
+    /// was included on chain.
+    pub fn record_attestation_inclusion(&self, beacon_node_id: &str, included: bool) {
+        if let Some(candidate) = self
+            .candidates()
+            .iter()
+            .find(|candidate| candidate.beacon_node.to_string() == beacon_node_id)
+        {
+            candidate.record_attestation_inclusion(included);
+            if !included && candidate.is_missing_inclusions(self.inclusion_miss_threshold) {
+                warn!(
+                    self.log,
+                    "Beacon node attestations not being included";
+                    "info" => "this node will be used after other synced nodes",
+                    "node" => beacon_node_id,
+                    "consecutive_misses" => candidate.missed_inclusions.load(Ordering::Relaxed),
+                );
+            }
+        }
+    }
+
     /// The count of candidates, regardless of their state.
     pub fn num_total(&self) -> usize {
-        self.candidates.len()
+        self.candidates.read().len()
     }
 
     /// The count of synced and ready candidates.
     pub async fn num_synced(&self) -> usize {
         let mut n = 0;
-        for candidate in &self.candidates {
+        let candidates = self.candidates();
+        for candidate in &candidates {
             if candidate.status(RequireSynced::Yes).await.is_ok() {
                 n += 1
             }
@@ -371,7 +503,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// The count of synced and ready fallbacks excluding the primary beacon node candidate.
     pub async fn num_synced_fallback(&self) -> usize {
         let mut n = 0;
-        for candidate in self.candidates.iter().skip(1) {
+        for candidate in self.candidates().iter().skip(1) {
             if candidate.status(RequireSynced::Yes).await.is_ok() {
                 n += 1
             }
@@ -382,7 +514,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// The count of candidates that are online and compatible, but not necessarily synced.
     pub async fn num_available(&self) -> usize {
         let mut n = 0;
-        for candidate in &self.candidates {
+        let candidates = self.candidates();
+        for candidate in &candidates {
             if candidate.status(RequireSynced::No).await.is_ok() {
                 n += 1
             }
@@ -396,8 +529,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// low quality responses. To route around this it's best to poll all connected beacon nodes.
     /// A previous implementation of this function polled only the unavailable BNs.
     pub async fn update_all_candidates(&self) {
-        let futures = self
-            .candidates
+        let candidates = self.candidates();
+        let futures = candidates
             .iter()
             .map(|candidate| {
                 candidate.refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
@@ -408,12 +541,34 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         let _ = future::join_all(futures).await;
     }
 
+    /// Concurrently ask all candidates (regardless of their status) for their current slot,
+    /// returning the slots of the candidates which responded.
+    pub async fn beacon_node_slots(&self) -> Vec<BeaconNodeSlot> {
+        let candidates = self.candidates();
+        let futures = candidates
+            .iter()
+            .map(|candidate| async {
+                let syncing = candidate.beacon_node.get_node_syncing().await.ok()?;
+                Some(BeaconNodeSlot {
+                    beacon_node: candidate.beacon_node.to_string(),
+                    slot: syncing.data.head_slot + syncing.data.sync_distance,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        future::join_all(futures)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
     /// Concurrently send a request to all candidates (regardless of
     /// offline/online) status and attempt to collect a rough reading on the
     /// latency between the VC and candidate.
     pub async fn measure_latency(&self) -> Vec<LatencyMeasurement> {
-        let futures: Vec<_> = self
-            .candidates
+        let candidates = self.candidates();
+        let futures: Vec<_> = candidates
             .iter()
             .map(|candidate| async {
                 let beacon_node_id = candidate.beacon_node.to_string();
@@ -455,20 +610,21 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// First this function will try all nodes with a suitable status. If no candidates are suitable
     /// or all the requests fail, it will try updating the status of all unsuitable nodes and
     /// re-running `func` again.
-    pub async fn first_success<'a, F, O, Err, R>(
-        &'a self,
+    pub async fn first_success<F, O, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<O, Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        F: Fn(BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
         let mut errors = vec![];
         let mut to_retry = vec![];
         let mut retry_unsynced = vec![];
+        let mut retry_missing_inclusions = vec![];
         let log = &self.log.clone();
 
         // Run `func` using a `candidate`, returning the value or capturing errors.
@@ -481,7 +637,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
                 // There exists a race condition where `func` may be called when the candidate is
                 // actually not ready. We deem this an acceptable inefficiency.
-                match func(&$candidate.beacon_node).await {
+                match func($candidate.beacon_node.clone()).await {
                     Ok(val) => return Ok(val),
                     Err(e) => {
                         debug!(
@@ -508,7 +664,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // First pass: try `func` on all synced and ready candidates.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        let candidates = self.candidates();
+        for candidate in &candidates {
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -520,10 +677,19 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                     to_retry.push(candidate);
                     errors.push((candidate.beacon_node.to_string(), Error::Unavailable(e)));
                 }
+                _ if candidate.is_missing_inclusions(self.inclusion_miss_threshold) => {
+                    // This client's attestations are not making it on chain, prefer any other
+                    // synced client.
+                    retry_missing_inclusions.push(candidate);
+                }
                 _ => try_func!(candidate),
             }
         }
 
+        for candidate in retry_missing_inclusions {
+            try_func!(candidate);
+        }
+
         // Second pass: try `func` on ready unsynced candidates. This only runs if we permit
         // unsynced candidates.
         //
@@ -571,14 +737,43 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
     /// It returns a list of errors along with the beacon node id that failed for `func`.
     /// Since this ignores the actual result of `func`, this function should only be used for beacon
     /// node calls whose results we do not care about, only that they completed successfully.
-    pub async fn run_on_all<'a, F, O, Err, R>(
-        &'a self,
+    pub async fn run_on_all<F, O, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<(), Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        F: Fn(BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+    {
+        let errors: Vec<_> = self
+            .results_from_all(require_synced, offline_on_failure, func)
+            .await
+            .into_iter()
+            .filter_map(|res| res.err())
+            .collect();
+
+        if !errors.is_empty() {
+            Err(Errors(errors))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Run `func` against all candidates in `self`, returning one result for each candidate.
+    ///
+    /// Candidates are tried in the same order as `Self::run_on_all`. A candidate which could not
+    /// be made ready is represented by an `Error::Unavailable`, so with `RequireSynced::Yes` the
+    /// number of results is always equal to the number of candidates.
+    pub async fn results_from_all<F, O, Err, R>(
+        &self,
+        require_synced: RequireSynced,
+        offline_on_failure: OfflineOnFailure,
+        func: F,
+    ) -> Vec<Result<O, (String, Error<Err>)>>
+    where
+        F: Fn(BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<O, Err>>,
     {
         let mut results = vec![];
@@ -595,7 +790,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
 
                 // There exists a race condition where `func` may be called when the candidate is
                 // actually not ready. We deem this an acceptable inefficiency.
-                match func(&$candidate.beacon_node).await {
+                match func($candidate.beacon_node.clone()).await {
                     Ok(val) => results.push(Ok(val)),
                     Err(e) => {
                         // If we have an error on this function, make the client as not-ready.
@@ -619,7 +814,8 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // First pass: try `func` on all synced and ready candidates.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        let candidates = self.candidates();
+        for candidate in &candidates {
             match candidate.status(RequireSynced::Yes).await {
                 Err(CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -669,25 +865,19 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }
         }
 
-        let errors: Vec<_> = results.into_iter().filter_map(|res| res.err()).collect();
-
-        if !errors.is_empty() {
-            Err(Errors(errors))
-        } else {
-            Ok(())
-        }
+        results
     }
 
     /// Call `func` on first beacon node that returns success or on all beacon nodes
     /// depending on the value of `disable_run_on_all`.
-    pub async fn run<'a, F, Err, R>(
-        &'a self,
+    pub async fn run<F, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<(), Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        F: Fn(BeaconNodeHttpClient) -> R,
         R: Future<Output = Result<(), Err>>,
         Err: Debug,
     {
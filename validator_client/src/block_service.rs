@@ -2,7 +2,9 @@ use crate::beacon_node_fallback::{Error as FallbackError, Errors};
 use crate::{
     beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
     determine_graffiti,
+    duty_recorder::DutyRecorder,
     graffiti_file::GraffitiFile,
+    signing_ledger::SigningLedger,
     OfflineOnFailure,
 };
 use crate::{
@@ -10,10 +12,13 @@ use crate::{
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
 };
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::SignedMessageKind;
 use eth2::{BeaconNodeHttpClient, StatusCode};
+use otlp::{OtlpExporter, Span};
 use slog::Logger;
 use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::future::Future;
 use std::ops::Deref;
@@ -22,8 +27,8 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 use types::{
-    AbstractExecPayload, BlindedPayload, BlockType, EthSpec, FullPayload, Graffiti, PublicKeyBytes,
-    Slot,
+    AbstractExecPayload, Address, BlindedPayload, BlockType, EthSpec, FullPayload, Graffiti,
+    PublicKeyBytes, Slot,
 };
 
 #[derive(Debug)]
@@ -57,6 +62,9 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
     block_delay: Option<Duration>,
+    duty_recorder: Option<Arc<DutyRecorder>>,
+    signing_ledger: Option<Arc<SigningLedger>>,
+    otlp_exporter: Option<OtlpExporter>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
@@ -70,6 +78,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             graffiti: None,
             graffiti_file: None,
             block_delay: None,
+            duty_recorder: None,
+            signing_ledger: None,
+            otlp_exporter: None,
         }
     }
 
@@ -113,6 +124,21 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    pub fn duty_recorder(mut self, duty_recorder: Option<Arc<DutyRecorder>>) -> Self {
+        self.duty_recorder = duty_recorder;
+        self
+    }
+
+    pub fn signing_ledger(mut self, signing_ledger: Option<Arc<SigningLedger>>) -> Self {
+        self.signing_ledger = signing_ledger;
+        self
+    }
+
+    pub fn otlp_exporter(mut self, otlp_exporter: Option<OtlpExporter>) -> Self {
+        self.otlp_exporter = otlp_exporter;
+        self
+    }
+
     pub fn build(self) -> Result<BlockService<T, E>, String> {
         Ok(BlockService {
             inner: Arc::new(Inner {
@@ -132,6 +158,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                 graffiti: self.graffiti,
                 graffiti_file: self.graffiti_file,
                 block_delay: self.block_delay,
+                duty_recorder: self.duty_recorder,
+                signing_ledger: self.signing_ledger,
+                otlp_exporter: self.otlp_exporter,
             }),
         })
     }
@@ -146,14 +175,14 @@ pub struct ProposerFallback<T, E: EthSpec> {
 
 impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
     // Try `func` on `self.proposer_nodes` first. If that doesn't work, try `self.beacon_nodes`.
-    pub async fn first_success_try_proposers_first<'a, F, O, Err, R>(
-        &'a self,
+    pub async fn first_success_try_proposers_first<F, O, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<O, Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R + Clone,
+        F: Fn(BeaconNodeHttpClient) -> R + Clone,
         R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
@@ -174,14 +203,14 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
     }
 
     // Try `func` on `self.beacon_nodes` first. If that doesn't work, try `self.proposer_nodes`.
-    pub async fn first_success_try_proposers_last<'a, F, O, Err, R>(
-        &'a self,
+    pub async fn first_success_try_proposers_last<F, O, Err, R>(
+        &self,
         require_synced: RequireSynced,
         offline_on_failure: OfflineOnFailure,
         func: F,
     ) -> Result<O, Errors<Err>>
     where
-        F: Fn(&'a BeaconNodeHttpClient) -> R + Clone,
+        F: Fn(BeaconNodeHttpClient) -> R + Clone,
         R: Future<Output = Result<O, Err>>,
         Err: Debug,
     {
@@ -216,6 +245,9 @@ pub struct Inner<T, E: EthSpec> {
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
     block_delay: Option<Duration>,
+    duty_recorder: Option<Arc<DutyRecorder>>,
+    signing_ledger: Option<Arc<SigningLedger>>,
+    otlp_exporter: Option<OtlpExporter>,
 }
 
 /// Attempts to produce attestations for any block producer(s) at the start of the epoch.
@@ -239,10 +271,29 @@ impl<T, E: EthSpec> Deref for BlockService<T, E> {
     }
 }
 
+/// One-off overrides for a single block proposal, which take precedence over the validator's
+/// configuration for that proposal only.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProposalOverrides {
+    pub graffiti: Option<Graffiti>,
+    /// The fee recipient is sent to the beacon node ahead of the proposal by the
+    /// `PreparationService`.
+    pub fee_recipient: Option<Address>,
+    pub builder_proposals: Option<bool>,
+}
+
+impl ProposalOverrides {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Notification from the duties service that we should try to produce a block.
 pub struct BlockServiceNotification {
     pub slot: Slot,
     pub block_proposers: Vec<PublicKeyBytes>,
+    /// Overrides for the proposals of any of the `block_proposers`.
+    pub overrides: HashMap<PublicKeyBytes, ProposalOverrides>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
@@ -317,6 +368,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         );
 
         let proposers = notification.block_proposers;
+        let mut overrides = notification.overrides;
 
         if proposers.is_empty() {
             trace!(
@@ -335,17 +387,48 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         }
 
         for validator_pubkey in proposers {
-            let builder_proposals = self
-                .validator_store
-                .get_builder_proposals(&validator_pubkey);
+            let overrides = overrides.remove(&validator_pubkey).unwrap_or_default();
+            if !overrides.is_empty() {
+                info!(
+                    log,
+                    "Applying proposal overrides";
+                    "overrides" => ?overrides,
+                    "slot" => slot.as_u64(),
+                );
+            }
+
+            // Builders pay the fee recipient from the validator's registration, so a fee recipient
+            // override requires a locally built payload unless a builder is explicitly requested.
+            let builder_proposals = overrides.builder_proposals.unwrap_or_else(|| {
+                overrides.fee_recipient.is_none()
+                    && self
+                        .validator_store
+                        .get_builder_proposals(&validator_pubkey)
+            });
+            let graffiti = overrides.graffiti;
             let service = self.clone();
             let log = log.clone();
+
+            // The root of the trace of this proposal, which begins when the proposal is triggered
+            // so that any delay before production is included. Its children are sent to the beacon
+            // node so that it may add its own spans.
+            let mut proposal_span = self.otlp_exporter.as_ref().map(|exporter| {
+                let mut span = exporter.start_span("block_proposal", None);
+                span.set_attribute("slot", slot);
+                span
+            });
+
             self.inner.context.executor.spawn(
                 async move {
                     if builder_proposals {
                         let result = service
                             .clone()
-                            .publish_block::<BlindedPayload<E>>(slot, validator_pubkey)
+                            .publish_block::<BlindedPayload<E>>(
+                                slot,
+                                validator_pubkey,
+                                graffiti,
+                                proposal_span.as_ref(),
+                            )
                             .await;
                         match result {
                             Err(BlockError::Recoverable(e)) => {
@@ -357,9 +440,17 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                                     "info" => "blinded proposal failed, attempting full block"
                                 );
                                 if let Err(e) = service
-                                    .publish_block::<FullPayload<E>>(slot, validator_pubkey)
+                                    .publish_block::<FullPayload<E>>(
+                                        slot,
+                                        validator_pubkey,
+                                        graffiti,
+                                        proposal_span.as_ref(),
+                                    )
                                     .await
                                 {
+                                    if let Some(span) = &mut proposal_span {
+                                        span.set_error(format!("{:?}", e));
+                                    }
                                     // Log a `crit` since a full block
                                     // (non-builder) proposal failed.
                                     crit!(
@@ -372,6 +463,9 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                                 }
                             }
                             Err(BlockError::Irrecoverable(e)) => {
+                                if let Some(span) = &mut proposal_span {
+                                    span.set_error(format!("{:?}", e));
+                                }
                                 // Only log an `error` since it's common for
                                 // builders to timeout on their response, only
                                 // to publish the block successfully themselves.
@@ -386,9 +480,17 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                             Ok(_) => {}
                         };
                     } else if let Err(e) = service
-                        .publish_block::<FullPayload<E>>(slot, validator_pubkey)
+                        .publish_block::<FullPayload<E>>(
+                            slot,
+                            validator_pubkey,
+                            graffiti,
+                            proposal_span.as_ref(),
+                        )
                         .await
                     {
+                        if let Some(span) = &mut proposal_span {
+                            span.set_error(format!("{:?}", e));
+                        }
                         // Log a `crit` since a full block (non-builder)
                         // proposal failed.
                         crit!(
@@ -407,11 +509,16 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok(())
     }
 
-    /// Produce a block at the given slot for validator_pubkey
+    /// Produce a block at the given slot for validator_pubkey, using `graffiti_override` in place of
+    /// the configured graffiti if it is provided.
+    ///
+    /// If `proposal_span` is provided, each step of the proposal is recorded as its child.
     async fn publish_block<Payload: AbstractExecPayload<E>>(
         self,
         slot: Slot,
         validator_pubkey: PublicKeyBytes,
+        graffiti_override: Option<Graffiti>,
+        proposal_span: Option<&Span>,
     ) -> Result<(), BlockError> {
         let log = self.context.log();
         let _timer =
@@ -421,6 +528,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             BlockError::Recoverable("Unable to determine current slot from clock".to_string())
         })?;
 
+        let randao_span = proposal_span.map(|span| span.child("randao_sign"));
         let randao_reveal = match self
             .validator_store
             .randao_reveal(validator_pubkey, slot.epoch(E::slots_per_epoch()))
@@ -446,14 +554,17 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 )))
             }
         };
+        drop(randao_span);
 
-        let graffiti = determine_graffiti(
-            &validator_pubkey,
-            log,
-            self.graffiti_file.clone(),
-            self.validator_store.graffiti(&validator_pubkey),
-            self.graffiti,
-        );
+        let graffiti = graffiti_override.or_else(|| {
+            determine_graffiti(
+                &validator_pubkey,
+                log,
+                self.graffiti_file.clone(),
+                self.validator_store.graffiti(&validator_pubkey),
+                self.graffiti,
+            )
+        });
 
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
@@ -479,6 +590,14 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
+                    let mut get_span = proposal_span.map(|span| span.child("get_block"));
+                    if let Some(span) = &mut get_span {
+                        span.set_attribute("beacon_node", beacon_node);
+                        span.set_attribute("block_type", format!("{:?}", Payload::block_type()));
+                    }
+                    let beacon_node =
+                        &beacon_node.with_trace_context(get_span.as_ref().map(Span::context));
+
                     let block = match Payload::block_type() {
                         BlockType::Full => {
                             let _get_timer = metrics::start_timer_vec(
@@ -539,6 +658,11 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             )
             .await?;
 
+        if let Some(duty_recorder) = &self.duty_recorder {
+            duty_recorder.record_block(validator_pubkey, current_slot, &block);
+        }
+
+        let signing_span = proposal_span.map(|span| span.child("sign_block"));
         let signing_timer = metrics::start_timer(&metrics::BLOCK_SIGNING_TIMES);
         let signed_block = match self_ref
             .validator_store
@@ -565,8 +689,10 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 )))
             }
         };
-        let signing_time_ms =
-            Duration::from_secs_f64(signing_timer.map_or(0.0, |t| t.stop_and_record())).as_millis();
+        let signing_time =
+            Duration::from_secs_f64(signing_timer.map_or(0.0, |t| t.stop_and_record()));
+        drop(signing_span);
+        let signing_time_ms = signing_time.as_millis();
 
         info!(
             log,
@@ -580,19 +706,27 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         // Try the proposer nodes first, since we've likely gone to efforts to
         // protect them from DoS attacks and they're most likely to successfully
         // publish a block.
-        proposer_fallback
+        let signed_block_ref = &signed_block;
+        let publication = proposer_fallback
             .first_success_try_proposers_first(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
-                |beacon_node| async {
+                |beacon_node| async move {
+                    let mut publish_span = proposal_span.map(|span| span.child("publish_block"));
+                    if let Some(span) = &mut publish_span {
+                        span.set_attribute("beacon_node", beacon_node);
+                    }
+                    let traced_beacon_node =
+                        beacon_node.with_trace_context(publish_span.as_ref().map(Span::context));
+
                     match Payload::block_type() {
                         BlockType::Full => {
                             let _post_timer = metrics::start_timer_vec(
                                 &metrics::BLOCK_SERVICE_TIMES,
                                 &[metrics::BEACON_BLOCK_HTTP_POST],
                             );
-                            beacon_node
-                                .post_beacon_blocks(&signed_block)
+                            traced_beacon_node
+                                .post_beacon_blocks(signed_block_ref)
                                 .await
                                 .or_else(|e| handle_block_post_error(e, slot, log))?
                         }
@@ -601,16 +735,27 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                                 &metrics::BLOCK_SERVICE_TIMES,
                                 &[metrics::BLINDED_BEACON_BLOCK_HTTP_POST],
                             );
-                            beacon_node
-                                .post_beacon_blinded_blocks(&signed_block)
+                            traced_beacon_node
+                                .post_beacon_blinded_blocks(signed_block_ref)
                                 .await
                                 .or_else(|e| handle_block_post_error(e, slot, log))?
                         }
                     }
-                    Ok::<_, BlockError>(())
+                    Ok::<_, BlockError>(beacon_node.to_string())
                 },
             )
-            .await?;
+            .await;
+
+        if let Some(signing_ledger) = &self.signing_ledger {
+            signing_ledger.record_or_warn(
+                SignedMessageKind::Block,
+                slot,
+                &[(validator_pubkey, signing_time)],
+                publication.as_deref().map_err(ToString::to_string),
+                log,
+            );
+        }
+        publication?;
 
         info!(
             log,
@@ -35,6 +35,25 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 )
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("beacon-nodes-dns")
+                .long("beacon-nodes-dns")
+                .value_name("DNS_NAME")
+                .help("A DNS name whose SRV and TXT records list additional beacon node HTTP \
+                       APIs. SRV records are used as `http://<target>:<port>` and TXT records \
+                       may contain a full URL. The name is re-resolved periodically and the \
+                       discovered nodes are used after those in --beacon-nodes.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("beacon-nodes-dns-interval")
+                .long("beacon-nodes-dns-interval")
+                .value_name("SECONDS")
+                .help("The number of seconds between DNS lookups of --beacon-nodes-dns. \
+                       Defaults to 60.")
+                .requires("beacon-nodes-dns")
+                .takes_value(true),
+        )
         .arg(
             Arg::with_name("disable-run-on-all")
                 .long("disable-run-on-all")
@@ -153,6 +172,17 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .value_name("FEE-RECIPIENT")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("suggested-fee-recipient-file")
+                .long("suggested-fee-recipient-file")
+                .help("Specify a file to load per-validator fee recipients from, with lines of the \
+                       form `<pubkey>: <address>` and an optional `default: <address>`. The file \
+                       is reloaded every epoch. Fee recipients in the validator definitions take \
+                       priority over the file, which takes priority over \
+                       --suggested-fee-recipient.")
+                .value_name("FEE-RECIPIENT-FILE")
+                .takes_value(true)
+        )
         /* REST API related arguments */
         .arg(
             Arg::with_name("http")
@@ -227,6 +257,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     definitions file.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("http-skip-remotekey-health-check")
+                .long("http-skip-remotekey-health-check")
+                .requires("http")
+                .help("If present, remote keys imported via the standard keymanager API are \
+                    enabled without first checking that their remote signer returns a valid \
+                    signature.")
+                .takes_value(false),
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::with_name("metrics")
@@ -369,6 +408,191 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .default_value("500")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("attestation-inclusion-check-delay")
+                .long("attestation-inclusion-check-delay")
+                .value_name("SLOTS")
+                .help("If present, check whether each published attestation was included on chain \
+                    this many slots after attesting. Beacon nodes whose attestations are persistently \
+                    not included will be used only after all other synced beacon nodes.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("attestation-inclusion-miss-threshold")
+                .long("attestation-inclusion-miss-threshold")
+                .value_name("INTEGER")
+                .help("The number of consecutive attestations a beacon node may fail to get \
+                    included before it is de-prioritised. Only used with \
+                    --attestation-inclusion-check-delay.")
+                .default_value("3")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slashing-protection-check-interval")
+                .long("slashing-protection-check-interval")
+                .value_name("SECONDS")
+                .help("The number of seconds between integrity checks of the slashing \
+                    protection database.")
+                .default_value("3600")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("remote-signer-check-interval")
+                .long("remote-signer-check-interval")
+                .value_name("SECONDS")
+                .help("The number of seconds between health checks of the remote signers of \
+                    Web3Signer validators. Each check requests a signature on a non-slashable \
+                    message. Validators with unreachable signers are reported via metrics and \
+                    the HTTP API.")
+                .default_value("60")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slashing-protection-backup-dir")
+                .long("slashing-protection-backup-dir")
+                .value_name("DIR")
+                .help("If present, write a backup of the slashing protection database to this \
+                    directory after each successful integrity check.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slashing-protection-backups-to-keep")
+                .long("slashing-protection-backups-to-keep")
+                .value_name("INTEGER")
+                .help("The number of slashing protection database backups to keep. Older \
+                    backups are deleted. Only used with --slashing-protection-backup-dir.")
+                .default_value("24")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("subscribe-head-events")
+                .long("subscribe-head-events")
+                .help("Subscribe to head events from the beacon node and poll attester duties as \
+                    soon as a re-org changes the duty dependent roots, rather than waiting for the \
+                    next slot. The event stream is re-established automatically if it drops.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("record-duty-inputs")
+                .long("record-duty-inputs")
+                .value_name("COUNT")
+                .help("If present, record the inputs received from the beacon node for this many \
+                    recent attestation and block duties. Recorded duties can be listed and \
+                    replayed via the HTTP API to investigate failed duties.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("signing-ledger")
+                .long("signing-ledger")
+                .help("If present, record every signed block, attestation and sync committee \
+                    message in a ledger in the validator directory, along with the signing \
+                    latency, the beacon node used and the result of publishing. The history of \
+                    each validator can be queried via the HTTP API.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("signing-ledger-retention-days")
+                .long("signing-ledger-retention-days")
+                .value_name("DAYS")
+                .help("The number of days for which records are kept in the signing ledger. Older \
+                    records are pruned on startup and then hourly. [default: 30]")
+                .requires("signing-ledger")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("otlp-endpoint")
+                .long("otlp-endpoint")
+                .value_name("URL")
+                .help("An OpenTelemetry collector accepting OTLP/HTTP (e.g. http://localhost:4318). \
+                    If present, each block proposal is traced from the duty trigger to \
+                    publication and the spans exported to the collector. The trace context is \
+                    sent to the beacon node, so that a Lighthouse beacon node with the same flag \
+                    adds its production, import and gossip spans to the trace.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-clock-skew-slots")
+                .long("max-clock-skew-slots")
+                .value_name("SLOTS")
+                .help("If present, compare the local clock with the current slot of each beacon \
+                    node every slot. If every beacon node is more than this many slots away \
+                    from the local clock, refuse to start or, once running, refuse to sign \
+                    until the clocks agree again.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("signing-concurrency-limit")
+                .long("signing-concurrency-limit")
+                .value_name("INTEGER")
+                .help("If present, limit the number of signatures which are requested from local \
+                    keystores and remote signers at once. Signatures beyond the limit wait, with \
+                    blocks signed first, then aggregates, then attestations and sync committee \
+                    messages, and finally validator registrations and voluntary exits. One \
+                    signature is reserved for blocks, so the limit must be at least 2. Useful \
+                    for preventing large batches of registrations from delaying a block \
+                    signature.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("validator-monitor-token-file")
+                .long("validator-monitor-token-file")
+                .value_name("PATH")
+                .help("If present, register the enabled validators with the validator monitor of \
+                    every beacon node once per epoch, using the token in this file. The beacon \
+                    nodes must be started with --http-validator-monitor-token-file containing \
+                    the same token.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("validator-monitor-cohort")
+                .long("validator-monitor-cohort")
+                .value_name("COHORT")
+                .help("The cohort under which the validators are registered with the validator \
+                    monitor of each beacon node. Cohorts may be up to 32 ASCII letters, digits, \
+                    '-' or '_' characters.")
+                .requires("validator-monitor-token-file")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("stale-duties-epochs")
+                .long("stale-duties-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs after which attester duties which could not be \
+                    refreshed are considered stale. Stale duties are refreshed, and attestation \
+                    subnet subscriptions re-sent, as soon as any beacon node becomes available \
+                    rather than at the next scheduled poll.")
+                .default_value("2")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dry-run")
+                .long("dry-run")
+                .help("Instead of connecting to a beacon node, run the duties of the local \
+                    validators against a mock beacon node serving the state given by \
+                    --dry-run-state, from the slot of that state until the end of its epoch, and \
+                    write the messages they publish to --dry-run-output. The keys are never \
+                    loaded and messages carry the empty signature, while the slashing protection \
+                    database is left untouched. Useful for checking keys, fee recipients and graffiti without \
+                    touching a network.")
+                .requires_all(&["dry-run-state", "dry-run-output"])
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("dry-run-state")
+                .long("dry-run-state")
+                .value_name("PATH")
+                .help("Path to an SSZ-encoded beacon state used by --dry-run.")
+                .requires("dry-run")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dry-run-output")
+                .long("dry-run-output")
+                .value_name("PATH")
+                .help("Path to which --dry-run writes the would-be messages as JSON.")
+                .requires("dry-run")
+                .takes_value(true),
+        )
         /*
          * Experimental/development options.
          */
@@ -0,0 +1,147 @@
+//! Comparison of the local clock with the beacon nodes.
+//!
+//! A validator client whose clock disagrees with the network signs messages for the wrong slots.
+//! The current slot of each beacon node is compared with the local slot every slot, and signing
+//! is refused whilst none of the beacon nodes which respond agree with the local clock.
+
+use crate::{BeaconNodeFallback, ValidatorStore};
+use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::{BeaconNodeSlot, ClockSkewStatus};
+use slog::{crit, debug, info, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+use types::{EthSpec, Slot};
+
+/// The comparison runs half way through the slot, so that a skew of less than half a slot never
+/// results in a different slot.
+pub const SLOT_DELAY_MULTIPLIER: u32 = 1;
+pub const SLOT_DELAY_DENOMINATOR: u32 = 2;
+
+/// Starts a service that compares the local clock with the beacon nodes every slot.
+pub fn start_clock_skew_monitor<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    slot_clock: T,
+    max_skew_slots: u64,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let sleep_time = slot_clock
+                .duration_to_next_slot()
+                .map(|next_slot| {
+                    next_slot
+                        + (slot_clock.slot_duration() / SLOT_DELAY_DENOMINATOR)
+                            * SLOT_DELAY_MULTIPLIER
+                })
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+
+            check_clock_skew(
+                &validator_store,
+                &beacon_nodes,
+                &slot_clock,
+                max_skew_slots,
+                &log,
+            )
+            .await;
+        }
+    };
+
+    context.executor.spawn(future, "clock_skew_monitor");
+}
+
+/// Compares the local clock with the beacon nodes, records the result in `validator_store` and
+/// returns it.
+///
+/// Returns `None`, keeping any previous result, if the local slot is unknown or no beacon node
+/// responded.
+pub async fn check_clock_skew<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    slot_clock: &T,
+    max_skew_slots: u64,
+    log: &Logger,
+) -> Option<ClockSkewStatus> {
+    let beacon_node_slots = beacon_nodes.beacon_node_slots().await;
+    let local_slot = slot_clock.now()?;
+    if beacon_node_slots.is_empty() {
+        debug!(log, "No beacon node available to check the clock");
+        return None;
+    }
+
+    let skew_detected = is_skewed(local_slot, &beacon_node_slots, max_skew_slots);
+    let previously_detected = validator_store
+        .clock_skew_status()
+        .map_or(false, |status| status.skew_detected);
+    if skew_detected {
+        crit!(
+            log,
+            "Local clock disagrees with all beacon nodes";
+            "msg" => "signing is disabled until the system time on this host is corrected",
+            "local_slot" => local_slot,
+            "beacon_node_slots" => ?beacon_node_slots,
+        );
+    } else if previously_detected {
+        info!(
+            log,
+            "Local clock agrees with beacon nodes, signing is enabled";
+            "local_slot" => local_slot,
+        );
+    }
+
+    let status = ClockSkewStatus {
+        local_slot,
+        beacon_node_slots,
+        skew_detected,
+        last_check: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |now| now.as_secs()),
+    };
+    validator_store.set_clock_skew_status(status.clone());
+    Some(status)
+}
+
+/// Returns `true` if every one of the `beacon_node_slots` is more than `max_skew_slots` away from
+/// `local_slot`.
+fn is_skewed(local_slot: Slot, beacon_node_slots: &[BeaconNodeSlot], max_skew_slots: u64) -> bool {
+    beacon_node_slots.iter().all(|beacon_node| {
+        let distance = if beacon_node.slot > local_slot {
+            beacon_node.slot - local_slot
+        } else {
+            local_slot - beacon_node.slot
+        };
+        distance > max_skew_slots
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beacon_node_slots(slots: &[u64]) -> Vec<BeaconNodeSlot> {
+        slots
+            .iter()
+            .map(|slot| BeaconNodeSlot {
+                beacon_node: format!("http://localhost:{}/", slot),
+                slot: Slot::new(*slot),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skew_requires_all_beacon_nodes_to_disagree() {
+        let local_slot = Slot::new(100);
+
+        assert!(!is_skewed(local_slot, &beacon_node_slots(&[100]), 1));
+        assert!(!is_skewed(local_slot, &beacon_node_slots(&[99, 101]), 1));
+        assert!(is_skewed(local_slot, &beacon_node_slots(&[98]), 1));
+        assert!(is_skewed(local_slot, &beacon_node_slots(&[102, 110]), 1));
+        // A single beacon node with a wrong clock does not block signing.
+        assert!(!is_skewed(local_slot, &beacon_node_slots(&[110, 100]), 1));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::fee_recipient_file::FeeRecipientFile;
 use crate::graffiti_file::GraffitiFile;
-use crate::{http_api, http_metrics};
+use crate::signing_ledger::DEFAULT_RETENTION_DAYS;
+use crate::{dry_run, http_api, http_metrics};
 use clap::ArgMatches;
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, parse_optional, parse_required};
 use directory::{
@@ -31,6 +33,10 @@ pub struct Config {
     pub beacon_nodes: Vec<SensitiveUrl>,
     /// An optional beacon node used for block proposals only.
     pub proposer_nodes: Vec<SensitiveUrl>,
+    /// A DNS name whose SRV and TXT records list additional beacon nodes.
+    pub beacon_nodes_dns: Option<String>,
+    /// Seconds between resolutions of `beacon_nodes_dns`.
+    pub beacon_nodes_dns_interval: u64,
     /// If true, the validator client will still poll for duties and produce blocks even if the
     /// beacon node is not synced at startup.
     pub allow_unsynced_beacon_node: bool,
@@ -46,6 +52,8 @@ pub struct Config {
     pub graffiti_file: Option<GraffitiFile>,
     /// Fallback fallback address.
     pub fee_recipient: Option<Address>,
+    /// Fee recipient file to load per validator fee recipients.
+    pub fee_recipient_file: Option<FeeRecipientFile>,
     /// Configuration for the HTTP REST API.
     pub http_api: http_api::Config,
     /// Configuration for the HTTP REST API.
@@ -79,6 +87,63 @@ pub struct Config {
     pub enable_latency_measurement_service: bool,
     /// Defines the number of validators per `validator/register_validator` request sent to the BN.
     pub validator_registration_batch_size: usize,
+    /// Number of slots after attesting to check whether attestations were included on chain.
+    ///
+    /// Inclusion is not checked if this is `None`.
+    pub attestation_inclusion_check_delay: Option<u64>,
+    /// Number of consecutive attestations a BN may fail to get included before it is only used
+    /// once all other synced BNs have failed.
+    pub attestation_inclusion_miss_threshold: usize,
+    /// Seconds between health checks of the slashing protection database.
+    pub slashing_protection_check_interval: u64,
+    /// Directory to which backups of the slashing protection database are written after each
+    /// successful health check.
+    ///
+    /// Backups are not taken if this is `None`.
+    pub slashing_protection_backup_dir: Option<PathBuf>,
+    /// The number of slashing protection backups to keep before deleting the oldest.
+    pub slashing_protection_backups_to_keep: usize,
+    /// Seconds between health checks of the remote signers of Web3Signer validators.
+    pub remote_signer_check_interval: u64,
+    /// Subscribe to head events from the beacon node and poll attester duties as soon as the
+    /// duty dependent roots change.
+    pub subscribe_head_events: bool,
+    /// The number of recent duties whose inputs are recorded so that they can be replayed via the
+    /// HTTP API.
+    ///
+    /// Duty inputs are not recorded if this is `None`.
+    pub record_duty_inputs: Option<usize>,
+    /// Record every signed block, attestation and sync committee message in a local ledger which
+    /// can be queried via the HTTP API.
+    pub signing_ledger: bool,
+    /// The number of days for which records are kept in the signing ledger.
+    pub signing_ledger_retention_days: u64,
+    /// The OTLP/HTTP collector to which the spans of block proposals are exported.
+    ///
+    /// Block proposals are not traced if this is `None`.
+    pub otlp_endpoint: Option<SensitiveUrl>,
+    /// The number of epochs without a successful refresh of attester duties after which they are
+    /// refreshed as soon as a beacon node becomes available.
+    pub stale_duties_epochs: u64,
+    /// The maximum number of slots by which the local clock may disagree with every beacon node
+    /// before signing is refused.
+    ///
+    /// The clock is not compared with the beacon nodes if this is `None`.
+    pub max_clock_skew_slots: Option<u64>,
+    /// The maximum number of signatures which may be requested concurrently. Signatures beyond
+    /// the limit wait, with blocks served before aggregates, attestations and registrations.
+    ///
+    /// The number of concurrent signatures is not limited if this is `None`.
+    pub signing_concurrency_limit: Option<usize>,
+    /// The token used to register the enabled validators with the validator monitor of each
+    /// beacon node.
+    ///
+    /// Validators are not registered if this is `None`.
+    pub validator_monitor_token: Option<String>,
+    /// The cohort under which the validators are registered with the validator monitor.
+    pub validator_monitor_cohort: Option<String>,
+    /// Simulate duties against a fixture state instead of connecting to a beacon node.
+    pub dry_run: Option<dry_run::Config>,
 }
 
 impl Default for Config {
@@ -100,6 +165,8 @@ impl Default for Config {
             secrets_dir,
             beacon_nodes,
             proposer_nodes: Vec::new(),
+            beacon_nodes_dns: None,
+            beacon_nodes_dns_interval: 60,
             allow_unsynced_beacon_node: false,
             disable_auto_discover: false,
             init_slashing_protection: false,
@@ -107,6 +174,7 @@ impl Default for Config {
             graffiti: None,
             graffiti_file: None,
             fee_recipient: None,
+            fee_recipient_file: None,
             http_api: <_>::default(),
             http_metrics: <_>::default(),
             monitoring_api: None,
@@ -120,14 +188,44 @@ impl Default for Config {
             disable_run_on_all: false,
             enable_latency_measurement_service: true,
             validator_registration_batch_size: 500,
+            attestation_inclusion_check_delay: None,
+            attestation_inclusion_miss_threshold: 3,
+            slashing_protection_check_interval: 3600,
+            slashing_protection_backup_dir: None,
+            slashing_protection_backups_to_keep: 24,
+            remote_signer_check_interval: 60,
+            subscribe_head_events: false,
+            record_duty_inputs: None,
+            signing_ledger: false,
+            signing_ledger_retention_days: DEFAULT_RETENTION_DAYS,
+            otlp_endpoint: None,
+            stale_duties_epochs: 2,
+            max_clock_skew_slots: None,
+            signing_concurrency_limit: None,
+            validator_monitor_token: None,
+            validator_monitor_cohort: None,
+            dry_run: None,
         }
     }
 }
 
 impl Config {
     /// Returns a `Default` implementation of `Self` with some parameters modified by the supplied
-    /// `cli_args`.
+    /// `cli_args`, creating the validator directory if it doesn't exist.
     pub fn from_cli(cli_args: &ArgMatches, log: &Logger) -> Result<Config, String> {
+        let config = Self::parse_cli(cli_args, log)?;
+
+        if !config.validator_dir.exists() {
+            fs::create_dir_all(&config.validator_dir)
+                .map_err(|e| format!("Failed to create {:?}: {:?}", config.validator_dir, e))?;
+        }
+
+        Ok(config)
+    }
+
+    /// Returns a `Default` implementation of `Self` with some parameters modified by the supplied
+    /// `cli_args`, without modifying the file system.
+    pub fn parse_cli(cli_args: &ArgMatches, log: &Logger) -> Result<Config, String> {
         let mut config = Config::default();
 
         let default_root_dir = dirs::home_dir()
@@ -159,11 +257,6 @@ impl Config {
                 .join(DEFAULT_SECRET_DIR)
         });
 
-        if !config.validator_dir.exists() {
-            fs::create_dir_all(&config.validator_dir)
-                .map_err(|e| format!("Failed to create {:?}: {:?}", config.validator_dir, e))?;
-        }
-
         if let Some(beacon_nodes) = parse_optional::<String>(cli_args, "beacon-nodes")? {
             config.beacon_nodes = beacon_nodes
                 .split(',')
@@ -200,6 +293,11 @@ impl Config {
                 .map_err(|e| format!("Unable to parse proposer node URL: {:?}", e))?;
         }
 
+        config.beacon_nodes_dns = parse_optional(cli_args, "beacon-nodes-dns")?;
+        if let Some(interval) = parse_optional(cli_args, "beacon-nodes-dns-interval")? {
+            config.beacon_nodes_dns_interval = interval;
+        }
+
         if cli_args.is_present("delete-lockfiles") {
             warn!(
                 log,
@@ -254,6 +352,19 @@ impl Config {
             config.fee_recipient = Some(input_fee_recipient);
         }
 
+        if let Some(fee_recipient_file_path) = cli_args.value_of("suggested-fee-recipient-file") {
+            let mut fee_recipient_file = FeeRecipientFile::new(fee_recipient_file_path.into());
+            fee_recipient_file
+                .read_fee_recipient_file()
+                .map_err(|e| format!("Error reading fee recipient file: {:?}", e))?;
+            config.fee_recipient_file = Some(fee_recipient_file);
+            info!(
+                log,
+                "Successfully loaded fee recipient file";
+                "path" => fee_recipient_file_path
+            );
+        }
+
         if let Some(tls_certs) = parse_optional::<String>(cli_args, "beacon-nodes-tls-certs")? {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
@@ -302,6 +413,10 @@ impl Config {
             config.http_api.store_passwords_in_secrets_dir = true;
         }
 
+        if cli_args.is_present("http-skip-remotekey-health-check") {
+            config.http_api.remotekey_health_check = false;
+        }
+
         /*
          * Prometheus metrics HTTP server
          */
@@ -397,6 +512,99 @@ impl Config {
             return Err("validator-registration-batch-size cannot be 0".to_string());
         }
 
+        config.attestation_inclusion_check_delay =
+            parse_optional(cli_args, "attestation-inclusion-check-delay")?;
+        if config.attestation_inclusion_check_delay == Some(0) {
+            return Err("attestation-inclusion-check-delay cannot be 0".to_string());
+        }
+        config.attestation_inclusion_miss_threshold =
+            parse_required(cli_args, "attestation-inclusion-miss-threshold")?;
+        if config.attestation_inclusion_miss_threshold == 0 {
+            return Err("attestation-inclusion-miss-threshold cannot be 0".to_string());
+        }
+
+        config.slashing_protection_check_interval =
+            parse_required(cli_args, "slashing-protection-check-interval")?;
+        if config.slashing_protection_check_interval == 0 {
+            return Err("slashing-protection-check-interval cannot be 0".to_string());
+        }
+        config.slashing_protection_backup_dir =
+            parse_optional(cli_args, "slashing-protection-backup-dir")?;
+        config.slashing_protection_backups_to_keep =
+            parse_required(cli_args, "slashing-protection-backups-to-keep")?;
+        if config.slashing_protection_backups_to_keep == 0 {
+            return Err("slashing-protection-backups-to-keep cannot be 0".to_string());
+        }
+
+        config.remote_signer_check_interval =
+            parse_required(cli_args, "remote-signer-check-interval")?;
+        if config.remote_signer_check_interval == 0 {
+            return Err("remote-signer-check-interval cannot be 0".to_string());
+        }
+
+        config.subscribe_head_events = cli_args.is_present("subscribe-head-events");
+
+        config.record_duty_inputs = parse_optional(cli_args, "record-duty-inputs")?;
+        if config.record_duty_inputs == Some(0) {
+            return Err("record-duty-inputs cannot be 0".to_string());
+        }
+
+        config.signing_ledger = cli_args.is_present("signing-ledger");
+        if let Some(days) = parse_optional(cli_args, "signing-ledger-retention-days")? {
+            if days == 0 {
+                return Err("signing-ledger-retention-days cannot be 0".to_string());
+            }
+            config.signing_ledger_retention_days = days;
+        }
+
+        if let Some(endpoint) = cli_args.value_of("otlp-endpoint") {
+            config.otlp_endpoint = Some(
+                SensitiveUrl::parse(endpoint)
+                    .map_err(|e| format!("Invalid --otlp-endpoint: {:?}", e))?,
+            );
+        }
+
+        config.stale_duties_epochs = parse_required(cli_args, "stale-duties-epochs")?;
+        if config.stale_duties_epochs == 0 {
+            return Err("stale-duties-epochs cannot be 0".to_string());
+        }
+
+        config.max_clock_skew_slots = parse_optional(cli_args, "max-clock-skew-slots")?;
+        if config.max_clock_skew_slots == Some(0) {
+            return Err("max-clock-skew-slots cannot be 0".to_string());
+        }
+
+        config.signing_concurrency_limit = parse_optional(cli_args, "signing-concurrency-limit")?;
+        if config
+            .signing_concurrency_limit
+            .map_or(false, |limit| limit < 2)
+        {
+            return Err(
+                "signing-concurrency-limit must be at least 2, since one signature is reserved \
+                for blocks"
+                    .to_string(),
+            );
+        }
+
+        if let Some(path) = parse_optional::<PathBuf>(cli_args, "validator-monitor-token-file")? {
+            let token = fs::read_to_string(&path)
+                .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?
+                .trim()
+                .to_string();
+            if token.is_empty() {
+                return Err(format!("{} does not contain a token", path.display()));
+            }
+            config.validator_monitor_token = Some(token);
+        }
+        config.validator_monitor_cohort = parse_optional(cli_args, "validator-monitor-cohort")?;
+
+        if cli_args.is_present("dry-run") {
+            config.dry_run = Some(dry_run::Config {
+                state_path: parse_required(cli_args, "dry-run-state")?,
+                output_path: parse_required(cli_args, "dry-run-output")?,
+            });
+        }
+
         /*
          * Experimental
          */
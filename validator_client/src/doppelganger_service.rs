@@ -11,6 +11,12 @@
 //!
 //! If a doppelganger is detected, the entire validator client will exit.
 //!
+//! Liveness is requested from every configured beacon node using the standard
+//! `POST /eth/v1/validator/liveness/{epoch}` endpoint, and a validator is considered live if any
+//! beacon node has observed it. Detection is never completed early, even if every beacon node
+//! reports no activity, since a beacon node only knows of the messages it has observed since it
+//! last started.
+//!
 //! For validators started during the genesis epoch, there is **no doppelganger protection!**. This
 //! prevents a stale-mate where all validators will cease to function for a few epochs and then all
 //! start at the same time.
@@ -35,7 +41,7 @@ use crate::OfflineOnFailure;
 use environment::RuntimeContext;
 use eth2::types::LivenessResponseData;
 use parking_lot::RwLock;
-use slog::{crit, error, info, Logger};
+use slog::{crit, debug, error, info, Logger};
 use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
@@ -151,10 +157,12 @@ impl DoppelgangerState {
     }
 }
 
-/// Perform two requests to the BN to obtain the liveness data for `validator_indices`. One
+/// Perform two requests to each BN to obtain the liveness data for `validator_indices`. One
 /// request will pertain to the `current_epoch`, the other to the `previous_epoch`.
 ///
-/// If the BN fails to respond to either of these requests, simply return an empty response.
+/// A validator is considered live in an epoch if *any* BN has observed it to be live.
+///
+/// If all BNs fail to respond to either of these requests, simply return an empty response.
 /// This behaviour is to help prevent spurious failures on the BN from needlessly preventing
 /// doppelganger progression.
 async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
@@ -175,60 +183,15 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
         // any of the doppelganger states.
         vec![]
     } else {
-        // Request the previous epoch liveness state from the beacon node.
-        beacon_nodes
-            .first_success(
-                RequireSynced::Yes,
-                OfflineOnFailure::Yes,
-                |beacon_node| async move {
-                    beacon_node
-                        .post_lighthouse_liveness(validator_indices, previous_epoch)
-                        .await
-                        .map_err(|e| format!("Failed query for validator liveness: {:?}", e))
-                        .map(|result| result.data)
-                },
-            )
-            .await
-            .unwrap_or_else(|e| {
-                crit!(
-                    log,
-                    "Failed previous epoch liveness query";
-                    "error" => %e,
-                    "previous_epoch" => %previous_epoch,
-                );
-                // Return an empty vec. In effect, this means to keep trying to make doppelganger
-                // progress even if some of the calls are failing.
-                vec![]
-            })
+        // Request the previous epoch liveness state from the beacon nodes.
+        epoch_liveness(&beacon_nodes, &log, previous_epoch, validator_indices).await
     };
 
-    // Request the current epoch liveness state from the beacon node.
-    let current_epoch_responses = beacon_nodes
-        .first_success(
-            RequireSynced::Yes,
-            OfflineOnFailure::Yes,
-            |beacon_node| async move {
-                beacon_node
-                    .post_lighthouse_liveness(validator_indices, current_epoch)
-                    .await
-                    .map_err(|e| format!("Failed query for validator liveness: {:?}", e))
-                    .map(|result| result.data)
-            },
-        )
-        .await
-        .unwrap_or_else(|e| {
-            crit!(
-                log,
-                "Failed current epoch liveness query";
-                "error" => %e,
-                "current_epoch" => %current_epoch,
-            );
-            // Return an empty vec. In effect, this means to keep trying to make doppelganger
-            // progress even if some of the calls are failing.
-            vec![]
-        });
+    // Request the current epoch liveness state from the beacon nodes.
+    let current_epoch_responses =
+        epoch_liveness(&beacon_nodes, &log, current_epoch, validator_indices).await;
 
-    // Alert the user if the beacon node is omitting validators from the response.
+    // Alert the user if the beacon nodes are omitting validators from the response.
     //
     // This is not perfect since the validator might return duplicate entries, but it's a quick
     // and easy way to detect issues.
@@ -250,6 +213,70 @@ async fn beacon_node_liveness<'a, T: 'static + SlotClock, E: EthSpec>(
     }
 }
 
+/// Query the standard liveness endpoint of every BN for `epoch`, merging the responses.
+async fn epoch_liveness<T: 'static + SlotClock, E: EthSpec>(
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    log: &Logger,
+    epoch: Epoch,
+    validator_indices: &[u64],
+) -> Vec<LivenessResponseData> {
+    let results = beacon_nodes
+        .results_from_all(
+            RequireSynced::Yes,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                beacon_node
+                    .post_validator_liveness_epoch(epoch, validator_indices.to_vec())
+                    .await
+                    .map_err(|e| format!("Failed query for validator liveness: {:?}", e))
+                    .map(|result| result.data)
+            },
+        )
+        .await;
+
+    if !results.iter().any(Result::is_ok) {
+        crit!(
+            log,
+            "Failed liveness query on all beacon nodes";
+            "epoch" => %epoch,
+        );
+        // Return an empty vec. In effect, this means to keep trying to make doppelganger
+        // progress even if some of the calls are failing.
+        return vec![];
+    }
+
+    let mut liveness = HashMap::with_capacity(validator_indices.len());
+    for result in results {
+        match result {
+            Ok(responses) => {
+                for response in responses {
+                    *liveness.entry(response.index).or_insert(false) |= response.is_live;
+                }
+            }
+            Err((beacon_node, e)) => {
+                debug!(
+                    log,
+                    "Liveness query failed";
+                    "error" => ?e,
+                    "beacon_node" => beacon_node,
+                    "epoch" => %epoch,
+                );
+            }
+        }
+    }
+
+    validator_indices
+        .iter()
+        .filter_map(|&index| {
+            liveness.get(&index).map(|&is_live| LivenessResponseData {
+                index,
+                epoch,
+                is_live,
+            })
+        })
+        .collect()
+}
+
 pub struct DoppelgangerService {
     doppelganger_states: RwLock<HashMap<PublicKeyBytes, DoppelgangerState>>,
     log: Logger,
@@ -1445,4 +1472,28 @@ mod test {
 
         scenario.assert_all_enabled();
     }
+
+    #[test]
+    fn detection_is_not_completed_before_the_full_window() {
+        let initial_epoch = genesis_epoch() + 42;
+        let initial_slot = initial_epoch.start_slot(E::slots_per_epoch());
+        let satisfaction_slot = initial_epoch.end_slot(E::slots_per_epoch());
+
+        TestBuilder::default()
+            .build()
+            .set_slot(initial_slot)
+            .register_all_in_doppelganger_protection_if_enabled()
+            .simulate_detect_doppelgangers(
+                satisfaction_slot,
+                ShouldShutdown::No,
+                |current_epoch, detection_indices: Vec<_>| {
+                    future::ready(get_false_responses(current_epoch, &detection_indices))
+                },
+            )
+            .assert_all_disabled()
+            .assert_all_states(&DoppelgangerState {
+                next_check_epoch: initial_epoch + 1,
+                remaining_epochs: DEFAULT_REMAINING_DETECTION_EPOCHS,
+            });
+    }
 }
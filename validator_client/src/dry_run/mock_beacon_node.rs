@@ -0,0 +1,646 @@
+//! A beacon node which serves the duties of a fixture `BeaconState` to the validator client
+//! services during a dry run, and records the messages which they publish instead of gossiping
+//! them.
+//!
+//! Every remaining slot of the state's epoch is assumed to be empty, so attestations always vote
+//! for the latest block in the state and produced blocks build upon it.
+
+use super::DryRunMessage;
+use eth2::types::{
+    AttesterData, DutiesResponse, ExecutionOptimisticFinalizedResponse, GenericResponse,
+    ProposerData, SyncDuty, SyncingData, ValidatorAggregateAttestationQuery,
+    ValidatorAttestationDataQuery, ValidatorBlocksQuery, ValidatorData, ValidatorId,
+    ValidatorIndexData, ValidatorStatus, VersionData,
+};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::{
+    map_fork_name, Address, Attestation, AttestationData, BeaconBlock, BeaconState, ChainSpec,
+    Checkpoint, CommitteeIndex, Config, EmptyBlock, Epoch, EthSpec, ForkVersionedResponse, Hash256,
+    ProposerPreparationData, RelativeEpoch, SignedAggregateAndProof, SignedBeaconBlock, Slot,
+};
+use warp::{Filter, Rejection};
+use warp_utils::reject::{custom_bad_request, custom_not_found, custom_server_error};
+use warp_utils::task::blocking_json_task;
+
+pub struct MockBeaconNode<E: EthSpec> {
+    state: BeaconState<E>,
+    /// The root of the latest block in `state`.
+    head_root: Hash256,
+    spec: ChainSpec,
+    /// Attestations published by the validator client, from which aggregates are produced.
+    attestations: Mutex<Vec<Attestation<E>>>,
+    /// The fee recipient most recently prepared for each validator index.
+    fee_recipients: Mutex<HashMap<u64, Address>>,
+    messages: Mutex<Vec<DryRunMessage<E>>>,
+    /// The number of upcoming attestation data requests which will fail.
+    attestation_data_failures: AtomicUsize,
+}
+
+impl<E: EthSpec> MockBeaconNode<E> {
+    pub fn new(mut state: BeaconState<E>, spec: ChainSpec) -> Result<Self, String> {
+        for relative_epoch in [RelativeEpoch::Current, RelativeEpoch::Next] {
+            state
+                .build_committee_cache(relative_epoch, &spec)
+                .map_err(|e| format!("Unable to build committee cache: {:?}", e))?;
+        }
+        state
+            .update_pubkey_cache()
+            .map_err(|e| format!("Unable to build pubkey cache: {:?}", e))?;
+
+        // The state root of the latest block is only filled in once the next slot is processed.
+        let mut latest_block_header = state.latest_block_header().clone();
+        if latest_block_header.state_root.is_zero() {
+            latest_block_header.state_root = state.canonical_root();
+        }
+        let head_root = latest_block_header.canonical_root();
+
+        Ok(Self {
+            state,
+            head_root,
+            spec,
+            attestations: <_>::default(),
+            fee_recipients: <_>::default(),
+            messages: <_>::default(),
+            attestation_data_failures: <_>::default(),
+        })
+    }
+
+    pub fn state(&self) -> &BeaconState<E> {
+        &self.state
+    }
+
+    /// Returns the messages published so far, ordered by slot.
+    pub fn messages(&self) -> Vec<DryRunMessage<E>> {
+        let mut messages = self.messages.lock().clone();
+        messages.sort_by_key(DryRunMessage::slot);
+        messages
+    }
+
+    /// Causes the next `count` attestation data requests to fail.
+    #[cfg(test)]
+    pub fn fail_attestation_data(&self, count: usize) {
+        self.attestation_data_failures
+            .store(count, Ordering::Relaxed);
+    }
+
+    fn validator(&self, validator_id: &ValidatorId) -> Result<ValidatorData, Rejection> {
+        let index = match validator_id {
+            ValidatorId::PublicKey(pubkey) => self.state.pubkey_cache().get(pubkey),
+            ValidatorId::Index(index) => Some(*index as usize),
+        };
+        let validator = index
+            .and_then(|index| Some((index, self.state.validators().get(index)?)))
+            .map(|(index, validator)| ValidatorData {
+                index: index as u64,
+                balance: self.state.balances().get(index).copied().unwrap_or(0),
+                status: ValidatorStatus::from_validator(
+                    validator,
+                    self.state.current_epoch(),
+                    self.spec.far_future_epoch,
+                ),
+                validator: validator.clone(),
+            });
+
+        validator.ok_or_else(|| custom_not_found(format!("unknown validator: {}", validator_id)))
+    }
+
+    fn relative_epoch(&self, epoch: Epoch) -> Result<RelativeEpoch, Rejection> {
+        match RelativeEpoch::from_epoch(self.state.current_epoch(), epoch) {
+            Ok(relative_epoch @ (RelativeEpoch::Current | RelativeEpoch::Next)) => {
+                Ok(relative_epoch)
+            }
+            _ => Err(custom_bad_request(format!(
+                "epoch {} is not served by the dry run",
+                epoch
+            ))),
+        }
+    }
+
+    fn attester_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[u64],
+    ) -> Result<DutiesResponse<Vec<AttesterData>>, Rejection> {
+        let relative_epoch = self.relative_epoch(epoch)?;
+
+        let mut data = vec![];
+        for &validator_index in indices {
+            let duty = self
+                .state
+                .get_attestation_duties(validator_index as usize, relative_epoch)
+                .map_err(|e| custom_bad_request(format!("unable to get duties: {:?}", e)))?;
+            // Slots which are already in the state have passed.
+            if let Some(duty) = duty.filter(|duty| duty.slot >= self.state.slot()) {
+                data.push(AttesterData {
+                    pubkey: self
+                        .validator(&ValidatorId::Index(validator_index))?
+                        .validator
+                        .pubkey,
+                    validator_index,
+                    committees_at_slot: duty.committees_at_slot,
+                    committee_index: duty.index,
+                    committee_length: duty.committee_len as u64,
+                    validator_committee_index: duty.committee_position as u64,
+                    slot: duty.slot,
+                });
+            }
+        }
+
+        Ok(DutiesResponse {
+            dependent_root: self
+                .state
+                .attester_shuffling_decision_root(self.head_root, relative_epoch)
+                .map_err(|e| {
+                    custom_bad_request(format!("unable to get dependent root: {:?}", e))
+                })?,
+            execution_optimistic: Some(false),
+            data,
+        })
+    }
+
+    fn proposer_duties(
+        &self,
+        epoch: Epoch,
+    ) -> Result<DutiesResponse<Vec<ProposerData>>, Rejection> {
+        if epoch != self.state.current_epoch() {
+            return Err(custom_bad_request(format!(
+                "proposers are only known for epoch {}",
+                self.state.current_epoch()
+            )));
+        }
+
+        let mut data = vec![];
+        for slot in epoch.slot_iter(E::slots_per_epoch()) {
+            if slot < self.state.slot() {
+                continue;
+            }
+            let validator_index = self.proposer_index(slot)?;
+            data.push(ProposerData {
+                pubkey: self
+                    .validator(&ValidatorId::Index(validator_index))?
+                    .validator
+                    .pubkey,
+                validator_index,
+                slot,
+            });
+        }
+
+        Ok(DutiesResponse {
+            dependent_root: self
+                .state
+                .proposer_shuffling_decision_root(self.head_root)
+                .map_err(|e| {
+                    custom_bad_request(format!("unable to get dependent root: {:?}", e))
+                })?,
+            execution_optimistic: Some(false),
+            data,
+        })
+    }
+
+    fn proposer_index(&self, slot: Slot) -> Result<u64, Rejection> {
+        self.state
+            .get_beacon_proposer_index(slot, &self.spec)
+            .map(|index| index as u64)
+            .map_err(|e| custom_bad_request(format!("unable to get proposer: {:?}", e)))
+    }
+
+    fn attestation_data(
+        &self,
+        slot: Slot,
+        index: CommitteeIndex,
+    ) -> Result<AttestationData, Rejection> {
+        if self
+            .attestation_data_failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |failures| {
+                failures.checked_sub(1)
+            })
+            .is_ok()
+        {
+            return Err(custom_server_error(
+                "attestation data is unavailable".to_string(),
+            ));
+        }
+
+        self.state
+            .get_beacon_committee(slot, index)
+            .map_err(|e| custom_bad_request(format!("unknown committee: {:?}", e)))?;
+
+        let epoch = slot.epoch(E::slots_per_epoch());
+        let target_slot = epoch.start_slot(E::slots_per_epoch());
+        let target_root = if target_slot < self.state.slot() {
+            *self
+                .state
+                .get_block_root(target_slot)
+                .map_err(|e| custom_bad_request(format!("unable to get target root: {:?}", e)))?
+        } else {
+            self.head_root
+        };
+
+        Ok(AttestationData {
+            slot,
+            index,
+            beacon_block_root: self.head_root,
+            source: self.state.current_justified_checkpoint(),
+            target: Checkpoint {
+                epoch,
+                root: target_root,
+            },
+        })
+    }
+
+    fn publish_attestations(&self, attestations: Vec<Attestation<E>>) -> Result<(), Rejection> {
+        for attestation in &attestations {
+            let committee = self
+                .state
+                .get_beacon_committee(attestation.data.slot, attestation.data.index)
+                .map_err(|e| custom_bad_request(format!("unknown committee: {:?}", e)))?;
+            let attesters = attestation
+                .aggregation_bits
+                .iter()
+                .zip(committee.committee)
+                .filter(|(bit, _)| *bit)
+                .map(|(_, validator_index)| *validator_index as u64)
+                .collect::<Vec<_>>();
+
+            let mut messages = self.messages.lock();
+            for validator_index in attesters {
+                messages.push(DryRunMessage::Attestation {
+                    slot: attestation.data.slot,
+                    validator_index,
+                    pubkey: self
+                        .validator(&ValidatorId::Index(validator_index))?
+                        .validator
+                        .pubkey,
+                    attestation: attestation.clone(),
+                });
+            }
+        }
+
+        self.attestations.lock().extend(attestations);
+        Ok(())
+    }
+
+    fn aggregate_attestation(
+        &self,
+        slot: Slot,
+        attestation_data_root: Hash256,
+    ) -> Result<Attestation<E>, Rejection> {
+        let mut aggregate: Option<Attestation<E>> = None;
+        for attestation in self.attestations.lock().iter().filter(|attestation| {
+            attestation.data.slot == slot
+                && attestation.data.tree_hash_root() == attestation_data_root
+        }) {
+            match &mut aggregate {
+                Some(aggregate) if aggregate.signers_disjoint_from(attestation) => {
+                    aggregate.aggregate(attestation)
+                }
+                Some(_) => (),
+                None => aggregate = Some(attestation.clone()),
+            }
+        }
+
+        aggregate.ok_or_else(|| custom_not_found("no matching attestations".to_string()))
+    }
+
+    fn publish_aggregates(
+        &self,
+        aggregates: Vec<SignedAggregateAndProof<E>>,
+    ) -> Result<(), Rejection> {
+        for aggregate in aggregates {
+            let validator_index = aggregate.message.aggregator_index;
+            let message = DryRunMessage::Aggregate {
+                slot: aggregate.message.aggregate.data.slot,
+                validator_index,
+                pubkey: self
+                    .validator(&ValidatorId::Index(validator_index))?
+                    .validator
+                    .pubkey,
+                aggregate,
+            };
+            self.messages.lock().push(message);
+        }
+        Ok(())
+    }
+
+    fn produce_block(
+        &self,
+        slot: Slot,
+        query: ValidatorBlocksQuery,
+    ) -> Result<ForkVersionedResponse<BeaconBlock<E>>, Rejection> {
+        if slot < self.state.slot() {
+            return Err(custom_bad_request(format!(
+                "slot {} is prior to the state",
+                slot
+            )));
+        }
+
+        let fork_name = self.spec.fork_name_at_slot::<E>(slot);
+        let mut block: BeaconBlock<E> =
+            map_fork_name!(fork_name, BeaconBlock, EmptyBlock::empty(&self.spec));
+        *block.slot_mut() = slot;
+        *block.proposer_index_mut() = self.proposer_index(slot)?;
+        *block.parent_root_mut() = self.head_root;
+        *block.body_mut().randao_reveal_mut() = query.randao_reveal.decompress().map_err(|e| {
+            custom_bad_request(format!(
+                "randao reveal is not a valid BLS signature: {:?}",
+                e
+            ))
+        })?;
+        *block.body_mut().eth1_data_mut() = self.state.eth1_data().clone();
+        if let Some(graffiti) = query.graffiti {
+            *block.body_mut().graffiti_mut() = graffiti;
+        }
+
+        Ok(ForkVersionedResponse {
+            version: Some(fork_name),
+            data: block,
+        })
+    }
+
+    fn publish_block(&self, block: SignedBeaconBlock<E>) -> Result<(), Rejection> {
+        let validator_index = block.message().proposer_index();
+        let message = DryRunMessage::Proposal {
+            slot: block.slot(),
+            validator_index,
+            pubkey: self
+                .validator(&ValidatorId::Index(validator_index))?
+                .validator
+                .pubkey,
+            graffiti: *block.message().body().graffiti(),
+            fee_recipient: self.fee_recipients.lock().get(&validator_index).copied(),
+            block,
+        };
+        self.messages.lock().push(message);
+        Ok(())
+    }
+
+    fn prepare_proposers(&self, preparation_data: Vec<ProposerPreparationData>) {
+        let mut fee_recipients = self.fee_recipients.lock();
+        for data in preparation_data {
+            fee_recipients.insert(data.validator_index, data.fee_recipient);
+        }
+    }
+
+    /// Serves the endpoints used by the validator client services on a random local port until
+    /// `shutdown` completes.
+    pub fn serve(
+        self: Arc<Self>,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<(SocketAddr, impl Future<Output = ()>), String> {
+        let node_filter = warp::any().map(move || self.clone());
+        let eth_v1 = warp::path("eth").and(warp::path("v1"));
+
+        // GET node/version
+        let get_node_version = eth_v1
+            .and(warp::path("node"))
+            .and(warp::path("version"))
+            .and(warp::path::end())
+            .and_then(|| {
+                blocking_json_task(|| {
+                    Ok(GenericResponse::from(VersionData {
+                        version: format!("{} (dry run)", lighthouse_version::VERSION),
+                    }))
+                })
+            });
+
+        // GET node/syncing
+        let get_node_syncing = eth_v1
+            .and(warp::path("node"))
+            .and(warp::path("syncing"))
+            .and(warp::path::end())
+            .and(node_filter.clone())
+            .and_then(|node: Arc<Self>| {
+                blocking_json_task(move || {
+                    Ok(GenericResponse::from(SyncingData {
+                        is_syncing: false,
+                        is_optimistic: Some(false),
+                        el_offline: Some(false),
+                        head_slot: node.state.slot(),
+                        sync_distance: Slot::new(0),
+                    }))
+                })
+            });
+
+        // GET config/spec
+        let get_config_spec = eth_v1
+            .and(warp::path("config"))
+            .and(warp::path("spec"))
+            .and(warp::path::end())
+            .and(node_filter.clone())
+            .and_then(|node: Arc<Self>| {
+                blocking_json_task(move || {
+                    Ok(GenericResponse::from(Config::from_chain_spec::<E>(
+                        &node.spec,
+                    )))
+                })
+            });
+
+        // GET beacon/states/{state_id}/validators/{validator_id}
+        let get_validator = eth_v1
+            .and(warp::path("beacon"))
+            .and(warp::path("states"))
+            .and(warp::path::param::<String>())
+            .and(warp::path("validators"))
+            .and(warp::path::param::<ValidatorId>())
+            .and(warp::path::end())
+            .and(node_filter.clone())
+            .and_then(
+                |_state_id: String, validator_id: ValidatorId, node: Arc<Self>| {
+                    blocking_json_task(move || {
+                        Ok(ExecutionOptimisticFinalizedResponse {
+                            execution_optimistic: Some(false),
+                            finalized: Some(false),
+                            data: node.validator(&validator_id)?,
+                        })
+                    })
+                },
+            );
+
+        // GET validator/duties/proposer/{epoch}
+        let get_proposer_duties = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("duties"))
+            .and(warp::path("proposer"))
+            .and(warp::path::param::<Epoch>())
+            .and(warp::path::end())
+            .and(node_filter.clone())
+            .and_then(|epoch: Epoch, node: Arc<Self>| {
+                blocking_json_task(move || node.proposer_duties(epoch))
+            });
+
+        // GET validator/attestation_data
+        let get_attestation_data = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("attestation_data"))
+            .and(warp::path::end())
+            .and(warp::query::<ValidatorAttestationDataQuery>())
+            .and(node_filter.clone())
+            .and_then(|query: ValidatorAttestationDataQuery, node: Arc<Self>| {
+                blocking_json_task(move || {
+                    node.attestation_data(query.slot, query.committee_index)
+                        .map(GenericResponse::from)
+                })
+            });
+
+        // GET validator/aggregate_attestation
+        let get_aggregate_attestation = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("aggregate_attestation"))
+            .and(warp::path::end())
+            .and(warp::query::<ValidatorAggregateAttestationQuery>())
+            .and(node_filter.clone())
+            .and_then(
+                |query: ValidatorAggregateAttestationQuery, node: Arc<Self>| {
+                    blocking_json_task(move || {
+                        node.aggregate_attestation(query.slot, query.attestation_data_root)
+                            .map(GenericResponse::from)
+                    })
+                },
+            );
+
+        // GET v2/validator/blocks/{slot}
+        let get_block = warp::path("eth")
+            .and(warp::path("v2"))
+            .and(warp::path("validator"))
+            .and(warp::path("blocks"))
+            .and(warp::path::param::<Slot>())
+            .and(warp::path::end())
+            .and(warp::query::<ValidatorBlocksQuery>())
+            .and(node_filter.clone())
+            .and_then(|slot: Slot, query: ValidatorBlocksQuery, node: Arc<Self>| {
+                blocking_json_task(move || node.produce_block(slot, query))
+            });
+
+        // POST validator/duties/attester/{epoch}
+        let post_attester_duties = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("duties"))
+            .and(warp::path("attester"))
+            .and(warp::path::param::<Epoch>())
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(node_filter.clone())
+            .and_then(
+                |epoch: Epoch, indices: ValidatorIndexData, node: Arc<Self>| {
+                    blocking_json_task(move || node.attester_duties(epoch, &indices.0))
+                },
+            );
+
+        // POST validator/duties/sync/{epoch}
+        //
+        // Sync committee messages are not produced during a dry run.
+        let post_sync_duties = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("duties"))
+            .and(warp::path("sync"))
+            .and(warp::path::param::<Epoch>())
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and_then(|_epoch: Epoch, _indices: ValidatorIndexData| {
+                blocking_json_task(|| {
+                    Ok(ExecutionOptimisticFinalizedResponse::<Vec<SyncDuty>> {
+                        execution_optimistic: Some(false),
+                        finalized: Some(false),
+                        data: vec![],
+                    })
+                })
+            });
+
+        // POST validator/beacon_committee_subscriptions
+        // POST validator/register_validator
+        let post_ignored = eth_v1
+            .and(warp::path("validator"))
+            .and(
+                warp::path("beacon_committee_subscriptions")
+                    .or(warp::path("register_validator"))
+                    .unify(),
+            )
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and_then(|_body: serde_json::Value| blocking_json_task(|| Ok(())));
+
+        // POST validator/prepare_beacon_proposer
+        let post_prepare_beacon_proposer = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("prepare_beacon_proposer"))
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(node_filter.clone())
+            .and_then(
+                |preparation_data: Vec<ProposerPreparationData>, node: Arc<Self>| {
+                    blocking_json_task(move || {
+                        node.prepare_proposers(preparation_data);
+                        Ok(())
+                    })
+                },
+            );
+
+        // POST beacon/pool/attestations
+        let post_attestations = eth_v1
+            .and(warp::path("beacon"))
+            .and(warp::path("pool"))
+            .and(warp::path("attestations"))
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(node_filter.clone())
+            .and_then(|attestations: Vec<Attestation<E>>, node: Arc<Self>| {
+                blocking_json_task(move || node.publish_attestations(attestations))
+            });
+
+        // POST validator/aggregate_and_proofs
+        let post_aggregates = eth_v1
+            .and(warp::path("validator"))
+            .and(warp::path("aggregate_and_proofs"))
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(node_filter.clone())
+            .and_then(
+                |aggregates: Vec<SignedAggregateAndProof<E>>, node: Arc<Self>| {
+                    blocking_json_task(move || node.publish_aggregates(aggregates))
+                },
+            );
+
+        // POST beacon/blocks
+        let post_block = eth_v1
+            .and(warp::path("beacon"))
+            .and(warp::path("blocks"))
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(node_filter)
+            .and_then(|block: SignedBeaconBlock<E>, node: Arc<Self>| {
+                blocking_json_task(move || node.publish_block(block))
+            });
+
+        let routes = warp::get()
+            .and(
+                get_node_version
+                    .or(get_node_syncing)
+                    .or(get_config_spec)
+                    .or(get_validator)
+                    .or(get_proposer_duties)
+                    .or(get_attestation_data)
+                    .or(get_aggregate_attestation)
+                    .or(get_block),
+            )
+            .or(warp::post().and(
+                post_attester_duties
+                    .or(post_sync_duties)
+                    .or(post_ignored)
+                    .or(post_prepare_beacon_proposer)
+                    .or(post_attestations)
+                    .or(post_aggregates)
+                    .or(post_block),
+            ))
+            .recover(warp_utils::reject::handle_rejection);
+
+        warp::serve(routes)
+            .try_bind_with_graceful_shutdown(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0), shutdown)
+            .map_err(|e| format!("Unable to start mock beacon node: {:?}", e))
+    }
+}
@@ -0,0 +1,594 @@
+//! Runs the duties of the local validators against a fixture `BeaconState`, without contacting a
+//! beacon node.
+//!
+//! The duties, attestation, block and preparation services run as usual, but against a mock beacon
+//! node which serves the fixture state and records the messages it is sent instead of publishing
+//! them. The slot clock starts at the slot of the state, and the messages are written to a file
+//! once the state's epoch has passed. The keystores are never decrypted and remote signers are never
+//! contacted: every message carries the empty signature, and slashing protection is checked against
+//! a temporary database. This allows the configuration of a validator client (keys, fee recipients
+//! and graffiti) to be validated without touching a network or the keys.
+mod mock_beacon_node;
+
+use crate::attestation_service::AttestationServiceBuilder;
+use crate::beacon_node_fallback::{
+    start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode,
+};
+use crate::block_service::BlockServiceBuilder;
+use crate::config::Config as VcConfig;
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::duties_service::{self, DutiesService};
+use crate::initialized_validators::InitializedValidators;
+use crate::preparation_service::PreparationServiceBuilder;
+use crate::validator_store::ValidatorStore;
+use account_utils::validator_definitions::ValidatorDefinitions;
+use environment::RuntimeContext;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use mock_beacon_node::MockBeaconNode;
+use sensitive_url::SensitiveUrl;
+use serde_derive::{Deserialize, Serialize};
+use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
+use slog::{info, warn, Logger};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use task_executor::TaskExecutor;
+use tokio::{sync::mpsc, time::sleep};
+use types::{
+    Address, Attestation, BeaconState, EthSpec, Graffiti, PublicKeyBytes, SignedAggregateAndProof,
+    SignedBeaconBlock, Slot,
+};
+
+/// Configuration for a dry run of the validator client.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Config {
+    /// Path to an SSZ-encoded `BeaconState` from which duties are computed.
+    pub state_path: PathBuf,
+    /// Path to which the would-be messages are written as JSON.
+    pub output_path: PathBuf,
+}
+
+/// A message which the validator client would have published for a local validator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[serde(bound = "E: EthSpec")]
+pub enum DryRunMessage<E: EthSpec> {
+    Proposal {
+        slot: Slot,
+        validator_index: u64,
+        pubkey: PublicKeyBytes,
+        graffiti: Graffiti,
+        /// The fee recipient which the validator client prepared for the proposer, if any.
+        fee_recipient: Option<Address>,
+        block: SignedBeaconBlock<E>,
+    },
+    Attestation {
+        slot: Slot,
+        validator_index: u64,
+        pubkey: PublicKeyBytes,
+        attestation: Attestation<E>,
+    },
+    Aggregate {
+        slot: Slot,
+        validator_index: u64,
+        pubkey: PublicKeyBytes,
+        aggregate: SignedAggregateAndProof<E>,
+    },
+}
+
+impl<E: EthSpec> DryRunMessage<E> {
+    pub fn slot(&self) -> Slot {
+        match self {
+            DryRunMessage::Proposal { slot, .. }
+            | DryRunMessage::Attestation { slot, .. }
+            | DryRunMessage::Aggregate { slot, .. } => *slot,
+        }
+    }
+}
+
+/// Loads the local validators and the fixture state described by `config`, then writes the
+/// messages which would have been produced during the remainder of the state's epoch.
+pub async fn run<E: EthSpec>(
+    context: RuntimeContext<E>,
+    vc_config: VcConfig,
+    config: Config,
+) -> Result<(), String> {
+    let log = context.log().clone();
+    let spec = &context.eth2_config.spec;
+
+    info!(
+        log,
+        "Starting validator client dry run";
+        "state" => ?config.state_path,
+        "output" => ?config.output_path,
+    );
+
+    let validators = load_validators(&vc_config, &log)?;
+
+    let state_bytes = fs::read(&config.state_path)
+        .map_err(|e| format!("Unable to read {:?}: {:?}", config.state_path, e))?;
+    let state = BeaconState::<E>::from_ssz_bytes(&state_bytes, spec)
+        .map_err(|e| format!("Unable to decode fixture state: {:?}", e))?;
+
+    let messages = simulate_epoch(context, &vc_config, validators, state).await?;
+
+    let output = File::create(&config.output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", config.output_path, e))?;
+    serde_json::to_writer_pretty(output, &messages)
+        .map_err(|e| format!("Unable to write dry run output: {:?}", e))?;
+
+    info!(
+        log,
+        "Completed validator client dry run";
+        "messages" => messages.len(),
+        "output" => ?config.output_path,
+    );
+
+    Ok(())
+}
+
+/// Loads the validators of `vc_config`, with placeholder signatures.
+fn load_validators(vc_config: &VcConfig, log: &Logger) -> Result<InitializedValidators, String> {
+    // Discover keystores as usual, but don't save the definitions: a dry run should leave the
+    // validator directory untouched.
+    let mut validator_defs = ValidatorDefinitions::open(&vc_config.validator_dir)
+        .map_err(|e| format!("Unable to open validator definitions: {:?}", e))?;
+    if !vc_config.disable_auto_discover {
+        validator_defs
+            .discover_local_keystores(&vc_config.validator_dir, &vc_config.secrets_dir, log)
+            .map_err(|e| format!("Unable to discover local validator keystores: {:?}", e))?;
+    }
+    // Never load the keystores or contact remote signers: messages signed with the live keys could
+    // be slashable if they were ever published.
+    InitializedValidators::placeholders(
+        validator_defs,
+        vc_config.validator_dir.clone(),
+        log.clone(),
+    )
+    .map_err(|e| format!("Unable to initialize validators: {:?}", e))
+}
+
+/// Runs the validator client services for `validators` against a mock beacon node serving
+/// `state`, from the slot of `state` until the end of its epoch, and returns the messages which
+/// they published.
+pub async fn simulate_epoch<E: EthSpec>(
+    context: RuntimeContext<E>,
+    vc_config: &VcConfig,
+    validators: InitializedValidators,
+    state: BeaconState<E>,
+) -> Result<Vec<DryRunMessage<E>>, String> {
+    let beacon_node = Arc::new(MockBeaconNode::new(
+        state,
+        context.eth2_config.spec.clone(),
+    )?);
+    run_services(context, vc_config, validators, beacon_node).await
+}
+
+/// Runs the validator client services for `validators` against `beacon_node`, from the slot of
+/// its state until the end of the state's epoch, and returns the messages which they published.
+async fn run_services<E: EthSpec>(
+    context: RuntimeContext<E>,
+    vc_config: &VcConfig,
+    validators: InitializedValidators,
+    beacon_node: Arc<MockBeaconNode<E>>,
+) -> Result<Vec<DryRunMessage<E>>, String> {
+    let log = context.log().clone();
+    let spec = context.eth2_config.spec.clone();
+    let slot_duration = Duration::from_secs(spec.seconds_per_slot);
+    let state = beacon_node.state();
+    let start_slot = state.slot();
+    let end_slot = state.current_epoch().end_slot(E::slots_per_epoch());
+    let genesis_validators_root = state.genesis_validators_root();
+
+    // The services are spawned on an executor of their own, so that they stop along with the mock
+    // beacon node once `exit_signal` is dropped at the end of the dry run.
+    let (exit_signal, exit) = exit_future::signal();
+    let handle = context
+        .executor
+        .handle()
+        .ok_or("Runtime is shutting down")?;
+    let executor = TaskExecutor::new(
+        handle,
+        exit,
+        log.clone(),
+        context.executor.shutdown_sender(),
+    );
+    let context = RuntimeContext {
+        executor,
+        ..context
+    };
+
+    // Builder proposals, doppelganger protection and head events aren't supported by the mock
+    // beacon node.
+    let mut vc_config = vc_config.clone();
+    vc_config.builder_proposals = false;
+    vc_config.enable_doppelganger_protection = false;
+    vc_config.subscribe_head_events = false;
+    vc_config.attestation_inclusion_check_delay = None;
+
+    let (listen_addr, server) = beacon_node.clone().serve(context.executor.exit())?;
+    context
+        .executor
+        .spawn_without_exit(server, "dry_run_beacon_node");
+
+    let url = SensitiveUrl::parse(&format!("http://{}", listen_addr))
+        .map_err(|e| format!("Invalid mock beacon node URL: {:?}", e))?;
+    let mut beacon_nodes: BeaconNodeFallback<_, E> = BeaconNodeFallback::new(
+        vec![CandidateBeaconNode::new(BeaconNodeHttpClient::new(
+            url,
+            Timeouts::set_all(slot_duration),
+        ))],
+        false,
+        spec.clone(),
+        log.clone(),
+    );
+
+    // The slot of the state starts half a slot from now, giving the services time to learn the
+    // indices of the validators before their first duties.
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Unable to read system time: {:?}", e))?;
+    let slot_clock = SystemTimeSlotClock::new(start_slot, now + slot_duration / 2, slot_duration);
+    beacon_nodes.set_slot_clock(slot_clock.clone());
+    beacon_nodes.update_all_candidates().await;
+    let beacon_nodes = Arc::new(beacon_nodes);
+    start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
+
+    // Sign using a temporary slashing protection database, so that the messages of the dry run
+    // can never prevent the validator client from signing.
+    let slashing_protection_dir = tempfile::tempdir()
+        .map_err(|e| format!("Unable to create slashing protection directory: {:?}", e))?;
+    let slashing_protection = SlashingDatabase::create(
+        &slashing_protection_dir
+            .path()
+            .join(SLASHING_PROTECTION_FILENAME),
+    )
+    .map_err(|e| format!("Unable to create slashing protection database: {:?}", e))?;
+    slashing_protection
+        .register_validators(validators.iter_voting_pubkeys())
+        .map_err(|e| format!("Error while registering slashing protection: {:?}", e))?;
+
+    let validator_store = Arc::new(ValidatorStore::new(
+        validators,
+        slashing_protection,
+        genesis_validators_root,
+        spec.clone(),
+        None,
+        slot_clock.clone(),
+        &vc_config,
+        context.executor.clone(),
+        log.clone(),
+    ));
+
+    for pubkey in validator_store.voting_pubkeys::<Vec<_>, _>(DoppelgangerStatus::ignored) {
+        if beacon_node.state().pubkey_cache().get(&pubkey).is_none() {
+            warn!(
+                log,
+                "Validator not found in fixture state";
+                "pubkey" => ?pubkey,
+            );
+        } else if validator_store.get_fee_recipient(&pubkey).is_none() {
+            warn!(
+                log,
+                "Validator has no fee recipient";
+                "pubkey" => ?pubkey,
+            );
+        }
+    }
+
+    let duties_service = Arc::new(DutiesService {
+        attesters: <_>::default(),
+        proposers: <_>::default(),
+        sync_duties: <_>::default(),
+        duty_sources: <_>::default(),
+        refresh_times: <_>::default(),
+        slot_clock: slot_clock.clone(),
+        beacon_nodes: beacon_nodes.clone(),
+        validator_store: validator_store.clone(),
+        spec: spec.clone(),
+        context: context.service_context("duties".into()),
+        enable_high_validator_count_metrics: false,
+        subscribe_head_events: false,
+        stale_duties_epochs: vc_config.stale_duties_epochs,
+    });
+
+    let block_service = BlockServiceBuilder::new()
+        .slot_clock(slot_clock.clone())
+        .validator_store(validator_store.clone())
+        .beacon_nodes(beacon_nodes.clone())
+        .runtime_context(context.service_context("block".into()))
+        .graffiti(vc_config.graffiti)
+        .graffiti_file(vc_config.graffiti_file.clone())
+        .build()?;
+
+    let attestation_service = AttestationServiceBuilder::new()
+        .duties_service(duties_service.clone())
+        .slot_clock(slot_clock.clone())
+        .validator_store(validator_store.clone())
+        .beacon_nodes(beacon_nodes.clone())
+        .runtime_context(context.service_context("attestation".into()))
+        .build()?;
+
+    let preparation_service = PreparationServiceBuilder::new()
+        .slot_clock(slot_clock.clone())
+        .validator_store(validator_store.clone())
+        .beacon_nodes(beacon_nodes)
+        .runtime_context(context.service_context("preparation".into()))
+        .validator_registration_batch_size(vc_config.validator_registration_batch_size)
+        .build()?;
+
+    let (block_service_tx, block_service_rx) = mpsc::channel(E::slots_per_epoch() as usize);
+    duties_service::start_update_service(duties_service, block_service_tx);
+    block_service
+        .start_update_service(block_service_rx)
+        .map_err(|e| format!("Unable to start block service: {}", e))?;
+    attestation_service
+        .start_update_service(&spec)
+        .map_err(|e| format!("Unable to start attestation service: {}", e))?;
+    preparation_service
+        .start_update_service(&spec)
+        .map_err(|e| format!("Unable to start preparation service: {}", e))?;
+
+    // Aggregates are published two thirds of the way through the last slot of the epoch.
+    let run_time = slot_clock
+        .duration_to_slot(end_slot + 1)
+        .ok_or("Unable to read slot clock")?;
+    info!(
+        log,
+        "Running validator duties";
+        "start_slot" => start_slot.as_u64(),
+        "end_slot" => end_slot.as_u64(),
+        "seconds" => run_time.as_secs(),
+    );
+    sleep(run_time).await;
+
+    drop(exit_signal);
+    Ok(beacon_node.messages())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_metrics::metrics;
+    use ssz::Encode;
+    use std::collections::HashSet;
+    use task_executor::test_utils::TestRuntime;
+    use tempfile::tempdir;
+    use types::{
+        test_utils::generate_deterministic_keypair, ChainSpec, Epoch, Eth1Data, Hash256,
+        MinimalEthSpec, Validator,
+    };
+    use validator_dir::insecure_keys::build_deterministic_validator_dirs;
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 8;
+
+    fn fixture_state(spec: &ChainSpec) -> BeaconState<E> {
+        let mut state = BeaconState::new(0, Eth1Data::default(), spec);
+        for i in 0..VALIDATOR_COUNT {
+            let validator = Validator {
+                pubkey: generate_deterministic_keypair(i).pk.into(),
+                withdrawal_credentials: Hash256::zero(),
+                effective_balance: spec.max_effective_balance,
+                slashed: false,
+                activation_eligibility_epoch: Epoch::new(0),
+                activation_epoch: Epoch::new(0),
+                exit_epoch: spec.far_future_epoch,
+                withdrawable_epoch: spec.far_future_epoch,
+            };
+            state.validators_mut().push(validator).unwrap();
+            state
+                .balances_mut()
+                .push(spec.max_effective_balance)
+                .unwrap();
+        }
+        // Start half way through the epoch, so that the earlier duties are skipped.
+        *state.slot_mut() = Slot::new(E::slots_per_epoch() / 2);
+        state
+    }
+
+    #[tokio::test]
+    async fn dry_run_publishes_placeholder_messages() {
+        let mut spec = E::default_spec();
+        spec.seconds_per_slot = 1;
+        // Proposers are only prepared close to the Bellatrix fork.
+        spec.altair_fork_epoch = Some(Epoch::new(1));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(2));
+
+        let validator_dir = tempdir().unwrap();
+        let secrets_dir = tempdir().unwrap();
+        let output_dir = tempdir().unwrap();
+        build_deterministic_validator_dirs(
+            validator_dir.path().into(),
+            secrets_dir.path().into(),
+            &(0..VALIDATOR_COUNT).collect::<Vec<_>>(),
+        )
+        .unwrap();
+
+        let mut state = fixture_state(&spec);
+        let state_path = output_dir.path().join("state.ssz");
+        fs::write(&state_path, state.as_ssz_bytes()).unwrap();
+
+        let fee_recipient = Address::repeat_byte(42);
+        let graffiti = Graffiti::from([7; 32]);
+        let mut vc_config = VcConfig::default();
+        vc_config.validator_dir = validator_dir.path().into();
+        vc_config.secrets_dir = secrets_dir.path().into();
+        vc_config.fee_recipient = Some(fee_recipient);
+        vc_config.graffiti = Some(graffiti);
+        let config = Config {
+            state_path,
+            output_path: output_dir.path().join("messages.json"),
+        };
+
+        let test_runtime = TestRuntime::default();
+        let mut context = RuntimeContext {
+            executor: test_runtime.task_executor.clone(),
+            eth_spec_instance: E::default(),
+            eth2_config: <_>::default(),
+            eth2_network_config: None,
+            sse_logging_components: None,
+            runtime_log_filter: None,
+        };
+        context.eth2_config.spec = spec.clone();
+
+        run(context, vc_config, config.clone()).await.unwrap();
+        let messages: Vec<DryRunMessage<E>> =
+            serde_json::from_reader(File::open(&config.output_path).unwrap()).unwrap();
+
+        state
+            .build_committee_cache(types::RelativeEpoch::Current, &spec)
+            .unwrap();
+        let slots = (state.slot().as_u64()..E::slots_per_epoch()).map(Slot::new);
+        let expected_proposals = slots
+            .clone()
+            .map(|slot| {
+                (
+                    slot,
+                    state.get_beacon_proposer_index(slot, &spec).unwrap() as u64,
+                )
+            })
+            .collect::<HashSet<_>>();
+        let expected_attestations = slots
+            .flat_map(|slot| state.get_beacon_committees_at_slot(slot).unwrap())
+            .flat_map(|committee| {
+                committee
+                    .committee
+                    .iter()
+                    .map(|index| (committee.slot, *index as u64))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>();
+
+        let mut proposals = HashSet::new();
+        let mut attestations = HashSet::new();
+        let mut aggregate_slots = HashSet::new();
+        for message in &messages {
+            match message {
+                DryRunMessage::Proposal {
+                    slot,
+                    validator_index,
+                    graffiti: block_graffiti,
+                    fee_recipient: prepared_fee_recipient,
+                    block,
+                    ..
+                } => {
+                    assert!(block.signature().is_empty());
+                    assert_eq!(*block_graffiti, graffiti);
+                    // The proposer is only certain to be prepared after the first slot.
+                    if *slot > state.slot() {
+                        assert_eq!(*prepared_fee_recipient, Some(fee_recipient));
+                    }
+                    assert!(proposals.insert((*slot, *validator_index)));
+                }
+                DryRunMessage::Attestation {
+                    slot,
+                    validator_index,
+                    attestation,
+                    ..
+                } => {
+                    // Only the empty signature was aggregated.
+                    assert!(attestation.signature.is_infinity());
+                    assert!(attestations.insert((*slot, *validator_index)));
+                }
+                DryRunMessage::Aggregate {
+                    slot,
+                    validator_index,
+                    aggregate,
+                    ..
+                } => {
+                    assert!(aggregate.message.aggregate.aggregation_bits.num_set_bits() > 0);
+                    assert!(aggregate.signature.is_empty());
+                    assert_eq!(aggregate.message.aggregator_index, *validator_index);
+                    aggregate_slots.insert(*slot);
+                }
+            }
+        }
+
+        assert_eq!(proposals, expected_proposals);
+        assert_eq!(attestations, expected_attestations);
+        // Every validator is an aggregator in committees this small.
+        assert_eq!(
+            aggregate_slots,
+            expected_attestations
+                .iter()
+                .map(|(slot, _)| *slot)
+                .collect::<HashSet<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn attestations_are_retried_when_attestation_data_fails() {
+        // Slots must be long enough to retry before aggregates are produced.
+        let mut spec = E::default_spec();
+        spec.seconds_per_slot = 3;
+
+        let validator_dir = tempdir().unwrap();
+        let secrets_dir = tempdir().unwrap();
+        build_deterministic_validator_dirs(
+            validator_dir.path().into(),
+            secrets_dir.path().into(),
+            &(0..VALIDATOR_COUNT).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let mut vc_config = VcConfig::default();
+        vc_config.validator_dir = validator_dir.path().into();
+        vc_config.secrets_dir = secrets_dir.path().into();
+
+        let test_runtime = TestRuntime::default();
+        let mut context = RuntimeContext {
+            executor: test_runtime.task_executor.clone(),
+            eth_spec_instance: E::default(),
+            eth2_config: <_>::default(),
+            eth2_network_config: None,
+            sse_logging_components: None,
+            runtime_log_filter: None,
+        };
+        context.eth2_config.spec = spec.clone();
+        let validators = load_validators(&vc_config, context.log()).unwrap();
+
+        // Only run the last slot of the epoch, which has a single committee.
+        let mut state = fixture_state(&spec);
+        *state.slot_mut() = Slot::new(E::slots_per_epoch() - 1);
+        let beacon_node = Arc::new(MockBeaconNode::new(state, spec.clone()).unwrap());
+        let committee = beacon_node
+            .state()
+            .get_beacon_committee(beacon_node.state().slot(), 0)
+            .unwrap()
+            .committee
+            .iter()
+            .copied()
+            .collect::<HashSet<_>>();
+
+        // The first attempt to produce the committee's attestations fails.
+        beacon_node.fail_attestation_data(1);
+        let messages = run_services(context, &vc_config, validators, beacon_node)
+            .await
+            .unwrap();
+
+        let attesters = messages
+            .iter()
+            .filter_map(|message| match message {
+                DryRunMessage::Attestation {
+                    validator_index, ..
+                } => Some(*validator_index as usize),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(attesters, committee);
+        assert!(messages
+            .iter()
+            .any(|message| matches!(message, DryRunMessage::Aggregate { .. })));
+
+        let failures = metrics::get_int_counter(
+            &metrics::ATTESTATION_COMMITTEE_FAILURES,
+            &["0", metrics::ATTESTATION_DATA],
+        )
+        .unwrap()
+        .get();
+        assert!(failures >= 1);
+    }
+}
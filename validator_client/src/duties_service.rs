@@ -16,8 +16,10 @@ use crate::{
     validator_store::{DoppelgangerStatus, Error as ValidatorStoreError, ValidatorStore},
 };
 use environment::RuntimeContext;
+use eth2::event_stream::ResubscribingEvent;
 use eth2::types::{
-    AttesterData, BeaconCommitteeSubscription, DutiesResponse, ProposerData, StateId, ValidatorId,
+    AttesterData, BeaconCommitteeSubscription, DutiesResponse, EventKind, EventTopic, ProposerData,
+    StateId, ValidatorId,
 };
 use futures::{stream, StreamExt};
 use parking_lot::RwLock;
@@ -30,7 +32,10 @@ use std::sync::Arc;
 use std::time::Duration;
 use sync::poll_sync_committee_duties;
 use sync::SyncDutiesMap;
-use tokio::{sync::mpsc::Sender, time::sleep};
+use tokio::{
+    sync::{mpsc::Sender, Notify},
+    time::sleep,
+};
 use types::{ChainSpec, Epoch, EthSpec, Hash256, PublicKeyBytes, SelectionProof, Slot};
 
 /// Since the BN does not like it when we subscribe to slots that are close to the current time, we
@@ -57,11 +62,22 @@ const SELECTION_PROOF_SCHEDULE_DENOM: u32 = 2;
 /// flag in the cli to enable collection of per validator metrics.
 const VALIDATOR_METRICS_MIN_COUNT: usize = 64;
 
+/// Delay between attempts to re-establish a dropped head event stream.
+const HEAD_EVENTS_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Number of disconnections from a beacon node's head event stream without a head event in
+/// between, after which the beacon node to follow is selected again. The same beacon node is chosen
+/// if it is still the first available one.
+const HEAD_EVENTS_MAX_DISCONNECTIONS: usize = 3;
+
 /// The number of validators to request duty information for in the initial request.
 /// The initial request is used to determine if further requests are required, so that it
 /// reduces the amount of data that needs to be transferred.
 const INITIAL_DUTIES_QUERY_SIZE: usize = 1;
 
+/// Delay between checks for an available beacon node whilst duties are stale.
+const STALE_DUTIES_RECHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub enum Error {
     UnableToReadSlotClock,
@@ -132,6 +148,32 @@ type DependentRoot = Hash256;
 type AttesterMap = HashMap<PublicKeyBytes, HashMap<Epoch, (DependentRoot, DutyAndProof)>>;
 type ProposerMap = HashMap<Epoch, (DependentRoot, Vec<ProposerData>)>;
 
+/// The beacon nodes which supplied the most recently downloaded duties.
+///
+/// This is only used to show operators where a duty came from.
+#[derive(Default)]
+pub struct DutySources {
+    /// Map from epoch to the beacon node which supplied attester duties for that epoch.
+    pub attesters: HashMap<Epoch, String>,
+    /// Map from epoch to the beacon node which supplied proposer duties for that epoch.
+    pub proposers: HashMap<Epoch, String>,
+    /// Map from sync committee period to the beacon node which supplied sync committee duties.
+    pub sync_committees: HashMap<u64, String>,
+}
+
+/// The times at which duties were last refreshed from a beacon node, as durations since the Unix
+/// epoch.
+#[derive(Default)]
+pub struct DutyRefreshTimes {
+    /// The last time attester duties for the current epoch were downloaded.
+    pub attesters: Option<Duration>,
+    /// The last time proposer duties for the current epoch were downloaded.
+    pub proposers: Option<Duration>,
+    /// The last time sync committee duties were checked, which only involves a download when
+    /// duties for a period are unknown.
+    pub sync_committees: Option<Duration>,
+}
+
 /// See the module-level documentation.
 pub struct DutiesService<T, E: EthSpec> {
     /// Maps a validator public key to their duties for each epoch.
@@ -141,6 +183,10 @@ pub struct DutiesService<T, E: EthSpec> {
     pub proposers: RwLock<ProposerMap>,
     /// Map from validator index to sync committee duties.
     pub sync_duties: SyncDutiesMap,
+    /// The beacon nodes which supplied the duties above.
+    pub duty_sources: RwLock<DutySources>,
+    /// The times at which the duties above were last refreshed.
+    pub refresh_times: RwLock<DutyRefreshTimes>,
     /// Provides the canonical list of locally-managed validators.
     pub validator_store: Arc<ValidatorStore<T, E>>,
     /// Tracks the current slot.
@@ -148,11 +194,32 @@ pub struct DutiesService<T, E: EthSpec> {
     /// Provides HTTP access to remote beacon nodes.
     pub beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     pub enable_high_validator_count_metrics: bool,
+    /// Poll attester duties early when head events show the duty dependent roots changing.
+    pub subscribe_head_events: bool,
+    /// Refresh duties as soon as a beacon node is available once attester duties have not been
+    /// refreshed for this many epochs.
+    pub stale_duties_epochs: u64,
     pub context: RuntimeContext<E>,
     pub spec: ChainSpec,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
+    /// Returns `true` if attester duties have not been refreshed for more than
+    /// `self.stale_duties_epochs` epochs.
+    ///
+    /// Before the first refresh, the time is measured from `started`.
+    pub fn duties_are_stale(&self, started: Duration) -> bool {
+        let Some(now) = self.slot_clock.now_duration() else {
+            return false;
+        };
+        let last_refresh = self.refresh_times.read().attesters.unwrap_or(started);
+        let stale_after = self
+            .slot_clock
+            .slot_duration()
+            .saturating_mul((self.stale_duties_epochs * E::slots_per_epoch()) as u32);
+        now.saturating_sub(last_refresh) > stale_after
+    }
+
     /// Returns the total number of validators known to the duties service.
     pub fn total_validator_count(&self) -> usize {
         self.validator_store.num_voting_validators()
@@ -326,13 +393,20 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
     /*
      * Spawn the task which keeps track of local attestation duties.
      */
+    let attester_poll_notify = Arc::new(Notify::new());
     let duties_service = core_duties_service.clone();
     let log = core_duties_service.context.log().clone();
+    let poll_notify = attester_poll_notify.clone();
     core_duties_service.context.executor.spawn(
         async move {
             loop {
                 if let Some(duration) = duties_service.slot_clock.duration_to_next_slot() {
-                    sleep(duration).await;
+                    // Poll at the start of the next slot, or earlier if a head event shows that
+                    // duties may have changed.
+                    tokio::select! {
+                        _ = sleep(duration) => {},
+                        _ = poll_notify.notified() => {},
+                    }
                 } else {
                     // Just sleep for one slot if we are unable to read the system clock, this gives
                     // us an opportunity for the clock to eventually come good.
@@ -352,6 +426,26 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
         "duties_service_attesters",
     );
 
+    /*
+     * Spawn the task which refreshes duties as soon as possible once they are stale.
+     */
+    let duties_service = core_duties_service.clone();
+    core_duties_service.context.executor.spawn(
+        monitor_stale_duties(duties_service, attester_poll_notify.clone()),
+        "duties_service_stale_duties",
+    );
+
+    /*
+     * Spawn the task which listens to head events and triggers attester polls between slots.
+     */
+    if core_duties_service.subscribe_head_events {
+        let duties_service = core_duties_service.clone();
+        core_duties_service.context.executor.spawn(
+            monitor_head_events(duties_service, attester_poll_notify),
+            "duties_service_head_events",
+        );
+    }
+
     // Spawn the task which keeps track of local sync committee duties.
     let duties_service = core_duties_service.clone();
     let log = core_duties_service.context.log().clone();
@@ -384,6 +478,165 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
     );
 }
 
+/// Follow the head events of a beacon node and notify the attester duties task whenever the duty
+/// dependent roots change, or when head events may have been missed.
+///
+/// This only supplements the per-slot polling, so any failure here is logged and retried.
+async fn monitor_head_events<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: Arc<DutiesService<T, E>>,
+    attester_poll_notify: Arc<Notify>,
+) {
+    let log = duties_service.context.log();
+
+    loop {
+        let beacon_node = match duties_service
+            .beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::No,
+                |beacon_node| async move { Ok::<_, ()>(beacon_node.clone()) },
+            )
+            .await
+        {
+            Ok(beacon_node) => beacon_node,
+            Err(e) => {
+                debug!(
+                    log,
+                    "No beacon node available for head events";
+                    "error" => %e,
+                );
+                sleep(duties_service.slot_clock.slot_duration()).await;
+                continue;
+            }
+        };
+
+        debug!(
+            log,
+            "Subscribing to head events";
+            "node" => %beacon_node,
+        );
+        let mut events = Box::pin(
+            beacon_node
+                .get_events_resubscribing::<E>(vec![EventTopic::Head], HEAD_EVENTS_RECONNECT_DELAY),
+        );
+        let mut dependent_roots = None;
+        let mut disconnections = 0;
+
+        while let Some(message) = events.next().await {
+            match message {
+                ResubscribingEvent::Event(EventKind::Head(head)) => {
+                    disconnections = 0;
+                    let new_roots = (
+                        head.previous_duty_dependent_root,
+                        head.current_duty_dependent_root,
+                    );
+                    if dependent_roots.map_or(false, |roots| roots != new_roots) {
+                        debug!(
+                            log,
+                            "Duty dependent roots changed";
+                            "slot" => head.slot,
+                            "block" => ?head.block,
+                        );
+                        attester_poll_notify.notify_one();
+                    }
+                    dependent_roots = Some(new_roots);
+                }
+                ResubscribingEvent::Event(_) => {}
+                ResubscribingEvent::HeadSlotGap {
+                    previous_slot,
+                    slot,
+                } => {
+                    debug!(
+                        log,
+                        "Gap in head events";
+                        "previous_slot" => previous_slot,
+                        "slot" => slot,
+                    );
+                }
+                ResubscribingEvent::Resubscribed => {
+                    debug!(
+                        log,
+                        "Resubscribed to head events";
+                        "node" => %beacon_node,
+                    );
+                    // Re-orgs may have happened whilst disconnected.
+                    attester_poll_notify.notify_one();
+                }
+                ResubscribingEvent::Disconnected(error) => {
+                    disconnections += 1;
+                    warn!(
+                        log,
+                        "Head event stream disconnected";
+                        "node" => %beacon_node,
+                        "error" => ?error,
+                    );
+                    if disconnections >= HEAD_EVENTS_MAX_DISCONNECTIONS {
+                        break;
+                    }
+                }
+                ResubscribingEvent::InvalidEvent(error) => {
+                    warn!(
+                        log,
+                        "Invalid head event";
+                        "node" => %beacon_node,
+                        "error" => ?error,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Detect attester duties which have not been refreshed for `stale_duties_epochs`, e.g. because all
+/// beacon nodes were down, and refresh all duties as soon as any beacon node becomes available
+/// rather than waiting for the next scheduled poll.
+///
+/// Attester duties are refreshed by waking the attester duties task, which also re-sends the
+/// attestation subnet subscriptions. Proposer duties are left to their own task since polling them
+/// also triggers block production for the current slot.
+async fn monitor_stale_duties<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: Arc<DutiesService<T, E>>,
+    attester_poll_notify: Arc<Notify>,
+) {
+    let log = duties_service.context.log();
+    let slot_duration = duties_service.slot_clock.slot_duration();
+    let started = duties_service.slot_clock.now_duration().unwrap_or_default();
+
+    loop {
+        if !duties_service.duties_are_stale(started) {
+            sleep(slot_duration).await;
+            continue;
+        }
+
+        warn!(
+            log,
+            "Duties are stale";
+            "last_refresh" => ?duties_service.refresh_times.read().attesters,
+            "stale_duties_epochs" => duties_service.stale_duties_epochs,
+        );
+
+        // Check the beacon nodes more often than the fallback updater so that duties are refreshed
+        // as soon as one of them is back.
+        while duties_service.beacon_nodes.num_available().await == 0 {
+            sleep(STALE_DUTIES_RECHECK_INTERVAL).await;
+            duties_service.beacon_nodes.update_all_candidates().await;
+        }
+
+        info!(log, "Beacon node available, refreshing stale duties");
+        attester_poll_notify.notify_one();
+        if let Err(e) = poll_sync_committee_duties(&duties_service).await {
+            error!(
+                log,
+                "Failed to poll sync committee duties";
+                "error" => ?e
+            );
+        }
+
+        // Give the attester duties task time to complete before checking again.
+        sleep(slot_duration).await;
+    }
+}
+
 /// Iterate through all the voting pubkeys in the `ValidatorStore` and attempt to learn any unknown
 /// validator indices.
 async fn poll_validator_indices<T: SlotClock + 'static, E: EthSpec>(
@@ -438,8 +691,8 @@ async fn poll_validator_indices<T: SlotClock + 'static, E: EthSpec>(
                 .get_fee_recipient(&pubkey)
                 .map(|fr| fr.to_string())
                 .unwrap_or_else(|| {
-                    "Fee recipient for validator not set in validator_definitions.yml \
-                    or provided with the `--suggested-fee-recipient` flag"
+                    "Fee recipient for validator not set in validator_definitions.yml, \
+                    the `--suggested-fee-recipient-file` or the `--suggested-fee-recipient` flag"
                         .to_string()
                 });
             match download_result {
@@ -529,7 +782,7 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
     };
 
     // Download the duties and update the duties for the current epoch.
-    if let Err(e) = poll_beacon_attesters_for_epoch(
+    match poll_beacon_attesters_for_epoch(
         duties_service,
         current_epoch,
         &local_indices,
@@ -537,13 +790,17 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
     )
     .await
     {
-        error!(
+        Ok(()) => {
+            duties_service.refresh_times.write().attesters =
+                duties_service.slot_clock.now_duration()
+        }
+        Err(e) => error!(
             log,
             "Failed to download attester duties";
             "current_epoch" => current_epoch,
             "request_epoch" => current_epoch,
             "err" => ?e,
-        )
+        ),
     }
 
     update_per_validator_duty_metrics::<T, E>(duties_service, current_epoch, current_slot);
@@ -645,6 +902,11 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
         .for_each(|(_, map)| {
             map.retain(|&epoch, _| epoch + HISTORICAL_DUTIES_EPOCHS >= current_epoch)
         });
+    duties_service
+        .duty_sources
+        .write()
+        .attesters
+        .retain(|&epoch, _| epoch + HISTORICAL_DUTIES_EPOCHS >= current_epoch);
 
     Ok(())
 }
@@ -861,9 +1123,18 @@ async fn post_validator_duties_attester<T: SlotClock + 'static, E: EthSpec>(
                 beacon_node
                     .post_validator_duties_attester(epoch, validator_indices)
                     .await
+                    .map(|response| (response, beacon_node.to_string()))
             },
         )
         .await
+        .map(|(response, beacon_node)| {
+            duties_service
+                .duty_sources
+                .write()
+                .attesters
+                .insert(epoch, beacon_node);
+            response
+        })
         .map_err(|e| Error::FailedToDownloadAttesters(e.to_string()))
 }
 
@@ -1082,13 +1353,21 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
                     beacon_node
                         .get_validator_duties_proposer(current_epoch)
                         .await
+                        .map(|response| (response, beacon_node.to_string()))
                 },
             )
             .await;
 
         match download_result {
-            Ok(response) => {
+            Ok((response, beacon_node)) => {
                 let dependent_root = response.dependent_root;
+                duties_service
+                    .duty_sources
+                    .write()
+                    .proposers
+                    .insert(current_epoch, beacon_node);
+                duties_service.refresh_times.write().proposers =
+                    duties_service.slot_clock.now_duration();
 
                 let relevant_duties = response
                     .data
@@ -1165,6 +1444,11 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
         .proposers
         .write()
         .retain(|&epoch, _| epoch + HISTORICAL_DUTIES_EPOCHS >= current_epoch);
+    duties_service
+        .duty_sources
+        .write()
+        .proposers
+        .retain(|&epoch, _| epoch + HISTORICAL_DUTIES_EPOCHS >= current_epoch);
 
     Ok(())
 }
@@ -1177,6 +1461,8 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
     validator_store: &ValidatorStore<T, E>,
     log: &Logger,
 ) {
+    validator_store.prune_proposal_overrides(current_slot);
+
     let non_doppelganger_proposers = block_proposers
         .iter()
         .filter(|pubkey| validator_store.doppelganger_protection_allows_signing(**pubkey))
@@ -1184,10 +1470,19 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
         .collect::<Vec<_>>();
 
     if !non_doppelganger_proposers.is_empty() {
+        let overrides = non_doppelganger_proposers
+            .iter()
+            .filter_map(|pubkey| {
+                let overrides = validator_store.proposal_overrides(pubkey, current_slot)?;
+                Some((*pubkey, overrides))
+            })
+            .collect();
+
         if let Err(e) = block_service_tx
             .send(BlockServiceNotification {
                 slot: current_slot,
                 block_proposers: non_doppelganger_proposers,
+                overrides,
             })
             .await
         {
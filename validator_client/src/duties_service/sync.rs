@@ -47,6 +47,8 @@ pub struct CommitteeDuties {
     /// validator index was not part of the set of local validators when the duties were fetched.
     /// This allows us to track changes to the set of local validators.
     validators: RwLock<HashMap<u64, Option<ValidatorDuties>>>,
+    /// The epoch at which the duties were most recently fetched from a beacon node.
+    fetched_epoch: RwLock<Option<Epoch>>,
 }
 
 /// Duties for a single validator.
@@ -104,6 +106,14 @@ impl SyncDutiesMap {
             })
     }
 
+    /// Returns the epoch at which the duties for `committee_period` were most recently fetched.
+    fn fetched_epoch(&self, committee_period: u64) -> Option<Epoch> {
+        self.committees
+            .read()
+            .get(&committee_period)
+            .and_then(|committee_duties| *committee_duties.fetched_epoch.read())
+    }
+
     /// Prepare for pre-computation of selection proofs for `committee_period`.
     ///
     /// Return the epoch up to which proofs should be pre-computed, as well as a vec of
@@ -229,6 +239,20 @@ impl SyncDutiesMap {
         })
     }
 
+    /// Returns the sync committee duties of the validator with `validator_index` for each known
+    /// sync committee period.
+    pub fn validator_duties(&self, validator_index: u64) -> Vec<(u64, SyncDuty)> {
+        self.committees
+            .read()
+            .iter()
+            .filter_map(|(period, committee_duties)| {
+                let validators = committee_duties.validators.read();
+                let duties = validators.get(&validator_index)?.as_ref()?;
+                Some((*period, duties.duty.clone()))
+            })
+            .collect()
+    }
+
     /// Prune duties for past sync committee periods from the map.
     fn prune(&self, current_sync_committee_period: u64) {
         self.committees
@@ -260,11 +284,53 @@ impl ValidatorDuties {
     }
 }
 
-/// Number of epochs to wait from the start of the period before actually fetching duties.
-fn epoch_offset(spec: &ChainSpec) -> u64 {
+/// Number of epochs to wait from the start of the period before fetching duties for the next
+/// period.
+///
+/// The next sync committee is fixed by the state at the start of the current period, so its
+/// membership can be computed as soon as that state is unlikely to be re-orged. In the usual case
+/// it is finalized two epochs into the period.
+pub const NEXT_PERIOD_DUTIES_DELAY_EPOCHS: u64 = 2;
+
+/// Number of epochs from the start of the period after which the duties for the next period are
+/// fetched once more, in case they were fetched before a re-org of the start of the period.
+fn next_period_duties_recheck_offset(spec: &ChainSpec) -> u64 {
     spec.epochs_per_sync_committee_period.as_u64() / 2
 }
 
+/// Returns `true` if the duties for the period after the period of `current_epoch` should be
+/// fetched.
+///
+/// They are fetched as soon as `NEXT_PERIOD_DUTIES_DELAY_EPOCHS` have passed (or whenever some of
+/// them are unknown after that), and again once `next_period_duties_recheck_offset` epochs have
+/// passed unless they were last fetched after that point.
+fn next_period_duties_due(
+    current_epoch: Epoch,
+    all_duties_known: bool,
+    fetched_epoch: Option<Epoch>,
+    spec: &ChainSpec,
+) -> bool {
+    let epochs_per_period = spec.epochs_per_sync_committee_period.as_u64();
+    let period_start_epoch = current_epoch - current_epoch.as_u64() % epochs_per_period;
+
+    if current_epoch < period_start_epoch + NEXT_PERIOD_DUTIES_DELAY_EPOCHS {
+        return false;
+    }
+    if !all_duties_known {
+        return true;
+    }
+
+    let recheck_epoch = period_start_epoch + next_period_duties_recheck_offset(spec);
+    current_epoch >= recheck_epoch && fetched_epoch.map_or(true, |epoch| epoch < recheck_epoch)
+}
+
+/// Returns the number of epochs between `current_epoch` and the start of `sync_committee_period`.
+fn epochs_until_period(sync_committee_period: u64, current_epoch: Epoch, spec: &ChainSpec) -> u64 {
+    first_epoch_of_period(sync_committee_period, spec)
+        .as_u64()
+        .saturating_sub(current_epoch.as_u64())
+}
+
 fn first_epoch_of_period(sync_committee_period: u64, spec: &ChainSpec) -> Epoch {
     spec.epochs_per_sync_committee_period * sync_committee_period
 }
@@ -327,6 +393,11 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
 
         // Prune previous duties (we avoid doing this too often as it locks the whole map).
         sync_duties.prune(current_sync_committee_period);
+        duties_service
+            .duty_sources
+            .write()
+            .sync_committees
+            .retain(|period, _| *period >= current_sync_committee_period);
     }
 
     // Pre-compute aggregator selection proofs for the current period.
@@ -350,11 +421,16 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         );
     }
 
-    // If we're past the point in the current period where we should determine duties for the next
-    // period and they are not yet known, then poll.
-    if current_epoch.as_u64() % spec.epochs_per_sync_committee_period.as_u64() >= epoch_offset(spec)
-        && !sync_duties.all_duties_known(next_sync_committee_period, &local_indices)
-    {
+    // If we're past the point in the current period where we can determine duties for the next
+    // period and they are not yet known (or should be checked again), then poll.
+    let next_period_duties_known =
+        sync_duties.all_duties_known(next_sync_committee_period, &local_indices);
+    if next_period_duties_due(
+        current_epoch,
+        next_period_duties_known,
+        sync_duties.fetched_epoch(next_sync_committee_period),
+        spec,
+    ) {
         poll_sync_committee_duties_for_period(
             duties_service,
             &local_indices,
@@ -362,9 +438,24 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         )
         .await?;
 
+        // Record how far in advance of the period its duties became known, ignoring re-checks.
+        if !next_period_duties_known
+            && sync_duties.all_duties_known(next_sync_committee_period, &local_indices)
+        {
+            metrics::set_gauge(
+                &metrics::SYNC_COMMITTEE_NEXT_PERIOD_DUTIES_LOOKAHEAD,
+                epochs_until_period(next_sync_committee_period, current_epoch, spec) as i64,
+            );
+        }
+
         // Prune (this is the main code path for updating duties, so we should almost always hit
         // this prune).
         sync_duties.prune(current_sync_committee_period);
+        duties_service
+            .duty_sources
+            .write()
+            .sync_committees
+            .retain(|period, _| *period >= current_sync_committee_period);
     }
 
     // Pre-compute aggregator selection proofs for the next period.
@@ -392,6 +483,8 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         }
     }
 
+    duties_service.refresh_times.write().sync_committees = duties_service.slot_clock.now_duration();
+
     Ok(())
 }
 
@@ -402,6 +495,11 @@ pub async fn poll_sync_committee_duties_for_period<T: SlotClock + 'static, E: Et
 ) -> Result<(), Error> {
     let spec = &duties_service.spec;
     let log = duties_service.context.log();
+    let current_epoch = duties_service
+        .slot_clock
+        .now()
+        .ok_or(Error::UnableToReadSlotClock)?
+        .epoch(E::slots_per_epoch());
 
     // no local validators don't need to poll for sync committee
     if local_indices.is_empty() {
@@ -435,12 +533,20 @@ pub async fn poll_sync_committee_duties_for_period<T: SlotClock + 'static, E: Et
                 beacon_node
                     .post_validator_duties_sync(period_start_epoch, local_indices)
                     .await
+                    .map(|response| (response, beacon_node.to_string()))
             },
         )
         .await;
 
     let duties = match duties_response {
-        Ok(res) => res.data,
+        Ok((res, beacon_node)) => {
+            duties_service
+                .duty_sources
+                .write()
+                .sync_committees
+                .insert(sync_committee_period, beacon_node);
+            res.data
+        }
         Err(e) => {
             warn!(
                 log,
@@ -459,7 +565,13 @@ pub async fn poll_sync_committee_duties_for_period<T: SlotClock + 'static, E: Et
         .sync_duties
         .get_or_create_committee_duties(sync_committee_period, local_indices);
 
+    *committee_duties.fetched_epoch.write() = Some(current_epoch);
+
     let mut validator_writer = committee_duties.validators.write();
+    let members = duties
+        .iter()
+        .map(|duty| duty.validator_index)
+        .collect::<HashSet<_>>();
     for duty in duties {
         let validator_duties = validator_writer
             .get_mut(&duty.validator_index)
@@ -490,6 +602,22 @@ pub async fn poll_sync_committee_duties_for_period<T: SlotClock + 'static, E: Et
         }
     }
 
+    // Duties which are fetched again may show that a validator is no longer a member.
+    for validator_index in local_indices {
+        if let Some(validator_duties) = validator_writer.get_mut(validator_index) {
+            if validator_duties.is_some() && !members.contains(validator_index) {
+                warn!(
+                    log,
+                    "Validator removed from sync committee";
+                    "validator_index" => validator_index,
+                    "sync_committee_period" => sync_committee_period,
+                    "message" => "this could be due to a really long re-org, or a bug"
+                );
+                *validator_duties = None;
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -643,3 +771,100 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    fn spec() -> ChainSpec {
+        MainnetEthSpec::default_spec()
+    }
+
+    #[test]
+    fn next_period_duties_are_fetched_after_delay() {
+        let spec = spec();
+        let period_start = first_epoch_of_period(3, &spec);
+
+        for offset in 0..NEXT_PERIOD_DUTIES_DELAY_EPOCHS {
+            assert!(!next_period_duties_due(
+                period_start + offset,
+                false,
+                None,
+                &spec
+            ));
+        }
+        assert!(next_period_duties_due(
+            period_start + NEXT_PERIOD_DUTIES_DELAY_EPOCHS,
+            false,
+            None,
+            &spec
+        ));
+        // Duties which are missing later in the period are always fetched.
+        assert!(next_period_duties_due(
+            last_epoch_of_period(3, &spec),
+            false,
+            Some(last_epoch_of_period(3, &spec)),
+            &spec
+        ));
+    }
+
+    #[test]
+    fn next_period_duties_are_fetched_again_at_recheck_offset() {
+        let spec = spec();
+        let period_start = first_epoch_of_period(3, &spec);
+        let early_fetch = Some(period_start + NEXT_PERIOD_DUTIES_DELAY_EPOCHS);
+        let recheck_epoch = period_start + next_period_duties_recheck_offset(&spec);
+
+        assert!(!next_period_duties_due(
+            recheck_epoch - 1,
+            true,
+            early_fetch,
+            &spec
+        ));
+        assert!(next_period_duties_due(
+            recheck_epoch,
+            true,
+            early_fetch,
+            &spec
+        ));
+        assert!(next_period_duties_due(
+            last_epoch_of_period(3, &spec),
+            true,
+            early_fetch,
+            &spec
+        ));
+
+        // Duties fetched at or after the recheck offset are not fetched again.
+        assert!(!next_period_duties_due(
+            recheck_epoch + 1,
+            true,
+            Some(recheck_epoch),
+            &spec
+        ));
+        // Duties fetched in a previous period are stale.
+        assert!(next_period_duties_due(
+            recheck_epoch,
+            true,
+            Some(recheck_epoch - spec.epochs_per_sync_committee_period.as_u64()),
+            &spec
+        ));
+    }
+
+    #[test]
+    fn next_period_duties_lookahead() {
+        let spec = spec();
+        let epochs_per_period = spec.epochs_per_sync_committee_period.as_u64();
+        let period_start = first_epoch_of_period(3, &spec);
+
+        assert_eq!(
+            epochs_until_period(4, period_start + NEXT_PERIOD_DUTIES_DELAY_EPOCHS, &spec),
+            epochs_per_period - NEXT_PERIOD_DUTIES_DELAY_EPOCHS
+        );
+        assert_eq!(
+            epochs_until_period(4, last_epoch_of_period(3, &spec), &spec),
+            1
+        );
+        assert_eq!(epochs_until_period(4, Epoch::new(u64::MAX), &spec), 0);
+    }
+}
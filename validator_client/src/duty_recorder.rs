@@ -0,0 +1,328 @@
+//! Records the inputs of recent duties, as received from the beacon node, so that they can be
+//! replayed via the HTTP API.
+//!
+//! Replaying a duty re-runs the signing pipeline on exactly the same inputs and optionally
+//! publishes the signed objects to a beacon node supplied by the caller, usually a mock. This
+//! allows the cause of a failed duty to be investigated after the fact.
+use crate::duties_service::DutyAndProof;
+use crate::validator_store::ValidatorStore;
+use eth2::lighthouse_vc::types::{RecordedDuty, RecordedDutyKind, ReplayedDuty, ReplayedSignature};
+use eth2::types::AttesterData;
+use eth2::BeaconNodeHttpClient;
+use parking_lot::Mutex;
+use slot_clock::SlotClock;
+use ssz::Encode;
+use std::collections::VecDeque;
+use types::{
+    AbstractExecPayload, AggregateSignature, Attestation, AttestationData, BeaconBlock, BitList,
+    BlindedPayload, BlockType, ChainSpec, Epoch, EthSpec, FullPayload, PublicKeyBytes,
+    SignatureBytes, Slot,
+};
+
+/// The inputs of a single duty.
+#[derive(Debug, Clone)]
+enum DutyInputs {
+    Attestation {
+        data: AttestationData,
+        current_epoch: Epoch,
+        duties: Vec<AttesterData>,
+    },
+    Block {
+        validator_pubkey: PublicKeyBytes,
+        current_slot: Slot,
+        blinded: bool,
+        /// The SSZ bytes of the unsigned block, which allow full and blinded blocks to be stored
+        /// alike.
+        block_bytes: Vec<u8>,
+    },
+}
+
+/// The inputs of a duty recorded by the `DutyRecorder`.
+#[derive(Debug, Clone)]
+pub struct RecordedDutyInputs {
+    id: u64,
+    slot: Slot,
+    inputs: DutyInputs,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: u64,
+    duties: VecDeque<RecordedDutyInputs>,
+}
+
+/// Keeps the inputs of the `capacity` most recent duties.
+pub struct DutyRecorder {
+    capacity: usize,
+    inner: Mutex<Inner>,
+}
+
+impl DutyRecorder {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: <_>::default(),
+        }
+    }
+
+    /// Records the attestation data downloaded for the given `duties`.
+    pub fn record_attestation(
+        &self,
+        data: &AttestationData,
+        current_epoch: Epoch,
+        duties: &[DutyAndProof],
+    ) {
+        self.record(
+            data.slot,
+            DutyInputs::Attestation {
+                data: data.clone(),
+                current_epoch,
+                duties: duties
+                    .iter()
+                    .map(|duty_and_proof| duty_and_proof.duty.clone())
+                    .collect(),
+            },
+        )
+    }
+
+    /// Records the unsigned block downloaded for `validator_pubkey`.
+    pub fn record_block<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        current_slot: Slot,
+        block: &BeaconBlock<E, Payload>,
+    ) {
+        self.record(
+            block.slot(),
+            DutyInputs::Block {
+                validator_pubkey,
+                current_slot,
+                blinded: Payload::block_type() == BlockType::Blinded,
+                block_bytes: block.as_ssz_bytes(),
+            },
+        )
+    }
+
+    fn record(&self, slot: Slot, inputs: DutyInputs) {
+        let mut inner = self.inner.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner
+            .duties
+            .push_back(RecordedDutyInputs { id, slot, inputs });
+        while inner.duties.len() > self.capacity {
+            inner.duties.pop_front();
+        }
+    }
+
+    /// Returns a summary of each recorded duty, oldest first.
+    pub fn recorded_duties(&self) -> Vec<RecordedDuty> {
+        self.inner
+            .lock()
+            .duties
+            .iter()
+            .map(RecordedDutyInputs::summary)
+            .collect()
+    }
+
+    /// Returns the inputs of the duty with the given `id`, if it is still recorded.
+    pub fn get(&self, id: u64) -> Option<RecordedDutyInputs> {
+        self.inner
+            .lock()
+            .duties
+            .iter()
+            .find(|duty| duty.id == id)
+            .cloned()
+    }
+}
+
+impl RecordedDutyInputs {
+    pub fn summary(&self) -> RecordedDuty {
+        let (kind, validators) = match &self.inputs {
+            DutyInputs::Attestation { duties, .. } => (
+                RecordedDutyKind::Attestation,
+                duties.iter().map(|duty| duty.pubkey).collect(),
+            ),
+            DutyInputs::Block {
+                validator_pubkey, ..
+            } => (RecordedDutyKind::Block, vec![*validator_pubkey]),
+        };
+        RecordedDuty {
+            id: self.id,
+            kind,
+            slot: self.slot,
+            validators,
+        }
+    }
+
+    /// Signs the recorded inputs again and, if `beacon_node` is supplied, publishes the signed
+    /// objects to it.
+    ///
+    /// Signing is subject to slashing protection as usual. Re-signing identical data is
+    /// permitted, so replaying a duty which was signed before is safe.
+    pub async fn replay<T: SlotClock + 'static, E: EthSpec>(
+        &self,
+        validator_store: &ValidatorStore<T, E>,
+        beacon_node: Option<&BeaconNodeHttpClient>,
+        spec: &ChainSpec,
+    ) -> Result<ReplayedDuty, String> {
+        let (signatures, publication) = match &self.inputs {
+            DutyInputs::Attestation {
+                data,
+                current_epoch,
+                duties,
+            } => {
+                replay_attestation(validator_store, beacon_node, data, *current_epoch, duties)
+                    .await?
+            }
+            DutyInputs::Block {
+                validator_pubkey,
+                current_slot,
+                blinded: false,
+                block_bytes,
+            } => {
+                replay_block::<_, _, FullPayload<E>>(
+                    validator_store,
+                    beacon_node,
+                    *validator_pubkey,
+                    *current_slot,
+                    block_bytes,
+                    spec,
+                )
+                .await?
+            }
+            DutyInputs::Block {
+                validator_pubkey,
+                current_slot,
+                blinded: true,
+                block_bytes,
+            } => {
+                replay_block::<_, _, BlindedPayload<E>>(
+                    validator_store,
+                    beacon_node,
+                    *validator_pubkey,
+                    *current_slot,
+                    block_bytes,
+                    spec,
+                )
+                .await?
+            }
+        };
+
+        let summary = self.summary();
+        Ok(ReplayedDuty {
+            id: summary.id,
+            kind: summary.kind,
+            slot: summary.slot,
+            signatures,
+            beacon_node: beacon_node.map(ToString::to_string),
+            publication_error: publication.and_then(Result::err),
+        })
+    }
+}
+
+/// The signatures produced by a replay and the result of publishing the signed objects, if they
+/// were published.
+type ReplayOutcome = (Vec<ReplayedSignature>, Option<Result<(), String>>);
+
+async fn replay_attestation<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_node: Option<&BeaconNodeHttpClient>,
+    data: &AttestationData,
+    current_epoch: Epoch,
+    duties: &[AttesterData],
+) -> Result<ReplayOutcome, String> {
+    let mut signatures = Vec::with_capacity(duties.len());
+    let mut attestations = Vec::with_capacity(duties.len());
+    for duty in duties {
+        let mut attestation = Attestation {
+            aggregation_bits: BitList::with_capacity(duty.committee_length as usize)
+                .map_err(|e| format!("Invalid committee length: {:?}", e))?,
+            data: data.clone(),
+            signature: AggregateSignature::infinity(),
+        };
+
+        match validator_store
+            .sign_attestation(
+                duty.pubkey,
+                duty.validator_committee_index as usize,
+                &mut attestation,
+                current_epoch,
+            )
+            .await
+        {
+            Ok(()) => {
+                let signature = SignatureBytes::deserialize(&attestation.signature.serialize())
+                    .map_err(|e| format!("Invalid attestation signature: {:?}", e))?;
+                signatures.push(signed(duty.pubkey, signature));
+                attestations.push(attestation);
+            }
+            Err(e) => signatures.push(failed(duty.pubkey, format!("{:?}", e))),
+        }
+    }
+
+    let publication = match beacon_node {
+        Some(beacon_node) if !attestations.is_empty() => Some(
+            beacon_node
+                .post_beacon_pool_attestations(&attestations)
+                .await
+                .map_err(|e| e.to_string()),
+        ),
+        _ => None,
+    };
+
+    Ok((signatures, publication))
+}
+
+async fn replay_block<T: SlotClock + 'static, E: EthSpec, Payload: AbstractExecPayload<E>>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_node: Option<&BeaconNodeHttpClient>,
+    validator_pubkey: PublicKeyBytes,
+    current_slot: Slot,
+    block_bytes: &[u8],
+    spec: &ChainSpec,
+) -> Result<ReplayOutcome, String> {
+    let block = BeaconBlock::<E, Payload>::from_ssz_bytes(block_bytes, spec)
+        .map_err(|e| format!("Unable to decode recorded block: {:?}", e))?;
+
+    let signed_block = match validator_store
+        .sign_block(validator_pubkey, block, current_slot)
+        .await
+    {
+        Ok(signed_block) => signed_block,
+        Err(e) => return Ok((vec![failed(validator_pubkey, format!("{:?}", e))], None)),
+    };
+    let signatures = vec![signed(
+        validator_pubkey,
+        signed_block.signature().clone().into(),
+    )];
+
+    let publication = match beacon_node {
+        Some(beacon_node) => {
+            let result = match Payload::block_type() {
+                BlockType::Full => beacon_node.post_beacon_blocks(&signed_block).await,
+                BlockType::Blinded => beacon_node.post_beacon_blinded_blocks(&signed_block).await,
+            };
+            Some(result.map_err(|e| e.to_string()))
+        }
+        None => None,
+    };
+
+    Ok((signatures, publication))
+}
+
+fn signed(pubkey: PublicKeyBytes, signature: SignatureBytes) -> ReplayedSignature {
+    ReplayedSignature {
+        pubkey,
+        signature: Some(signature),
+        error: None,
+    }
+}
+
+fn failed(pubkey: PublicKeyBytes, error: String) -> ReplayedSignature {
+    ReplayedSignature {
+        pubkey,
+        signature: None,
+        error: Some(error),
+    }
+}
@@ -0,0 +1,288 @@
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{prelude::*, BufReader};
+use std::path::PathBuf;
+use std::str::FromStr;
+use tiny_keccak::{Hasher, Keccak};
+
+use bls::PublicKeyBytes;
+use types::Address;
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum Error {
+    InvalidFile(std::io::Error),
+    InvalidLine(String),
+    InvalidPublicKey(String),
+    InvalidFeeRecipient(String),
+    InvalidChecksum(String),
+}
+
+/// Struct to load validator fee recipients from file.
+/// The fee recipient file is expected to have the following structure
+///
+/// default: 0x00000000219ab540356cbb839cbe05303d7705fa
+/// public_key1: fee_recipient1
+/// public_key2: fee_recipient2
+/// ...
+///
+/// Fee recipients in mixed case must be valid EIP-55 checksummed addresses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeRecipientFile {
+    fee_recipient_path: PathBuf,
+    fee_recipients: HashMap<PublicKeyBytes, Address>,
+    default: Option<Address>,
+}
+
+impl FeeRecipientFile {
+    pub fn new(fee_recipient_path: PathBuf) -> Self {
+        Self {
+            fee_recipient_path,
+            fee_recipients: HashMap::new(),
+            default: None,
+        }
+    }
+
+    /// Returns the fee recipient for the given public key if present, else returns the default
+    /// fee recipient of the file.
+    pub fn get_fee_recipient(&self, public_key: &PublicKeyBytes) -> Option<Address> {
+        self.fee_recipients
+            .get(public_key)
+            .copied()
+            .or(self.default)
+    }
+
+    /// Reads from a fee recipient file with the specified format and replaces the default value
+    /// and the hashmap with its contents.
+    ///
+    /// Returns an error if the file does not exist, or if the format is invalid, in which case
+    /// the previously loaded fee recipients are kept.
+    pub fn read_fee_recipient_file(&mut self) -> Result<(), Error> {
+        let file = File::open(self.fee_recipient_path.as_path()).map_err(Error::InvalidFile)?;
+        let reader = BufReader::new(file);
+
+        let mut fee_recipients = HashMap::new();
+        let mut default = None;
+        for line in reader.lines() {
+            let line = line.map_err(|e| Error::InvalidLine(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (pk_opt, fee_recipient) = read_line(&line)?;
+            match pk_opt {
+                Some(pk) => {
+                    fee_recipients.insert(pk, fee_recipient);
+                }
+                None => default = Some(fee_recipient),
+            }
+        }
+
+        self.fee_recipients = fee_recipients;
+        self.default = default;
+        Ok(())
+    }
+}
+
+/// Parses a line from the fee recipient file.
+///
+/// `Ok((None, fee_recipient))` represents the fee recipient for the default key.
+/// `Ok((Some(pk), fee_recipient))` represents the fee recipient for the public key `pk`.
+/// Returns an error if the line is in the wrong format or does not contain a valid public key or
+/// fee recipient.
+fn read_line(line: &str) -> Result<(Option<PublicKeyBytes>, Address), Error> {
+    if let Some(i) = line.find(':') {
+        let (key, value) = line.split_at(i);
+        // Note: `value.len() >=1` so `value[1..]` is safe
+        let fee_recipient = parse_fee_recipient(value[1..].trim())?;
+        if key.trim() == "default" {
+            Ok((None, fee_recipient))
+        } else {
+            let pk = PublicKeyBytes::from_str(key.trim()).map_err(Error::InvalidPublicKey)?;
+            Ok((Some(pk), fee_recipient))
+        }
+    } else {
+        Err(Error::InvalidLine(format!("Missing delimiter: {}", line)))
+    }
+}
+
+/// Parses a `0x`-prefixed address, which must have a valid EIP-55 checksum if it is not
+/// entirely lower or upper case.
+fn parse_fee_recipient(value: &str) -> Result<Address, Error> {
+    let hex_str = value
+        .strip_prefix("0x")
+        .ok_or_else(|| Error::InvalidFeeRecipient(format!("Missing 0x prefix: {}", value)))?;
+    if hex_str.len() != 40 {
+        return Err(Error::InvalidFeeRecipient(format!(
+            "Expected 20 bytes: {}",
+            value
+        )));
+    }
+    let fee_recipient = Address::from_str(hex_str)
+        .map_err(|e| Error::InvalidFeeRecipient(format!("{}: {:?}", value, e)))?;
+
+    let is_mixed_case = hex_str.chars().any(|c| c.is_ascii_lowercase())
+        && hex_str.chars().any(|c| c.is_ascii_uppercase());
+    if is_mixed_case && to_checksum_address(&fee_recipient) != value {
+        return Err(Error::InvalidChecksum(value.to_string()));
+    }
+
+    Ok(fee_recipient)
+}
+
+/// Returns the EIP-55 checksummed representation of `address`.
+pub fn to_checksum_address(address: &Address) -> String {
+    let hex_address = hex::encode(address.as_bytes());
+
+    let mut hasher = Keccak::v256();
+    hasher.update(hex_address.as_bytes());
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+
+    let checksummed = hex_address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+
+    format!("0x{}", checksummed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::Keypair;
+    use std::io::LineWriter;
+    use tempfile::TempDir;
+
+    const DEFAULT_FEE_RECIPIENT: &str = "0x00000000219ab540356cbb839cbe05303d7705fa";
+    const CUSTOM_FEE_RECIPIENT1: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    const CUSTOM_FEE_RECIPIENT2: &str = "0xFB6916095CA1DF60BB79CE92CE3EA74C37C5D359";
+    const PK1: &str = "0x800012708dc03f611751aad7a43a082142832b5c1aceed07ff9b543cf836381861352aa923c70eeb02018b638aa306aa";
+    const PK2: &str = "0x80001866ce324de7d80ec73be15e2d064dcf121adf1b34a0d679f2b9ecbab40ce021e03bb877e1a2fe72eaaf475e6e21";
+
+    // Create a fee recipient file with the given lines and return a path to the file.
+    fn create_fee_recipient_file(lines: &[String]) -> PathBuf {
+        let temp = TempDir::new().unwrap();
+        let file_name = temp.into_path().join("fee_recipients.txt");
+        write_lines(&file_name, lines);
+        file_name
+    }
+
+    fn write_lines(path: &PathBuf, lines: &[String]) {
+        let file = File::create(path).unwrap();
+        let mut fee_recipient_file = LineWriter::new(file);
+        for line in lines {
+            fee_recipient_file
+                .write_all(format!("{}\n", line).as_bytes())
+                .unwrap();
+        }
+        fee_recipient_file.flush().unwrap();
+    }
+
+    fn address(s: &str) -> Address {
+        Address::from_str(&s[2..]).unwrap()
+    }
+
+    #[test]
+    fn test_load_fee_recipients() {
+        let path = create_fee_recipient_file(&[
+            format!("default: {}", DEFAULT_FEE_RECIPIENT),
+            format!("{}: {}", PK1, CUSTOM_FEE_RECIPIENT1),
+            "".to_string(),
+            format!("{}:{}", PK2, CUSTOM_FEE_RECIPIENT2),
+        ]);
+        let mut file = FeeRecipientFile::new(path);
+        file.read_fee_recipient_file().unwrap();
+
+        let pk1 = PublicKeyBytes::from_str(PK1).unwrap();
+        let pk2 = PublicKeyBytes::from_str(PK2).unwrap();
+        assert_eq!(
+            file.get_fee_recipient(&pk1),
+            Some(address(CUSTOM_FEE_RECIPIENT1))
+        );
+        assert_eq!(
+            file.get_fee_recipient(&pk2),
+            Some(address(CUSTOM_FEE_RECIPIENT2))
+        );
+
+        // Random pk should return the default fee recipient
+        let random_pk = Keypair::random().pk.compress();
+        assert_eq!(
+            file.get_fee_recipient(&random_pk),
+            Some(address(DEFAULT_FEE_RECIPIENT))
+        );
+    }
+
+    #[test]
+    fn test_reload_fee_recipients() {
+        let path = create_fee_recipient_file(&[format!("{}: {}", PK1, CUSTOM_FEE_RECIPIENT1)]);
+        let mut file = FeeRecipientFile::new(path.clone());
+        file.read_fee_recipient_file().unwrap();
+
+        let pk1 = PublicKeyBytes::from_str(PK1).unwrap();
+        let pk2 = PublicKeyBytes::from_str(PK2).unwrap();
+        assert_eq!(file.get_fee_recipient(&pk2), None);
+
+        // Entries which are removed from the file are forgotten.
+        write_lines(&path, &[format!("{}: {}", PK2, CUSTOM_FEE_RECIPIENT2)]);
+        file.read_fee_recipient_file().unwrap();
+        assert_eq!(file.get_fee_recipient(&pk1), None);
+        assert_eq!(
+            file.get_fee_recipient(&pk2),
+            Some(address(CUSTOM_FEE_RECIPIENT2))
+        );
+
+        // An invalid file leaves the previous entries in place.
+        write_lines(&path, &[format!("{}: not-an-address", PK1)]);
+        assert!(matches!(
+            file.read_fee_recipient_file(),
+            Err(Error::InvalidFeeRecipient(_))
+        ));
+        assert_eq!(
+            file.get_fee_recipient(&pk2),
+            Some(address(CUSTOM_FEE_RECIPIENT2))
+        );
+    }
+
+    #[test]
+    fn test_checksum_validation() {
+        for checksummed in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let fee_recipient = parse_fee_recipient(checksummed).unwrap();
+            assert_eq!(to_checksum_address(&fee_recipient), checksummed);
+            assert_eq!(
+                parse_fee_recipient(&checksummed.to_lowercase()).unwrap(),
+                fee_recipient
+            );
+        }
+
+        assert!(matches!(
+            parse_fee_recipient("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+            Err(Error::InvalidChecksum(_))
+        ));
+        assert!(matches!(
+            parse_fee_recipient("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+            Err(Error::InvalidFeeRecipient(_))
+        ));
+        assert!(matches!(
+            parse_fee_recipient("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea"),
+            Err(Error::InvalidFeeRecipient(_))
+        ));
+    }
+}
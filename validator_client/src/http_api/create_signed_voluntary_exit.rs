@@ -1,7 +1,8 @@
+use crate::beacon_node_fallback::{BeaconNodeFallback, OfflineOnFailure, RequireSynced};
 use crate::validator_store::ValidatorStore;
 use bls::{PublicKey, PublicKeyBytes};
 use eth2::types::GenericResponse;
-use slog::{info, Logger};
+use slog::{error, info, Logger};
 use slot_clock::SlotClock;
 use std::sync::Arc;
 use types::{Epoch, EthSpec, SignedVoluntaryExit, VoluntaryExit};
@@ -10,6 +11,7 @@ pub async fn create_signed_voluntary_exit<T: 'static + SlotClock + Clone, E: Eth
     pubkey: PublicKey,
     maybe_epoch: Option<Epoch>,
     validator_store: Arc<ValidatorStore<T, E>>,
+    broadcast_to: Option<Arc<BeaconNodeFallback<T, E>>>,
     slot_clock: T,
     log: Logger,
 ) -> Result<GenericResponse<SignedVoluntaryExit>, warp::Rejection> {
@@ -39,6 +41,15 @@ pub async fn create_signed_voluntary_exit<T: 'static + SlotClock + Clone, E: Eth
             ))
         })?;
 
+    // Exits are not slashable, but a key which is still being checked for doppelgangers may be
+    // in use elsewhere and should not be acted on yet.
+    if !validator_store.doppelganger_protection_allows_signing(pubkey_bytes) {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "{} is still being checked by doppelganger protection",
+            pubkey_bytes.as_hex_string()
+        )));
+    }
+
     let voluntary_exit = VoluntaryExit {
         epoch,
         validator_index,
@@ -61,6 +72,38 @@ pub async fn create_signed_voluntary_exit<T: 'static + SlotClock + Clone, E: Eth
             ))
         })?;
 
+    if let Some(beacon_nodes) = broadcast_to {
+        beacon_nodes
+            .first_success(RequireSynced::No, OfflineOnFailure::No, |beacon_node| {
+                let signed_voluntary_exit = signed_voluntary_exit.clone();
+                async move {
+                    beacon_node
+                        .post_beacon_pool_voluntary_exits(&signed_voluntary_exit)
+                        .await
+                }
+            })
+            .await
+            .map_err(|e| {
+                error!(
+                    log,
+                    "Failed to broadcast voluntary exit";
+                    "validator" => pubkey_bytes.as_hex_string(),
+                    "error" => %e,
+                );
+                warp_utils::reject::custom_server_error(format!(
+                    "Failed to broadcast voluntary exit: {}",
+                    e
+                ))
+            })?;
+
+        info!(
+            log,
+            "Broadcast voluntary exit";
+            "validator" => pubkey_bytes.as_hex_string(),
+            "epoch" => epoch
+        );
+    }
+
     Ok(GenericResponse::from(signed_voluntary_exit))
 }
 
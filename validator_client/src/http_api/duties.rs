@@ -0,0 +1,111 @@
+//! A consolidated view of the duties of a single validator, for debugging missed duties.
+use crate::duties_service::DutiesService;
+use eth2::lighthouse_vc::types::{
+    AttesterDutyInfo, DutyRefreshTimes, ProposerDutyInfo, SyncCommitteeDutyInfo, ValidatorDuties,
+};
+use slot_clock::SlotClock;
+use std::time::Duration;
+use types::{EthSpec, PublicKeyBytes, Slot};
+use warp::Rejection;
+use warp_utils::reject::custom_not_found;
+
+pub fn validator_duties<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: PublicKeyBytes,
+    duties_service: &DutiesService<T, E>,
+) -> Result<ValidatorDuties, Rejection> {
+    if !duties_service.validator_store.has_validator(&pubkey) {
+        return Err(custom_not_found(format!("no validator for {:?}", pubkey)));
+    }
+
+    let validator_index = duties_service.validator_store.validator_index(&pubkey);
+    let slot_clock = &duties_service.slot_clock;
+    let sources = duties_service.duty_sources.read();
+
+    let mut attester = duties_service
+        .attesters
+        .read()
+        .get(&pubkey)
+        .map(|duties| {
+            duties
+                .iter()
+                .map(|(epoch, (dependent_root, duty_and_proof))| {
+                    let duty = &duty_and_proof.duty;
+                    AttesterDutyInfo {
+                        epoch: *epoch,
+                        slot: duty.slot,
+                        committee_index: duty.committee_index,
+                        validator_committee_index: duty.validator_committee_index,
+                        is_aggregator: duty_and_proof.selection_proof.is_some(),
+                        dependent_root: *dependent_root,
+                        scheduled_time: scheduled_time(
+                            slot_clock,
+                            duty.slot,
+                            slot_clock.unagg_attestation_production_delay(),
+                        ),
+                        beacon_node: sources.attesters.get(epoch).cloned(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    attester.sort_by_key(|duty| duty.slot);
+
+    let mut proposer = duties_service
+        .proposers
+        .read()
+        .iter()
+        .flat_map(|(epoch, (dependent_root, proposers))| {
+            proposers
+                .iter()
+                .filter(|data| data.pubkey == pubkey)
+                .map(|data| ProposerDutyInfo {
+                    epoch: *epoch,
+                    slot: data.slot,
+                    dependent_root: *dependent_root,
+                    scheduled_time: scheduled_time(slot_clock, data.slot, Duration::ZERO),
+                    beacon_node: sources.proposers.get(epoch).cloned(),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    proposer.sort_by_key(|duty| duty.slot);
+
+    let mut sync_committee = validator_index
+        .map(|index| duties_service.sync_duties.validator_duties(index))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(period, duty)| SyncCommitteeDutyInfo {
+            sync_committee_period: period,
+            validator_sync_committee_indices: duty.validator_sync_committee_indices,
+            beacon_node: sources.sync_committees.get(&period).cloned(),
+        })
+        .collect::<Vec<_>>();
+    sync_committee.sort_by_key(|duty| duty.sync_committee_period);
+
+    Ok(ValidatorDuties {
+        pubkey,
+        validator_index,
+        attester,
+        proposer,
+        sync_committee,
+    })
+}
+
+/// Returns the times at which duties were last refreshed from a beacon node.
+pub fn refresh_times<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+) -> DutyRefreshTimes {
+    let refresh_times = duties_service.refresh_times.read();
+    DutyRefreshTimes {
+        attesters: refresh_times.attesters.map(|time| time.as_secs()),
+        proposers: refresh_times.proposers.map(|time| time.as_secs()),
+        sync_committees: refresh_times.sync_committees.map(|time| time.as_secs()),
+    }
+}
+
+/// Returns the Unix timestamp, in seconds, of `delay` after the start of `slot`.
+fn scheduled_time<T: SlotClock>(slot_clock: &T, slot: Slot, delay: Duration) -> Option<u64> {
+    slot_clock
+        .start_of(slot)
+        .map(|start| (start + delay).as_secs())
+}
@@ -44,7 +44,9 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
                         ref voting_keystore,
                         ..
                     } => (voting_keystore.path(), Some(false)),
-                    SigningMethod::Web3Signer { .. } => (None, Some(true)),
+                    SigningMethod::Web3Signer { .. } | SigningMethod::Placeholder { .. } => {
+                        (None, Some(true))
+                    }
                 });
 
             SingleKeystoreResponse {
@@ -184,6 +186,11 @@ fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
             return Ok(ImportKeystoreStatus::Duplicate);
         }
     }
+    validator_store
+        .initialized_validators()
+        .read()
+        .check_migration_lock(&pubkey.compress())
+        .map_err(|e| format!("validator was migrated: {:?}", e))?;
 
     let password_storage = if let Some(secrets_dir) = &secrets_dir {
         let password_path = keystore_password_path(secrets_dir, &keystore);
@@ -0,0 +1,162 @@
+//! Export of validators for migration to another machine.
+//!
+//! Exported validators are deleted from this validator client and a signed record of the epoch
+//! at which they were disabled is kept, which prevents them from being enabled again here until
+//! a waiting period has passed.
+use crate::http_api::keystores;
+use crate::ValidatorStore;
+use eth2::lighthouse_vc::{
+    std_types::{DeleteKeystoreStatus, DeleteKeystoresRequest},
+    types::{
+        MigrationExportRequest, MigrationExportResponse, MigrationRecord, SignedMigrationRecord,
+    },
+};
+use slog::{error, info, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use types::{EthSpec, PublicKeyBytes};
+use warp::Rejection;
+use warp_utils::reject::custom_server_error;
+
+/// The default number of epochs that must pass before a migrated validator can be enabled again.
+pub const DEFAULT_MIGRATION_WAIT_EPOCHS: u64 = 2;
+
+pub fn export<T: SlotClock + 'static, E: EthSpec>(
+    request: MigrationExportRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+    signer: impl Fn(&[u8]) -> String,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<MigrationExportResponse, Rejection> {
+    let disabled_at_epoch = slot_clock
+        .now_or_genesis()
+        .ok_or_else(|| custom_server_error("unable to read slot clock".to_string()))?
+        .epoch(E::slots_per_epoch());
+    let reenable_epoch =
+        disabled_at_epoch + request.wait_epochs.unwrap_or(DEFAULT_MIGRATION_WAIT_EPOCHS);
+    let reenable_time = slot_clock
+        .start_of(reenable_epoch.start_slot(E::slots_per_epoch()))
+        .ok_or_else(|| custom_server_error("unable to read slot clock".to_string()))?
+        .as_secs();
+
+    let sign_record = |pubkey: PublicKeyBytes| -> Result<SignedMigrationRecord, Rejection> {
+        let record = MigrationRecord {
+            pubkey,
+            disabled_at_epoch,
+            reenable_epoch,
+            reenable_time,
+        };
+        let record_bytes = serde_json::to_vec(&record).map_err(|e| {
+            custom_server_error(format!("unable to serialize migration record: {:?}", e))
+        })?;
+        Ok(SignedMigrationRecord {
+            signature: signer(&record_bytes),
+            record,
+        })
+    };
+
+    // Lock the validators managed by this validator client before any of them are deleted, so that
+    // a failure to save the locks can't leave a deleted validator unlocked.
+    let managed_pubkeys = {
+        let initialized_validators = validator_store.initialized_validators();
+        let initialized_validators = initialized_validators.read();
+        request
+            .pubkeys
+            .iter()
+            .filter(|pubkey| {
+                initialized_validators
+                    .validator_definitions()
+                    .iter()
+                    .any(|def| def.voting_public_key.compress() == **pubkey)
+            })
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    let managed_records = managed_pubkeys
+        .iter()
+        .map(|pubkey| sign_record(*pubkey))
+        .collect::<Result<Vec<_>, _>>()?;
+    validator_store
+        .initialized_validators()
+        .write()
+        .add_migration_locks(managed_records.clone())
+        .map_err(|e| custom_server_error(format!("unable to save migration locks: {:?}", e)))?;
+
+    let export_response = keystores::export(
+        DeleteKeystoresRequest {
+            pubkeys: request.pubkeys.clone(),
+        },
+        validator_store.clone(),
+        task_executor,
+        log.clone(),
+    )?;
+
+    // Validators which are only known from their slashing protection data weren't deleted, so
+    // they're locked after the export.
+    let unmanaged_records = request
+        .pubkeys
+        .iter()
+        .zip(&export_response.data)
+        .filter(|(pubkey, response)| {
+            response.status.status == DeleteKeystoreStatus::NotActive
+                && !managed_pubkeys.contains(pubkey)
+        })
+        .map(|(pubkey, _)| sign_record(*pubkey))
+        .collect::<Result<Vec<_>, _>>()?;
+    let error = if unmanaged_records.is_empty() {
+        None
+    } else {
+        validator_store
+            .initialized_validators()
+            .write()
+            .add_migration_locks(unmanaged_records.clone())
+            .err()
+            .map(|e| {
+                error!(
+                    log,
+                    "Unable to save migration locks";
+                    "error" => ?e,
+                );
+                format!("unable to save migration locks: {:?}", e)
+            })
+    };
+
+    // A validator which failed to be deleted stays locked, which is harmless since it remains
+    // enabled.
+    let mut migration_records = managed_records
+        .into_iter()
+        .filter(|signed_record| {
+            request
+                .pubkeys
+                .iter()
+                .zip(&export_response.data)
+                .any(|(pubkey, response)| {
+                    *pubkey == signed_record.record.pubkey
+                        && response.status.status == DeleteKeystoreStatus::Deleted
+                })
+        })
+        .collect::<Vec<_>>();
+    if error.is_none() {
+        migration_records.extend(unmanaged_records);
+    }
+    for signed_record in &migration_records {
+        info!(
+            log,
+            "Exported validator for migration";
+            "pubkey" => ?signed_record.record.pubkey,
+            "disabled_at_epoch" => disabled_at_epoch,
+            "reenable_epoch" => reenable_epoch,
+        );
+    }
+
+    // Return the exported keystores and slashing protection data even if some locks couldn't be
+    // saved, since the validators have already been deleted.
+    Ok(MigrationExportResponse {
+        data: export_response.data,
+        slashing_protection: export_response.slashing_protection,
+        migration_records,
+        error,
+    })
+}
@@ -1,14 +1,20 @@
 mod api_secret;
 mod create_signed_voluntary_exit;
 mod create_validator;
+mod duties;
 mod keystores;
+mod migration;
 mod remotekeys;
+mod replay_duty;
 mod tests;
 
 pub mod test_utils;
 
+use crate::beacon_node_fallback::BeaconNodeFallback;
+use crate::block_service::ProposalOverrides;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
-use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
+use crate::signing_ledger::{SigningLedger, DEFAULT_HISTORY_LIMIT};
+use crate::{determine_graffiti, DutiesService, DutyRecorder, GraffitiFile, ValidatorStore};
 use account_utils::{
     mnemonic_from_phrase,
     validator_definitions::{SigningDefinition, ValidatorDefinition, Web3SignerDefinition},
@@ -22,7 +28,7 @@ use eth2::lighthouse_vc::{
     types::{self as api_types, GenericResponse, Graffiti, PublicKey, PublicKeyBytes},
 };
 use lighthouse_version::version_with_platform;
-use logging::SSELoggingComponents;
+use logging::{RuntimeLogFilter, SSELoggingComponents};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use slog::{crit, info, warn, Logger};
@@ -74,6 +80,9 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub duties_service: Option<Arc<DutiesService<T, E>>>,
+    pub duty_recorder: Option<Arc<DutyRecorder>>,
+    pub signing_ledger: Option<Arc<SigningLedger>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -82,6 +91,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub config: Config,
     pub log: Logger,
     pub sse_logging_components: Option<SSELoggingComponents>,
+    pub runtime_log_filter: Option<RuntimeLogFilter>,
     pub slot_clock: T,
     pub _phantom: PhantomData<E>,
 }
@@ -95,6 +105,9 @@ pub struct Config {
     pub allow_origin: Option<String>,
     pub allow_keystore_export: bool,
     pub store_passwords_in_secrets_dir: bool,
+    /// Check that the remote signer of a key imported via the keymanager API returns a valid
+    /// signature before enabling it.
+    pub remotekey_health_check: bool,
 }
 
 impl Default for Config {
@@ -106,6 +119,7 @@ impl Default for Config {
             allow_origin: None,
             allow_keystore_export: false,
             store_passwords_in_secrets_dir: false,
+            remotekey_health_check: true,
         }
     }
 }
@@ -132,6 +146,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let config = &ctx.config;
     let allow_keystore_export = config.allow_keystore_export;
     let store_passwords_in_secrets_dir = config.store_passwords_in_secrets_dir;
+    let remotekey_health_check = config.remotekey_health_check;
     let log = ctx.log.clone();
 
     // Configure CORS.
@@ -184,6 +199,56 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_duties_service = ctx.duties_service.clone();
+    let duties_service_filter = warp::any()
+        .map(move || inner_duties_service.clone())
+        .and_then(|duties_service: Option<_>| async move {
+            duties_service.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "duties service is not initialized.".to_string(),
+                )
+            })
+        });
+
+    let inner_beacon_nodes = ctx
+        .duties_service
+        .as_ref()
+        .map(|duties_service| duties_service.beacon_nodes.clone());
+    let beacon_nodes_filter = warp::any().map(move || inner_beacon_nodes.clone());
+
+    let inner_duty_recorder = ctx.duty_recorder.clone();
+    let duty_recorder_filter = warp::any()
+        .map(move || inner_duty_recorder.clone())
+        .and_then(|duty_recorder: Option<_>| async move {
+            duty_recorder.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "duty recording is not enabled, see --record-duty-inputs.".to_string(),
+                )
+            })
+        });
+
+    let inner_runtime_log_filter = ctx.runtime_log_filter.clone();
+    let runtime_log_filter_filter = warp::any()
+        .map(move || inner_runtime_log_filter.clone())
+        .and_then(|runtime_log_filter: Option<_>| async move {
+            runtime_log_filter.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "runtime log filters are not available.".to_string(),
+                )
+            })
+        });
+
+    let inner_signing_ledger = ctx.signing_ledger.clone();
+    let signing_ledger_filter = warp::any()
+        .map(move || inner_signing_ledger.clone())
+        .and_then(|signing_ledger: Option<_>| async move {
+            signing_ledger.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "the signing ledger is not enabled, see --signing-ledger.".to_string(),
+                )
+            })
+        });
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -286,6 +351,60 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/clock_skew
+    let get_lighthouse_clock_skew = warp::path("lighthouse")
+        .and(warp::path("clock_skew"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                validator_store
+                    .clock_skew_status()
+                    .map(api_types::GenericResponse::from)
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "the clock has not been compared with the beacon nodes".to_string(),
+                        )
+                    })
+            })
+        });
+
+    // GET lighthouse/slashing_protection/health
+    let get_lighthouse_slashing_protection_health = warp::path("lighthouse")
+        .and(warp::path("slashing_protection"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                validator_store
+                    .slashing_protection_health()
+                    .map(api_types::GenericResponse::from)
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "slashing protection health has not been checked yet".to_string(),
+                        )
+                    })
+            })
+        });
+
+    // GET lighthouse/remotekeys/health
+    let get_lighthouse_remotekeys_health = warp::path("lighthouse")
+        .and(warp::path("remotekeys"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                Ok(api_types::GenericResponse::from(
+                    validator_store.remote_signer_health(),
+                ))
+            })
+        });
+
     // GET lighthouse/spec
     let get_lighthouse_spec = warp::path("lighthouse")
         .and(warp::path("spec"))
@@ -323,6 +442,30 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/fee_recipients
+    let get_lighthouse_fee_recipients = warp::path("lighthouse")
+        .and(warp::path("fee_recipients"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                let pubkeys = validator_store
+                    .initialized_validators()
+                    .read()
+                    .validator_definitions()
+                    .iter()
+                    .map(|def| PublicKeyBytes::from(&def.voting_public_key))
+                    .collect::<Vec<_>>();
+                let resolutions = pubkeys
+                    .iter()
+                    .map(|pubkey| validator_store.fee_recipient_resolution(pubkey))
+                    .collect::<Vec<_>>();
+
+                Ok(api_types::GenericResponse::from(resolutions))
+            })
+        });
+
     // GET lighthouse/validators/{validator_pubkey}
     let get_lighthouse_validators_pubkey = warp::path("lighthouse")
         .and(warp::path("validators"))
@@ -356,6 +499,133 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // GET lighthouse/validators/{validator_pubkey}/duties
+    let get_lighthouse_validators_pubkey_duties = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("duties"))
+        .and(warp::path::end())
+        .and(duties_service_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey, duties_service: Arc<DutiesService<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    duties::validator_duties(
+                        PublicKeyBytes::from(&validator_pubkey),
+                        &duties_service,
+                    )
+                    .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/validators/{validator_pubkey}/proposal_overrides
+    let post_lighthouse_validators_pubkey_proposal_overrides = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("proposal_overrides"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             request: api_types::ProposalOverridesRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    let validator_pubkey = PublicKeyBytes::from(&validator_pubkey);
+                    if !validator_store.has_validator(&validator_pubkey) {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator for {:?}",
+                            validator_pubkey
+                        )));
+                    }
+                    if request.fee_recipient.is_some() && request.builder_proposals == Some(true) {
+                        return Err(warp_utils::reject::custom_bad_request(
+                            "a fee_recipient override cannot be used with builder proposals"
+                                .to_string(),
+                        ));
+                    }
+
+                    validator_store
+                        .set_proposal_overrides(
+                            validator_pubkey,
+                            request.slot,
+                            ProposalOverrides {
+                                graffiti: request.graffiti.clone().map(Into::into),
+                                fee_recipient: request.fee_recipient,
+                                builder_proposals: request.builder_proposals,
+                            },
+                        )
+                        .map_err(warp_utils::reject::custom_bad_request)?;
+
+                    Ok(api_types::GenericResponse::from(request))
+                })
+            },
+        );
+
+    // GET lighthouse/validators/{validator_pubkey}/history
+    let get_lighthouse_validators_pubkey_history = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("history"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::SigningHistoryQuery>())
+        .and(signing_ledger_filter)
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             query: api_types::SigningHistoryQuery,
+             signing_ledger: Arc<SigningLedger>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    signing_ledger
+                        .history(
+                            &PublicKeyBytes::from(&validator_pubkey),
+                            query.limit.unwrap_or(DEFAULT_HISTORY_LIMIT),
+                        )
+                        .map(api_types::GenericResponse::from)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "unable to read signing ledger: {:?}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/duties/recorded
+    let get_lighthouse_duties_recorded = warp::path("lighthouse")
+        .and(warp::path("duties"))
+        .and(warp::path("recorded"))
+        .and(warp::path::end())
+        .and(duty_recorder_filter.clone())
+        .and(signer.clone())
+        .and_then(|duty_recorder: Arc<DutyRecorder>, signer| {
+            blocking_signed_json_task(signer, move || {
+                Ok(api_types::GenericResponse::from(
+                    duty_recorder.recorded_duties(),
+                ))
+            })
+        });
+
+    // GET lighthouse/duties/refresh_times
+    let get_lighthouse_duties_refresh_times = warp::path("lighthouse")
+        .and(warp::path("duties"))
+        .and(warp::path("refresh_times"))
+        .and(warp::path::end())
+        .and(duties_service_filter.clone())
+        .and(signer.clone())
+        .and_then(|duties_service: Arc<DutiesService<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                Ok(api_types::GenericResponse::from(duties::refresh_times(
+                    &duties_service,
+                )))
+            })
+        });
+
     // GET lighthouse/ui/health
     let get_lighthouse_ui_health = warp::path("lighthouse")
         .and(warp::path("ui"))
@@ -465,7 +735,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(validator_dir_filter.clone())
         .and(secrets_dir_filter.clone())
         .and(validator_store_filter.clone())
-        .and(spec_filter)
+        .and(spec_filter.clone())
         .and(signer.clone())
         .and(task_executor_filter.clone())
         .and_then(
@@ -757,6 +1027,57 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST /lighthouse/migration/export
+    let post_lighthouse_migration_export = warp::path("lighthouse")
+        .and(warp::path("migration"))
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(slot_clock_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            move |request, signer, validator_store, slot_clock, task_executor, log| {
+                blocking_signed_json_task(signer.clone(), move || {
+                    if allow_keystore_export {
+                        migration::export(
+                            request,
+                            validator_store,
+                            slot_clock,
+                            signer,
+                            task_executor,
+                            log,
+                        )
+                    } else {
+                        Err(warp_utils::reject::custom_bad_request(
+                            "keystore export is disabled".to_string(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // GET /lighthouse/migration/locks
+    let get_lighthouse_migration_locks = warp::path("lighthouse")
+        .and(warp::path("migration"))
+        .and(warp::path("locks"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(|validator_store: Arc<ValidatorStore<T, E>>, signer| {
+            blocking_signed_json_task(signer, move || {
+                Ok(api_types::GenericResponse::from(
+                    validator_store
+                        .initialized_validators()
+                        .read()
+                        .migration_locks()
+                        .to_vec(),
+                ))
+            })
+        });
+
     // Standard key-manager endpoints.
     let eth_v1 = warp::path("eth").and(warp::path("v1"));
     let std_keystores = eth_v1.and(warp::path("keystores")).and(warp::path::end());
@@ -996,6 +1317,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(warp::query::<api_types::VoluntaryExitQuery>())
         .and(warp::path::end())
         .and(validator_store_filter.clone())
+        .and(beacon_nodes_filter)
         .and(slot_clock_filter)
         .and(log_filter.clone())
         .and(signer.clone())
@@ -1004,17 +1326,28 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             |pubkey: PublicKey,
              query: api_types::VoluntaryExitQuery,
              validator_store: Arc<ValidatorStore<T, E>>,
+             beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
              slot_clock: T,
              log,
              signer,
              task_executor: TaskExecutor| {
                 blocking_signed_json_task(signer, move || {
+                    let broadcast_to = if query.broadcast {
+                        Some(beacon_nodes.ok_or_else(|| {
+                            warp_utils::reject::custom_server_error(
+                                "no beacon nodes are available to broadcast the exit".to_string(),
+                            )
+                        })?)
+                    } else {
+                        None
+                    };
                     if let Some(handle) = task_executor.handle() {
                         let signed_voluntary_exit =
                             handle.block_on(create_signed_voluntary_exit(
                                 pubkey,
                                 query.epoch,
                                 validator_store,
+                                broadcast_to,
                                 slot_clock,
                                 log,
                             ))?;
@@ -1028,6 +1361,72 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/duties/recorded/{id}/replay
+    let post_lighthouse_duties_recorded_replay = warp::path("lighthouse")
+        .and(warp::path("duties"))
+        .and(warp::path("recorded"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path("replay"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(duty_recorder_filter)
+        .and(validator_store_filter.clone())
+        .and(spec_filter)
+        .and(signer.clone())
+        .and(task_executor_filter.clone())
+        .and_then(
+            |id: u64,
+             request: api_types::ReplayDutyRequest,
+             duty_recorder: Arc<DutyRecorder>,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             spec: Arc<ChainSpec>,
+             signer,
+             task_executor: TaskExecutor| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(handle) = task_executor.handle() {
+                        let replayed_duty = handle.block_on(replay_duty::replay_duty(
+                            id,
+                            request,
+                            &duty_recorder,
+                            &validator_store,
+                            &spec,
+                        ))?;
+                        Ok(api_types::GenericResponse::from(replayed_duty))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(runtime_log_filter_filter)
+        .and(signer.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request: api_types::LoggingRequest,
+             runtime_log_filter: RuntimeLogFilter,
+             signer,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    let status = warp_utils::log_filter::update(&runtime_log_filter, request)?;
+                    info!(
+                        log,
+                        "Log filters updated";
+                        "filter" => &status.filter,
+                        "debug_capture" => ?status.debug_capture,
+                    );
+                    Ok(api_types::GenericResponse::from(status))
+                })
+            },
+        );
+
     // GET /eth/v1/keystores
     let get_std_keystores = std_keystores
         .and(signer.clone())
@@ -1097,7 +1496,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(log_filter.clone())
         .and_then(|request, signer, validator_store, task_executor, log| {
             blocking_signed_json_task(signer, move || {
-                remotekeys::import(request, validator_store, task_executor, log)
+                remotekeys::import(
+                    request,
+                    validator_store,
+                    remotekey_health_check,
+                    task_executor,
+                    log,
+                )
             })
         });
 
@@ -1168,11 +1573,20 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                 .and(
                     get_node_version
                         .or(get_lighthouse_health)
+                        .or(get_lighthouse_slashing_protection_health)
+                        .or(get_lighthouse_clock_skew)
+                        .or(get_lighthouse_remotekeys_health)
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
                         .or(get_lighthouse_validators_pubkey)
+                        .or(get_lighthouse_validators_pubkey_duties)
+                        .or(get_lighthouse_validators_pubkey_history)
+                        .or(get_lighthouse_fee_recipients)
+                        .or(get_lighthouse_duties_recorded)
+                        .or(get_lighthouse_duties_refresh_times)
                         .or(get_lighthouse_ui_health)
                         .or(get_lighthouse_ui_graffiti)
+                        .or(get_lighthouse_migration_locks)
                         .or(get_fee_recipient)
                         .or(get_gas_limit)
                         .or(get_std_keystores)
@@ -1184,6 +1598,10 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_validators_mnemonic)
                         .or(post_validators_web3signer)
                         .or(post_validators_voluntary_exits)
+                        .or(post_lighthouse_migration_export)
+                        .or(post_lighthouse_duties_recorded_replay)
+                        .or(post_lighthouse_validators_pubkey_proposal_overrides)
+                        .or(post_lighthouse_logging)
                         .or(post_fee_recipient)
                         .or(post_gas_limit)
                         .or(post_std_keystores)
@@ -1,5 +1,6 @@
 //! Implementation of the standard remotekey management API.
-use crate::{initialized_validators::Error, InitializedValidators, ValidatorStore};
+use crate::initialized_validators::{web3_signer_signing_method, Error};
+use crate::{InitializedValidators, ValidatorStore};
 use account_utils::validator_definitions::{
     SigningDefinition, ValidatorDefinition, Web3SignerDefinition,
 };
@@ -50,6 +51,7 @@ pub fn list<T: SlotClock + 'static, E: EthSpec>(
 pub fn import<T: SlotClock + 'static, E: EthSpec>(
     request: ImportRemotekeysRequest,
     validator_store: Arc<ValidatorStore<T, E>>,
+    health_check: bool,
     task_executor: TaskExecutor,
     log: Logger,
 ) -> Result<ImportRemotekeysResponse, Rejection> {
@@ -64,8 +66,13 @@ pub fn import<T: SlotClock + 'static, E: EthSpec>(
     for remotekey in request.remote_keys {
         let status = if let Some(handle) = task_executor.handle() {
             // Import the keystore.
-            match import_single_remotekey(remotekey.pubkey, remotekey.url, &validator_store, handle)
-            {
+            match import_single_remotekey(
+                remotekey.pubkey,
+                remotekey.url,
+                &validator_store,
+                health_check,
+                handle,
+            ) {
                 Ok(status) => Status::ok(status),
                 Err(e) => {
                     warn!(
@@ -92,6 +99,7 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
     pubkey: PublicKeyBytes,
     url: String,
     validator_store: &ValidatorStore<T, E>,
+    health_check: bool,
     handle: Handle,
 ) -> Result<ImportRemotekeyStatus, String> {
     if let Err(url_err) = Url::parse(&url) {
@@ -118,6 +126,24 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
 
     // Remotekeys are stored as web3signers.
     // The remotekey API provides less confgiuration option than the web3signer API.
+    let web3_signer = Web3SignerDefinition {
+        url,
+        root_certificate_path: None,
+        request_timeout_ms: None,
+        client_identity_path: None,
+        client_identity_password: None,
+    };
+
+    // Check that the remote signer holds the key before enabling it, otherwise the validator
+    // would silently miss its duties.
+    if health_check {
+        let signing_method = web3_signer_signing_method(&pubkey, &web3_signer)
+            .map_err(|e| format!("failed to build remote signer client: {:?}", e))?;
+        handle
+            .block_on(validator_store.check_signing_method_health(&signing_method))
+            .map_err(|e| format!("remote signer health check failed: {:?}", e))?;
+    }
+
     let web3signer_validator = ValidatorDefinition {
         enabled: true,
         voting_public_key: pubkey,
@@ -126,13 +152,7 @@ fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
         gas_limit: None,
         builder_proposals: None,
         description: String::from("Added by remotekey API"),
-        signing_definition: SigningDefinition::Web3Signer(Web3SignerDefinition {
-            url,
-            root_certificate_path: None,
-            request_timeout_ms: None,
-            client_identity_path: None,
-            client_identity_password: None,
-        }),
+        signing_definition: SigningDefinition::Web3Signer(web3_signer),
     };
     handle
         .block_on(validator_store.add_validator(web3signer_validator))
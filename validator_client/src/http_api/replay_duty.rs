@@ -0,0 +1,35 @@
+use crate::duty_recorder::DutyRecorder;
+use crate::validator_store::ValidatorStore;
+use eth2::lighthouse_vc::types::{ReplayDutyRequest, ReplayedDuty};
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use slot_clock::SlotClock;
+use std::time::Duration;
+use types::{ChainSpec, EthSpec};
+use warp::Rejection;
+use warp_utils::reject::{custom_not_found, custom_server_error};
+
+/// Replays the recorded duty with the given `id`, publishing the signed objects to the beacon node
+/// in the `request`, if any.
+pub async fn replay_duty<T: SlotClock + 'static, E: EthSpec>(
+    id: u64,
+    request: ReplayDutyRequest,
+    duty_recorder: &DutyRecorder,
+    validator_store: &ValidatorStore<T, E>,
+    spec: &ChainSpec,
+) -> Result<ReplayedDuty, Rejection> {
+    let inputs = duty_recorder
+        .get(id)
+        .ok_or_else(|| custom_not_found(format!("no recorded duty with id {}", id)))?;
+
+    let beacon_node = request.beacon_node.map(|url| {
+        BeaconNodeHttpClient::new(
+            url,
+            Timeouts::set_all(Duration::from_secs(spec.seconds_per_slot)),
+        )
+    });
+
+    inputs
+        .replay(validator_store, beacon_node.as_ref(), spec)
+        .await
+        .map_err(|e| custom_server_error(format!("unable to replay duty: {}", e)))
+}
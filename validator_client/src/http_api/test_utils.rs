@@ -129,12 +129,16 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            duties_service: None,
+            duty_recorder: None,
+            signing_ledger: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
             config: http_config,
             log,
             sse_logging_components: None,
+            runtime_log_filter: None,
             slot_clock,
             _phantom: PhantomData,
         });
@@ -178,6 +182,7 @@ impl ApiTester {
             allow_origin: None,
             allow_keystore_export: true,
             store_passwords_in_secrets_dir: false,
+            remotekey_health_check: false,
         }
     }
 
@@ -3,8 +3,13 @@
 
 mod keystores;
 
+use crate::beacon_node_fallback::BeaconNodeFallback;
 use crate::doppelganger_service::DoppelgangerService;
+use crate::duties_service::{DutiesService, DutyAndProof};
+use crate::duty_recorder::DutyRecorder;
+use crate::signing_ledger::{SigningLedger, SIGNING_LEDGER_FILENAME};
 use crate::{
+    block_service::ProposalOverrides,
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
     Config, ValidatorDefinitions, ValidatorStore,
@@ -14,13 +19,14 @@ use account_utils::{
     random_password_string, ZeroizeString,
 };
 use deposit_contract::decode_eth1_tx_data;
+use environment::RuntimeContext;
 use eth2::{
     lighthouse_vc::{http_client::ValidatorClientHttpClient, types::*},
-    types::ErrorMessage as ApiErrorMessage,
+    types::{AttesterData, ErrorMessage as ApiErrorMessage, ProposerData},
     Error as ApiError,
 };
 use eth2_keystore::KeystoreBuilder;
-use logging::test_logger;
+use logging::{test_logger, LogDirectives, RuntimeLogFilter};
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
@@ -44,6 +50,9 @@ struct ApiTester {
     client: ValidatorClientHttpClient,
     initialized_validators: Arc<RwLock<InitializedValidators>>,
     validator_store: Arc<ValidatorStore<TestingSlotClock, E>>,
+    duties_service: Arc<DutiesService<TestingSlotClock, E>>,
+    duty_recorder: Arc<DutyRecorder>,
+    signing_ledger: Arc<SigningLedger>,
     url: SensitiveUrl,
     slot_clock: TestingSlotClock,
     _validator_dir: TempDir,
@@ -52,6 +61,23 @@ struct ApiTester {
 
 impl ApiTester {
     pub async fn new() -> Self {
+        Self::new_with_http_config(Self::default_http_config()).await
+    }
+
+    pub fn default_http_config() -> HttpConfig {
+        HttpConfig {
+            enabled: true,
+            listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
+            listen_port: 0,
+            allow_origin: None,
+            allow_keystore_export: true,
+            store_passwords_in_secrets_dir: false,
+            // The remote signers used by the tests are unreachable.
+            remotekey_health_check: false,
+        }
+    }
+
+    pub async fn new_with_http_config(http_config: HttpConfig) -> Self {
         let log = test_logger();
 
         let validator_dir = tempdir().unwrap();
@@ -107,24 +133,62 @@ impl ApiTester {
 
         let initialized_validators = validator_store.initialized_validators();
 
+        let duties_service = Arc::new(DutiesService {
+            attesters: <_>::default(),
+            proposers: <_>::default(),
+            sync_duties: <_>::default(),
+            duty_sources: <_>::default(),
+            refresh_times: <_>::default(),
+            slot_clock: slot_clock.clone(),
+            beacon_nodes: Arc::new(BeaconNodeFallback::new(
+                vec![],
+                false,
+                spec.clone(),
+                log.clone(),
+            )),
+            validator_store: validator_store.clone(),
+            spec: spec.clone(),
+            context: RuntimeContext {
+                executor: test_runtime.task_executor.clone(),
+                eth_spec_instance: E::default(),
+                eth2_config: <_>::default(),
+                eth2_network_config: None,
+                sse_logging_components: None,
+                runtime_log_filter: None,
+            },
+            enable_high_validator_count_metrics: false,
+            subscribe_head_events: false,
+            stale_duties_epochs: 2,
+        });
+
+        let duty_recorder = Arc::new(DutyRecorder::new(4));
+        let signing_ledger = Arc::new(
+            SigningLedger::open(
+                &validator_dir.path().join(SIGNING_LEDGER_FILENAME),
+                Duration::from_secs(24 * 60 * 60),
+                test_runtime.log.clone(),
+            )
+            .unwrap(),
+        );
+
         let context = Arc::new(Context {
             task_executor: test_runtime.task_executor.clone(),
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            duties_service: Some(duties_service.clone()),
+            duty_recorder: Some(duty_recorder.clone()),
+            signing_ledger: Some(signing_ledger.clone()),
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
-            config: HttpConfig {
-                enabled: true,
-                listen_addr: IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)),
-                listen_port: 0,
-                allow_origin: None,
-                allow_keystore_export: true,
-                store_passwords_in_secrets_dir: false,
-            },
+            config: http_config,
             sse_logging_components: None,
+            runtime_log_filter: Some(RuntimeLogFilter::new(
+                LogDirectives::new(slog::Level::Info),
+                Some(validator_dir.path().join("logs")),
+            )),
             log,
             slot_clock: slot_clock.clone(),
             _phantom: PhantomData,
@@ -148,6 +212,9 @@ impl ApiTester {
             client,
             initialized_validators,
             validator_store,
+            duties_service,
+            duty_recorder,
+            signing_ledger,
             url,
             slot_clock,
             _validator_dir: validator_dir,
@@ -241,6 +308,489 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_get_lighthouse_slashing_protection_health(self) -> Self {
+        // No check has run yet.
+        assert!(self
+            .client
+            .get_lighthouse_slashing_protection_health()
+            .await
+            .unwrap()
+            .is_none());
+
+        let backup_dir = tempdir().unwrap();
+        let expected = self
+            .validator_store
+            .check_slashing_protection_db(Some(backup_dir.path()), 2)
+            .unwrap();
+        assert!(expected.integrity_ok);
+        assert!(expected.database_size_bytes > 0);
+        assert!(expected.last_backup.as_ref().unwrap().exists());
+
+        let result = self
+            .client
+            .get_lighthouse_slashing_protection_health()
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_clock_skew_blocks_signing(self, index: usize) -> Self {
+        // The clock has not been compared with any beacon node yet.
+        assert!(self
+            .client
+            .get_lighthouse_clock_skew()
+            .await
+            .unwrap()
+            .is_none());
+
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        self.initialized_validators.write().set_index(&pubkey, 0);
+
+        let local_slot = self.slot_clock.now().unwrap();
+        let status = ClockSkewStatus {
+            local_slot,
+            beacon_node_slots: vec![BeaconNodeSlot {
+                beacon_node: "http://localhost:5052/".to_string(),
+                slot: local_slot + 10,
+            }],
+            skew_detected: true,
+            last_check: 0,
+        };
+        self.validator_store.set_clock_skew_status(status.clone());
+
+        let result = self
+            .client
+            .get_lighthouse_clock_skew()
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        assert_eq!(result, status);
+
+        // Signing is refused whilst the skew is detected.
+        assert!(self
+            .client
+            .post_validator_voluntary_exit(&pubkey, None)
+            .await
+            .is_err());
+
+        self.validator_store.set_clock_skew_status(ClockSkewStatus {
+            skew_detected: false,
+            ..status
+        });
+        assert!(self
+            .client
+            .post_validator_voluntary_exit(&pubkey, None)
+            .await
+            .is_ok());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validators_pubkey_duties(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        let beacon_node = "http://localhost:5052/".to_string();
+
+        self.initialized_validators.write().set_index(&pubkey, 42);
+
+        let epoch = Epoch::new(1);
+        let attestation_slot = epoch.start_slot(E::slots_per_epoch()) + 3;
+        let proposal_slot = epoch.start_slot(E::slots_per_epoch()) + 5;
+        let attester = AttesterData {
+            pubkey,
+            validator_index: 42,
+            committees_at_slot: 1,
+            committee_index: 2,
+            committee_length: 128,
+            validator_committee_index: 7,
+            slot: attestation_slot,
+        };
+        self.duties_service.attesters.write().insert(
+            pubkey,
+            [(
+                epoch,
+                (
+                    Hash256::repeat_byte(1),
+                    DutyAndProof::new_without_selection_proof(attester),
+                ),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        self.duties_service.proposers.write().insert(
+            epoch,
+            (
+                Hash256::repeat_byte(2),
+                vec![
+                    ProposerData {
+                        pubkey: PublicKeyBytes::empty(),
+                        validator_index: 1,
+                        slot: proposal_slot - 1,
+                    },
+                    ProposerData {
+                        pubkey,
+                        validator_index: 42,
+                        slot: proposal_slot,
+                    },
+                ],
+            ),
+        );
+        self.duties_service
+            .duty_sources
+            .write()
+            .attesters
+            .insert(epoch, beacon_node.clone());
+
+        let duties = self
+            .client
+            .get_lighthouse_validators_pubkey_duties(&pubkey)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+
+        let slot_start = |slot: Slot| self.slot_clock.start_of(slot).unwrap();
+        assert_eq!(
+            duties,
+            ValidatorDuties {
+                pubkey,
+                validator_index: Some(42),
+                attester: vec![AttesterDutyInfo {
+                    epoch,
+                    slot: attestation_slot,
+                    committee_index: 2,
+                    validator_committee_index: 7,
+                    is_aggregator: false,
+                    dependent_root: Hash256::repeat_byte(1),
+                    scheduled_time: Some(
+                        (slot_start(attestation_slot)
+                            + self.slot_clock.unagg_attestation_production_delay())
+                        .as_secs()
+                    ),
+                    beacon_node: Some(beacon_node),
+                }],
+                proposer: vec![ProposerDutyInfo {
+                    epoch,
+                    slot: proposal_slot,
+                    dependent_root: Hash256::repeat_byte(2),
+                    scheduled_time: Some(slot_start(proposal_slot).as_secs()),
+                    // The source of the proposer duties is unknown.
+                    beacon_node: None,
+                }],
+                sync_committee: vec![],
+            }
+        );
+
+        // Unknown validators are not found.
+        assert!(self
+            .client
+            .get_lighthouse_validators_pubkey_duties(&PublicKeyBytes::empty())
+            .await
+            .unwrap()
+            .is_none());
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_validators_pubkey_proposal_overrides(
+        self,
+        index: usize,
+    ) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+
+        self.slot_clock.set_slot(10);
+        let proposal_slot = Slot::new(12);
+        let fee_recipient = Address::repeat_byte(42);
+        let request = ProposalOverridesRequest {
+            slot: proposal_slot,
+            graffiti: Some(GraffitiString::from_str("experiment").unwrap()),
+            fee_recipient: Some(fee_recipient),
+            builder_proposals: None,
+        };
+
+        let response = self
+            .client
+            .post_lighthouse_validators_pubkey_proposal_overrides(&pubkey, &request)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(response, request);
+        assert_eq!(
+            self.validator_store
+                .proposal_overrides(&pubkey, proposal_slot),
+            Some(ProposalOverrides {
+                graffiti: Some(GraffitiString::from_str("experiment").unwrap().into()),
+                fee_recipient: Some(fee_recipient),
+                builder_proposals: None,
+            })
+        );
+
+        // The fee recipient is sent to the BN from the slot prior to the proposal.
+        assert_eq!(
+            self.validator_store
+                .upcoming_fee_recipient_override(&pubkey, proposal_slot - 2),
+            None
+        );
+        assert_eq!(
+            self.validator_store
+                .upcoming_fee_recipient_override(&pubkey, proposal_slot - 1),
+            Some(fee_recipient)
+        );
+
+        // Empty overrides remove the existing overrides.
+        self.client
+            .post_lighthouse_validators_pubkey_proposal_overrides(
+                &pubkey,
+                &ProposalOverridesRequest {
+                    slot: proposal_slot,
+                    ..<_>::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            self.validator_store
+                .proposal_overrides(&pubkey, proposal_slot),
+            None
+        );
+
+        // Overrides for past slots or slots beyond the next epoch, or which require a builder to
+        // use a different fee recipient, are rejected.
+        for invalid_request in [
+            ProposalOverridesRequest {
+                slot: Slot::new(9),
+                builder_proposals: Some(false),
+                ..<_>::default()
+            },
+            ProposalOverridesRequest {
+                slot: Slot::new(2 * E::slots_per_epoch()),
+                builder_proposals: Some(false),
+                ..<_>::default()
+            },
+            ProposalOverridesRequest {
+                slot: proposal_slot,
+                fee_recipient: Some(fee_recipient),
+                builder_proposals: Some(true),
+                ..<_>::default()
+            },
+        ] {
+            let err = self
+                .client
+                .post_lighthouse_validators_pubkey_proposal_overrides(&pubkey, &invalid_request)
+                .await
+                .unwrap_err();
+            assert_eq!(err.status().unwrap(), 400);
+        }
+
+        let err = self
+            .client
+            .post_lighthouse_validators_pubkey_proposal_overrides(
+                &PublicKeyBytes::empty(),
+                &request,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 404);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_duties_refresh_times(self) -> Self {
+        let refresh_times = self
+            .client
+            .get_lighthouse_duties_refresh_times()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            refresh_times,
+            DutyRefreshTimes {
+                attesters: None,
+                proposers: None,
+                sync_committees: None,
+            }
+        );
+
+        let now = self.slot_clock.now_duration().unwrap();
+        self.duties_service.refresh_times.write().attesters = Some(now);
+
+        let refresh_times = self
+            .client
+            .get_lighthouse_duties_refresh_times()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(refresh_times.attesters, Some(now.as_secs()));
+        assert_eq!(refresh_times.proposers, None);
+        assert!(!self.duties_service.duties_are_stale(Duration::ZERO));
+
+        // Duties become stale once they have not been refreshed for more than
+        // `stale_duties_epochs`.
+        let current_slot = self.slot_clock.now().unwrap();
+        let stale_duties_epochs = self.duties_service.stale_duties_epochs;
+        self.slot_clock
+            .set_slot((current_slot + stale_duties_epochs * E::slots_per_epoch()).as_u64());
+        assert!(!self.duties_service.duties_are_stale(Duration::ZERO));
+        self.slot_clock.advance_slot();
+        assert!(self.duties_service.duties_are_stale(Duration::ZERO));
+
+        self
+    }
+
+    pub async fn test_replay_recorded_attestation(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+
+        let slot = Slot::new(3);
+        let data = AttestationData {
+            slot,
+            index: 2,
+            beacon_block_root: Hash256::repeat_byte(1),
+            source: Checkpoint {
+                epoch: Epoch::new(0),
+                root: Hash256::repeat_byte(2),
+            },
+            target: Checkpoint {
+                epoch: Epoch::new(0),
+                root: Hash256::repeat_byte(3),
+            },
+        };
+        let attester = AttesterData {
+            pubkey,
+            validator_index: 42,
+            committees_at_slot: 4,
+            committee_index: 2,
+            committee_length: 128,
+            validator_committee_index: 7,
+            slot,
+        };
+        self.duty_recorder.record_attestation(
+            &data,
+            Epoch::new(0),
+            &[DutyAndProof::new_without_selection_proof(attester)],
+        );
+
+        let recorded = self
+            .client
+            .get_lighthouse_duties_recorded()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            recorded,
+            vec![RecordedDuty {
+                id: 0,
+                kind: RecordedDutyKind::Attestation,
+                slot,
+                validators: vec![pubkey],
+            }]
+        );
+
+        let request = ReplayDutyRequest { beacon_node: None };
+        let replayed = self
+            .client
+            .post_lighthouse_duties_recorded_replay(0, &request)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(replayed.id, 0);
+        assert_eq!(replayed.kind, RecordedDutyKind::Attestation);
+        assert_eq!(replayed.beacon_node, None);
+        assert_eq!(replayed.publication_error, None);
+        assert_eq!(replayed.signatures.len(), 1);
+        assert_eq!(replayed.signatures[0].pubkey, pubkey);
+        assert!(replayed.signatures[0].signature.is_some());
+        assert_eq!(replayed.signatures[0].error, None);
+
+        // Re-signing the same data is permitted by slashing protection.
+        let replayed_again = self
+            .client
+            .post_lighthouse_duties_recorded_replay(0, &request)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(replayed_again, replayed);
+
+        // Duties which have not been recorded are not found.
+        match self
+            .client
+            .post_lighthouse_duties_recorded_replay(1, &request)
+            .await
+        {
+            Err(ApiError::ServerMessage(ApiErrorMessage { code: 404, .. })) => (),
+            other => panic!("expected not found error, got {:?}", other),
+        }
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validators_pubkey_history(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        let pubkey = validator.voting_pubkey;
+        let beacon_node = "http://localhost:5052/";
+
+        assert!(self
+            .client
+            .get_lighthouse_validators_pubkey_history(&pubkey, None)
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+
+        self.signing_ledger
+            .record(
+                SignedMessageKind::Attestation,
+                Slot::new(1),
+                &[(pubkey, Duration::from_millis(3))],
+                Ok(beacon_node),
+            )
+            .unwrap();
+        self.signing_ledger
+            .record(
+                SignedMessageKind::Block,
+                Slot::new(2),
+                &[(pubkey, Duration::from_millis(40))],
+                Err("no beacon node accepted the block".to_string()),
+            )
+            .unwrap();
+
+        let history = self
+            .client
+            .get_lighthouse_validators_pubkey_history(&pubkey, None)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].kind, SignedMessageKind::Block);
+        assert_eq!(history[0].slot, Slot::new(2));
+        assert_eq!(history[0].signing_latency_ms, 40);
+        assert!(!history[0].published);
+        assert_eq!(history[0].beacon_node, None);
+        assert!(history[0].publication_error.is_some());
+        assert_eq!(history[1].kind, SignedMessageKind::Attestation);
+        assert!(history[1].published);
+        assert_eq!(history[1].beacon_node.as_deref(), Some(beacon_node));
+        assert_eq!(history[1].publication_error, None);
+
+        let latest = self
+            .client
+            .get_lighthouse_validators_pubkey_history(&pubkey, Some(1))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(latest, history[..1].to_vec());
+
+        self
+    }
+
     pub fn vals_total(&self) -> usize {
         self.initialized_validators.read().num_total()
     }
@@ -507,6 +1057,28 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_broadcast_voluntary_exit_without_beacon_nodes(self, index: usize) -> Self {
+        let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
+        self.initialized_validators
+            .write()
+            .set_index(&validator.voting_pubkey, 0);
+
+        // The tester's beacon node fallback is empty, so the exit is signed but can't be published.
+        match self
+            .client
+            .post_validator_voluntary_exit_and_broadcast(&validator.voting_pubkey, None)
+            .await
+        {
+            Err(ApiError::ServerMessage(ApiErrorMessage {
+                code: 500, message, ..
+            })) if message.contains("Failed to broadcast voluntary exit") => (),
+            Err(other) => panic!("expected broadcast error, got {:?}", other),
+            Ok(_) => panic!("expected broadcast error, got Ok"),
+        }
+
+        self
+    }
+
     fn get_current_epoch(&self) -> Epoch {
         self.slot_clock
             .now()
@@ -640,6 +1212,57 @@ impl ApiTester {
 
         self
     }
+
+    pub async fn test_post_lighthouse_logging(self) -> Self {
+        let status = self
+            .client
+            .post_lighthouse_logging(&LoggingRequest {
+                filter: Some("debug,validator_client::http_api=trace".to_string()),
+                debug_capture: Some(DebugCaptureRequest {
+                    duration_secs: 60,
+                    level: Some("trace".to_string()),
+                }),
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.filter, "debug,validator_client::http_api=trace");
+        let debug_capture = status.debug_capture.unwrap();
+        assert_eq!(debug_capture.level, "trace");
+        assert!(debug_capture.remaining_secs <= 60);
+        assert!(debug_capture.path.exists());
+
+        // An invalid level is rejected without ending the capture.
+        let err = self
+            .client
+            .post_lighthouse_logging(&LoggingRequest {
+                filter: None,
+                debug_capture: Some(DebugCaptureRequest {
+                    duration_secs: 60,
+                    level: Some("loud".to_string()),
+                }),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        let status = self
+            .client
+            .post_lighthouse_logging(&LoggingRequest {
+                filter: None,
+                debug_capture: Some(DebugCaptureRequest {
+                    duration_secs: 0,
+                    level: None,
+                }),
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(status.filter, "debug,validator_client::http_api=trace");
+        assert_eq!(status.debug_capture, None);
+
+        self
+    }
 }
 
 struct HdValidatorScenario {
@@ -676,6 +1299,10 @@ async fn routes_with_invalid_auth() {
         .await
         .test_with_invalid_auth(|client| async move { client.get_lighthouse_health().await })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_slashing_protection_health().await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client.get_lighthouse_spec::<types::Config>().await
         })
@@ -688,6 +1315,47 @@ async fn routes_with_invalid_auth() {
                 .await
         })
         .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_lighthouse_validators_pubkey_duties(&PublicKeyBytes::empty())
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_validators_pubkey_proposal_overrides(
+                    &PublicKeyBytes::empty(),
+                    &ProposalOverridesRequest::default(),
+                )
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .get_lighthouse_validators_pubkey_history(&PublicKeyBytes::empty(), None)
+                .await
+        })
+        .await
+        .test_with_invalid_auth(
+            |client| async move { client.get_lighthouse_fee_recipients().await },
+        )
+        .await
+        .test_with_invalid_auth(
+            |client| async move { client.get_lighthouse_duties_recorded().await },
+        )
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client.get_lighthouse_duties_refresh_times().await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move { client.get_lighthouse_clock_skew().await })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_duties_recorded_replay(0, &ReplayDutyRequest { beacon_node: None })
+                .await
+        })
+        .await
         .test_with_invalid_auth(|client| async move {
             client
                 .post_lighthouse_validators(vec![ValidatorRequest {
@@ -771,6 +1439,26 @@ async fn routes_with_invalid_auth() {
                 })
                 .await
         })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            let keypair = Keypair::random();
+            client
+                .post_lighthouse_migration_export(&MigrationExportRequest {
+                    pubkeys: vec![keypair.pk.compress()],
+                    wait_epochs: None,
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(
+            |client| async move { client.get_lighthouse_migration_locks().await },
+        )
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_logging(&LoggingRequest::default())
+                .await
+        })
         .await;
 }
 
@@ -783,6 +1471,81 @@ async fn simple_getters() {
         .test_get_lighthouse_health()
         .await
         .test_get_lighthouse_spec()
+        .await
+        .test_get_lighthouse_slashing_protection_health()
+        .await;
+}
+
+#[tokio::test]
+async fn validator_duties() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_get_lighthouse_validators_pubkey_duties(1)
+        .await;
+}
+
+#[tokio::test]
+async fn proposal_overrides() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_post_lighthouse_validators_pubkey_proposal_overrides(1)
+        .await;
+}
+
+#[tokio::test]
+async fn duties_refresh_times() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_duties_refresh_times()
+        .await;
+}
+
+#[tokio::test]
+async fn replay_recorded_duty() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_replay_recorded_attestation(1)
+        .await;
+}
+
+#[tokio::test]
+async fn runtime_log_filters() {
+    ApiTester::new().await.test_post_lighthouse_logging().await;
+}
+
+#[tokio::test]
+async fn validator_signing_history() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_get_lighthouse_validators_pubkey_history(0)
         .await;
 }
 
@@ -837,6 +1600,23 @@ async fn validator_exit() {
         .test_sign_voluntary_exits(0, None)
         .await
         .test_sign_voluntary_exits(0, Some(Epoch::new(256)))
+        .await
+        .test_broadcast_voluntary_exit_without_beacon_nodes(1)
+        .await;
+}
+
+#[tokio::test]
+async fn clock_skew() {
+    ApiTester::new()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_clock_skew_blocks_signing(0)
         .await;
 }
 
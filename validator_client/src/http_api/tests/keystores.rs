@@ -1,5 +1,7 @@
 use super::super::super::validator_store::DEFAULT_GAS_LIMIT;
 use super::*;
+use crate::http_api::migration::DEFAULT_MIGRATION_WAIT_EPOCHS;
+use crate::initialized_validators::MIGRATION_LOCKS_FILENAME;
 use account_utils::random_password_string;
 use bls::PublicKeyBytes;
 use eth2::lighthouse_vc::types::UpdateFeeRecipientRequest;
@@ -11,7 +13,7 @@ use eth2::lighthouse_vc::{
 use itertools::Itertools;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
 use slashing_protection::interchange::{Interchange, InterchangeMetadata};
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, fs, path::Path};
 use tokio::runtime::Handle;
 use types::Address;
 
@@ -774,6 +776,82 @@ async fn check_get_set_fee_recipient() {
     .await;
 }
 
+#[tokio::test]
+async fn fee_recipient_resolution() {
+    run_test(|tester: ApiTester| async move {
+        let password = random_password_string();
+        let keystores = (0..2)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+        let all_pubkeys = keystores.iter().map(keystore_pubkey).collect::<Vec<_>>();
+
+        let import_res = tester
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords: vec![password.clone(); keystores.len()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        // Before setting anything, every fee recipient comes from the process default.
+        let resolutions = tester
+            .client
+            .get_lighthouse_fee_recipients()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resolutions.len(), all_pubkeys.len());
+        for (resolution, pubkey) in resolutions.iter().zip(&all_pubkeys) {
+            assert_eq!(
+                *resolution,
+                FeeRecipientResolution {
+                    pubkey: *pubkey,
+                    fee_recipient: Some(TEST_DEFAULT_FEE_RECIPIENT),
+                    source: Some(FeeRecipientSource::ProcessDefault),
+                    definition: None,
+                    file: None,
+                    process_default: Some(TEST_DEFAULT_FEE_RECIPIENT),
+                }
+            );
+        }
+
+        // A fee recipient in the validator definitions takes priority.
+        let fee_recipient = Address::repeat_byte(1);
+        tester
+            .client
+            .post_fee_recipient(
+                &all_pubkeys[0],
+                &UpdateFeeRecipientRequest {
+                    ethaddress: fee_recipient,
+                },
+            )
+            .await
+            .expect("should update fee recipient");
+
+        let resolutions = tester
+            .client
+            .get_lighthouse_fee_recipients()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(resolutions[0].fee_recipient, Some(fee_recipient));
+        assert_eq!(resolutions[0].source, Some(FeeRecipientSource::Definition));
+        assert_eq!(resolutions[0].definition, Some(fee_recipient));
+        assert_eq!(
+            resolutions[0].process_default,
+            Some(TEST_DEFAULT_FEE_RECIPIENT)
+        );
+        assert_eq!(
+            resolutions[1].source,
+            Some(FeeRecipientSource::ProcessDefault)
+        );
+    })
+    .await
+}
+
 #[tokio::test]
 async fn check_get_set_gas_limit() {
     run_test(|tester: ApiTester| async move {
@@ -1408,6 +1486,96 @@ async fn delete_then_reimport() {
     .await
 }
 
+#[tokio::test]
+async fn migration_export_then_reimport() {
+    run_test(|tester| async move {
+        let password = random_password_string();
+        let keystores = (0..2)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+        let pubkeys = keystores.iter().map(keystore_pubkey).collect::<Vec<_>>();
+
+        // 1. Import all keystores.
+        let import_req = ImportKeystoresRequest {
+            keystores: keystores.clone(),
+            passwords: vec![password.clone(); keystores.len()],
+            slashing_protection: None,
+        };
+        let import_res = tester.client.post_keystores(&import_req).await.unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        // 2. Export all for migration.
+        let export_res = tester
+            .client
+            .post_lighthouse_migration_export(&MigrationExportRequest {
+                pubkeys: pubkeys.clone(),
+                wait_epochs: None,
+            })
+            .await
+            .unwrap();
+        assert!(export_res
+            .data
+            .iter()
+            .all(|response| response.status.status == DeleteKeystoreStatus::Deleted));
+        assert_eq!(export_res.migration_records.len(), keystores.len());
+        for (record, pubkey) in export_res.migration_records.iter().zip(&pubkeys) {
+            assert_eq!(record.record.pubkey, *pubkey);
+            assert_eq!(
+                record.record.reenable_epoch,
+                record.record.disabled_at_epoch + DEFAULT_MIGRATION_WAIT_EPOCHS
+            );
+        }
+
+        let locks = tester
+            .client
+            .get_lighthouse_migration_locks()
+            .await
+            .unwrap();
+        assert_eq!(locks.data, export_res.migration_records);
+
+        // 3. Re-importing is refused until the waiting period has passed.
+        let import_res = tester.client.post_keystores(&import_req).await.unwrap();
+        check_keystore_import_response(&import_res, all_import_error(keystores.len()));
+        tester.assert_enabled_validators_count(0);
+    })
+    .await
+}
+
+#[tokio::test]
+async fn migration_export_keeps_validators_if_locks_cannot_be_saved() {
+    run_test(|tester| async move {
+        let password = random_password_string();
+        let keystores = (0..2)
+            .map(|_| new_keystore(password.clone()))
+            .collect::<Vec<_>>();
+        let pubkeys = keystores.iter().map(keystore_pubkey).collect::<Vec<_>>();
+
+        let import_req = ImportKeystoresRequest {
+            keystores: keystores.clone(),
+            passwords: vec![password.clone(); keystores.len()],
+            slashing_protection: None,
+        };
+        let import_res = tester.client.post_keystores(&import_req).await.unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        // A directory in place of the locks file prevents the locks from being saved.
+        fs::create_dir(tester._validator_dir.path().join(MIGRATION_LOCKS_FILENAME)).unwrap();
+
+        tester
+            .client
+            .post_lighthouse_migration_export(&MigrationExportRequest {
+                pubkeys,
+                wait_epochs: None,
+            })
+            .await
+            .unwrap_err();
+
+        // No validator was deleted without its lock.
+        tester.assert_enabled_validators_count(keystores.len());
+    })
+    .await
+}
+
 #[tokio::test]
 async fn get_empty_remotekeys() {
     run_test(|tester| async move {
@@ -1457,6 +1625,145 @@ async fn import_new_remotekeys() {
     .await
 }
 
+#[tokio::test]
+async fn import_remotekey_with_unreachable_signer() {
+    let tester = ApiTester::new_with_http_config(HttpConfig {
+        remotekey_health_check: true,
+        ..ApiTester::default_http_config()
+    })
+    .await;
+
+    let remotekeys = vec![new_remotekey_validator().1];
+    let import_res = tester
+        .client
+        .post_remotekeys(&ImportRemotekeysRequest {
+            remote_keys: remotekeys.clone(),
+        })
+        .await
+        .unwrap();
+
+    // The remote signer doesn't respond, so the key should not be enabled.
+    check_remotekey_import_response(
+        &import_res,
+        all_with_status(remotekeys.len(), ImportRemotekeyStatus::Error),
+    );
+    let get_res = tester.client.get_remotekeys().await.unwrap();
+    check_remotekey_get_response(&get_res, vec![]);
+}
+
+#[tokio::test]
+async fn remotekeys_health() {
+    run_test(|tester| async move {
+        let res = tester
+            .client
+            .get_lighthouse_remotekeys_health()
+            .await
+            .unwrap();
+        assert!(res.data.is_empty());
+
+        let remotekeys = (0..2)
+            .map(|_| new_remotekey_validator().1)
+            .collect::<Vec<_>>();
+        tester
+            .client
+            .post_remotekeys(&ImportRemotekeysRequest {
+                remote_keys: remotekeys.clone(),
+            })
+            .await
+            .unwrap();
+
+        tester.validator_store.check_remote_signers().await;
+
+        // Every remote signer is unreachable.
+        let res = tester
+            .client
+            .get_lighthouse_remotekeys_health()
+            .await
+            .unwrap();
+        assert_eq!(res.data.len(), remotekeys.len());
+        for remotekey in &remotekeys {
+            let health = res
+                .data
+                .iter()
+                .find(|health| health.pubkey == remotekey.pubkey)
+                .unwrap();
+            assert_eq!(health.url, remotekey.url);
+            assert!(!health.reachable);
+            assert!(health.error.is_some());
+        }
+    })
+    .await
+}
+
+/// Serves a Web3Signer which signs with `keypair` and lists `listed_keys` as the keys it holds.
+///
+/// Returns the URL of the server.
+fn mock_web3_signer(keypair: Keypair, listed_keys: Vec<PublicKeyBytes>) -> String {
+    use warp::Filter;
+
+    let sign = warp::post()
+        .and(warp::path!("api" / "v1" / "eth2" / "sign" / String))
+        .and(warp::body::json())
+        .map(move |_pubkey: String, request: serde_json::Value| {
+            let signing_root: Hash256 =
+                serde_json::from_value(request["signingRoot"].clone()).unwrap();
+            warp::reply::json(&serde_json::json!({
+                "signature": keypair.sk.sign(signing_root),
+            }))
+        });
+    let public_keys = warp::get()
+        .and(warp::path!("api" / "v1" / "eth2" / "publicKeys"))
+        .map(move || warp::reply::json(&listed_keys));
+
+    let (addr, server) = warp::serve(sign.or(public_keys)).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    format!("http://{}", addr)
+}
+
+#[tokio::test]
+async fn remotekeys_health_flags_missing_keys() {
+    run_test(|tester| async move {
+        // Both signers hold the key of their validator, but only the first one lists it.
+        let (listed_keypair, _) = new_remotekey_validator();
+        let (unlisted_keypair, _) = new_remotekey_validator();
+        let listed = SingleImportRemotekeysRequest {
+            pubkey: listed_keypair.pk.compress(),
+            url: mock_web3_signer(listed_keypair.clone(), vec![listed_keypair.pk.compress()]),
+        };
+        let unlisted = SingleImportRemotekeysRequest {
+            pubkey: unlisted_keypair.pk.compress(),
+            url: mock_web3_signer(unlisted_keypair.clone(), vec![]),
+        };
+        tester
+            .client
+            .post_remotekeys(&ImportRemotekeysRequest {
+                remote_keys: vec![listed.clone(), unlisted.clone()],
+            })
+            .await
+            .unwrap();
+
+        let results = tester.validator_store.check_remote_signers().await;
+        assert_eq!(results.len(), 2);
+
+        let listed_health = results
+            .iter()
+            .find(|health| health.pubkey == listed.pubkey)
+            .unwrap();
+        assert!(listed_health.reachable);
+        assert!(!listed_health.key_missing);
+        assert_eq!(listed_health.error, None);
+
+        let unlisted_health = results
+            .iter()
+            .find(|health| health.pubkey == unlisted.pubkey)
+            .unwrap();
+        assert!(unlisted_health.reachable);
+        assert!(unlisted_health.key_missing);
+        assert!(unlisted_health.error.is_some());
+    })
+    .await
+}
+
 #[tokio::test]
 async fn import_same_remotekey_different_url() {
     run_test(|tester| async move {
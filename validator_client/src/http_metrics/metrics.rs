@@ -5,6 +5,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use types::EthSpec;
 
 pub const SUCCESS: &str = "success";
+pub const FAILURE: &str = "failure";
 pub const SLASHABLE: &str = "slashable";
 pub const SAME_DATA: &str = "same_data";
 pub const UNREGISTERED: &str = "unregistered";
@@ -37,6 +38,10 @@ pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const INCLUDED: &str = "included";
+pub const SLOT_CLOCK: &str = "slot_clock";
+pub const ATTESTATION_DATA: &str = "attestation_data";
+pub const MISSED: &str = "missed";
 
 pub use lighthouse_metrics::*;
 
@@ -109,10 +114,52 @@ lazy_static::lazy_static! {
         "Duration to perform attestation service tasks",
         &["task"]
     );
+    pub static ref ATTESTATION_COMMITTEE_FAILURES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_attestation_committee_failures_total",
+        "Count of failed attempts to attest or aggregate, by committee index and the step which failed",
+        &["committee", "reason"]
+    );
     pub static ref SLASHING_PROTECTION_PRUNE_TIMES: Result<Histogram> = try_create_histogram(
         "vc_slashing_protection_prune_times_seconds",
         "Time required to prune the slashing protection DB",
     );
+    pub static ref SLASHING_PROTECTION_INTEGRITY_OK: Result<IntGauge> = try_create_int_gauge(
+        "vc_slashing_protection_integrity_ok",
+        "Set to 1 if the last integrity check of the slashing protection DB passed, 0 otherwise",
+    );
+    pub static ref SLASHING_PROTECTION_DB_SIZE: Result<IntGauge> = try_create_int_gauge(
+        "vc_slashing_protection_db_size_bytes",
+        "Size of the slashing protection DB file",
+    );
+    pub static ref SLASHING_PROTECTION_WAL_SIZE: Result<IntGauge> = try_create_int_gauge(
+        "vc_slashing_protection_wal_size_bytes",
+        "Size of the slashing protection DB write-ahead log",
+    );
+    pub static ref SLASHING_PROTECTION_LAST_CHECK: Result<IntGauge> = try_create_int_gauge(
+        "vc_slashing_protection_last_check_timestamp_seconds",
+        "Unix timestamp of the last health check of the slashing protection DB",
+    );
+    pub static ref SLASHING_PROTECTION_LAST_BACKUP: Result<IntGauge> = try_create_int_gauge(
+        "vc_slashing_protection_last_backup_timestamp_seconds",
+        "Unix timestamp of the last backup of the slashing protection DB",
+    );
+    pub static ref SLASHING_PROTECTION_CHECK_ERRORS: Result<IntCounter> = try_create_int_counter(
+        "vc_slashing_protection_check_errors_total",
+        "Count of failed health checks or backups of the slashing protection DB",
+    );
+    pub static ref REMOTE_SIGNERS_UNREACHABLE: Result<IntGauge> = try_create_int_gauge(
+        "vc_remote_signers_unreachable",
+        "Number of validators whose remote signer failed the last health check",
+    );
+    pub static ref REMOTE_SIGNER_KEYS_MISSING: Result<IntGauge> = try_create_int_gauge(
+        "vc_remote_signer_keys_missing",
+        "Number of validators whose key is not listed by their remote signer",
+    );
+    pub static ref REMOTE_SIGNER_HEALTH_CHECKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_remote_signer_health_checks_total",
+        "Total count of remote signer health checks",
+        &["status"]
+    );
     pub static ref BLOCK_SERVICE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "vc_beacon_block_service_task_times_seconds",
         "Duration to perform beacon block service tasks",
@@ -145,6 +192,11 @@ lazy_static::lazy_static! {
         "The number of beacon node requests for each endpoint",
         &["endpoint"]
     );
+    pub static ref ENDPOINT_MISSED_INCLUSIONS: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "bn_endpoint_missed_inclusions",
+        "The number of consecutive attestations published via each endpoint which were not included",
+        &["endpoint"]
+    );
 
     /*
     * Beacon node availability metrics
@@ -183,12 +235,48 @@ lazy_static::lazy_static! {
         "vc_block_signing_times_seconds",
         "Duration to obtain a signature for a block",
     );
+    pub static ref SIGNING_ACTIVE: Result<IntGauge> = try_create_int_gauge(
+        "vc_signing_active",
+        "Number of signatures currently being obtained",
+    );
+    pub static ref SIGNING_QUEUE_DEPTH: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_signing_queue_depth",
+        "Number of signatures waiting for the signing concurrency limit",
+        &["priority"]
+    );
+    pub static ref SIGNING_QUEUE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "vc_signing_queue_times_seconds",
+        "Duration spent waiting for the signing concurrency limit",
+        &["priority"]
+    );
 
     pub static ref ATTESTATION_DUTY: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "vc_attestation_duty_slot",
         "Attestation duty slot for all managed validators",
         &["validator"]
     );
+    pub static ref ATTESTATION_INCLUSION_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_attestation_inclusion_total",
+        "Count of published attestations checked for inclusion on chain",
+        &["status"]
+    );
+    pub static ref ATTESTATION_INCLUSION_DELAY: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_attestation_inclusion_delay_slots",
+        "Inclusion delay of the most recently included attestation for all managed validators",
+        &["validator"]
+    );
+    /*
+     * Sync committee subscriptions
+     */
+    pub static ref SYNC_COMMITTEE_NEXT_PERIOD_DUTIES_LOOKAHEAD: Result<IntGauge> = try_create_int_gauge(
+        "vc_sync_committee_next_period_duties_lookahead_epochs",
+        "Number of epochs before the start of the next sync committee period at which its duties were fetched",
+    );
+    pub static ref SYNC_COMMITTEE_SUBSCRIPTION_LOOKAHEAD: Result<Histogram> = try_create_histogram_with_buckets(
+        "vc_sync_committee_subscription_lookahead_slots",
+        "Number of slots before the start of a sync committee period at which subscriptions for it were first published",
+        Ok(vec![0.0, 1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0]),
+    );
     /*
      * BN latency
      */
@@ -15,6 +15,7 @@ use account_utils::{
     },
     ZeroizeString,
 };
+use eth2::lighthouse_vc::types::{MigrationRecord, SignedMigrationRecord};
 use eth2_keystore::Keystore;
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
@@ -26,9 +27,9 @@ use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use types::graffiti::GraffitiString;
-use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
+use types::{Address, Epoch, Graffiti, Keypair, PublicKey, PublicKeyBytes};
 use url::{ParseError, Url};
 use validator_dir::Builder as ValidatorDirBuilder;
 
@@ -44,6 +45,10 @@ const DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT: Duration = Duration::from_secs(12);
 // Use TTY instead of stdin to capture passwords from users.
 const USE_STDIN: bool = false;
 
+/// The file in the validators directory which stores the `SignedMigrationRecord`s of validators
+/// that were exported for migration to another machine.
+pub const MIGRATION_LOCKS_FILENAME: &str = "migration_locks.json";
+
 pub enum OnDecryptFailure {
     /// If the key cache fails to decrypt, create a new cache.
     CreateNew,
@@ -116,6 +121,12 @@ pub enum Error {
     UnableToSaveKeyCache(key_cache::Error),
     UnableToDecryptKeyCache(key_cache::Error),
     UnableToDeletePasswordFile(PathBuf, io::Error),
+    /// The validator was exported for migration and cannot be enabled until `reenable_epoch`.
+    MigrationLocked {
+        reenable_epoch: Epoch,
+    },
+    UnableToReadMigrationLocks(String),
+    UnableToSaveMigrationLocks(String),
 }
 
 impl From<LockfileError> for Error {
@@ -147,7 +158,7 @@ impl InitializedValidator {
             })
             .ok(),
             // Web3Signer validators do not have any lockfiles.
-            SigningMethod::Web3Signer { .. } => None,
+            SigningMethod::Web3Signer { .. } | SigningMethod::Placeholder { .. } => None,
         }
     }
 
@@ -341,15 +352,38 @@ impl InitializedValidator {
 
     /// Returns the voting public key for this validator.
     pub fn voting_public_key(&self) -> &PublicKey {
-        match self.signing_method.as_ref() {
-            SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
-            SigningMethod::Web3Signer {
-                voting_public_key, ..
-            } => voting_public_key,
-        }
+        self.signing_method.voting_public_key()
     }
 }
 
+/// Builds a `SigningMethod` for `voting_public_key` from `web3_signer`, without adding it to any
+/// `InitializedValidators`.
+///
+/// This allows a remote signer to be checked before the validator is enabled.
+pub fn web3_signer_signing_method(
+    voting_public_key: &PublicKey,
+    web3_signer: &Web3SignerDefinition,
+) -> Result<SigningMethod, Error> {
+    let signing_url = build_web3_signer_url(&web3_signer.url, voting_public_key)
+        .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+    let request_timeout = web3_signer
+        .request_timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
+    let http_client = build_web3_signer_client(
+        web3_signer.root_certificate_path.clone(),
+        web3_signer.client_identity_path.clone(),
+        web3_signer.client_identity_password.clone(),
+        request_timeout,
+    )?;
+
+    Ok(SigningMethod::Web3Signer {
+        signing_url,
+        http_client,
+        voting_public_key: voting_public_key.clone(),
+    })
+}
+
 pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate, Error> {
     let mut buf = Vec::new();
     File::open(&pem_path)
@@ -376,6 +410,11 @@ fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Resul
     Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
 
+/// Returns the URL at which the Web3Signer at `base_url` lists the public keys it holds.
+pub fn build_web3_signer_public_keys_url(base_url: &str) -> Result<Url, ParseError> {
+    Url::parse(base_url)?.join("api/v1/eth2/publicKeys")
+}
+
 fn build_web3_signer_client(
     root_certificate_path: Option<PathBuf>,
     client_identity_path: Option<PathBuf>,
@@ -461,6 +500,9 @@ pub struct InitializedValidators {
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
     /// The clients used for communications with a remote signer.
     web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    /// Validators which were exported for migration, and must not be enabled again until their
+    /// waiting period has passed.
+    migration_locks: Vec<SignedMigrationRecord>,
     /// For logging via `slog`.
     log: Logger,
 }
@@ -472,17 +514,56 @@ impl InitializedValidators {
         validators_dir: PathBuf,
         log: Logger,
     ) -> Result<Self, Error> {
+        let migration_locks = load_migration_locks(&validators_dir)?;
         let mut this = Self {
             validators_dir,
             definitions,
             validators: HashMap::default(),
             web3_signer_client_map: None,
+            migration_locks,
             log,
         };
         this.update_validators().await?;
         Ok(this)
     }
 
+    /// Instantiates `Self` with a `SigningMethod::Placeholder` for each enabled validator in
+    /// `definitions`.
+    ///
+    /// Neither keystores nor remote signers are accessed, so the validators can't produce valid
+    /// signatures. Used by dry runs.
+    pub fn placeholders(
+        definitions: ValidatorDefinitions,
+        validators_dir: PathBuf,
+        log: Logger,
+    ) -> Result<Self, Error> {
+        let migration_locks = load_migration_locks(&validators_dir)?;
+        let mut validators = HashMap::new();
+        for def in definitions.as_slice().iter().filter(|def| def.enabled) {
+            let validator = InitializedValidator {
+                signing_method: Arc::new(SigningMethod::Placeholder {
+                    voting_public_key: def.voting_public_key.clone(),
+                }),
+                graffiti: def.graffiti.clone().map(Into::into),
+                suggested_fee_recipient: def.suggested_fee_recipient,
+                gas_limit: def.gas_limit,
+                builder_proposals: def.builder_proposals,
+                index: None,
+            };
+            validators
+                .entry(def.voting_public_key.compress())
+                .or_insert(validator);
+        }
+        Ok(Self {
+            validators_dir,
+            definitions,
+            validators,
+            web3_signer_client_map: None,
+            migration_locks,
+            log,
+        })
+    }
+
     /// The count of enabled validators contained in `self`.
     pub fn num_enabled(&self) -> usize {
         self.validators.len()
@@ -517,6 +598,10 @@ impl InitializedValidators {
         &mut self,
         def: ValidatorDefinition,
     ) -> Result<(), Error> {
+        if def.enabled {
+            self.check_migration_lock(&def.voting_public_key.compress())?;
+        }
+
         // Drop any disabled definitions with the same public key.
         let delete_def = |existing_def: &ValidatorDefinition| {
             !existing_def.enabled && existing_def.voting_public_key == def.voting_public_key
@@ -537,6 +622,9 @@ impl InitializedValidators {
         {
             return Err(Error::DuplicatePublicKey);
         }
+        if def.enabled {
+            self.check_migration_lock(&def.voting_public_key.compress())?;
+        }
 
         self.definitions.push(def);
 
@@ -775,6 +863,10 @@ impl InitializedValidators {
         builder_proposals: Option<bool>,
         graffiti: Option<GraffitiString>,
     ) -> Result<(), Error> {
+        if enabled == Some(true) {
+            self.check_migration_lock(&voting_public_key.compress())?;
+        }
+
         if let Some(def) = self
             .definitions
             .as_mut_slice()
@@ -821,6 +913,51 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Returns the migration locks of validators that were exported for migration.
+    pub fn migration_locks(&self) -> &[SignedMigrationRecord] {
+        &self.migration_locks
+    }
+
+    /// Returns the migration lock for `pubkey` if it's waiting period has not yet passed.
+    fn active_migration_lock(&self, pubkey: &PublicKeyBytes) -> Option<&MigrationRecord> {
+        let now = unix_timestamp();
+        self.migration_locks
+            .iter()
+            .map(|lock| &lock.record)
+            .find(|record| record.pubkey == *pubkey && now < record.reenable_time)
+    }
+
+    /// Returns an error if `pubkey` must not be enabled due to an active migration lock.
+    pub fn check_migration_lock(&self, pubkey: &PublicKeyBytes) -> Result<(), Error> {
+        match self.active_migration_lock(pubkey) {
+            Some(record) => Err(Error::MigrationLocked {
+                reenable_epoch: record.reenable_epoch,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Records that validators were exported for migration, preventing each from being enabled
+    /// until the waiting period in its lock has passed. Any previous lock for the same validators
+    /// and any expired locks are removed.
+    ///
+    /// Saves the migration locks to file, once for all of `locks`.
+    pub fn add_migration_locks(&mut self, locks: Vec<SignedMigrationRecord>) -> Result<(), Error> {
+        let now = unix_timestamp();
+        self.migration_locks.retain(|existing| {
+            locks
+                .iter()
+                .all(|lock| existing.record.pubkey != lock.record.pubkey)
+                && now < existing.record.reenable_time
+        });
+        self.migration_locks.extend(locks);
+
+        let bytes = serde_json::to_vec_pretty(&self.migration_locks)
+            .map_err(|e| Error::UnableToSaveMigrationLocks(format!("{:?}", e)))?;
+        fs::write(self.validators_dir.join(MIGRATION_LOCKS_FILENAME), bytes)
+            .map_err(|e| Error::UnableToSaveMigrationLocks(format!("{:?}", e)))
+    }
+
     /// Sets the `InitializedValidator` and `ValidatorDefinition` `suggested_fee_recipient` values.
     ///
     /// ## Notes
@@ -1100,6 +1237,17 @@ impl InitializedValidators {
             KeyCache::new()
         };
 
+        let migration_locked = self
+            .definitions
+            .as_slice()
+            .iter()
+            .filter_map(|def| {
+                let pubkey_bytes = def.voting_public_key.compress();
+                self.active_migration_lock(&pubkey_bytes)
+                    .map(|record| (pubkey_bytes, record.reenable_epoch))
+            })
+            .collect::<HashMap<_, _>>();
+
         let mut disabled_uuids = HashSet::new();
         for def in self.definitions.as_slice() {
             if def.enabled {
@@ -1109,6 +1257,16 @@ impl InitializedValidators {
                     continue;
                 }
 
+                if let Some(reenable_epoch) = migration_locked.get(&pubkey_bytes) {
+                    warn!(
+                        self.log,
+                        "Not enabling migrated validator";
+                        "reenable_epoch" => reenable_epoch,
+                        "validator" => format!("{:?}", def.voting_public_key),
+                    );
+                    continue;
+                }
+
                 match &def.signing_definition {
                     SigningDefinition::LocalKeystore {
                         voting_keystore_path,
@@ -1310,3 +1468,22 @@ impl InitializedValidators {
         self.definitions.as_mut_slice()
     }
 }
+
+/// Loads the migration locks from the validators directory, if any have been saved.
+fn load_migration_locks(validators_dir: &Path) -> Result<Vec<SignedMigrationRecord>, Error> {
+    let path = validators_dir.join(MIGRATION_LOCKS_FILENAME);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let bytes =
+        fs::read(&path).map_err(|e| Error::UnableToReadMigrationLocks(format!("{:?}", e)))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| Error::UnableToReadMigrationLocks(format!("{:?}", e)))
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
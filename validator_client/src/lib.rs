@@ -1,20 +1,31 @@
+mod attestation_inclusion;
 mod attestation_service;
+mod beacon_node_discovery;
 mod beacon_node_fallback;
 mod block_service;
 mod check_synced;
 mod cli;
+mod clock_skew_monitor;
 mod config;
 mod duties_service;
+mod duty_recorder;
+mod fee_recipient_file;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
 mod latency;
 mod notifier;
 mod preparation_service;
+mod remote_signer_monitor;
+mod signing_ledger;
 mod signing_method;
+mod signing_scheduler;
+mod slashing_protection_monitor;
 mod sync_committee_service;
+mod validator_monitor_registration;
 
 mod doppelganger_service;
+pub mod dry_run;
 pub mod http_api;
 pub mod initialized_validators;
 pub mod validator_store;
@@ -27,6 +38,7 @@ use monitoring_api::{MonitoringHttpClient, ProcessType};
 use sensitive_url::SensitiveUrl;
 pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 
+use crate::beacon_node_discovery::start_beacon_node_discovery_service;
 use crate::beacon_node_fallback::{
     start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode, OfflineOnFailure,
     RequireSynced,
@@ -39,13 +51,16 @@ use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
 use duties_service::DutiesService;
+use duty_recorder::DutyRecorder;
 use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, types::Graffiti, BeaconNodeHttpClient, StatusCode, Timeouts};
 use http_api::ApiSecret;
 use notifier::spawn_notifier;
+use otlp::OtlpExporter;
 use parking_lot::RwLock;
 use preparation_service::{PreparationService, PreparationServiceBuilder};
 use reqwest::Certificate;
+use signing_ledger::{SigningLedger, SIGNING_LEDGER_FILENAME};
 use slog::{error, info, warn, Logger};
 use slot_clock::SlotClock;
 use slot_clock::SystemTimeSlotClock;
@@ -100,6 +115,8 @@ pub struct ProductionValidatorClient<T: EthSpec> {
     http_api_listen_addr: Option<SocketAddr>,
     config: Config,
     beacon_nodes: Arc<BeaconNodeFallback<SystemTimeSlotClock, T>>,
+    duty_recorder: Option<Arc<DutyRecorder>>,
+    signing_ledger: Option<Arc<SigningLedger>>,
     genesis_time: u64,
 }
 
@@ -270,58 +287,27 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .checked_sub(1)
             .ok_or_else(|| "No beacon nodes defined.".to_string())?;
 
+        let slot_duration = Duration::from_secs(context.eth2_config.spec.seconds_per_slot);
         let beacon_node_setup = |x: (usize, &SensitiveUrl)| {
-            let i = x.0;
-            let url = x.1;
-            let slot_duration = Duration::from_secs(context.eth2_config.spec.seconds_per_slot);
-
-            let mut beacon_node_http_client_builder = ClientBuilder::new();
-
-            // Add new custom root certificates if specified.
-            if let Some(certificates) = &config.beacon_nodes_tls_certs {
-                for cert in certificates {
-                    beacon_node_http_client_builder = beacon_node_http_client_builder
-                        .add_root_certificate(load_pem_certificate(cert)?);
-                }
-            }
-
-            let beacon_node_http_client = beacon_node_http_client_builder
-                // Set default timeout to be the full slot duration.
-                .timeout(slot_duration)
-                .build()
-                .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+            let (i, url) = x;
 
             // Use quicker timeouts if a fallback beacon node exists.
-            let timeouts = if i < last_beacon_node_index && !config.use_long_timeouts {
+            let use_fallback_timeouts = (i < last_beacon_node_index
+                || config.beacon_nodes_dns.is_some())
+                && !config.use_long_timeouts;
+            if use_fallback_timeouts {
                 info!(
                     log,
                     "Fallback endpoints are available, using optimized timeouts.";
                 );
-                Timeouts {
-                    attestation: slot_duration / HTTP_ATTESTATION_TIMEOUT_QUOTIENT,
-                    attester_duties: slot_duration / HTTP_ATTESTER_DUTIES_TIMEOUT_QUOTIENT,
-                    liveness: slot_duration / HTTP_LIVENESS_TIMEOUT_QUOTIENT,
-                    proposal: slot_duration / HTTP_PROPOSAL_TIMEOUT_QUOTIENT,
-                    proposer_duties: slot_duration / HTTP_PROPOSER_DUTIES_TIMEOUT_QUOTIENT,
-                    sync_committee_contribution: slot_duration
-                        / HTTP_SYNC_COMMITTEE_CONTRIBUTION_TIMEOUT_QUOTIENT,
-                    sync_duties: slot_duration / HTTP_SYNC_DUTIES_TIMEOUT_QUOTIENT,
-                    get_beacon_blocks_ssz: slot_duration
-                        / HTTP_GET_BEACON_BLOCK_SSZ_TIMEOUT_QUOTIENT,
-                    get_debug_beacon_states: slot_duration / HTTP_GET_DEBUG_BEACON_STATE_QUOTIENT,
-                    get_deposit_snapshot: slot_duration / HTTP_GET_DEPOSIT_SNAPSHOT_QUOTIENT,
-                    get_validator_block_ssz: slot_duration
-                        / HTTP_GET_VALIDATOR_BLOCK_SSZ_TIMEOUT_QUOTIENT,
-                }
-            } else {
-                Timeouts::set_all(slot_duration)
-            };
+            }
 
-            Ok(BeaconNodeHttpClient::from_components(
+            build_beacon_node_http_client(
                 url.clone(),
-                beacon_node_http_client,
-                timeouts,
-            ))
+                &config,
+                slot_duration,
+                use_fallback_timeouts,
+            )
         };
 
         let beacon_nodes: Vec<BeaconNodeHttpClient> = config
@@ -401,9 +387,22 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         beacon_nodes.set_slot_clock(slot_clock.clone());
         proposer_nodes.set_slot_clock(slot_clock.clone());
 
+        if config.attestation_inclusion_check_delay.is_some() {
+            beacon_nodes.set_inclusion_miss_threshold(config.attestation_inclusion_miss_threshold);
+        }
+
         let beacon_nodes = Arc::new(beacon_nodes);
         start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
 
+        if let Some(dns_name) = &config.beacon_nodes_dns {
+            start_beacon_node_discovery_service(
+                context.clone(),
+                dns_name.clone(),
+                config.clone(),
+                beacon_nodes.clone(),
+            );
+        }
+
         let proposer_nodes = Arc::new(proposer_nodes);
         start_fallback_updater_service(context.clone(), proposer_nodes.clone())?;
 
@@ -451,12 +450,16 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             attesters: <_>::default(),
             proposers: <_>::default(),
             sync_duties: <_>::default(),
+            duty_sources: <_>::default(),
+            refresh_times: <_>::default(),
             slot_clock: slot_clock.clone(),
             beacon_nodes: beacon_nodes.clone(),
             validator_store: validator_store.clone(),
             spec: context.eth2_config.spec.clone(),
             context: duties_context,
             enable_high_validator_count_metrics: config.enable_high_validator_count_metrics,
+            subscribe_head_events: config.subscribe_head_events,
+            stale_duties_epochs: config.stale_duties_epochs,
         });
 
         // Update the metrics server.
@@ -465,6 +468,55 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             ctx.shared.write().duties_service = Some(duties_service.clone());
         }
 
+        let duty_recorder = config
+            .record_duty_inputs
+            .map(|capacity| Arc::new(DutyRecorder::new(capacity)));
+
+        let signing_ledger = if config.signing_ledger {
+            let ledger_path = config.validator_dir.join(SIGNING_LEDGER_FILENAME);
+            let retention = Duration::from_secs(
+                config
+                    .signing_ledger_retention_days
+                    .saturating_mul(24 * 60 * 60),
+            );
+            let signing_ledger = SigningLedger::open(&ledger_path, retention, log.clone())
+                .map_err(|e| {
+                    format!(
+                        "Failed to open signing ledger at {:?}: {:?}",
+                        ledger_path, e
+                    )
+                })?;
+            info!(
+                log,
+                "Recording signed messages";
+                "path" => ?ledger_path,
+            );
+            Some(Arc::new(signing_ledger))
+        } else {
+            None
+        };
+
+        let otlp_exporter = config.otlp_endpoint.clone().map(|endpoint| {
+            info!(
+                log,
+                "Tracing block proposals";
+                "otlp_endpoint" => %endpoint,
+            );
+            let (exporter, task) = OtlpExporter::new(endpoint, "lighthouse-validator-client");
+            let otlp_log = log.clone();
+            context.executor.spawn(
+                task.run(move |e| {
+                    warn!(
+                        otlp_log,
+                        "Unable to export traces";
+                        "error" => ?e,
+                    )
+                }),
+                "otlp_export",
+            );
+            exporter
+        });
+
         let mut block_service_builder = BlockServiceBuilder::new()
             .slot_clock(slot_clock.clone())
             .validator_store(validator_store.clone())
@@ -472,7 +524,10 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .runtime_context(context.service_context("block".into()))
             .graffiti(config.graffiti)
             .graffiti_file(config.graffiti_file.clone())
-            .block_delay(config.block_delay);
+            .block_delay(config.block_delay)
+            .duty_recorder(duty_recorder.clone())
+            .signing_ledger(signing_ledger.clone())
+            .otlp_exporter(otlp_exporter);
 
         // If we have proposer nodes, add them to the block service builder.
         if proposer_nodes_num > 0 {
@@ -487,6 +542,9 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .attestation_inclusion_check_delay(config.attestation_inclusion_check_delay)
+            .duty_recorder(duty_recorder.clone())
+            .signing_ledger(signing_ledger.clone())
             .build()?;
 
         let preparation_service = PreparationServiceBuilder::new()
@@ -504,6 +562,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             slot_clock.clone(),
             beacon_nodes.clone(),
             context.service_context("sync_committee".into()),
+            signing_ledger.clone(),
         );
 
         Ok(Self {
@@ -520,6 +579,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             http_api_listen_addr: None,
             genesis_time,
             beacon_nodes,
+            duty_recorder,
+            signing_ledger,
         })
     }
 
@@ -538,6 +599,9 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                duties_service: Some(self.duties_service.clone()),
+                duty_recorder: self.duty_recorder.clone(),
+                signing_ledger: self.signing_ledger.clone(),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -545,6 +609,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 spec: self.context.eth2_config.spec.clone(),
                 config: self.config.http_api.clone(),
                 sse_logging_components: self.context.sse_logging_components.clone(),
+                runtime_log_filter: self.context.runtime_log_filter.clone(),
                 slot_clock: self.slot_clock.clone(),
                 log: log.clone(),
                 _phantom: PhantomData,
@@ -569,6 +634,35 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         // Wait until genesis has occurred.
         wait_for_genesis(&self.beacon_nodes, self.genesis_time, &self.context).await?;
 
+        if let Some(max_skew_slots) = self.config.max_clock_skew_slots {
+            // Refuse to start with a clock which is known to be wrong, rather than signing for the
+            // wrong slots.
+            if let Some(status) = clock_skew_monitor::check_clock_skew(
+                &self.validator_store,
+                &self.duties_service.beacon_nodes,
+                &self.slot_clock,
+                max_skew_slots,
+                log,
+            )
+            .await
+            .filter(|status| status.skew_detected)
+            {
+                return Err(format!(
+                    "Local clock disagrees with all beacon nodes, check the system time. Local \
+                     slot: {}, beacon node slots: {:?}",
+                    status.local_slot, status.beacon_node_slots
+                ));
+            }
+
+            clock_skew_monitor::start_clock_skew_monitor(
+                self.context.service_context("clock_skew_monitor".into()),
+                self.validator_store.clone(),
+                self.duties_service.beacon_nodes.clone(),
+                self.slot_clock.clone(),
+                max_skew_slots,
+            );
+        }
+
         duties_service::start_update_service(self.duties_service.clone(), block_service_tx);
 
         self.block_service
@@ -607,6 +701,34 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
 
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
+        slashing_protection_monitor::start_slashing_protection_monitor(
+            self.context
+                .service_context("slashing_protection_monitor".into()),
+            self.validator_store.clone(),
+            self.slot_clock.clone(),
+            Duration::from_secs(self.config.slashing_protection_check_interval),
+            self.config.slashing_protection_backup_dir.clone(),
+            self.config.slashing_protection_backups_to_keep,
+        );
+
+        remote_signer_monitor::start_remote_signer_monitor(
+            self.context.service_context("remote_signer_monitor".into()),
+            self.validator_store.clone(),
+            Duration::from_secs(self.config.remote_signer_check_interval),
+        );
+
+        if let Some(token) = self.config.validator_monitor_token.clone() {
+            validator_monitor_registration::start_validator_monitor_registration(
+                self.context
+                    .service_context("validator_monitor_registration".into()),
+                self.validator_store.clone(),
+                self.duties_service.beacon_nodes.clone(),
+                self.slot_clock.clone(),
+                token,
+                self.config.validator_monitor_cohort.clone(),
+            );
+        }
+
         if self.config.enable_latency_measurement_service {
             latency::start_latency_service(
                 self.context.clone(),
@@ -823,6 +945,59 @@ pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate,
     Certificate::from_pem(&buf).map_err(|e| format!("Unable to parse certificate: {}", e))
 }
 
+/// Builds a client for the beacon node HTTP API at `url`, trusting any custom certificates from
+/// `config`.
+///
+/// If `use_fallback_timeouts` is true, requests time out early so that another beacon node can be
+/// tried within the same slot.
+pub fn build_beacon_node_http_client(
+    url: SensitiveUrl,
+    config: &Config,
+    slot_duration: Duration,
+    use_fallback_timeouts: bool,
+) -> Result<BeaconNodeHttpClient, String> {
+    let mut beacon_node_http_client_builder = ClientBuilder::new();
+
+    // Add new custom root certificates if specified.
+    if let Some(certificates) = &config.beacon_nodes_tls_certs {
+        for cert in certificates {
+            beacon_node_http_client_builder =
+                beacon_node_http_client_builder.add_root_certificate(load_pem_certificate(cert)?);
+        }
+    }
+
+    let beacon_node_http_client = beacon_node_http_client_builder
+        // Set default timeout to be the full slot duration.
+        .timeout(slot_duration)
+        .build()
+        .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+
+    let timeouts = if use_fallback_timeouts {
+        Timeouts {
+            attestation: slot_duration / HTTP_ATTESTATION_TIMEOUT_QUOTIENT,
+            attester_duties: slot_duration / HTTP_ATTESTER_DUTIES_TIMEOUT_QUOTIENT,
+            liveness: slot_duration / HTTP_LIVENESS_TIMEOUT_QUOTIENT,
+            proposal: slot_duration / HTTP_PROPOSAL_TIMEOUT_QUOTIENT,
+            proposer_duties: slot_duration / HTTP_PROPOSER_DUTIES_TIMEOUT_QUOTIENT,
+            sync_committee_contribution: slot_duration
+                / HTTP_SYNC_COMMITTEE_CONTRIBUTION_TIMEOUT_QUOTIENT,
+            sync_duties: slot_duration / HTTP_SYNC_DUTIES_TIMEOUT_QUOTIENT,
+            get_beacon_blocks_ssz: slot_duration / HTTP_GET_BEACON_BLOCK_SSZ_TIMEOUT_QUOTIENT,
+            get_debug_beacon_states: slot_duration / HTTP_GET_DEBUG_BEACON_STATE_QUOTIENT,
+            get_deposit_snapshot: slot_duration / HTTP_GET_DEPOSIT_SNAPSHOT_QUOTIENT,
+            get_validator_block_ssz: slot_duration / HTTP_GET_VALIDATOR_BLOCK_SSZ_TIMEOUT_QUOTIENT,
+        }
+    } else {
+        Timeouts::set_all(slot_duration)
+    };
+
+    Ok(BeaconNodeHttpClient::from_components(
+        url,
+        beacon_node_http_client,
+        timeouts,
+    ))
+}
+
 // Given the various graffiti control methods, determine the graffiti that will be used for
 // the next block produced by the validator with the given public key.
 pub fn determine_graffiti(
@@ -184,7 +184,26 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
         let spec = spec.clone();
 
         let interval_fut = async move {
+            let mut fee_recipient_file_epoch = None;
             loop {
+                // Reload the fee recipient file once per epoch, so that edits take effect without
+                // a restart.
+                let current_epoch = self
+                    .slot_clock
+                    .now()
+                    .map(|slot| slot.epoch(E::slots_per_epoch()));
+                if current_epoch != fee_recipient_file_epoch {
+                    if let Err(e) = self.validator_store.reload_fee_recipient_file() {
+                        error!(
+                            log,
+                            "Unable to reload fee recipient file";
+                            "error" => ?e,
+                            "msg" => "the previously loaded fee recipients will be used",
+                        );
+                    }
+                    fee_recipient_file_epoch = current_epoch;
+                }
+
                 if self.should_publish_at_current_slot(&spec) {
                     // Poll the endpoint immediately to ensure fee recipients are received.
                     self.prepare_proposers_and_publish(&spec)
@@ -274,8 +293,17 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
 
     fn collect_preparation_data(&self, spec: &ChainSpec) -> Vec<ProposerPreparationData> {
         let log = self.context.log();
+        let current_slot = self.slot_clock.now();
         self.collect_proposal_data(|pubkey, proposal_data| {
-            if let Some(fee_recipient) = proposal_data.fee_recipient {
+            // A one-off override for an upcoming proposal takes precedence.
+            let fee_recipient = current_slot
+                .and_then(|slot| {
+                    self.validator_store
+                        .upcoming_fee_recipient_override(&pubkey, slot)
+                })
+                .or(proposal_data.fee_recipient);
+
+            if let Some(fee_recipient) = fee_recipient {
                 Some(ProposerPreparationData {
                     // Ignore fee recipients for keys without indices, they are inactive.
                     validator_index: proposal_data.validator_index?,
@@ -0,0 +1,39 @@
+//! Periodic health checks of the remote signers of Web3Signer validators.
+//!
+//! A validator whose remote signer is unreachable silently misses all of its duties, so each
+//! remote signer is asked for a signature on a non-slashable message at a regular interval. The
+//! results are exposed via metrics and the HTTP API.
+
+use crate::ValidatorStore;
+use environment::RuntimeContext;
+use slog::debug;
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// Starts a service that checks every remote signer on startup and then every `check_interval`.
+pub fn start_remote_signer_monitor<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    check_interval: Duration,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let results = validator_store.check_remote_signers().await;
+            debug!(
+                log,
+                "Checked remote signers";
+                "total" => results.len(),
+                "unreachable" => results.iter().filter(|health| !health.reachable).count(),
+            );
+
+            sleep(check_interval).await;
+        }
+    };
+
+    context.executor.spawn(future, "remote_signer_monitor");
+}
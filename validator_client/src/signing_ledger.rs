@@ -0,0 +1,432 @@
+//! A local ledger of the messages signed by each validator.
+//!
+//! Every signed block, attestation and sync committee message is recorded along with the time
+//! taken to sign it and the outcome of publishing it, so that the history of a validator can be
+//! audited via the HTTP API after an incident. Unlike the slashing protection database, the
+//! ledger is purely informational and is never consulted before signing.
+//!
+//! Records are written by a dedicated thread, so that a slow disk never delays the duties which
+//! produced them, and records older than the retention period are pruned periodically.
+use eth2::lighthouse_vc::types::{SignedMessageKind, SignedMessageRecord};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection};
+use slog::{warn, Logger};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use types::{PublicKeyBytes, Slot};
+
+/// The name of the ledger file in the validator directory.
+pub const SIGNING_LEDGER_FILENAME: &str = "signing_ledger.sqlite";
+
+/// The number of records returned by `SigningLedger::history` if no limit is given.
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// The default number of days for which records are kept.
+pub const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+/// The interval at which the writer prunes records older than the retention period.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// A set of signed messages waiting to be written by the writer thread.
+struct PendingRecord {
+    kind: SignedMessageKind,
+    slot: Slot,
+    signers: Vec<(PublicKeyBytes, Duration)>,
+    publication: Result<String, String>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Sqlite(rusqlite::Error),
+    WriterThread(std::io::Error),
+}
+
+impl From<rusqlite::Error> for Error {
+    fn from(e: rusqlite::Error) -> Self {
+        Error::Sqlite(e)
+    }
+}
+
+pub struct SigningLedger {
+    conn: Arc<Mutex<Connection>>,
+    sender: mpsc::UnboundedSender<PendingRecord>,
+}
+
+impl SigningLedger {
+    /// Open the ledger at `path`, creating it if it does not exist, and start the thread which
+    /// writes records to it.
+    ///
+    /// Records older than `retention` are pruned on startup and then once per hour.
+    pub fn open(path: &Path, retention: Duration, log: Logger) -> Result<Self, Error> {
+        let conn = Connection::open(path)?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS signed_messages (
+                id INTEGER PRIMARY KEY,
+                public_key BLOB NOT NULL,
+                kind TEXT NOT NULL,
+                slot INTEGER NOT NULL,
+                signing_latency_ms INTEGER NOT NULL,
+                published INTEGER NOT NULL,
+                beacon_node TEXT,
+                publication_error TEXT,
+                timestamp INTEGER NOT NULL
+            )",
+            params![],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS signed_messages_public_key
+                ON signed_messages (public_key, id)",
+            params![],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS signed_messages_timestamp
+                ON signed_messages (timestamp)",
+            params![],
+        )?;
+        prune(&conn, retention)?;
+
+        let conn = Arc::new(Mutex::new(conn));
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let writer_conn = conn.clone();
+        thread::Builder::new()
+            .name("signing_ledger".to_string())
+            .spawn(move || run_writer(writer_conn, receiver, retention, log))
+            .map_err(Error::WriterThread)?;
+
+        Ok(Self { conn, sender })
+    }
+
+    /// Record that each of `signers` signed a message of `kind` at `slot`, taking the
+    /// accompanying time to do so, and that the messages were published together with the given
+    /// result.
+    ///
+    /// The result of a successful publication is the beacon node which accepted the messages.
+    ///
+    /// The record is written before returning. Use `record_or_warn` from async tasks.
+    pub fn record(
+        &self,
+        kind: SignedMessageKind,
+        slot: Slot,
+        signers: &[(PublicKeyBytes, Duration)],
+        publication: Result<&str, String>,
+    ) -> Result<(), rusqlite::Error> {
+        insert(&mut self.conn.lock(), kind, slot, signers, publication)
+    }
+
+    /// As per `record`, but the record is written by the writer thread and a failure to write it
+    /// is logged instead of returned.
+    ///
+    /// A failure to write to the ledger should never interrupt (or delay) the duties of a
+    /// validator.
+    pub fn record_or_warn(
+        &self,
+        kind: SignedMessageKind,
+        slot: Slot,
+        signers: &[(PublicKeyBytes, Duration)],
+        publication: Result<&str, String>,
+        log: &Logger,
+    ) {
+        let record = PendingRecord {
+            kind,
+            slot,
+            signers: signers.to_vec(),
+            publication: publication.map(ToString::to_string),
+        };
+        if self.sender.send(record).is_err() {
+            warn!(
+                log,
+                "Unable to write to signing ledger";
+                "error" => "writer thread stopped",
+                "kind" => ?kind,
+                "slot" => slot,
+            );
+        }
+    }
+
+    /// Delete the records older than `retention`, returning the number deleted.
+    pub fn prune(&self, retention: Duration) -> Result<usize, rusqlite::Error> {
+        prune(&self.conn.lock(), retention)
+    }
+
+    /// Returns the `limit` most recently recorded messages signed by `pubkey`, newest first.
+    pub fn history(
+        &self,
+        pubkey: &PublicKeyBytes,
+        limit: usize,
+    ) -> Result<Vec<SignedMessageRecord>, rusqlite::Error> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT kind, slot, signing_latency_ms, published, beacon_node, publication_error,
+                timestamp
+            FROM signed_messages
+            WHERE public_key = ?1
+            ORDER BY id DESC
+            LIMIT ?2",
+        )?;
+
+        let records = stmt
+            .query_map(params![pubkey.as_serialized(), limit as i64], |row| {
+                let kind: String = row.get(0)?;
+                Ok(SignedMessageRecord {
+                    kind: kind_from_str(&kind).ok_or_else(|| {
+                        rusqlite::Error::InvalidColumnType(
+                            0,
+                            "kind".to_string(),
+                            rusqlite::types::Type::Text,
+                        )
+                    })?,
+                    slot: Slot::new(row.get::<_, i64>(1)? as u64),
+                    signing_latency_ms: row.get::<_, i64>(2)? as u64,
+                    published: row.get(3)?,
+                    beacon_node: row.get(4)?,
+                    publication_error: row.get(5)?,
+                    timestamp: row.get::<_, i64>(6)? as u64,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(records)
+    }
+}
+
+/// Writes the records received from `receiver` until every `SigningLedger` handle is dropped.
+fn run_writer(
+    conn: Arc<Mutex<Connection>>,
+    mut receiver: mpsc::UnboundedReceiver<PendingRecord>,
+    retention: Duration,
+    log: Logger,
+) {
+    let mut last_prune = Instant::now();
+    while let Some(record) = receiver.blocking_recv() {
+        let mut conn = conn.lock();
+        if let Err(e) = insert(
+            &mut conn,
+            record.kind,
+            record.slot,
+            &record.signers,
+            record.publication.as_deref().map_err(Clone::clone),
+        ) {
+            warn!(
+                log,
+                "Unable to write to signing ledger";
+                "error" => ?e,
+                "kind" => ?record.kind,
+                "slot" => record.slot,
+            );
+        }
+
+        if last_prune.elapsed() >= PRUNE_INTERVAL {
+            last_prune = Instant::now();
+            if let Err(e) = prune(&conn, retention) {
+                warn!(
+                    log,
+                    "Unable to prune signing ledger";
+                    "error" => ?e,
+                );
+            }
+        }
+    }
+}
+
+fn insert(
+    conn: &mut Connection,
+    kind: SignedMessageKind,
+    slot: Slot,
+    signers: &[(PublicKeyBytes, Duration)],
+    publication: Result<&str, String>,
+) -> Result<(), rusqlite::Error> {
+    let timestamp = unix_timestamp();
+    let (beacon_node, publication_error) = match publication {
+        Ok(beacon_node) => (Some(beacon_node), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let txn = conn.transaction()?;
+    {
+        let mut stmt = txn.prepare(
+            "INSERT INTO signed_messages (public_key, kind, slot, signing_latency_ms,
+                published, beacon_node, publication_error, timestamp)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for (pubkey, signing_latency) in signers {
+            stmt.execute(params![
+                pubkey.as_serialized(),
+                kind_to_str(kind),
+                slot.as_u64() as i64,
+                signing_latency.as_millis() as i64,
+                beacon_node.is_some(),
+                beacon_node,
+                publication_error,
+                timestamp as i64,
+            ])?;
+        }
+    }
+    txn.commit()
+}
+
+fn prune(conn: &Connection, retention: Duration) -> Result<usize, rusqlite::Error> {
+    let cutoff = unix_timestamp().saturating_sub(retention.as_secs());
+    conn.execute(
+        "DELETE FROM signed_messages WHERE timestamp < ?1",
+        params![cutoff as i64],
+    )
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn kind_to_str(kind: SignedMessageKind) -> &'static str {
+    match kind {
+        SignedMessageKind::Block => "block",
+        SignedMessageKind::Attestation => "attestation",
+        SignedMessageKind::SyncCommitteeMessage => "sync_committee_message",
+    }
+}
+
+fn kind_from_str(kind: &str) -> Option<SignedMessageKind> {
+    match kind {
+        "block" => Some(SignedMessageKind::Block),
+        "attestation" => Some(SignedMessageKind::Attestation),
+        "sync_committee_message" => Some(SignedMessageKind::SyncCommitteeMessage),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use types::Keypair;
+
+    fn open_ledger(path: &Path) -> SigningLedger {
+        let retention = Duration::from_secs(DEFAULT_RETENTION_DAYS * 24 * 60 * 60);
+        SigningLedger::open(path, retention, logging::test_logger()).unwrap()
+    }
+
+    #[test]
+    fn record_and_query_history() {
+        let dir = tempdir().unwrap();
+        let ledger = open_ledger(&dir.path().join(SIGNING_LEDGER_FILENAME));
+        let alice = Keypair::random().pk.compress();
+        let bob = Keypair::random().pk.compress();
+
+        ledger
+            .record(
+                SignedMessageKind::Attestation,
+                Slot::new(1),
+                &[
+                    (alice, Duration::from_millis(5)),
+                    (bob, Duration::from_millis(7)),
+                ],
+                Ok("http://localhost:5052/"),
+            )
+            .unwrap();
+        ledger
+            .record(
+                SignedMessageKind::Block,
+                Slot::new(2),
+                &[(alice, Duration::from_millis(20))],
+                Err("beacon node offline".to_string()),
+            )
+            .unwrap();
+
+        let history = ledger.history(&alice, DEFAULT_HISTORY_LIMIT).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].kind, SignedMessageKind::Block);
+        assert_eq!(history[0].slot, Slot::new(2));
+        assert!(!history[0].published);
+        assert_eq!(
+            history[0].publication_error.as_deref(),
+            Some("beacon node offline")
+        );
+        assert_eq!(history[1].kind, SignedMessageKind::Attestation);
+        assert_eq!(history[1].signing_latency_ms, 5);
+        assert!(history[1].published);
+        assert_eq!(
+            history[1].beacon_node.as_deref(),
+            Some("http://localhost:5052/")
+        );
+
+        assert_eq!(ledger.history(&alice, 1).unwrap().len(), 1);
+        assert_eq!(
+            ledger.history(&bob, DEFAULT_HISTORY_LIMIT).unwrap().len(),
+            1
+        );
+
+        // The ledger persists across restarts.
+        drop(ledger);
+        let ledger = open_ledger(&dir.path().join(SIGNING_LEDGER_FILENAME));
+        assert_eq!(
+            ledger.history(&alice, DEFAULT_HISTORY_LIMIT).unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn records_are_written_by_the_writer_thread() {
+        let dir = tempdir().unwrap();
+        let ledger = open_ledger(&dir.path().join(SIGNING_LEDGER_FILENAME));
+        let alice = Keypair::random().pk.compress();
+
+        ledger.record_or_warn(
+            SignedMessageKind::SyncCommitteeMessage,
+            Slot::new(3),
+            &[(alice, Duration::from_millis(2))],
+            Ok("http://localhost:5052/"),
+            &logging::test_logger(),
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let history = loop {
+            let history = ledger.history(&alice, DEFAULT_HISTORY_LIMIT).unwrap();
+            if !history.is_empty() || Instant::now() >= deadline {
+                break history;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind, SignedMessageKind::SyncCommitteeMessage);
+        assert_eq!(history[0].slot, Slot::new(3));
+    }
+
+    #[test]
+    fn prune_deletes_expired_records() {
+        let dir = tempdir().unwrap();
+        let ledger = open_ledger(&dir.path().join(SIGNING_LEDGER_FILENAME));
+        let alice = Keypair::random().pk.compress();
+
+        for slot in 0..3 {
+            ledger
+                .record(
+                    SignedMessageKind::Attestation,
+                    Slot::new(slot),
+                    &[(alice, Duration::from_millis(1))],
+                    Ok("http://localhost:5052/"),
+                )
+                .unwrap();
+        }
+
+        // Backdate the first two records by two days.
+        let two_days = 2 * 24 * 60 * 60;
+        ledger
+            .conn
+            .lock()
+            .execute(
+                "UPDATE signed_messages SET timestamp = timestamp - ?1 WHERE slot < 2",
+                params![two_days],
+            )
+            .unwrap();
+
+        assert_eq!(ledger.prune(Duration::from_secs(24 * 60 * 60)).unwrap(), 2);
+        let history = ledger.history(&alice, DEFAULT_HISTORY_LIMIT).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].slot, Slot::new(2));
+    }
+}
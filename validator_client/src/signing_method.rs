@@ -31,6 +31,8 @@ pub enum Error {
     TokioJoin(String),
     MergeForkNotSupported,
     GenesisForkVersionRequired,
+    InvalidSignature,
+    NotWeb3Signer,
 }
 
 /// Enumerates all messages that can be signed by a validator.
@@ -93,6 +95,11 @@ pub enum SigningMethod {
         http_client: Client,
         voting_public_key: PublicKey,
     },
+    /// A validator whose keys are never loaded, which signs every message with the empty
+    /// signature.
+    ///
+    /// Only used by dry runs, which must not produce valid signatures with the live keys.
+    Placeholder { voting_public_key: PublicKey },
 }
 
 /// The additional information used to construct a signature. Mostly used for protection from replay
@@ -143,6 +150,75 @@ impl SigningMethod {
             .await
     }
 
+    /// Requests a selection proof for the genesis slot and verifies the returned signature.
+    ///
+    /// Selection proofs are not slashable and are never published for the genesis slot, so this
+    /// confirms that the signer is reachable and holds the right key without any risk.
+    pub async fn check_health<T: EthSpec>(
+        &self,
+        genesis_validators_root: Hash256,
+        spec: &ChainSpec,
+        executor: &TaskExecutor,
+    ) -> Result<(), Error> {
+        let slot = spec.genesis_slot;
+        let signing_context = SigningContext {
+            domain: Domain::SelectionProof,
+            epoch: slot.epoch(T::slots_per_epoch()),
+            fork: spec.fork_at_epoch(slot.epoch(T::slots_per_epoch())),
+            genesis_validators_root,
+        };
+        let signing_root = slot.signing_root(signing_context.domain_hash(spec));
+
+        let signature = self
+            .get_signature::<T, BlindedPayload<T>>(
+                SignableMessage::SelectionProof(slot),
+                signing_context,
+                spec,
+                executor,
+            )
+            .await?;
+
+        if signature.verify(self.voting_public_key(), signing_root) {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+
+    /// Returns the public keys held by the Web3Signer of `self`, as listed at
+    /// `public_keys_url`.
+    pub async fn web3signer_public_keys(
+        &self,
+        public_keys_url: Url,
+    ) -> Result<Vec<PublicKeyBytes>, Error> {
+        match self {
+            SigningMethod::Web3Signer { http_client, .. } => http_client
+                .get(public_keys_url)
+                .send()
+                .await
+                .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+                .error_for_status()
+                .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
+                .json()
+                .await
+                .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string())),
+            SigningMethod::LocalKeystore { .. } | SigningMethod::Placeholder { .. } => {
+                Err(Error::NotWeb3Signer)
+            }
+        }
+    }
+
+    /// Returns the public key of the validator which signs with `self`.
+    pub fn voting_public_key(&self) -> &PublicKey {
+        match self {
+            SigningMethod::LocalKeystore { voting_keypair, .. } => &voting_keypair.pk,
+            SigningMethod::Web3Signer {
+                voting_public_key, ..
+            }
+            | SigningMethod::Placeholder { voting_public_key } => voting_public_key,
+        }
+    }
+
     pub async fn get_signature_from_root<T: EthSpec, Payload: AbstractExecPayload<T>>(
         &self,
         signable_message: SignableMessage<'_, T, Payload>,
@@ -241,6 +317,7 @@ impl SigningMethod {
 
                 Ok(response.signature)
             }
+            SigningMethod::Placeholder { .. } => Ok(Signature::empty()),
         }
     }
 }
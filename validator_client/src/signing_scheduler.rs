@@ -0,0 +1,372 @@
+//! Limits the number of signatures which are requested concurrently and orders those which must
+//! wait by the urgency of the message being signed.
+//!
+//! Without a limit, a large batch of validator registrations or voluntary exits may occupy a
+//! remote signer (or the blocking thread pool) at the very moment a block must be signed. With a
+//! limit, each signature waits for a permit and waiting signatures are served strictly in the
+//! order of their `SigningPriority`, then in the order in which they arrived. One permit is
+//! reserved for blocks, so that a block may be signed even whilst the remaining permits are held
+//! by less urgent signatures.
+
+use crate::http_metrics::metrics;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// The classes of signatures, from the most to the least urgent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SigningPriority {
+    /// Blocks and RANDAO reveals.
+    Block,
+    /// Aggregates, sync committee contributions and the selection proofs for both.
+    Aggregate,
+    /// Attestations and sync committee messages.
+    Attestation,
+    /// Validator registrations and voluntary exits.
+    Registration,
+}
+
+impl SigningPriority {
+    /// All priorities, in the order in which waiting signatures are served.
+    pub const ALL: [SigningPriority; 4] = [
+        SigningPriority::Block,
+        SigningPriority::Aggregate,
+        SigningPriority::Attestation,
+        SigningPriority::Registration,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the number of permits which signatures of this priority may hold between them
+    /// (along with any other signatures), given the `limit` on all permits.
+    fn limit(self, limit: usize) -> usize {
+        match self {
+            SigningPriority::Block => limit,
+            _ => limit.saturating_sub(1),
+        }
+    }
+
+    pub fn metrics_label(self) -> &'static str {
+        match self {
+            SigningPriority::Block => "block",
+            SigningPriority::Aggregate => "aggregate",
+            SigningPriority::Attestation => "attestation",
+            SigningPriority::Registration => "registration",
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// The number of permits which are currently held.
+    active: usize,
+    /// Signatures waiting for a permit, indexed by `SigningPriority::index`.
+    queues: [VecDeque<oneshot::Sender<SigningPermit>>; 4],
+}
+
+struct Inner {
+    limit: Option<usize>,
+    state: Mutex<State>,
+}
+
+impl Inner {
+    /// Returns `true` if a signature of the given `priority` may be given a permit whilst
+    /// `active` permits are held.
+    fn may_acquire(&self, priority: SigningPriority, active: usize) -> bool {
+        self.limit
+            .map_or(true, |limit| active < priority.limit(limit))
+    }
+
+    /// Passes the permit being released to the most urgent waiting signature which may hold it,
+    /// or frees it if there are none.
+    fn release(self: &Arc<Self>) {
+        let sender = {
+            let mut state = self.state.lock();
+            state.active = state.active.saturating_sub(1);
+            let active = state.active;
+            let sender = SigningPriority::ALL
+                .iter()
+                .filter(|priority| self.may_acquire(**priority, active))
+                .find_map(|priority| {
+                    let queue = &mut state.queues[priority.index()];
+                    // Skip signatures which stopped waiting, e.g. because their duty timed out.
+                    std::iter::from_fn(|| queue.pop_front()).find(|sender| !sender.is_closed())
+                });
+            if sender.is_some() {
+                state.active += 1;
+            }
+            update_metrics(&state);
+            sender
+        };
+
+        // The permit is sent without holding the lock, since it is released again (and this
+        // function re-entered) if the waiting signature stops waiting before receiving it.
+        if let Some(sender) = sender {
+            let _ = sender.send(SigningPermit {
+                scheduler: Some(self.clone()),
+            });
+        }
+    }
+
+    /// Removes the signatures of the given `priority` which stopped waiting from their queue.
+    fn remove_abandoned(&self, priority: SigningPriority) {
+        let mut state = self.state.lock();
+        state.queues[priority.index()].retain(|sender| !sender.is_closed());
+        update_metrics(&state);
+    }
+}
+
+fn update_metrics(state: &State) {
+    metrics::set_gauge(&metrics::SIGNING_ACTIVE, state.active as i64);
+    for priority in SigningPriority::ALL {
+        metrics::set_int_gauge(
+            &metrics::SIGNING_QUEUE_DEPTH,
+            &[priority.metrics_label()],
+            state.queues[priority.index()].len() as i64,
+        );
+    }
+}
+
+/// Allows a single signature to be requested. The permit is released when dropped.
+pub struct SigningPermit {
+    scheduler: Option<Arc<Inner>>,
+}
+
+impl Drop for SigningPermit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.scheduler.take() {
+            scheduler.release();
+        }
+    }
+}
+
+/// A signature waiting for a permit, which is removed from its queue if it stops waiting.
+struct Waiting {
+    receiver: oneshot::Receiver<SigningPermit>,
+    received: bool,
+    priority: SigningPriority,
+    scheduler: Arc<Inner>,
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        if !self.received {
+            // Close the receiver first, so that its sender is recognised as abandoned.
+            self.receiver.close();
+            self.scheduler.remove_abandoned(self.priority);
+        }
+    }
+}
+
+/// Hands out `SigningPermit`s, allowing at most `limit` to be held at once and at most
+/// `limit - 1` to be held by signatures other than blocks.
+pub struct SigningScheduler {
+    inner: Arc<Inner>,
+}
+
+impl SigningScheduler {
+    /// Creates a scheduler which allows `limit` concurrent signatures, or any number of
+    /// signatures if `limit` is `None`.
+    ///
+    /// A `limit` of less than 2 leaves no permits for signatures other than blocks.
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                limit,
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    /// Waits until a signature of the given `priority` may be requested.
+    pub async fn acquire(&self, priority: SigningPriority) -> SigningPermit {
+        let mut waiting = {
+            let mut state = self.inner.state.lock();
+            if self.inner.may_acquire(priority, state.active) {
+                state.active += 1;
+                metrics::set_gauge(&metrics::SIGNING_ACTIVE, state.active as i64);
+                return SigningPermit {
+                    scheduler: Some(self.inner.clone()),
+                };
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let queue = &mut state.queues[priority.index()];
+            queue.push_back(sender);
+            metrics::set_int_gauge(
+                &metrics::SIGNING_QUEUE_DEPTH,
+                &[priority.metrics_label()],
+                queue.len() as i64,
+            );
+            Waiting {
+                receiver,
+                received: false,
+                priority,
+                scheduler: self.inner.clone(),
+            }
+        };
+
+        let _timer =
+            metrics::start_timer_vec(&metrics::SIGNING_QUEUE_TIMES, &[priority.metrics_label()]);
+
+        let permit = (&mut waiting.receiver).await;
+        waiting.received = true;
+
+        // The sender is only dropped without sending if the scheduler itself is dropped, which
+        // cannot happen whilst it is borrowed here.
+        permit.unwrap_or(SigningPermit { scheduler: None })
+    }
+
+    /// Returns the number of permits which are currently held.
+    pub fn active(&self) -> usize {
+        self.inner.state.lock().active
+    }
+
+    /// Returns the number of signatures waiting for a permit with the given `priority`.
+    pub fn queue_depth(&self, priority: SigningPriority) -> usize {
+        self.inner.state.lock().queues[priority.index()].len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[tokio::test]
+    async fn unlimited_scheduler_never_waits() {
+        let scheduler = SigningScheduler::new(None);
+        let permits = (0..100)
+            .map(|_| {
+                scheduler
+                    .acquire(SigningPriority::Registration)
+                    .now_or_never()
+                    .expect("permit should be available")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(scheduler.active(), 100);
+
+        drop(permits);
+        assert_eq!(scheduler.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn waiting_signatures_are_served_by_priority() {
+        let scheduler = SigningScheduler::new(Some(2));
+        let permit = scheduler.acquire(SigningPriority::Attestation).await;
+        let reserved = scheduler.acquire(SigningPriority::Block).await;
+
+        let mut registration = Box::pin(scheduler.acquire(SigningPriority::Registration));
+        let mut attestation = Box::pin(scheduler.acquire(SigningPriority::Attestation));
+        let mut block = Box::pin(scheduler.acquire(SigningPriority::Block));
+        assert!((&mut registration).now_or_never().is_none());
+        assert!((&mut attestation).now_or_never().is_none());
+        assert!((&mut block).now_or_never().is_none());
+        assert_eq!(scheduler.queue_depth(SigningPriority::Registration), 1);
+        assert_eq!(scheduler.queue_depth(SigningPriority::Attestation), 1);
+        assert_eq!(scheduler.queue_depth(SigningPriority::Block), 1);
+
+        // The block jumps ahead of the signatures which were already waiting.
+        drop(permit);
+        assert!((&mut registration).now_or_never().is_none());
+        assert!((&mut attestation).now_or_never().is_none());
+        let permit = block.now_or_never().expect("block should hold the permit");
+        assert_eq!(scheduler.active(), 2);
+
+        // The permit which is reserved for blocks is not given to other signatures.
+        drop(reserved);
+        assert!((&mut registration).now_or_never().is_none());
+        assert!((&mut attestation).now_or_never().is_none());
+        assert_eq!(scheduler.active(), 1);
+
+        drop(permit);
+        assert!((&mut registration).now_or_never().is_none());
+        let permit = attestation
+            .now_or_never()
+            .expect("attestation should hold the permit");
+
+        drop(permit);
+        let permit = registration
+            .now_or_never()
+            .expect("registration should hold the permit");
+        assert_eq!(scheduler.active(), 1);
+
+        drop(permit);
+        assert_eq!(scheduler.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn blocks_are_reserved_a_permit() {
+        let scheduler = SigningScheduler::new(Some(3));
+        let permits = [SigningPriority::Aggregate, SigningPriority::Attestation]
+            .into_iter()
+            .map(|priority| {
+                scheduler
+                    .acquire(priority)
+                    .now_or_never()
+                    .expect("permit should be available")
+            })
+            .collect::<Vec<_>>();
+
+        // The lower classes are saturated, but a block is still signed immediately.
+        for priority in &SigningPriority::ALL[1..] {
+            let mut waiting = Box::pin(scheduler.acquire(*priority));
+            assert!((&mut waiting).now_or_never().is_none());
+        }
+        let block = scheduler
+            .acquire(SigningPriority::Block)
+            .now_or_never()
+            .expect("block should not wait");
+        assert_eq!(scheduler.active(), 3);
+
+        // Only one permit is reserved.
+        let mut waiting_block = Box::pin(scheduler.acquire(SigningPriority::Block));
+        assert!((&mut waiting_block).now_or_never().is_none());
+
+        drop(block);
+        let block = waiting_block
+            .now_or_never()
+            .expect("block should hold the permit");
+        drop(block);
+        drop(permits);
+        assert_eq!(scheduler.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn abandoned_signatures_do_not_hold_permits() {
+        let scheduler = SigningScheduler::new(Some(2));
+        let reserved = scheduler.acquire(SigningPriority::Block).await;
+        let permit = scheduler.acquire(SigningPriority::Block).await;
+
+        let mut abandoned_early = Box::pin(scheduler.acquire(SigningPriority::Block));
+        let mut abandoned_late = Box::pin(scheduler.acquire(SigningPriority::Block));
+        let mut waiting = Box::pin(scheduler.acquire(SigningPriority::Aggregate));
+        assert!((&mut abandoned_early).now_or_never().is_none());
+        assert!((&mut abandoned_late).now_or_never().is_none());
+        assert!((&mut waiting).now_or_never().is_none());
+        assert_eq!(scheduler.queue_depth(SigningPriority::Block), 2);
+
+        // A signature which stops waiting is removed from its queue straight away.
+        drop(abandoned_early);
+        assert_eq!(scheduler.queue_depth(SigningPriority::Block), 1);
+
+        // A signature which stops waiting after being sent the permit releases it.
+        drop(permit);
+        assert_eq!(scheduler.queue_depth(SigningPriority::Block), 0);
+        assert_eq!(scheduler.active(), 2);
+        drop(abandoned_late);
+        assert_eq!(scheduler.active(), 1);
+
+        drop(reserved);
+        let permit = waiting
+            .now_or_never()
+            .expect("waiting signature should hold the permit");
+        assert_eq!(scheduler.active(), 1);
+
+        drop(permit);
+        assert_eq!(scheduler.active(), 0);
+    }
+}
@@ -0,0 +1,189 @@
+//! Periodic health checks and backups of the slashing protection database.
+//!
+//! Losing or corrupting the slashing protection database exposes every validator on this host to
+//! slashing, so its integrity is checked regularly and, if configured, a rotating set of backups
+//! is written after each check which passes.
+
+use crate::ValidatorStore;
+use environment::RuntimeContext;
+use slashing_protection::SlashingDatabase;
+use slog::{crit, debug, error, info, Logger};
+use slot_clock::SlotClock;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The checks will run 3/4ths of the way through the slot, clear of block proposals and
+/// attestations.
+pub const SLOT_DELAY_MULTIPLIER: u32 = 3;
+pub const SLOT_DELAY_DENOMINATOR: u32 = 4;
+
+const BACKUP_PREFIX: &str = "slashing_protection_";
+const BACKUP_EXTENSION: &str = ".sqlite";
+
+/// Starts a service that checks the slashing protection database on startup and then every
+/// `check_interval`.
+pub fn start_slashing_protection_monitor<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    slot_clock: T,
+    check_interval: Duration,
+    backup_dir: Option<PathBuf>,
+    backups_to_keep: usize,
+) {
+    let log = context.log().clone();
+    let executor = context.executor.clone();
+
+    let future = async move {
+        loop {
+            let store = validator_store.clone();
+            let dir = backup_dir.clone();
+            if let Some(handle) = executor.spawn_blocking_handle(
+                move || store.check_slashing_protection_db(dir.as_deref(), backups_to_keep),
+                "slashing_protection_check",
+            ) {
+                match handle.await {
+                    Ok(Ok(health)) if health.integrity_ok => debug!(
+                        log,
+                        "Slashing protection DB is healthy";
+                        "size_bytes" => health.database_size_bytes,
+                        "wal_size_bytes" => health.wal_size_bytes,
+                    ),
+                    Ok(Ok(health)) => crit!(
+                        log,
+                        "Slashing protection DB failed integrity check";
+                        "errors" => ?health.integrity_errors,
+                        "msg" => "restore the database from a backup or an interchange file",
+                    ),
+                    Ok(Err(e)) => error!(
+                        log,
+                        "Unable to check slashing protection DB";
+                        "error" => e,
+                    ),
+                    Err(e) => error!(
+                        log,
+                        "Slashing protection check task failed";
+                        "error" => ?e,
+                    ),
+                }
+            }
+
+            sleep(check_interval).await;
+
+            // Wait until 3/4ths of the way through a slot, so the check doesn't hold the
+            // database lock while duties are being signed.
+            let sleep_time = slot_clock
+                .duration_to_next_slot()
+                .map(|next_slot| {
+                    next_slot
+                        + (slot_clock.slot_duration() / SLOT_DELAY_DENOMINATOR)
+                            * SLOT_DELAY_MULTIPLIER
+                })
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+        }
+    };
+
+    context
+        .executor
+        .spawn(future, "slashing_protection_monitor");
+}
+
+/// Write a backup of `db` into `backup_dir`, then delete the oldest backups so that at most
+/// `backups_to_keep` remain.
+pub fn backup_slashing_protection_db(
+    db: &SlashingDatabase,
+    backup_dir: &Path,
+    backups_to_keep: usize,
+    timestamp: u64,
+    log: &Logger,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(backup_dir).map_err(|e| {
+        format!(
+            "Unable to create backup directory {:?}: {:?}",
+            backup_dir, e
+        )
+    })?;
+
+    let backup_path = backup_dir.join(format!(
+        "{}{}{}",
+        BACKUP_PREFIX, timestamp, BACKUP_EXTENSION
+    ));
+    db.backup(&backup_path)
+        .map_err(|e| format!("Unable to write backup to {:?}: {:?}", backup_path, e))?;
+    info!(log, "Backed up slashing protection DB"; "path" => ?backup_path);
+
+    for old_backup in expired_backups(backup_dir, backups_to_keep)? {
+        fs::remove_file(&old_backup)
+            .map_err(|e| format!("Unable to delete old backup {:?}: {:?}", old_backup, e))?;
+        debug!(log, "Deleted old slashing protection backup"; "path" => ?old_backup);
+    }
+
+    Ok(backup_path)
+}
+
+/// Returns the backups in `backup_dir` beyond the newest `backups_to_keep`.
+fn expired_backups(backup_dir: &Path, backups_to_keep: usize) -> Result<Vec<PathBuf>, String> {
+    let mut backups = fs::read_dir(backup_dir)
+        .map_err(|e| format!("Unable to read backup directory {:?}: {:?}", backup_dir, e))?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let timestamp = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix(BACKUP_PREFIX)?
+                .strip_suffix(BACKUP_EXTENSION)?
+                .parse::<u64>()
+                .ok()?;
+            Some((timestamp, path))
+        })
+        .collect::<Vec<_>>();
+
+    // Newest first.
+    backups.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+
+    Ok(backups
+        .into_iter()
+        .skip(backups_to_keep)
+        .map(|(_, path)| path)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn rotate_backups() {
+        let dir = tempdir().unwrap();
+        let db = SlashingDatabase::create(&dir.path().join("db.sqlite")).unwrap();
+        let backup_dir = dir.path().join("backups");
+        let log = logging::test_logger();
+
+        // Files which aren't backups are left alone.
+        fs::create_dir_all(&backup_dir).unwrap();
+        fs::write(backup_dir.join("notes.txt"), b"").unwrap();
+
+        for timestamp in [100, 300, 200, 400] {
+            backup_slashing_protection_db(&db, &backup_dir, 2, timestamp, &log).unwrap();
+        }
+
+        let mut remaining = fs::read_dir(&backup_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec![
+                "notes.txt",
+                "slashing_protection_300.sqlite",
+                "slashing_protection_400.sqlite"
+            ]
+        );
+    }
+}
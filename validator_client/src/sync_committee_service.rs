@@ -1,10 +1,13 @@
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
 use crate::{
     duties_service::DutiesService,
+    http_metrics::metrics,
+    signing_ledger::SigningLedger,
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
     OfflineOnFailure,
 };
 use environment::RuntimeContext;
+use eth2::lighthouse_vc::types::SignedMessageKind;
 use eth2::types::BlockId;
 use futures::future::join_all;
 use futures::future::FutureExt;
@@ -12,7 +15,7 @@ use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::ops::Deref;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::time::{sleep, sleep_until, Duration, Instant};
 use types::{
@@ -48,10 +51,15 @@ pub struct Inner<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    /// Records each signed sync committee message, if enabled.
+    signing_ledger: Option<Arc<SigningLedger>>,
     /// Boolean to track whether the service has posted subscriptions to the BN at least once.
     ///
     /// This acts as a latch that fires once upon start-up, and then never again.
     first_subscription_done: AtomicBool,
+    /// The most recent future sync committee period for which subscriptions have been published,
+    /// or zero if there is none.
+    next_period_subscribed: AtomicU64,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
@@ -61,6 +69,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         slot_clock: T,
         beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
         context: RuntimeContext<E>,
+        signing_ledger: Option<Arc<SigningLedger>>,
     ) -> Self {
         Self {
             inner: Arc::new(Inner {
@@ -69,7 +78,9 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 slot_clock,
                 beacon_nodes,
                 context,
+                signing_ledger,
                 first_subscription_done: AtomicBool::new(false),
+                next_period_subscribed: AtomicU64::new(0),
             }),
         }
     }
@@ -257,6 +268,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         // Create futures to produce sync committee signatures.
         let signature_futures = validator_duties.iter().map(|duty| async move {
+            let signing_start = Instant::now();
             match self
                 .validator_store
                 .produce_sync_committee_signature(
@@ -267,7 +279,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 )
                 .await
             {
-                Ok(signature) => Some(signature),
+                Ok(signature) => Some((signature, (duty.pubkey, signing_start.elapsed()))),
                 Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
                     // A pubkey can be missing when a validator was recently
                     // removed via the API.
@@ -294,13 +306,14 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         });
 
         // Execute all the futures in parallel, collecting any successful results.
-        let committee_signatures = &join_all(signature_futures)
+        let (ref committee_signatures, signers): (Vec<_>, Vec<_>) = join_all(signature_futures)
             .await
             .into_iter()
             .flatten()
-            .collect::<Vec<_>>();
+            .unzip();
 
-        self.beacon_nodes
+        let publication = self
+            .beacon_nodes
             .first_success(
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
@@ -308,17 +321,29 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                     beacon_node
                         .post_beacon_pool_sync_committee_signatures(committee_signatures)
                         .await
+                        .map(|()| beacon_node.to_string())
                 },
             )
-            .await
-            .map_err(|e| {
-                error!(
-                    log,
-                    "Unable to publish sync committee messages";
-                    "slot" => slot,
-                    "error" => %e,
-                );
-            })?;
+            .await;
+
+        if let Some(signing_ledger) = &self.signing_ledger {
+            signing_ledger.record_or_warn(
+                SignedMessageKind::SyncCommitteeMessage,
+                slot,
+                &signers,
+                publication.as_deref().map_err(ToString::to_string),
+                log,
+            );
+        }
+
+        publication.map_err(|e| {
+            error!(
+                log,
+                "Unable to publish sync committee messages";
+                "slot" => slot,
+                "error" => %e,
+            );
+        })?;
 
         info!(
             log,
@@ -524,6 +549,10 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         // Near the end of the current period, push subscriptions for the next period to the
         // beacon node. We aggressively push every slot in the lead-up, as this is the main way
         // that we want to ensure that the BN is subscribed (well in advance).
+        //
+        // The duties for the next period are usually known from early in the current period, but
+        // the BN joins the subnets as soon as it receives a subscription, so we only start pushing
+        // them within `SUBSCRIPTION_LOOKAHEAD_EPOCHS` of the boundary.
         let lookahead_slot = slot + SUBSCRIPTION_LOOKAHEAD_EPOCHS * E::slots_per_epoch();
 
         let lookahead_period = sync_period_of_slot::<E>(lookahead_slot, spec)?;
@@ -538,6 +567,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         // Collect subscriptions.
         let mut subscriptions = vec![];
+        let mut next_period_subscriptions = None;
 
         for (duty_slot, sync_committee_period) in duty_slots {
             debug!(
@@ -551,11 +581,16 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 .sync_duties
                 .get_duties_for_slot::<E>(duty_slot, spec)
             {
-                Some(duties) => subscriptions.extend(subscriptions_from_sync_duties(
-                    duties.duties,
-                    sync_committee_period,
-                    spec,
-                )),
+                Some(duties) => {
+                    if sync_committee_period > current_period && !duties.duties.is_empty() {
+                        next_period_subscriptions = Some(sync_committee_period);
+                    }
+                    subscriptions.extend(subscriptions_from_sync_duties(
+                        duties.duties,
+                        sync_committee_period,
+                        spec,
+                    ))
+                }
                 None => {
                     debug!(
                         log,
@@ -614,6 +649,13 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 "error" => %e,
             );
             all_succeeded = false;
+        } else if let Some(period) = next_period_subscriptions {
+            observe_first_subscription_lookahead::<E>(
+                &self.next_period_subscribed,
+                period,
+                slot,
+                spec,
+            );
         }
 
         // Disable first-subscription latch once all duties have succeeded once.
@@ -625,6 +667,32 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
     }
 }
 
+/// Records how far in advance of `sync_committee_period` its subscriptions were published at
+/// `slot`, if this is the first time they were published.
+///
+/// `next_period_subscribed` holds the most recent period for which this has been recorded.
+fn observe_first_subscription_lookahead<E: EthSpec>(
+    next_period_subscribed: &AtomicU64,
+    sync_committee_period: u64,
+    slot: Slot,
+    spec: &ChainSpec,
+) -> Option<Slot> {
+    if next_period_subscribed.fetch_max(sync_committee_period, Ordering::Relaxed)
+        >= sync_committee_period
+    {
+        return None;
+    }
+
+    let period_start_slot = (spec.epochs_per_sync_committee_period * sync_committee_period)
+        .start_slot(E::slots_per_epoch());
+    let lookahead = period_start_slot.saturating_sub(slot);
+    metrics::observe(
+        &metrics::SYNC_COMMITTEE_SUBSCRIPTION_LOOKAHEAD,
+        lookahead.as_u64() as f64,
+    );
+    Some(lookahead)
+}
+
 fn sync_period_of_slot<E: EthSpec>(slot: Slot, spec: &ChainSpec) -> Result<u64, String> {
     slot.epoch(E::slots_per_epoch())
         .sync_committee_period(spec)
@@ -645,3 +713,50 @@ fn subscriptions_from_sync_duties(
             until_epoch,
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn subscription_lookahead_is_observed_once_per_period() {
+        let spec = E::default_spec();
+        let period_start_slot = |period: u64| {
+            (spec.epochs_per_sync_committee_period * period).start_slot(E::slots_per_epoch())
+        };
+        let sample_count = || {
+            metrics::SYNC_COMMITTEE_SUBSCRIPTION_LOOKAHEAD
+                .as_ref()
+                .unwrap()
+                .get_sample_count()
+        };
+        let next_period_subscribed = AtomicU64::new(0);
+        let initial_count = sample_count();
+
+        let slot = period_start_slot(5) - 3 * E::slots_per_epoch();
+        assert_eq!(
+            observe_first_subscription_lookahead::<E>(&next_period_subscribed, 5, slot, &spec),
+            Some(Slot::new(3 * E::slots_per_epoch()))
+        );
+        assert_eq!(
+            observe_first_subscription_lookahead::<E>(&next_period_subscribed, 5, slot + 1, &spec),
+            None
+        );
+        assert_eq!(sample_count(), initial_count + 1);
+
+        // A subscription published after the period started has no lookahead.
+        let slot = period_start_slot(6) + 1;
+        assert_eq!(
+            observe_first_subscription_lookahead::<E>(&next_period_subscribed, 6, slot, &spec),
+            Some(Slot::new(0))
+        );
+        assert_eq!(
+            observe_first_subscription_lookahead::<E>(&next_period_subscribed, 5, slot, &spec),
+            None
+        );
+        assert_eq!(sample_count(), initial_count + 2);
+    }
+}
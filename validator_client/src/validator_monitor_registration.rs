@@ -0,0 +1,80 @@
+//! Registration of the enabled validators with the validator monitor of each beacon node.
+//!
+//! The validator monitor of a beacon node only reports on the validators it knows about. The
+//! pubkeys of all enabled validators are posted to every beacon node once per epoch, so that
+//! validators which are added at runtime and beacon nodes which restart are covered without any
+//! manual configuration.
+
+use crate::beacon_node_fallback::{OfflineOnFailure, RequireSynced};
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::{BeaconNodeFallback, ValidatorStore};
+use environment::RuntimeContext;
+use eth2::lighthouse::ValidatorMonitorRegistration;
+use slog::{debug, error};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{EthSpec, PublicKeyBytes};
+
+/// Starts a service that registers the enabled validators with the validator monitor of every
+/// beacon node on startup and then once per epoch.
+pub fn start_validator_monitor_registration<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    slot_clock: T,
+    token: String,
+    cohort: Option<String>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let registrations = validator_store
+                .voting_pubkeys::<Vec<PublicKeyBytes>, _>(DoppelgangerStatus::ignored)
+                .into_iter()
+                .map(|pubkey| ValidatorMonitorRegistration {
+                    pubkey,
+                    cohort: cohort.clone(),
+                })
+                .collect::<Vec<_>>();
+
+            if !registrations.is_empty() {
+                let registrations = registrations.as_slice();
+                let token = token.as_str();
+                match beacon_nodes
+                    .run_on_all(
+                        RequireSynced::No,
+                        OfflineOnFailure::No,
+                        |beacon_node| async move {
+                            beacon_node
+                                .post_lighthouse_validator_monitor_validators(registrations, token)
+                                .await
+                        },
+                    )
+                    .await
+                {
+                    Ok(()) => debug!(
+                        log,
+                        "Registered validators with the validator monitor";
+                        "count" => registrations.len(),
+                    ),
+                    Err(e) => error!(
+                        log,
+                        "Unable to register validators with the validator monitor";
+                        "error" => %e,
+                    ),
+                }
+            }
+
+            let sleep_time = slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+                .unwrap_or_else(|| slot_clock.slot_duration());
+            sleep(sleep_time).await;
+        }
+    };
+
+    context
+        .executor
+        .spawn(future, "validator_monitor_registration");
+}
@@ -1,22 +1,36 @@
 use crate::{
+    block_service::ProposalOverrides,
     doppelganger_service::DoppelgangerService,
+    fee_recipient_file::{Error as FeeRecipientFileError, FeeRecipientFile},
     http_metrics::metrics,
-    initialized_validators::InitializedValidators,
+    initialized_validators::{build_web3_signer_public_keys_url, InitializedValidators},
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
+    signing_scheduler::{SigningPriority, SigningScheduler},
+    slashing_protection_monitor::backup_slashing_protection_db,
     Config,
 };
-use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use account_utils::validator_definitions::{
+    PasswordStorage, SigningDefinition, ValidatorDefinition,
+};
+use eth2::lighthouse_vc::types::{
+    ClockSkewStatus, FeeRecipientResolution, FeeRecipientSource, RemoteSignerHealth,
+    SlashingProtectionHealth,
+};
+use futures::future::join_all;
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
 };
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use task_executor::TaskExecutor;
+use tokio::time::timeout;
 use types::{
     attestation::Error as AttestationError, graffiti::GraffitiString, AbstractExecPayload, Address,
     AggregateAndProof, Attestation, BeaconBlock, BlindedPayload, ChainSpec, ContributionAndProof,
@@ -42,6 +56,8 @@ pub enum Error {
     GreaterThanCurrentEpoch { epoch: Epoch, current_epoch: Epoch },
     UnableToSignAttestation(AttestationError),
     UnableToSign(SigningError),
+    ClockSkewDetected,
+    HealthCheckTimedOut,
 }
 
 impl From<SigningError> for Error {
@@ -55,6 +71,9 @@ impl From<SigningError> for Error {
 /// This acts as a maximum safe-guard against clock drift.
 const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
 
+/// The time allowed for a signing method to produce a signature during a health check.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Currently used as the default gas limit in execution clients.
 ///
 /// https://github.com/ethereum/builder-specs/issues/17
@@ -89,14 +108,22 @@ pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
     slashing_protection: SlashingDatabase,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
+    slashing_protection_health: RwLock<Option<SlashingProtectionHealth>>,
+    clock_skew_status: RwLock<Option<ClockSkewStatus>>,
+    remote_signer_health: RwLock<Vec<RemoteSignerHealth>>,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
+    fee_recipient_file: Option<RwLock<FeeRecipientFile>>,
     gas_limit: Option<u64>,
     builder_proposals: bool,
+    /// One-off overrides for upcoming block proposals, keyed by validator and slot.
+    proposal_overrides: RwLock<HashMap<(PublicKeyBytes, Slot), ProposalOverrides>>,
+    /// Limits the number of concurrent signatures, serving the most urgent first.
+    signing_scheduler: SigningScheduler,
     task_executor: TaskExecutor,
     _phantom: PhantomData<E>,
 }
@@ -120,14 +147,20 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
             slashing_protection_last_prune: Arc::new(Mutex::new(Epoch::new(0))),
+            slashing_protection_health: RwLock::new(None),
+            clock_skew_status: RwLock::new(None),
+            remote_signer_health: RwLock::new(vec![]),
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
             doppelganger_service,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
+            fee_recipient_file: config.fee_recipient_file.clone().map(RwLock::new),
             gas_limit: config.gas_limit,
             builder_proposals: config.builder_proposals,
+            proposal_overrides: RwLock::new(HashMap::new()),
+            signing_scheduler: SigningScheduler::new(config.signing_concurrency_limit),
             task_executor,
             _phantom: PhantomData,
         }
@@ -235,7 +268,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map(|validator| ProposalData {
                 validator_index: validator.get_index(),
                 fee_recipient: self
-                    .get_fee_recipient_defaulting(validator.get_suggested_fee_recipient()),
+                    .get_fee_recipient_defaulting(pubkey, validator.get_suggested_fee_recipient()),
                 gas_limit: self.get_gas_limit_defaulting(validator.get_gas_limit()),
                 builder_proposals: self
                     .get_builder_proposals_defaulting(validator.get_builder_proposals()),
@@ -342,6 +375,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
+        self.check_clock_skew()?;
         if self.doppelganger_protection_allows_signing(validator_pubkey) {
             self.validators
                 .read()
@@ -362,12 +396,28 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
+        self.check_clock_skew()?;
         self.validators
             .read()
             .signing_method(&validator_pubkey)
             .ok_or(Error::UnknownPubkey(validator_pubkey))
     }
 
+    /// Returns an error if the local clock disagrees with the beacon nodes, since messages signed
+    /// with the wrong slot are at best useless.
+    fn check_clock_skew(&self) -> Result<(), Error> {
+        if self
+            .clock_skew_status
+            .read()
+            .as_ref()
+            .map_or(false, |status| status.skew_detected)
+        {
+            Err(Error::ClockSkewDetected)
+        } else {
+            Ok(())
+        }
+    }
+
     fn signing_context(&self, domain: Domain, signing_epoch: Epoch) -> SigningContext {
         SigningContext {
             domain,
@@ -385,6 +435,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
         let signing_context = self.signing_context(Domain::Randao, signing_epoch);
 
+        let _permit = self.signing_scheduler.acquire(SigningPriority::Block).await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::RandaoReveal(signing_epoch),
@@ -404,16 +455,75 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// Returns the fee recipient for the given public key. The priority order for fetching
     /// the fee recipient is:
     /// 1. validator_definitions.yml
-    /// 2. process level fee recipient
+    /// 2. fee recipient file
+    /// 3. process level fee recipient
     pub fn get_fee_recipient(&self, validator_pubkey: &PublicKeyBytes) -> Option<Address> {
         // If there is a `suggested_fee_recipient` in the validator definitions yaml
         // file, use that value.
-        self.get_fee_recipient_defaulting(self.suggested_fee_recipient(validator_pubkey))
+        self.get_fee_recipient_defaulting(
+            validator_pubkey,
+            self.suggested_fee_recipient(validator_pubkey),
+        )
+    }
+
+    pub fn get_fee_recipient_defaulting(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        fee_recipient: Option<Address>,
+    ) -> Option<Address> {
+        fee_recipient
+            // If there's nothing in the definitions, try the fee recipient file.
+            .or_else(|| self.file_fee_recipient(validator_pubkey))
+            // If there's nothing in the file, try the process-level default value.
+            .or(self.fee_recipient_process)
     }
 
-    pub fn get_fee_recipient_defaulting(&self, fee_recipient: Option<Address>) -> Option<Address> {
-        // If there's nothing in the file, try the process-level default value.
-        fee_recipient.or(self.fee_recipient_process)
+    /// Returns the fee recipient for the given public key from the fee recipient file, if any.
+    fn file_fee_recipient(&self, validator_pubkey: &PublicKeyBytes) -> Option<Address> {
+        self.fee_recipient_file
+            .as_ref()
+            .and_then(|file| file.read().get_fee_recipient(validator_pubkey))
+    }
+
+    /// Re-reads the fee recipient file, if one is configured.
+    ///
+    /// If the file can't be read or is invalid, the previously loaded fee recipients are kept
+    /// and an error is returned.
+    pub fn reload_fee_recipient_file(&self) -> Result<(), FeeRecipientFileError> {
+        if let Some(file) = &self.fee_recipient_file {
+            let mut reloaded = file.read().clone();
+            reloaded.read_fee_recipient_file()?;
+            *file.write() = reloaded;
+        }
+        Ok(())
+    }
+
+    /// Returns the fee recipient configured for the given public key by each source, along with
+    /// the one which takes priority.
+    pub fn fee_recipient_resolution(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+    ) -> FeeRecipientResolution {
+        let definition = self.suggested_fee_recipient(validator_pubkey);
+        let file = self.file_fee_recipient(validator_pubkey);
+        let process_default = self.fee_recipient_process;
+
+        let effective = [
+            (definition, FeeRecipientSource::Definition),
+            (file, FeeRecipientSource::File),
+            (process_default, FeeRecipientSource::ProcessDefault),
+        ]
+        .into_iter()
+        .find_map(|(fee_recipient, source)| Some((fee_recipient?, source)));
+
+        FeeRecipientResolution {
+            pubkey: *validator_pubkey,
+            fee_recipient: effective.map(|(fee_recipient, _)| fee_recipient),
+            source: effective.map(|(_, source)| source),
+            definition,
+            file,
+            process_default,
+        }
     }
 
     /// Returns the suggested_fee_recipient from `validator_definitions.yml` if any.
@@ -463,6 +573,79 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .unwrap_or(self.builder_proposals)
     }
 
+    /// Sets one-off overrides for the block proposed by the given validator at `slot`, replacing
+    /// any previous overrides for that slot. Empty overrides are removed.
+    ///
+    /// Returns an error if `slot` is in the past or beyond the next epoch, which bounds the number
+    /// of overrides that may be held for each validator.
+    pub fn set_proposal_overrides(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        slot: Slot,
+        overrides: ProposalOverrides,
+    ) -> Result<(), String> {
+        let current_slot = self
+            .slot_clock
+            .now_or_genesis()
+            .ok_or("unable to read slot clock")?;
+        if slot < current_slot {
+            return Err(format!(
+                "slot {} is prior to the current slot {}",
+                slot, current_slot
+            ));
+        }
+        let last_slot =
+            (current_slot.epoch(E::slots_per_epoch()) + 1).end_slot(E::slots_per_epoch());
+        if slot > last_slot {
+            return Err(format!(
+                "slot {} is beyond the end of the next epoch at slot {}",
+                slot, last_slot
+            ));
+        }
+
+        let mut proposal_overrides = self.proposal_overrides.write();
+        if overrides.is_empty() {
+            proposal_overrides.remove(&(validator_pubkey, slot));
+        } else {
+            proposal_overrides.insert((validator_pubkey, slot), overrides);
+        }
+        Ok(())
+    }
+
+    /// Returns the overrides for the block proposed by the given validator at `slot`, if any.
+    pub fn proposal_overrides(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+    ) -> Option<ProposalOverrides> {
+        self.proposal_overrides
+            .read()
+            .get(&(*validator_pubkey, slot))
+            .cloned()
+    }
+
+    /// Returns the fee recipient override for a proposal by the given validator at `slot` or the
+    /// slot after it. The beacon node must learn of it in advance in order to prepare a payload.
+    pub fn upcoming_fee_recipient_override(
+        &self,
+        validator_pubkey: &PublicKeyBytes,
+        slot: Slot,
+    ) -> Option<Address> {
+        let proposal_overrides = self.proposal_overrides.read();
+        [slot, slot + 1].into_iter().find_map(|slot| {
+            proposal_overrides
+                .get(&(*validator_pubkey, slot))?
+                .fee_recipient
+        })
+    }
+
+    /// Removes the overrides for all proposals prior to `current_slot`.
+    pub fn prune_proposal_overrides(&self, current_slot: Slot) {
+        self.proposal_overrides
+            .write()
+            .retain(|(_, slot), _| *slot >= current_slot);
+    }
+
     pub async fn sign_block<Payload: AbstractExecPayload<E>>(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -500,6 +683,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                 metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::SUCCESS]);
 
                 let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+                let _permit = self.signing_scheduler.acquire(SigningPriority::Block).await;
                 let signature = signing_method
                     .get_signature::<E, Payload>(
                         SignableMessage::BeaconBlock(&block),
@@ -569,6 +753,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             // We can safely sign this attestation.
             Ok(Safe::Valid) => {
                 let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+                let _permit = self
+                    .signing_scheduler
+                    .acquire(SigningPriority::Attestation)
+                    .await;
                 let signature = signing_method
                     .get_signature::<E, BlindedPayload<E>>(
                         SignableMessage::AttestationData(&attestation.data),
@@ -634,6 +822,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let signing_context = self.signing_context(Domain::VoluntaryExit, signing_epoch);
         let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
 
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Registration)
+            .await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::VoluntaryExit(&voluntary_exit),
@@ -660,6 +852,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         let signing_method =
             self.doppelganger_bypassed_signing_method(validator_registration_data.pubkey)?;
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Registration)
+            .await;
         let signature = signing_method
             .get_signature_from_root::<E, BlindedPayload<E>>(
                 SignableMessage::ValidatorRegistration(&validator_registration_data),
@@ -701,6 +897,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         };
 
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Aggregate)
+            .await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SignedAggregateAndProof(&message),
@@ -734,6 +934,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // be published on the network.
         let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
 
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Aggregate)
+            .await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SelectionProof(slot),
@@ -773,6 +977,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             subcommittee_index: subnet_id.into(),
         };
 
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Aggregate)
+            .await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SyncSelectionProof(&message),
@@ -799,6 +1007,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         // Bypass `with_validator_signing_method`: sync committee messages are not slashable.
         let signing_method = self.doppelganger_bypassed_signing_method(*validator_pubkey)?;
 
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Attestation)
+            .await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SyncCommitteeSignature {
@@ -844,6 +1056,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             selection_proof: selection_proof.into(),
         };
 
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Aggregate)
+            .await;
         let signature = signing_method
             .get_signature::<E, BlindedPayload<E>>(
                 SignableMessage::SignedContributionAndProof(&message),
@@ -965,4 +1181,380 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         info!(self.log, "Completed pruning of slashing protection DB");
     }
+
+    /// Check the integrity of the slashing protection database and record its size.
+    ///
+    /// If `backup_dir` is set and the check passes, a backup is also written to `backup_dir`,
+    /// keeping at most `backups_to_keep` backups.
+    pub fn check_slashing_protection_db(
+        &self,
+        backup_dir: Option<&Path>,
+        backups_to_keep: usize,
+    ) -> Result<SlashingProtectionHealth, String> {
+        let result = self.check_slashing_protection_db_inner(backup_dir, backups_to_keep);
+        if result.is_err() {
+            metrics::inc_counter(&metrics::SLASHING_PROTECTION_CHECK_ERRORS);
+        }
+        result
+    }
+
+    fn check_slashing_protection_db_inner(
+        &self,
+        backup_dir: Option<&Path>,
+        backups_to_keep: usize,
+    ) -> Result<SlashingProtectionHealth, String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| format!("Unable to read system time: {:?}", e))?
+            .as_secs();
+
+        let integrity_errors = self
+            .slashing_protection
+            .integrity_check()
+            .map_err(|e| format!("Unable to run integrity check: {:?}", e))?;
+        let (database_size_bytes, wal_size_bytes) = self
+            .slashing_protection
+            .file_sizes()
+            .map_err(|e| format!("Unable to read database size: {:?}", e))?;
+
+        let (mut last_backup, mut last_backup_time) = self
+            .slashing_protection_health
+            .read()
+            .as_ref()
+            .map(|health| (health.last_backup.clone(), health.last_backup_time))
+            .unwrap_or_default();
+
+        let integrity_ok = integrity_errors.is_empty();
+        let mut backup_result = Ok(());
+        if let Some(backup_dir) = backup_dir.filter(|_| integrity_ok) {
+            match backup_slashing_protection_db(
+                &self.slashing_protection,
+                backup_dir,
+                backups_to_keep,
+                now,
+                &self.log,
+            ) {
+                Ok(path) => {
+                    last_backup = Some(path);
+                    last_backup_time = Some(now);
+                    metrics::set_gauge(&metrics::SLASHING_PROTECTION_LAST_BACKUP, now as i64);
+                }
+                Err(e) => backup_result = Err(e),
+            }
+        }
+
+        metrics::set_gauge(
+            &metrics::SLASHING_PROTECTION_INTEGRITY_OK,
+            i64::from(integrity_ok),
+        );
+        metrics::set_gauge(
+            &metrics::SLASHING_PROTECTION_DB_SIZE,
+            database_size_bytes as i64,
+        );
+        metrics::set_gauge(
+            &metrics::SLASHING_PROTECTION_WAL_SIZE,
+            wal_size_bytes as i64,
+        );
+        metrics::set_gauge(&metrics::SLASHING_PROTECTION_LAST_CHECK, now as i64);
+
+        let health = SlashingProtectionHealth {
+            integrity_ok,
+            integrity_errors,
+            database_size_bytes,
+            wal_size_bytes,
+            last_check: now,
+            last_backup,
+            last_backup_time,
+        };
+        *self.slashing_protection_health.write() = Some(health.clone());
+
+        backup_result.map(|()| health)
+    }
+
+    /// Records the result of a comparison of the local clock with the beacon nodes.
+    ///
+    /// Signing is refused whilst the most recent result has `skew_detected` set.
+    pub fn set_clock_skew_status(&self, status: ClockSkewStatus) {
+        *self.clock_skew_status.write() = Some(status);
+    }
+
+    /// Returns the result of the most recent comparison of the local clock with the beacon nodes,
+    /// if any.
+    pub fn clock_skew_status(&self) -> Option<ClockSkewStatus> {
+        self.clock_skew_status.read().clone()
+    }
+
+    /// Checks that `signing_method` produces valid signatures, without signing anything slashable.
+    ///
+    /// The check fails if the signature is not produced within `HEALTH_CHECK_TIMEOUT`.
+    pub async fn check_signing_method_health(
+        &self,
+        signing_method: &SigningMethod,
+    ) -> Result<(), Error> {
+        // Health checks are the least urgent of all signatures.
+        let _permit = self
+            .signing_scheduler
+            .acquire(SigningPriority::Registration)
+            .await;
+        let result = timeout(
+            HEALTH_CHECK_TIMEOUT,
+            signing_method.check_health::<E>(
+                self.genesis_validators_root,
+                &self.spec,
+                &self.task_executor,
+            ),
+        )
+        .await
+        .map_err(|_| Error::HealthCheckTimedOut)
+        .and_then(|result| result.map_err(Error::UnableToSign));
+        let status = if result.is_ok() {
+            metrics::SUCCESS
+        } else {
+            metrics::FAILURE
+        };
+        metrics::inc_counter_vec(&metrics::REMOTE_SIGNER_HEALTH_CHECKS_TOTAL, &[status]);
+        result
+    }
+
+    /// Checks the remote signer of every enabled Web3Signer validator and records the results.
+    ///
+    /// Each distinct remote signer is checked once, using one of its validators, and all remote
+    /// signers are checked concurrently. Each signer is also asked for the public keys it holds,
+    /// and every configured key which it does not list is flagged as missing.
+    pub async fn check_remote_signers(&self) -> Vec<RemoteSignerHealth> {
+        let (remote_signers, signing_methods) = {
+            let validators = self.validators.read();
+            let mut signing_methods = HashMap::new();
+            let remote_signers = validators
+                .validator_definitions()
+                .iter()
+                .filter(|def| def.enabled)
+                .filter_map(|def| match &def.signing_definition {
+                    SigningDefinition::Web3Signer(web3_signer) => {
+                        let pubkey = def.voting_public_key.compress();
+                        let signing_method = validators.signing_method(&pubkey)?;
+                        signing_methods
+                            .entry(web3_signer.url.clone())
+                            .or_insert(signing_method);
+                        Some((pubkey, web3_signer.url.clone()))
+                    }
+                    SigningDefinition::LocalKeystore { .. } => None,
+                })
+                .collect::<Vec<_>>();
+            (remote_signers, signing_methods)
+        };
+
+        let checks = join_all(
+            signing_methods
+                .iter()
+                .map(|(url, signing_method)| async move {
+                    let health = async {
+                        self.check_signing_method_health(signing_method)
+                            .await
+                            .err()
+                            .map(|e| format!("{:?}", e))
+                    };
+                    let public_keys = async {
+                        let public_keys_url = build_web3_signer_public_keys_url(url)
+                            .map_err(|e| format!("Invalid remote signer URL: {:?}", e))?;
+                        signing_method
+                            .web3signer_public_keys(public_keys_url)
+                            .await
+                            .map(|keys| keys.into_iter().collect::<HashSet<_>>())
+                            .map_err(|e| format!("Unable to list remote signer keys: {:?}", e))
+                    };
+                    let (error, public_keys) = futures::join!(health, public_keys);
+                    if let Some(error) = &error {
+                        warn!(
+                            self.log,
+                            "Remote signer health check failed";
+                            "url" => url,
+                            "error" => error,
+                        );
+                    }
+                    (url.as_str(), (error, public_keys))
+                }),
+        )
+        .await
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        let last_check = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let results = remote_signers
+            .into_iter()
+            .map(|(pubkey, url)| {
+                let (health_error, public_keys) = match checks.get(url.as_str()) {
+                    Some((health_error, public_keys)) => (health_error.clone(), Some(public_keys)),
+                    None => (None, None),
+                };
+                let reachable = health_error.is_none();
+                let key_missing = matches!(
+                    public_keys,
+                    Some(Ok(public_keys)) if !public_keys.contains(&pubkey)
+                );
+                if key_missing {
+                    warn!(
+                        self.log,
+                        "Remote signer does not hold validator key";
+                        "url" => &url,
+                        "pubkey" => ?pubkey,
+                    );
+                }
+                let error = health_error.or_else(|| {
+                    if key_missing {
+                        Some("Key is not listed by the remote signer".to_string())
+                    } else {
+                        public_keys.and_then(|public_keys| public_keys.clone().err())
+                    }
+                });
+                RemoteSignerHealth {
+                    pubkey,
+                    url,
+                    reachable,
+                    key_missing,
+                    error,
+                    last_check,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        metrics::set_gauge(
+            &metrics::REMOTE_SIGNERS_UNREACHABLE,
+            results.iter().filter(|health| !health.reachable).count() as i64,
+        );
+        metrics::set_gauge(
+            &metrics::REMOTE_SIGNER_KEYS_MISSING,
+            results.iter().filter(|health| health.key_missing).count() as i64,
+        );
+        *self.remote_signer_health.write() = results.clone();
+
+        results
+    }
+
+    /// Returns the results of the most recent remote signer health checks.
+    pub fn remote_signer_health(&self) -> Vec<RemoteSignerHealth> {
+        self.remote_signer_health.read().clone()
+    }
+
+    /// Returns the result of the most recent slashing protection database health check, if any.
+    pub fn slashing_protection_health(&self) -> Option<SlashingProtectionHealth> {
+        self.slashing_protection_health.read().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use account_utils::validator_definitions::ValidatorDefinitions;
+    use futures::FutureExt;
+    use slashing_protection::SLASHING_PROTECTION_FILENAME;
+    use slot_clock::TestingSlotClock;
+    use task_executor::test_utils::TestRuntime;
+    use tempfile::tempdir;
+    use types::{test_utils::generate_deterministic_keypair, BeaconBlockBase, MinimalEthSpec};
+    use validator_dir::insecure_keys::build_deterministic_validator_dirs;
+
+    type E = MinimalEthSpec;
+
+    #[tokio::test]
+    async fn blocks_are_signed_whilst_lower_priorities_are_saturated() {
+        let spec = E::default_spec();
+        let validator_dir = tempdir().unwrap();
+        let secrets_dir = tempdir().unwrap();
+        let slashing_protection_dir = tempdir().unwrap();
+        build_deterministic_validator_dirs(
+            validator_dir.path().into(),
+            secrets_dir.path().into(),
+            &[0],
+        )
+        .unwrap();
+
+        let test_runtime = TestRuntime::default();
+        let log = test_runtime.log.clone();
+        let mut definitions = ValidatorDefinitions::open_or_create(validator_dir.path()).unwrap();
+        definitions
+            .discover_local_keystores(validator_dir.path(), secrets_dir.path(), &log)
+            .unwrap();
+        let validators = InitializedValidators::from_definitions(
+            definitions,
+            validator_dir.path().into(),
+            log.clone(),
+        )
+        .await
+        .unwrap();
+        let slashing_protection = SlashingDatabase::create(
+            &slashing_protection_dir
+                .path()
+                .join(SLASHING_PROTECTION_FILENAME),
+        )
+        .unwrap();
+        slashing_protection
+            .register_validators(validators.iter_voting_pubkeys())
+            .unwrap();
+
+        let config = Config {
+            signing_concurrency_limit: Some(2),
+            ..Config::default()
+        };
+        let slot_clock = TestingSlotClock::new(
+            Slot::new(0),
+            Duration::from_secs(0),
+            Duration::from_secs(spec.seconds_per_slot),
+        );
+        let store = ValidatorStore::<_, E>::new(
+            validators,
+            slashing_protection,
+            Hash256::zero(),
+            spec.clone(),
+            None,
+            slot_clock,
+            &config,
+            test_runtime.task_executor.clone(),
+            log,
+        );
+        let pubkey: PublicKeyBytes = generate_deterministic_keypair(0).pk.into();
+
+        // Saturate the permits which are available to the lower classes.
+        let registration = store
+            .signing_scheduler
+            .acquire(SigningPriority::Registration)
+            .await;
+        let signing_method = store.validators.read().signing_method(&pubkey).unwrap();
+        let mut health_check = Box::pin(store.check_signing_method_health(&signing_method));
+        let mut attestation = Box::pin(
+            store
+                .signing_scheduler
+                .acquire(SigningPriority::Attestation),
+        );
+        assert!((&mut health_check).now_or_never().is_none());
+        assert!((&mut attestation).now_or_never().is_none());
+
+        timeout(
+            Duration::from_secs(10),
+            store.randao_reveal(pubkey, Epoch::new(0)),
+        )
+        .await
+        .expect("RANDAO reveal should not wait")
+        .unwrap();
+        let block: BeaconBlock<E> = BeaconBlock::Base(BeaconBlockBase::empty(&spec));
+        timeout(
+            Duration::from_secs(10),
+            store.sign_block(pubkey, block, Slot::new(0)),
+        )
+        .await
+        .expect("block should not wait")
+        .unwrap();
+
+        // The lower classes were kept waiting throughout.
+        assert!((&mut health_check).now_or_never().is_none());
+        assert!((&mut attestation).now_or_never().is_none());
+
+        drop(registration);
+        attestation
+            .now_or_never()
+            .expect("attestation should hold the permit");
+    }
 }